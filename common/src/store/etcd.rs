@@ -1,27 +1,42 @@
 use crate::{
-    store::etcd_keep_alive::{EtcdSingletonLock, LeaseLockInfo},
-    types::v0::store::{
-        definitions::{
-            Connect, Delete, DeserialiseValue, Get, GetPrefix, KeyString, ObjectKey, Put,
-            SerialiseValue, StorableObject, Store, StoreError, StoreError::MissingEntry, StoreKey,
-            StoreValue, ValueString, Watch, WatchEvent,
+    store::{
+        etcd_endpoints::{is_connection_error, timed_out, EtcdEndpoints},
+        etcd_keep_alive::{EtcdSingletonLock, LeaseLockInfo},
+    },
+    types::v0::{
+        store::{
+            definitions::{
+                Delete, DeserialiseValue, Get, GetPrefix, KeyString, ObjectKey, Put, PutBatch,
+                SerialiseValue, StorableObject, Store, StoreBatch, StoreError,
+                StoreError::MissingEntry, StoreKey, StoreValue, ValueString, Watch, WatchEvent,
+            },
+            registry::ControlPlaneService,
         },
-        registry::ControlPlaneService,
+        transport::StoreHealth,
     },
 };
 use async_trait::async_trait;
 use etcd_client::{
-    Client, Compare, CompareOp, EventType, GetOptions, KeyValue, SortOrder, SortTarget, Txn, TxnOp,
-    WatchStream, Watcher,
+    Client, Compare, CompareOp, DeleteResponse, EventType, GetOptions, GetResponse, KeyValue,
+    PutResponse, SortOrder, SortTarget, Txn, TxnOp, TxnResponse, WatchStream, Watcher,
 };
 use serde_json::Value;
 use snafu::ResultExt;
+use std::{future::Future, time::Duration};
 use tokio::sync::mpsc::{channel, Receiver, Sender};
 
+/// How long each endpoint may take to reply to the health probe, when the client has no timeout
+const HEALTH_PROBE_TIMEOUT: Duration = Duration::from_secs(1);
+/// The maximum number of operations in a transaction, as per the etcd default of --max-txn-ops
+/// (the lease compare is not counted as an operation)
+const MAX_TXN_OPS: usize = 128;
+
 /// etcd client
 #[derive(Clone)]
 pub struct Etcd {
-    client: Client,
+    endpoints: EtcdEndpoints,
+    /// overall timeout of each operation, shared between the endpoints it's attempted on
+    timeout: Option<Duration>,
     lease_lock_info: Option<LeaseLockInfo>,
 }
 
@@ -39,22 +54,24 @@ impl Etcd {
             .map_err(|error| StoreError::NotReady {
                 reason: format!("Platform not ready: {}", error),
             })?;
-        Ok(Self::from(
-            &Client::connect([endpoint], None)
-                .await
-                .context(Connect {})?,
-            None,
-        ))
+        Ok(Etcd {
+            endpoints: EtcdEndpoints::connect([endpoint]).await?,
+            timeout: None,
+            lease_lock_info: None,
+        })
     }
     /// Create `Etcd` from an existing instance of the etcd `Client`
     pub(crate) fn from(client: &Client, lease_lock_info: Option<LeaseLockInfo>) -> Etcd {
         Etcd {
-            client: client.clone(),
+            endpoints: EtcdEndpoints::from_client(client),
+            timeout: None,
             lease_lock_info,
         }
     }
     /// Create a new instance of the etcd client with a lease associated with `service_name`.
     /// See `EtcdLeaseLockKeeper` for more information.
+    /// When more than one endpoint is given, the operations fail over to the next endpoint when
+    /// the current one cannot be reached.
     pub async fn new_leased<E: AsRef<str>, S: AsRef<[E]>>(
         endpoints: S,
         service_name: ControlPlaneService,
@@ -66,10 +83,21 @@ impl Etcd {
                 reason: format!("Platform not ready: {}", error),
             })?;
 
-        let client = Client::connect(endpoints, None).await.context(Connect {})?;
+        let endpoints = EtcdEndpoints::connect(endpoints).await?;
 
-        let lease_info = EtcdSingletonLock::start(client.clone(), service_name, lease_time).await?;
-        Ok(Self::from(&client, Some(lease_info)))
+        let lease_info =
+            EtcdSingletonLock::start(endpoints.clone(), service_name, lease_time).await?;
+        Ok(Etcd {
+            endpoints,
+            timeout: None,
+            lease_lock_info: Some(lease_info),
+        })
+    }
+    /// Bound each operation by the given `timeout`, which is shared between all the endpoints
+    /// the operation is attempted on, rather than allowed for each of them
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
     }
 
     /// Get the lease lock pair, (lease_id, lock_key)
@@ -87,6 +115,122 @@ impl Etcd {
             info.revoke().await;
         }
     }
+
+    /// Probe each of the endpoints and report their health, along with the endpoint which the
+    /// operations are currently sent to
+    pub async fn health(&self) -> StoreHealth {
+        let timeout = match self.timeout {
+            Some(timeout) => timeout / self.endpoints.count().max(1) as u32,
+            None => HEALTH_PROBE_TIMEOUT,
+        };
+        self.endpoints.health(timeout).await
+    }
+
+    /// Run the `operation` on the active endpoint, failing over to the next endpoints if it
+    /// cannot be reached. The endpoint which completes the operation becomes the active one.
+    /// The timeout, if any, is split between the remaining attempts so the operation as a whole
+    /// does not exceed it.
+    async fn failover<T, F, Fut>(&self, operation: F) -> Result<T, etcd_client::Error>
+    where
+        F: Fn(Client) -> Fut,
+        Fut: Future<Output = Result<T, etcd_client::Error>>,
+    {
+        let order = self.endpoints.failover_order();
+        let deadline = self
+            .timeout
+            .map(|timeout| tokio::time::Instant::now() + timeout);
+        let mut last_error = None;
+        for (attempt, index) in order.iter().enumerate() {
+            let attempt_operation = async {
+                let client = self.endpoints.client(*index).await?;
+                operation(client).await
+            };
+            let result = match deadline {
+                Some(deadline) => {
+                    let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+                    let timeout = remaining / (order.len() - attempt) as u32;
+                    tokio::time::timeout(timeout, attempt_operation)
+                        .await
+                        .unwrap_or_else(|_| Err(timed_out(timeout)))
+                }
+                None => attempt_operation.await,
+            };
+            match result {
+                Err(error) if is_connection_error(&error) => {
+                    tracing::warn!(%error, "Failed to reach the etcd endpoint");
+                    self.endpoints.set_unreachable(*index, &error);
+                    last_error = Some(error);
+                }
+                result => {
+                    self.endpoints.set_active(*index);
+                    return result;
+                }
+            }
+        }
+        Err(last_error
+            .unwrap_or_else(|| etcd_client::Error::InvalidArgs("no etcd endpoints".to_string())))
+    }
+    async fn txn(&self, txn: Txn) -> Result<TxnResponse, etcd_client::Error> {
+        self.failover(|mut client| {
+            let txn = txn.clone();
+            async move { client.txn(txn).await }
+        })
+        .await
+    }
+    /// Put the given key-value pairs in a single transaction, guarded by the lease lock if any
+    async fn put_txn(&self, puts: &[(String, Vec<u8>)]) -> Result<(), StoreError> {
+        let ops = puts
+            .iter()
+            .map(|(key, value)| TxnOp::put(key.as_str(), value.as_slice(), None))
+            .collect::<Vec<_>>();
+        let keys = puts.iter().map(|(key, _)| key.clone()).collect::<Vec<_>>();
+        match self.lease_lock()? {
+            Some((lease_id, lock_key)) => {
+                let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
+                let resp = self
+                    .txn(Txn::new().when([cmp]).and_then(ops))
+                    .await
+                    .context(PutBatch { keys })?;
+                if !resp.succeeded() {
+                    return Err(StoreError::FailedLock {
+                        reason: format!(
+                            "Etcd Txn Compare key '{}' to lease id '{:x}' failed",
+                            lock_key, lease_id
+                        ),
+                    });
+                }
+            }
+            None => {
+                self.txn(Txn::new().and_then(ops))
+                    .await
+                    .context(PutBatch { keys })?;
+            }
+        }
+        Ok(())
+    }
+    async fn put(&self, key: &str, value: &[u8]) -> Result<PutResponse, etcd_client::Error> {
+        self.failover(|mut client| async move { client.put(key, value, None).await })
+            .await
+    }
+    async fn get(
+        &self,
+        key: &str,
+        options: Option<GetOptions>,
+    ) -> Result<GetResponse, etcd_client::Error> {
+        self.failover(|mut client| {
+            let options = options.clone();
+            async move { client.get(key, options).await }
+        })
+        .await
+    }
+    async fn delete(&self, key: &str) -> Result<DeleteResponse, etcd_client::Error> {
+        self.failover(|mut client| async move { client.delete(key, None).await })
+            .await
+    }
+    async fn watch_key(&self, key: &str) -> Result<(Watcher, WatchStream), etcd_client::Error> {
+        self.failover(|mut client| async move { client.watch(key, None).await })
+            .await
+    }
 }
 
 #[async_trait]
@@ -102,7 +246,6 @@ impl Store for Etcd {
             let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
             let put = TxnOp::put(key.to_string(), vec_value, None);
             let resp = self
-                .client
                 .txn(Txn::new().when([cmp]).and_then([put]))
                 .await
                 .context(Put {
@@ -118,13 +261,10 @@ impl Store for Etcd {
                 });
             }
         } else {
-            self.client
-                .put(key.to_string(), vec_value, None)
-                .await
-                .context(Put {
-                    key: key.to_string(),
-                    value: serde_json::to_string(value).context(SerialiseValue)?,
-                })?;
+            self.put(&key.to_string(), &vec_value).await.context(Put {
+                key: key.to_string(),
+                value: serde_json::to_string(value).context(SerialiseValue)?,
+            })?;
         };
 
         Ok(())
@@ -132,7 +272,7 @@ impl Store for Etcd {
 
     /// 'Get' the value for the given key from etcd.
     async fn get_kv<K: StoreKey>(&mut self, key: &K) -> Result<Value, StoreError> {
-        let resp = self.client.get(key.to_string(), None).await.context(Get {
+        let resp = self.get(&key.to_string(), None).await.context(Get {
             key: key.to_string(),
         })?;
         match resp.kvs().first() {
@@ -153,7 +293,6 @@ impl Store for Etcd {
             let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
             let del = TxnOp::delete(key.to_string(), None);
             let resp = self
-                .client
                 .txn(Txn::new().when([cmp]).and_then([del]))
                 .await
                 .context(Delete {
@@ -168,12 +307,9 @@ impl Store for Etcd {
                 });
             }
         } else {
-            self.client
-                .delete(key.to_string(), None)
-                .await
-                .context(Delete {
-                    key: key.to_string(),
-                })?;
+            self.delete(&key.to_string()).await.context(Delete {
+                key: key.to_string(),
+            })?;
         };
 
         Ok(())
@@ -187,13 +323,9 @@ impl Store for Etcd {
         key: &K,
     ) -> Result<Receiver<Result<WatchEvent, StoreError>>, StoreError> {
         let (sender, receiver) = channel(100);
-        let (watcher, stream) = self
-            .client
-            .watch(key.to_string(), None)
-            .await
-            .context(Watch {
-                key: key.to_string(),
-            })?;
+        let (watcher, stream) = self.watch_key(&key.to_string()).await.context(Watch {
+            key: key.to_string(),
+        })?;
         watch(watcher, stream, sender);
         Ok(receiver)
     }
@@ -206,7 +338,6 @@ impl Store for Etcd {
             let cmp = Compare::lease(lock_key.clone(), CompareOp::Equal, lease_id);
             let put = TxnOp::put(key.to_string(), vec_value, None);
             let resp = self
-                .client
                 .txn(Txn::new().when([cmp]).and_then([put]))
                 .await
                 .context(Put {
@@ -222,7 +353,7 @@ impl Store for Etcd {
                 });
             }
         } else {
-            self.client.put(key, vec_value, None).await.context(Put {
+            self.put(&key, &vec_value).await.context(Put {
                 key: object.key().key(),
                 value: serde_json::to_string(object).context(SerialiseValue)?,
             })?;
@@ -233,8 +364,7 @@ impl Store for Etcd {
 
     async fn get_obj<O: StorableObject>(&mut self, key: &O::Key) -> Result<O, StoreError> {
         let resp = self
-            .client
-            .get(key.key(), None)
+            .get(&key.key(), None)
             .await
             .context(Get { key: key.key() })?;
         match resp.kvs().first() {
@@ -247,13 +377,26 @@ impl Store for Etcd {
         }
     }
 
+    /// 'Put' the objects of the `batch` into etcd in a single transaction, so they're either all
+    /// stored or none is. A batch too large for a single transaction is split into as many
+    /// transactions as needed, which are committed one after the other.
+    async fn put_batch(&mut self, batch: &StoreBatch) -> Result<(), StoreError> {
+        if batch.len() > MAX_TXN_OPS {
+            // splitting the batch would put its objects non-atomically
+            return Err(StoreError::BatchTooLarge {
+                len: batch.len(),
+                max: MAX_TXN_OPS,
+            });
+        }
+        self.put_txn(batch.puts()).await
+    }
+
     /// Retrieve objects with the given key prefix
     async fn get_values_prefix(
         &mut self,
         key_prefix: &str,
     ) -> Result<Vec<(String, Value)>, StoreError> {
         let resp = self
-            .client
             .get(key_prefix, Some(GetOptions::new().with_prefix()))
             .await
             .context(GetPrefix { prefix: key_prefix })?;
@@ -283,7 +426,6 @@ impl Store for Etcd {
         }
 
         let resp = self
-            .client
             .get(
                 key_prefix,
                 Some(
@@ -318,8 +460,7 @@ impl Store for Etcd {
     ) -> Result<Receiver<Result<WatchEvent, StoreError>>, StoreError> {
         let (sender, receiver) = channel(100);
         let (watcher, stream) = self
-            .client
-            .watch(key.key(), None)
+            .watch_key(&key.key())
             .await
             .context(Watch { key: key.key() })?;
         watch(watcher, stream, sender);
@@ -327,7 +468,9 @@ impl Store for Etcd {
     }
 
     async fn online(&mut self) -> bool {
-        self.client.status().await.is_ok()
+        self.failover(|mut client| async move { client.status().await })
+            .await
+            .is_ok()
     }
 }
 
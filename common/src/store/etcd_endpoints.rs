@@ -0,0 +1,219 @@
+use crate::types::v0::{
+    store::definitions::{Connect, StoreError},
+    transport::{StoreEndpointHealth, StoreHealth},
+};
+use etcd_client::Client;
+use snafu::ResultExt;
+use std::{sync::Arc, time::Duration};
+
+/// The etcd endpoints which the store client may use, along with their health as seen by the
+/// client. The operations are sent to the active endpoint and fail over to the next endpoints
+/// when it cannot be reached.
+#[derive(Clone)]
+pub(crate) struct EtcdEndpoints(Arc<parking_lot::Mutex<EtcdEndpointsInner>>);
+
+struct EtcdEndpointsInner {
+    endpoints: Vec<EtcdEndpoint>,
+    /// index of the endpoint which the operations are sent to first
+    active: usize,
+}
+
+struct EtcdEndpoint {
+    url: String,
+    /// the client is connected lazily if the endpoint could not be reached at first
+    client: Option<Client>,
+    reachable: bool,
+    error: Option<String>,
+}
+
+impl EtcdEndpoint {
+    fn connected(url: &str, client: Client) -> Self {
+        Self {
+            url: url.to_string(),
+            client: Some(client),
+            reachable: true,
+            error: None,
+        }
+    }
+    fn unreachable(url: &str, error: &etcd_client::Error) -> Self {
+        Self {
+            url: url.to_string(),
+            client: None,
+            reachable: false,
+            error: Some(error.to_string()),
+        }
+    }
+}
+
+impl EtcdEndpoints {
+    /// Connect to each of the `endpoints`, failing only if none of them can be reached.
+    /// The first reachable endpoint becomes the active one.
+    pub(crate) async fn connect<E: AsRef<str>, S: AsRef<[E]>>(
+        endpoints: S,
+    ) -> Result<Self, StoreError> {
+        let mut connected = Vec::with_capacity(endpoints.as_ref().len());
+        let mut last_error = None;
+        for url in endpoints.as_ref() {
+            let url = url.as_ref();
+            match Client::connect([url], None).await {
+                Ok(client) => connected.push(EtcdEndpoint::connected(url, client)),
+                Err(error) => {
+                    tracing::warn!(endpoint = url, %error, "Failed to connect to etcd");
+                    connected.push(EtcdEndpoint::unreachable(url, &error));
+                    last_error = Some(error);
+                }
+            }
+        }
+        let active = match connected.iter().position(|e| e.reachable) {
+            Some(active) => active,
+            None => {
+                return match last_error {
+                    Some(error) => Err(error).context(Connect {}),
+                    None => Err(StoreError::NotReady {
+                        reason: "No etcd endpoints were given".to_string(),
+                    }),
+                }
+            }
+        };
+        Ok(Self(Arc::new(parking_lot::Mutex::new(
+            EtcdEndpointsInner {
+                endpoints: connected,
+                active,
+            },
+        ))))
+    }
+    /// Create `Self` from an existing instance of the etcd `Client`
+    pub(crate) fn from_client(client: &Client) -> Self {
+        Self(Arc::new(parking_lot::Mutex::new(EtcdEndpointsInner {
+            endpoints: vec![EtcdEndpoint::connected("", client.clone())],
+            active: 0,
+        })))
+    }
+
+    /// Number of endpoints
+    pub(crate) fn count(&self) -> usize {
+        self.0.lock().endpoints.len()
+    }
+    /// Indexes of the endpoints in the order in which they should be tried, starting with the
+    /// active endpoint
+    pub(crate) fn failover_order(&self) -> Vec<usize> {
+        let inner = self.0.lock();
+        let count = inner.endpoints.len();
+        (0 .. count)
+            .map(|offset| (inner.active + offset) % count)
+            .collect()
+    }
+    /// Get the client of the endpoint with the given `index`, connecting to it if required
+    pub(crate) async fn client(&self, index: usize) -> Result<Client, etcd_client::Error> {
+        let url = {
+            let inner = self.0.lock();
+            let endpoint = &inner.endpoints[index];
+            if let Some(client) = &endpoint.client {
+                return Ok(client.clone());
+            }
+            endpoint.url.clone()
+        };
+        let client = Client::connect([url], None).await?;
+        self.0.lock().endpoints[index].client = Some(client.clone());
+        Ok(client)
+    }
+    /// Get the client of the first endpoint which replies to a status request, starting with
+    /// the active endpoint, which is then made the active endpoint
+    pub(crate) async fn reachable_client(&self) -> Option<Client> {
+        for index in self.failover_order() {
+            let status = match self.client(index).await {
+                Ok(mut client) => client.status().await.map(|_| client),
+                Err(error) => Err(error),
+            };
+            match status {
+                Ok(client) => {
+                    self.set_active(index);
+                    return Some(client);
+                }
+                Err(error) => self.set_unreachable(index, &error),
+            }
+        }
+        None
+    }
+
+    /// The endpoint with the given `index` replied, so it becomes the active endpoint
+    pub(crate) fn set_active(&self, index: usize) {
+        let mut inner = self.0.lock();
+        if inner.active != index {
+            tracing::warn!(
+                from = %inner.endpoints[inner.active].url,
+                to = %inner.endpoints[index].url,
+                "Failing over to another etcd endpoint"
+            );
+            inner.active = index;
+        }
+        drop(inner);
+        self.set_reachable(index);
+    }
+    /// The endpoint with the given `index` replied
+    fn set_reachable(&self, index: usize) {
+        let mut inner = self.0.lock();
+        let endpoint = &mut inner.endpoints[index];
+        endpoint.reachable = true;
+        endpoint.error = None;
+    }
+    /// The endpoint with the given `index` could not be reached
+    pub(crate) fn set_unreachable(&self, index: usize, error: &etcd_client::Error) {
+        let mut inner = self.0.lock();
+        let endpoint = &mut inner.endpoints[index];
+        endpoint.reachable = false;
+        endpoint.error = Some(error.to_string());
+    }
+
+    /// Probe each endpoint with a status request, allowing each at most `timeout`, and report
+    /// their health
+    pub(crate) async fn health(&self, timeout: Duration) -> StoreHealth {
+        for index in 0 .. self.count() {
+            let status = tokio::time::timeout(timeout, async {
+                let mut client = self.client(index).await?;
+                client.status().await
+            })
+            .await
+            .unwrap_or_else(|_| Err(timed_out(timeout)));
+            match status {
+                Ok(_) => self.set_reachable(index),
+                Err(error) => self.set_unreachable(index, &error),
+            }
+        }
+
+        let inner = self.0.lock();
+        StoreHealth {
+            active: inner.endpoints[inner.active].url.clone(),
+            endpoints: inner
+                .endpoints
+                .iter()
+                .map(|endpoint| StoreEndpointHealth {
+                    endpoint: endpoint.url.clone(),
+                    reachable: endpoint.reachable,
+                    error: endpoint.error.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Error of an etcd request which did not complete within the given `timeout`
+pub(crate) fn timed_out(timeout: Duration) -> etcd_client::Error {
+    etcd_client::Error::IoError(std::io::Error::new(
+        std::io::ErrorKind::TimedOut,
+        format!("etcd request timed out after {:?}", timeout),
+    ))
+}
+
+/// Check if the error is due to the endpoint not being reachable, in which case the request may
+/// be sent to another endpoint
+pub(crate) fn is_connection_error(error: &etcd_client::Error) -> bool {
+    match error {
+        etcd_client::Error::TransportError(_) | etcd_client::Error::IoError(_) => true,
+        etcd_client::Error::GRpcStatus(status) => matches!(
+            status.code(),
+            tonic::Code::Unavailable | tonic::Code::DeadlineExceeded | tonic::Code::Cancelled
+        ),
+        _ => false,
+    }
+}
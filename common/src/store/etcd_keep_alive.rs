@@ -1,4 +1,4 @@
-use super::etcd::Etcd;
+use super::{etcd::Etcd, etcd_endpoints::EtcdEndpoints};
 use crate::types::v0::store::{
     definitions::{ObjectKey, Store, StoreError},
     registry::{ControlPlaneService, StoreLeaseLockKey, StoreLeaseOwner, StoreLeaseOwnerKey},
@@ -11,6 +11,8 @@ use std::{cmp::max, ops::Deref, sync::Arc, time::Duration};
 /// reestablishes the lease and the lock.
 pub(crate) struct EtcdSingletonLock {
     client: Client,
+    /// endpoints to reconnect to when the client's endpoint is lost
+    endpoints: EtcdEndpoints,
     state: Option<LeaseKeeperState>,
     lease_ttl: Duration,
     lease_id: i64,
@@ -50,6 +52,11 @@ impl LeaseLockInfo {
         lease_info.lease_id = lease_id;
     }
 
+    /// Use the provided `client` from now on, eg: after reconnecting to another endpoint
+    fn set_client(&self, client: &Client) {
+        self.0.lock().client = client.clone();
+    }
+
     /// New `Self` with the provided `lease_id` and `lock_key`
     fn new(lease_id: i64, lock_key: &str, client: &Client) -> Self {
         Self(Arc::new(parking_lot::Mutex::new(LeaseLockInfoInner::new(
@@ -117,10 +124,18 @@ impl EtcdSingletonLock {
     /// A background thread will attempt to keep the lease alive, and will handle reconnections if
     /// the connection to etcd is lost.
     pub(crate) async fn start(
-        mut client: Client,
+        endpoints: EtcdEndpoints,
         service_kind: ControlPlaneService,
         lease_ttl: std::time::Duration,
     ) -> Result<LeaseLockInfo, StoreError> {
+        let mut client = match endpoints.reachable_client().await {
+            Some(client) => client,
+            None => {
+                return Err(StoreError::NotReady {
+                    reason: "None of the etcd endpoints can be reached".to_string(),
+                })
+            }
+        };
         let lock_owner_key_prefix = EtcdSingletonLock::lock_key(&service_kind);
         let lease_resp = client
             .lease_grant(*LeaseTtl::from(lease_ttl), None)
@@ -157,6 +172,7 @@ impl EtcdSingletonLock {
 
         let mut keeper = Self {
             client,
+            endpoints,
             state: Some(LeaseKeeperState::Locked(Locked {
                 lock_key: lock_key.clone(),
                 lease_id: lease_resp.id(),
@@ -280,6 +296,17 @@ impl EtcdSingletonLock {
             Ok(())
         }
     }
+    /// Reconnect to the first reachable etcd endpoint, which may not be the one we were using
+    async fn reconnect(&mut self) -> bool {
+        match self.endpoints.reachable_client().await {
+            Some(client) => {
+                self.lease_info.set_client(&client);
+                self.client = client;
+                true
+            }
+            None => false,
+        }
+    }
 }
 
 #[derive(Copy, Clone)]
@@ -371,7 +398,7 @@ impl LeaseLockKeeperClocking<Reconnect> for EtcdSingletonLock {
     async fn clock(&mut self, state: Reconnect) -> LockStatesResult {
         tokio::time::sleep(state.0).await;
         let sleep = max(state.0 + Duration::from_secs(1), Duration::from_secs(5));
-        if self.client.status().await.is_err() {
+        if !self.reconnect().await {
             Ok(LeaseKeeperState::Reconnect(Reconnect(sleep)))
         } else {
             Ok(LeaseKeeperState::LeaseExpired(LeaseExpired {}))
@@ -1,2 +1,3 @@
 pub mod etcd;
+mod etcd_endpoints;
 mod etcd_keep_alive;
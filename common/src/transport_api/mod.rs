@@ -10,7 +10,7 @@ pub mod v0;
 
 pub use macros::*;
 
-use crate::types::v0::transport::{MessageIdVs, VERSION};
+use crate::types::v0::transport::{Filter, MessageIdVs, VERSION};
 use async_trait::async_trait;
 use dyn_clonable::clonable;
 
@@ -108,7 +108,7 @@ pub trait Message {
 }
 
 /// All the different variants of Resources
-#[derive(Serialize, Deserialize, Debug, Clone, AsRefStr, ToString)]
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash, AsRefStr, ToString)]
 pub enum ResourceKind {
     /// Unknown or unspecified resource
     Unknown,
@@ -153,13 +153,19 @@ pub struct ReplyError {
     pub extra: String,
 }
 
+/// gRPC metadata key of a request rejected by the rate limiter, with the number of milliseconds
+/// after which it may be retried.
+pub const RETRY_AFTER_MS_KEY: &str = "retry-after-ms";
+
 impl From<tonic::Status> for ReplyError {
     fn from(status: tonic::Status) -> Self {
-        Self::tonic_reply_error(
-            status.code().into(),
-            status.message().to_string(),
-            status.full_string(),
-        )
+        let kind = match status.code() {
+            Code::ResourceExhausted if status.metadata().contains_key(RETRY_AFTER_MS_KEY) => {
+                ReplyErrorKind::RateLimited
+            }
+            code => code.into(),
+        };
+        Self::tonic_reply_error(kind, status.message().to_string(), status.full_string())
     }
 }
 
@@ -242,6 +248,19 @@ impl ReplyError {
             extra: format!("Invalid {} was provided", arg_name),
         }
     }
+    /// used when a resource is listed with a filter which it cannot be filtered by.
+    pub fn invalid_filter(resource: ResourceKind, filter: &Filter) -> Self {
+        Self {
+            kind: ReplyErrorKind::InvalidArgument,
+            resource: resource.clone(),
+            source: format!("{:?}", filter),
+            extra: format!(
+                "{} cannot be filtered by {:?}",
+                resource.to_string(),
+                filter
+            ),
+        }
+    }
     /// used when we encounter a missing argument.
     pub fn missing_argument(resource: ResourceKind, arg_name: &str) -> Self {
         Self {
@@ -322,6 +341,9 @@ pub enum ReplyErrorKind {
     ReplicaCreateNumber,
     VolumeNoReplicas,
     InUse,
+    LastHealthyReplica,
+    BelowMinReplicationFactor,
+    RateLimited,
 }
 
 impl From<tonic::Code> for ReplyErrorKind {
@@ -530,6 +552,12 @@ impl TimeoutOptions {
     pub fn client(&self) -> &ClientId {
         &self.client
     }
+    /// Set the client.
+    #[must_use]
+    pub fn with_client(mut self, client: ClientId) -> Self {
+        self.client = client;
+        self
+    }
 
     /// Set the connect timeout.
     pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
@@ -561,6 +589,16 @@ pub enum ClientId {
     CoreAgent,
     /// The JsonGrpc Agent.
     JsonGrpcAgent,
+    /// The HA Cluster Agent.
+    ClusterAgent,
     /// Not Specified.
     Unnamed,
 }
+
+impl ClientId {
+    /// Check if the client is part of the control-plane itself, and so exempt from the rate
+    /// limits of the core agent.
+    pub fn internal(&self) -> bool {
+        matches!(self, Self::CoreAgent | Self::ClusterAgent)
+    }
+}
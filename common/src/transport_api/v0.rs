@@ -29,6 +29,8 @@ impl_message!(CreatePool);
 impl_message!(DestroyPool);
 impl_vector_request!(Pools, Pool);
 impl_message!(GetPools);
+impl_vector_request!(DiscoveredPools, DiscoveredPool);
+impl_message!(DiscoverPools);
 
 impl_vector_request!(Replicas, Replica);
 impl_message!(GetReplicas);
@@ -43,11 +45,21 @@ impl_message!(CreateNexus);
 impl_message!(DestroyNexus);
 impl_message!(ShareNexus);
 impl_message!(UnshareNexus);
+impl_message!(PauseNexus);
+impl_message!(ResumeNexus);
+impl_message!(PauseNexusRebuild);
+impl_message!(ResumeNexusRebuild);
 impl_message!(RemoveNexusChild);
 impl_message!(AddNexusChild);
+impl_message!(OnlineNexusChild);
+impl_message!(OfflineNexusChild);
 
 impl_vector_request_token!(Volumes, Volume);
 impl_message!(GetVolumes);
+impl_message!(GetVolumesStatus);
+impl_message!(GetVolumeTopology);
+impl_message!(GetVolumeHistory);
+impl_message!(GetVolumeRevisionDiff);
 impl_message!(CreateVolume);
 impl_message!(ShareVolume);
 impl_message!(UnshareVolume);
@@ -57,6 +69,12 @@ impl_message!(DestroyVolume);
 impl_message!(AddVolumeNexus);
 impl_message!(RemoveVolumeNexus);
 impl_message!(SetVolumeReplica);
+impl_message!(SetVolumeSourceReplica);
+impl_message!(PatchVolume);
+impl_message!(FreezeVolume);
+impl_message!(ThawVolume);
+impl_message!(PauseVolumeRebuild);
+impl_message!(ResumeVolumeRebuild);
 
 impl_message!(JsonGrpcRequest, JsonGrpc);
 
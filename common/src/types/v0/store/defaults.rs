@@ -0,0 +1,74 @@
+use crate::types::v0::{
+    openapi::models,
+    store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+    transport::VolumePlacementPolicy,
+};
+use serde::{Deserialize, Serialize};
+
+/// Cluster-wide default parameters of the volumes, loaded from/stored into the persistent store.
+/// They're applied to the parameters which a volume creation request leaves unset, and only at
+/// creation time, so changing them does not affect the existing volumes.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct ClusterDefaults {
+    /// number of storage replicas
+    pub replicas: u64,
+    /// flag indicating whether the volumes should be thin provisioned
+    pub thin: bool,
+    /// what to do when not all of the replicas can be placed
+    pub placement: VolumePlacementPolicy,
+}
+
+impl Default for ClusterDefaults {
+    fn default() -> Self {
+        Self {
+            replicas: 1,
+            thin: false,
+            placement: VolumePlacementPolicy::default(),
+        }
+    }
+}
+
+impl From<ClusterDefaults> for models::ClusterDefaults {
+    fn from(src: ClusterDefaults) -> Self {
+        Self::new(src.placement.into(), src.replicas as u8, src.thin)
+    }
+}
+
+impl From<models::ClusterDefaults> for ClusterDefaults {
+    fn from(src: models::ClusterDefaults) -> Self {
+        Self {
+            replicas: src.replicas as u64,
+            thin: src.thin,
+            placement: src.placement.into(),
+        }
+    }
+}
+
+/// Key used to store the cluster defaults
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ClusterDefaultsKey(String);
+
+const CLUSTER_DEFAULTS_KEY_DFLT: &str = "5c8fa2b1-91d3-4a6e-bc2e-7d0a4f3e6b19";
+impl Default for ClusterDefaultsKey {
+    fn default() -> Self {
+        Self(CLUSTER_DEFAULTS_KEY_DFLT.to_string())
+    }
+}
+
+impl ObjectKey for ClusterDefaultsKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::ClusterDefaults
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for ClusterDefaults {
+    type Key = ClusterDefaultsKey;
+
+    fn key(&self) -> Self::Key {
+        ClusterDefaultsKey::default()
+    }
+}
@@ -2,7 +2,7 @@ use async_trait::async_trait;
 use etcd_client::Error;
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{Error as SerdeError, Value};
-use snafu::Snafu;
+use snafu::{ResultExt, Snafu};
 use strum_macros::Display;
 use tokio::sync::mpsc::Receiver;
 
@@ -25,6 +25,13 @@ pub enum StoreError {
         value: String,
         source: Error,
     },
+    /// Failed to 'put' a batch of entries in the store.
+    #[snafu(display(
+        "Failed to 'put' the batch of entries with keys {:?}. Error {}",
+        keys,
+        source
+    ))]
+    PutBatch { keys: Vec<String>, source: Error },
     /// Failed to 'get' an entry from the store.
     #[snafu(display("Failed to 'get' entry with key {}. Error {}", key, source))]
     Get { key: String, source: Error },
@@ -64,6 +71,13 @@ pub enum StoreError {
     NotReady { reason: String },
     #[snafu(display("Minimum paged value is 2"))]
     PagedMinimum,
+    /// The batch has more entries than can be put in a single transaction.
+    #[snafu(display(
+        "Batch of {} entries exceeds the maximum of {} entries of a transaction",
+        len,
+        max
+    ))]
+    BatchTooLarge { len: usize, max: usize },
 }
 
 /// Representation of a watch event.
@@ -106,6 +120,11 @@ pub trait Store: Sync + Send + Clone {
 
     async fn get_obj<O: StorableObject>(&mut self, _key: &O::Key) -> Result<O, StoreError>;
 
+    /// Put all the objects of the `batch` into the store, as a single transaction where the
+    /// store supports it, otherwise one after the other in the order they were added.
+    /// A batch which is too large for a single transaction is refused, rather than split.
+    async fn put_batch(&mut self, batch: &StoreBatch) -> Result<(), StoreError>;
+
     /// Returns a vector of tuples. Each tuple represents a key-value pair.
     async fn get_values_prefix(
         &mut self,
@@ -126,6 +145,42 @@ pub trait Store: Sync + Send + Clone {
 
 pub type StoreWatchReceiver = Receiver<Result<WatchEvent, StoreError>>;
 
+/// Objects which belong to a single logical operation, eg: the specs of a volume and of its
+/// replicas at creation, and so are to be put into the store together, see `Store::put_batch`.
+#[derive(Debug, Default, Clone)]
+pub struct StoreBatch {
+    puts: Vec<(String, Vec<u8>)>,
+}
+
+impl StoreBatch {
+    /// Add the serialised `object` to the batch, replacing an earlier version of it, if any.
+    pub fn put_obj<O: StorableObject>(&mut self, object: &O) -> Result<(), StoreError> {
+        let key = object.key().key();
+        let value = serde_json::to_vec(object).context(SerialiseValue)?;
+        match self.puts.iter_mut().find(|(k, _)| k == &key) {
+            Some((_, previous)) => *previous = value,
+            None => self.puts.push((key, value)),
+        }
+        Ok(())
+    }
+    /// Get the keys of the objects in the batch, in the order they were added.
+    pub fn keys(&self) -> Vec<String> {
+        self.puts.iter().map(|(key, _)| key.clone()).collect()
+    }
+    /// Get the keys and serialised values of the objects in the batch.
+    pub fn puts(&self) -> &[(String, Vec<u8>)] {
+        &self.puts
+    }
+    /// Get the number of objects in the batch.
+    pub fn len(&self) -> usize {
+        self.puts.len()
+    }
+    /// Check if the batch has no objects.
+    pub fn is_empty(&self) -> bool {
+        self.puts.is_empty()
+    }
+}
+
 /// Implemented by Keys of Storable Objects, eg: VolumeId
 pub trait ObjectKey: Sync + Send {
     fn key(&self) -> String {
@@ -166,6 +221,11 @@ pub enum StorableObjectType {
     CoreRegistryConfig,
     StoreLeaseLock,
     StoreLeaseOwner,
+    Tombstone,
+    ClusterDefaults,
+    VolumeQuotas,
+    ReconcilerPasses,
+    SpecHistory,
 }
 
 /// Returns the key prefix that should is used for the keys, when running from within the cluster.
@@ -198,3 +258,38 @@ pub fn key_prefix_obj(obj_type: StorableObjectType) -> String {
 pub fn generate_key<K: ObjectKey + ?Sized>(k: &K) -> String {
     format!("{}/{}", key_prefix_obj(k.key_type()), k.key_uuid())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::v0::{
+        store::{replica::ReplicaSpec, volume::VolumeSpec},
+        transport::{ReplicaId, VolumeId},
+    };
+
+    #[test]
+    fn store_batch() {
+        let volume = VolumeSpec {
+            uuid: VolumeId::new(),
+            ..Default::default()
+        };
+        let mut replica = ReplicaSpec {
+            uuid: ReplicaId::new(),
+            ..Default::default()
+        };
+        let mut batch = StoreBatch::default();
+        assert!(batch.is_empty());
+        batch.put_obj(&volume).unwrap();
+        batch.put_obj(&replica).unwrap();
+        assert_eq!(batch.keys(), vec![volume.key().key(), replica.key().key()]);
+
+        // a newer version of an object replaces the older one, keeping its place in the batch
+        replica.size = 4096;
+        batch.put_obj(&replica).unwrap();
+        assert_eq!(batch.len(), 2);
+        let (key, value) = &batch.puts()[1];
+        assert_eq!(key, &replica.key().key());
+        let stored: ReplicaSpec = serde_json::from_slice(value).unwrap();
+        assert_eq!(stored, replica);
+    }
+}
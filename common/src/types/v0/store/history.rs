@@ -0,0 +1,98 @@
+use crate::{
+    transport_api::ResourceKind,
+    types::v0::store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+};
+use serde::{Deserialize, Serialize};
+use std::time::SystemTime;
+
+/// A revision of a resource spec, as it was persisted by an operation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SpecRevision {
+    /// Number of the revision, incremented on each revision of the spec
+    pub revision: u64,
+    /// When the spec was persisted
+    pub updated_at: SystemTime,
+    /// The operation which resulted in this revision, as it's persisted
+    pub operation: Option<serde_json::Value>,
+    /// The spec, as it was persisted
+    pub spec: serde_json::Value,
+}
+
+/// The last revisions of a resource spec, the oldest first.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SpecHistory {
+    /// Kind of the resource
+    pub kind: ResourceKind,
+    /// Id of the resource
+    pub id: String,
+    /// The revisions which are retained
+    pub revisions: Vec<SpecRevision>,
+}
+
+impl SpecHistory {
+    /// Return a new empty `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: ResourceKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            revisions: vec![],
+        }
+    }
+    /// Add a new revision of the spec, pruning the oldest revisions so that no more than `retain`
+    /// are kept. Returns the number of the new revision.
+    pub fn push(
+        &mut self,
+        updated_at: SystemTime,
+        operation: Option<serde_json::Value>,
+        spec: serde_json::Value,
+        retain: usize,
+    ) -> u64 {
+        let revision = self.revisions.last().map(|r| r.revision + 1).unwrap_or(1);
+        self.revisions.push(SpecRevision {
+            revision,
+            updated_at,
+            operation,
+            spec,
+        });
+        let excess = self.revisions.len().saturating_sub(retain.max(1));
+        self.revisions.drain(.. excess);
+        revision
+    }
+    /// Get the given `revision`, unless it was pruned
+    pub fn revision(&self, revision: u64) -> Option<&SpecRevision> {
+        self.revisions.iter().find(|r| r.revision == revision)
+    }
+    /// Get the revision which precedes the given `revision`, unless it was pruned
+    pub fn previous(&self, revision: u64) -> Option<&SpecRevision> {
+        self.revision(revision.checked_sub(1)?)
+    }
+}
+
+/// Key used to store a `SpecHistory`
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct SpecHistoryKey(String);
+
+impl SpecHistoryKey {
+    /// Return a new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: &ResourceKind, id: &str) -> Self {
+        Self(format!("{}/{}", kind.as_ref(), id))
+    }
+}
+
+impl ObjectKey for SpecHistoryKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::SpecHistory
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl StorableObject for SpecHistory {
+    type Key = SpecHistoryKey;
+
+    fn key(&self) -> Self::Key {
+        SpecHistoryKey::new(&self.kind, &self.id)
+    }
+}
@@ -1,12 +1,17 @@
 pub mod child;
+pub mod defaults;
 pub mod definitions;
+pub mod history;
 pub mod nexus;
 pub mod nexus_child;
 pub mod nexus_persistence;
 pub mod node;
 pub mod pool;
+pub mod quota;
+pub mod reconciler;
 pub mod registry;
 pub mod replica;
+pub mod tombstone;
 pub mod volume;
 pub mod watch;
 
@@ -17,6 +22,7 @@ use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use strum_macros::ToString;
 
@@ -74,6 +80,45 @@ pub trait SpecTransaction<Operation> {
     fn start_op(&mut self, operation: Operation);
     /// Sets the result of the operation
     fn set_op_result(&mut self, result: bool);
+    /// Sets the result of the operation as unknown, as it may or may not have taken effect
+    fn set_op_result_unknown(&mut self);
+}
+
+/// Creation and modification times of a Spec
+pub trait SpecTimestamps {
+    /// Record that the spec was created, and so last updated, at `now`
+    fn stamp_created(&mut self, now: SystemTime);
+    /// Record that the spec was last updated at `now`
+    fn stamp_updated(&mut self, now: SystemTime);
+}
+
+/// Implements `SpecTimestamps` for the given $type, which stores its times in its
+/// `created_at` and `updated_at` fields.
+#[macro_export]
+macro_rules! impl_spec_timestamps {
+    ($type:tt) => {
+        impl crate::types::v0::store::SpecTimestamps for $type {
+            fn stamp_created(&mut self, now: std::time::SystemTime) {
+                self.created_at = Some(now);
+                self.updated_at = Some(now);
+            }
+
+            fn stamp_updated(&mut self, now: std::time::SystemTime) {
+                self.updated_at = Some(now);
+            }
+        }
+    };
+}
+
+/// Seconds since the unix epoch of the given `time`, as the spec times are exposed.
+pub fn epoch_secs(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+/// The time which is the given `secs` since the unix epoch.
+pub fn from_epoch_secs(secs: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_secs(secs)
 }
 
 /// Trait which allows a UUID to be returned as the associated type Id.
@@ -89,6 +134,8 @@ pub trait ResourceUuid {
 pub struct OperationSequence {
     uuid: String,
     state: OperationSequenceState,
+    /// number of times the sequence was forcibly released
+    releases: u64,
 }
 impl OperationSequence {
     /// Create new `Self` with a uuid for observability
@@ -96,8 +143,13 @@ impl OperationSequence {
         Self {
             uuid: uuid.into(),
             state: Default::default(),
+            releases: 0,
         }
     }
+    /// Check if an operation is currently sequenced
+    pub fn busy(&self) -> bool {
+        self.state != OperationSequenceState::Idle
+    }
 }
 
 /// Sequence operations
@@ -134,6 +186,11 @@ pub trait OperationSequencer: std::fmt::Debug + Clone {
     fn sequence(&self, mode: OperationMode) -> Option<OperationSequenceState>;
     /// Complete the operation sequenced using the provided `OperationMode`.
     fn complete(&self, revert: OperationSequenceState);
+    /// Number of times the sequence was forcibly released.
+    fn releases(&self) -> u64;
+    /// Forcibly release the sequence, whatever operation it's sequencing, returning the state
+    /// it was in. The guard of that operation then leaves the sequence alone when dropped.
+    fn release(&self) -> OperationSequenceState;
 }
 
 impl<T: AsOperationSequencer + std::fmt::Debug + Clone> OperationSequencer for ResourceMutex<T> {
@@ -149,6 +206,12 @@ impl<T: AsOperationSequencer + std::fmt::Debug + Clone> OperationSequencer for R
     fn complete(&self, revert: OperationSequenceState) {
         self.lock().as_mut().complete(revert);
     }
+    fn releases(&self) -> u64 {
+        self.lock().as_ref().releases
+    }
+    fn release(&self) -> OperationSequenceState {
+        self.lock().as_mut().release()
+    }
 }
 
 /// Operation Guard for a ResourceMutex<T> type.
@@ -211,6 +274,8 @@ pub struct OperationGuard<T: OperationSequencer, R> {
     inner_value: R,
     mode: OperationMode,
     locked: Option<OperationSequenceState>,
+    /// releases of the sequence when it was locked, as it must not be unlocked once released
+    releases: u64,
 }
 impl<T: OperationSequencer + Sized, R> OperationGuard<T, R> {
     /// Get a copy of the `OperationMode` constrained by this Guard.
@@ -219,9 +284,17 @@ impl<T: OperationSequencer + Sized, R> OperationGuard<T, R> {
     }
     fn unlock(&mut self) {
         if let Some(revert) = self.locked.take() {
-            self.inner.complete(revert);
+            if self.inner.releases() == self.releases {
+                self.inner.complete(revert);
+            }
         }
     }
+    /// Leave the sequence locked once this guard is dropped, as if the guard itself was leaked,
+    /// eg: by a task which was aborted halfway through its operation.
+    /// The sequence may then only be released forcibly.
+    pub fn leak_lock(&mut self) {
+        self.locked = None;
+    }
     /// Peek at the resource without locking.
     /// Note, this value may be outdated *During* an operation, and so must not be used to
     /// inspect fields which are being mutated.
@@ -242,6 +315,7 @@ impl<T: OperationSequencer + Sized, R> OperationGuard<T, R> {
                 inner_value: value(resource),
                 mode,
                 locked: Some(revert),
+                releases: resource.releases(),
             }),
             None => Err(format!(
                 "Cannot transition from '{:?}' to '{:?}'",
@@ -346,6 +420,11 @@ impl OperationSequence {
             self.state = OperationSequenceState::Idle;
         }
     }
+    /// Forcibly release the sequence, returning the state it was in.
+    fn release(&mut self) -> OperationSequenceState {
+        self.releases += 1;
+        std::mem::replace(&mut self.state, OperationSequenceState::Idle)
+    }
 }
 
 /// Tracing simple string messages with resource specific information
@@ -466,3 +545,50 @@ macro_rules! impl_trace_span {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Clone, Default)]
+    struct Resource {
+        sequencer: OperationSequence,
+    }
+    impl AsOperationSequencer for Resource {
+        fn as_ref(&self) -> &OperationSequence {
+            &self.sequencer
+        }
+        fn as_mut(&mut self) -> &mut OperationSequence {
+            &mut self.sequencer
+        }
+    }
+
+    fn guard(resource: &ResourceMutex<Resource>) -> Option<OperationGuardArc<Resource>> {
+        OperationGuardArc::try_sequence(resource, |r| r.lock().clone(), OperationMode::Exclusive)
+            .ok()
+    }
+
+    #[test]
+    fn released_guard() {
+        let resource = ResourceMutex::from(Resource::default());
+
+        // a leaked guard leaves the resource busy for good
+        let mut leaked = guard(&resource).unwrap();
+        leaked.leak_lock();
+        drop(leaked);
+        assert!(resource.lock().as_ref().busy());
+        assert!(guard(&resource).is_none());
+
+        assert_eq!(resource.release(), OperationSequenceState::Exclusive);
+        assert!(!resource.lock().as_ref().busy());
+
+        // a guard which outlives the release of its sequence doesn't unlock the next operation
+        let stale = guard(&resource).unwrap();
+        resource.release();
+        let next = guard(&resource).unwrap();
+        drop(stale);
+        assert!(resource.lock().as_ref().busy());
+        drop(next);
+        assert!(!resource.lock().as_ref().busy());
+    }
+}
@@ -4,17 +4,19 @@ use crate::types::v0::{
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
+        epoch_secs,
         nexus_child::NexusChild,
         AsOperationSequencer, OperationGuardArc, OperationSequence, ResourceMutex, ResourceUuid,
         SpecStatus, SpecTransaction,
     },
     transport::{
         self, ChildState, ChildStateReason, ChildUri, CreateNexus, DestroyNexus,
-        Nexus as MbusNexus, NexusId, NexusShareProtocol, NodeId, Protocol, ReplicaId, VolumeId,
+        Nexus as MbusNexus, NexusId, NexusNvmfConfig, NexusShareProtocol, NodeId, Protocol,
+        ReplicaId, ResumeNexus, VolumeId,
     },
 };
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, time::SystemTime};
 
 /// Nexus information
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -101,6 +103,26 @@ pub struct NexusSpec {
     pub sequencer: OperationSequence,
     /// Record of the operation in progress
     pub operation: Option<NexusOperationState>,
+    /// NVMe-oF configuration of the nexus, including its persistent reservation
+    #[serde(default)]
+    pub config: Option<NexusNvmfConfig>,
+    /// Children which could not be reached when the nexus was created, to be added once their
+    /// nodes are back online
+    #[serde(default)]
+    pub deferred_children: Vec<NexusChild>,
+    /// When the spec was created, unknown for the specs created before this was recorded
+    #[serde(default)]
+    pub created_at: Option<SystemTime>,
+    /// When the spec was last updated, unknown for the specs stored before this was recorded
+    #[serde(default)]
+    pub updated_at: Option<SystemTime>,
+    /// The IO of the nexus is paused until it's resumed, or until this deadline at the latest
+    #[serde(default)]
+    pub frozen_until: Option<SystemTime>,
+    /// Children which were taken offline administratively, and are kept in the nexus until
+    /// they're brought back online
+    #[serde(default)]
+    pub offline_children: Vec<ChildUri>,
 }
 impl NexusSpec {
     /// Check if the spec contains the provided replica by it's `ReplicaId`
@@ -110,6 +132,11 @@ impl NexusSpec {
             NexusChild::Uri(_) => false,
         })
     }
+    /// Defer the given `children`, moving them out of the nexus children until they can be added
+    pub fn defer_children(&mut self, children: &[NexusChild]) {
+        self.children.retain(|child| !children.contains(child));
+        self.deferred_children.extend(children.iter().cloned());
+    }
     /// Disown nexus by its volume owner
     pub fn disowned_by_volume(&mut self) {
         let _ = self.owner.take();
@@ -166,6 +193,15 @@ macro_rules! nexus_span {
 }
 crate::impl_trace_span!(nexus_span, NexusSpec);
 
+impl From<&NexusSpec> for ResumeNexus {
+    fn from(spec: &NexusSpec) -> Self {
+        Self {
+            node: spec.node.clone(),
+            uuid: spec.uuid.clone(),
+        }
+    }
+}
+
 impl From<&NexusSpec> for CreateNexus {
     fn from(spec: &NexusSpec) -> Self {
         CreateNexus::new(
@@ -175,7 +211,7 @@ impl From<&NexusSpec> for CreateNexus {
             &spec.children,
             spec.managed,
             spec.owner.as_ref(),
-            None,
+            spec.config.clone(),
         )
     }
 }
@@ -199,24 +235,31 @@ impl ResourceUuid for NexusSpec {
 
 impl From<NexusSpec> for models::NexusSpec {
     fn from(src: NexusSpec) -> Self {
-        Self::new(
-            src.children,
-            src.managed,
-            src.node,
-            src.share,
-            src.size,
-            src.spec_status,
-            openapi::apis::Uuid::try_from(src.uuid).unwrap(),
-        )
+        Self {
+            created_at: src.created_at.map(epoch_secs),
+            updated_at: src.updated_at.map(epoch_secs),
+            ..Self::new(
+                src.children,
+                src.managed,
+                src.node,
+                src.share,
+                src.size,
+                src.spec_status,
+                openapi::apis::Uuid::try_from(src.uuid).unwrap(),
+            )
+        }
     }
 }
 
 /// ReplicaUri used by managed nexus creation
 /// Includes the ReplicaId which is unique and allows us to pinpoint the exact replica
-#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq)]
 pub struct ReplicaUri {
     uuid: ReplicaId,
     share_uri: ChildUri,
+    /// Generation of the replica, 0 if unknown
+    #[serde(default)]
+    generation: u64,
 }
 
 impl ReplicaUri {
@@ -225,8 +268,14 @@ impl ReplicaUri {
         Self {
             uuid: uuid.clone(),
             share_uri: share_uri.clone(),
+            generation: 0,
         }
     }
+    /// Refer to the replica of the given `generation`
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
     /// Get the replica uuid
     pub fn uuid(&self) -> &ReplicaId {
         &self.uuid
@@ -235,6 +284,18 @@ impl ReplicaUri {
     pub fn uri(&self) -> &ChildUri {
         &self.share_uri
     }
+    /// Get the replica generation, 0 if unknown
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+}
+
+/// The generation is left out, so a child may be found or removed by a reference which doesn't
+/// know it
+impl PartialEq for ReplicaUri {
+    fn eq(&self, other: &Self) -> bool {
+        self.uuid == other.uuid && self.share_uri == other.share_uri
+    }
 }
 
 /// Operation State for a Nexus spec resource
@@ -246,6 +307,8 @@ pub struct NexusOperationState {
     pub result: Option<bool>,
 }
 
+crate::impl_spec_timestamps!(NexusSpec);
+
 impl SpecTransaction<NexusOperation> for NexusSpec {
     fn pending_op(&self) -> bool {
         self.operation.is_some()
@@ -267,7 +330,18 @@ impl SpecTransaction<NexusOperation> for NexusSpec {
                     self.share = Protocol::None;
                 }
                 NexusOperation::AddChild(uri) => self.children.push(uri),
-                NexusOperation::RemoveChild(uri) => self.children.retain(|c| c != &uri),
+                NexusOperation::RemoveChild(uri) => {
+                    self.children.retain(|c| c != &uri);
+                    self.offline_children.retain(|c| c != &uri.uri());
+                }
+                NexusOperation::Pause(deadline) => self.frozen_until = Some(deadline),
+                NexusOperation::Resume => self.frozen_until = None,
+                NexusOperation::OnlineChild(uri) => self.offline_children.retain(|c| c != &uri),
+                NexusOperation::OfflineChild(uri) => {
+                    if !self.offline_children.contains(&uri) {
+                        self.offline_children.push(uri);
+                    }
+                }
             }
         }
         self.clear_op();
@@ -289,6 +363,12 @@ impl SpecTransaction<NexusOperation> for NexusSpec {
             op.result = Some(result);
         }
     }
+
+    fn set_op_result_unknown(&mut self) {
+        if let Some(op) = &mut self.operation {
+            op.result = None;
+        }
+    }
 }
 
 /// Available Nexus Operations
@@ -300,6 +380,10 @@ pub enum NexusOperation {
     Unshare,
     AddChild(NexusChild),
     RemoveChild(NexusChild),
+    Pause(SystemTime),
+    Resume,
+    OnlineChild(ChildUri),
+    OfflineChild(ChildUri),
 }
 
 /// Key used by the store to uniquely identify a NexusSpec structure.
@@ -343,6 +427,12 @@ impl From<&CreateNexus> for NexusSpec {
             owner: request.owner.clone(),
             sequencer: OperationSequence::new(request.uuid.clone()),
             operation: None,
+            config: request.config.clone(),
+            deferred_children: vec![],
+            created_at: Some(SystemTime::now()),
+            updated_at: None,
+            frozen_until: None,
+            offline_children: vec![],
         }
     }
 }
@@ -352,6 +442,11 @@ impl PartialEq<CreateNexus> for NexusSpec {
         let mut other = NexusSpec::from(other);
         other.spec_status = self.spec_status.clone();
         other.sequencer = self.sequencer.clone();
+        other.defer_children(&self.deferred_children);
+        other.created_at = self.created_at;
+        other.updated_at = self.updated_at;
+        other.frozen_until = self.frozen_until;
+        other.offline_children = self.offline_children.clone();
         &other == self
     }
 }
@@ -377,6 +472,7 @@ impl From<&NexusSpec> for transport::Nexus {
                     state: ChildState::Unknown,
                     rebuild_progress: None,
                     state_reason: ChildStateReason::Unknown,
+                    last_fault: None,
                 })
                 .collect(),
             device_uri: "".to_string(),
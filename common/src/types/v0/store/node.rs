@@ -4,12 +4,15 @@ use crate::types::v0::{
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
-        ResourceUuid,
+        epoch_secs, ResourceUuid,
     },
     transport::{self, NodeId},
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 pub type NodeLabels = HashMap<String, String>;
 
@@ -37,6 +40,19 @@ pub struct NodeSpec {
     /// Cordon labels.
     #[serde(default)] // Ensure backwards compatibility in etcd when upgrading.
     cordon_labels: Vec<String>,
+    /// Expiry of the cordon labels, after which they are removed.
+    /// Cordon labels without an expiry are permanent.
+    #[serde(default)]
+    cordon_expiry: HashMap<String, SystemTime>,
+    /// End of the maintenance window of the node, if it's in maintenance.
+    #[serde(default)]
+    maintenance_until: Option<SystemTime>,
+    /// When the spec was created, unknown for the specs created before this was recorded.
+    #[serde(default)]
+    created_at: Option<SystemTime>,
+    /// When the spec was last updated, unknown for the specs stored before this was recorded.
+    #[serde(default)]
+    updated_at: Option<SystemTime>,
 }
 
 impl NodeSpec {
@@ -52,8 +68,35 @@ impl NodeSpec {
             endpoint,
             labels,
             cordon_labels: cordon_label.unwrap_or_default(),
+            cordon_expiry: HashMap::new(),
+            maintenance_until: None,
+            created_at: None,
+            updated_at: None,
         }
     }
+    /// Return `Self` with the given cordon label expiries.
+    #[must_use]
+    pub fn with_cordon_expiry(mut self, cordon_expiry: HashMap<String, SystemTime>) -> Self {
+        self.cordon_expiry = cordon_expiry;
+        self
+    }
+    /// Return `Self` with the given end of the maintenance window.
+    #[must_use]
+    pub fn with_maintenance_until(mut self, maintenance_until: Option<SystemTime>) -> Self {
+        self.maintenance_until = maintenance_until;
+        self
+    }
+    /// Return `Self` with the given creation and last update times.
+    #[must_use]
+    pub fn with_timestamps(
+        mut self,
+        created_at: Option<SystemTime>,
+        updated_at: Option<SystemTime>,
+    ) -> Self {
+        self.created_at = created_at;
+        self.updated_at = updated_at;
+        self
+    }
     /// Node identification
     pub fn id(&self) -> &NodeId {
         &self.id
@@ -70,8 +113,11 @@ impl NodeSpec {
     pub fn set_endpoint(&mut self, endpoint: String) {
         self.endpoint = endpoint
     }
-    /// Cordon node by applying the label.
-    pub fn cordon(&mut self, label: String) {
+    /// Cordon node by applying the label, which is removed after the `expiry`, if any.
+    pub fn cordon(&mut self, label: String, expiry: Option<SystemTime>) {
+        if let Some(expiry) = expiry {
+            self.cordon_expiry.insert(label.clone(), expiry);
+        }
         self.cordon_labels.push(label);
     }
     /// Uncordon node by removing the corresponding label.
@@ -79,20 +125,79 @@ impl NodeSpec {
         if let Some(index) = self.cordon_labels.iter().position(|l| l == &label) {
             self.cordon_labels.remove(index);
         }
+        self.cordon_expiry.remove(&label);
     }
     /// Returns whether or not the node is cordoned.
+    /// A node in maintenance is cordoned for the duration of the maintenance window.
     pub fn cordoned(&self) -> bool {
-        !self.cordon_labels.is_empty()
+        !self.cordon_labels.is_empty() || self.maintenance_until.is_some()
     }
     /// Returns the cordon labels
     pub fn cordon_labels(&self) -> Vec<String> {
         self.cordon_labels.clone()
     }
+    /// Returns the expiry of the cordon labels which have one.
+    pub fn cordon_expiry(&self) -> &HashMap<String, SystemTime> {
+        &self.cordon_expiry
+    }
+    /// Returns the cordon labels which have expired by `now`.
+    pub fn expired_cordon_labels(&self, now: SystemTime) -> Vec<String> {
+        self.cordon_expiry
+            .iter()
+            .filter(|(_, expiry)| **expiry <= now)
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+    /// Put the node in maintenance until the given time.
+    pub fn set_maintenance(&mut self, until: SystemTime) {
+        self.maintenance_until = Some(until);
+    }
+    /// Take the node out of maintenance.
+    pub fn end_maintenance(&mut self) {
+        self.maintenance_until = None;
+    }
+    /// End of the maintenance window, if the node is in maintenance.
+    pub fn maintenance_until(&self) -> Option<SystemTime> {
+        self.maintenance_until
+    }
+    /// Returns whether or not the node is in a maintenance window which has not expired by `now`.
+    pub fn in_maintenance(&self, now: SystemTime) -> bool {
+        matches!(self.maintenance_until, Some(until) if until > now)
+    }
+    /// When the spec was created, if known.
+    pub fn created_at(&self) -> Option<SystemTime> {
+        self.created_at
+    }
+    /// When the spec was last updated, if known.
+    pub fn updated_at(&self) -> Option<SystemTime> {
+        self.updated_at
+    }
 }
 
+crate::impl_spec_timestamps!(NodeSpec);
+
 impl From<NodeSpec> for models::NodeSpec {
     fn from(src: NodeSpec) -> Self {
-        Self::new(src.endpoint, src.id, src.cordon_labels)
+        let cordon_expiry = src
+            .cordon_expiry
+            .into_iter()
+            .map(|(label, expiry)| {
+                let expiry = expiry.duration_since(UNIX_EPOCH).unwrap_or_default();
+                (label, expiry.as_secs())
+            })
+            .collect::<HashMap<_, _>>();
+        Self::new_all(
+            src.endpoint,
+            src.id,
+            src.cordon_labels,
+            match cordon_expiry.is_empty() {
+                true => None,
+                false => Some(cordon_expiry),
+            },
+            src.maintenance_until.map(epoch_secs),
+            src.created_at.map(epoch_secs),
+            src.updated_at.map(epoch_secs),
+        )
     }
 }
 
@@ -4,8 +4,8 @@ use crate::types::v0::{
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
-        AsOperationSequencer, OperationGuardArc, OperationSequence, ResourceUuid, SpecStatus,
-        SpecTransaction,
+        epoch_secs, AsOperationSequencer, OperationGuardArc, OperationSequence, ResourceUuid,
+        SpecStatus, SpecTransaction,
     },
     transport::{self, CreatePool, NodeId, PoolDeviceUri, PoolId},
 };
@@ -15,7 +15,7 @@ pub type PoolLabel = ::std::collections::HashMap<String, String>;
 
 use crate::types::v0::store::ResourceMutex;
 use serde::{Deserialize, Serialize};
-use std::{convert::From, fmt::Debug};
+use std::{convert::From, fmt::Debug, time::SystemTime};
 
 /// Pool data structure used by the persistent store.
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -60,6 +60,8 @@ impl From<&CreatePool> for PoolSpec {
             labels: request.labels.clone(),
             sequencer: OperationSequence::new(request.id.clone()),
             operation: None,
+            created_at: Some(SystemTime::now()),
+            updated_at: None,
         }
     }
 }
@@ -68,6 +70,8 @@ impl PartialEq<CreatePool> for PoolSpec {
         let mut other = PoolSpec::from(other);
         other.status = self.status.clone();
         other.sequencer = self.sequencer.clone();
+        other.created_at = self.created_at;
+        other.updated_at = self.updated_at;
         &other == self
     }
 }
@@ -90,6 +94,12 @@ pub struct PoolSpec {
     pub sequencer: OperationSequence,
     /// Record of the operation in progress
     pub operation: Option<PoolOperationState>,
+    /// When the spec was created, unknown for the specs created before this was recorded
+    #[serde(default)]
+    pub created_at: Option<SystemTime>,
+    /// When the spec was last updated, unknown for the specs stored before this was recorded
+    #[serde(default)]
+    pub updated_at: Option<SystemTime>,
 }
 
 impl ResourceMutex<PoolSpec> {
@@ -133,7 +143,15 @@ impl ResourceUuid for PoolSpec {
 
 impl From<PoolSpec> for models::PoolSpec {
     fn from(src: PoolSpec) -> Self {
-        Self::new_all(src.disks, src.id, src.labels, src.node, src.status)
+        Self::new_all(
+            src.disks,
+            src.id,
+            src.labels,
+            src.node,
+            src.status,
+            src.created_at.map(epoch_secs),
+            src.updated_at.map(epoch_secs),
+        )
     }
 }
 
@@ -145,6 +163,8 @@ pub struct PoolOperationState {
     pub result: Option<bool>,
 }
 
+crate::impl_spec_timestamps!(PoolSpec);
+
 impl SpecTransaction<PoolOperation> for PoolSpec {
     fn pending_op(&self) -> bool {
         self.operation.is_some()
@@ -180,6 +200,12 @@ impl SpecTransaction<PoolOperation> for PoolSpec {
             op.result = Some(result);
         }
     }
+
+    fn set_op_result_unknown(&mut self) {
+        if let Some(op) = &mut self.operation {
+            op.result = None;
+        }
+    }
 }
 
 /// Available Pool Operations
@@ -231,6 +257,7 @@ impl From<&PoolSpec> for transport::PoolState {
             status: transport::PoolStatus::Unknown,
             capacity: 0,
             used: 0,
+            cluster_size: transport::DEFAULT_POOL_CLUSTER_SIZE,
         }
     }
 }
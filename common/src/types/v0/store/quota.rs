@@ -0,0 +1,89 @@
+use crate::types::v0::{
+    openapi::models,
+    store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+    transport::VolumeLabels,
+};
+use serde::{Deserialize, Serialize};
+
+/// Maximum capacity which may be provisioned by the volumes tagged with a label, eg: the volumes
+/// of a tenant.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub struct VolumeQuota {
+    /// key of the label which tags the volumes
+    pub key: String,
+    /// value of the label which tags the volumes
+    pub value: String,
+    /// maximum capacity in bytes
+    pub capacity: u64,
+}
+
+impl VolumeQuota {
+    /// Check whether the volume with the given `labels` is tagged with the label of the quota.
+    pub fn applies_to(&self, labels: &Option<VolumeLabels>) -> bool {
+        match labels {
+            Some(labels) => labels.get(&self.key) == Some(&self.value),
+            None => false,
+        }
+    }
+}
+
+impl From<VolumeQuota> for models::VolumeQuota {
+    fn from(src: VolumeQuota) -> Self {
+        Self::new(src.capacity, src.key, src.value)
+    }
+}
+
+impl From<models::VolumeQuota> for VolumeQuota {
+    fn from(src: models::VolumeQuota) -> Self {
+        Self {
+            key: src.key,
+            value: src.value,
+            capacity: src.capacity,
+        }
+    }
+}
+
+/// Per label quotas of the volumes, loaded from/stored into the persistent store.
+/// The volumes which are not tagged with the label of any quota are not limited.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct VolumeQuotas {
+    /// the quotas, at most one for each label
+    pub quotas: Vec<VolumeQuota>,
+}
+
+impl From<models::VolumeQuotas> for VolumeQuotas {
+    fn from(src: models::VolumeQuotas) -> Self {
+        Self {
+            quotas: src.quotas.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+/// Key used to store the volume quotas
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct VolumeQuotasKey(String);
+
+const VOLUME_QUOTAS_KEY_DFLT: &str = "0e4b7f3a-6c2d-4f81-9a5e-b3d17c9e2f60";
+impl Default for VolumeQuotasKey {
+    fn default() -> Self {
+        Self(VOLUME_QUOTAS_KEY_DFLT.to_string())
+    }
+}
+
+impl ObjectKey for VolumeQuotasKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::VolumeQuotas
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for VolumeQuotas {
+    type Key = VolumeQuotasKey;
+
+    fn key(&self) -> Self::Key {
+        VolumeQuotasKey::default()
+    }
+}
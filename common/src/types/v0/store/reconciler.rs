@@ -0,0 +1,110 @@
+use crate::types::v0::store::definitions::{ObjectKey, StorableObject, StorableObjectType};
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// A reconciler pass which may be disabled at runtime, eg: to stop the automatic replacement of
+/// the replicas during an incident, without stopping the whole core agent.
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReconcilerPass {
+    /// Disown and destroy the replicas which are no longer needed.
+    GarbageCollectReplicas,
+    /// Remove the faulted children of the volume targets and replace them with new replicas.
+    ReplaceFaultedChildren,
+    /// Share the volume targets again when their protocol doesn't match their spec.
+    ReshareReplicas,
+    /// Recreate the volume targets which are missing, eg: after an io-engine restart.
+    NexusRecreate,
+    /// Write the specs which failed to be persisted back to the persistent store.
+    DirtySpecReplay,
+}
+
+impl ReconcilerPass {
+    /// All the reconciler passes.
+    pub const ALL: [ReconcilerPass; 5] = [
+        Self::GarbageCollectReplicas,
+        Self::ReplaceFaultedChildren,
+        Self::ReshareReplicas,
+        Self::NexusRecreate,
+        Self::DirtySpecReplay,
+    ];
+    /// The stable name of the pass.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::GarbageCollectReplicas => "garbage-collect-replicas",
+            Self::ReplaceFaultedChildren => "replace-faulted-children",
+            Self::ReshareReplicas => "reshare-replicas",
+            Self::NexusRecreate => "nexus-recreate",
+            Self::DirtySpecReplay => "dirty-spec-replay",
+        }
+    }
+}
+
+impl std::fmt::Display for ReconcilerPass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ReconcilerPass {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|pass| pass.name() == source)
+            .ok_or_else(|| format!("Unknown reconciler pass '{}'", source))
+    }
+}
+
+/// The reconciler passes which have been disabled at runtime, loaded from/stored into the
+/// persistent store so that they remain disabled across restarts.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, Eq, PartialEq)]
+pub struct ReconcilerPasses {
+    /// the disabled passes
+    pub disabled: Vec<ReconcilerPass>,
+}
+
+impl ReconcilerPasses {
+    /// Check whether the given `pass` is enabled.
+    pub fn enabled(&self, pass: ReconcilerPass) -> bool {
+        !self.disabled.contains(&pass)
+    }
+    /// Enable or disable the given `pass`.
+    pub fn set(&mut self, pass: ReconcilerPass, enabled: bool) {
+        self.disabled.retain(|disabled| disabled != &pass);
+        if !enabled {
+            self.disabled.push(pass);
+            self.disabled.sort();
+        }
+    }
+}
+
+/// Key used to store the reconciler passes
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconcilerPassesKey(String);
+
+const RECONCILER_PASSES_KEY_DFLT: &str = "a3d6e1f4-52b7-4c08-8e9d-1f2b6c7a4e53";
+impl Default for ReconcilerPassesKey {
+    fn default() -> Self {
+        Self(RECONCILER_PASSES_KEY_DFLT.to_string())
+    }
+}
+
+impl ObjectKey for ReconcilerPassesKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::ReconcilerPasses
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+impl StorableObject for ReconcilerPasses {
+    type Key = ReconcilerPassesKey;
+
+    fn key(&self) -> Self::Key {
+        ReconcilerPassesKey::default()
+    }
+}
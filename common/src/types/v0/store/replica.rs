@@ -4,8 +4,8 @@ use crate::types::v0::{
     openapi::models,
     store::{
         definitions::{ObjectKey, StorableObject, StorableObjectType},
-        AsOperationSequencer, OperationSequence, ResourceMutex, ResourceUuid, SpecStatus,
-        SpecTransaction,
+        epoch_secs, AsOperationSequencer, OperationSequence, ResourceMutex, ResourceUuid,
+        SpecStatus, SpecTransaction,
     },
     transport::{
         self, CreateReplica, NodeId, PoolId, Protocol, Replica as MbusReplica, ReplicaId,
@@ -13,7 +13,7 @@ use crate::types::v0::{
     },
 };
 use serde::{Deserialize, Serialize};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, time::SystemTime};
 
 /// Replica information
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
@@ -74,6 +74,10 @@ pub struct ReplicaSpec {
     pub uuid: ReplicaId,
     /// The size that the replica should be.
     pub size: u64,
+    /// The size which was requested for the replica, when its `size` was rounded up to a whole
+    /// number of the clusters of its pool.
+    #[serde(default)]
+    pub requested_size: Option<u64>,
     /// The pool that the replica should live on.
     pub pool: PoolId,
     /// Protocol used for exposing the replica.
@@ -86,11 +90,40 @@ pub struct ReplicaSpec {
     pub managed: bool,
     /// Owner Resource
     pub owners: ReplicaOwners,
+    /// Generation of the replica, which tells it apart from the other replicas created with the
+    /// same uuid. The specs created before generations were recorded are generation 0
+    #[serde(default)]
+    pub generation: u64,
     /// Update in progress
     #[serde(skip)]
     pub sequencer: OperationSequence,
     /// Record of the operation in progress
     pub operation: Option<ReplicaOperationState>,
+    /// When the spec was created, unknown for the specs created before this was recorded
+    #[serde(default)]
+    pub created_at: Option<SystemTime>,
+    /// When the spec was last updated, unknown for the specs stored before this was recorded
+    #[serde(default)]
+    pub updated_at: Option<SystemTime>,
+    /// The last rebuild of the replica did not complete, so it may not hold the latest data of
+    /// its volume. Cleared once a subsequent rebuild of the replica completes.
+    #[serde(default)]
+    pub out_of_sync: bool,
+}
+
+impl ReplicaSpec {
+    /// Set the generation of the replica, and of the reference held by its owners
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+        self.owners.set_generation(generation);
+    }
+    /// Check if a reference to the replica of the given `generation` refers to this replica,
+    /// rather than to another replica previously created with the same uuid.
+    /// References which don't know the generation (0), eg: those stored before generations were
+    /// recorded, refer to any generation
+    pub fn is_generation(&self, generation: u64) -> bool {
+        generation == 0 || generation == self.generation
+    }
 }
 
 impl ResourceMutex<ReplicaSpec> {
@@ -119,16 +152,23 @@ impl ResourceUuid for ReplicaSpec {
 
 impl From<ReplicaSpec> for models::ReplicaSpec {
     fn from(src: ReplicaSpec) -> Self {
-        Self::new(
-            src.managed,
-            src.owners,
-            src.pool,
-            src.share,
-            src.size,
-            src.status,
-            src.thin,
-            openapi::apis::Uuid::try_from(src.uuid).unwrap(),
-        )
+        Self {
+            created_at: src.created_at.map(epoch_secs),
+            updated_at: src.updated_at.map(epoch_secs),
+            generation: Some(src.generation),
+            out_of_sync: Some(src.out_of_sync),
+            requested_size: src.requested_size,
+            ..Self::new(
+                src.managed,
+                src.owners,
+                src.pool,
+                src.share,
+                src.size,
+                src.status,
+                src.thin,
+                openapi::apis::Uuid::try_from(src.uuid).unwrap(),
+            )
+        }
     }
 }
 
@@ -140,6 +180,8 @@ pub struct ReplicaOperationState {
     pub result: Option<bool>,
 }
 
+crate::impl_spec_timestamps!(ReplicaSpec);
+
 impl SpecTransaction<ReplicaOperation> for ReplicaSpec {
     fn pending_op(&self) -> bool {
         self.operation.is_some()
@@ -181,6 +223,12 @@ impl SpecTransaction<ReplicaOperation> for ReplicaSpec {
             op.result = Some(result);
         }
     }
+
+    fn set_op_result_unknown(&mut self) {
+        if let Some(op) = &mut self.operation {
+            op.result = None;
+        }
+    }
 }
 
 /// Available Replica Operations
@@ -244,14 +292,19 @@ impl From<&CreateReplica> for ReplicaSpec {
             name: ReplicaName::from_opt_uuid(request.name.as_ref(), &request.uuid),
             uuid: request.uuid.clone(),
             size: request.size,
+            requested_size: None,
             pool: request.pool.clone(),
             share: request.share,
             thin: request.thin,
             status: ReplicaSpecStatus::Creating,
             managed: request.managed,
             owners: request.owners.clone(),
+            generation: 0,
             sequencer: OperationSequence::new(request.uuid.clone()),
             operation: None,
+            created_at: Some(SystemTime::now()),
+            updated_at: None,
+            out_of_sync: false,
         }
     }
 }
@@ -260,6 +313,10 @@ impl PartialEq<CreateReplica> for ReplicaSpec {
         let mut other = ReplicaSpec::from(other);
         other.status = self.status.clone();
         other.sequencer = self.sequencer.clone();
+        other.set_generation(self.generation);
+        other.created_at = self.created_at;
+        other.updated_at = self.updated_at;
+        other.requested_size = self.requested_size;
         &other == self
     }
 }
@@ -268,3 +325,37 @@ impl PartialEq<transport::Replica> for ReplicaSpec {
         self.share == other.share && self.pool == other.pool
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replica_generation() {
+        let mut replica = ReplicaSpec::default();
+        replica.set_generation(2);
+        assert_eq!(replica.owners.generation(), 2);
+
+        assert!(replica.is_generation(2));
+        // a reference to the replica previously created with the same uuid
+        assert!(!replica.is_generation(1));
+        // a reference which doesn't know the generation
+        assert!(replica.is_generation(0));
+    }
+
+    #[test]
+    fn replica_generation_compat() {
+        // a spec stored before generations were recorded
+        let mut stored = serde_json::to_value(&ReplicaSpec::default()).unwrap();
+        let spec = stored.as_object_mut().unwrap();
+        spec.remove("generation").unwrap();
+        let owners = spec.get_mut("owners").unwrap().as_object_mut().unwrap();
+        owners.remove("generation").unwrap();
+
+        let replica: ReplicaSpec = serde_json::from_value(stored).unwrap();
+        assert_eq!(replica.generation, 0);
+        assert_eq!(replica.owners.generation(), 0);
+        assert!(replica.is_generation(0));
+        assert!(!replica.is_generation(1));
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{
+    transport_api::ResourceKind,
+    types::v0::store::definitions::{ObjectKey, StorableObject, StorableObjectType},
+};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, SystemTime};
+
+/// Record of a resource spec which has been destroyed.
+/// Whilst the tombstone is alive, a resource with the same id may not be created, which prevents
+/// stale state of the destroyed resource from resurrecting it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Tombstone {
+    /// Kind of the destroyed resource
+    pub kind: ResourceKind,
+    /// Id of the destroyed resource
+    pub id: String,
+    /// When the resource was destroyed
+    pub deleted_at: SystemTime,
+}
+
+impl Tombstone {
+    /// Return a new `Self` for the resource `id` of the given `kind`, destroyed just now
+    pub fn new(kind: ResourceKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            deleted_at: SystemTime::now(),
+        }
+    }
+    /// Check if the tombstone has outlived the given `ttl`
+    pub fn expired(&self, ttl: Duration) -> bool {
+        self.deleted_at
+            .elapsed()
+            .map(|elapsed| elapsed >= ttl)
+            .unwrap_or(false)
+    }
+}
+
+/// Key used to store a `Tombstone`
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq, Hash)]
+pub struct TombstoneKey(String);
+
+impl TombstoneKey {
+    /// Return a new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: &ResourceKind, id: &str) -> Self {
+        Self(format!("{}/{}", kind.as_ref(), id))
+    }
+}
+
+impl ObjectKey for TombstoneKey {
+    fn key_type(&self) -> StorableObjectType {
+        StorableObjectType::Tombstone
+    }
+
+    fn key_uuid(&self) -> String {
+        self.0.clone()
+    }
+}
+
+impl StorableObject for Tombstone {
+    type Key = TombstoneKey;
+
+    fn key(&self) -> Self::Key {
+        TombstoneKey::new(&self.kind, &self.id)
+    }
+}
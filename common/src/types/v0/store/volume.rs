@@ -5,18 +5,19 @@ use crate::{
         openapi::models,
         store::{
             definitions::{ObjectKey, StorableObject, StorableObjectType},
-            AsOperationSequencer, OperationGuardArc, OperationSequence, ResourceMutex,
+            epoch_secs, AsOperationSequencer, OperationGuardArc, OperationSequence, ResourceMutex,
             ResourceUuid, SpecStatus, SpecTransaction,
         },
         transport::{
-            self, CreateVolume, NexusId, NodeId, ReplicaId, Topology, VolumeId, VolumeLabels,
-            VolumePolicy, VolumeShareProtocol, VolumeStatus,
+            self, CreateVolume, NexusId, NodeId, NvmeReservation, RebuildPriority, ReplicaId,
+            Topology, VolumeDefault, VolumeId, VolumeLabels, VolumePolicy, VolumeShareProtocol,
+            VolumeStatus,
         },
     },
     IntoOption,
 };
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::{collections::HashMap, time::SystemTime};
 
 /// Key used by the store to uniquely identify a VolumeState structure.
 pub struct VolumeStateKey(VolumeId);
@@ -104,6 +105,34 @@ pub struct VolumeSpec {
     /// Flag indicating whether the volume should be thin provisioned
     #[serde(default)]
     pub thin: bool,
+    /// NVMe persistent reservation held by the volume target, which is re-applied whenever
+    /// the volume is republished
+    #[serde(default)]
+    pub reservation: Option<NvmeReservation>,
+    /// When the spec was created, unknown for the specs created before this was recorded
+    #[serde(default)]
+    pub created_at: Option<SystemTime>,
+    /// Parameters which were taken from the cluster defaults when the volume was created
+    #[serde(default)]
+    pub defaults: Vec<VolumeDefault>,
+    /// When the spec was last updated, unknown for the specs stored before this was recorded
+    #[serde(default)]
+    pub updated_at: Option<SystemTime>,
+    /// Replica designated as the source of truth of the volume data, from which the other
+    /// replicas are rebuilt
+    #[serde(default)]
+    pub source_replica: Option<ReplicaId>,
+    /// Size requested on creation, before it was rounded up to the effective `size`, unknown
+    /// for the specs created before this was recorded
+    #[serde(default)]
+    pub requested_size: Option<u64>,
+    /// Priority of the rebuilds of the volume replicas over those of the other volumes
+    #[serde(default)]
+    pub rebuild_priority: RebuildPriority,
+    /// The rebuilds of the volume replicas are paused until they're resumed, including those
+    /// which start in the meantime
+    #[serde(default)]
+    pub rebuild_paused: bool,
 }
 
 impl ResourceMutex<VolumeSpec> {
@@ -199,6 +228,8 @@ impl From<VolumeOperationState> for models::VolumeSpecOperation {
     }
 }
 
+crate::impl_spec_timestamps!(VolumeSpec);
+
 impl SpecTransaction<VolumeOperation> for VolumeSpec {
     fn pending_op(&self) -> bool {
         self.operation.is_some()
@@ -224,10 +255,22 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
                     }
                 }
                 VolumeOperation::SetReplica(count) => self.num_replicas = count,
-                VolumeOperation::RemoveUnusedReplica(_) => {}
-                VolumeOperation::Publish((node, nexus, protocol)) => {
+                VolumeOperation::SetSourceReplica(replica) => self.source_replica = Some(replica),
+                VolumeOperation::SetRebuildPriority(priority) => self.rebuild_priority = priority,
+                VolumeOperation::PauseRebuild => self.rebuild_paused = true,
+                VolumeOperation::ResumeRebuild => self.rebuild_paused = false,
+                VolumeOperation::ReleaseReservation => self.reservation = None,
+                VolumeOperation::RemoveUnusedReplica(replica) => {
+                    if self.source_replica.as_ref() == Some(&replica) {
+                        self.source_replica = None;
+                    }
+                }
+                VolumeOperation::Publish((node, nexus, protocol, reservation)) => {
                     self.target = Some(VolumeTarget::new(node, nexus.clone(), protocol));
                     self.last_nexus_id = Some(nexus);
+                    if reservation.is_some() {
+                        self.reservation = reservation;
+                    }
                 }
                 VolumeOperation::Unpublish => {
                     self.target = None;
@@ -253,6 +296,12 @@ impl SpecTransaction<VolumeOperation> for VolumeSpec {
             op.result = Some(result);
         }
     }
+
+    fn set_op_result_unknown(&mut self) {
+        if let Some(op) = &mut self.operation {
+            op.result = None;
+        }
+    }
 }
 
 /// Available Volume Operations
@@ -263,9 +312,21 @@ pub enum VolumeOperation {
     Share(VolumeShareProtocol),
     Unshare,
     SetReplica(u8),
-    Publish((NodeId, NexusId, Option<VolumeShareProtocol>)),
+    Publish(
+        (
+            NodeId,
+            NexusId,
+            Option<VolumeShareProtocol>,
+            Option<NvmeReservation>,
+        ),
+    ),
     Unpublish,
     RemoveUnusedReplica(ReplicaId),
+    SetSourceReplica(ReplicaId),
+    SetRebuildPriority(RebuildPriority),
+    PauseRebuild,
+    ResumeRebuild,
+    ReleaseReservation,
 }
 
 impl From<VolumeOperation> for models::volume_spec_operation::Operation {
@@ -281,6 +342,19 @@ impl From<VolumeOperation> for models::volume_spec_operation::Operation {
             VolumeOperation::RemoveUnusedReplica(_) => {
                 models::volume_spec_operation::Operation::RemoveUnusedReplica
             }
+            VolumeOperation::SetSourceReplica(_) => {
+                models::volume_spec_operation::Operation::SetSourceReplica
+            }
+            VolumeOperation::SetRebuildPriority(_) => {
+                models::volume_spec_operation::Operation::SetRebuildPriority
+            }
+            VolumeOperation::PauseRebuild => models::volume_spec_operation::Operation::PauseRebuild,
+            VolumeOperation::ResumeRebuild => {
+                models::volume_spec_operation::Operation::ResumeRebuild
+            }
+            VolumeOperation::ReleaseReservation => {
+                models::volume_spec_operation::Operation::ReleaseReservation
+            }
         }
     }
 }
@@ -330,6 +404,14 @@ impl From<&CreateVolume> for VolumeSpec {
             last_nexus_id: None,
             operation: None,
             thin: request.thin,
+            reservation: None,
+            created_at: Some(SystemTime::now()),
+            defaults: request.defaults.clone(),
+            updated_at: None,
+            source_replica: None,
+            requested_size: request.requested_size,
+            rebuild_priority: request.rebuild_priority,
+            rebuild_paused: false,
         }
     }
 }
@@ -338,6 +420,8 @@ impl PartialEq<CreateVolume> for VolumeSpec {
         let mut other = VolumeSpec::from(other);
         other.status = self.status.clone();
         other.sequencer = self.sequencer.clone();
+        other.created_at = self.created_at;
+        other.updated_at = self.updated_at;
         &other == self
     }
 }
@@ -349,6 +433,8 @@ impl From<&VolumeSpec> for transport::VolumeState {
             status: transport::VolumeStatus::Unknown,
             target: None,
             replica_topology: HashMap::new(),
+            conditions: vec![],
+            frozen_until: None,
         }
     }
 }
@@ -378,6 +464,14 @@ impl From<VolumeSpec> for models::VolumeSpec {
             src.topology.into_opt(),
             src.policy,
             src.thin,
+            src.reservation.into_opt(),
+            Some(src.defaults.into_iter().map(From::from).collect()),
+            src.created_at.map(epoch_secs),
+            src.updated_at.map(epoch_secs),
+            src.source_replica.map(From::from),
+            src.requested_size,
+            src.rebuild_priority.into(),
+            src.rebuild_paused,
         )
     }
 }
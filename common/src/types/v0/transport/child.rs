@@ -2,7 +2,12 @@ use super::*;
 
 use percent_encoding::percent_decode_str;
 use serde::{Deserialize, Serialize};
-use std::{cmp::Ordering, fmt::Debug, str::FromStr};
+use std::{
+    cmp::Ordering,
+    fmt::Debug,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 /// Child information
 #[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
@@ -16,17 +21,58 @@ pub struct Child {
     pub rebuild_progress: Option<u8>,
     /// Reason for the child state.
     pub state_reason: ChildStateReason,
+    /// The latest fault of the child, kept even once the child is no longer faulted.
+    #[serde(default)]
+    pub last_fault: Option<ChildFault>,
 }
 impl Child {
     /// If if the state reason is lack of space.
     pub fn enospc(&self) -> bool {
         self.state_reason == ChildStateReason::NoSpace
     }
+    /// If the child is being rebuilt.
+    pub fn rebuilding(&self) -> bool {
+        self.state == ChildState::Degraded && self.rebuild_progress.is_some()
+    }
+    /// Carry the latest fault over from the `previous` state of the child, or record a new one
+    /// if the child has faulted since, or faulted for a different reason.
+    pub fn with_fault_history(mut self, previous: Option<&Child>) -> Self {
+        let previous_fault = previous.and_then(|p| p.last_fault.clone());
+        self.last_fault = match previous_fault {
+            Some(fault)
+                if !self.state.faulted()
+                    || previous.map(|p| p.state.faulted()) == Some(true)
+                        && fault.reason == self.state_reason =>
+            {
+                Some(fault)
+            }
+            _ if self.state.faulted() => Some(ChildFault {
+                reason: self.state_reason.clone(),
+                faulted_at: SystemTime::now(),
+            }),
+            _ => None,
+        };
+        self
+    }
+    /// How much longer a faulted child should be left in place before it's replaced, given the
+    /// `grace_period` allowed to the children whose device timed out, as it may come back.
+    /// Children faulted for any other reason, eg: I/O errors, are replaced right away.
+    pub fn fault_grace_left(&self, grace_period: Duration) -> Option<Duration> {
+        let fault = self.last_fault.as_ref().filter(|_| self.state.faulted())?;
+        if fault.reason != ChildStateReason::TimedOut {
+            return None;
+        }
+        let elapsed = fault.faulted_at.elapsed().unwrap_or_default();
+        grace_period
+            .checked_sub(elapsed)
+            .filter(|left| !left.is_zero())
+    }
 }
 
 impl From<Child> for models::Child {
     fn from(src: Child) -> Self {
         Self {
+            last_fault: src.last_fault.map(From::from),
             rebuild_progress: src.rebuild_progress,
             state: src.state.into(),
             uri: src.uri.into(),
@@ -34,6 +80,26 @@ impl From<Child> for models::Child {
     }
 }
 
+/// A fault of a nexus child, as observed by the control plane.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildFault {
+    /// Why the child was faulted.
+    pub reason: ChildStateReason,
+    /// When the fault was first observed.
+    pub faulted_at: SystemTime,
+}
+
+impl From<ChildFault> for models::ChildFault {
+    fn from(src: ChildFault) -> Self {
+        let faulted_at = src
+            .faulted_at
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        Self::new(faulted_at.as_secs(), src.reason)
+    }
+}
+
 bus_impl_string_id_percent_decoding!(ChildUri, "URI of a nexus child");
 
 impl ChildUri {
@@ -144,6 +210,25 @@ pub enum ChildStateReason {
     AdminCommandFailed,
 }
 
+impl From<ChildStateReason> for models::ChildStateReason {
+    fn from(src: ChildStateReason) -> Self {
+        match src {
+            ChildStateReason::Unknown => Self::Unknown,
+            ChildStateReason::Init => Self::Init,
+            ChildStateReason::Closed => Self::Closed,
+            ChildStateReason::ConfigInvalid => Self::ConfigInvalid,
+            ChildStateReason::OutOfSync => Self::OutOfSync,
+            ChildStateReason::NoSpace => Self::NoSpace,
+            ChildStateReason::TimedOut => Self::TimedOut,
+            ChildStateReason::CantOpen => Self::CantOpen,
+            ChildStateReason::RebuildFailed => Self::RebuildFailed,
+            ChildStateReason::IoError => Self::IoError,
+            ChildStateReason::ByClient => Self::ByClient,
+            ChildStateReason::AdminCommandFailed => Self::AdminCommandFailed,
+        }
+    }
+}
+
 impl Default for ChildState {
     fn default() -> Self {
         Self::Unknown
@@ -170,6 +255,9 @@ pub struct RemoveNexusChild {
     pub nexus: NexusId,
     /// URI of the child device to be removed
     pub uri: ChildUri,
+    /// Remove the child even if it's the last healthy replica of the volume, which destroys
+    /// the volume data
+    pub force: bool,
 }
 impl RemoveNexusChild {
     /// Return new `Self`
@@ -178,6 +266,7 @@ impl RemoveNexusChild {
             node: node.clone(),
             nexus: nexus.clone(),
             uri: uri.clone(),
+            force: false,
         }
     }
 }
@@ -187,6 +276,7 @@ impl From<AddNexusChild> for RemoveNexusChild {
             node: add.node,
             nexus: add.nexus,
             uri: add.uri,
+            force: false,
         }
     }
 }
@@ -204,3 +294,47 @@ pub struct AddNexusChild {
     /// auto start rebuilding
     pub auto_rebuild: bool,
 }
+
+/// Bring an offline child of a Nexus back online Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OnlineNexusChild {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub nexus: NexusId,
+    /// URI of the child device to be brought online
+    pub uri: ChildUri,
+}
+impl OnlineNexusChild {
+    /// Return new `Self`
+    pub fn new(node: &NodeId, nexus: &NexusId, uri: &ChildUri) -> Self {
+        Self {
+            node: node.clone(),
+            nexus: nexus.clone(),
+            uri: uri.clone(),
+        }
+    }
+}
+
+/// Take a child of a Nexus offline, without removing it from the nexus, Request
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OfflineNexusChild {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub nexus: NexusId,
+    /// URI of the child device to be taken offline
+    pub uri: ChildUri,
+}
+impl OfflineNexusChild {
+    /// Return new `Self`
+    pub fn new(node: &NodeId, nexus: &NexusId, uri: &ChildUri) -> Self {
+        Self {
+            node: node.clone(),
+            nexus: nexus.clone(),
+            uri: uri.clone(),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use std::net::SocketAddr;
+use std::{net::SocketAddr, time::Duration};
 
 use super::*;
 
@@ -27,22 +27,112 @@ impl NodeAgentInfo {
     }
 }
 
+/// Registration of a node-agent with the cluster-agent, which the node-agent must renew by
+/// registering again before it expires.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeAgentRegistration {
+    /// how long the registration lasts unless it's renewed
+    ttl: Duration,
+    /// how often the node-agent is expected to renew its registration
+    heartbeat_interval: Duration,
+}
+
+impl NodeAgentRegistration {
+    /// Create a new registration which expires after the `ttl`, allowing for a couple of
+    /// heartbeats to be missed before it does.
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            ttl,
+            heartbeat_interval: ttl / 3,
+        }
+    }
+    /// Create a new registration from its `ttl` and `heartbeat_interval`.
+    pub fn with_heartbeat_interval(ttl: Duration, heartbeat_interval: Duration) -> Self {
+        Self {
+            ttl,
+            heartbeat_interval,
+        }
+    }
+
+    /// How long the registration lasts unless it's renewed.
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    /// How often the node-agent is expected to renew its registration.
+    pub fn heartbeat_interval(&self) -> Duration {
+        self.heartbeat_interval
+    }
+}
+
+/// Severity of the failure of an NVMe path, the most severe first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PathSeverity {
+    /// The path failed with a hard error, eg: its controller could not reconnect.
+    Failed,
+    /// The IO on the path timed out.
+    Timeout,
+    /// The path is alive but its latency is degraded.
+    DegradedLatency,
+}
+
+impl PathSeverity {
+    /// Check if the target of the path should be failed over, or if the path is merely degraded.
+    pub fn failover(&self) -> bool {
+        match self {
+            Self::Failed | Self::Timeout => true,
+            Self::DegradedLatency => false,
+        }
+    }
+}
+
+impl Default for PathSeverity {
+    fn default() -> Self {
+        Self::Failed
+    }
+}
+
+impl std::fmt::Display for PathSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Failed => write!(f, "failed"),
+            Self::Timeout => write!(f, "timeout"),
+            Self::DegradedLatency => write!(f, "degraded-latency"),
+        }
+    }
+}
+
 /// Failed NVMe path.
 #[derive(Debug, Clone)]
 pub struct FailedPath {
     target_nqn: String,
+    severity: PathSeverity,
 }
 
 impl FailedPath {
     /// Create a new instance of FailedPath for a given NVMe target NQN.
     pub fn new(target_nqn: String) -> Self {
-        Self { target_nqn }
+        Self {
+            target_nqn,
+            severity: PathSeverity::default(),
+        }
+    }
+    /// Set the severity of the failure of the path.
+    #[must_use]
+    pub fn with_severity(mut self, severity: PathSeverity) -> Self {
+        self.severity = severity;
+        self
     }
 
     /// Get target NQN.
     pub fn target_nqn(&self) -> &str {
         &self.target_nqn
     }
+
+    /// Get the severity of the failure.
+    pub fn severity(&self) -> PathSeverity {
+        self.severity
+    }
 }
 
 /// Report failed NVMe paths.
@@ -64,4 +154,179 @@ impl ReportFailedPaths {
     pub fn failed_paths(&self) -> &Vec<FailedPath> {
         &self.failed_paths
     }
+
+    /// Deduplicate the failed paths by their target NQN, keeping the most severe failure of each
+    /// target, in the order in which the targets were first reported.
+    #[must_use]
+    pub fn dedup(mut self) -> Self {
+        let mut paths: Vec<FailedPath> = Vec::with_capacity(self.failed_paths.len());
+        for path in self.failed_paths {
+            match paths.iter_mut().find(|p| p.target_nqn == path.target_nqn) {
+                Some(known) => known.severity = known.severity.min(path.severity),
+                None => paths.push(path),
+            }
+        }
+        self.failed_paths = paths;
+        self
+    }
+}
+
+/// NVMe path statistics counters of a target.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct PathStats {
+    /// Total number of paths.
+    pub total_paths: u32,
+    /// Number of live paths.
+    pub live_paths: u32,
+    /// Number of failed paths.
+    pub failed_paths: u32,
+    /// Number of controller reconnects.
+    pub reconnects: u64,
+    /// Mean latency of the controller reconnects.
+    pub mean_reconnect_latency: std::time::Duration,
+}
+
+impl PathStats {
+    /// Accumulate `other` into these statistics.
+    /// The mean reconnect latency is weighted by the number of reconnects of each side.
+    pub fn accumulate(&mut self, other: &Self) {
+        let reconnects = self.reconnects + other.reconnects;
+        if reconnects > 0 {
+            let total = self.mean_reconnect_latency.as_micros() * self.reconnects as u128
+                + other.mean_reconnect_latency.as_micros() * other.reconnects as u128;
+            self.mean_reconnect_latency =
+                std::time::Duration::from_micros((total / reconnects as u128) as u64);
+        }
+        self.total_paths += other.total_paths;
+        self.live_paths += other.live_paths;
+        self.failed_paths += other.failed_paths;
+        self.reconnects = reconnects;
+    }
+}
+
+impl From<PathStats> for models::PathStats {
+    fn from(src: PathStats) -> Self {
+        Self::new(
+            src.failed_paths,
+            src.live_paths,
+            src.mean_reconnect_latency.as_micros() as u64,
+            src.reconnects,
+            src.total_paths,
+        )
+    }
+}
+
+/// NVMe path statistics of a target, as seen by an application node.
+#[derive(Debug, Clone)]
+pub struct TargetPathStats {
+    target_nqn: String,
+    stats: PathStats,
+}
+
+impl TargetPathStats {
+    /// Create a new instance of TargetPathStats for a given NVMe target NQN.
+    pub fn new(target_nqn: String, stats: PathStats) -> Self {
+        Self { target_nqn, stats }
+    }
+
+    /// Get target NQN.
+    pub fn target_nqn(&self) -> &str {
+        &self.target_nqn
+    }
+
+    /// Get the path statistics.
+    pub fn stats(&self) -> &PathStats {
+        &self.stats
+    }
+}
+
+/// Report NVMe path statistics.
+#[derive(Debug)]
+pub struct ReportPathStats {
+    node: String,
+    stats: Vec<TargetPathStats>,
+}
+
+impl ReportPathStats {
+    pub fn new(node: String, stats: Vec<TargetPathStats>) -> Self {
+        Self { node, stats }
+    }
+
+    pub fn node_name(&self) -> &str {
+        &self.node
+    }
+
+    pub fn stats(&self) -> &Vec<TargetPathStats> {
+        &self.stats
+    }
+}
+
+/// Get the NVMe path statistics of a volume.
+#[derive(Debug)]
+pub struct GetPathStats {
+    volume_id: VolumeId,
+}
+
+impl GetPathStats {
+    pub fn new(volume_id: VolumeId) -> Self {
+        Self { volume_id }
+    }
+
+    pub fn volume_id(&self) -> &VolumeId {
+        &self.volume_id
+    }
+}
+
+/// NVMe path statistics of a volume reported by a single node.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NodePathStats {
+    /// Node which reported the statistics.
+    pub node: NodeId,
+    /// The reported statistics.
+    pub stats: PathStats,
+}
+
+impl From<NodePathStats> for models::NodePathStats {
+    fn from(src: NodePathStats) -> Self {
+        Self::new(src.node, src.stats)
+    }
+}
+
+/// NVMe path statistics of a volume aggregated across all nodes at a point in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathStatsSample {
+    /// Seconds since the unix epoch when the sample was taken.
+    pub timestamp: u64,
+    /// The aggregated statistics.
+    pub stats: PathStats,
+}
+
+impl From<PathStatsSample> for models::PathStatsSample {
+    fn from(src: PathStatsSample) -> Self {
+        Self::new(src.stats, src.timestamp)
+    }
+}
+
+/// Aggregated NVMe path statistics of a volume.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VolumePathStats {
+    /// The volume which the statistics belong to.
+    pub volume_id: VolumeId,
+    /// The current statistics, aggregated across all nodes.
+    pub current: PathStats,
+    /// The latest statistics reported by each node.
+    pub nodes: Vec<NodePathStats>,
+    /// Previous aggregated statistics, oldest first.
+    pub history: Vec<PathStatsSample>,
+}
+
+impl From<VolumePathStats> for models::VolumePathStats {
+    fn from(src: VolumePathStats) -> Self {
+        Self::new(
+            src.current,
+            src.history.into_iter().map(From::from).collect::<Vec<_>>(),
+            src.nodes.into_iter().map(From::from).collect::<Vec<_>>(),
+            src.volume_id,
+        )
+    }
 }
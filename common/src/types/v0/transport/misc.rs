@@ -340,3 +340,72 @@ impl From<Protocol> for models::Protocol {
 /// Liveness Probe
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct Liveness {}
+
+/// Status of a resource condition
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, EnumString, ToString)]
+pub enum ConditionStatus {
+    /// the condition holds
+    True,
+    /// the condition does not hold
+    False,
+    /// whether the condition holds is not known
+    Unknown,
+}
+
+impl From<ConditionStatus> for models::ConditionStatus {
+    fn from(src: ConditionStatus) -> Self {
+        match src {
+            ConditionStatus::True => Self::True,
+            ConditionStatus::False => Self::False,
+            ConditionStatus::Unknown => Self::Unknown,
+        }
+    }
+}
+
+/// Condition of a resource, in the format of the kubernetes conditions
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceCondition {
+    /// type of the condition, eg: Ready
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// whether the condition holds
+    pub status: ConditionStatus,
+    /// reason for the last transition of the condition, in CamelCase
+    pub reason: String,
+    /// human readable details of the last transition of the condition
+    pub message: String,
+    /// time of the last transition of the status of the condition
+    pub last_transition_time: std::time::SystemTime,
+}
+
+impl ResourceCondition {
+    /// Return a new `Self` which transitions at `time`
+    pub fn new(
+        kind: impl Into<String>,
+        status: ConditionStatus,
+        reason: impl Into<String>,
+        message: impl Into<String>,
+        time: std::time::SystemTime,
+    ) -> Self {
+        Self {
+            kind: kind.into(),
+            status,
+            reason: reason.into(),
+            message: message.into(),
+            last_transition_time: time,
+        }
+    }
+}
+
+impl From<ResourceCondition> for models::ResourceCondition {
+    fn from(src: ResourceCondition) -> Self {
+        Self::new(
+            crate::types::v0::store::epoch_secs(src.last_transition_time),
+            src.message,
+            src.reason,
+            src.status,
+            src.kind,
+        )
+    }
+}
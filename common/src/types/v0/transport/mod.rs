@@ -67,6 +67,8 @@ pub enum MessageIdVs {
     CreatePool,
     /// Destroy Pool,
     DestroyPool,
+    /// Discover the pools which could be imported from the disks of a node
+    DiscoverPools,
     /// Get replicas with filter
     GetReplicas,
     /// Create Replica,
@@ -89,14 +91,36 @@ pub enum MessageIdVs {
     ShareNexus,
     /// Unshare Nexus
     UnshareNexus,
+    /// Pause the IO of a nexus
+    PauseNexus,
+    /// Resume the IO of a nexus
+    ResumeNexus,
+    /// Pause the rebuild of a nexus child
+    PauseNexusRebuild,
+    /// Resume the rebuild of a nexus child
+    ResumeNexusRebuild,
     /// Remove a child from its parent nexus
     RemoveNexusChild,
     /// Add a child to a nexus
     AddNexusChild,
+    /// Bring an offline child of a nexus back online
+    OnlineNexusChild,
+    /// Take a child of a nexus offline
+    OfflineNexusChild,
     /// Get all volumes
     GetVolumes,
+    /// Get the status of many volumes
+    GetVolumesStatus,
+    /// Get the topology tree of a volume
+    GetVolumeTopology,
+    /// Get the revisions of a volume spec
+    GetVolumeHistory,
+    /// Get the changes made by a revision of a volume spec
+    GetVolumeRevisionDiff,
     /// Create Volume,
     CreateVolume,
+    /// Validate a volume creation request, without creating the volume
+    ValidateCreateVolume,
     /// Delete Volume
     DestroyVolume,
     /// Publish Volume,
@@ -113,6 +137,18 @@ pub enum MessageIdVs {
     RemoveVolumeNexus,
     /// Set replica count
     SetVolumeReplica,
+    /// Set the source replica of the volume
+    SetVolumeSourceReplica,
+    /// Update the mutable parameters of a volume
+    PatchVolume,
+    /// Freeze the IO of a volume
+    FreezeVolume,
+    /// Thaw the IO of a volume
+    ThawVolume,
+    /// Pause the rebuilds of a volume
+    PauseVolumeRebuild,
+    /// Resume the rebuilds of a volume
+    ResumeVolumeRebuild,
     /// Generic JSON gRPC message
     JsonGrpc,
     /// Get block devices
@@ -127,8 +163,48 @@ pub enum MessageIdVs {
     GetSpecs,
     /// Get States
     GetStates,
+    /// Clear the tombstone of a destroyed resource
+    ClearTombstone,
+    /// Reload the resources of all the nodes
+    RefreshRegistry,
+    /// Get the cluster-wide volume defaults
+    GetClusterDefaults,
+    /// Replace the cluster-wide volume defaults
+    SetClusterDefaults,
+    /// Get the per label volume quotas and their usage
+    GetVolumeQuotas,
+    /// Replace the per label volume quotas
+    SetVolumeQuotas,
+    /// Get the runtime state of the reconciler passes
+    GetReconcilerPasses,
+    /// Enable or disable a reconciler pass
+    SetReconcilerPass,
+    /// Get the health of the persistent store endpoints
+    GetStoreHealth,
+    /// Patch the persisted spec of a resource
+    PatchSpec,
+    /// Get the operation pending on the spec of a resource
+    GetPendingOperation,
+    /// Clear the operation pending on the spec of a resource
+    ClearPendingOperation,
+    /// Forcibly release the operation guard of a resource
+    ReleaseOperationGuard,
+    /// Get the operations pending on the specs of all the resources
+    GetPendingOperations,
+    /// Retry replaying the operation pending on the spec of a resource, given up on as stuck
+    RetryOperationReplay,
+    GetRateLimits,
+    GetSchedulingTraces,
+    /// Get the round-trip times which the nodes measured to their peers
+    GetLatencyMatrix,
+    /// Get the replicas and nexuses which only exist as a spec or as a dataplane object
+    GetOrphans,
     /// Report failed NVMe paths
     ReportFailedPaths,
+    /// Report NVMe path statistics
+    ReportPathStats,
+    /// Get the NVMe path statistics of a volume
+    GetPathStats,
 }
 
 impl From<MessageIdVs> for MessageId {
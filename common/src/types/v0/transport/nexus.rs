@@ -7,7 +7,7 @@ use crate::{
     },
 };
 use serde::{Deserialize, Serialize};
-use std::{convert::TryFrom, fmt::Debug, ops::RangeInclusive};
+use std::{convert::TryFrom, fmt::Debug, ops::RangeInclusive, time::SystemTime};
 use strum_macros::{EnumString, ToString};
 
 /// Volume Nexuses
@@ -176,6 +176,10 @@ pub struct CreateNexus {
     pub owner: Option<VolumeId>,
     /// Nexus Nvmf Configuration
     pub config: Option<NexusNvmfConfig>,
+    /// Don't probe whether the node can connect to the remote children before the creation,
+    /// eg: on air-gapped setups
+    #[serde(default)]
+    pub skip_child_probe: bool,
 }
 
 /// Nvmf Controller Id Range
@@ -238,6 +242,9 @@ pub struct NexusNvmfConfig {
     reservation_key: u64,
     /// preempts this reservation key
     preempt_reservation_key: Option<u64>,
+    /// persistent reservation type, if not set the dataplane uses its default
+    #[serde(default)]
+    reservation_type: Option<NvmeReservationType>,
 }
 
 impl NexusNvmfConfig {
@@ -267,8 +274,16 @@ impl NexusNvmfConfig {
             controller_id_range,
             reservation_key,
             preempt_reservation_key,
+            reservation_type: None,
         }
     }
+    /// set the persistent reservation key and type from the given `reservation`
+    #[must_use]
+    pub fn with_reservation(mut self, reservation: &NvmeReservation) -> Self {
+        self.reservation_key = reservation.key;
+        self.reservation_type = Some(reservation.reservation_type);
+        self
+    }
     /// get controller_id_range
     pub fn controller_id_range(&self) -> NvmfControllerIdRange {
         self.controller_id_range.clone()
@@ -281,6 +296,10 @@ impl NexusNvmfConfig {
     pub fn preempt_reservation_key(&self) -> Option<u64> {
         self.preempt_reservation_key
     }
+    /// get reservation_type
+    pub fn reservation_type(&self) -> Option<NvmeReservationType> {
+        self.reservation_type
+    }
 }
 
 impl Default for NexusNvmfConfig {
@@ -290,17 +309,87 @@ impl Default for NexusNvmfConfig {
                 controller_id_range: NvmfControllerIdRange::random_min(),
                 reservation_key: 1,
                 preempt_reservation_key: None,
+                reservation_type: None,
             }
         } else {
             Self {
                 controller_id_range: NvmfControllerIdRange::default(),
                 reservation_key: 1,
                 preempt_reservation_key: None,
+                reservation_type: None,
             }
         }
     }
 }
 
+/// NVMe persistent reservation type
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NvmeReservationType {
+    WriteExclusive,
+    ExclusiveAccess,
+    WriteExclusiveRegsOnly,
+    ExclusiveAccessRegsOnly,
+    WriteExclusiveAllRegs,
+    ExclusiveAccessAllRegs,
+}
+
+impl From<models::NvmeReservationType> for NvmeReservationType {
+    fn from(src: models::NvmeReservationType) -> Self {
+        match src {
+            models::NvmeReservationType::WriteExclusive => Self::WriteExclusive,
+            models::NvmeReservationType::ExclusiveAccess => Self::ExclusiveAccess,
+            models::NvmeReservationType::WriteExclusiveRegsOnly => Self::WriteExclusiveRegsOnly,
+            models::NvmeReservationType::ExclusiveAccessRegsOnly => Self::ExclusiveAccessRegsOnly,
+            models::NvmeReservationType::WriteExclusiveAllRegs => Self::WriteExclusiveAllRegs,
+            models::NvmeReservationType::ExclusiveAccessAllRegs => Self::ExclusiveAccessAllRegs,
+        }
+    }
+}
+impl From<NvmeReservationType> for models::NvmeReservationType {
+    fn from(src: NvmeReservationType) -> Self {
+        match src {
+            NvmeReservationType::WriteExclusive => Self::WriteExclusive,
+            NvmeReservationType::ExclusiveAccess => Self::ExclusiveAccess,
+            NvmeReservationType::WriteExclusiveRegsOnly => Self::WriteExclusiveRegsOnly,
+            NvmeReservationType::ExclusiveAccessRegsOnly => Self::ExclusiveAccessRegsOnly,
+            NvmeReservationType::WriteExclusiveAllRegs => Self::WriteExclusiveAllRegs,
+            NvmeReservationType::ExclusiveAccessAllRegs => Self::ExclusiveAccessAllRegs,
+        }
+    }
+}
+
+/// NVMe persistent reservation which the nexus holds on its replicas, fencing off any other
+/// initiator which might still be writing to them
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NvmeReservation {
+    /// reservation key
+    pub key: u64,
+    /// reservation type
+    pub reservation_type: NvmeReservationType,
+}
+
+impl NvmeReservation {
+    /// Create new `Self` from the given parameters
+    pub fn new(key: u64, reservation_type: NvmeReservationType) -> Self {
+        Self {
+            key,
+            reservation_type,
+        }
+    }
+}
+
+impl From<models::NvmeReservation> for NvmeReservation {
+    fn from(src: models::NvmeReservation) -> Self {
+        Self::new(src.key, src.reservation_type.into())
+    }
+}
+impl From<NvmeReservation> for models::NvmeReservation {
+    fn from(src: NvmeReservation) -> Self {
+        Self::new(src.key, src.reservation_type)
+    }
+}
+
 impl CreateNexus {
     /// Create new `Self` from the given parameters
     pub fn new(
@@ -320,6 +409,7 @@ impl CreateNexus {
             managed,
             owner: owner.cloned(),
             config,
+            skip_child_probe: false,
         }
     }
     /// Name of the nexus.
@@ -469,3 +559,79 @@ pub struct UnshareNexus {
     /// uuid of the nexus
     pub uuid: NexusId,
 }
+
+/// Pause the IO of a Nexus
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseNexus {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub uuid: NexusId,
+    /// the IO is resumed by the control plane once past this deadline, if not resumed before
+    pub thaw_deadline: SystemTime,
+}
+impl PauseNexus {
+    /// Pause the IO of the given nexus until the `thaw_deadline` at the latest
+    pub fn new(node: &NodeId, uuid: &NexusId, thaw_deadline: SystemTime) -> Self {
+        Self {
+            node: node.clone(),
+            uuid: uuid.clone(),
+            thaw_deadline,
+        }
+    }
+}
+
+/// Resume the paused IO of a Nexus
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeNexus {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub uuid: NexusId,
+}
+
+/// Pause the rebuild of a Nexus child
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseNexusRebuild {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub nexus: NexusId,
+    /// uri of the child which is being rebuilt
+    pub uri: ChildUri,
+}
+impl PauseNexusRebuild {
+    /// Pause the rebuild of the child `uri` of the given nexus
+    pub fn new(node: &NodeId, nexus: &NexusId, uri: &ChildUri) -> Self {
+        Self {
+            node: node.clone(),
+            nexus: nexus.clone(),
+            uri: uri.clone(),
+        }
+    }
+}
+
+/// Resume the paused rebuild of a Nexus child
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeNexusRebuild {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// uuid of the nexus
+    pub nexus: NexusId,
+    /// uri of the child whose rebuild is paused
+    pub uri: ChildUri,
+}
+impl ResumeNexusRebuild {
+    /// Resume the paused rebuild of the child `uri` of the given nexus
+    pub fn new(node: &NodeId, nexus: &NexusId, uri: &ChildUri) -> Self {
+        Self {
+            node: node.clone(),
+            nexus: nexus.clone(),
+            uri: uri.clone(),
+        }
+    }
+}
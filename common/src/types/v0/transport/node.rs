@@ -18,6 +18,19 @@ pub struct Register {
     pub grpc_endpoint: String,
     /// api versions registered by the dataplane
     pub api_versions: Option<Vec<APIVersion>>,
+    /// round-trip times measured by the io-engine instance to its peers, if any
+    #[serde(default)]
+    pub peer_latencies: Vec<PeerLatency>,
+}
+
+/// Round-trip time measured by a node to one of its peers
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PeerLatency {
+    /// id of the peer node
+    pub node: NodeId,
+    /// round-trip time to the peer node, in microseconds
+    pub rtt_us: u64,
 }
 
 /// Deregister message payload
@@ -201,3 +214,47 @@ impl Default for APIVersion {
         Self::V0
     }
 }
+
+/// Get the round-trip times which the nodes measured to their peers
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLatencyMatrix {}
+
+/// Round-trip times which a node measured to its peers
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeLatencies {
+    /// id of the node which measured the round-trip times
+    pub node: NodeId,
+    /// the latest round-trip times reported by the node
+    pub peers: Vec<PeerLatency>,
+}
+
+/// The round-trip times between the nodes, as last reported by each node on its registration
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LatencyMatrix {
+    /// the nodes which reported any round-trip times
+    pub nodes: Vec<NodeLatencies>,
+}
+
+impl From<PeerLatency> for models::PeerLatency {
+    fn from(src: PeerLatency) -> Self {
+        Self::new(src.node, src.rtt_us)
+    }
+}
+
+impl From<NodeLatencies> for models::NodeLatencies {
+    fn from(src: NodeLatencies) -> Self {
+        Self::new(
+            src.node,
+            src.peers.into_iter().map(From::from).collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl From<LatencyMatrix> for models::LatencyMatrix {
+    fn from(src: LatencyMatrix) -> Self {
+        Self::new(src.nodes.into_iter().map(From::from).collect::<Vec<_>>())
+    }
+}
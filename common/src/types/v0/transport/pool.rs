@@ -55,6 +55,14 @@ impl From<PoolStatus> for models::PoolStatus {
     }
 }
 
+/// Cluster size of the pools whose io-engine doesn't report it, which is that of the lvol stores
+/// created with the default options.
+pub const DEFAULT_POOL_CLUSTER_SIZE: u64 = 4 * 1024 * 1024;
+
+fn default_cluster_size() -> u64 {
+    DEFAULT_POOL_CLUSTER_SIZE
+}
+
 /// Pool information
 #[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -71,6 +79,23 @@ pub struct PoolState {
     pub capacity: u64,
     /// used bytes from the pool
     pub used: u64,
+    /// size of the clusters which the pool allocates the replicas in, in bytes
+    #[serde(default = "default_cluster_size")]
+    pub cluster_size: u64,
+}
+
+impl PoolState {
+    /// Get the size which a replica of `size` bytes actually takes on the pool, rounded up to a
+    /// multiple of the cluster size of the pool.
+    pub fn replica_size(&self, size: u64) -> u64 {
+        match self.cluster_size {
+            0 => size,
+            cluster_size => match size % cluster_size {
+                0 => size,
+                rem => size.saturating_add(cluster_size - rem),
+            },
+        }
+    }
 }
 
 impl From<PoolState> for models::PoolState {
@@ -132,6 +157,9 @@ pub struct Pool {
     spec: Option<PoolSpec>,
     /// Runtime state of the pool.
     state: Option<PoolState>,
+    /// Conditions of the pool, in the format of the kubernetes conditions.
+    #[serde(default)]
+    conditions: Vec<ResourceCondition>,
 }
 
 impl Pool {
@@ -141,6 +169,7 @@ impl Pool {
             id: spec.id.clone(),
             spec: Some(spec),
             state: Some(state),
+            conditions: vec![],
         }
     }
     /// Construct a new pool with spec but no state
@@ -149,6 +178,7 @@ impl Pool {
             id: spec.id.clone(),
             spec: Some(spec),
             state: None,
+            conditions: vec![],
         }
     }
     /// Construct a new pool with optional spec and state
@@ -157,6 +187,7 @@ impl Pool {
             id: state.id.clone(),
             spec,
             state: Some(state),
+            conditions: vec![],
         }
     }
     /// Try to construct a new pool from spec and state
@@ -168,6 +199,16 @@ impl Pool {
             _ => None,
         }
     }
+    /// Set the conditions of the pool.
+    #[must_use]
+    pub fn with_conditions(mut self, conditions: Vec<ResourceCondition>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+    /// Get the conditions of the pool.
+    pub fn conditions(&self) -> &Vec<ResourceCondition> {
+        &self.conditions
+    }
     /// Get the pool spec.
     pub fn spec(&self) -> Option<PoolSpec> {
         self.spec.clone()
@@ -193,7 +234,12 @@ impl Pool {
 
 impl From<Pool> for models::Pool {
     fn from(src: Pool) -> Self {
-        models::Pool::new_all(src.id, src.spec.into_opt(), src.state.into_opt())
+        models::Pool::new_all(
+            src.id,
+            src.spec.into_opt(),
+            src.state.into_opt(),
+            Some(src.conditions.into_iter().map(From::from).collect()),
+        )
     }
 }
 
@@ -280,3 +326,91 @@ pub struct DestroyPool {
     /// id of the pool
     pub id: PoolId,
 }
+
+/// Discover the pools which could be imported from the disks of a node
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoverPools {
+    /// id of the io-engine instance
+    pub node: NodeId,
+}
+
+/// A pool found on the disks of a node, as per its on-disk metadata
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DiscoveredPool {
+    /// id of the io-engine instance
+    pub node: NodeId,
+    /// id of the pool
+    pub id: PoolId,
+    /// uuid of the pool, if its metadata records one
+    pub uuid: Option<String>,
+    /// disk device paths or URIs which the pool was found on
+    pub disks: Vec<PoolDeviceUri>,
+    /// approximate size of the pool in bytes
+    pub capacity: u64,
+    /// whether the control plane already has a spec for the pool
+    pub spec_exists: bool,
+}
+
+impl From<&DiscoveredPool> for CreatePool {
+    fn from(pool: &DiscoveredPool) -> Self {
+        Self::new(&pool.node, &pool.id, &pool.disks, &None)
+    }
+}
+
+impl From<DiscoveredPool> for models::DiscoveredPool {
+    fn from(src: DiscoveredPool) -> Self {
+        Self::new_all(
+            src.capacity,
+            src.disks,
+            src.id,
+            src.node,
+            src.spec_exists,
+            src.uuid,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replica_size() {
+        let pool = |cluster_size| PoolState {
+            cluster_size,
+            ..Default::default()
+        };
+        let mib = 1024 * 1024;
+
+        let default = pool(DEFAULT_POOL_CLUSTER_SIZE);
+        assert_eq!(default.replica_size(0), 0);
+        assert_eq!(default.replica_size(1), 4 * mib);
+        assert_eq!(default.replica_size(4 * mib), 4 * mib);
+        assert_eq!(default.replica_size(5 * mib), 8 * mib);
+
+        let small = pool(mib);
+        assert_eq!(small.replica_size(5 * mib), 5 * mib);
+        assert_eq!(small.replica_size(5 * mib + 512), 6 * mib);
+
+        let odd = pool(3 * mib);
+        assert_eq!(odd.replica_size(4 * mib), 6 * mib);
+
+        // a pool whose cluster size is not known doesn't round the sizes
+        assert_eq!(pool(0).replica_size(5 * mib + 512), 5 * mib + 512);
+    }
+
+    #[test]
+    fn cluster_size_compat() {
+        // a state serialized before the cluster size was recorded
+        let mut state = serde_json::to_value(&PoolState::default()).unwrap();
+        state
+            .as_object_mut()
+            .unwrap()
+            .remove("clusterSize")
+            .unwrap();
+        let state: PoolState = serde_json::from_value(state).unwrap();
+        assert_eq!(state.cluster_size, DEFAULT_POOL_CLUSTER_SIZE);
+    }
+}
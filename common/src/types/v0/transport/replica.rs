@@ -130,6 +130,7 @@ impl From<Replica> for DestroyReplica {
             uuid: replica.uuid,
             name: replica.name.into(),
             disowners: Default::default(),
+            override_redundancy_check: false,
         }
     }
 }
@@ -165,6 +166,9 @@ pub struct ReplicaOwners {
     #[serde(skip)]
     nexuses: Vec<NexusId>,
     disown_all: bool,
+    /// Generation of the replica the owners refer to, 0 if unknown
+    #[serde(default)]
+    generation: u64,
 }
 impl ReplicaOwners {
     /// Create new owners from the given volume and nexus id's
@@ -173,6 +177,7 @@ impl ReplicaOwners {
             volume,
             nexuses,
             disown_all: false,
+            generation: 0,
         }
     }
     /// Create a special `Self` that will disown all owners.
@@ -187,6 +192,19 @@ impl ReplicaOwners {
         self.disown_all = true;
         self
     }
+    /// Refer to the replica of the given `generation`.
+    pub fn with_generation(mut self, generation: u64) -> Self {
+        self.generation = generation;
+        self
+    }
+    /// Set the generation of the replica the owners refer to
+    pub fn set_generation(&mut self, generation: u64) {
+        self.generation = generation;
+    }
+    /// Return the generation of the replica the owners refer to, 0 if unknown
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
     /// Return the volume owner, if any
     pub fn volume(&self) -> Option<&VolumeId> {
         self.volume.as_ref()
@@ -217,6 +235,7 @@ impl ReplicaOwners {
             volume: Some(volume.clone()),
             nexuses: vec![],
             disown_all: false,
+            generation: 0,
         }
     }
     /// The replica is no longer part of the volume
@@ -283,6 +302,10 @@ pub struct DestroyReplica {
     pub name: Option<ReplicaName>,
     /// delete by owners
     pub disowners: ReplicaOwners,
+    /// Destroy a volume replica even if it leaves the volume with fewer healthy replicas than the
+    /// minimum replication factor
+    #[serde(default)]
+    pub override_redundancy_check: bool,
 }
 impl DestroyReplica {
     /// Return a new `Self` from the provided arguments
@@ -299,6 +322,7 @@ impl DestroyReplica {
             uuid: uuid.clone(),
             name: name.clone().into(),
             disowners: disowners.clone(),
+            override_redundancy_check: false,
         }
     }
     /// Disown all owners.
@@ -306,6 +330,11 @@ impl DestroyReplica {
         self.disowners.disown_all = true;
         self
     }
+    /// Destroy the replica even if its volume is left below the minimum replication factor.
+    pub fn with_override_redundancy_check(mut self) -> Self {
+        self.override_redundancy_check = true;
+        self
+    }
 }
 
 /// Share Replica Request
@@ -511,6 +540,9 @@ pub struct RemoveNexusReplica {
     pub nexus: NexusId,
     /// UUID and URI of the replica to be added
     pub replica: ReplicaUri,
+    /// Remove the replica even if it's the last healthy replica of the volume, which destroys
+    /// the volume data
+    pub force: bool,
 }
 impl RemoveNexusReplica {
     /// Return new `Self`
@@ -519,6 +551,7 @@ impl RemoveNexusReplica {
             node: node.clone(),
             nexus: nexus.clone(),
             replica: replica.clone(),
+            force: false,
         }
     }
 }
@@ -528,6 +561,7 @@ impl From<&RemoveNexusReplica> for RemoveNexusChild {
             node: rm.node.clone(),
             nexus: rm.nexus.clone(),
             uri: rm.replica.uri().clone(),
+            force: rm.force,
         }
     }
 }
@@ -2,7 +2,17 @@ use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
 
 use super::*;
-use crate::types::v0::store::{nexus, pool, replica, volume};
+use crate::{
+    transport_api::ResourceKind,
+    types::v0::store::{
+        defaults::ClusterDefaults,
+        nexus, pool,
+        quota::{VolumeQuota, VolumeQuotas},
+        reconciler::ReconcilerPass,
+        replica, volume, SpecStatus,
+    },
+    IntoOption,
+};
 
 /// Retrieve all specs from core agent
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
@@ -23,8 +33,707 @@ pub struct Specs {
     pub replicas: Vec<replica::ReplicaSpec>,
 }
 
+/// Clear the tombstone of a destroyed resource, allowing a resource with the same id to be
+/// created again straight away
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearTombstone {
+    /// kind of the destroyed resource
+    pub kind: ResourceKind,
+    /// id of the destroyed resource
+    pub id: String,
+}
+impl ClearTombstone {
+    /// Return new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: ResourceKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+        }
+    }
+}
+
+/// Apply a JSON merge patch (RFC 7386) to the persisted spec of a resource, to fix up specs
+/// which the control-plane cannot recover from by itself.
+/// The patch must first be tried with `dry_run`, whose result carries the `confirm` token which
+/// must then be given to apply it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchSpec {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+    /// the JSON merge patch
+    pub patch: serde_json::Value,
+    /// only return the patched spec, without applying it
+    pub dry_run: bool,
+    /// token returned by the dry run of the same patch
+    pub confirm: Option<String>,
+}
+impl PatchSpec {
+    /// Return new `Self` which tries the `patch` on the resource `id` of the given `kind`
+    pub fn dry_run(kind: ResourceKind, id: &str, patch: serde_json::Value) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            patch,
+            dry_run: true,
+            confirm: None,
+        }
+    }
+    /// Return new `Self` which applies the `patch` confirmed by a previous dry run
+    pub fn confirmed(
+        kind: ResourceKind,
+        id: &str,
+        patch: serde_json::Value,
+        confirm: &str,
+    ) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            patch,
+            dry_run: false,
+            confirm: Some(confirm.to_string()),
+        }
+    }
+}
+
+/// Result of a spec patch
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SpecPatch {
+    /// the spec before the patch
+    pub before: serde_json::Value,
+    /// the spec with the patch applied
+    pub after: serde_json::Value,
+    /// token which confirms that this patch is to be applied
+    pub confirm: String,
+    /// whether the patched spec was persisted
+    pub applied: bool,
+}
+
+/// Get the operation pending on the spec of a resource, if any
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPendingOperation {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+}
+impl GetPendingOperation {
+    /// Return new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: ResourceKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+        }
+    }
+}
+
+/// Clear the operation pending on the spec of a resource, when the control-plane is unable to
+/// recover it by itself, so that the resource is reconciled from a clean baseline.
+/// Unless `force`d, only an operation which is known to have failed is cleared, as any other may
+/// already have taken effect.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearPendingOperation {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+    /// clear the operation even if it may have taken effect
+    pub force: bool,
+}
+impl ClearPendingOperation {
+    /// Return new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: ResourceKind, id: &str, force: bool) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+            force,
+        }
+    }
+}
+
+/// Forcibly release the operation guard of a resource, which was left held by an operation
+/// abandoned without completing, so that further operations may proceed.
+/// The operation pending on the spec, if any, is left for the control-plane to verify.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ReleaseOperationGuard {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+}
+impl ReleaseOperationGuard {
+    /// Return new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: ResourceKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+        }
+    }
+}
+
+/// Retry replaying the operation pending on the spec of a resource, which the dirty spec
+/// reconciler gave up on as stuck after failing to replay it too many times.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryOperationReplay {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+}
+impl RetryOperationReplay {
+    /// Return new `Self` for the resource `id` of the given `kind`
+    pub fn new(kind: ResourceKind, id: &str) -> Self {
+        Self {
+            kind,
+            id: id.to_string(),
+        }
+    }
+}
+
+/// Get the operations pending on the specs of all the resources, including those which could not
+/// be replayed
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetPendingOperations {}
+
+/// The operation pending on the spec of a resource
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingOperation {
+    /// kind of the resource
+    pub kind: ResourceKind,
+    /// id of the resource
+    pub id: String,
+    /// status of the spec
+    pub status: SpecStatus<()>,
+    /// the pending operation, as it's persisted, if any
+    pub operation: Option<serde_json::Value>,
+    /// outcome of the pending operation, if known
+    pub result: Option<bool>,
+    /// whether an operation is currently in progress on the resource
+    pub busy: bool,
+    /// the failed attempts at replaying the pending operation, if any
+    pub replay: Option<OperationReplay>,
+}
+
+/// The failed attempts of the dirty spec reconciler at replaying the operation pending on a spec
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OperationReplay {
+    /// number of consecutive failed attempts
+    pub attempts: u32,
+    /// whether the replay was given up on, until it's retried through the admin API
+    pub stuck: bool,
+}
+
+/// The operations pending on the specs of all the resources
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingOperations {
+    /// the pending operations
+    pub operations: Vec<PendingOperation>,
+}
+impl PendingOperations {
+    /// Get the number of pending operations whose replay is stuck
+    pub fn stuck(&self) -> usize {
+        self.operations
+            .iter()
+            .filter(|op| op.replay.as_ref().map(|r| r.stuck).unwrap_or_default())
+            .count()
+    }
+}
+
+/// Reload the resources of all the nodes into the registry straight away, rather than waiting
+/// for the next cache period
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RefreshRegistry {}
+
+/// Result of the registry refresh of a single node
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRefresh {
+    /// id of the node
+    pub node: NodeId,
+    /// why the node's resources could not be reloaded, if they couldn't
+    pub error: Option<String>,
+}
+impl NodeRefresh {
+    /// Check if the node's resources were reloaded
+    pub fn refreshed(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Result of the registry refresh, for each node
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistryRefresh {
+    /// results of each node
+    pub nodes: Vec<NodeRefresh>,
+}
+
+/// Get the cluster-wide default parameters of the volumes
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetClusterDefaults {}
+
+/// Replace the cluster-wide default parameters of the volumes
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetClusterDefaults {
+    /// the new defaults
+    pub defaults: ClusterDefaults,
+}
+
+/// Get the per label quotas of the volumes, along with their usage
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeQuotas {}
+
+/// Replace the per label quotas of the volumes
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetVolumeQuotas {
+    /// the new quotas
+    pub quotas: VolumeQuotas,
+}
+
+/// Quota of the volumes tagged with a label along with the capacity which they provision
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeQuotaUsage {
+    /// the quota
+    pub quota: VolumeQuota,
+    /// capacity provisioned by the volumes tagged with the label of the quota, in bytes
+    pub used: u64,
+}
+
+/// Usage of all the volume quotas
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeQuotasUsage {
+    /// usage of each quota
+    pub quotas: Vec<VolumeQuotaUsage>,
+}
+
+impl From<VolumeQuotaUsage> for models::VolumeQuotaUsage {
+    fn from(src: VolumeQuotaUsage) -> Self {
+        Self::new(src.quota, src.used)
+    }
+}
+
+impl From<VolumeQuotasUsage> for models::VolumeQuotasUsage {
+    fn from(src: VolumeQuotasUsage) -> Self {
+        Self::new(src.quotas.into_iter().map(From::from).collect::<Vec<_>>())
+    }
+}
+
+/// Get the runtime state of the reconciler passes
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetReconcilerPasses {}
+
+/// Enable or disable a reconciler pass
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetReconcilerPass {
+    /// the reconciler pass
+    pub pass: ReconcilerPass,
+    /// whether the pass should run
+    pub enabled: bool,
+}
+
+/// Runtime state of a reconciler pass
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcilerPassState {
+    /// the reconciler pass
+    pub pass: ReconcilerPass,
+    /// whether the pass runs
+    pub enabled: bool,
+    /// number of runs of the pass skipped since the core agent started, as it was disabled
+    pub skipped: u64,
+}
+
+/// Runtime state of all the reconciler passes
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcilerPassStates {
+    /// state of each pass
+    pub passes: Vec<ReconcilerPassState>,
+}
+
+impl From<ReconcilerPassState> for models::ReconcilerPassState {
+    fn from(src: ReconcilerPassState) -> Self {
+        Self::new(src.enabled, src.pass.to_string(), src.skipped)
+    }
+}
+
+impl From<ReconcilerPassStates> for models::ReconcilerPassStates {
+    fn from(src: ReconcilerPassStates) -> Self {
+        Self::new(src.passes.into_iter().map(From::from).collect::<Vec<_>>())
+    }
+}
+
+/// Get the health of the persistent store endpoints
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStoreHealth {}
+
+/// Reachability of a persistent store endpoint, as seen by the store client
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreEndpointHealth {
+    /// url of the endpoint
+    pub endpoint: String,
+    /// whether the endpoint could be reached
+    pub reachable: bool,
+    /// why the endpoint could not be reached, if it couldn't
+    pub error: Option<String>,
+}
+
+/// Health of the persistent store endpoints
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreHealth {
+    /// the endpoint which the store operations are currently sent to
+    pub active: String,
+    /// health of each of the endpoints
+    pub endpoints: Vec<StoreEndpointHealth>,
+}
+
+/// Token bucket rate limit of the requests of each caller
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimit {
+    /// number of requests per second which the bucket of a caller is refilled with
+    pub rate: u32,
+    /// number of requests which a caller may make in a single burst
+    pub burst: u32,
+}
+impl RateLimit {
+    /// Return new `Self` with the given `rate`, allowing bursts of up to `burst` requests,
+    /// or `rate` requests if not specified
+    pub fn new(rate: std::num::NonZeroU32, burst: Option<std::num::NonZeroU32>) -> Self {
+        Self {
+            rate: rate.get(),
+            burst: burst.unwrap_or(rate).get(),
+        }
+    }
+}
+
+/// Class of the gRPC methods which share a rate limit
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash, Ord, PartialOrd)]
+#[serde(rename_all = "camelCase")]
+pub enum MethodClass {
+    /// methods which create, modify or delete resources
+    Mutation,
+    /// methods which only get resources, or validate requests without acting on them
+    Read,
+}
+impl MethodClass {
+    /// Get the class of the gRPC method `name`, eg: `CreateVolume`.
+    pub fn of(name: &str) -> Self {
+        if name.starts_with("Get") || name.starts_with("Validate") || name == "Probe" {
+            Self::Read
+        } else {
+            Self::Mutation
+        }
+    }
+}
+impl std::fmt::Display for MethodClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mutation => write!(f, "mutation"),
+            Self::Read => write!(f, "read"),
+        }
+    }
+}
+
+/// Number of the requests of a caller which were rejected by the rate limiter
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CallerThrottles {
+    /// identity of the caller: its tenant, or its address if it has none
+    pub identity: String,
+    /// class of the rejected requests
+    pub class: MethodClass,
+    /// number of requests rejected since the core agent started
+    pub throttled: u64,
+}
+
+/// Get the rate limits of the gRPC requests, along with the callers which were throttled
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetRateLimits {}
+
+/// Rate limits of the gRPC requests, along with the callers which were throttled
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateLimits {
+    /// rate limit of the mutations, if any
+    pub mutations: Option<RateLimit>,
+    /// rate limit of the reads, if any
+    pub reads: Option<RateLimit>,
+    /// the throttled requests, per caller and class
+    pub throttled: Vec<CallerThrottles>,
+}
+
+/// Get the report of the replicas and nexuses which only exist on one side: as a spec or as a
+/// dataplane object
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetOrphans {}
+
+/// The side on which an orphan is left
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum OrphanCategory {
+    /// the spec exists but the dataplane object is missing
+    ControlPlane,
+    /// the dataplane object exists but it has no spec
+    Dataplane,
+}
+
+/// The remediation suggested for an orphan
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum OrphanRemediation {
+    /// recreate the dataplane object from its spec
+    Recreate,
+    /// create a spec for the dataplane object
+    Adopt,
+    /// destroy the spec, or the dataplane object, which is left over
+    Destroy,
+    /// leave it alone, as it's not ours to manage or it must be looked at by hand
+    Ignore,
+}
+
+/// A replica or a nexus which only exists on one side
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Orphan {
+    /// id of the replica or nexus
+    pub id: String,
+    /// the side on which it's left
+    pub category: OrphanCategory,
+    /// the node where it lives, or should live
+    pub node: Option<NodeId>,
+    /// the pool where it lives, or should live, if a replica
+    pub pool: Option<PoolId>,
+    /// seconds since its spec was created, or since it was first reported when it has no spec
+    pub age_secs: Option<u64>,
+    /// the suggested remediation
+    pub remediation: OrphanRemediation,
+    /// why the remediation is suggested
+    pub reason: String,
+}
+
+/// The orphans of a kind of resource, per category
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceOrphans {
+    /// the specs whose dataplane object is missing
+    pub control_plane: Vec<Orphan>,
+    /// the dataplane objects which have no spec
+    pub dataplane: Vec<Orphan>,
+}
+impl ResourceOrphans {
+    /// Return new `Self` with the given `orphans`, split by category
+    pub fn new(orphans: Vec<Orphan>) -> Self {
+        let (control_plane, dataplane) = orphans
+            .into_iter()
+            .partition(|orphan| orphan.category == OrphanCategory::ControlPlane);
+        Self {
+            control_plane,
+            dataplane,
+        }
+    }
+    /// Get all the orphans, of both categories
+    pub fn iter(&self) -> impl Iterator<Item = &Orphan> {
+        self.control_plane.iter().chain(self.dataplane.iter())
+    }
+}
+
+/// Report of the replicas and nexuses which only exist on one side, per kind of resource
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanReport {
+    /// the orphaned replicas
+    pub replicas: ResourceOrphans,
+    /// the orphaned nexuses
+    pub nexuses: ResourceOrphans,
+}
+
 impl From<Specs> for models::Specs {
     fn from(src: Specs) -> Self {
         Self::new(src.nexuses, src.pools, src.replicas, src.volumes)
     }
 }
+
+impl From<NodeRefresh> for models::NodeRefresh {
+    fn from(src: NodeRefresh) -> Self {
+        Self::new_all(src.node, src.error)
+    }
+}
+
+impl From<RegistryRefresh> for models::RegistryRefresh {
+    fn from(src: RegistryRefresh) -> Self {
+        Self::new(src.nodes.into_iter().map(From::from).collect::<Vec<_>>())
+    }
+}
+
+impl From<StoreEndpointHealth> for models::StoreEndpointHealth {
+    fn from(src: StoreEndpointHealth) -> Self {
+        Self::new_all(src.endpoint, src.reachable, src.error)
+    }
+}
+
+impl From<StoreHealth> for models::StoreHealth {
+    fn from(src: StoreHealth) -> Self {
+        Self::new(
+            src.active,
+            src.endpoints
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl From<SpecPatch> for models::SpecPatch {
+    fn from(src: SpecPatch) -> Self {
+        Self::new(src.after, src.applied, src.before, src.confirm)
+    }
+}
+
+impl From<PendingOperation> for models::PendingOperation {
+    fn from(src: PendingOperation) -> Self {
+        Self::new_all(
+            src.busy,
+            src.operation,
+            src.result,
+            src.status,
+            src.kind.to_string(),
+            src.id,
+            src.replay.map(From::from),
+        )
+    }
+}
+
+impl From<OperationReplay> for models::OperationReplay {
+    fn from(src: OperationReplay) -> Self {
+        Self::new(src.attempts, src.stuck)
+    }
+}
+
+impl From<PendingOperations> for models::PendingOperations {
+    fn from(src: PendingOperations) -> Self {
+        let stuck = src.stuck();
+        Self::new(
+            src.operations
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+            stuck as u64,
+        )
+    }
+}
+
+impl From<RateLimit> for models::RateLimit {
+    fn from(src: RateLimit) -> Self {
+        Self::new(src.burst, src.rate)
+    }
+}
+
+impl From<MethodClass> for models::MethodClass {
+    fn from(src: MethodClass) -> Self {
+        match src {
+            MethodClass::Mutation => Self::Mutation,
+            MethodClass::Read => Self::Read,
+        }
+    }
+}
+
+impl From<CallerThrottles> for models::CallerThrottles {
+    fn from(src: CallerThrottles) -> Self {
+        Self::new(src.class, src.identity, src.throttled)
+    }
+}
+
+impl From<RateLimits> for models::RateLimits {
+    fn from(src: RateLimits) -> Self {
+        Self::new_all(
+            src.mutations.map(From::from),
+            src.reads.map(From::from),
+            src.throttled
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl From<OrphanCategory> for models::OrphanCategory {
+    fn from(src: OrphanCategory) -> Self {
+        match src {
+            OrphanCategory::ControlPlane => Self::ControlPlane,
+            OrphanCategory::Dataplane => Self::Dataplane,
+        }
+    }
+}
+
+impl From<OrphanRemediation> for models::OrphanRemediation {
+    fn from(src: OrphanRemediation) -> Self {
+        match src {
+            OrphanRemediation::Recreate => Self::Recreate,
+            OrphanRemediation::Adopt => Self::Adopt,
+            OrphanRemediation::Destroy => Self::Destroy,
+            OrphanRemediation::Ignore => Self::Ignore,
+        }
+    }
+}
+
+impl From<Orphan> for models::Orphan {
+    fn from(src: Orphan) -> Self {
+        Self::new_all(
+            src.age_secs,
+            src.category,
+            src.id,
+            src.node.into_opt(),
+            src.pool.into_opt(),
+            src.reason,
+            src.remediation,
+        )
+    }
+}
+
+impl From<ResourceOrphans> for models::ResourceOrphans {
+    fn from(src: ResourceOrphans) -> Self {
+        Self::new(
+            src.control_plane
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+            src.dataplane
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+impl From<OrphanReport> for models::OrphanReport {
+    fn from(src: OrphanReport) -> Self {
+        Self::new(src.nexuses, src.replicas)
+    }
+}
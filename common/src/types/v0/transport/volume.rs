@@ -1,8 +1,16 @@
 use super::*;
 
-use crate::{types::v0::store::volume::VolumeSpec, IntoOption};
+use crate::{
+    types::v0::store::{defaults::ClusterDefaults, epoch_secs, volume::VolumeSpec},
+    IntoOption,
+};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, convert::TryFrom, fmt::Debug};
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fmt::Debug,
+    time::{Duration, SystemTime},
+};
 
 bus_impl_string_uuid!(VolumeId, "UUID of a volume");
 
@@ -16,12 +24,29 @@ pub struct Volume {
     spec: VolumeSpec,
     /// Runtime state of the volume.
     state: VolumeState,
+    /// Conditions of the volume, in the format of the kubernetes conditions.
+    #[serde(default)]
+    conditions: Vec<ResourceCondition>,
 }
 
 impl Volume {
     /// Construct a new volume.
     pub fn new(spec: VolumeSpec, state: VolumeState) -> Self {
-        Self { spec, state }
+        Self {
+            spec,
+            state,
+            conditions: vec![],
+        }
+    }
+    /// Set the conditions of the volume.
+    #[must_use]
+    pub fn with_conditions(mut self, conditions: Vec<ResourceCondition>) -> Self {
+        self.conditions = conditions;
+        self
+    }
+    /// Get the conditions of the volume.
+    pub fn conditions(&self) -> &Vec<ResourceCondition> {
+        &self.conditions
     }
 
     /// Get the volume spec.
@@ -47,7 +72,11 @@ impl Volume {
 
 impl From<Volume> for models::Volume {
     fn from(volume: Volume) -> Self {
-        models::Volume::new_all(volume.spec(), volume.state())
+        models::Volume::new_all(
+            volume.spec(),
+            volume.state(),
+            Some(volume.conditions.into_iter().map(From::from).collect()),
+        )
     }
 }
 
@@ -65,6 +94,12 @@ pub struct VolumeState {
     pub target: Option<Nexus>,
     /// replica topology information
     pub replica_topology: HashMap<ReplicaId, ReplicaTopology>,
+    /// where the runtime state diverges from the spec
+    #[serde(default)]
+    pub conditions: Vec<VolumeCondition>,
+    /// the IO of the volume is frozen until it's thawed, or until this deadline at the latest
+    #[serde(default)]
+    pub frozen_until: Option<SystemTime>,
 }
 
 impl From<VolumeState> for models::VolumeState {
@@ -79,10 +114,66 @@ impl From<VolumeState> for models::VolumeState {
                 .iter()
                 .map(|(k, v)| (k.into(), v.into()))
                 .collect(),
+            conditions: Some(volume.conditions.into_iter().map(From::from).collect()),
+            frozen: volume.frozen_until.is_some(),
+            thaw_deadline: volume.frozen_until.map(epoch_secs),
         }
     }
 }
 
+/// Divergence of the runtime state of a volume from its spec.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    strum_macros::ToString,
+)]
+pub enum VolumeCondition {
+    /// the volume is published but its target nexus does not exist on any node
+    TargetMissing,
+    /// a replica of the volume does not exist on any node
+    ReplicaMissing,
+    /// an operation on the volume or its target did not complete, leaving the spec dirty
+    SpecDirty,
+    /// the volume is missing replicas which can't be placed as per its topology
+    TopologyUnsatisfiable,
+    /// a replica of the volume was placed regardless of its topology
+    TopologyRelaxed,
+    /// a child of the volume target is backed by a replica smaller than the target
+    ChildSizeMismatch,
+    /// the operation pending on the volume or its target could not be replayed, and was given up
+    /// on until it's retried through the admin API
+    ReplayStuck,
+}
+
+impl From<VolumeCondition> for models::VolumeCondition {
+    fn from(src: VolumeCondition) -> Self {
+        match src {
+            VolumeCondition::TargetMissing => Self::TargetMissing,
+            VolumeCondition::ReplicaMissing => Self::ReplicaMissing,
+            VolumeCondition::SpecDirty => Self::SpecDirty,
+            VolumeCondition::TopologyUnsatisfiable => Self::TopologyUnsatisfiable,
+            VolumeCondition::TopologyRelaxed => Self::TopologyRelaxed,
+            VolumeCondition::ChildSizeMismatch => Self::ChildSizeMismatch,
+            VolumeCondition::ReplayStuck => Self::ReplayStuck,
+        }
+    }
+}
+
+impl VolumeState {
+    /// Check if the volume has the given `condition`
+    pub fn has_condition(&self, condition: VolumeCondition) -> bool {
+        self.conditions.contains(&condition)
+    }
+}
+
 impl VolumeState {
     /// Get the target node if the volume is published
     pub fn target_node(&self) -> Option<Option<NodeId>> {
@@ -98,6 +189,22 @@ impl VolumeState {
                 .unwrap_or_default(),
         }
     }
+    /// Get the number of healthy replicas, ie the online target children if the volume is
+    /// published, otherwise the online replicas
+    pub fn healthy_replicas(&self) -> usize {
+        match &self.target {
+            Some(target) => target
+                .children
+                .iter()
+                .filter(|child| child.state == ChildState::Online)
+                .count(),
+            None => self
+                .replica_topology
+                .values()
+                .filter(|replica| replica.status().online())
+                .count(),
+        }
+    }
 }
 
 impl From<(&VolumeId, &Nexus)> for VolumeState {
@@ -110,6 +217,8 @@ impl From<(&VolumeId, &Nexus)> for VolumeState {
             status: nexus.status.clone(),
             target: Some(nexus.clone()),
             replica_topology: HashMap::new(),
+            conditions: vec![],
+            frozen_until: None,
         }
     }
 }
@@ -180,6 +289,12 @@ impl From<LabelledTopology> for models::LabelledTopology {
 pub struct Topology {
     pub node: Option<NodeTopology>,
     pub pool: Option<PoolTopology>,
+    /// replicas are not placed on pools which hold replicas of any of these volumes
+    #[serde(default)]
+    pub anti_colocation: Vec<VolumeId>,
+    /// label key whose values the replicas are spread across, eg: the zone of their pools
+    #[serde(default)]
+    pub spread: Option<String>,
 }
 impl Topology {
     /// Get a reference to the explicit topology
@@ -189,7 +304,16 @@ impl Topology {
 }
 impl From<Topology> for models::Topology {
     fn from(src: Topology) -> Self {
-        Self::new_all(src.node.into_opt(), src.pool.into_opt())
+        let anti_colocation = match src.anti_colocation.is_empty() {
+            true => None,
+            false => Some(src.anti_colocation.into_iter().map(From::from).collect()),
+        };
+        Self::new_all(
+            src.node.into_opt(),
+            src.pool.into_opt(),
+            anti_colocation,
+            src.spread,
+        )
     }
 }
 impl From<models::Topology> for Topology {
@@ -197,6 +321,13 @@ impl From<models::Topology> for Topology {
         Self {
             node: src.node_topology.into_opt(),
             pool: src.pool_topology.into_opt(),
+            anti_colocation: src
+                .anti_colocation
+                .unwrap_or_default()
+                .into_iter()
+                .map(From::from)
+                .collect(),
+            spread: src.spread,
         }
     }
 }
@@ -306,11 +437,30 @@ pub struct VolumePolicy {
     /// the server will attempt to heal the volume by itself
     /// the client should not attempt to do the same if this is enabled
     pub self_heal: bool,
+    /// minimum number of healthy replicas required for the volume to be available for IO
+    /// when the volume drops below it, the target is made unavailable until the quorum returns
+    /// (which is only enforced for self healing volumes)
+    #[serde(default)]
+    pub quorum: Option<u8>,
 }
 
 impl Default for VolumePolicy {
     fn default() -> Self {
-        Self { self_heal: true }
+        Self {
+            self_heal: true,
+            quorum: None,
+        }
+    }
+}
+
+impl VolumePolicy {
+    /// Check if the given number of `healthy` replicas is below the quorum, if any.
+    /// The quorum is only enforced for self healing volumes.
+    pub fn below_quorum(&self, healthy: usize) -> bool {
+        match self.quorum {
+            Some(quorum) if self.self_heal => healthy < quorum as usize,
+            _ => false,
+        }
     }
 }
 
@@ -318,12 +468,13 @@ impl From<models::VolumePolicy> for VolumePolicy {
     fn from(src: models::VolumePolicy) -> Self {
         Self {
             self_heal: src.self_heal,
+            quorum: src.quorum,
         }
     }
 }
 impl From<VolumePolicy> for models::VolumePolicy {
     fn from(src: VolumePolicy) -> Self {
-        Self::new_all(src.self_heal)
+        Self::new_all(src.self_heal, src.quorum)
     }
 }
 
@@ -343,6 +494,386 @@ impl GetVolumes {
     }
 }
 
+/// Get the status of many volumes in a single request
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumesStatus {
+    /// uuids of the volumes, or all the volumes if empty
+    pub volumes: Vec<VolumeId>,
+}
+impl GetVolumesStatus {
+    /// Return new `Self` to retrieve the status of the specified volumes
+    pub fn new(volumes: Vec<VolumeId>) -> Self {
+        Self { volumes }
+    }
+}
+
+/// Status of a single volume of a `GetVolumesStatus` request
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeStatusEntry {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// current status of the volume, None if the volume does not exist
+    pub status: Option<VolumeStatus>,
+}
+impl VolumeStatusEntry {
+    /// Check if the volume exists
+    pub fn found(&self) -> bool {
+        self.status.is_some()
+    }
+}
+
+/// Status of each volume of a `GetVolumesStatus` request, in the order requested
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumesStatus {
+    /// status of each volume
+    pub entries: Vec<VolumeStatusEntry>,
+}
+
+impl From<VolumeStatusEntry> for models::VolumeStatusEntry {
+    fn from(src: VolumeStatusEntry) -> Self {
+        Self::new_all(src.uuid, src.status.into_opt())
+    }
+}
+
+impl From<VolumesStatus> for models::VolumesStatus {
+    fn from(src: VolumesStatus) -> Self {
+        Self::new(src.entries.into_iter().map(From::from).collect::<Vec<_>>())
+    }
+}
+
+/// Rule of the volume creation requests which a request violates
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeViolation {
+    /// path of the field of the request which violates the rule, eg: topology
+    pub field: String,
+    /// why the field violates the rule
+    pub message: String,
+}
+
+/// Violations of the rules of the volume creation requests by a request, which is valid if there
+/// are none
+#[derive(Serialize, Deserialize, Default, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeViolations {
+    /// the violations, in the order in which the rules are checked
+    pub violations: Vec<VolumeViolation>,
+}
+
+impl From<VolumeViolation> for models::VolumeViolation {
+    fn from(src: VolumeViolation) -> Self {
+        Self::new(src.field, src.message)
+    }
+}
+
+impl From<VolumeViolations> for models::VolumeViolations {
+    fn from(src: VolumeViolations) -> Self {
+        Self::new(
+            src.violations
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+        )
+    }
+}
+
+/// Get the topology tree of a volume, from its targets down to the nodes of its replicas
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeTopology {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+}
+impl GetVolumeTopology {
+    /// Return new `Self` to retrieve the topology of the given volume
+    pub fn new(uuid: &VolumeId) -> Self {
+        Self { uuid: uuid.clone() }
+    }
+}
+
+/// Topology tree of a volume
+/// The components on nodes which are not online carry the last state reported by their node,
+/// and are flagged as `last_known`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTopologyTree {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// status of the volume
+    pub status: VolumeStatus,
+    /// the target nexuses of the volume
+    pub targets: Vec<NexusTopologyTree>,
+    /// the replicas of the volume which are not a child of any of its targets
+    pub replicas: Vec<ReplicaTopologyTree>,
+}
+
+/// A nexus of a volume topology tree
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NexusTopologyTree {
+    /// uuid of the nexus
+    pub uuid: NexusId,
+    /// status of the nexus, unknown if it was never reported
+    pub status: NexusStatus,
+    /// whether the status is the last known one, as the node is not online
+    pub last_known: bool,
+    /// the node of the nexus
+    pub node: NodeTopologyTree,
+    /// the children of the nexus
+    pub children: Vec<ChildTopologyTree>,
+}
+
+/// A nexus child of a volume topology tree
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChildTopologyTree {
+    /// uri of the child
+    pub uri: ChildUri,
+    /// state of the child, unknown if it was never reported
+    pub state: ChildState,
+    /// the replica backing the child, if it's a replica of the volume
+    pub replica: Option<ReplicaTopologyTree>,
+}
+
+/// A replica of a volume topology tree
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplicaTopologyTree {
+    /// uuid of the replica
+    pub uuid: ReplicaId,
+    /// status of the replica, unknown if it was never reported
+    pub status: ReplicaStatus,
+    /// whether the status is the last known one, as the node is not online
+    pub last_known: bool,
+    /// the pool of the replica
+    pub pool: PoolTopologyTree,
+}
+
+/// A pool of a volume topology tree
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolTopologyTree {
+    /// id of the pool
+    pub id: PoolId,
+    /// status of the pool, unknown if it was never reported
+    pub status: PoolStatus,
+    /// whether the status is the last known one, as the node is not online
+    pub last_known: bool,
+    /// the node of the pool
+    pub node: NodeTopologyTree,
+}
+
+/// A node of a volume topology tree
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeTopologyTree {
+    /// id of the node
+    pub id: NodeId,
+    /// status of the node
+    pub status: NodeStatus,
+}
+
+/// Get the revisions of a volume spec which are retained in its history
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeHistory {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+}
+impl GetVolumeHistory {
+    /// Return new `Self` to retrieve the history of the given volume
+    pub fn new(uuid: &VolumeId) -> Self {
+        Self { uuid: uuid.clone() }
+    }
+}
+
+/// Get the changes made to a volume spec by one of its revisions
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeRevisionDiff {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// number of the revision
+    pub revision: u64,
+}
+impl GetVolumeRevisionDiff {
+    /// Return new `Self` to retrieve the diff of the `revision` of the given volume
+    pub fn new(uuid: &VolumeId, revision: u64) -> Self {
+        Self {
+            uuid: uuid.clone(),
+            revision,
+        }
+    }
+}
+
+/// A revision of a volume spec
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeRevision {
+    /// number of the revision, incremented on each revision of the spec
+    pub revision: u64,
+    /// when the spec was persisted
+    pub updated_at: SystemTime,
+    /// the operation which resulted in the revision, as it's persisted
+    pub operation: Option<serde_json::Value>,
+}
+
+/// The revisions of a volume spec which are retained in its history, the oldest first
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeHistory {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// the retained revisions
+    pub revisions: Vec<VolumeRevision>,
+}
+
+/// The changes made to a volume spec by one of its revisions
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeRevisionDiff {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// number of the revision
+    pub revision: u64,
+    /// number of the revision which the diff is against, if it's retained, otherwise the diff is
+    /// against an empty spec
+    pub previous: Option<u64>,
+    /// the changes, as a JSON patch
+    pub diff: serde_json::Value,
+}
+
+impl From<VolumeRevision> for models::VolumeRevision {
+    fn from(src: VolumeRevision) -> Self {
+        Self::new_all(src.operation, src.revision, epoch_secs(src.updated_at))
+    }
+}
+
+impl From<VolumeHistory> for models::VolumeHistory {
+    fn from(src: VolumeHistory) -> Self {
+        Self::new(
+            src.revisions
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+            src.uuid,
+        )
+    }
+}
+
+impl From<VolumeRevisionDiff> for models::VolumeRevisionDiff {
+    fn from(src: VolumeRevisionDiff) -> Self {
+        Self::new_all(src.diff, src.previous, src.revision, src.uuid)
+    }
+}
+
+/// Get the scheduling traces of the latest volume creations
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct GetSchedulingTraces {
+    /// only get the traces of this volume, if specified
+    pub volume: Option<VolumeId>,
+}
+impl GetSchedulingTraces {
+    /// Return new `Self` to retrieve the traces of the given volume, or of all volumes if `None`
+    pub fn new(volume: Option<&VolumeId>) -> Self {
+        Self {
+            volume: volume.cloned(),
+        }
+    }
+}
+
+/// Decision of the scheduler about a pool which may host a replica
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolCandidate {
+    /// id of the pool
+    pub pool: PoolId,
+    /// id of the node of the pool
+    pub node: NodeId,
+    /// the filter which rejected the pool, if any
+    pub rejected_by: Option<String>,
+}
+
+/// A pass of the scheduler over the pools, which filters and then sorts the candidate pools
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulingAttempt {
+    /// the filters, in the order in which they were applied
+    pub filters: Vec<String>,
+    /// the sorters, in the order in which they were applied
+    pub sorters: Vec<String>,
+    /// all the pools which were considered, along with the decision made about them
+    pub candidates: Vec<PoolCandidate>,
+    /// the pools which were not rejected, in order of preference
+    pub order: Vec<PoolId>,
+}
+
+/// Trace of the scheduling of the replicas of a volume on its creation
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulingTrace {
+    /// uuid of the volume
+    pub volume: VolumeId,
+    /// when the scheduling started
+    pub started_at: SystemTime,
+    /// the passes of the scheduler, more than one if the placement constraints were relaxed
+    pub attempts: Vec<SchedulingAttempt>,
+    /// the error, if the replicas could not be scheduled
+    pub error: Option<String>,
+}
+
+/// The scheduling traces of the latest volume creations, the oldest first
+#[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulingTraces {
+    /// the retained traces
+    pub traces: Vec<SchedulingTrace>,
+}
+
+impl From<PoolCandidate> for models::PoolCandidate {
+    fn from(src: PoolCandidate) -> Self {
+        Self::new_all(src.node, src.pool, src.rejected_by)
+    }
+}
+
+impl From<SchedulingAttempt> for models::SchedulingAttempt {
+    fn from(src: SchedulingAttempt) -> Self {
+        Self::new(
+            src.candidates
+                .into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>(),
+            src.filters,
+            src.order
+                .into_iter()
+                .map(|p| p.to_string())
+                .collect::<Vec<_>>(),
+            src.sorters,
+        )
+    }
+}
+
+impl From<SchedulingTrace> for models::SchedulingTrace {
+    fn from(src: SchedulingTrace) -> Self {
+        Self::new_all(
+            src.attempts.into_iter().map(From::from).collect::<Vec<_>>(),
+            src.error,
+            epoch_secs(src.started_at),
+            src.volume,
+        )
+    }
+}
+
+impl From<SchedulingTraces> for models::SchedulingTraces {
+    fn from(src: SchedulingTraces) -> Self {
+        Self::new(src.traces.into_iter().map(From::from).collect::<Vec<_>>())
+    }
+}
+
 /// Create volume
 #[derive(Serialize, Deserialize, Default, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -361,12 +892,152 @@ pub struct CreateVolume {
     pub labels: Option<VolumeLabels>,
     /// flag indicating whether the volume should be thin provisioned
     pub thin: bool,
+    /// what to do when not all of the replicas can be placed
+    #[serde(default)]
+    pub placement: VolumePlacementPolicy,
+    /// pools explicitly selected to host the replicas, bypassing the scheduler
+    /// any replicas in excess of the selected pools are placed by the scheduler
+    #[serde(default)]
+    pub pools: Vec<PoolId>,
+    /// allow more than one of the explicitly selected pools to be on the same node
+    #[serde(default)]
+    pub allow_same_node: bool,
+    /// parameters which the caller left unset, to be taken from the cluster defaults
+    #[serde(default)]
+    pub defaults: Vec<VolumeDefault>,
+    /// size requested by the caller, before it was rounded up to the effective `size`
+    #[serde(default)]
+    pub requested_size: Option<u64>,
+    /// priority of the rebuilds of the volume replicas over those of other volumes
+    #[serde(default)]
+    pub rebuild_priority: RebuildPriority,
+}
+
+/// Volume creation parameter which may be left unset and taken from the cluster defaults
+#[derive(
+    Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq, Hash, strum_macros::ToString,
+)]
+pub enum VolumeDefault {
+    /// number of storage replicas
+    Replicas,
+    /// thin provisioning flag
+    Thin,
+    /// placement policy
+    Placement,
+}
+
+impl From<VolumeDefault> for models::VolumeDefault {
+    fn from(src: VolumeDefault) -> Self {
+        match src {
+            VolumeDefault::Replicas => Self::Replicas,
+            VolumeDefault::Thin => Self::Thin,
+            VolumeDefault::Placement => Self::Placement,
+        }
+    }
+}
+
+/// Placement policy used when creating the volume replicas
+#[derive(Serialize, Deserialize, Debug, Copy, Clone, Eq, PartialEq)]
+pub enum VolumePlacementPolicy {
+    /// the creation fails if not all of the requested replicas can be placed
+    Strict,
+    /// the volume is created with as many replicas as can be placed (at least one) and is left
+    /// degraded until the reconcilers create the remaining replicas
+    BestEffort,
+    /// the placement constraints of the topology are relaxed one at a time, until all of the
+    /// requested replicas can be placed, with the replicas' nodes still kept apart
+    Relaxed,
+}
+
+impl Default for VolumePlacementPolicy {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+impl From<models::VolumePlacementPolicy> for VolumePlacementPolicy {
+    fn from(src: models::VolumePlacementPolicy) -> Self {
+        match src {
+            models::VolumePlacementPolicy::Strict => Self::Strict,
+            models::VolumePlacementPolicy::BestEffort => Self::BestEffort,
+            models::VolumePlacementPolicy::Relaxed => Self::Relaxed,
+        }
+    }
+}
+impl From<VolumePlacementPolicy> for models::VolumePlacementPolicy {
+    fn from(src: VolumePlacementPolicy) -> Self {
+        match src {
+            VolumePlacementPolicy::Strict => Self::Strict,
+            VolumePlacementPolicy::BestEffort => Self::BestEffort,
+            VolumePlacementPolicy::Relaxed => Self::Relaxed,
+        }
+    }
+}
+
+/// Priority of the rebuilds of the replicas of a volume, when the rebuilds are throttled.
+/// The variants are ordered from the lowest priority to the highest.
+#[derive(
+    Serialize,
+    Deserialize,
+    Debug,
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    strum_macros::ToString,
+)]
+pub enum RebuildPriority {
+    /// rebuilt only once the other volumes are, eg: scratch volumes
+    Low,
+    /// the priority of the volumes which don't specify any
+    Normal,
+    /// rebuilt before the other volumes, eg: production databases
+    High,
+}
+
+impl Default for RebuildPriority {
+    fn default() -> Self {
+        Self::Normal
+    }
+}
+
+impl From<models::RebuildPriority> for RebuildPriority {
+    fn from(src: models::RebuildPriority) -> Self {
+        match src {
+            models::RebuildPriority::High => Self::High,
+            models::RebuildPriority::Normal => Self::Normal,
+            models::RebuildPriority::Low => Self::Low,
+        }
+    }
+}
+impl From<RebuildPriority> for models::RebuildPriority {
+    fn from(src: RebuildPriority) -> Self {
+        match src {
+            RebuildPriority::High => Self::High,
+            RebuildPriority::Normal => Self::Normal,
+            RebuildPriority::Low => Self::Low,
+        }
+    }
 }
 
 /// Volume label information
 pub type VolumeLabels = HashMap<String, String>;
 
 impl CreateVolume {
+    /// Fill in the parameters left unset with the given cluster `defaults`
+    pub fn with_defaults(mut self, defaults: &ClusterDefaults) -> Self {
+        for default in &self.defaults {
+            match default {
+                VolumeDefault::Replicas => self.replicas = defaults.replicas,
+                VolumeDefault::Thin => self.thin = defaults.thin,
+                VolumeDefault::Placement => self.placement = defaults.placement,
+            }
+        }
+        self
+    }
     /// explicitly selected allowed_nodes
     pub fn allowed_nodes(&self) -> Vec<NodeId> {
         match &self.topology {
@@ -411,6 +1082,10 @@ pub struct PublishVolume {
     pub target_node: Option<NodeId>,
     /// share protocol
     pub share: Option<VolumeShareProtocol>,
+    /// NVMe persistent reservation to be held by the target on the replicas
+    /// if not specified, the reservation of a previous publish is used
+    #[serde(default)]
+    pub reservation: Option<NvmeReservation>,
 }
 impl PublishVolume {
     /// Create new `PublishVolume` based on the provided arguments
@@ -423,8 +1098,15 @@ impl PublishVolume {
             uuid,
             target_node,
             share,
+            reservation: None,
         }
     }
+    /// Use the given NVMe persistent reservation for the target
+    #[must_use]
+    pub fn with_reservation(mut self, reservation: NvmeReservation) -> Self {
+        self.reservation = Some(reservation);
+        self
+    }
 }
 
 /// Unpublish a volume from any node where it may be published
@@ -438,6 +1120,10 @@ pub struct UnpublishVolume {
     /// the nexus. Note: this option should be used only when we know the node will not become
     /// accessible again and it is safe to do so.
     force: bool,
+    /// release the NVMe reservation of the volume, so that it may be published again with
+    /// another reservation key
+    #[serde(default)]
+    release_reservation: bool,
 }
 impl UnpublishVolume {
     /// Create a new `UnpublishVolume` for the given uuid
@@ -445,12 +1131,22 @@ impl UnpublishVolume {
         Self {
             uuid: uuid.clone(),
             force,
+            release_reservation: false,
         }
     }
+    /// Release the NVMe reservation of the volume once it's unpublished
+    pub fn with_release_reservation(mut self, release_reservation: bool) -> Self {
+        self.release_reservation = release_reservation;
+        self
+    }
     /// It's a force `Self`
     pub fn force(&self) -> bool {
         self.force
     }
+    /// The NVMe reservation of the volume is released
+    pub fn release_reservation(&self) -> bool {
+        self.release_reservation
+    }
 }
 
 /// Share Volume request
@@ -490,11 +1186,112 @@ pub struct SetVolumeReplica {
     pub uuid: VolumeId,
     /// replica count
     pub replicas: u8,
+    /// Decrease the replica count even if it leaves the volume with fewer healthy replicas than
+    /// the minimum replication factor
+    #[serde(default)]
+    pub override_redundancy_check: bool,
 }
 impl SetVolumeReplica {
     /// Create new `Self` based on the provided arguments
     pub fn new(uuid: VolumeId, replicas: u8) -> Self {
-        Self { uuid, replicas }
+        Self {
+            uuid,
+            replicas,
+            override_redundancy_check: false,
+        }
+    }
+}
+
+/// Designate a replica as the source of truth of the volume data
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SetVolumeSourceReplica {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// uuid of the replica which the other replicas are rebuilt from
+    pub replica: ReplicaId,
+}
+impl SetVolumeSourceReplica {
+    /// Create new `Self` based on the provided arguments
+    pub fn new(uuid: VolumeId, replica: ReplicaId) -> Self {
+        Self { uuid, replica }
+    }
+}
+
+/// Update the mutable parameters of a volume, leaving those which are not specified unchanged
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PatchVolume {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// priority of the rebuilds of the volume replicas
+    pub rebuild_priority: Option<RebuildPriority>,
+}
+impl PatchVolume {
+    /// Create new `Self` based on the provided arguments
+    pub fn new(uuid: VolumeId, rebuild_priority: Option<RebuildPriority>) -> Self {
+        Self {
+            uuid,
+            rebuild_priority,
+        }
+    }
+}
+
+/// Freeze the IO of a published volume, eg: whilst the host takes a consistent snapshot of it
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FreezeVolume {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+    /// the IO is thawed by the control plane once this has elapsed, if not thawed before
+    pub timeout: Duration,
+}
+impl FreezeVolume {
+    /// Create new `Self` based on the provided arguments
+    pub fn new(uuid: VolumeId, timeout: Duration) -> Self {
+        Self { uuid, timeout }
+    }
+}
+
+/// Thaw the frozen IO of a volume
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ThawVolume {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+}
+impl ThawVolume {
+    /// Create new `Self` to thaw the specified volume
+    pub fn new(uuid: VolumeId) -> Self {
+        Self { uuid }
+    }
+}
+
+/// Pause the rebuilds of the volume replicas, eg: whilst they'd compete with the application IO
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PauseVolumeRebuild {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+}
+impl PauseVolumeRebuild {
+    /// Create new `Self` to pause the rebuilds of the specified volume
+    pub fn new(uuid: VolumeId) -> Self {
+        Self { uuid }
+    }
+}
+
+/// Resume the paused rebuilds of the volume replicas
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResumeVolumeRebuild {
+    /// uuid of the volume
+    pub uuid: VolumeId,
+}
+impl ResumeVolumeRebuild {
+    /// Create new `Self` to resume the rebuilds of the specified volume
+    pub fn new(uuid: VolumeId) -> Self {
+        Self { uuid }
     }
 }
 
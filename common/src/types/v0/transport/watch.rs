@@ -102,6 +102,8 @@ pub enum WatchType {
     Actual,
     /// watch for both `Desired` and `Actual` changes
     All,
+    /// watch for changes of the target endpoint of a volume, ie: the device uri of its nexus
+    TargetChange,
 }
 impl Default for WatchType {
     fn default() -> Self {
@@ -109,6 +111,19 @@ impl Default for WatchType {
     }
 }
 
+/// Notification of a `TargetChange` watch: the device uri of the target of a published volume
+/// has changed, eg: because its nexus was re-shared or the volume was republished elsewhere
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeTargetChanged {
+    /// id of the volume
+    pub volume: VolumeId,
+    /// device uri of the previous target
+    pub old_uri: String,
+    /// device uri of the current target
+    pub new_uri: String,
+}
+
 /// Delete watch which was previously created by CreateWatch
 /// Fields should match the ones used for the creation
 #[derive(Serialize, Deserialize, Default, Debug, Clone)]
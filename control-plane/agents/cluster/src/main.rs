@@ -1,8 +1,15 @@
+use common_lib::transport_api::ClientId;
+use grpc::{client::CoreClient, context::TimeoutOptions};
+use http::Uri;
 use std::net::SocketAddr;
 
 use structopt::StructOpt;
-use utils::{package_description, version_info_str, DEFAULT_CLUSTER_AGENT_SERVER_ADDR};
+use utils::{
+    package_description, version_info_str, DEFAULT_CLUSTER_AGENT_SERVER_ADDR,
+    DEFAULT_GRPC_CLIENT_ADDR, NODE_AGENT_REGISTRATION_TTL, NVME_PATH_STATS_HISTORY,
+};
 
+mod path_stats;
 mod server;
 
 #[derive(Debug, StructOpt)]
@@ -11,6 +18,19 @@ struct Cli {
     /// IP address and port for the cluster-agent to listen on
     #[structopt(long, short, default_value = DEFAULT_CLUSTER_AGENT_SERVER_ADDR)]
     grpc_endpoint: SocketAddr,
+
+    /// The CORE gRPC client URL or address to connect to the core services.
+    #[structopt(long, short = "z", default_value = DEFAULT_GRPC_CLIENT_ADDR)]
+    core_grpc: Uri,
+
+    /// Number of aggregated NVMe path statistics samples to keep per volume.
+    #[structopt(long, default_value = NVME_PATH_STATS_HISTORY)]
+    path_stats_history: usize,
+
+    /// How long a node-agent registration lasts unless it's renewed.
+    /// The node-agents are told to renew their registration at a third of this period.
+    #[structopt(long, default_value = NODE_AGENT_REGISTRATION_TTL)]
+    node_agent_ttl: humantime::Duration,
 }
 
 impl Cli {
@@ -23,8 +43,17 @@ impl Cli {
 async fn main() -> anyhow::Result<()> {
     let cli = Cli::args();
 
-    server::ClusterAgent::new(cli.grpc_endpoint)
-        .run()
-        .await
-        .map_err(|e| anyhow::anyhow!("Error running server: {e}"))
+    // marked as internal so its requests are not rate limited by the core agent
+    let timeout_opts = TimeoutOptions::new().with_client(ClientId::ClusterAgent);
+    let core = CoreClient::new(cli.core_grpc, timeout_opts).await;
+
+    server::ClusterAgent::new(
+        cli.grpc_endpoint,
+        core,
+        cli.path_stats_history,
+        cli.node_agent_ttl.into(),
+    )
+    .run()
+    .await
+    .map_err(|e| anyhow::anyhow!("Error running server: {e}"))
 }
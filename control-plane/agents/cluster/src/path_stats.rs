@@ -0,0 +1,124 @@
+use common_lib::types::v0::transport::{
+    NodeId, NodePathStats, PathStats, PathStatsSample, VolumeId, VolumePathStats,
+};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::atomic::{AtomicU64, Ordering},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Path statistics of a volume as reported by each node, along with the aggregated history.
+#[derive(Debug, Default)]
+struct VolumeEntry {
+    nodes: HashMap<NodeId, PathStats>,
+    history: VecDeque<PathStatsSample>,
+}
+
+impl VolumeEntry {
+    /// Statistics aggregated across all the reporting nodes.
+    fn aggregate(&self) -> PathStats {
+        self.nodes
+            .values()
+            .fold(PathStats::default(), |mut total, stats| {
+                total.accumulate(stats);
+                total
+            })
+    }
+
+    /// Add a new aggregated sample to the history, keeping up to `history_size` samples.
+    fn sample(&mut self, timestamp: u64, history_size: usize) {
+        let sample = PathStatsSample {
+            timestamp,
+            stats: self.aggregate(),
+        };
+        while self.history.len() >= history_size.max(1) {
+            self.history.pop_front();
+        }
+        self.history.push_back(sample);
+    }
+}
+
+/// In-memory store of the NVMe path statistics reported by the node agents, aggregated per
+/// volume. Each report is a full snapshot of the node's paths, so a volume which is no longer
+/// part of a node's report is dropped from that node.
+#[derive(Debug)]
+pub(crate) struct PathStatsStore {
+    history_size: usize,
+    volumes: Mutex<HashMap<VolumeId, VolumeEntry>>,
+    ignored: AtomicU64,
+}
+
+impl PathStatsStore {
+    /// Return a new `Self` which keeps up to `history_size` aggregated samples per volume.
+    pub(crate) fn new(history_size: usize) -> Self {
+        Self {
+            history_size,
+            volumes: Default::default(),
+            ignored: AtomicU64::new(0),
+        }
+    }
+
+    /// Update the statistics of the given `node` with its latest `stats` per volume.
+    pub(crate) fn report(&self, node: &NodeId, stats: HashMap<VolumeId, PathStats>) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let mut volumes = self.volumes.lock();
+
+        let history_size = self.history_size;
+
+        volumes.retain(|volume_id, entry| {
+            if !stats.contains_key(volume_id) && entry.nodes.remove(node).is_some() {
+                if entry.nodes.is_empty() {
+                    return false;
+                }
+                entry.sample(timestamp, history_size);
+            }
+            true
+        });
+
+        for (volume_id, stats) in stats {
+            let entry = volumes.entry(volume_id).or_default();
+            entry.nodes.insert(node.clone(), stats);
+            entry.sample(timestamp, history_size);
+        }
+    }
+
+    /// Record that `count` target statistics were ignored, returning the overall total.
+    pub(crate) fn ignore(&self, count: u64) -> u64 {
+        self.ignored.fetch_add(count, Ordering::Relaxed) + count
+    }
+
+    /// Get the aggregated statistics of the given volume.
+    /// A volume which no node has reported on has empty statistics.
+    pub(crate) fn volume(&self, volume_id: &VolumeId) -> VolumePathStats {
+        let volumes = self.volumes.lock();
+        match volumes.get(volume_id) {
+            Some(entry) => {
+                let mut nodes = entry
+                    .nodes
+                    .iter()
+                    .map(|(node, stats)| NodePathStats {
+                        node: node.clone(),
+                        stats: *stats,
+                    })
+                    .collect::<Vec<_>>();
+                nodes.sort_by(|a, b| a.node.to_string().cmp(&b.node.to_string()));
+                VolumePathStats {
+                    volume_id: volume_id.clone(),
+                    current: entry.aggregate(),
+                    nodes,
+                    history: entry.history.iter().cloned().collect(),
+                }
+            }
+            None => VolumePathStats {
+                volume_id: volume_id.clone(),
+                current: PathStats::default(),
+                nodes: vec![],
+                history: vec![],
+            },
+        }
+    }
+}
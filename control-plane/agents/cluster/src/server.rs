@@ -1,23 +1,68 @@
+use crate::path_stats::PathStatsStore;
 use anyhow::anyhow;
-use common_lib::transport_api::{ReplyError, ResourceKind};
-use grpc::operations::ha_node::{
-    server::ClusterAgentServer,
-    traits::{ClusterAgentOperations, NodeInfo, ReportFailedPathsInfo},
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        cluster_agent::NodeAgentRegistration, Filter, NodeId, NodeStatus, PathStats, PublishVolume,
+        UnpublishVolume, VolumeId, VolumePathStats, VolumeShareProtocol,
+    },
+};
+use grpc::{
+    client::CoreClient,
+    operations::{
+        ha_node::{
+            server::ClusterAgentServer,
+            traits::{
+                ClusterAgentOperations, GetPathStatsInfo, NodeInfo, ReportFailedPathsInfo,
+                ReportPathStatsInfo,
+            },
+        },
+        node::traits::NodeOperations,
+        volume::traits::VolumeOperations,
+    },
+};
+use parking_lot::Mutex;
+use std::{
+    collections::{HashMap, HashSet},
+    convert::TryFrom,
+    net::SocketAddr,
+    sync::Arc,
+    time::{Duration, Instant},
 };
-use std::{net::SocketAddr, sync::Arc};
 use tonic::transport::Server;
+use utils::NVME_TARGET_NQN_PREFIX;
 
 pub(crate) struct ClusterAgent {
     endpoint: SocketAddr,
+    core: CoreClient,
+    path_stats_history: usize,
+    node_agent_ttl: Duration,
 }
 
 impl ClusterAgent {
-    pub(crate) fn new(endpoint: SocketAddr) -> Self {
-        ClusterAgent { endpoint }
+    pub(crate) fn new(
+        endpoint: SocketAddr,
+        core: CoreClient,
+        path_stats_history: usize,
+        node_agent_ttl: Duration,
+    ) -> Self {
+        ClusterAgent {
+            endpoint,
+            core,
+            path_stats_history,
+            node_agent_ttl,
+        }
     }
 
-    pub(crate) async fn run(&self) -> anyhow::Result<()> {
-        let r = ClusterAgentServer::new(Arc::new(ClusterAgentSvc {}));
+    pub(crate) async fn run(self) -> anyhow::Result<()> {
+        let svc = ClusterAgentSvc {
+            core: self.core,
+            path_stats: PathStatsStore::new(self.path_stats_history),
+            node_agent_ttl: self.node_agent_ttl,
+            registrations: Default::default(),
+            failovers: Default::default(),
+        };
+        let r = ClusterAgentServer::new(Arc::new(svc));
         Server::builder()
             .add_service(r.into_grpc_server())
             .serve(self.endpoint)
@@ -26,11 +71,157 @@ impl ClusterAgent {
     }
 }
 
-struct ClusterAgentSvc {}
+struct ClusterAgentSvc {
+    core: CoreClient,
+    path_stats: PathStatsStore,
+    /// how long a node-agent registration lasts unless it's renewed
+    node_agent_ttl: Duration,
+    /// when each node-agent last registered
+    registrations: Mutex<HashMap<String, Instant>>,
+    /// the failovers whose volume target was unpublished but not republished yet
+    failovers: Mutex<HashMap<VolumeId, Failover>>,
+}
+
+/// The target of a volume which is being failed over.
+#[derive(Debug, Clone)]
+struct Failover {
+    /// the node which the target is failed over from
+    from: NodeId,
+    protocol: Option<VolumeShareProtocol>,
+}
+
+impl ClusterAgentSvc {
+    /// Get the volume which the NVMe target `nqn` belongs to, if it's a known volume.
+    async fn nqn_volume(&self, nqn: &str) -> Result<Option<VolumeId>, ReplyError> {
+        let volume_id = match nqn
+            .strip_prefix(NVME_TARGET_NQN_PREFIX)
+            .and_then(|uuid| VolumeId::try_from(uuid).ok())
+        {
+            Some(volume_id) => volume_id,
+            None => return Ok(None),
+        };
+        match self.volume_exists(&volume_id).await? {
+            true => Ok(Some(volume_id)),
+            false => {
+                // the volume may have been deleted whilst it was being failed over
+                self.failovers.lock().remove(&volume_id);
+                Ok(None)
+            }
+        }
+    }
+
+    /// Fail the target of the volume over to another node, as its paths have failed.
+    /// A failover whose target was unpublished but not republished, eg: as the publish failed,
+    /// is carried on rather than taken as done, as the volume is then unpublished.
+    async fn failover(&self, volume_id: &VolumeId) -> Result<(), ReplyError> {
+        let volume = self
+            .core
+            .volume()
+            .get(Filter::Volume(volume_id.clone()), None, None)
+            .await?
+            .entries
+            .pop()
+            .ok_or_else(|| ReplyError::invalid_response(ResourceKind::Volume))?;
+        let in_flight = self.failovers.lock().get(volume_id).cloned();
+        let failover = match (&volume.spec().target, in_flight) {
+            (None, Some(failover)) => failover,
+            (None, None) => {
+                tracing::warn!(volume.uuid = %volume_id, "Failed paths of an unpublished volume");
+                return Ok(());
+            }
+            // the target was republished elsewhere meanwhile
+            (Some(target), Some(failover)) if target.node() != &failover.from => {
+                self.failovers.lock().remove(volume_id);
+                return Ok(());
+            }
+            (Some(target), _) => {
+                let failover = Failover {
+                    from: target.node().clone(),
+                    protocol: target.protocol().cloned(),
+                };
+                self.failovers
+                    .lock()
+                    .insert(volume_id.clone(), failover.clone());
+                if let Err(error) = self
+                    .core
+                    .volume()
+                    .unpublish(&UnpublishVolume::new(volume_id, true), None)
+                    .await
+                {
+                    self.failovers.lock().remove(volume_id);
+                    return Err(error);
+                }
+                failover
+            }
+        };
+        self.republish(volume_id, &failover).await
+    }
+
+    /// Republish the target of the volume which the `failover` unpublished on any other online
+    /// and uncordoned node, if there's one, or on whichever node the core agent picks otherwise.
+    /// If that fails, the target is republished on the node it was failed over from, so the
+    /// volume isn't left unpublished, and the failover is retried on the next report.
+    /// If that fails as well, the failover is left in flight and so the next report carries on
+    /// republishing the target.
+    async fn republish(&self, volume_id: &VolumeId, failover: &Failover) -> Result<(), ReplyError> {
+        let nodes = self.core.node().get(Filter::None, None).await?.into_inner();
+        let other_node = nodes
+            .into_iter()
+            .filter(|node| node.id() != &failover.from)
+            .filter(|node| node.state().map(|s| s.status() == &NodeStatus::Online) == Some(true))
+            .find(|node| node.spec().map(|s| !s.cordoned()).unwrap_or(true))
+            .map(|node| node.id().clone());
+
+        tracing::info!(
+            volume.uuid = %volume_id,
+            from = %failover.from,
+            to = ?other_node,
+            "Failing the volume target over"
+        );
+        let publish = |node: Option<NodeId>| {
+            PublishVolume::new(volume_id.clone(), node, failover.protocol.clone())
+        };
+        let result = match self.core.volume().publish(&publish(other_node), None).await {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                tracing::error!(
+                    volume.uuid = %volume_id,
+                    %error,
+                    "Failed to republish the volume target, republishing it where it was"
+                );
+                let original = publish(Some(failover.from.clone()));
+                if let Err(error) = self.core.volume().publish(&original, None).await {
+                    tracing::error!(
+                        volume.uuid = %volume_id,
+                        %error,
+                        "Failed to republish the volume target where it was"
+                    );
+                    return Err(error);
+                }
+                Err(error)
+            }
+        };
+        self.failovers.lock().remove(volume_id);
+        result
+    }
+
+    async fn volume_exists(&self, volume_id: &VolumeId) -> Result<bool, ReplyError> {
+        match self
+            .core
+            .volume()
+            .get(Filter::Volume(volume_id.clone()), None, None)
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(error) if error.kind == ReplyErrorKind::NotFound => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+}
 
 #[tonic::async_trait]
 impl ClusterAgentOperations for ClusterAgentSvc {
-    async fn register(&self, request: &dyn NodeInfo) -> Result<(), ReplyError> {
+    async fn register(&self, request: &dyn NodeInfo) -> Result<NodeAgentRegistration, ReplyError> {
         if request.node().is_empty() {
             return Err(ReplyError::missing_argument(
                 ResourceKind::Unknown,
@@ -45,16 +236,131 @@ impl ClusterAgentOperations for ClusterAgentSvc {
             ));
         }
 
+        self.registrations
+            .lock()
+            .insert(request.node(), Instant::now());
         tracing::trace!(agent = request.node(), "node successfully registered");
-        Ok(())
+        Ok(NodeAgentRegistration::new(self.node_agent_ttl))
     }
 
     async fn report_failed_nvme_paths(
         &self,
-        _request: &dyn ReportFailedPathsInfo,
+        request: &dyn ReportFailedPathsInfo,
     ) -> Result<(), ReplyError> {
-        Err(ReplyError::unimplemented(
-            "NVMe path reporting is not yet implemented".to_string(),
-        ))
+        if request.node().is_empty() {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::Unknown,
+                "node_name",
+            ));
+        }
+
+        // the targets whose paths failed hard are failed over first
+        let mut paths = request.failed_paths();
+        paths.sort_by_key(|path| path.severity());
+
+        let mut failed_over = HashSet::new();
+        let mut result = Ok(());
+        for path in paths {
+            if !path.severity().failover() {
+                tracing::warn!(
+                    node = request.node(),
+                    target = path.target_nqn(),
+                    severity = %path.severity(),
+                    "Degraded NVMe path"
+                );
+                continue;
+            }
+            let volume_id = match self.nqn_volume(path.target_nqn()).await {
+                Ok(Some(volume_id)) => volume_id,
+                Ok(None) => {
+                    tracing::debug!(
+                        node = request.node(),
+                        target = path.target_nqn(),
+                        "Ignored failed path of an unknown volume"
+                    );
+                    continue;
+                }
+                Err(error) => {
+                    tracing::error!(
+                        node = request.node(),
+                        target = path.target_nqn(),
+                        %error,
+                        "Failed to get the volume of a failed path"
+                    );
+                    result = Err(error);
+                    continue;
+                }
+            };
+            if !failed_over.insert(volume_id.clone()) {
+                continue;
+            }
+            tracing::error!(
+                node = request.node(),
+                target = path.target_nqn(),
+                severity = %path.severity(),
+                "Failed NVMe path"
+            );
+            if let Err(error) = self.failover(&volume_id).await {
+                tracing::error!(volume.uuid = %volume_id, %error, "Failed to fail the volume over");
+                result = Err(error);
+            }
+        }
+        // the node-agent keeps on reporting the paths until the volumes are all failed over
+        result
+    }
+
+    async fn report_path_stats(
+        &self,
+        request: &dyn ReportPathStatsInfo,
+    ) -> Result<u32, ReplyError> {
+        if request.node().is_empty() {
+            return Err(ReplyError::missing_argument(
+                ResourceKind::Unknown,
+                "node_name",
+            ));
+        }
+
+        let mut volumes = HashMap::<VolumeId, PathStats>::new();
+        let mut ignored = 0;
+        for target in request.stats() {
+            match self.nqn_volume(target.target_nqn()).await? {
+                Some(volume_id) => volumes
+                    .entry(volume_id)
+                    .or_default()
+                    .accumulate(target.stats()),
+                None => ignored += 1,
+            }
+        }
+
+        if ignored > 0 {
+            let total = self.path_stats.ignore(ignored as u64);
+            tracing::debug!(
+                node = request.node(),
+                ignored,
+                total,
+                "Ignored path statistics of unknown volumes"
+            );
+        }
+        self.path_stats.report(&request.node().into(), volumes);
+        Ok(ignored)
+    }
+
+    async fn get_path_stats(
+        &self,
+        request: &dyn GetPathStatsInfo,
+    ) -> Result<VolumePathStats, ReplyError> {
+        let volume_id = request.volume_id();
+        // the core agent replies with NotFound if the volume doesn't exist
+        self.core
+            .volume()
+            .get(Filter::Volume(volume_id.clone()), None, None)
+            .await?;
+        Ok(self.path_stats.volume(&volume_id))
+    }
+
+    async fn get_registrations(&self) -> Result<u32, ReplyError> {
+        let mut registrations = self.registrations.lock();
+        registrations.retain(|_, registered| registered.elapsed() < self.node_agent_ttl);
+        Ok(registrations.len() as u32)
     }
 }
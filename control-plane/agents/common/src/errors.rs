@@ -2,7 +2,7 @@ use common_lib::{
     transport_api::{ErrorChain, ReplyError, ReplyErrorKind, ResourceKind},
     types::v0::{
         store::definitions::StoreError,
-        transport::{APIVersion, Filter, NodeId, PoolId, ReplicaId},
+        transport::{APIVersion, Filter, NodeId, NvmeReservationType, PoolId, ReplicaId},
     },
 };
 use snafu::{Error, Snafu};
@@ -19,6 +19,11 @@ pub enum SvcError {
     GetNodes { source: ReplyError },
     #[snafu(display("Node '{}' is not online", node))]
     NodeNotOnline { node: NodeId },
+    #[snafu(display("Node '{}' keeps failing to respond, retry in '{:?}'", node, retry_in))]
+    NodeCircuitOpen {
+        node: NodeId,
+        retry_in: std::time::Duration,
+    },
     #[snafu(display("No available online nodes"))]
     NoNodes {},
     #[snafu(display("Node {} is cordoned", node_id))]
@@ -27,6 +32,10 @@ pub enum SvcError {
     CordonLabel { node_id: String, label: String },
     #[snafu(display("Node {} does not have a cordon label '{}'", node_id, label))]
     UncordonLabel { node_id: String, label: String },
+    #[snafu(display("Node '{}' is online, it must be offline to be deleted", node))]
+    NodeOnline { node: NodeId },
+    #[snafu(display("Node '{}' still hosts {}", node, blockers.join(", ")))]
+    NodeInUse { node: NodeId, blockers: Vec<String> },
     #[snafu(display(
         "Timed out after '{:?}' attempting to connect to node '{}' via gRPC endpoint '{}'",
         timeout,
@@ -38,6 +47,11 @@ pub enum SvcError {
         endpoint: String,
         timeout: std::time::Duration,
     },
+    #[snafu(display("Timed out after '{:?}' polling node '{}'", timeout, node_id))]
+    NodePollTimeout {
+        node_id: String,
+        timeout: std::time::Duration,
+    },
     #[snafu(display(
         "Failed to connect to node '{}' via gRPC endpoint '{}'",
         node_id,
@@ -69,6 +83,8 @@ pub enum SvcError {
     NodeNotFound { node_id: NodeId },
     #[snafu(display("Pool '{}' not found", pool_id))]
     PoolNotFound { pool_id: PoolId },
+    #[snafu(display("Pool '{}' cannot host a replica of the volume: {}", pool_id, reason))]
+    UnsuitablePool { pool_id: PoolId, reason: String },
     #[snafu(display("Nexus '{}' not found", nexus_id))]
     NexusNotFound { nexus_id: String },
     #[snafu(display("{} '{}' not found", kind.to_string(), id))]
@@ -81,6 +97,21 @@ pub enum SvcError {
     ChildNotFound { nexus: String, child: String },
     #[snafu(display("Child '{}' already exists in Nexus '{}'", child, nexus))]
     ChildAlreadyExists { nexus: String, child: String },
+    #[snafu(display("Child '{}' of Nexus '{}' is already offline", child, nexus))]
+    ChildAlreadyOffline { nexus: String, child: String },
+    #[snafu(display("Child '{}' of Nexus '{}' is not offline", child, nexus))]
+    ChildNotOffline { nexus: String, child: String },
+    #[snafu(display(
+        "Node '{}' cannot connect to the children of nexus '{}': {}",
+        node,
+        nexus,
+        report
+    ))]
+    ChildrenUnreachable {
+        nexus: String,
+        node: NodeId,
+        report: String,
+    },
     #[snafu(display("Volume '{}' not found", vol_id))]
     VolumeNotFound { vol_id: String },
     #[snafu(display("Volume '{}' not published", vol_id))]
@@ -102,6 +133,26 @@ pub enum SvcError {
         node: String,
         protocol: String,
     },
+    #[snafu(display(
+        "Volume '{}' is fenced with another NVMe reservation key than '{}'",
+        vol_id,
+        key
+    ))]
+    NvmeReservationConflict { vol_id: String, key: u64 },
+    #[snafu(display(
+        "The io-engine on node '{}' does not support the NVMe reservation type '{:?}'",
+        node_id,
+        reservation_type
+    ))]
+    NvmeReservationUnsupported {
+        node_id: NodeId,
+        reservation_type: NvmeReservationType,
+    },
+    #[snafu(display(
+        "Node '{}' cannot scan its disks for the pools which could be imported",
+        node_id
+    ))]
+    PoolDiscoveryUnsupported { node_id: NodeId },
     #[snafu(display("Replica '{}' not found", replica_id))]
     ReplicaNotFound { replica_id: ReplicaId },
     #[snafu(display("{} '{}' is already shared over {}", kind.to_string(), id, share))]
@@ -112,8 +163,30 @@ pub enum SvcError {
     },
     #[snafu(display("{} '{}' is not shared", kind.to_string(), id))]
     NotShared { kind: ResourceKind, id: String },
-    #[snafu(display("Invalid filter value: {:?}", filter))]
-    InvalidFilter { filter: Filter },
+    #[snafu(display("The IO of {} '{}' is already frozen", kind.to_string(), id))]
+    AlreadyFrozen { kind: ResourceKind, id: String },
+    #[snafu(display("The IO of {} '{}' is not frozen", kind.to_string(), id))]
+    NotFrozen { kind: ResourceKind, id: String },
+    #[snafu(display("The rebuilds of volume '{}' are already paused", vol_id))]
+    RebuildAlreadyPaused { vol_id: String },
+    #[snafu(display("The rebuilds of volume '{}' are not paused", vol_id))]
+    RebuildNotPaused { vol_id: String },
+    #[snafu(display("{} cannot be filtered by {:?}", resource.to_string(), filter))]
+    InvalidFilter {
+        resource: ResourceKind,
+        filter: Filter,
+    },
+    #[snafu(display(
+        "Cannot sort {} by '{}', the sortable fields are: {}",
+        kind.to_string(),
+        field,
+        allowed.join(", ")
+    ))]
+    InvalidSortField {
+        kind: ResourceKind,
+        field: String,
+        allowed: Vec<String>,
+    },
     #[snafu(display("Operation failed due to insufficient resources"))]
     NotEnoughResources { source: NotEnough },
     #[snafu(display("Failed to deserialise JsonRpc response"))]
@@ -139,14 +212,81 @@ pub enum SvcError {
     Store { source: StoreError },
     #[snafu(display("Storage Error: {} Config for Resource id {} not committed to the store", kind.to_string(), id))]
     StoreSave { kind: ResourceKind, id: String },
+    #[snafu(display("The persistent store is unavailable, only read operations are served"))]
+    StoreUnavailable {},
     #[snafu(display("Watch Config Not Found"))]
     WatchNotFound {},
     #[snafu(display("{} Resource to be watched does not exist", kind.to_string()))]
     WatchResourceNotFound { kind: ResourceKind },
     #[snafu(display("Watch Already Exists"))]
     WatchAlreadyExists {},
+    #[snafu(display("{} Resource can't be watched for target changes", kind.to_string()))]
+    WatchTypeNotSupported { kind: ResourceKind },
     #[snafu(display("Conflicts with existing operation - please retry"))]
     Conflict {},
+    #[snafu(display(
+        "{} '{}' was recently destroyed and cannot be created again until its tombstone expires",
+        kind.to_string(),
+        id
+    ))]
+    Tombstoned { kind: ResourceKind, id: String },
+    #[snafu(display("{} '{}' has no tombstone", kind.to_string(), id))]
+    TombstoneNotFound { kind: ResourceKind, id: String },
+    #[snafu(display(
+        "{} '{}' has no revision {} in its spec history",
+        kind.to_string(),
+        id,
+        revision
+    ))]
+    SpecRevisionNotFound {
+        kind: ResourceKind,
+        id: String,
+        revision: u64,
+    },
+    #[snafu(display("Invalid patch of the {} '{}' spec: {}", kind.to_string(), id, reason))]
+    InvalidSpecPatch {
+        kind: ResourceKind,
+        id: String,
+        reason: String,
+    },
+    #[snafu(display(
+        "Patch of the {} '{}' spec does not match the confirmation token of its dry run",
+        kind.to_string(),
+        id
+    ))]
+    SpecPatchNotConfirmed { kind: ResourceKind, id: String },
+    #[snafu(display(
+        "The pending operation of the {} '{}' may have taken effect and is only cleared if forced",
+        kind.to_string(),
+        id
+    ))]
+    PendingOperationNotForced { kind: ResourceKind, id: String },
+    #[snafu(display("Forcibly releasing the operation guards is not enabled"))]
+    GuardReleaseDisabled {},
+    #[snafu(display("Patching the persisted specs is not enabled"))]
+    SpecPatchDisabled {},
+    #[snafu(display(
+        "A call to node '{}' may be in flight for the {} '{}'",
+        node,
+        kind.to_string(),
+        id
+    ))]
+    DataplaneCallInFlight {
+        kind: ResourceKind,
+        id: String,
+        node: NodeId,
+    },
+    #[snafu(display(
+        "{} '{}' is reported by node '{}' differently from its spec and must be resolved manually",
+        kind.to_string(),
+        id,
+        node
+    ))]
+    UnresolvedConflict {
+        kind: ResourceKind,
+        id: String,
+        node: NodeId,
+    },
     #[snafu(display("Pending deletion - please retry"))]
     Deleting {},
     #[snafu(display(
@@ -176,6 +316,34 @@ pub enum SvcError {
         volume
     ))]
     LastHealthyReplica { replica: String, volume: String },
+    #[snafu(display(
+        "The {} would leave the volumes '{}' with fewer than {} healthy replicas",
+        operation,
+        volumes.join(", "),
+        min
+    ))]
+    BelowMinReplicationFactor {
+        operation: String,
+        volumes: Vec<String>,
+        min: u8,
+    },
+    #[snafu(display(
+        "Replica '{}' of volume '{}' is not a healthy child of its target",
+        replica,
+        volume
+    ))]
+    UnhealthyReplica { replica: String, volume: String },
+    #[snafu(display(
+        "Replica '{}' has been recreated as generation '{}', the reference is to generation '{}'",
+        replica_id,
+        generation,
+        referenced
+    ))]
+    ReplicaGenerationMismatch {
+        replica_id: ReplicaId,
+        generation: u64,
+        referenced: u64,
+    },
     #[snafu(display("Replica count of Volume '{}' is already '{}'", id, count))]
     ReplicaCountAchieved { id: String, count: u8 },
     #[snafu(display("Replica count only allowed to change by a maximum of one at a time"))]
@@ -206,8 +374,72 @@ pub enum SvcError {
         max_rebuilds
     ))]
     MaxRebuilds { max_rebuilds: u32 },
+    #[snafu(display(
+        "Rebuild of volume '{}' of {} rebuild priority is queued behind {} volumes ranked before it",
+        volume,
+        priority,
+        ahead
+    ))]
+    RebuildQueued {
+        volume: String,
+        priority: String,
+        ahead: usize,
+    },
+    #[snafu(display(
+        "Volume '{}' of size {} bytes exceeds the maximum volume size of {} bytes",
+        id,
+        size,
+        max_size
+    ))]
+    VolumeTooLarge {
+        id: String,
+        size: u64,
+        max_size: u64,
+    },
+    #[snafu(display(
+        "Volume '{}' of size {} bytes is smaller than the minimum volume size of {} bytes",
+        id,
+        size,
+        min_size
+    ))]
+    VolumeTooSmall {
+        id: String,
+        size: u64,
+        min_size: u64,
+    },
+    #[snafu(display("Invalid {} of the volume creation request: {}", field, reason))]
+    InvalidVolumeRequest { field: String, reason: String },
+    #[snafu(display(
+        "Volume '{}' of size {} bytes exceeds the quota of {} bytes of the volumes labelled '{}={}', which already provision {} bytes",
+        id,
+        size,
+        capacity,
+        key,
+        value,
+        used
+    ))]
+    VolumeQuotaExceeded {
+        id: String,
+        size: u64,
+        key: String,
+        value: String,
+        used: u64,
+        capacity: u64,
+    },
     #[snafu(display("The api version: {:?} is not valid", api_version))]
     InvalidApiVersion { api_version: Option<APIVersion> },
+    #[snafu(display(
+        "The '{}' request is not supported over the {:?} api of node '{}'",
+        request,
+        api_version,
+        node_id
+    ))]
+    UnsupportedApiRequest {
+        node_id: NodeId,
+        api_version: APIVersion,
+        resource: ResourceKind,
+        request: String,
+    },
 }
 
 impl From<StoreError> for SvcError {
@@ -249,6 +481,30 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error_str,
             },
+            SvcError::AlreadyFrozen { kind, .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: kind,
+                source: desc.to_string(),
+                extra: error_str,
+            },
+            SvcError::NotFrozen { kind, .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: kind,
+                source: desc.to_string(),
+                extra: error_str,
+            },
+            SvcError::RebuildAlreadyPaused { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error_str,
+            },
+            SvcError::RebuildNotPaused { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error_str,
+            },
             SvcError::InvalidShareProtocol { kind, .. } => ReplyError {
                 kind: ReplyErrorKind::InvalidArgument,
                 resource: kind,
@@ -267,6 +523,24 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::ChildAlreadyOffline { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Child,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::ChildNotOffline { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Child,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::ChildrenUnreachable { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Child,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::InUse { kind, id } => ReplyError {
                 kind: ReplyErrorKind::InUse,
                 resource: kind,
@@ -291,6 +565,66 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::Tombstoned { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::Conflict,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::UnresolvedConflict { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::Conflict,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::TombstoneNotFound { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::NotFound,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::SpecRevisionNotFound { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::NotFound,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::InvalidSpecPatch { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::SpecPatchNotConfirmed { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::PendingOperationNotForced { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::GuardReleaseDisabled {} => ReplyError {
+                kind: ReplyErrorKind::PermissionDenied,
+                resource: ResourceKind::Spec,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::SpecPatchDisabled {} => ReplyError {
+                kind: ReplyErrorKind::PermissionDenied,
+                resource: ResourceKind::Spec,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::DataplaneCallInFlight { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::Conflict,
+                resource: kind.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::Deleting { .. } => ReplyError {
                 kind: ReplyErrorKind::Deleting,
                 resource: ResourceKind::Unknown,
@@ -331,6 +665,13 @@ impl From<SvcError> for ReplyError {
                 extra: error.full_string(),
             },
 
+            SvcError::NodeCircuitOpen { .. } => ReplyError {
+                kind: ReplyErrorKind::Unavailable,
+                resource: ResourceKind::Node,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+
             SvcError::NoNodes { .. } => ReplyError {
                 kind: ReplyErrorKind::FailedPrecondition,
                 resource: ResourceKind::Node,
@@ -359,12 +700,32 @@ impl From<SvcError> for ReplyError {
                 extra: error.full_string(),
             },
 
+            SvcError::NodeOnline { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Node,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+
+            SvcError::NodeInUse { .. } => ReplyError {
+                kind: ReplyErrorKind::InUse,
+                resource: ResourceKind::Node,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+
             SvcError::GrpcConnectTimeout { .. } => ReplyError {
                 kind: ReplyErrorKind::Timeout,
                 resource: ResourceKind::Unknown,
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::NodePollTimeout { .. } => ReplyError {
+                kind: ReplyErrorKind::Timeout,
+                resource: ResourceKind::Node,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
 
             SvcError::GrpcConnectUri { .. } => ReplyError {
                 kind: ReplyErrorKind::Internal,
@@ -403,6 +764,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::StoreUnavailable { .. } => ReplyError {
+                kind: ReplyErrorKind::Unavailable,
+                resource: ResourceKind::Unknown,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::StoreMissingEntry { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Unknown,
@@ -427,6 +794,12 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::UnsuitablePool { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::ReplicaNotFound { .. } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: ResourceKind::Replica,
@@ -475,6 +848,24 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::NvmeReservationConflict { .. } => ReplyError {
+                kind: ReplyErrorKind::Conflict,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::NvmeReservationUnsupported { .. } => ReplyError {
+                kind: ReplyErrorKind::Unimplemented,
+                resource: ResourceKind::Nexus,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::PoolDiscoveryUnsupported { .. } => ReplyError {
+                kind: ReplyErrorKind::Unimplemented,
+                resource: ResourceKind::Pool,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::WatchResourceNotFound { kind } => ReplyError {
                 kind: ReplyErrorKind::NotFound,
                 resource: kind,
@@ -493,9 +884,21 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
-            SvcError::InvalidFilter { .. } => ReplyError {
-                kind: ReplyErrorKind::Internal,
-                resource: ResourceKind::Unknown,
+            SvcError::WatchTypeNotSupported { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Watch,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::InvalidFilter { ref resource, .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: resource.clone(),
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::InvalidSortField { ref kind, .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: kind.clone(),
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
@@ -517,12 +920,18 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
-            SvcError::LastHealthyReplica { .. } => ReplyError {
+            SvcError::UnhealthyReplica { .. } => ReplyError {
                 kind: ReplyErrorKind::FailedPrecondition,
                 resource: ResourceKind::Volume,
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::ReplicaGenerationMismatch { .. } => ReplyError {
+                kind: ReplyErrorKind::FailedPrecondition,
+                resource: ResourceKind::Replica,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::ReplicaCountAchieved { .. } => ReplyError {
                 kind: ReplyErrorKind::ReplicaCountAchieved,
                 resource: ResourceKind::Volume,
@@ -559,6 +968,18 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::LastHealthyReplica { .. } => ReplyError {
+                kind: ReplyErrorKind::LastHealthyReplica,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::BelowMinReplicationFactor { .. } => ReplyError {
+                kind: ReplyErrorKind::BelowMinReplicationFactor,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::ReplicaCreateNumber { .. } => ReplyError {
                 kind: ReplyErrorKind::ReplicaCreateNumber,
                 resource: ResourceKind::Volume,
@@ -577,12 +998,48 @@ impl From<SvcError> for ReplyError {
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::RebuildQueued { .. } => ReplyError {
+                kind: ReplyErrorKind::ResourceExhausted,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::VolumeTooLarge { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::VolumeTooSmall { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::InvalidVolumeRequest { .. } => ReplyError {
+                kind: ReplyErrorKind::InvalidArgument,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
+            SvcError::VolumeQuotaExceeded { .. } => ReplyError {
+                kind: ReplyErrorKind::ResourceExhausted,
+                resource: ResourceKind::Volume,
+                source: desc.to_string(),
+                extra: error.full_string(),
+            },
             SvcError::InvalidApiVersion { .. } => ReplyError {
                 kind: ReplyErrorKind::InvalidArgument,
                 resource: ResourceKind::Unknown,
                 source: desc.to_string(),
                 extra: error.full_string(),
             },
+            SvcError::UnsupportedApiRequest { resource, .. } => ReplyError {
+                kind: ReplyErrorKind::Unimplemented,
+                resource,
+                source: desc.to_string(),
+                extra: error_str,
+            },
         }
     }
 }
@@ -93,6 +93,8 @@ impl IoEngineToAgent for v0_rpc::Pool {
             status: self.state.into(),
             capacity: self.capacity,
             used: self.used,
+            // the cluster size of the pools is not reported by the io-engine
+            cluster_size: transport::DEFAULT_POOL_CLUSTER_SIZE,
         }
     }
 }
@@ -207,6 +209,8 @@ impl IoEngineToAgent for v0_rpc::Child {
             state_reason: v0_rpc::ChildStateReason::from_i32(self.reason)
                 .map(|f| From::from(ExternalType(f)))
                 .unwrap_or(ChildStateReason::Unknown),
+            // recorded by the control plane as the child state is refreshed
+            last_fault: None,
         }
     }
 }
@@ -315,6 +319,44 @@ impl AgentToIoEngine for transport::UnshareNexus {
     }
 }
 
+impl AgentToIoEngine for transport::PauseNexus {
+    type IoEngineMessage = v0_rpc::PauseNexusRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.uuid.clone().into(),
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::ResumeNexus {
+    type IoEngineMessage = v0_rpc::ResumeNexusRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.uuid.clone().into(),
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::PauseNexusRebuild {
+    type IoEngineMessage = v0_rpc::PauseRebuildRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.nexus.clone().into(),
+            uri: self.uri.clone().into(),
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::ResumeNexusRebuild {
+    type IoEngineMessage = v0_rpc::ResumeRebuildRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.nexus.clone().into(),
+            uri: self.uri.clone().into(),
+        }
+    }
+}
+
 impl AgentToIoEngine for transport::DestroyNexus {
     type IoEngineMessage = v0_rpc::DestroyNexusRequest;
     fn to_rpc(&self) -> Self::IoEngineMessage {
@@ -344,3 +386,25 @@ impl AgentToIoEngine for transport::RemoveNexusChild {
         }
     }
 }
+
+impl AgentToIoEngine for transport::OnlineNexusChild {
+    type IoEngineMessage = v0_rpc::ChildNexusRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.nexus.clone().into(),
+            uri: self.uri.clone().into(),
+            action: v0_rpc::ChildAction::Online as i32,
+        }
+    }
+}
+
+impl AgentToIoEngine for transport::OfflineNexusChild {
+    type IoEngineMessage = v0_rpc::ChildNexusRequest;
+    fn to_rpc(&self) -> Self::IoEngineMessage {
+        Self::IoEngineMessage {
+            uuid: self.nexus.clone().into(),
+            uri: self.uri.clone().into(),
+            action: v0_rpc::ChildAction::Offline as i32,
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use common_lib::types::v0::transport::{
+    ConditionStatus, PoolState, PoolStatus, ResourceCondition, VolumeCondition, VolumeStatus,
+};
+use std::{collections::HashMap, hash::Hash, time::SystemTime};
+
+/// Type of the condition which reflects the status of a resource.
+const READY: &str = "Ready";
+
+/// The divergences of a volume from its spec, each of which is reported as a condition.
+const VOLUME_DIVERGENCES: [VolumeCondition; 7] = [
+    VolumeCondition::TargetMissing,
+    VolumeCondition::ReplicaMissing,
+    VolumeCondition::SpecDirty,
+    VolumeCondition::TopologyUnsatisfiable,
+    VolumeCondition::TopologyRelaxed,
+    VolumeCondition::ChildSizeMismatch,
+    VolumeCondition::ReplayStuck,
+];
+
+/// The time of the last transition of the conditions of each resource.
+/// The conditions are derived anew from the state of a resource every time it's observed, so
+/// each condition is given the time at which it was first observed with its current status, which
+/// is only bumped when it's observed with another status.
+#[derive(Debug)]
+pub(crate) struct ConditionTransitions<K> {
+    resources: HashMap<K, HashMap<String, (ConditionStatus, SystemTime)>>,
+}
+
+impl<K> Default for ConditionTransitions<K> {
+    fn default() -> Self {
+        Self {
+            resources: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash + Clone> ConditionTransitions<K> {
+    /// Observe the `conditions` of the `resource`, returning them with the time of their last
+    /// transition. A condition which was not observed before, or whose status has changed since,
+    /// transitions at the time which it's observed with.
+    pub(crate) fn observe(
+        &mut self,
+        resource: &K,
+        conditions: Vec<ResourceCondition>,
+    ) -> Vec<ResourceCondition> {
+        let previous = self.resources.remove(resource).unwrap_or_default();
+        let conditions = conditions
+            .into_iter()
+            .map(|mut condition| {
+                if let Some((status, time)) = previous.get(&condition.kind) {
+                    if status == &condition.status {
+                        condition.last_transition_time = *time;
+                    }
+                }
+                condition
+            })
+            .collect::<Vec<_>>();
+        let transitions = conditions
+            .iter()
+            .map(|c| (c.kind.clone(), (c.status, c.last_transition_time)))
+            .collect();
+        self.resources.insert(resource.clone(), transitions);
+        conditions
+    }
+    /// Forget the transitions of the resources which don't satisfy `keep`, eg: deleted resources
+    pub(crate) fn retain(&mut self, keep: impl Fn(&K) -> bool) {
+        self.resources.retain(|resource, _| keep(resource));
+    }
+}
+
+/// Get the conditions of a volume with the given `status` and `divergences`, observed at `now`:
+/// whether it's ready as per its status, and whether it diverges from its spec in each of the
+/// possible ways.
+pub(crate) fn volume_conditions(
+    status: &VolumeStatus,
+    divergences: &[VolumeCondition],
+    now: SystemTime,
+) -> Vec<ResourceCondition> {
+    let (condition_status, message) = match status {
+        VolumeStatus::Online => (ConditionStatus::True, "the volume is healthy"),
+        VolumeStatus::Degraded => (
+            ConditionStatus::False,
+            "the volume is not healthy but is able to serve IO",
+        ),
+        VolumeStatus::Faulted => (
+            ConditionStatus::False,
+            "the volume is broken and unable to serve IO",
+        ),
+        VolumeStatus::Unknown => (
+            ConditionStatus::Unknown,
+            "the status of the volume is not known",
+        ),
+    };
+    let ready = ResourceCondition::new(READY, condition_status, status.to_string(), message, now);
+
+    let divergences = VOLUME_DIVERGENCES.iter().map(|divergence| {
+        if divergences.contains(divergence) {
+            let message = match divergence {
+                VolumeCondition::TargetMissing => {
+                    "the volume is published but its target does not exist on any node"
+                }
+                VolumeCondition::ReplicaMissing => {
+                    "a replica of the volume does not exist on any node"
+                }
+                VolumeCondition::SpecDirty => "an operation on the volume did not complete",
+                VolumeCondition::TopologyUnsatisfiable => {
+                    "the volume is missing replicas which can't be placed as per its topology"
+                }
+                VolumeCondition::TopologyRelaxed => {
+                    "a replica of the volume was placed regardless of its topology"
+                }
+                VolumeCondition::ChildSizeMismatch => {
+                    "a child of the volume target is backed by a replica smaller than the target"
+                }
+                VolumeCondition::ReplayStuck => {
+                    "the operation pending on the volume or its target could not be replayed"
+                }
+            };
+            let kind = divergence.to_string();
+            ResourceCondition::new(kind.clone(), ConditionStatus::True, kind, message, now)
+        } else {
+            let kind = divergence.to_string();
+            ResourceCondition::new(kind, ConditionStatus::False, "AsExpected", "", now)
+        }
+    });
+
+    std::iter::once(ready).chain(divergences).collect()
+}
+
+/// Get the conditions of a pool with the given `state`, observed at `now`: whether it's ready as
+/// per its status, which is not known when its node does not report it.
+pub(crate) fn pool_conditions(
+    state: Option<&PoolState>,
+    now: SystemTime,
+) -> Vec<ResourceCondition> {
+    let ready = match state.map(|state| &state.status) {
+        Some(status) => {
+            let (condition_status, message) = match status {
+                PoolStatus::Online => (ConditionStatus::True, "the pool is healthy"),
+                PoolStatus::Degraded => (
+                    ConditionStatus::False,
+                    "the pool has experienced a failure but can still function",
+                ),
+                PoolStatus::Faulted => (
+                    ConditionStatus::False,
+                    "the pool is completely inaccessible",
+                ),
+                PoolStatus::Unknown => (
+                    ConditionStatus::Unknown,
+                    "the status of the pool is not known",
+                ),
+            };
+            ResourceCondition::new(READY, condition_status, status.to_string(), message, now)
+        }
+        None => ResourceCondition::new(
+            READY,
+            ConditionStatus::Unknown,
+            "StateMissing",
+            "the pool is not reported by its node",
+            now,
+        ),
+    };
+    vec![ready]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::PoolId;
+    use std::time::Duration;
+
+    fn pool_state(status: PoolStatus) -> PoolState {
+        PoolState {
+            status,
+            ..Default::default()
+        }
+    }
+
+    /// Observe the conditions of the pool with the given `state` at `secs` past the epoch
+    fn observe_pool(
+        transitions: &mut ConditionTransitions<PoolId>,
+        state: Option<PoolState>,
+        secs: u64,
+    ) -> ResourceCondition {
+        let pool = PoolId::from("p1");
+        let mut conditions = transitions.observe(&pool, pool_conditions(state.as_ref(), at(secs)));
+        assert_eq!(conditions.len(), 1);
+        conditions.remove(0)
+    }
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    #[test]
+    fn transition_time_stable_across_refreshes() {
+        let mut transitions = ConditionTransitions::default();
+
+        let ready = observe_pool(&mut transitions, Some(pool_state(PoolStatus::Online)), 10);
+        assert_eq!(ready.status, ConditionStatus::True);
+        assert_eq!(ready.last_transition_time, at(10));
+        for secs in 11 .. 20 {
+            let ready = observe_pool(&mut transitions, Some(pool_state(PoolStatus::Online)), secs);
+            assert_eq!(ready.last_transition_time, at(10));
+        }
+
+        // the pool's node goes away
+        let ready = observe_pool(&mut transitions, None, 20);
+        assert_eq!(ready.status, ConditionStatus::Unknown);
+        assert_eq!(ready.reason, "StateMissing");
+        assert_eq!(ready.last_transition_time, at(20));
+        let ready = observe_pool(&mut transitions, None, 25);
+        assert_eq!(ready.last_transition_time, at(20));
+
+        // the status changes but the condition does not, so it keeps its transition time
+        observe_pool(&mut transitions, Some(pool_state(PoolStatus::Degraded)), 30);
+        let ready = observe_pool(&mut transitions, Some(pool_state(PoolStatus::Faulted)), 35);
+        assert_eq!(ready.status, ConditionStatus::False);
+        assert_eq!(ready.reason, "Faulted");
+        assert_eq!(ready.last_transition_time, at(30));
+
+        let ready = observe_pool(&mut transitions, Some(pool_state(PoolStatus::Online)), 40);
+        assert_eq!(ready.last_transition_time, at(40));
+    }
+
+    #[test]
+    fn forgotten_resources_transition_anew() {
+        let mut transitions = ConditionTransitions::default();
+        observe_pool(&mut transitions, Some(pool_state(PoolStatus::Online)), 10);
+
+        transitions.retain(|pool| pool != &PoolId::from("p1"));
+        let ready = observe_pool(&mut transitions, Some(pool_state(PoolStatus::Online)), 20);
+        assert_eq!(ready.last_transition_time, at(20));
+    }
+
+    #[test]
+    fn volume_divergences() {
+        let mut transitions = ConditionTransitions::<()>::default();
+
+        let conditions =
+            transitions.observe(&(), volume_conditions(&VolumeStatus::Online, &[], at(10)));
+        let kinds = conditions
+            .iter()
+            .map(|c| c.kind.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                "Ready",
+                "TargetMissing",
+                "ReplicaMissing",
+                "SpecDirty",
+                "TopologyUnsatisfiable",
+                "TopologyRelaxed",
+                "ChildSizeMismatch",
+                "ReplayStuck"
+            ]
+        );
+        assert_eq!(conditions[0].status, ConditionStatus::True);
+        assert!(conditions[1 ..]
+            .iter()
+            .all(|c| c.status == ConditionStatus::False));
+
+        let divergences = [VolumeCondition::ChildSizeMismatch];
+        let conditions = volume_conditions(&VolumeStatus::Degraded, &divergences, at(20));
+        let conditions = transitions.observe(&(), conditions);
+        let changed = conditions
+            .iter()
+            .filter(|c| c.last_transition_time == at(20))
+            .map(|c| (c.kind.as_str(), c.status, c.reason.as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            changed,
+            vec![
+                ("Ready", ConditionStatus::False, "Degraded"),
+                (
+                    "ChildSizeMismatch",
+                    ConditionStatus::True,
+                    "ChildSizeMismatch"
+                )
+            ]
+        );
+        assert_eq!(conditions[1].last_transition_time, at(10));
+    }
+}
@@ -0,0 +1,68 @@
+use common_lib::types::v0::transport::{NodeId, PoolId, ReplicaId};
+use std::{collections::HashMap, str::FromStr};
+
+/// How to resolve a pool or replica which a registering node reports differently from its spec,
+/// eg: a pool whose id is in use by a pool on another node.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ConflictPolicy {
+    /// Keep the spec as it is in the persistent store.
+    PreferStore,
+    /// Update the spec to match what the node reports.
+    PreferNode,
+    /// Keep the spec but refuse any operation on the resource until it's resolved by hand.
+    Manual,
+}
+
+impl Default for ConflictPolicy {
+    fn default() -> Self {
+        Self::PreferStore
+    }
+}
+
+impl FromStr for ConflictPolicy {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "prefer-store" => Ok(Self::PreferStore),
+            "prefer-node" => Ok(Self::PreferNode),
+            "manual" => Ok(Self::Manual),
+            _ => Err(format!(
+                "Invalid conflict policy '{}', expected one of: prefer-store, prefer-node, manual",
+                source
+            )),
+        }
+    }
+}
+
+/// Pools and replicas which are flagged for manual resolution, along with the node which reported
+/// them differently from their specs.
+#[derive(Debug, Default)]
+pub(crate) struct FlaggedConflicts {
+    pools: HashMap<PoolId, NodeId>,
+    replicas: HashMap<ReplicaId, NodeId>,
+}
+
+impl FlaggedConflicts {
+    /// Forget the conflicts reported by the given node, which is about to be checked again.
+    pub(crate) fn clear_node(&mut self, node: &NodeId) {
+        self.pools.retain(|_, reported_by| reported_by != node);
+        self.replicas.retain(|_, reported_by| reported_by != node);
+    }
+    /// Flag the pool reported differently by the given node.
+    pub(crate) fn flag_pool(&mut self, pool: &PoolId, node: &NodeId) {
+        self.pools.insert(pool.clone(), node.clone());
+    }
+    /// Flag the replica reported differently by the given node.
+    pub(crate) fn flag_replica(&mut self, replica: &ReplicaId, node: &NodeId) {
+        self.replicas.insert(replica.clone(), node.clone());
+    }
+    /// Get the node which reported the flagged pool, if it's flagged.
+    pub(crate) fn pool(&self, pool: &PoolId) -> Option<&NodeId> {
+        self.pools.get(pool)
+    }
+    /// Get the node which reported the flagged replica, if it's flagged.
+    pub(crate) fn replica(&self, replica: &ReplicaId) -> Option<&NodeId> {
+        self.replicas.get(replica)
+    }
+}
@@ -0,0 +1,58 @@
+use common::errors::SvcError;
+use common_lib::types::v0::store::{AsOperationSequencer, OperationGuardArc};
+use std::fmt::Debug;
+
+/// Environment variable with the comma separated list of the armed fault injection points.
+#[cfg(feature = "fault-injection")]
+const FAULT_INJECTION_ENV: &str = "CORE_FAULT_INJECTION";
+
+/// The replicas of a volume have been created, but the specs of the volume and its replicas are
+/// not yet stored as created.
+pub(crate) const VOLUME_CREATE_REPLICAS: &str = "volume-create-replicas";
+/// The share operation of a volume has been started, but not yet carried out.
+pub(crate) const VOLUME_SHARE: &str = "volume-share";
+
+/// Check if the fault injection `point` is armed, as per the environment of the agent when it
+/// was first checked.
+#[cfg(feature = "fault-injection")]
+fn armed(point: &str) -> bool {
+    static ARMED: once_cell::sync::Lazy<Vec<String>> = once_cell::sync::Lazy::new(|| {
+        std::env::var(FAULT_INJECTION_ENV)
+            .map(|points| points.split(',').map(|p| p.trim().to_string()).collect())
+            .unwrap_or_default()
+    });
+    ARMED.iter().any(|armed| armed == point)
+}
+/// The fault injection points are only compiled in with the `fault-injection` feature, otherwise
+/// they're never armed.
+#[cfg(not(feature = "fault-injection"))]
+fn armed(_point: &str) -> bool {
+    false
+}
+
+/// Abort the agent if the fault injection `point` is armed, simulating a crash at this point.
+pub(crate) fn crash_point(point: &str) {
+    if armed(point) {
+        tracing::error!(point, "Crashing at the armed fault injection point");
+        std::process::abort();
+    }
+}
+
+/// Fail the operation of the `guard` if the fault injection `point` is armed, without releasing
+/// its resource, simulating an operation which was abandoned at this point.
+pub(crate) fn wedge_point<T: AsOperationSequencer + Debug + Clone>(
+    point: &str,
+    guard: &mut OperationGuardArc<T>,
+) -> Result<(), SvcError> {
+    if armed(point) {
+        tracing::error!(
+            point,
+            "Wedging the operation at the armed fault injection point"
+        );
+        guard.leak_lock();
+        return Err(SvcError::Internal {
+            details: format!("operation wedged at the fault injection point '{}'", point),
+        });
+    }
+    Ok(())
+}
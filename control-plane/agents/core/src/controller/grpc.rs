@@ -9,12 +9,16 @@ use common::{
 use common_lib::{
     transport_api::{v0::BlockDevices, MessageId, ResourceKind},
     types::v0::transport::{
-        APIVersion, GetBlockDevices, Nexus, NodeId, PoolState, Register, Replica,
+        APIVersion, ChildUri, DiscoverPools, DiscoveredPool, GetBlockDevices, Nexus, NodeId,
+        PoolState, Register, Replica,
     },
 };
-use grpc::{context::timeout_grpc, operations::registration::traits::ApiVersion};
+use grpc::operations::registration::traits::ApiVersion;
 use rpc::{
-    io_engine::{IoEngineClient, ListBlockDevicesRequest as V0ListBlockDevicesRequest, Null},
+    io_engine::{
+        bdev_rpc_client::BdevRpcClient, BdevUri, IoEngineClient,
+        ListBlockDevicesRequest as V0ListBlockDevicesRequest, Null,
+    },
     v1::host::ListBlockDevicesRequest as V1ListBlockDevicesRequest,
 };
 use snafu::ResultExt;
@@ -57,7 +61,7 @@ impl GrpcContext {
         })?;
 
         let timeout = request
-            .map(|r| timeout_grpc(r, comms_timeouts.opts().clone()))
+            .map(|r| comms_timeouts.request_for(r))
             .unwrap_or_else(|| comms_timeouts.request());
 
         let endpoint = tonic::transport::Endpoint::from(uri)
@@ -98,6 +102,8 @@ impl GrpcContext {
 }
 
 pub(crate) type MayaClientV0 = IoEngineClient<Channel>;
+/// V0 BdevClient
+pub(crate) type BdevClientV0 = BdevRpcClient<Channel>;
 /// V1 HostClient
 pub(crate) type HostClient = rpc::v1::host::host_rpc_client::HostRpcClient<Channel>;
 
@@ -113,6 +119,8 @@ pub(crate) struct GrpcClient {
     context: GrpcContext,
     /// v0 gRPC IoEngine Client
     pub(crate) io_engine_v0: Option<MayaClientV0>,
+    /// v0 gRPC Bdev Client
+    bdev_v0: Option<BdevClientV0>,
     /// v1 gRPC IoEngine Client
     pub(crate) io_engine_v1: Option<MayaClientV1>,
 }
@@ -122,9 +130,9 @@ impl GrpcClient {
     pub(crate) async fn new(context: &GrpcContext) -> Result<Self, SvcError> {
         match context.api_version {
             APIVersion::V0 => {
-                let client_v0 = match tokio::time::timeout(
+                let channel = match tokio::time::timeout(
                     context.comms_timeouts.connect(),
-                    MayaClientV0::connect(context.endpoint.clone()),
+                    context.endpoint.connect(),
                 )
                 .await
                 {
@@ -141,7 +149,8 @@ impl GrpcClient {
 
                 Ok(Self {
                     context: context.clone(),
-                    io_engine_v0: Some(client_v0),
+                    io_engine_v0: Some(MayaClientV0::new(channel.clone())),
+                    bdev_v0: Some(BdevClientV0::new(channel)),
                     io_engine_v1: None,
                 })
             }
@@ -165,6 +174,7 @@ impl GrpcClient {
                 Ok(Self {
                     context: context.clone(),
                     io_engine_v0: None,
+                    bdev_v0: None,
                     io_engine_v1: Some(MayaClientV1 { host }),
                 })
             }
@@ -179,6 +189,14 @@ impl GrpcClient {
         }
     }
 
+    /// get the v0 api bdev client
+    fn bdev_client_v0(&self) -> Result<BdevClientV0, SvcError> {
+        match self.bdev_v0.clone() {
+            Some(client) => Ok(client),
+            None => Err(SvcError::InvalidArguments {}),
+        }
+    }
+
     /// get the v1 api client wrapper
     pub(crate) fn client_v1(&self) -> Result<MayaClientV1, SvcError> {
         match self.io_engine_v1.clone() {
@@ -254,6 +272,7 @@ impl GrpcClient {
                     id: self.context.node.clone(),
                     grpc_endpoint: self.context.endpoint.uri().to_string(),
                     api_versions: Some(vec![APIVersion::V0]),
+                    peer_latencies: vec![],
                 })
             }
             APIVersion::V1 => {
@@ -297,11 +316,66 @@ impl GrpcClient {
                             })
                             .collect(),
                     ),
+                    peer_latencies: vec![],
                 })
             }
         }
     }
 
+    /// probe whether the node can connect to the nexus child `uri`, by creating a bdev for it
+    /// which is destroyed straight away
+    pub(crate) async fn probe_child(&self, uri: &ChildUri) -> Result<(), SvcError> {
+        match self.context.api_version {
+            APIVersion::V0 => {
+                let mut client = self.bdev_client_v0()?;
+                let bdev = BdevUri {
+                    uri: uri.to_string(),
+                };
+                let result = match client.create(bdev.clone()).await {
+                    // the node is already connected to the child, eg: it's part of a nexus
+                    Err(status) if status.code() == tonic::Code::AlreadyExists => return Ok(()),
+                    result => result,
+                };
+                // the bdev may still be created after a timeout, so destroy it regardless
+                if let Err(error) = client.destroy(bdev).await {
+                    if result.is_ok() {
+                        tracing::warn!(
+                            node.id = %self.context.node,
+                            child.uri = %uri,
+                            error = %error,
+                            "Failed to destroy the bdev of the probed child"
+                        );
+                    }
+                }
+                result.context(GrpcRequestError {
+                    resource: ResourceKind::Child,
+                    request: "probe_child",
+                })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.context.node.clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Child,
+                request: "probe_child".to_string(),
+            }),
+        }
+    }
+
+    /// discover the pools which could be imported from the disks of the node
+    /// Neither api version can scan the disks for pool metadata, the pools are only listed once
+    /// they're imported, and so the discovery is reported as unsupported.
+    pub(crate) async fn discover_pools(
+        &self,
+        _request: &DiscoverPools,
+    ) -> Result<Vec<DiscoveredPool>, SvcError> {
+        match self.context.api_version {
+            APIVersion::V0 | APIVersion::V1 => Err(SvcError::PoolDiscoveryUnsupported {
+                node_id: self.context.node.clone(),
+            }),
+        }
+    }
+
     /// list replicas based on api version in context
     pub(crate) async fn list_replicas(&self, id: &NodeId) -> Result<Vec<Replica>, SvcError> {
         match self.context.api_version {
@@ -439,6 +513,10 @@ impl GrpcClientLocked {
     pub(crate) fn api_version(&self) -> APIVersion {
         self.context.api_version.clone()
     }
+    /// get the id of the node from grpc context
+    pub(crate) fn node(&self) -> &NodeId {
+        &self.context.node
+    }
 }
 
 impl Deref for GrpcClientLocked {
@@ -0,0 +1,148 @@
+use crate::controller::{registry::Registry, specs::SpecOperationsHelper};
+use common::errors::SvcError;
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::store::{
+        definitions::ObjectKey,
+        history::{SpecHistory, SpecHistoryKey, SpecRevision},
+    },
+};
+use std::time::SystemTime;
+
+impl Registry {
+    /// Add the `spec`, as persisted at `updated_at` by the `operation`, to its history, pruning
+    /// the oldest revisions so that no more than the configured number of revisions are retained.
+    /// Failing to do so is not fatal as the history is only kept to troubleshoot the resource.
+    pub(crate) async fn record_spec_revision<T: SpecOperationsHelper>(
+        &self,
+        spec: &T,
+        updated_at: SystemTime,
+        operation: Option<serde_json::Value>,
+    ) {
+        let result = async {
+            let mut history = self.spec_history(&spec.kind(), &spec.uuid_str()).await?;
+            let value = serde_json::to_value(spec).map_err(|error| SvcError::Internal {
+                details: error.to_string(),
+            })?;
+            let revision = history.push(updated_at, operation, value, self.spec_revisions());
+            self.store_obj(&history).await.map(|_| revision)
+        }
+        .await;
+        match result {
+            Ok(revision) => tracing::trace!(
+                kind = ?spec.kind(),
+                id = %spec.uuid_str(),
+                revision,
+                "Recorded a revision of the spec"
+            ),
+            Err(error) => tracing::warn!(
+                kind = ?spec.kind(),
+                id = %spec.uuid_str(),
+                %error,
+                "Failed to record a revision of the spec"
+            ),
+        }
+    }
+
+    /// Get the history of the spec of the resource `id` of the given `kind`, which is empty if no
+    /// revision was recorded yet.
+    pub(crate) async fn spec_history(
+        &self,
+        kind: &ResourceKind,
+        id: &str,
+    ) -> Result<SpecHistory, SvcError> {
+        match self
+            .load_obj::<SpecHistory>(&SpecHistoryKey::new(kind, id))
+            .await
+        {
+            Ok(history) => Ok(history),
+            Err(SvcError::StoreMissingEntry { .. }) => Ok(SpecHistory::new(kind.clone(), id)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Delete the history of the spec of the resource `id` of the given `kind`, once the resource
+    /// is destroyed. Failing to do so is not fatal, though the history is then leaked.
+    pub(crate) async fn delete_spec_history(&self, kind: &ResourceKind, id: &str) {
+        let key = SpecHistoryKey::new(kind, id);
+        if let Err(error) = self.delete_kv(&key.key()).await {
+            tracing::warn!(?kind, %id, %error, "Failed to delete the history of the spec");
+        }
+    }
+}
+
+/// The changes made to the spec by the `revision` of the `history`, as a JSON patch against its
+/// previous revision, if it's retained, otherwise against an empty spec.
+/// Returns the number of the previous revision along with the diff.
+pub(crate) fn spec_revision_diff(
+    history: &SpecHistory,
+    revision: u64,
+) -> Result<(Option<u64>, serde_json::Value), SvcError> {
+    let current = history
+        .revision(revision)
+        .ok_or_else(|| SvcError::SpecRevisionNotFound {
+            kind: history.kind.clone(),
+            id: history.id.clone(),
+            revision,
+        })?;
+    let empty = serde_json::json!({});
+    let (previous, before) = match history.previous(revision) {
+        Some(SpecRevision { revision, spec, .. }) => (Some(*revision), spec),
+        None => (None, &empty),
+    };
+    let diff = json_patch::diff(before, &current.spec);
+    let diff = serde_json::to_value(diff).map_err(|error| SvcError::Internal {
+        details: error.to_string(),
+    })?;
+    Ok((previous, diff))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn history(replicas: &[u8], retain: usize) -> SpecHistory {
+        let mut history = SpecHistory::new(ResourceKind::Volume, "volume");
+        for replicas in replicas {
+            let operation = json!({ "SetReplica": replicas });
+            let spec = json!({ "num_replicas": replicas, "size": 10 });
+            history.push(SystemTime::now(), Some(operation), spec, retain);
+        }
+        history
+    }
+
+    #[test]
+    fn revisions_are_pruned_oldest_first() {
+        let history = history(&[1, 2, 3, 2, 1], 3);
+        let revisions = history
+            .revisions
+            .iter()
+            .map(|r| (r.revision, r.spec["num_replicas"].clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(revisions, vec![(3, json!(3)), (4, json!(2)), (5, json!(1))]);
+    }
+
+    #[test]
+    fn revision_diffs() {
+        let history = history(&[1, 2, 3, 2, 1], 3);
+
+        let (previous, diff) = spec_revision_diff(&history, 4).unwrap();
+        assert_eq!(previous, Some(3));
+        assert_eq!(
+            diff,
+            json!([{ "op": "replace", "path": "/num_replicas", "value": 2 }])
+        );
+
+        // the previous revision of the oldest one retained was pruned
+        let (previous, diff) = spec_revision_diff(&history, 3).unwrap();
+        assert_eq!(previous, None);
+        assert_eq!(diff.as_array().map(Vec::len), Some(2));
+
+        let error = spec_revision_diff(&history, 2).unwrap_err();
+        assert!(matches!(
+            error,
+            SvcError::SpecRevisionNotFound { revision: 2, .. }
+        ));
+    }
+}
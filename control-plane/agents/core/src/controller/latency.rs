@@ -0,0 +1,185 @@
+use common_lib::types::v0::transport::{LatencyMatrix, NodeId, NodeLatencies, PeerLatency};
+use std::collections::HashMap;
+
+/// The round-trip times between the nodes, as last reported by each node on its registration.
+/// The round-trip times reported by a node replace those which it reported before.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct LatencyMap {
+    /// round-trip times in microseconds, per reporting node and per peer
+    rows: HashMap<NodeId, HashMap<NodeId, u64>>,
+}
+
+impl LatencyMap {
+    /// Replace the round-trip times reported by the `node`, forgetting them if it reported none
+    pub(crate) fn update(&mut self, node: &NodeId, peers: &[PeerLatency]) {
+        let row = peers
+            .iter()
+            .filter(|peer| &peer.node != node)
+            .map(|peer| (peer.node.clone(), peer.rtt_us))
+            .collect::<HashMap<_, _>>();
+        if row.is_empty() {
+            self.rows.remove(node);
+        } else {
+            self.rows.insert(node.clone(), row);
+        }
+    }
+    /// Forget the round-trip times reported by the `node`, as well as those to the `node`
+    pub(crate) fn remove(&mut self, node: &NodeId) {
+        self.rows.remove(node);
+        for row in self.rows.values_mut() {
+            row.remove(node);
+        }
+        self.rows.retain(|_, row| !row.is_empty());
+    }
+    /// Get the round-trip time between the nodes `a` and `b`, if measured by either of them.
+    /// When measured by both, the mean of both measurements is used.
+    pub(crate) fn rtt(&self, a: &NodeId, b: &NodeId) -> Option<u64> {
+        if a == b {
+            return Some(0);
+        }
+        let a_to_b = self.rows.get(a).and_then(|row| row.get(b));
+        let b_to_a = self.rows.get(b).and_then(|row| row.get(a));
+        match (a_to_b, b_to_a) {
+            (Some(a_to_b), Some(b_to_a)) => Some((a_to_b + b_to_a) / 2),
+            (Some(rtt), None) | (None, Some(rtt)) => Some(*rtt),
+            (None, None) => None,
+        }
+    }
+    /// Mean of all the measured round-trip times, if any
+    fn mean(&self) -> Option<u64> {
+        let (sum, count) = self
+            .rows
+            .values()
+            .flat_map(|row| row.values())
+            .fold((0u64, 0u64), |(sum, count), rtt| {
+                (sum.saturating_add(*rtt), count + 1)
+            });
+        sum.checked_div(count)
+    }
+    /// Get the aggregate round-trip time from the `node` to the `peers`, ie: the sum of the
+    /// round-trip times to each of them.
+    /// A round-trip time which was not measured counts as the mean of the measured ones, so that
+    /// it neither favours nor penalises the `node`. Nothing is aggregated when none was measured.
+    pub(crate) fn aggregate(&self, node: &NodeId, peers: &[NodeId]) -> Option<u64> {
+        let mean = self.mean()?;
+        Some(
+            peers
+                .iter()
+                .map(|peer| self.rtt(node, peer).unwrap_or(mean))
+                .fold(0u64, |sum, rtt| sum.saturating_add(rtt)),
+        )
+    }
+    /// Get the reported round-trip times, sorted by node
+    pub(crate) fn matrix(&self) -> LatencyMatrix {
+        let mut nodes = self
+            .rows
+            .iter()
+            .map(|(node, row)| {
+                let mut peers = row
+                    .iter()
+                    .map(|(peer, rtt_us)| PeerLatency {
+                        node: peer.clone(),
+                        rtt_us: *rtt_us,
+                    })
+                    .collect::<Vec<_>>();
+                peers.sort_by(|a, b| a.node.as_str().cmp(b.node.as_str()));
+                NodeLatencies {
+                    node: node.clone(),
+                    peers,
+                }
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.node.as_str().cmp(b.node.as_str()));
+        LatencyMatrix { nodes }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peers(rtts: &[(&str, u64)]) -> Vec<PeerLatency> {
+        rtts.iter()
+            .map(|(node, rtt_us)| PeerLatency {
+                node: (*node).into(),
+                rtt_us: *rtt_us,
+            })
+            .collect()
+    }
+
+    fn nodes(ids: &[&str]) -> Vec<NodeId> {
+        ids.iter().map(|id| (*id).into()).collect()
+    }
+
+    /// n1 and n2 are close to each other, n3 is far from both
+    fn matrix() -> LatencyMap {
+        let mut map = LatencyMap::default();
+        map.update(&"n1".into(), &peers(&[("n2", 100), ("n3", 1000)]));
+        map.update(&"n2".into(), &peers(&[("n1", 300), ("n3", 900)]));
+        map.update(&"n3".into(), &peers(&[("n1", 1000)]));
+        map
+    }
+
+    #[test]
+    fn rtt() {
+        let map = matrix();
+        // measured by both ends
+        assert_eq!(map.rtt(&"n1".into(), &"n2".into()), Some(200));
+        assert_eq!(map.rtt(&"n2".into(), &"n1".into()), Some(200));
+        // measured by a single end
+        assert_eq!(map.rtt(&"n3".into(), &"n2".into()), Some(900));
+        assert_eq!(map.rtt(&"n1".into(), &"n1".into()), Some(0));
+        assert_eq!(map.rtt(&"n1".into(), &"n4".into()), None);
+    }
+
+    #[test]
+    fn aggregate() {
+        let map = matrix();
+        let replicas = nodes(&["n1", "n2"]);
+        let n1 = map.aggregate(&"n1".into(), &replicas).unwrap();
+        let n2 = map.aggregate(&"n2".into(), &replicas).unwrap();
+        let n3 = map.aggregate(&"n3".into(), &replicas).unwrap();
+        assert_eq!((n1, n2, n3), (200, 200, 1900));
+        assert!(n1 < n3 && n2 < n3);
+
+        // n4 measured nothing, so it's deemed as far as the average pair of nodes
+        let mean = (100 + 1000 + 300 + 900 + 1000) / 5;
+        assert_eq!(map.aggregate(&"n4".into(), &replicas), Some(2 * mean));
+        assert!(map.aggregate(&"n4".into(), &replicas) < Some(n3));
+    }
+
+    #[test]
+    fn no_measurements_are_neutral() {
+        let map = LatencyMap::default();
+        let replicas = nodes(&["n1", "n2"]);
+        assert_eq!(map.aggregate(&"n1".into(), &replicas), None);
+        assert_eq!(map.aggregate(&"n3".into(), &replicas), None);
+    }
+
+    #[test]
+    fn update_and_remove() {
+        let mut map = matrix();
+        // a node reporting nothing forgets its previous report
+        map.update(&"n3".into(), &[]);
+        assert_eq!(map.rtt(&"n3".into(), &"n2".into()), Some(900));
+        assert_eq!(map.rtt(&"n3".into(), &"n1".into()), Some(1000));
+        map.update(&"n1".into(), &peers(&[("n1", 5), ("n2", 100)]));
+        assert_eq!(map.rtt(&"n3".into(), &"n1".into()), None);
+
+        map.remove(&"n2".into());
+        assert_eq!(map.rtt(&"n1".into(), &"n2".into()), None);
+        assert_eq!(map.matrix(), LatencyMatrix::default());
+    }
+
+    #[test]
+    fn sorted_matrix() {
+        let matrix = matrix().matrix();
+        let reporters = matrix
+            .nodes
+            .iter()
+            .map(|n| n.node.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(reporters, vec!["n1", "n2", "n3"]);
+        assert_eq!(matrix.nodes[0].peers, peers(&[("n2", 100), ("n3", 1000)]));
+    }
+}
@@ -1,21 +1,49 @@
 //! Common modules used by the different core services
 
+/// kubernetes style conditions of the resources, and the time of their last transition
+pub(crate) mod conditions;
+/// resolution of the resources reported differently from their specs
+pub(crate) mod conflicts;
+/// points where the tests may crash the agent, or wedge an operation, midway through it
+pub(crate) mod fault_injection;
 /// gRPC helpers
 pub(crate) mod grpc;
+/// history of the revisions of the resource specs
+pub(crate) mod history;
+/// round-trip times between the nodes
+pub(crate) mod latency;
 pub(crate) mod operations;
+/// detection of the replicas and nexuses which only exist as a spec or as a dataplane object
+pub(crate) mod orphans;
+/// runtime switches of the reconciler passes
+pub(crate) mod passes;
+/// ordering of the volumes waiting for a rebuild, by their rebuild priority
+pub(crate) mod rebuild_queue;
 /// reconciliation logic
 pub(crate) mod reconciler;
+/// minimum redundancy of the volumes whose replicas are removed
+pub(crate) mod redundancy;
 /// registry with node and all its resources
 pub(crate) mod registry;
+/// bounded retry of the replays of the operations left pending on the specs
+pub(crate) mod replay;
 /// generic resources
 mod resource_map;
 /// helpers for node/pool/replica scheduling
 pub(crate) mod scheduling;
+/// helpers for sorting the listed resources
+pub(crate) mod sorting;
 /// registry with all the resource specs
 pub(crate) mod specs;
 /// registry with all the resource states
 pub(crate) mod states;
+/// read-only operation whilst the persistent store is unavailable
+pub(crate) mod store_policy;
+/// changes of the device uri of the targets of the volumes
+pub(crate) mod targets;
 /// generic task pollers (eg used by the reconcilers)
 mod task_poller;
+/// handling of the volumes whose topology can't be satisfied
+pub(crate) mod topology;
 /// helper wrappers over the resources
 pub(crate) mod wrapper;
@@ -63,6 +63,19 @@ pub(crate) trait ResourcePublishing {
     ) -> Result<(), SvcError>;
 }
 
+/// Resource IO Pausing Operations.
+#[async_trait::async_trait]
+pub(crate) trait ResourcePausing {
+    type Pause: Sync + Send;
+    type Resume: Sync + Send;
+
+    /// Pause the IO of the resource.
+    async fn pause(&mut self, registry: &Registry, request: &Self::Pause) -> Result<(), SvcError>;
+    /// Resume the paused IO of the resource.
+    async fn resume(&mut self, registry: &Registry, request: &Self::Resume)
+        -> Result<(), SvcError>;
+}
+
 /// Resource Replica Operations.
 #[async_trait::async_trait]
 pub(crate) trait ResourceReplicas {
@@ -96,3 +109,23 @@ pub(crate) trait ResourceOffspring {
         request: &Self::Remove,
     ) -> Result<(), SvcError>;
 }
+
+/// Resource Children/Offspring Availability Operations.
+#[async_trait::async_trait]
+pub(crate) trait ResourceOffspringAvailability {
+    type Online: Sync + Send;
+    type Offline: Sync + Send;
+
+    /// Bring an offline child of the resource back online.
+    async fn online_child(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Online,
+    ) -> Result<(), SvcError>;
+    /// Take a child of the resource offline, keeping it as a child of the resource.
+    async fn offline_child(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Offline,
+    ) -> Result<(), SvcError>;
+}
@@ -0,0 +1,608 @@
+use crate::controller::{registry::Registry, wrapper::GetterOps};
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{nexus::NexusSpec, replica::ReplicaSpec},
+        transport::{
+            Nexus, NexusId, NodeId, Orphan, OrphanCategory, OrphanRemediation, OrphanReport,
+            PoolState, Replica, ReplicaId, ResourceOrphans,
+        },
+    },
+};
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, SystemTime},
+};
+
+/// How much longer the resource created at `created_at` may be absent from the state of its node
+/// before it's deemed missing, as `now` is within the `grace_period` of its creation and the node
+/// may not have reported it yet.
+/// The resources whose creation time is not known are given no grace.
+pub(crate) fn creation_grace_left(
+    created_at: Option<SystemTime>,
+    grace_period: Duration,
+    now: SystemTime,
+) -> Option<Duration> {
+    let created_at = created_at?;
+    // a creation time ahead of now, eg: after a clock step, counts as a creation just now
+    let elapsed = now.duration_since(created_at).unwrap_or_default();
+    grace_period
+        .checked_sub(elapsed)
+        .filter(|left| !left.is_zero())
+}
+
+/// The specs and the dataplane objects which the orphans are detected from, all taken at once.
+/// Only the online nodes are accounted for, as the objects of the others are not known.
+#[derive(Debug, Default)]
+pub(crate) struct OrphanSnapshot {
+    now: SystemTime,
+    grace_period: Duration,
+    online_nodes: HashSet<NodeId>,
+    pools: Vec<PoolState>,
+    replicas: Vec<Replica>,
+    nexuses: Vec<Nexus>,
+    replica_specs: Vec<ReplicaSpec>,
+    nexus_specs: Vec<NexusSpec>,
+    /// the nexuses which are the target of a volume
+    volume_targets: HashSet<NexusId>,
+    tombstoned_replicas: HashSet<ReplicaId>,
+    tombstoned_nexuses: HashSet<NexusId>,
+    /// the replicas which are, or whose pool is, reported differently by different nodes
+    conflicted_replicas: HashSet<ReplicaId>,
+}
+
+impl OrphanSnapshot {
+    /// Take a snapshot of the specs and of the dataplane objects of the online nodes
+    pub(crate) async fn new(registry: &Registry) -> Self {
+        let mut snapshot = Self {
+            now: SystemTime::now(),
+            grace_period: registry.replica_creation_grace_period(),
+            ..Default::default()
+        };
+        for node in registry.get_node_wrappers().await {
+            if !node.read().await.is_online() {
+                continue;
+            }
+            snapshot.online_nodes.insert(node.read().await.id().clone());
+            snapshot.pools.extend(node.pools().await);
+            snapshot.replicas.extend(node.replicas().await);
+            snapshot.nexuses.extend(node.nexuses().await);
+        }
+
+        let specs = registry.specs();
+        snapshot.replica_specs = specs
+            .get_replicas()
+            .into_iter()
+            .map(|replica| replica.lock().clone())
+            .collect();
+        snapshot.nexus_specs = specs
+            .get_nexuses()
+            .into_iter()
+            .map(|nexus| nexus.lock().clone())
+            .collect();
+        snapshot.volume_targets = specs
+            .get_volumes()
+            .into_iter()
+            .filter_map(|volume| volume.target.map(|target| target.nexus().clone()))
+            .collect();
+
+        let replica_ids = snapshot
+            .replica_specs
+            .iter()
+            .map(|spec| &spec.uuid)
+            .chain(snapshot.replicas.iter().map(|replica| &replica.uuid));
+        for uuid in replica_ids {
+            if specs.tombstoned(registry, &ResourceKind::Replica, uuid.as_str()) {
+                snapshot.tombstoned_replicas.insert(uuid.clone());
+            }
+            if registry.check_replica_conflict(uuid).is_err() {
+                snapshot.conflicted_replicas.insert(uuid.clone());
+            }
+        }
+        for replica in &snapshot.replicas {
+            if registry.check_pool_conflict(&replica.pool).is_err() {
+                snapshot.conflicted_replicas.insert(replica.uuid.clone());
+            }
+        }
+        for spec in &snapshot.replica_specs {
+            if registry.check_pool_conflict(&spec.pool).is_err() {
+                snapshot.conflicted_replicas.insert(spec.uuid.clone());
+            }
+        }
+        let nexus_ids = snapshot
+            .nexus_specs
+            .iter()
+            .map(|spec| &spec.uuid)
+            .chain(snapshot.nexuses.iter().map(|nexus| &nexus.uuid));
+        for uuid in nexus_ids {
+            if specs.tombstoned(registry, &ResourceKind::Nexus, uuid.as_str()) {
+                snapshot.tombstoned_nexuses.insert(uuid.clone());
+            }
+        }
+        snapshot
+    }
+
+    /// Seconds elapsed at the time of the snapshot since `since`, if known
+    fn age_secs(&self, since: Option<SystemTime>) -> Option<u64> {
+        since.map(|since| self.now.duration_since(since).unwrap_or_default().as_secs())
+    }
+
+    /// Get the replica specs whose replica is missing, though the pool where it should live is
+    /// reported by its node.
+    pub(crate) fn control_plane_replicas(&self) -> Vec<Orphan> {
+        self.replica_specs
+            .iter()
+            .filter(|spec| spec.status.created())
+            .filter(|spec| !self.replicas.iter().any(|r| r.uuid == spec.uuid))
+            .filter(|spec| {
+                creation_grace_left(spec.created_at, self.grace_period, self.now).is_none()
+            })
+            .filter_map(|spec| {
+                // without its pool, the replica's absence is down to the pool's
+                let pool = self.pools.iter().find(|pool| pool.id == spec.pool)?;
+                let (remediation, reason) = if self.conflicted_replicas.contains(&spec.uuid) {
+                    (
+                        OrphanRemediation::Ignore,
+                        "the replica or its pool is in conflict, which must be resolved by hand",
+                    )
+                } else if self.tombstoned_replicas.contains(&spec.uuid) {
+                    (
+                        OrphanRemediation::Destroy,
+                        "stale spec of a replica which has since been destroyed",
+                    )
+                } else if spec.managed && spec.owners.is_owned() {
+                    (
+                        OrphanRemediation::Recreate,
+                        "the replica is owned, so it's recreated on its pool",
+                    )
+                } else {
+                    (
+                        OrphanRemediation::Destroy,
+                        "the replica is not owned, so its spec is no longer needed",
+                    )
+                };
+                Some(Orphan {
+                    id: spec.uuid.to_string(),
+                    category: OrphanCategory::ControlPlane,
+                    node: Some(pool.node.clone()),
+                    pool: Some(spec.pool.clone()),
+                    age_secs: self.age_secs(spec.created_at),
+                    remediation,
+                    reason: reason.to_string(),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the replicas which have no spec.
+    pub(crate) fn dataplane_replicas(&self) -> Vec<Orphan> {
+        self.replicas
+            .iter()
+            .filter(|replica| !self.replica_specs.iter().any(|s| s.uuid == replica.uuid))
+            .map(|replica| {
+                let parent = self
+                    .nexus_specs
+                    .iter()
+                    .find(|nexus| nexus.contains_replica(&replica.uuid));
+                let (remediation, reason) = if self.conflicted_replicas.contains(&replica.uuid) {
+                    (
+                        OrphanRemediation::Ignore,
+                        "the replica or its pool is in conflict, which must be resolved by hand"
+                            .to_string(),
+                    )
+                } else if self.tombstoned_replicas.contains(&replica.uuid) {
+                    (
+                        OrphanRemediation::Destroy,
+                        "stale copy of a replica which has since been destroyed".to_string(),
+                    )
+                } else if let Some(nexus) = parent {
+                    (
+                        OrphanRemediation::Adopt,
+                        format!("the replica is a child of the nexus '{}'", nexus.uuid),
+                    )
+                } else {
+                    (
+                        OrphanRemediation::Ignore,
+                        "nothing refers to the replica, so it's not ours to manage".to_string(),
+                    )
+                };
+                Orphan {
+                    id: replica.uuid.to_string(),
+                    category: OrphanCategory::Dataplane,
+                    node: Some(replica.node.clone()),
+                    pool: Some(replica.pool.clone()),
+                    age_secs: None,
+                    remediation,
+                    reason,
+                }
+            })
+            .collect()
+    }
+
+    /// Get the nexus specs whose nexus is missing from their online node.
+    pub(crate) fn control_plane_nexuses(&self) -> Vec<Orphan> {
+        self.nexus_specs
+            .iter()
+            .filter(|spec| spec.spec_status.created())
+            .filter(|spec| self.online_nodes.contains(&spec.node))
+            .filter(|spec| !self.nexuses.iter().any(|n| n.uuid == spec.uuid))
+            .filter(|spec| {
+                creation_grace_left(spec.created_at, self.grace_period, self.now).is_none()
+            })
+            .map(|spec| {
+                let (remediation, reason) = if self.tombstoned_nexuses.contains(&spec.uuid) {
+                    (
+                        OrphanRemediation::Destroy,
+                        "stale spec of a nexus which has since been destroyed",
+                    )
+                } else if !spec.managed {
+                    (
+                        OrphanRemediation::Destroy,
+                        "the nexus is not managed, so it's not recreated",
+                    )
+                } else if spec.owner.is_some() && !self.volume_targets.contains(&spec.uuid) {
+                    (
+                        OrphanRemediation::Destroy,
+                        "the nexus is no longer the target of its volume",
+                    )
+                } else {
+                    (
+                        OrphanRemediation::Recreate,
+                        "the nexus is managed, so it's recreated on its node",
+                    )
+                };
+                Orphan {
+                    id: spec.uuid.to_string(),
+                    category: OrphanCategory::ControlPlane,
+                    node: Some(spec.node.clone()),
+                    pool: None,
+                    age_secs: self.age_secs(spec.created_at),
+                    remediation,
+                    reason: reason.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the nexuses which have no spec.
+    pub(crate) fn dataplane_nexuses(&self) -> Vec<Orphan> {
+        self.nexuses
+            .iter()
+            .filter(|nexus| !self.nexus_specs.iter().any(|s| s.uuid == nexus.uuid))
+            .map(|nexus| {
+                let (remediation, reason) = if self.tombstoned_nexuses.contains(&nexus.uuid) {
+                    (
+                        OrphanRemediation::Destroy,
+                        "stale copy of a nexus which has since been destroyed",
+                    )
+                } else if self.volume_targets.contains(&nexus.uuid) {
+                    (
+                        OrphanRemediation::Adopt,
+                        "the nexus is the target of a volume",
+                    )
+                } else {
+                    (
+                        OrphanRemediation::Ignore,
+                        "nothing refers to the nexus, so it's not ours to manage",
+                    )
+                };
+                Orphan {
+                    id: nexus.uuid.to_string(),
+                    category: OrphanCategory::Dataplane,
+                    node: Some(nexus.node.clone()),
+                    pool: None,
+                    age_secs: None,
+                    remediation,
+                    reason: reason.to_string(),
+                }
+            })
+            .collect()
+    }
+
+    /// Get the report of all the orphans, of all kinds and categories
+    pub(crate) fn report(&self) -> OrphanReport {
+        let mut replicas = self.control_plane_replicas();
+        replicas.extend(self.dataplane_replicas());
+        let mut nexuses = self.control_plane_nexuses();
+        nexuses.extend(self.dataplane_nexuses());
+        OrphanReport {
+            replicas: ResourceOrphans::new(replicas),
+            nexuses: ResourceOrphans::new(nexuses),
+        }
+    }
+}
+
+/// When each of the dataplane orphans was first reported.
+/// The dataplane objects don't record when they were created, so the age of a dataplane orphan is
+/// counted from the first report which it appeared in.
+#[derive(Debug, Default)]
+pub(crate) struct OrphanSightings {
+    /// first sighting of the orphans, per kind and id
+    seen: HashMap<(&'static str, String), SystemTime>,
+}
+
+impl OrphanSightings {
+    /// Fill in the age of the dataplane orphans of the `report` as of `now`, forgetting those
+    /// which are no longer orphans.
+    pub(crate) fn observe(&mut self, report: &mut OrphanReport, now: SystemTime) {
+        let previous = std::mem::take(&mut self.seen);
+        let kinds = [
+            ("replica", &mut report.replicas),
+            ("nexus", &mut report.nexuses),
+        ];
+        for (kind, orphans) in kinds {
+            for orphan in &mut orphans.dataplane {
+                let key = (kind, orphan.id.clone());
+                let since = previous.get(&key).copied().unwrap_or(now);
+                orphan.age_secs = Some(now.duration_since(since).unwrap_or_default().as_secs());
+                self.seen.insert(key, since);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::{
+        store::{nexus::ReplicaUri, nexus_child::NexusChild, SpecStatus},
+        transport::{NexusStatus, PoolId, ReplicaOwners, ReplicaStatus, VolumeId},
+    };
+
+    const GRACE: Duration = Duration::from_secs(10);
+
+    fn at(secs: u64) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_secs(secs)
+    }
+
+    fn replica_spec(uuid: &ReplicaId, pool: &str, managed: bool, owned: bool) -> ReplicaSpec {
+        ReplicaSpec {
+            uuid: uuid.clone(),
+            pool: pool.into(),
+            managed,
+            owners: if owned {
+                ReplicaOwners::from_volume(&VolumeId::new())
+            } else {
+                ReplicaOwners::default()
+            },
+            status: SpecStatus::Created(ReplicaStatus::Online),
+            created_at: Some(at(100)),
+            ..Default::default()
+        }
+    }
+
+    fn replica(uuid: &ReplicaId, pool: &str) -> Replica {
+        Replica {
+            uuid: uuid.clone(),
+            pool: pool.into(),
+            node: "n1".into(),
+            ..Default::default()
+        }
+    }
+
+    fn nexus_spec(uuid: &NexusId, owner: Option<VolumeId>) -> NexusSpec {
+        NexusSpec {
+            uuid: uuid.clone(),
+            node: "n1".into(),
+            managed: true,
+            owner,
+            spec_status: SpecStatus::Created(NexusStatus::Online),
+            created_at: Some(at(100)),
+            ..Default::default()
+        }
+    }
+
+    fn nexus(uuid: &NexusId) -> Nexus {
+        Nexus {
+            uuid: uuid.clone(),
+            node: "n1".into(),
+            ..Default::default()
+        }
+    }
+
+    /// Snapshot at 1000 secs past the epoch of the node n1 which is online with the pool p1
+    fn snapshot() -> OrphanSnapshot {
+        OrphanSnapshot {
+            now: at(1000),
+            grace_period: GRACE,
+            online_nodes: vec!["n1".into()].into_iter().collect(),
+            pools: vec![PoolState {
+                node: "n1".into(),
+                id: "p1".into(),
+                ..Default::default()
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn remediations(orphans: &[Orphan]) -> Vec<(String, OrphanRemediation)> {
+        orphans
+            .iter()
+            .map(|orphan| (orphan.id.clone(), orphan.remediation))
+            .collect()
+    }
+
+    #[test]
+    fn absent_within_creation_grace() {
+        let now = SystemTime::now();
+        let created_at = Some(now - Duration::from_secs(4));
+        assert_eq!(
+            creation_grace_left(created_at, GRACE, now),
+            Some(Duration::from_secs(6))
+        );
+
+        // created "in the future" as the clock went backwards since
+        let created_at = Some(now + Duration::from_secs(4));
+        assert_eq!(creation_grace_left(created_at, GRACE, now), Some(GRACE));
+    }
+
+    #[test]
+    fn absent_beyond_creation_grace() {
+        let now = SystemTime::now();
+        assert_eq!(creation_grace_left(Some(now - GRACE), GRACE, now), None);
+        let created_at = Some(now - Duration::from_secs(60));
+        assert_eq!(creation_grace_left(created_at, GRACE, now), None);
+
+        // no grace without a creation time, nor when the grace period is disabled
+        assert_eq!(creation_grace_left(None, GRACE, now), None);
+        assert_eq!(creation_grace_left(Some(now), Duration::ZERO, now), None);
+    }
+
+    #[test]
+    fn control_plane_replicas() {
+        let mut snapshot = snapshot();
+        let ids = (0 .. 7).map(|_| ReplicaId::new()).collect::<Vec<_>>();
+        let mut just_created = replica_spec(&ids[4], "p1", true, true);
+        just_created.created_at = Some(at(995));
+        snapshot.replica_specs = vec![
+            replica_spec(&ids[0], "p1", true, true),
+            replica_spec(&ids[1], "p1", true, false),
+            replica_spec(&ids[2], "p1", true, true),
+            replica_spec(&ids[3], "p2", true, true),
+            just_created,
+            replica_spec(&ids[5], "p1", true, true),
+            replica_spec(&ids[6], "p1", true, true),
+        ];
+        snapshot.replicas = vec![replica(&ids[2], "p1")];
+        snapshot.tombstoned_replicas.insert(ids[5].clone());
+        snapshot.conflicted_replicas.insert(ids[6].clone());
+
+        // the replicas which are present, whose pool is not, or which were just created are not
+        // orphans
+        let orphans = snapshot.control_plane_replicas();
+        assert_eq!(
+            remediations(&orphans),
+            vec![
+                (ids[0].to_string(), OrphanRemediation::Recreate),
+                (ids[1].to_string(), OrphanRemediation::Destroy),
+                (ids[5].to_string(), OrphanRemediation::Destroy),
+                (ids[6].to_string(), OrphanRemediation::Ignore),
+            ]
+        );
+        assert_eq!(orphans[0].category, OrphanCategory::ControlPlane);
+        assert_eq!(orphans[0].node, Some("n1".into()));
+        assert_eq!(orphans[0].pool, Some(PoolId::from("p1")));
+        assert_eq!(orphans[0].age_secs, Some(900));
+    }
+
+    #[test]
+    fn dataplane_replicas() {
+        let mut snapshot = snapshot();
+        let ids = (0 .. 4).map(|_| ReplicaId::new()).collect::<Vec<_>>();
+        let mut parent = nexus_spec(&NexusId::new(), None);
+        parent.children = vec![NexusChild::Replica(ReplicaUri::new(
+            &ids[0],
+            &"nvmf://n1/replica".into(),
+        ))];
+        snapshot.nexus_specs = vec![parent];
+        snapshot.replica_specs = vec![replica_spec(&ids[3], "p1", true, true)];
+        snapshot.replicas = ids.iter().map(|id| replica(id, "p1")).collect();
+        snapshot.tombstoned_replicas.insert(ids[1].clone());
+
+        let orphans = snapshot.dataplane_replicas();
+        assert_eq!(
+            remediations(&orphans),
+            vec![
+                (ids[0].to_string(), OrphanRemediation::Adopt),
+                (ids[1].to_string(), OrphanRemediation::Destroy),
+                (ids[2].to_string(), OrphanRemediation::Ignore),
+            ]
+        );
+        assert!(orphans
+            .iter()
+            .all(|o| o.category == OrphanCategory::Dataplane && o.age_secs.is_none()));
+    }
+
+    #[test]
+    fn nexuses() {
+        let mut snapshot = snapshot();
+        let ids = (0 .. 6).map(|_| NexusId::new()).collect::<Vec<_>>();
+        let mut unmanaged = nexus_spec(&ids[2], None);
+        unmanaged.managed = false;
+        let mut offline = nexus_spec(&ids[3], None);
+        offline.node = "n2".into();
+        snapshot.nexus_specs = vec![
+            nexus_spec(&ids[0], Some(VolumeId::new())),
+            nexus_spec(&ids[1], Some(VolumeId::new())),
+            unmanaged,
+            offline,
+        ];
+        snapshot.volume_targets = vec![ids[0].clone(), ids[4].clone()].into_iter().collect();
+        snapshot.nexuses = vec![nexus(&ids[4]), nexus(&ids[5])];
+
+        // the nexus on the node which is not online is not known to be missing
+        assert_eq!(
+            remediations(&snapshot.control_plane_nexuses()),
+            vec![
+                (ids[0].to_string(), OrphanRemediation::Recreate),
+                (ids[1].to_string(), OrphanRemediation::Destroy),
+                (ids[2].to_string(), OrphanRemediation::Destroy),
+            ]
+        );
+        assert_eq!(
+            remediations(&snapshot.dataplane_nexuses()),
+            vec![
+                (ids[4].to_string(), OrphanRemediation::Adopt),
+                (ids[5].to_string(), OrphanRemediation::Ignore),
+            ]
+        );
+    }
+
+    #[test]
+    fn report_per_kind_and_category() {
+        let mut snapshot = snapshot();
+        let (missing, unexpected) = (ReplicaId::new(), ReplicaId::new());
+        snapshot.replica_specs = vec![replica_spec(&missing, "p1", true, true)];
+        snapshot.replicas = vec![replica(&unexpected, "p1")];
+        snapshot.nexuses = vec![nexus(&NexusId::new())];
+
+        let report = snapshot.report();
+        assert_eq!(report.replicas.control_plane.len(), 1);
+        assert_eq!(report.replicas.control_plane[0].id, missing.to_string());
+        assert_eq!(report.replicas.dataplane.len(), 1);
+        assert_eq!(report.replicas.dataplane[0].id, unexpected.to_string());
+        assert!(report.nexuses.control_plane.is_empty());
+        assert_eq!(report.nexuses.dataplane.len(), 1);
+    }
+
+    #[test]
+    fn dataplane_ages_from_first_sighting() {
+        let mut snapshot = snapshot();
+        let (first, second) = (ReplicaId::new(), ReplicaId::new());
+        let mut sightings = OrphanSightings::default();
+
+        snapshot.replicas = vec![replica(&first, "p1")];
+        let mut report = snapshot.report();
+        sightings.observe(&mut report, at(1000));
+        assert_eq!(report.replicas.dataplane[0].age_secs, Some(0));
+
+        snapshot.replicas.push(replica(&second, "p1"));
+        let mut report = snapshot.report();
+        sightings.observe(&mut report, at(1030));
+        let ages = report
+            .replicas
+            .dataplane
+            .iter()
+            .map(|o| o.age_secs)
+            .collect::<Vec<_>>();
+        assert_eq!(ages, vec![Some(30), Some(0)]);
+
+        // an orphan which goes away is forgotten, so it's deemed new if it comes back
+        snapshot.replicas.remove(0);
+        let mut report = snapshot.report();
+        sightings.observe(&mut report, at(1060));
+        snapshot.replicas.push(replica(&first, "p1"));
+        let mut report = snapshot.report();
+        sightings.observe(&mut report, at(1090));
+        let ages = report
+            .replicas
+            .dataplane
+            .iter()
+            .map(|o| (o.id.clone(), o.age_secs))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            ages,
+            vec![(second.to_string(), Some(60)), (first.to_string(), Some(0))]
+        );
+    }
+}
@@ -0,0 +1,86 @@
+use common_lib::types::v0::{
+    store::reconciler::{ReconcilerPass, ReconcilerPasses},
+    transport::{ReconcilerPassState, ReconcilerPassStates},
+};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// How often a reminder is logged whilst a reconciler pass is disabled.
+const DISABLED_REMINDER_PERIOD: Duration = Duration::from_secs(60);
+
+/// Runtime switches of the reconciler passes, along with the work skipped by the disabled ones.
+#[derive(Debug, Default)]
+pub(crate) struct ReconcilerSwitches {
+    passes: ReconcilerPasses,
+    skipped: HashMap<ReconcilerPass, SkippedPass>,
+}
+
+/// Work skipped by a disabled reconciler pass.
+#[derive(Debug, Default)]
+struct SkippedPass {
+    /// number of skipped runs
+    runs: u64,
+    /// when the pass was last reported as disabled
+    reminded: Option<Instant>,
+}
+
+impl ReconcilerSwitches {
+    /// Create a new `Self` with the given `passes` switches.
+    pub(crate) fn new(passes: ReconcilerPasses) -> Self {
+        Self {
+            passes,
+            skipped: Default::default(),
+        }
+    }
+    /// Get the switches of the passes.
+    pub(crate) fn passes(&self) -> &ReconcilerPasses {
+        &self.passes
+    }
+    /// Enable or disable the given `pass`.
+    pub(crate) fn set(&mut self, pass: ReconcilerPass, enabled: bool) {
+        self.passes.set(pass, enabled);
+        if enabled {
+            if let Some(skipped) = self.skipped.get_mut(&pass) {
+                skipped.reminded = None;
+            }
+        }
+    }
+    /// Check whether a run of the given `pass` may go ahead.
+    /// Otherwise the run is counted as skipped and a reminder that the pass is disabled is logged
+    /// every so often.
+    pub(crate) fn run(&mut self, pass: ReconcilerPass) -> bool {
+        if self.passes.enabled(pass) {
+            return true;
+        }
+        let skipped = self.skipped.entry(pass).or_default();
+        skipped.runs += 1;
+        let remind = match skipped.reminded {
+            Some(reminded) => reminded.elapsed() >= DISABLED_REMINDER_PERIOD,
+            None => true,
+        };
+        if remind {
+            skipped.reminded = Some(Instant::now());
+            tracing::warn!(
+                pass = %pass,
+                skipped = skipped.runs,
+                "Reconciler pass is disabled, skipping its work"
+            );
+        }
+        false
+    }
+    /// Get the state of all the passes.
+    pub(crate) fn states(&self) -> ReconcilerPassStates {
+        ReconcilerPassStates {
+            passes: ReconcilerPass::ALL
+                .iter()
+                .map(|pass| ReconcilerPassState {
+                    pass: *pass,
+                    enabled: self.passes.enabled(*pass),
+                    skipped: self.skipped.get(pass).map(|s| s.runs).unwrap_or_default(),
+                })
+                .collect(),
+        }
+    }
+}
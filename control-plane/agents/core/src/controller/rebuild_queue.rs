@@ -0,0 +1,242 @@
+use common_lib::types::v0::transport::{RebuildPriority, VolumeId};
+use std::{
+    cmp::Reverse,
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// Rank of a volume waiting for a rebuild, the lowest rank is the first to get a rebuild slot.
+pub(crate) type RebuildRank = (Reverse<RebuildPriority>, Instant);
+
+/// A volume waiting for a rebuild slot.
+#[derive(Debug, Clone, Copy)]
+struct RebuildWaiter {
+    priority: RebuildPriority,
+    /// when the volume started waiting
+    since: Instant,
+    /// when the volume last asked for a slot
+    seen: Instant,
+}
+
+/// The volumes waiting for a rebuild slot, when the number of rebuilds is limited.
+/// The free slots go to the volumes of the highest rebuild priority, the longest waiting first.
+/// A volume which has been waiting for longer than the aging threshold is deemed of the highest
+/// priority, so the volumes of lower priority can't be starved by those of higher priority.
+/// A volume which stops asking for a slot, eg: as it was deleted, is forgotten once stale.
+#[derive(Debug)]
+pub(crate) struct RebuildQueue {
+    aging: Duration,
+    stale: Duration,
+    waiting: HashMap<VolumeId, RebuildWaiter>,
+}
+
+impl RebuildQueue {
+    /// Return a new `Self` with the given `aging` threshold, forgetting the volumes which have
+    /// not asked for a slot for as long as `stale`
+    pub(crate) fn new(aging: Duration, stale: Duration) -> Self {
+        Self {
+            aging,
+            stale,
+            waiting: HashMap::new(),
+        }
+    }
+    /// Get the priority of a volume of the given `priority` which has been waiting `since`
+    fn effective_priority(
+        &self,
+        priority: RebuildPriority,
+        since: Instant,
+        now: Instant,
+    ) -> RebuildPriority {
+        if now.saturating_duration_since(since) >= self.aging {
+            RebuildPriority::High
+        } else {
+            priority
+        }
+    }
+    /// Get the rank of the `volume` of the given `priority`, ranked as if it had just started
+    /// waiting when it's not waiting.
+    pub(crate) fn rank(
+        &self,
+        volume: &VolumeId,
+        priority: RebuildPriority,
+        now: Instant,
+    ) -> RebuildRank {
+        let since = self.waiting.get(volume).map_or(now, |waiter| waiter.since);
+        (
+            Reverse(self.effective_priority(priority, since, now)),
+            since,
+        )
+    }
+    /// Ask for a rebuild slot for the `volume` of the given `priority`, out of the number of
+    /// free `slots`. The volume is granted a slot if fewer volumes than the free slots are ranked
+    /// before it, otherwise it's left waiting and the number of volumes ranked before it is
+    /// returned.
+    pub(crate) fn admit(
+        &mut self,
+        volume: &VolumeId,
+        priority: RebuildPriority,
+        slots: u32,
+        now: Instant,
+    ) -> Result<(), usize> {
+        let stale = self.stale;
+        self.waiting
+            .retain(|_, waiter| now.saturating_duration_since(waiter.seen) < stale);
+        let waiter = self.waiting.entry(volume.clone()).or_insert(RebuildWaiter {
+            priority,
+            since: now,
+            seen: now,
+        });
+        waiter.priority = priority;
+        waiter.seen = now;
+
+        let rank = self.rank(volume, priority, now);
+        let ahead = self
+            .waiting
+            .iter()
+            .filter(|(id, _)| id != &volume)
+            .filter(|(_, waiter)| {
+                let waiter_rank = (
+                    Reverse(self.effective_priority(waiter.priority, waiter.since, now)),
+                    waiter.since,
+                );
+                waiter_rank < rank
+            })
+            .count();
+        if ahead < slots as usize {
+            self.waiting.remove(volume);
+            Ok(())
+        } else {
+            Err(ahead)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AGING: Duration = Duration::from_secs(600);
+    const STALE: Duration = Duration::from_secs(60);
+
+    fn queue() -> RebuildQueue {
+        RebuildQueue::new(AGING, STALE)
+    }
+
+    #[test]
+    fn priority_then_age() {
+        let mut queue = queue();
+        let start = Instant::now();
+        let at = |secs| start + Duration::from_secs(secs);
+        let (low, normal, high, newer_normal) = (
+            VolumeId::new(),
+            VolumeId::new(),
+            VolumeId::new(),
+            VolumeId::new(),
+        );
+
+        // all the slots are taken, so they're all left waiting
+        assert_eq!(queue.admit(&low, RebuildPriority::Low, 0, at(0)), Err(0));
+        assert_eq!(
+            queue.admit(&normal, RebuildPriority::Normal, 0, at(1)),
+            Err(0)
+        );
+        assert_eq!(queue.admit(&high, RebuildPriority::High, 0, at(2)), Err(0));
+        assert_eq!(
+            queue.admit(&newer_normal, RebuildPriority::Normal, 0, at(3)),
+            Err(2)
+        );
+
+        // a single slot is freed, which only the high priority volume gets
+        assert_eq!(queue.admit(&low, RebuildPriority::Low, 1, at(4)), Err(3));
+        assert_eq!(
+            queue.admit(&newer_normal, RebuildPriority::Normal, 1, at(4)),
+            Err(2)
+        );
+        assert_eq!(
+            queue.admit(&normal, RebuildPriority::Normal, 1, at(4)),
+            Err(1)
+        );
+        assert_eq!(queue.admit(&high, RebuildPriority::High, 1, at(4)), Ok(()));
+
+        // the volumes of the same priority are ranked by how long they've been waiting
+        assert_eq!(
+            queue.admit(&newer_normal, RebuildPriority::Normal, 1, at(5)),
+            Err(1)
+        );
+        assert_eq!(
+            queue.admit(&normal, RebuildPriority::Normal, 1, at(5)),
+            Ok(())
+        );
+        assert_eq!(
+            queue.admit(&newer_normal, RebuildPriority::Normal, 1, at(5)),
+            Ok(())
+        );
+        assert_eq!(queue.admit(&low, RebuildPriority::Low, 1, at(5)), Ok(()));
+    }
+
+    #[test]
+    fn aging_prevents_starvation() {
+        let mut queue = queue();
+        let start = Instant::now();
+        let low = VolumeId::new();
+        assert_eq!(queue.admit(&low, RebuildPriority::Low, 0, start), Err(0));
+
+        // a steady stream of high priority volumes gets the slots ahead of the low priority one
+        let mut now = start;
+        while now.duration_since(start) < AGING {
+            let high = VolumeId::new();
+            assert_eq!(queue.admit(&low, RebuildPriority::Low, 0, now), Err(0));
+            assert_eq!(queue.admit(&high, RebuildPriority::High, 0, now), Err(0));
+            assert_eq!(queue.admit(&low, RebuildPriority::Low, 1, now), Err(1));
+            assert_eq!(queue.admit(&high, RebuildPriority::High, 1, now), Ok(()));
+            now += Duration::from_secs(30);
+        }
+
+        // until it's been waiting for longer than the aging threshold
+        let high = VolumeId::new();
+        assert_eq!(queue.admit(&high, RebuildPriority::High, 0, now), Err(1));
+        assert_eq!(
+            queue.rank(&low, RebuildPriority::Low, now),
+            (Reverse(RebuildPriority::High), start)
+        );
+        assert_eq!(queue.admit(&high, RebuildPriority::High, 1, now), Err(1));
+        assert_eq!(queue.admit(&low, RebuildPriority::Low, 1, now), Ok(()));
+        assert_eq!(queue.admit(&high, RebuildPriority::High, 1, now), Ok(()));
+    }
+
+    #[test]
+    fn priority_changes_apply() {
+        let mut queue = queue();
+        let start = Instant::now();
+        let (first, second) = (VolumeId::new(), VolumeId::new());
+        assert_eq!(
+            queue.admit(&first, RebuildPriority::Normal, 0, start),
+            Err(0)
+        );
+        assert_eq!(
+            queue.admit(&second, RebuildPriority::Normal, 0, start),
+            Err(0)
+        );
+
+        // the priority of the second volume is raised whilst it's waiting
+        let now = start + Duration::from_secs(1);
+        assert_eq!(queue.admit(&second, RebuildPriority::High, 1, now), Ok(()));
+        assert_eq!(queue.admit(&first, RebuildPriority::Normal, 1, now), Ok(()));
+    }
+
+    #[test]
+    fn stale_waiters_are_forgotten() {
+        let mut queue = queue();
+        let start = Instant::now();
+        let (gone, waiting) = (VolumeId::new(), VolumeId::new());
+        assert_eq!(queue.admit(&gone, RebuildPriority::High, 0, start), Err(0));
+
+        // eg: the high priority volume was deleted, so it no longer asks for a slot
+        let now = start + STALE;
+        assert_eq!(queue.admit(&waiting, RebuildPriority::Low, 1, now), Ok(()));
+        assert_eq!(
+            queue.rank(&gone, RebuildPriority::High, now),
+            (Reverse(RebuildPriority::High), now)
+        );
+    }
+}
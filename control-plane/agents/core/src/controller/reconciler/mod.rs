@@ -1,8 +1,9 @@
 mod nexus;
+mod node;
 mod persistent_store;
 pub(crate) mod poller;
 mod pool;
-mod replica;
+pub(crate) mod replica;
 mod volume;
 
 pub(crate) use crate::controller::task_poller::PollTriggerEvent;
@@ -52,7 +53,7 @@ impl ReconcilerControl {
     }
 
     /// Send an event signal to the poller's main loop
-    /// todo: don't requeque duplicate events
+    /// Duplicate events sent within the debounce window are coalesced by the poller
     pub(crate) async fn notify(&self, event: PollEvent) {
         if let Err(error) = self.event_channel.try_send(event) {
             tracing::warn!(error=?error, "Failed to send event to reconcile worker");
@@ -28,14 +28,20 @@ impl GarbageCollector {
 impl TaskPoller for GarbageCollector {
     async fn poll(&mut self, context: &PollContext) -> PollResult {
         let nexuses = context.specs().get_nexuses();
+        let nexus_ids = nexuses
+            .iter()
+            .map(|nexus| nexus.lock().uuid.clone())
+            .collect::<Vec<_>>();
+        context.registry().retain_disowned_nexuses(&nexus_ids);
+        let mut results = vec![];
         for nexus in nexuses {
             let mut nexus = match nexus.operation_guard() {
                 Ok(guard) => guard,
                 Err(_) => continue,
             };
-            let _ = nexus.garbage_collect(context).await;
+            results.push(nexus.garbage_collect(context).await);
         }
-        PollResult::Ok(PollerState::Idle)
+        Self::squash_results(results)
     }
 
     async fn poll_timer(&mut self, _context: &PollContext) -> bool {
@@ -43,10 +49,10 @@ impl TaskPoller for GarbageCollector {
     }
 
     async fn poll_event(&mut self, context: &PollContext) -> bool {
-        match context.event() {
+        context.events().iter().any(|event| match event {
             PollEvent::TimedRun | PollEvent::Triggered(PollTriggerEvent::Start) => true,
             PollEvent::Shutdown | PollEvent::Triggered(_) => false,
-        }
+        })
     }
 }
 
@@ -112,23 +118,39 @@ async fn destroy_orphaned_nexus(
 }
 
 /// Given a control plane managed nexus
-/// When a nexus is not owned by a volume
+/// When a nexus is not owned by a volume for longer than the grace period
 /// Then it should eventually be destroyed
+/// Unless it regains an owner within the grace period
 #[tracing::instrument(level = "debug", skip(nexus, context), fields(nexus.uuid = %nexus.lock().uuid, request.reconcile = true))]
 async fn destroy_disowned_nexus(
     nexus: &mut OperationGuardArc<NexusSpec>,
     context: &PollContext,
 ) -> PollResult {
-    let not_owned = {
-        let nexus_spec = nexus.lock();
-        nexus_spec.managed && !nexus_spec.owned()
-    };
-    if not_owned {
-        let span = tracing::info_span!("destroy_disowned_nexus", nexus.uuid = %nexus.lock().uuid, request.reconcile = true);
-        destroy_nexus(nexus, context).instrument(span).await?;
+    let nexus_clone = nexus.lock().clone();
+    if !nexus_clone.managed || nexus_clone.owned() {
+        if context.registry().forget_disowned_nexus(&nexus_clone.uuid) {
+            nexus_clone.info_span(|| {
+                tracing::info!("Nexus regained an owner within its grace period, sparing it")
+            });
+        }
+        return PollResult::Ok(PollerState::Idle);
     }
 
-    PollResult::Ok(PollerState::Idle)
+    if let Some(left) = context
+        .registry()
+        .disowned_nexus_grace_left(&nexus_clone.uuid)
+    {
+        nexus_clone.debug_span(|| {
+            tracing::debug!(
+                "Nexus is not owned, it may regain an owner within {:?}",
+                left
+            )
+        });
+        return PollResult::Ok(PollerState::Busy);
+    }
+
+    let span = tracing::info_span!("destroy_disowned_nexus", nexus.uuid = %nexus_clone.uuid, request.reconcile = true);
+    destroy_nexus(nexus, context).instrument(span).await
 }
 
 /// Given a control plane nexus
@@ -10,7 +10,7 @@ use crate::{
         },
         wrapper::ClientOps,
     },
-    nexus::scheduling::get_healthy_nexus_children,
+    nexus::{scheduling::get_healthy_nexus_children, undersized_children},
 };
 use common_lib::{
     transport_api::ErrorChain,
@@ -18,20 +18,24 @@ use common_lib::{
         store::{
             nexus::{NexusSpec, ReplicaUri},
             nexus_child::NexusChild,
+            reconciler::ReconcilerPass,
             TraceSpan, TraceStrLog,
         },
-        transport::{CreateNexus, NexusShareProtocol, NodeStatus, ShareNexus, UnshareNexus},
+        transport::{
+            AddNexusChild, AddNexusReplica, ChildState, CreateNexus, NexusShareProtocol,
+            NodeStatus, ResumeNexus, ShareNexus, UnshareNexus,
+        },
     },
 };
 use garbage_collector::GarbageCollector;
 
 use crate::controller::{
-    operations::ResourceSharing,
+    operations::{ResourceOffspring, ResourcePausing, ResourceSharing},
     reconciler::{ReCreate, Reconciler},
     wrapper::NodeWrapper,
 };
 use common_lib::types::v0::{store::OperationGuardArc, transport::NexusStatus};
-use std::{convert::TryFrom, sync::Arc};
+use std::{convert::TryFrom, sync::Arc, time::SystemTime};
 use tokio::sync::RwLock;
 use tracing::Instrument;
 
@@ -109,18 +113,44 @@ async fn nexus_reconciler(
 
     if created {
         squash_results(vec![
+            frozen_nexus_thaw(nexus, context).await,
             faulted_children_remover(nexus, context).await,
             unknown_children_remover(nexus, context).await,
             missing_children_remover(nexus, context).await,
+            undersized_children_remover(nexus, context).await,
+            deferred_children_adder(nexus, context).await,
             fixup_nexus_protocol(nexus, context).await,
             enospc_children_finder(nexus, context).await,
+            rebuilt_children_finder(nexus, context).await,
         ])
     } else {
         PollResult::Ok(PollerState::Idle)
     }
 }
 
-/// Find and removes faulted children from the given nexus
+/// Resume the paused IO of the given nexus once past its thaw deadline, so the IO is not left
+/// frozen by a client which never got around to resuming it
+#[tracing::instrument(skip(nexus, context), level = "trace", fields(nexus.uuid = %nexus.lock().uuid, request.reconcile = true))]
+pub(super) async fn frozen_nexus_thaw(
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let nexus_spec = nexus.lock().clone();
+    match nexus_spec.frozen_until {
+        Some(deadline) if deadline <= SystemTime::now() => {}
+        _ => return PollResult::Ok(PollerState::Idle),
+    }
+
+    nexus_spec.warn_span(|| {
+        tracing::warn!("Thawing the frozen IO of the nexus as it's past its thaw deadline")
+    });
+    nexus
+        .resume(context.registry(), &ResumeNexus::from(&nexus_spec))
+        .await?;
+    PollResult::Ok(PollerState::Idle)
+}
+
+/// Find and removes faulted children from the given nexus, except those which were taken offline
 /// If the child is a replica it also disowns and destroys it
 #[tracing::instrument(skip(nexus, context), level = "trace", fields(nexus.uuid = %nexus.lock().uuid, request.reconcile = true))]
 pub(super) async fn faulted_children_remover(
@@ -130,13 +160,32 @@ pub(super) async fn faulted_children_remover(
     let nexus_uuid = nexus.uuid();
     let nexus_state = context.registry().get_nexus(nexus_uuid).await?;
     let child_count = nexus_state.children.len();
+    let grace_period = context.registry().timed_out_child_grace_period();
+    let mut waiting = false;
 
     // Remove faulted children only from a degraded nexus with other healthy children left
     if nexus_state.status == NexusStatus::Degraded && child_count > 1 {
         let span = tracing::info_span!("faulted_children_remover", nexus.uuid = %nexus_uuid, request.reconcile = true);
         async {
             let nexus_spec_clone = nexus.lock().clone();
-            for child in nexus_state.children.iter().filter(|c| c.state.faulted()) {
+            // the children taken offline administratively are faulted until brought back online
+            let offline = &nexus_spec_clone.offline_children;
+            for child in nexus_state
+                .children
+                .iter()
+                .filter(|c| c.state.faulted() && !offline.contains(&c.uri))
+            {
+                if let Some(left) = child.fault_grace_left(grace_period) {
+                    nexus_spec_clone.debug_span(|| {
+                        tracing::debug!(
+                            child.uri = %child.uri.as_str(),
+                            "Faulted child's device timed out, it may come back within {:?}",
+                            left
+                        )
+                    });
+                    waiting = true;
+                    continue;
+                }
                 nexus_spec_clone.warn_span(|| {
                     tracing::warn!("Attempting to remove faulted child '{}'", child.uri)
                 });
@@ -172,7 +221,10 @@ pub(super) async fn faulted_children_remover(
         .await
     }
 
-    PollResult::Ok(PollerState::Idle)
+    match waiting {
+        true => PollResult::Ok(PollerState::Busy),
+        false => PollResult::Ok(PollerState::Idle),
+    }
 }
 
 /// Find and removes unknown children from the given nexus
@@ -276,6 +328,152 @@ pub(super) async fn missing_children_remover(
     result
 }
 
+/// Find and removes the children of the given nexus which are backed by a replica smaller than
+/// the nexus, as long as other healthy children which are large enough are left.
+/// The replicas are also disowned and destroyed, so they can be replaced with larger ones.
+#[tracing::instrument(skip(nexus, context), level = "trace", fields(nexus.uuid = %nexus.lock().uuid, request.reconcile = true))]
+pub(super) async fn undersized_children_remover(
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    if !context.registry().retire_undersized_children() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let nexus_state = context.registry().get_nexus(nexus.uuid()).await?;
+    let replicas = context.registry().get_replicas().await;
+    let undersized = undersized_children(&nexus_state, &replicas);
+    let healthy = nexus_state
+        .children
+        .iter()
+        .filter(|c| c.state == ChildState::Online)
+        .filter(|c| !undersized.iter().any(|(uri, _)| uri == &c.uri))
+        .count();
+    if undersized.is_empty() || healthy == 0 {
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    let mut result = PollResult::Ok(PollerState::Idle);
+    for (child, size) in undersized {
+        nexus.warn_span(|| {
+            tracing::warn!(
+                child.uri = %child.as_str(),
+                child.size = size,
+                nexus.size = nexus_state.size,
+                "Attempting to remove child backed by a replica smaller than the nexus"
+            )
+        });
+        if let Err(error) = context
+            .specs()
+            .remove_nexus_child_by_uri(context.registry(), nexus, &nexus_state, &child, true)
+            .await
+        {
+            nexus.error_span(|| {
+                tracing::error!(
+                    error = %error.full_string().as_str(),
+                    child.uri = %child.as_str(),
+                    "Failed to remove undersized child"
+                )
+            });
+            result = PollResult::Err(error);
+        } else {
+            nexus.info_span(|| {
+                tracing::info!(
+                    child.uri = %child.as_str(),
+                    "Successfully removed undersized child"
+                )
+            });
+        }
+    }
+    result
+}
+
+/// Add the children which were left out when the nexus was created, as their nodes were
+/// offline or they failed to attach, once their nodes are online.
+#[tracing::instrument(skip(nexus, context), level = "trace", fields(nexus.uuid = %nexus.lock().uuid, request.reconcile = true))]
+pub(super) async fn deferred_children_adder(
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let deferred = nexus.lock().deferred_children.clone();
+    if deferred.is_empty() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let offline = context
+        .specs()
+        .offline_nexus_children(context.registry(), &deferred)
+        .await;
+
+    let mut result = PollResult::Ok(PollerState::Idle);
+    for child in deferred
+        .into_iter()
+        .filter(|child| !offline.iter().any(|(offline, _)| offline == child))
+    {
+        let (node, uuid, added) = {
+            let spec = nexus.lock();
+            (
+                spec.node.clone(),
+                spec.uuid.clone(),
+                spec.children.contains(&child),
+            )
+        };
+        let add = match &child {
+            // the child may have been added before the spec could be updated
+            _ if added => Ok(()),
+            NexusChild::Replica(replica) => context
+                .specs()
+                .add_nexus_replica(
+                    Some(nexus),
+                    context.registry(),
+                    &AddNexusReplica::new(&node, &uuid, replica, true),
+                )
+                .await
+                .map(|_| ()),
+            NexusChild::Uri(uri) => nexus
+                .add_child(
+                    context.registry(),
+                    &AddNexusChild {
+                        node,
+                        nexus: uuid,
+                        uri: uri.clone(),
+                        auto_rebuild: true,
+                    },
+                )
+                .await
+                .map(|_| ()),
+        };
+        let add = match add {
+            Ok(()) => {
+                let mut spec = nexus.lock().clone();
+                spec.deferred_children.retain(|deferred| deferred != &child);
+                let stored = context.registry().store_obj(&spec).await;
+                if stored.is_ok() {
+                    nexus.lock().deferred_children = spec.deferred_children;
+                }
+                stored
+            }
+            Err(error) => Err(error),
+        };
+
+        match add {
+            Ok(()) => nexus.info_span(|| {
+                tracing::info!("Successfully added deferred child '{}'", child.uri())
+            }),
+            Err(error) => {
+                nexus.error_span(|| {
+                    tracing::error!(
+                        "Failed to add deferred child '{}', error: '{}'",
+                        child.uri(),
+                        error.full_string(),
+                    )
+                });
+                result = PollResult::Err(error);
+            }
+        }
+    }
+
+    result
+}
+
 /// Recreate the given nexus on its associated node
 /// Only healthy and online replicas are reused in the nexus recreate request
 pub(super) async fn missing_nexus_recreate(
@@ -287,6 +485,12 @@ pub(super) async fn missing_nexus_recreate(
     if context.registry().get_nexus(nexus_uuid).await.is_ok() {
         return PollResult::Ok(PollerState::Idle);
     }
+    if !context
+        .registry()
+        .reconciler_pass_enabled(ReconcilerPass::NexusRecreate)
+    {
+        return PollResult::Ok(PollerState::Idle);
+    }
 
     #[tracing::instrument(skip(nexus, context), fields(nexus.uuid = %nexus.uuid, request.reconcile = true))]
     async fn missing_nexus_recreate(mut nexus: NexusSpec, context: &PollContext) -> PollResult {
@@ -326,10 +530,10 @@ pub(super) async fn missing_nexus_recreate(
                 .await
             {
                 Ok(uri) => {
-                    nexus_replicas.push(NexusChild::Replica(ReplicaUri::new(
-                        &item.spec().uuid,
-                        &uri,
-                    )));
+                    nexus_replicas.push(NexusChild::Replica(
+                        ReplicaUri::new(&item.spec().uuid, &uri)
+                            .with_generation(item.spec().generation),
+                    ));
                 }
                 Err(error) => {
                     nexus.error_span(|| {
@@ -389,6 +593,12 @@ pub(super) async fn fixup_nexus_protocol(
     if let Ok(nexus_state) = context.registry().get_nexus(nexus_uuid).await {
         let nexus_spec = nexus.lock().clone();
         if nexus_spec.share != nexus_state.share {
+            if !context
+                .registry()
+                .reconciler_pass_enabled(ReconcilerPass::ReshareReplicas)
+            {
+                return PollResult::Ok(PollerState::Idle);
+            }
             nexus_spec.warn_span(|| {
                 tracing::warn!(
                     "Attempting to fix wrong nexus share protocol, current: '{}', expected: '{}'",
@@ -501,3 +711,44 @@ pub(super) async fn enospc_children_finder(
 
     PollResult::Ok(PollerState::Idle)
 }
+
+/// Find the out-of-sync replicas which have since been rebuilt by the given nexus, and clear
+/// their out-of-sync marker.
+#[tracing::instrument(skip(nexus, context), level = "trace", fields(nexus.uuid = %nexus.lock().uuid, request.reconcile = true))]
+pub(super) async fn rebuilt_children_finder(
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let nexus_uuid = nexus.uuid();
+    let nexus_state = context.registry().get_nexus(nexus_uuid).await?;
+    let nexus_spec_clone = nexus.lock().clone();
+
+    for child in nexus_state
+        .children
+        .iter()
+        .filter(|c| c.state == ChildState::Online)
+    {
+        let replica = nexus_spec_clone
+            .children
+            .iter()
+            .filter_map(|c| c.as_replica())
+            .find(|r| r.uri() == child)
+            .and_then(|r| context.specs().get_replica(r.uuid()));
+        let replica = match replica {
+            Some(replica) if replica.lock().out_of_sync => replica,
+            _ => continue,
+        };
+        context
+            .specs()
+            .set_replica_out_of_sync(context.registry(), &replica, false)
+            .await?;
+        nexus_spec_clone.info_span(|| {
+            tracing::info!(
+                replica.uuid = %replica.lock().uuid.as_str(),
+                "Replica has been rebuilt and is no longer out-of-sync"
+            )
+        });
+    }
+
+    PollResult::Ok(PollerState::Idle)
+}
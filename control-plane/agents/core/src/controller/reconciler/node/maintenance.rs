@@ -0,0 +1,42 @@
+use crate::controller::task_poller::{PollContext, PollResult, PollerState};
+use common_lib::types::v0::transport::NodeId;
+
+/// End the maintenance of the node if its maintenance window has expired, after which the node
+/// is handled as usual: if it has not returned by then, it's deemed offline right away rather
+/// than when it next misses its registration deadline.
+#[tracing::instrument(skip(context), level = "debug", fields(request.reconcile = true))]
+pub(super) async fn expired_maintenance_reconciler(
+    node_id: &NodeId,
+    context: &PollContext,
+) -> PollResult {
+    if context
+        .specs()
+        .end_expired_maintenance(context.registry(), node_id)
+        .await?
+        .is_none()
+    {
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    let node = match context.registry().get_node_wrapper(node_id).await {
+        Ok(node) => node,
+        Err(_) => {
+            tracing::warn!(node.id = %node_id, "Node maintenance expired but the node has never registered");
+            return PollResult::Ok(PollerState::Idle);
+        }
+    };
+    let mut node = node.write().await;
+    if node.is_online() && !node.registration_expired() {
+        tracing::info!(node.id = %node_id, "Node maintenance expired, the node is back online");
+    } else {
+        tracing::warn!(
+            node.id = %node_id,
+            node.status = %node.status().to_string(),
+            "Node maintenance expired but the node has not returned, resuming its liveness checks"
+        );
+        if node.is_online() {
+            node.update_liveness().await;
+        }
+    }
+    PollResult::Ok(PollerState::Idle)
+}
@@ -0,0 +1,77 @@
+mod maintenance;
+mod returned;
+
+use crate::controller::task_poller::{
+    PollContext, PollEvent, PollResult, PollTimer, PollTriggerEvent, PollerState, TaskPoller,
+};
+use common_lib::types::v0::transport::NodeId;
+use std::collections::HashSet;
+
+/// Node reconciler loop which:
+/// 1. uncordons the cordon labels which have expired
+/// 2. ends the maintenance windows which have expired
+/// 3. reconciles the pools and replicas of the nodes which have come back online
+#[derive(Debug)]
+pub(crate) struct NodeReconciler {
+    counter: PollTimer,
+    /// Nodes which have come back online and whose resources are yet to be reconciled.
+    returned: HashSet<NodeId>,
+}
+
+impl NodeReconciler {
+    /// Return a new `Self`
+    pub(crate) fn new() -> Self {
+        Self {
+            counter: PollTimer::from(1),
+            returned: HashSet::new(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TaskPoller for NodeReconciler {
+    async fn poll(&mut self, context: &PollContext) -> PollResult {
+        let mut results = vec![];
+        for node in context.specs().get_cordoned_nodes() {
+            if node.maintenance_until().is_some() {
+                let result = maintenance::expired_maintenance_reconciler(node.id(), context).await;
+                results.push(result);
+            }
+            if node.cordon_expiry().is_empty() {
+                continue;
+            }
+            let result = context
+                .specs()
+                .uncordon_expired(context.registry(), node.id())
+                .await;
+            results.push(result.map(|_| PollerState::Idle));
+        }
+
+        for event in context.events() {
+            if let PollEvent::Triggered(PollTriggerEvent::NodeStateChangeOnline(node)) = event {
+                self.returned.insert(node.clone());
+            }
+        }
+        for node in self.returned.clone() {
+            let result = returned::returned_node_reconciler(&node, context).await;
+            if let Ok(PollerState::Idle) = result {
+                self.returned.remove(&node);
+            }
+            results.push(result);
+        }
+        Self::squash_results(results)
+    }
+
+    async fn poll_timer(&mut self, _context: &PollContext) -> bool {
+        self.counter.poll()
+    }
+
+    async fn poll_event(&mut self, context: &PollContext) -> bool {
+        context.events().iter().any(|event| match event {
+            PollEvent::TimedRun
+            | PollEvent::Triggered(PollTriggerEvent::Start)
+            | PollEvent::Triggered(PollTriggerEvent::NodeStateChangeOnline(_)) => true,
+            PollEvent::Shutdown | PollEvent::Triggered(_) => false,
+        })
+    }
+}
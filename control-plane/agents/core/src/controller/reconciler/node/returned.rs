@@ -0,0 +1,212 @@
+use crate::controller::{
+    orphans::OrphanSnapshot,
+    specs::{OperationSequenceGuard, SpecOperationsHelper},
+    task_poller::{squash_results, PollContext, PollResult, PollerState},
+    wrapper::{ClientOps, GetterOps, NodeWrapper},
+};
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{
+            nexus_child::NexusChild, replica::ReplicaSpec, ResourceMutex, SpecStatus,
+            SpecTimestamps,
+        },
+        transport::{
+            CreateReplica, NodeId, Orphan, OrphanRemediation, PoolState, Replica, ReplicaOwners,
+        },
+    },
+};
+use std::{sync::Arc, time::SystemTime};
+use tokio::sync::RwLock;
+
+/// Reconcile the pools and replicas reported by a node which has just come back online against
+/// their specs, as some may have been created or destroyed out-of-band whilst it was away:
+/// 1. recreates the managed replicas which are missing from the node
+/// 2. adopts the replicas without a spec which are children of a nexus spec
+/// 3. flags the pools and replicas without a spec which nothing refers to
+/// The node is reconciled again (Busy) whilst any of its pools is yet to be recreated.
+/// The replicas are told apart as per the orphan detection, which the orphan report also uses.
+#[tracing::instrument(skip(context), level = "debug", fields(request.reconcile = true))]
+pub(super) async fn returned_node_reconciler(
+    node_id: &NodeId,
+    context: &PollContext,
+) -> PollResult {
+    let node = match context.registry().get_node_wrapper(node_id).await {
+        Ok(node) if node.read().await.is_online() => node,
+        // it's gone away again, it'll be reconciled the next time it comes back
+        _ => return PollResult::Ok(PollerState::Idle),
+    };
+    let pools = node.pools().await;
+    let replicas = node.replicas().await;
+
+    let mut results = vec![];
+    let pool_specs = context.specs().get_pools();
+    for pool in pool_specs.iter().filter(|p| &p.node == node_id) {
+        if pool.status().created() && !pools.iter().any(|p| p.id == pool.id) {
+            // the pool reconciler recreates it, after which its replicas can be checked
+            results.push(PollResult::Ok(PollerState::Busy));
+        }
+    }
+    for pool in pools
+        .iter()
+        .filter(|p| !pool_specs.iter().any(|s| s.id == p.id))
+    {
+        tracing::warn!(
+            pool.uuid = %pool.id,
+            node.id = %node_id,
+            "Node reports a pool which has no spec"
+        );
+    }
+
+    let snapshot = OrphanSnapshot::new(context.registry()).await;
+    let on_node = |orphans: Vec<Orphan>| {
+        orphans
+            .into_iter()
+            .filter(|orphan| orphan.node.as_ref() == Some(node_id))
+            .map(|orphan| (orphan.id, orphan.remediation))
+            .collect::<Vec<_>>()
+    };
+
+    // the replicas in conflict are left alone until the conflict is resolved by hand
+    let missing = on_node(snapshot.control_plane_replicas());
+    for spec in context.specs().get_replicas() {
+        let uuid = spec.lock().uuid.to_string();
+        if missing
+            .iter()
+            .any(|(id, remediation)| id == &uuid && remediation == &OrphanRemediation::Recreate)
+        {
+            results.push(recreate_missing_replica(&node, spec, &pools, context).await);
+        }
+    }
+
+    let unexpected = on_node(snapshot.dataplane_replicas());
+    for replica in &replicas {
+        match unexpected
+            .iter()
+            .find(|(id, _)| id == replica.uuid.as_str())
+        {
+            Some((_, OrphanRemediation::Adopt)) => {
+                results.push(adopt_unexpected_replica(replica, context).await);
+            }
+            Some((_, OrphanRemediation::Ignore)) => {
+                tracing::warn!(
+                    replica.uuid = %replica.uuid,
+                    pool.uuid = %replica.pool,
+                    node.id = %node_id,
+                    "Node reports a replica which has no spec"
+                );
+            }
+            // stale copies of the replicas which have since been destroyed are left alone
+            _ => {}
+        }
+    }
+
+    squash_results(results)
+}
+
+/// Recreate the managed replica `spec` which its node no longer reports, though the pool where
+/// it should live is present.
+async fn recreate_missing_replica(
+    node: &Arc<RwLock<NodeWrapper>>,
+    spec: ResourceMutex<ReplicaSpec>,
+    pools: &[PoolState],
+    context: &PollContext,
+) -> PollResult {
+    let replica = match spec.operation_guard() {
+        Ok(guard) => guard,
+        Err(_) => return PollResult::Ok(PollerState::Busy),
+    };
+    let spec = replica.lock().clone();
+    if !spec.managed || !spec.owned() || !spec.status().created() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    if context.specs().tombstoned(
+        context.registry(),
+        &ResourceKind::Replica,
+        spec.uuid.as_str(),
+    ) {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let node_id = match pools.iter().find(|p| p.id == spec.pool) {
+        Some(pool) => pool.node.clone(),
+        None => return PollResult::Ok(PollerState::Idle),
+    };
+
+    tracing::warn!(replica.uuid = %spec.uuid, pool.uuid = %spec.pool, node.id = %node_id, "Attempting to recreate missing replica");
+    let request = CreateReplica {
+        node: node_id,
+        name: Some(spec.name.clone()),
+        uuid: spec.uuid.clone(),
+        pool: spec.pool.clone(),
+        size: spec.size,
+        thin: spec.thin,
+        share: spec.share,
+        managed: spec.managed,
+        owners: spec.owners.clone(),
+    };
+    match node.create_replica(&request).await {
+        Ok(_) => {
+            tracing::info!(replica.uuid = %spec.uuid, "Replica successfully recreated");
+            PollResult::Ok(PollerState::Idle)
+        }
+        Err(error) => {
+            tracing::error!(replica.uuid = %spec.uuid, error = %error, "Failed to recreate the replica");
+            PollResult::Err(error)
+        }
+    }
+}
+
+/// Create a spec for the `replica` which has none, if it's a child of a nexus spec, making it
+/// owned by that nexus and its volume. Otherwise it's only flagged, as it's not ours to manage.
+async fn adopt_unexpected_replica(replica: &Replica, context: &PollContext) -> PollResult {
+    let nexus = context.specs().get_nexuses().into_iter().find(|nexus| {
+        nexus.lock().children.iter().any(|child| match child {
+            NexusChild::Replica(child) => child.uuid() == &replica.uuid,
+            NexusChild::Uri(_) => false,
+        })
+    });
+    let nexus = match nexus {
+        Some(nexus) => nexus.lock().clone(),
+        None => {
+            tracing::warn!(
+                replica.uuid = %replica.uuid,
+                pool.uuid = %replica.pool,
+                node.id = %replica.node,
+                "Node reports a replica which has no spec"
+            );
+            return PollResult::Ok(PollerState::Idle);
+        }
+    };
+
+    let request = CreateReplica {
+        node: replica.node.clone(),
+        name: Some(replica.name.clone()),
+        uuid: replica.uuid.clone(),
+        pool: replica.pool.clone(),
+        size: replica.size,
+        thin: replica.thin,
+        share: replica.share,
+        managed: true,
+        owners: ReplicaOwners::new(nexus.owner.clone(), vec![nexus.uuid.clone()]),
+    };
+    let now = SystemTime::now();
+    let mut spec = ReplicaSpec::from(&request);
+    spec.status = SpecStatus::Created(replica.status.clone());
+    spec.stamp_created(now);
+    if let Err(error) = context.registry().store_obj(&spec).await {
+        tracing::error!(replica.uuid = %replica.uuid, error = %error, "Failed to persist the adopted replica spec");
+        return PollResult::Err(error);
+    }
+    let adopted = context.specs().get_or_create_replica(&request);
+    {
+        let mut adopted = adopted.lock();
+        adopted.status = spec.status;
+        adopted.stamp_created(now);
+    }
+    tracing::info!(
+        replica.uuid = %replica.uuid,
+        nexus.uuid = %nexus.uuid,
+        "Adopted a replica which has no spec but is a child of a nexus"
+    );
+    PollResult::Ok(PollerState::Idle)
+}
@@ -1,4 +1,5 @@
 use crate::controller::task_poller::{PollContext, PollResult, PollerState, TaskPoller};
+use common_lib::types::v0::store::reconciler::ReconcilerPass;
 
 /// Reconcile dirty specs in the persistent store.
 /// This happens when we fail to update the persistent store and we have a "live" spec that
@@ -17,13 +18,20 @@ impl PersistentStoreReconciler {
 impl TaskPoller for PersistentStoreReconciler {
     async fn poll(&mut self, context: &PollContext) -> PollResult {
         let specs = context.specs();
+        if !context
+            .registry()
+            .reconciler_pass_enabled(ReconcilerPass::DirtySpecReplay)
+        {
+            return PollResult::Ok(PollerState::Idle);
+        }
         if context.registry().store_online().await {
             let dirty_pools = specs.reconcile_dirty_pools(context.registry()).await;
             let dirty_replicas = specs.reconcile_dirty_replicas(context.registry()).await;
             let dirty_nexuses = specs.reconcile_dirty_nexuses(context.registry()).await;
             let dirty_volumes = specs.reconcile_dirty_volumes(context.registry()).await;
+            let tombstones = specs.reconcile_tombstones(context.registry()).await;
 
-            if dirty_nexuses || dirty_replicas || dirty_volumes || dirty_pools {
+            if dirty_nexuses || dirty_replicas || dirty_volumes || dirty_pools || tombstones {
                 return PollResult::Ok(PollerState::Busy);
             }
         }
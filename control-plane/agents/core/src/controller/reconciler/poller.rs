@@ -1,5 +1,5 @@
 use crate::controller::{
-    reconciler::{nexus, persistent_store::PersistentStoreReconciler, pool, replica, volume},
+    reconciler::{nexus, node, persistent_store::PersistentStoreReconciler, pool, replica, volume},
     registry::Registry,
     task_poller::{
         squash_results, PollContext, PollEvent, PollResult, PollTriggerEvent, PollerState,
@@ -32,6 +32,7 @@ impl ReconcilerWorker {
             Box::new(volume::VolumeReconciler::new()),
             Box::new(PersistentStoreReconciler::new()),
             Box::new(replica::ReplicaReconciler::new()),
+            Box::new(node::NodeReconciler::new()),
         ];
 
         // if events are sent before the worker is started they may fill up the buffer
@@ -65,19 +66,15 @@ impl ReconcilerWorker {
     /// The polling will continue until we receive the shutdown signal
     pub(super) async fn poller(mut self, registry: Registry) {
         // kick-off the first run
-        let mut event = PollEvent::Triggered(PollTriggerEvent::Start);
+        let mut events = vec![PollEvent::Triggered(PollTriggerEvent::Start)];
         loop {
-            let result = match &event {
-                PollEvent::Shutdown => {
-                    tracing::warn!("Shutting down... (reconcilers will NOT be polled again)");
-                    return;
-                }
-                PollEvent::TimedRun | PollEvent::Triggered(_) => {
-                    self.poller_work(PollContext::from(&event, &registry)).await
-                }
-            };
+            if events.contains(&PollEvent::Shutdown) {
+                tracing::warn!("Shutting down... (reconcilers will NOT be polled again)");
+                return;
+            }
+            let result = self.poller_work(PollContext::from(events, &registry)).await;
 
-            event = tokio::select! {
+            let event = tokio::select! {
                 _shutdown = self.shutdown_channel.recv() => {
                     PollEvent::Shutdown
                 },
@@ -92,11 +89,27 @@ impl ReconcilerWorker {
                     PollEvent::TimedRun
                 }
             };
+            events = match event {
+                PollEvent::Triggered(_) => {
+                    coalesce(
+                        event,
+                        &mut self.event_channel,
+                        registry.reconcile_debounce(),
+                    )
+                    .await
+                }
+                PollEvent::TimedRun | PollEvent::Shutdown => vec![event],
+            };
         }
     }
 
     #[tracing::instrument(skip(context), level = "trace", err)]
     async fn poller_work(&mut self, context: PollContext) -> PollResult {
+        if context.registry().store_read_only() {
+            // the passes write the specs, which can't be done until the store is back
+            tracing::trace!("Skipping the reconcile loop whilst the store is unavailable...");
+            return PollResult::Ok(PollerState::Idle);
+        }
         tracing::trace!("Entering the reconcile loop...");
         let mut results = vec![];
         for target in &mut self.poll_targets {
@@ -106,3 +119,79 @@ impl ReconcilerWorker {
         squash_results(results)
     }
 }
+
+/// Coalesce the burst of events which follows the triggering `event` on the `channel` within the
+/// debounce `window`, so the burst is handled by a single reconcile run.
+/// The window starts with the first event, so an isolated event is delayed by at most the window.
+async fn coalesce(
+    event: PollEvent,
+    channel: &mut tokio::sync::mpsc::Receiver<PollEvent>,
+    window: std::time::Duration,
+) -> Vec<PollEvent> {
+    let mut events = vec![event];
+    if window.is_zero() {
+        return events;
+    }
+    let deadline = tokio::time::Instant::now() + window;
+    while let Ok(event) = tokio::time::timeout_at(deadline, channel.recv()).await {
+        let event = event.unwrap_or(PollEvent::Shutdown);
+        let shutdown = event == PollEvent::Shutdown;
+        if !events.contains(&event) {
+            events.push(event);
+        }
+        if shutdown {
+            break;
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::NodeId;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn coalesce_burst() {
+        let (sender, mut channel) = tokio::sync::mpsc::channel(32);
+        let node_online = |node: &str| {
+            PollEvent::Triggered(PollTriggerEvent::NodeStateChangeOnline(NodeId::from(node)))
+        };
+        for _ in 0 .. 5 {
+            sender.try_send(node_online("node-1")).unwrap();
+            sender
+                .try_send(PollEvent::Triggered(PollTriggerEvent::VolumeDegraded))
+                .unwrap();
+        }
+        sender.try_send(node_online("node-2")).unwrap();
+
+        let events = coalesce(
+            PollEvent::Triggered(PollTriggerEvent::VolumeDegraded),
+            &mut channel,
+            Duration::from_millis(100),
+        )
+        .await;
+        assert_eq!(
+            events,
+            vec![
+                PollEvent::Triggered(PollTriggerEvent::VolumeDegraded),
+                node_online("node-1"),
+                node_online("node-2")
+            ]
+        );
+        assert!(channel.try_recv().is_err(), "the burst is fully coalesced");
+
+        // with the debounce disabled each event gets its own run
+        sender.try_send(node_online("node-1")).unwrap();
+        let events = coalesce(node_online("node-2"), &mut channel, Duration::ZERO).await;
+        assert_eq!(events, vec![node_online("node-2")]);
+        assert_eq!(channel.try_recv().unwrap(), node_online("node-1"));
+
+        // a shutdown ends the burst
+        sender.try_send(PollEvent::Shutdown).unwrap();
+        sender.try_send(node_online("node-1")).unwrap();
+        let events = coalesce(node_online("node-2"), &mut channel, Duration::from_secs(1)).await;
+        assert_eq!(events, vec![node_online("node-2"), PollEvent::Shutdown]);
+    }
+}
@@ -5,9 +5,12 @@ use crate::controller::{
     task_poller::{PollContext, PollPeriods, PollResult, PollTimer, PollerState, TaskPoller},
     wrapper::ClientOps,
 };
-use common_lib::types::v0::{
-    store::{pool::PoolSpec, OperationGuardArc, TraceSpan},
-    transport::{CreatePool, DestroyPool, NodeStatus},
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{pool::PoolSpec, OperationGuardArc, TraceSpan},
+        transport::{CreatePool, DestroyPool, NodeStatus},
+    },
 };
 use tracing::Instrument;
 
@@ -40,6 +43,18 @@ impl TaskPoller for PoolReconciler {
                 Ok(guard) => guard,
                 Err(_) => continue,
             };
+            let id = pool.lock().id.clone();
+            if context
+                .specs()
+                .tombstoned(context.registry(), &ResourceKind::Pool, id.as_str())
+            {
+                // stale copy of a pool which has since been destroyed
+                continue;
+            }
+            if context.registry().check_pool_conflict(&id).is_err() {
+                // left alone until the conflict is resolved by hand
+                continue;
+            }
 
             results.push(Self::squash_results(vec![
                 pool.garbage_collect(context).await,
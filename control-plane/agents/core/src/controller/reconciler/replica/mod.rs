@@ -6,9 +6,12 @@ use crate::controller::{
         PollContext, PollEvent, PollResult, PollTimer, PollTriggerEvent, PollerState, TaskPoller,
     },
 };
-use common_lib::types::v0::{
-    store::{replica::ReplicaSpec, OperationGuardArc},
-    transport::ReplicaOwners,
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{reconciler::ReconcilerPass, replica::ReplicaSpec, OperationGuardArc},
+        transport::ReplicaOwners,
+    },
 };
 
 /// Replica reconciler
@@ -29,6 +32,12 @@ impl ReplicaReconciler {
 #[async_trait::async_trait]
 impl TaskPoller for ReplicaReconciler {
     async fn poll(&mut self, context: &PollContext) -> PollResult {
+        if !context
+            .registry()
+            .reconciler_pass_enabled(ReconcilerPass::GarbageCollectReplicas)
+        {
+            return PollResult::Ok(PollerState::Idle);
+        }
         let replicas = context.specs().get_replicas();
         let mut results = Vec::with_capacity(replicas.len());
 
@@ -37,6 +46,22 @@ impl TaskPoller for ReplicaReconciler {
                 Ok(guard) => guard,
                 Err(_) => continue,
             };
+            let uuid = replica.lock().uuid.to_string();
+            if context
+                .specs()
+                .tombstoned(context.registry(), &ResourceKind::Replica, &uuid)
+            {
+                // stale copy of a replica which has since been destroyed
+                continue;
+            }
+            if context
+                .registry()
+                .check_replica_conflict(&replica.lock().uuid)
+                .is_err()
+            {
+                // left alone until the conflict is resolved by hand
+                continue;
+            }
             results.push(replica.garbage_collect(context).await);
         }
 
@@ -48,10 +73,10 @@ impl TaskPoller for ReplicaReconciler {
     }
 
     async fn poll_event(&mut self, context: &PollContext) -> bool {
-        match context.event() {
+        context.events().iter().any(|event| match event {
             PollEvent::TimedRun | PollEvent::Triggered(PollTriggerEvent::Start) => true,
             PollEvent::Shutdown | PollEvent::Triggered(_) => false,
-        }
+        })
     }
 }
 
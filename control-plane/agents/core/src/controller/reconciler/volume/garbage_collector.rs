@@ -7,13 +7,15 @@ use crate::controller::{
 use common_lib::types::v0::store::{volume::VolumeSpec, OperationGuardArc, TraceSpan, TraceStrLog};
 
 use crate::controller::{
-    operations::ResourceLifecycle, reconciler::GarbageCollect, specs::SpecOperationsHelper,
+    operations::ResourceLifecycle, orphans::creation_grace_left, reconciler::GarbageCollect,
+    specs::SpecOperationsHelper,
 };
 use common::errors::SvcError;
 use common_lib::types::v0::{
     store::{nexus_persistence::NexusInfo, replica::ReplicaSpec},
     transport::{DestroyVolume, VolumeStatus},
 };
+use std::time::SystemTime;
 use tracing::Instrument;
 
 /// Volume Garbage Collector reconciler
@@ -49,12 +51,12 @@ impl TaskPoller for GarbageCollector {
     }
 
     async fn poll_event(&mut self, context: &PollContext) -> bool {
-        match context.event() {
+        context.events().iter().any(|event| match event {
             PollEvent::TimedRun
             | PollEvent::Triggered(PollTriggerEvent::VolumeDegraded)
             | PollEvent::Triggered(PollTriggerEvent::Start) => true,
             PollEvent::Shutdown | PollEvent::Triggered(_) => false,
-        }
+        })
     }
 }
 
@@ -213,7 +215,17 @@ async fn disown_unused_replicas(
         let replica_clone = replica.lock().clone();
 
         let replica_in_target = target.lock().contains_replica(&replica_clone.uuid);
-        let replica_online = matches!(context.registry().get_replica(&replica_clone.uuid).await, Ok(state) if state.online());
+        let replica_state = context.registry().get_replica(&replica_clone.uuid).await;
+        if replica_state.is_err() {
+            let grace_period = context.registry().replica_creation_grace_period();
+            let created_at = replica_clone.created_at;
+            if let Some(left) = creation_grace_left(created_at, grace_period, SystemTime::now()) {
+                // the node may not have reported the replica which was only just created
+                volume_clone.debug_span(|| tracing::debug!(replica.uuid = %replica_clone.uuid, "Replica was just created, it's deemed missing if still absent in {:?}", left));
+                continue;
+            }
+        }
+        let replica_online = matches!(replica_state, Ok(state) if state.online());
         if !replica_online
             && replica_clone.owners.owned_by(&volume_clone.uuid)
             && !replica_clone.owners.owned_by_a_nexus()
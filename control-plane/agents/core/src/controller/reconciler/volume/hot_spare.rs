@@ -7,7 +7,7 @@ use crate::controller::{
 use common_lib::{
     transport_api::ErrorChain,
     types::v0::{
-        store::{nexus::NexusSpec, volume::VolumeSpec},
+        store::{nexus::NexusSpec, reconciler::ReconcilerPass, volume::VolumeSpec},
         transport::{VolumeState, VolumeStatus},
     },
 };
@@ -33,7 +33,17 @@ impl HotSpareReconciler {
 impl TaskPoller for HotSpareReconciler {
     async fn poll(&mut self, context: &PollContext) -> PollResult {
         let mut results = vec![];
-        let volumes = context.specs().get_locked_volumes();
+        let mut volumes = context.specs().get_locked_volumes();
+        // reconcile the volumes in the order in which they'd get a slot to rebuild their replicas,
+        // so the volumes of higher rebuild priority ask for one first
+        {
+            let now = std::time::Instant::now();
+            let queue = context.registry().rebuild_queue().lock();
+            volumes.sort_by_cached_key(|volume| {
+                let volume = volume.lock();
+                queue.rank(&volume.uuid, volume.rebuild_priority, now)
+            });
+        }
         for mut volume in volumes {
             results.push(hot_spare_reconcile(&mut volume, context).await);
         }
@@ -59,9 +69,21 @@ async fn hot_spare_reconcile(
     if !volume.lock().status.created() {
         return PollResult::Ok(PollerState::Idle);
     }
+    if matches!(
+        volume_state.status,
+        VolumeStatus::Unknown | VolumeStatus::Degraded
+    ) && !context
+        .registry()
+        .reconciler_pass_enabled(ReconcilerPass::ReplaceFaultedChildren)
+    {
+        return PollResult::Ok(PollerState::Idle);
+    }
 
     match volume_state.status {
-        VolumeStatus::Online => volume_replica_count_reconciler(&mut volume, context).await,
+        VolumeStatus::Online => squash_results(vec![
+            volume_replica_count_reconciler(&mut volume, context).await,
+            online_undersized_children_remover(&volume_state, context).await,
+        ]),
         VolumeStatus::Unknown | VolumeStatus::Degraded => {
             hot_spare_nexus_reconcile(&mut volume, &volume_state, context).await
         }
@@ -103,6 +125,8 @@ async fn generic_nexus_reconciler(
     results.push(faulted_children_remover(nexus, context).await);
     results.push(unknown_children_remover(nexus, context).await);
     results.push(missing_children_remover(nexus, context).await);
+    results.push(undersized_children_remover(nexus, context).await);
+    results.push(rebuilt_children_finder(nexus, context).await);
     squash_results(results)
 }
 
@@ -117,6 +141,32 @@ async fn faulted_children_remover(
     nexus::faulted_children_remover(nexus, context).await
 }
 
+/// Given a volume
+/// When a nexus state has children backed by replicas smaller than the nexus
+/// Then they should eventually be removed from the state and spec, if healthy children are left
+/// And the replicas should eventually be destroyed
+async fn undersized_children_remover(
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    nexus::undersized_children_remover(nexus, context).await
+}
+
+/// The undersized children of the target of an online volume are also removed, as all children
+/// of an online target are healthy, though not all of them may be large enough.
+async fn online_undersized_children_remover(
+    volume_state: &VolumeState,
+    context: &PollContext,
+) -> PollResult {
+    match &volume_state.target {
+        Some(nexus) if context.registry().retire_undersized_children() => {
+            let mut nexus = context.specs().nexus(&nexus.uuid).await?;
+            undersized_children_remover(&mut nexus, context).await
+        }
+        _ => PollResult::Ok(PollerState::Idle),
+    }
+}
+
 /// Given a degraded volume
 /// When a nexus state has children that are not present in the spec
 /// Then the children should eventually be removed from the state
@@ -128,6 +178,16 @@ async fn unknown_children_remover(
     nexus::unknown_children_remover(nexus, context).await
 }
 
+/// Given a volume with out-of-sync replicas
+/// When a nexus state has the replicas online, ie: their rebuild has completed
+/// Then the replicas should no longer be marked as out-of-sync
+async fn rebuilt_children_finder(
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    nexus::rebuilt_children_finder(nexus, context).await
+}
+
 /// Given a degraded volume
 /// When a nexus spec has children that are not present in the state
 /// Then the children should eventually be removed from the spec
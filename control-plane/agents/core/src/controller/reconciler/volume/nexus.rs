@@ -1,16 +1,23 @@
 use crate::controller::{
+    operations::ResourceSharing,
     reconciler::{
-        nexus::{fixup_nexus_protocol, missing_nexus_recreate},
+        nexus::{fixup_nexus_protocol, frozen_nexus_thaw, missing_nexus_recreate},
         PollContext, TaskPoller,
     },
     specs::OperationSequenceGuard,
     task_poller::{PollResult, PollerState},
+    wrapper::ClientOps,
 };
 
-use common_lib::types::v0::store::volume::VolumeSpec;
+use common_lib::types::v0::store::{nexus::NexusSpec, volume::VolumeSpec};
 
 use crate::controller::reconciler::nexus::faulted_nexus_remover;
-use common_lib::types::v0::{store::ResourceMutex, transport::VolumeStatus};
+use common_lib::types::v0::{
+    store::{OperationGuardArc, ResourceMutex, TraceSpan},
+    transport::{
+        PauseNexusRebuild, ShareNexus, UnshareNexus, VolumeCondition, VolumeState, VolumeStatus,
+    },
+};
 
 /// Volume nexus reconciler
 /// When io-engine instances restart they come up "empty" and so we need to recreate
@@ -47,7 +54,7 @@ async fn volume_nexus_reconcile(
     };
     let volume_spec = {
         let volume_spec = volume.lock();
-        if !volume_spec.policy.self_heal || !volume_spec.status.created() {
+        if !volume_spec.status.created() {
             return PollResult::Ok(PollerState::Idle);
         }
         volume_spec.clone()
@@ -62,6 +69,14 @@ async fn volume_nexus_reconcile(
                 return PollResult::Ok(PollerState::Idle);
             }
 
+            // the frozen IO is thawed past its deadline regardless of the self healing policy
+            frozen_nexus_thaw(&mut nexus, context).await?;
+            // and the rebuilds are kept paused, as they were requested to be
+            paused_rebuilds_hold(&volume_spec, &nexus, context).await?;
+            if !volume_spec.policy.self_heal {
+                return PollResult::Ok(PollerState::Idle);
+            }
+
             let volume_state = context
                 .registry()
                 .get_volume_state(&volume_spec.uuid)
@@ -69,10 +84,125 @@ async fn volume_nexus_reconcile(
 
             if volume_state.status != VolumeStatus::Online {
                 faulted_nexus_remover(&mut nexus, context).await?;
+            }
+            if volume_state.has_condition(VolumeCondition::TargetMissing) {
                 missing_nexus_recreate(&mut nexus, context).await?;
             }
+            if volume_spec
+                .policy
+                .below_quorum(volume_state.healthy_replicas())
+            {
+                return volume_quorum_enforce(&volume_spec, &volume_state, &mut nexus, context)
+                    .await;
+            }
+            volume_quorum_restore(&volume_spec, &volume_state, &mut nexus, context).await?;
             fixup_nexus_protocol(&mut nexus, context).await
         }
         None => PollResult::Ok(PollerState::Idle),
     }
 }
+
+/// Given a volume whose rebuilds are paused
+/// When the target has children which are being rebuilt, eg: once it was recreated
+/// Then their rebuilds should be paused as well
+/// A rebuild which is already paused is paused again, as its state is not reported by the nexus.
+async fn paused_rebuilds_hold(
+    volume_spec: &VolumeSpec,
+    nexus: &OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    if !volume_spec.rebuild_paused {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let nexus_state = context
+        .registry()
+        .get_nexus(&nexus.immutable_peek().uuid)
+        .await?;
+    let rebuilding = nexus_state
+        .children
+        .iter()
+        .filter(|child| child.rebuilding())
+        .collect::<Vec<_>>();
+    if rebuilding.is_empty() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+
+    let node = context
+        .registry()
+        .get_node_wrapper(&nexus_state.node)
+        .await?;
+    for child in rebuilding {
+        volume_spec.debug_span(|| {
+            tracing::debug!(child.uri = %child.uri.as_str(), "Holding the paused rebuild of child")
+        });
+        let pause = PauseNexusRebuild::new(&nexus_state.node, &nexus_state.uuid, &child.uri);
+        node.pause_rebuild(&pause).await?;
+    }
+    PollResult::Ok(PollerState::Idle)
+}
+
+/// Given a published volume with a quorum policy
+/// When the number of healthy replicas drops below the quorum
+/// Then the target should be unshared, making the volume unavailable for IO
+/// The target is unshared through its spec, so that it's not shared again by the nexus protocol
+/// fixup whilst the volume is below its quorum.
+async fn volume_quorum_enforce(
+    volume_spec: &VolumeSpec,
+    volume_state: &VolumeState,
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    let nexus_state = match &volume_state.target {
+        Some(nexus_state) if nexus_state.share.shared() => nexus_state,
+        _ => return PollResult::Ok(PollerState::Idle),
+    };
+
+    volume_spec.warn_span(|| {
+        tracing::warn!(
+            healthy.replicas = volume_state.healthy_replicas(),
+            quorum = volume_spec.policy.quorum.unwrap_or_default(),
+            "Volume is below its quorum, making the target unavailable"
+        )
+    });
+
+    nexus
+        .unshare(context.registry(), &UnshareNexus::from(nexus_state))
+        .await?;
+    PollResult::Ok(PollerState::Idle)
+}
+
+/// Given a published volume whose target was unshared as it dropped below its quorum
+/// When the quorum returns
+/// Then the target should be shared again with the protocol of the volume
+async fn volume_quorum_restore(
+    volume_spec: &VolumeSpec,
+    volume_state: &VolumeState,
+    nexus: &mut OperationGuardArc<NexusSpec>,
+    context: &PollContext,
+) -> PollResult {
+    if volume_spec.policy.quorum.is_none() {
+        return PollResult::Ok(PollerState::Idle);
+    }
+    let protocol = match volume_spec.target.as_ref().and_then(|t| t.protocol()) {
+        Some(protocol) if !nexus.lock().share.shared() => *protocol,
+        _ => return PollResult::Ok(PollerState::Idle),
+    };
+    let nexus_state = match &volume_state.target {
+        Some(nexus_state) if !nexus_state.share.shared() => nexus_state,
+        _ => return PollResult::Ok(PollerState::Idle),
+    };
+
+    volume_spec.info_span(|| {
+        tracing::info!(
+            healthy.replicas = volume_state.healthy_replicas(),
+            "Volume is back within its quorum, making the target available"
+        )
+    });
+    nexus
+        .share(
+            context.registry(),
+            &ShareNexus::from((nexus_state, None, protocol)),
+        )
+        .await?;
+    PollResult::Ok(PollerState::Idle)
+}
@@ -0,0 +1,63 @@
+use crate::controller::registry::Registry;
+use common_lib::types::v0::transport::{ReplicaId, VolumeId};
+use std::collections::{HashMap, HashSet};
+
+/// Volume replicas which in-flight operations are about to remove, so that concurrent operations
+/// account for each other's removals when checking the redundancy of the volumes.
+#[derive(Debug, Default)]
+pub(crate) struct PendingRemovals {
+    volumes: HashMap<VolumeId, HashSet<ReplicaId>>,
+}
+
+impl PendingRemovals {
+    /// Get the replicas of the volume which are being removed.
+    pub(crate) fn volume(&self, volume: &VolumeId) -> Option<&HashSet<ReplicaId>> {
+        self.volumes.get(volume)
+    }
+    /// Add the replicas of the volume which are being removed.
+    pub(crate) fn add(&mut self, volume: &VolumeId, replicas: &HashSet<ReplicaId>) {
+        self.volumes
+            .entry(volume.clone())
+            .or_default()
+            .extend(replicas.iter().cloned());
+    }
+    /// Remove the replicas of the volume whose removal has completed or failed.
+    fn remove(&mut self, volume: &VolumeId, replicas: &HashSet<ReplicaId>) {
+        if let Some(pending) = self.volumes.get_mut(volume) {
+            pending.retain(|replica| !replicas.contains(replica));
+            if pending.is_empty() {
+                self.volumes.remove(volume);
+            }
+        }
+    }
+}
+
+/// Replica removals reserved by an operation which passed the redundancy check.
+/// The reservations are released when the guard is dropped, ie: when the operation completes.
+#[derive(Debug)]
+pub(crate) struct RedundancyGuard {
+    registry: Registry,
+    removals: HashMap<VolumeId, HashSet<ReplicaId>>,
+}
+
+impl RedundancyGuard {
+    /// Create a new `Self` for the already reserved `removals`.
+    pub(crate) fn new(
+        registry: &Registry,
+        removals: HashMap<VolumeId, HashSet<ReplicaId>>,
+    ) -> Self {
+        Self {
+            registry: registry.clone(),
+            removals,
+        }
+    }
+}
+
+impl Drop for RedundancyGuard {
+    fn drop(&mut self) {
+        let mut pending = self.registry.pending_removals().lock();
+        for (volume, replicas) in &self.removals {
+            pending.remove(volume, replicas);
+        }
+    }
+}
@@ -14,28 +14,50 @@
 //! Each instance also contains the known nexus, pools and replicas that live in
 //! said instance.
 use super::{specs::*, wrapper::NodeWrapper};
-use crate::controller::{
-    reconciler::ReconcilerControl,
-    task_poller::{PollEvent, PollTriggerEvent},
-    wrapper::InternalOps,
+use crate::{
+    controller::{
+        conditions::ConditionTransitions,
+        conflicts::{ConflictPolicy, FlaggedConflicts},
+        latency::LatencyMap,
+        orphans::OrphanSightings,
+        passes::ReconcilerSwitches,
+        rebuild_queue::RebuildQueue,
+        reconciler::ReconcilerControl,
+        redundancy::PendingRemovals,
+        replay::{ReplayPolicy, ReplayTransition, SpecReplays},
+        scheduling::trace::SchedulingTraceLog,
+        store_policy::{StoreAvailability, StoreTransition, StoreUnavailablePolicy},
+        targets::VolumeTargets,
+        task_poller::{PollEvent, PollTriggerEvent},
+        topology::TopologyPolicy,
+        wrapper::InternalOps,
+    },
+    nexus::NexusChildrenPolicy,
 };
 use common::errors::SvcError;
 use common_lib::{
     store::etcd::Etcd,
+    transport_api::ResourceKind,
     types::v0::{
         store::{
-            definitions::{StorableObject, Store, StoreError, StoreKey},
+            defaults::ClusterDefaults,
+            definitions::{StorableObject, Store, StoreBatch, StoreError, StoreKey},
+            quota::VolumeQuotas,
+            reconciler::{ReconcilerPass, ReconcilerPasses},
             registry::{ControlPlaneService, CoreRegistryConfig, NodeRegistration},
         },
-        transport::NodeId,
+        transport::{
+            NexusId, NodeId, PoolId, RebuildPriority, ReconcilerPassStates, StoreHealth, Topology,
+            VolumeCondition, VolumeId, VolumeTargetChanged,
+        },
     },
 };
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::{Deref, DerefMut},
     sync::Arc,
 };
-use tokio::sync::{Mutex, RwLock};
+use tokio::sync::{broadcast, Mutex, MutexGuard, RwLock};
 
 /// Registry containing all io-engine instances (aka nodes)
 #[derive(Clone, Debug)]
@@ -57,6 +79,9 @@ impl Deref for Registry {
 /// Number of rebuilds
 pub(crate) type NumRebuilds = u32;
 
+/// Number of volume target changes buffered for each subscriber
+const VOLUME_TARGET_EVENTS: usize = 64;
+
 /// Generic Registry Inner with a Store trait
 #[derive(Debug)]
 pub(crate) struct RegistryInner<S: Store> {
@@ -67,53 +92,311 @@ pub(crate) struct RegistryInner<S: Store> {
     /// period to refresh the cache
     cache_period: std::time::Duration,
     store: Arc<Mutex<S>>,
+    /// handle to the store which isn't serialized with the store operations, so the health of
+    /// the store endpoints can be reported even whilst an operation is stuck
+    store_monitor: S,
     /// store gRPC operation timeout
     store_timeout: std::time::Duration,
+    /// how the agent operates whilst the store is unavailable
+    store_unavailable_policy: StoreUnavailablePolicy,
+    /// availability of the store, as per its latest health check
+    store_availability: parking_lot::Mutex<StoreAvailability>,
     /// reconciliation period when no work is being done
     reconcile_idle_period: std::time::Duration,
     /// reconciliation period when work is pending
     reconcile_period: std::time::Duration,
+    /// window within which a burst of reconcile triggering events is coalesced into a single run
+    reconcile_debounce: std::time::Duration,
     reconciler: ReconcilerControl,
     config: CoreRegistryConfig,
     /// system-wide maximum number of concurrent rebuilds allowed
     max_rebuilds: Option<NumRebuilds>,
+    /// serializes the start of the rebuilds which are subject to the system-wide maximum
+    rebuild_admission_lock: Mutex<()>,
+    /// volumes waiting for a rebuild, once the system-wide maximum is reached
+    rebuild_queue: parking_lot::Mutex<RebuildQueue>,
+    /// maximum number of rebuilds a node may be the destination of, to host a replacement replica
+    max_rebuild_destinations: Option<u32>,
+    /// unsharing a resource which is not shared fails rather than succeeding
+    legacy_unshare: bool,
+    /// how long a destroyed resource id may not be reused for
+    tombstone_ttl: std::time::Duration,
+    /// topology of the volumes which are created without one
+    default_topology: Option<Topology>,
+    /// conditions of the volumes whose state diverges from their spec, updated every cache period
+    volume_conditions: parking_lot::Mutex<HashMap<VolumeId, Vec<VolumeCondition>>>,
+    /// how to resolve the pools and replicas which a node reports differently from their specs
+    conflict_policy: ConflictPolicy,
+    /// pools and replicas flagged for manual resolution
+    conflicts: parking_lot::Mutex<FlaggedConflicts>,
+    /// default parameters of the volumes, applied to those left unset on creation
+    cluster_defaults: parking_lot::Mutex<ClusterDefaults>,
+    /// per label quotas of the capacity provisioned by the volumes
+    volume_quotas: parking_lot::Mutex<VolumeQuotas>,
+    /// serializes the creation of the volumes which are subject to a quota
+    volume_quotas_lock: Mutex<()>,
+    /// maximum size of a volume, in bytes
+    max_volume_size: Option<u64>,
+    /// minimum size of a volume, in bytes
+    min_volume_size: u64,
+    /// how long a child whose device timed out is left in place before it's replaced
+    timed_out_child_grace_period: std::time::Duration,
+    /// how long a managed nexus may be left without an owner before it's destroyed
+    disowned_nexus_grace_period: std::time::Duration,
+    /// since when each managed nexus has been observed without an owner
+    disowned_nexuses: parking_lot::Mutex<HashMap<NexusId, std::time::Instant>>,
+    /// whether a nexus may be created without the children whose nodes are offline
+    nexus_children_policy: NexusChildrenPolicy,
+    /// how long a node may take to connect to a remote nexus child before it's deemed unreachable
+    child_probe_timeout: std::time::Duration,
+    /// how long the poll of a node may take before it's given up on
+    node_poll_timeout: std::time::Duration,
+    /// how many times a failed poll of a node is retried, in the background
+    node_poll_retries: u32,
+    /// wait before the first retry of a failed poll, doubled on each subsequent retry
+    node_poll_backoff: std::time::Duration,
+    /// the nodes whose failed poll is being retried
+    node_poll_retrying: parking_lot::Mutex<HashSet<NodeId>>,
+    /// minimum number of healthy replicas which the volumes are left with by the operations which
+    /// remove replicas
+    min_replication_factor: u8,
+    /// volume replicas which in-flight operations are about to remove
+    pending_removals: parking_lot::Mutex<PendingRemovals>,
+    /// how to handle the volumes missing replicas which can't be placed as per their topology
+    topology_policy: TopologyPolicy,
+    /// reconciler passes which may be disabled at runtime
+    reconciler_switches: parking_lot::Mutex<ReconcilerSwitches>,
+    /// serializes the updates of the reconciler passes
+    reconciler_switches_lock: Mutex<()>,
+    /// number of revisions retained in the history of each volume spec
+    spec_revisions: usize,
+    /// how long a just created replica may be absent from its node's state before it's deemed
+    /// missing
+    replica_creation_grace_period: std::time::Duration,
+    /// traces of the scheduling of the replicas of the latest volume creations
+    scheduling_traces: parking_lot::Mutex<SchedulingTraceLog>,
+    /// nexus children backed by a replica smaller than their nexus are removed
+    retire_undersized_children: bool,
+    /// the operation guards may be released forcibly through the admin API
+    allow_guard_release: bool,
+    /// the persisted specs may be patched through the admin API
+    allow_spec_patch: bool,
+    /// round-trip times between the nodes, as reported on their registration
+    latencies: parking_lot::Mutex<LatencyMap>,
+    /// time of the last transition of the conditions of each volume
+    volume_transitions: parking_lot::Mutex<ConditionTransitions<VolumeId>>,
+    /// time of the last transition of the conditions of each pool
+    pool_transitions: parking_lot::Mutex<ConditionTransitions<PoolId>>,
+    /// when each of the dataplane orphans was first reported
+    orphan_sightings: parking_lot::Mutex<OrphanSightings>,
+    /// the failed replays of the operations left pending on the specs
+    spec_replays: parking_lot::Mutex<SpecReplays>,
+    /// factor of the capacity of a pool which its replica specs may add up to
+    pool_overcommit_factor: Option<f64>,
+    /// device uri of the target of each volume, as last observed
+    volume_targets: Mutex<VolumeTargets>,
+    /// changes of the targets of the volumes, as they're observed
+    volume_target_events: broadcast::Sender<VolumeTargetChanged>,
+}
+
+/// The settings of the `Registry`, eg: as given through the command line arguments.
+#[derive(Debug, Clone)]
+pub(crate) struct RegistryConfig {
+    /// period at which the cache of all resources is reloaded from all nodes
+    pub(crate) cache_period: std::time::Duration,
+    /// comma separated endpoints of the persistent store
+    pub(crate) store_url: String,
+    /// timeout of the store operations
+    pub(crate) store_timeout: std::time::Duration,
+    /// ttl of the lease of the store
+    pub(crate) store_lease_ttl: std::time::Duration,
+    /// period at which the reconcile loop attempts to do work
+    pub(crate) reconcile_period: std::time::Duration,
+    /// period at which the reconcile loop checks for new work
+    pub(crate) reconcile_idle_period: std::time::Duration,
+    /// window within which a burst of reconcile triggers is coalesced into a single run
+    pub(crate) reconcile_debounce: std::time::Duration,
+    /// maximum number of concurrent rebuilds, if any
+    pub(crate) max_rebuilds: Option<NumRebuilds>,
+    /// maximum number of rebuilds which a node may be the destination of, if any
+    pub(crate) max_rebuild_destinations: Option<u32>,
+    /// fail with NotShared when unsharing a resource which is not shared
+    pub(crate) legacy_unshare: bool,
+    /// how long the id of a destroyed pool or replica is kept from being reused
+    pub(crate) tombstone_ttl: std::time::Duration,
+    /// topology of the volumes which are created without one
+    pub(crate) default_topology: Option<Topology>,
+    /// how to resolve a pool or replica which a node reports differently from its spec
+    pub(crate) conflict_policy: ConflictPolicy,
+    /// maximum size of a volume, if any
+    pub(crate) max_volume_size: Option<u64>,
+    /// minimum size of a volume
+    pub(crate) min_volume_size: u64,
+    /// how long a nexus child faulted by a device timeout is left in place before it's replaced
+    pub(crate) timed_out_child_grace_period: std::time::Duration,
+    /// how long a managed nexus may be left without an owner before it's destroyed
+    pub(crate) disowned_nexus_grace_period: std::time::Duration,
+    /// whether a nexus may be created when some of its replicas can't be attached
+    pub(crate) nexus_children_policy: NexusChildrenPolicy,
+    /// how long a node may take to connect to a remote child of a nexus it's about to create
+    pub(crate) child_probe_timeout: std::time::Duration,
+    /// each node is polled within this timeout, or the `cache_period` if `None`
+    pub(crate) node_poll_timeout: Option<std::time::Duration>,
+    /// number of retries of a failed node poll
+    pub(crate) node_poll_retries: u32,
+    /// backoff of the first retry of a failed node poll
+    pub(crate) node_poll_backoff: std::time::Duration,
+    /// minimum number of healthy replicas which a volume must be left with by the removals
+    pub(crate) min_replication_factor: u8,
+    /// how the replicas which can't be placed as per the volume topology are handled
+    pub(crate) topology_policy: TopologyPolicy,
+    /// number of the latest revisions retained in the history of each volume spec
+    pub(crate) spec_revisions: usize,
+    /// a replica absent from its node's state within this grace period of its creation is not
+    /// deemed missing
+    pub(crate) replica_creation_grace_period: std::time::Duration,
+    /// number of the latest volume creations whose scheduling trace is retained
+    pub(crate) scheduling_traces: usize,
+    /// remove the nexus children which are backed by undersized replicas
+    pub(crate) retire_undersized_children: bool,
+    /// allow the operation guards to be released forcibly
+    pub(crate) allow_guard_release: bool,
+    /// allow the persisted specs to be patched
+    pub(crate) allow_spec_patch: bool,
+    /// a volume waiting for a rebuild for longer than this is deemed of the highest priority
+    pub(crate) rebuild_aging: std::time::Duration,
+    /// how the agent operates whilst the store is unavailable
+    pub(crate) store_unavailable_policy: StoreUnavailablePolicy,
+    /// how the dirty spec reconciler retries a failed replay of a pending operation
+    pub(crate) replay_policy: ReplayPolicy,
+    /// the replicas are not placed on the pools whose replica specs would add up to more than
+    /// this factor of their capacity, if any
+    pub(crate) pool_overcommit_factor: Option<f64>,
 }
 
 impl Registry {
-    /// Create a new registry with the `cache_period` to reload the cache, the
-    /// `store_url` to connect to, a `store_timeout` for store operations
-    /// and a `reconcile_period` for reconcile operations
-    pub(crate) async fn new(
-        cache_period: std::time::Duration,
-        store_url: String,
-        store_timeout: std::time::Duration,
-        store_lease_tll: std::time::Duration,
-        reconcile_period: std::time::Duration,
-        reconcile_idle_period: std::time::Duration,
-        max_rebuilds: Option<NumRebuilds>,
-    ) -> Self {
-        let store_endpoint = Self::format_store_endpoint(&store_url);
-        tracing::info!("Connecting to persistent store at {}", store_endpoint);
+    /// Create a new registry as per the given `config`.
+    pub(crate) async fn new(config: RegistryConfig) -> Self {
+        let RegistryConfig {
+            cache_period,
+            store_url,
+            store_timeout,
+            store_lease_ttl,
+            reconcile_period,
+            reconcile_idle_period,
+            reconcile_debounce,
+            max_rebuilds,
+            max_rebuild_destinations,
+            legacy_unshare,
+            tombstone_ttl,
+            default_topology,
+            conflict_policy,
+            max_volume_size,
+            min_volume_size,
+            timed_out_child_grace_period,
+            disowned_nexus_grace_period,
+            nexus_children_policy,
+            child_probe_timeout,
+            node_poll_timeout,
+            node_poll_retries,
+            node_poll_backoff,
+            min_replication_factor,
+            topology_policy,
+            spec_revisions,
+            replica_creation_grace_period,
+            scheduling_traces,
+            retire_undersized_children,
+            allow_guard_release,
+            allow_spec_patch,
+            rebuild_aging,
+            store_unavailable_policy,
+            replay_policy,
+            pool_overcommit_factor,
+        } = config;
+        let store_endpoints = store_url
+            .split(',')
+            .map(|endpoint| Self::format_store_endpoint(endpoint.trim()))
+            .collect::<Vec<_>>();
+        tracing::info!("Connecting to persistent store at {:?}", store_endpoints);
         let store = Etcd::new_leased(
-            [&store_endpoint],
+            &store_endpoints,
             ControlPlaneService::CoreAgent,
-            store_lease_tll,
+            store_lease_ttl,
         )
         .await
-        .expect("Should connect to the persistent store");
-        tracing::info!("Connected to persistent store at {}", store_endpoint);
+        .expect("Should connect to the persistent store")
+        .with_timeout(store_timeout);
+        tracing::info!("Connected to persistent store at {:?}", store_endpoints);
         let registry = Self {
             inner: Arc::new(RegistryInner {
                 nodes: Default::default(),
                 specs: ResourceSpecsLocked::new(),
                 cache_period,
                 store: Arc::new(Mutex::new(store.clone())),
+                store_monitor: store.clone(),
                 store_timeout,
+                store_unavailable_policy,
+                store_availability: Default::default(),
                 reconcile_period,
                 reconcile_idle_period,
+                reconcile_debounce,
                 reconciler: ReconcilerControl::new(),
-                config: Self::get_config_or_panic(store).await,
+                config: Self::get_config_or_panic(store.clone()).await,
                 max_rebuilds,
+                rebuild_admission_lock: Default::default(),
+                // a waiting volume asks for a rebuild on each reconcile
+                rebuild_queue: parking_lot::Mutex::new(RebuildQueue::new(
+                    rebuild_aging,
+                    2 * reconcile_period.max(reconcile_idle_period),
+                )),
+                max_rebuild_destinations,
+                legacy_unshare,
+                tombstone_ttl,
+                default_topology,
+                volume_conditions: Default::default(),
+                conflict_policy,
+                conflicts: Default::default(),
+                cluster_defaults: parking_lot::Mutex::new(
+                    Self::get_cluster_defaults_or_panic(store.clone()).await,
+                ),
+                volume_quotas: parking_lot::Mutex::new(
+                    Self::get_volume_quotas_or_panic(store.clone()).await,
+                ),
+                volume_quotas_lock: Default::default(),
+                max_volume_size,
+                min_volume_size,
+                timed_out_child_grace_period,
+                disowned_nexus_grace_period,
+                disowned_nexuses: Default::default(),
+                nexus_children_policy,
+                child_probe_timeout,
+                node_poll_timeout: node_poll_timeout.unwrap_or(cache_period),
+                node_poll_retries,
+                node_poll_backoff,
+                node_poll_retrying: Default::default(),
+                min_replication_factor,
+                pending_removals: Default::default(),
+                topology_policy,
+                reconciler_switches: parking_lot::Mutex::new(ReconcilerSwitches::new(
+                    Self::get_reconciler_passes_or_panic(store).await,
+                )),
+                reconciler_switches_lock: Default::default(),
+                spec_revisions,
+                replica_creation_grace_period,
+                scheduling_traces: parking_lot::Mutex::new(SchedulingTraceLog::new(
+                    scheduling_traces,
+                )),
+                retire_undersized_children,
+                allow_guard_release,
+                allow_spec_patch,
+                latencies: Default::default(),
+                volume_transitions: Default::default(),
+                pool_transitions: Default::default(),
+                orphan_sightings: Default::default(),
+                spec_replays: parking_lot::Mutex::new(SpecReplays::new(replay_policy)),
+                pool_overcommit_factor,
+                volume_targets: Default::default(),
+                volume_target_events: broadcast::channel(VOLUME_TARGET_EVENTS).0,
             }),
         };
         registry.init().await;
@@ -150,6 +433,97 @@ impl Registry {
         &self.config
     }
 
+    /// Get the `ClusterDefaults` from etcd, if they've been set, or use the built-in defaults.
+    async fn get_cluster_defaults_or_panic<S: Store>(mut store: S) -> ClusterDefaults {
+        let defaults = ClusterDefaults::default();
+        match store.get_obj(&defaults.key()).await {
+            Ok(store_defaults) => store_defaults,
+            Err(StoreError::MissingEntry { .. }) => defaults,
+            Err(error) => panic!(
+                "Must be able to access the persistent store to load the cluster defaults. Got error: '{:#?}'", error
+            ),
+        }
+    }
+    /// Get the current cluster defaults
+    pub(crate) fn cluster_defaults(&self) -> ClusterDefaults {
+        self.cluster_defaults.lock().clone()
+    }
+    /// Persist the new cluster `defaults` and use them from now on
+    pub(crate) async fn set_cluster_defaults(
+        &self,
+        defaults: ClusterDefaults,
+    ) -> Result<ClusterDefaults, SvcError> {
+        self.store_obj(&defaults).await?;
+        *self.cluster_defaults.lock() = defaults.clone();
+        Ok(defaults)
+    }
+
+    /// Get the `VolumeQuotas` from etcd, if they've been set, or start without any quotas.
+    async fn get_volume_quotas_or_panic<S: Store>(mut store: S) -> VolumeQuotas {
+        let quotas = VolumeQuotas::default();
+        match store.get_obj(&quotas.key()).await {
+            Ok(store_quotas) => store_quotas,
+            Err(StoreError::MissingEntry { .. }) => quotas,
+            Err(error) => panic!(
+                "Must be able to access the persistent store to load the volume quotas. Got error: '{:#?}'", error
+            ),
+        }
+    }
+    /// Get the current volume quotas
+    pub(crate) fn volume_quotas(&self) -> VolumeQuotas {
+        self.volume_quotas.lock().clone()
+    }
+    /// Persist the new volume `quotas` and enforce them from now on
+    pub(crate) async fn set_volume_quotas(&self, quotas: VolumeQuotas) -> Result<(), SvcError> {
+        // not while a volume is being checked against the current quotas
+        let _guard = self.volume_quotas_lock.lock().await;
+        self.store_obj(&quotas).await?;
+        *self.volume_quotas.lock() = quotas;
+        Ok(())
+    }
+    /// Lock which serializes the creation of the volumes which are subject to a quota
+    pub(crate) fn volume_quotas_lock(&self) -> &Mutex<()> {
+        &self.volume_quotas_lock
+    }
+
+    /// Get the `ReconcilerPasses` from etcd, if they've been set, or start with all passes enabled.
+    async fn get_reconciler_passes_or_panic<S: Store>(mut store: S) -> ReconcilerPasses {
+        let passes = ReconcilerPasses::default();
+        match store.get_obj(&passes.key()).await {
+            Ok(store_passes) => store_passes,
+            Err(StoreError::MissingEntry { .. }) => passes,
+            Err(error) => panic!(
+                "Must be able to access the persistent store to load the reconciler passes. Got error: '{:#?}'", error
+            ),
+        }
+    }
+    /// Check whether a run of the reconciler `pass` may go ahead, counting it as skipped if not
+    pub(crate) fn reconciler_pass_enabled(&self, pass: ReconcilerPass) -> bool {
+        self.reconciler_switches.lock().run(pass)
+    }
+    /// Get the current state of the reconciler passes
+    pub(crate) fn reconciler_passes(&self) -> ReconcilerPassStates {
+        self.reconciler_switches.lock().states()
+    }
+    /// Persist whether the reconciler `pass` is `enabled` and apply it from now on
+    pub(crate) async fn set_reconciler_pass(
+        &self,
+        pass: ReconcilerPass,
+        enabled: bool,
+    ) -> Result<(), SvcError> {
+        let _guard = self.reconciler_switches_lock.lock().await;
+        let mut passes = self.reconciler_switches.lock().passes().clone();
+        passes.set(pass, enabled);
+        self.store_obj(&passes).await?;
+        self.reconciler_switches.lock().set(pass, enabled);
+        Ok(())
+    }
+
+    /// Probe the persistent store endpoints and report their health
+    pub(crate) async fn store_health(&self) -> StoreHealth {
+        self.store_monitor.health().await
+    }
+
     /// reconciliation period when no work is being done
     pub(crate) fn reconcile_idle_period(&self) -> std::time::Duration {
         self.reconcile_idle_period
@@ -158,6 +532,220 @@ impl Registry {
     pub(crate) fn reconcile_period(&self) -> std::time::Duration {
         self.reconcile_period
     }
+    /// window within which a burst of reconcile triggering events is coalesced into a single run
+    pub(crate) fn reconcile_debounce(&self) -> std::time::Duration {
+        self.reconcile_debounce
+    }
+    /// how long a destroyed resource id may not be reused for
+    pub(crate) fn tombstone_ttl(&self) -> std::time::Duration {
+        self.tombstone_ttl
+    }
+    /// topology of the volumes which are created without one
+    pub(crate) fn default_topology(&self) -> Option<&Topology> {
+        self.default_topology.as_ref()
+    }
+    /// maximum size of a volume, in bytes
+    pub(crate) fn max_volume_size(&self) -> Option<u64> {
+        self.max_volume_size
+    }
+    /// minimum size of a volume, in bytes
+    pub(crate) fn min_volume_size(&self) -> u64 {
+        self.min_volume_size
+    }
+    /// how long a child whose device timed out is left in place before it's replaced
+    pub(crate) fn timed_out_child_grace_period(&self) -> std::time::Duration {
+        self.timed_out_child_grace_period
+    }
+    /// How much longer the managed nexus `nexus`, which is not owned, should be left in place
+    /// before it's destroyed, as it may yet regain an owner.
+    /// The grace period starts when the nexus is first observed without an owner.
+    pub(crate) fn disowned_nexus_grace_left(&self, nexus: &NexusId) -> Option<std::time::Duration> {
+        let mut disowned = self.disowned_nexuses.lock();
+        let since = disowned.entry(nexus.clone()).or_insert_with(|| {
+            tracing::warn!(
+                nexus.uuid = %nexus,
+                "Managed nexus is not owned, destroying it in {:?} unless it regains an owner",
+                self.disowned_nexus_grace_period
+            );
+            std::time::Instant::now()
+        });
+        self.disowned_nexus_grace_period
+            .checked_sub(since.elapsed())
+            .filter(|left| !left.is_zero())
+    }
+    /// Stop tracking the nexus `nexus` as not owned, returning whether it was tracked.
+    pub(crate) fn forget_disowned_nexus(&self, nexus: &NexusId) -> bool {
+        self.disowned_nexuses.lock().remove(nexus).is_some()
+    }
+    /// Stop tracking the nexuses which are not owned and which no longer exist.
+    pub(crate) fn retain_disowned_nexuses(&self, existing: &[NexusId]) {
+        self.disowned_nexuses
+            .lock()
+            .retain(|nexus, _| existing.contains(nexus));
+    }
+    /// whether a nexus may be created without the children whose nodes are offline
+    pub(crate) fn nexus_children_policy(&self) -> NexusChildrenPolicy {
+        self.nexus_children_policy
+    }
+    /// how long a node may take to connect to a remote nexus child before it's deemed unreachable
+    pub(crate) fn child_probe_timeout(&self) -> std::time::Duration {
+        self.child_probe_timeout
+    }
+    /// Get the policy used to resolve the pools and replicas reported differently from their specs
+    pub(crate) fn conflict_policy(&self) -> ConflictPolicy {
+        self.conflict_policy
+    }
+    /// Get the pools and replicas flagged for manual resolution
+    pub(crate) fn conflicts(&self) -> &parking_lot::Mutex<FlaggedConflicts> {
+        &self.conflicts
+    }
+    /// Minimum number of healthy replicas which the volumes are left with by the operations which
+    /// remove replicas
+    pub(crate) fn min_replication_factor(&self) -> u8 {
+        self.min_replication_factor
+    }
+    /// Get the volume replicas which in-flight operations are about to remove
+    pub(crate) fn pending_removals(&self) -> &parking_lot::Mutex<PendingRemovals> {
+        &self.pending_removals
+    }
+    /// Get the maximum number of rebuilds a node may be the destination of to be given a
+    /// replacement replica, if any
+    pub(crate) fn max_rebuild_destinations(&self) -> Option<u32> {
+        self.max_rebuild_destinations
+    }
+    /// Get the factor of the capacity of a pool which its replica specs may add up to, if any
+    pub(crate) fn pool_overcommit_factor(&self) -> Option<f64> {
+        self.pool_overcommit_factor
+    }
+    /// Get the policy for the volumes missing replicas which can't be placed as per their topology
+    pub(crate) fn topology_policy(&self) -> TopologyPolicy {
+        self.topology_policy
+    }
+    /// Get the number of revisions retained in the history of each volume spec
+    pub(crate) fn spec_revisions(&self) -> usize {
+        self.spec_revisions
+    }
+    /// how long a just created replica may be absent from its node's state before it's deemed
+    /// missing
+    pub(crate) fn replica_creation_grace_period(&self) -> std::time::Duration {
+        self.replica_creation_grace_period
+    }
+    /// Get the traces of the scheduling of the replicas of the latest volume creations
+    pub(crate) fn scheduling_traces(&self) -> &parking_lot::Mutex<SchedulingTraceLog> {
+        &self.scheduling_traces
+    }
+    /// Whether the nexus children backed by a replica smaller than their nexus are removed
+    pub(crate) fn retire_undersized_children(&self) -> bool {
+        self.retire_undersized_children
+    }
+    /// Whether the operation guards may be released forcibly through the admin API
+    pub(crate) fn allow_guard_release(&self) -> bool {
+        self.allow_guard_release
+    }
+    /// Whether the persisted specs may be patched through the admin API
+    pub(crate) fn allow_spec_patch(&self) -> bool {
+        self.allow_spec_patch
+    }
+    /// Get the round-trip times between the nodes, as reported on their registration
+    pub(crate) fn latencies(&self) -> &parking_lot::Mutex<LatencyMap> {
+        &self.latencies
+    }
+    /// Get the time of the last transition of the conditions of each volume
+    pub(crate) fn volume_transitions(&self) -> &parking_lot::Mutex<ConditionTransitions<VolumeId>> {
+        &self.volume_transitions
+    }
+    /// Get the time of the last transition of the conditions of each pool
+    pub(crate) fn pool_transitions(&self) -> &parking_lot::Mutex<ConditionTransitions<PoolId>> {
+        &self.pool_transitions
+    }
+    /// Get when each of the dataplane orphans was first reported
+    pub(crate) fn orphan_sightings(&self) -> &parking_lot::Mutex<OrphanSightings> {
+        &self.orphan_sightings
+    }
+    /// Get the failed replays of the operations left pending on the specs
+    pub(crate) fn spec_replays(&self) -> &parking_lot::Mutex<SpecReplays> {
+        &self.spec_replays
+    }
+    /// Check whether the operation pending on the spec `id` of the given `kind` may be replayed,
+    /// ie: it's neither waiting to retry a failed replay nor given up on as stuck.
+    pub(crate) fn replay_due(&self, kind: &ResourceKind, id: &str) -> bool {
+        self.spec_replays
+            .lock()
+            .due(kind, id, std::time::Instant::now())
+    }
+    /// Record whether the replay of the operation pending on the spec `id` of the given `kind`
+    /// `succeeded`, returning it.
+    pub(crate) fn replayed(&self, kind: &ResourceKind, id: &str, succeeded: bool) -> bool {
+        let now = std::time::Instant::now();
+        let transition = self.spec_replays.lock().record(kind, id, succeeded, now);
+        match transition {
+            Some(ReplayTransition::Recovered(attempts)) => {
+                tracing::info!(?kind, %id, attempts, "Replayed the pending operation of the spec");
+            }
+            Some(ReplayTransition::Backoff(backoff)) => {
+                tracing::debug!(
+                    ?kind,
+                    %id,
+                    backoff = %humantime::Duration::from(backoff),
+                    "Failed to replay the pending operation of the spec, retrying later"
+                );
+            }
+            Some(ReplayTransition::Stuck) => {
+                let stuck = self
+                    .spec_replays
+                    .lock()
+                    .failed()
+                    .iter()
+                    .filter(|(_, _, replay)| replay.stuck)
+                    .count();
+                tracing::error!(
+                    ?kind,
+                    %id,
+                    stuck,
+                    "Gave up replaying the pending operation of the spec, until it's retried"
+                );
+            }
+            None => {}
+        }
+        succeeded
+    }
+    /// Get the volumes waiting for a rebuild
+    pub(crate) fn rebuild_queue(&self) -> &parking_lot::Mutex<RebuildQueue> {
+        &self.rebuild_queue
+    }
+    /// Get a reference to the device uris of the targets of the volumes, as last observed
+    pub(crate) fn volume_targets(&self) -> &Mutex<VolumeTargets> {
+        &self.volume_targets
+    }
+    /// Publish an observed change of the target of a volume to its subscribers, if any
+    pub(crate) fn publish_volume_target_change(&self, event: VolumeTargetChanged) {
+        tracing::info!(
+            volume.uuid = %event.volume,
+            old_uri = %event.old_uri,
+            new_uri = %event.new_uri,
+            "Volume target changed"
+        );
+        self.volume_target_events.send(event).ok();
+    }
+    /// Subscribe to the changes of the targets of the volumes
+    pub(crate) fn subscribe_volume_targets(&self) -> broadcast::Receiver<VolumeTargetChanged> {
+        self.volume_target_events.subscribe()
+    }
+    /// Get the conditions of the given volume, as of the latest divergence check
+    pub(crate) fn volume_conditions(&self, volume: &VolumeId) -> Vec<VolumeCondition> {
+        self.volume_conditions
+            .lock()
+            .get(volume)
+            .cloned()
+            .unwrap_or_default()
+    }
+    /// Replace the conditions of all the volumes, returning the previous conditions
+    pub(crate) fn replace_volume_conditions(
+        &self,
+        conditions: HashMap<VolumeId, Vec<VolumeCondition>>,
+    ) -> HashMap<VolumeId, Vec<VolumeCondition>> {
+        std::mem::replace(&mut *self.volume_conditions.lock(), conditions)
+    }
 
     /// Get a reference to the actual state of the nodes
     pub(crate) fn nodes(&self) -> &NodesMapLocked {
@@ -168,8 +756,24 @@ impl Registry {
         &self.specs
     }
 
+    /// Check whether the agent is degraded to read-only operation, as the persistent store is
+    /// unavailable
+    pub(crate) fn store_read_only(&self) -> bool {
+        self.store_unavailable_policy == StoreUnavailablePolicy::ReadOnly
+            && self.store_availability.lock().unavailable()
+    }
+    /// Fail fast with `StoreUnavailable` whilst the agent is degraded to read-only operation,
+    /// rather than waiting for the store operation to time out
+    pub(crate) fn store_available(&self) -> Result<(), SvcError> {
+        match self.store_read_only() {
+            true => Err(SvcError::StoreUnavailable {}),
+            false => Ok(()),
+        }
+    }
+
     /// Serialized write to the persistent store
     pub(crate) async fn store_obj<O: StorableObject>(&self, object: &O) -> Result<(), SvcError> {
+        self.store_available()?;
         let mut store = self.store.lock().await;
         match tokio::time::timeout(
             self.store_timeout,
@@ -186,8 +790,28 @@ impl Registry {
         }
     }
 
+    /// Serialized write of a batch of objects to the persistent store, all at once if possible
+    pub(crate) async fn store_batch(&self, batch: &StoreBatch) -> Result<(), SvcError> {
+        self.store_available()?;
+        let mut store = self.store.lock().await;
+        match tokio::time::timeout(
+            self.store_timeout,
+            async move { store.put_batch(batch).await },
+        )
+        .await
+        {
+            Ok(result) => result.map_err(Into::into),
+            Err(_) => Err(StoreError::Timeout {
+                operation: "PutBatch".to_string(),
+                timeout: self.store_timeout,
+            }
+            .into()),
+        }
+    }
+
     /// Serialized read from the persistent store
     pub(crate) async fn load_obj<O: StorableObject>(&self, key: &O::Key) -> Result<O, SvcError> {
+        self.store_available()?;
         let mut store = self.store.lock().await;
         match tokio::time::timeout(self.store_timeout, async move { store.get_obj(key).await })
             .await
@@ -203,6 +827,7 @@ impl Registry {
 
     /// Serialized delete to the persistent store
     pub(crate) async fn delete_kv<K: StoreKey>(&self, key: &K) -> Result<(), SvcError> {
+        self.store_available()?;
         let mut store = self.store.lock().await;
         match tokio::time::timeout(
             self.store_timeout,
@@ -245,6 +870,12 @@ impl Registry {
         tokio::spawn(async move {
             registry.poller().await;
         });
+        if self.store_unavailable_policy == StoreUnavailablePolicy::ReadOnly {
+            let registry = self.clone();
+            tokio::spawn(async move {
+                registry.store_watchdog().await;
+            });
+        }
         let registry = self.clone();
         self.reconciler.start(registry).await;
     }
@@ -270,47 +901,171 @@ impl Registry {
         self.reconciler.notify(PollEvent::Triggered(event)).await
     }
 
-    /// Poll each node for resource updates
+    /// Check the health of the persistent store every cache period, degrading the agent to
+    /// read-only operation whilst none of the store endpoints can be reached.
+    /// Once the store is back the reconciler is triggered, replaying the dirty specs.
+    async fn store_watchdog(&self) {
+        loop {
+            let health = self.store_health().await;
+            let available = health.endpoints.iter().any(|endpoint| endpoint.reachable);
+            let transition = self
+                .store_availability
+                .lock()
+                .update(available, std::time::Instant::now());
+            match transition {
+                Some(StoreTransition::Lost) => {
+                    tracing::warn!(
+                        ?health,
+                        "The persistent store is unavailable, switching to read-only operation"
+                    );
+                }
+                Some(StoreTransition::Recovered(outage)) => {
+                    tracing::info!(
+                        ?outage,
+                        "The persistent store is available again, resuming normal operation"
+                    );
+                    self.notify(PollTriggerEvent::StoreAvailable).await;
+                }
+                None => {}
+            }
+            tokio::time::sleep(self.cache_period).await;
+        }
+    }
+
+    /// Poll each node for resource updates and check the volumes against the updated state
+    /// The nodes are polled concurrently, each within the node poll timeout, so a slow node
+    /// doesn't delay the others. Failed polls are retried in the background.
     async fn poller(&self) {
         loop {
-            {
-                // Clone the nodes so we don't hold the read lock on the nodes list while
-                // we may be busy or waiting on node information being fetched.
-                let nodes = self.nodes().read().await.clone();
-                for (_, node) in nodes.iter() {
-                    let (id, online) = {
-                        let node = node.read().await;
-                        (node.id().clone(), node.is_online())
-                    };
-                    if online {
-                        if let Err(error) = node.update_all(false).await {
-                            tracing::error!(node = %id, error = %error, "Failed to reload node");
-                        }
+            for (id, result) in self.poll_nodes().await {
+                match result {
+                    Ok(()) | Err(SvcError::NodeNotOnline { .. }) => {}
+                    Err(error) => {
+                        tracing::error!(node = %id, error = %error, "Failed to reload node");
+                        self.retry_node_poll(id);
                     }
                 }
             }
+            self.check_volume_conditions().await;
+            self.refresh_volume_transitions().await;
+            self.refresh_volume_targets().await;
+            self.refresh_pool_transitions().await;
             tokio::time::sleep(self.cache_period).await;
         }
     }
 
-    /// Determine if a rebuild is allowed to start.
-    /// Constrain the number of system-wide rebuilds to the maximum specified.
+    /// Reload the resources of all the online nodes into the registry, returning the result of
+    /// each node. The nodes which are not online are not polled and fail with `NodeNotOnline`.
+    /// The nodes in maintenance are not polled either, keeping their last known resources, as
+    /// they may be restarted without their status being changed.
+    pub(crate) async fn poll_nodes(&self) -> Vec<(NodeId, Result<(), SvcError>)> {
+        // Clone the nodes so we don't hold the read lock on the nodes list while
+        // we may be busy or waiting on node information being fetched.
+        let nodes = self.nodes().read().await.clone();
+        let polls = nodes.into_iter().map(|(_, node)| self.poll_node(node));
+        futures::future::join_all(polls).await
+    }
+
+    /// Reload the resources of the `node` into the registry, giving up on it if it takes longer
+    /// than the node poll timeout.
+    async fn poll_node(&self, node: Arc<RwLock<NodeWrapper>>) -> (NodeId, Result<(), SvcError>) {
+        let (id, online) = {
+            let node = node.read().await;
+            (node.id().clone(), node.is_online())
+        };
+        let result = match online {
+            true if self.specs().node_in_maintenance(&id) => Ok(()),
+            true => tokio::time::timeout(self.node_poll_timeout, node.update_all(false))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(SvcError::NodePollTimeout {
+                        node_id: id.to_string(),
+                        timeout: self.node_poll_timeout,
+                    })
+                }),
+            false => Err(SvcError::NodeNotOnline { node: id.clone() }),
+        };
+        (id, result)
+    }
+
+    /// Retry the failed poll of the node `id` in the background, with an exponential backoff,
+    /// unless it's already being retried.
+    fn retry_node_poll(&self, id: NodeId) {
+        if self.node_poll_retries == 0 || !self.node_poll_retrying.lock().insert(id.clone()) {
+            return;
+        }
+        let registry = self.clone();
+        tokio::spawn(async move {
+            let mut backoff = registry.node_poll_backoff;
+            for attempt in 1 ..= registry.node_poll_retries {
+                tokio::time::sleep(backoff).await;
+                let node = match registry.get_node_wrapper(&id).await {
+                    Ok(node) => node,
+                    Err(_) => break,
+                };
+                match registry.poll_node(node).await.1 {
+                    Ok(()) => {
+                        tracing::info!(node = %id, attempt, "Reloaded node after a failed poll");
+                        break;
+                    }
+                    Err(SvcError::NodeNotOnline { .. }) => break,
+                    Err(error) => {
+                        tracing::warn!(node = %id, attempt, error = %error, "Failed to reload node");
+                    }
+                }
+                backoff *= 2;
+            }
+            registry.node_poll_retrying.lock().remove(&id);
+        });
+    }
+
+    /// Determine if a rebuild of the `volume` of the given rebuild `priority` is allowed to start.
+    /// Constrain the number of system-wide rebuilds to the maximum specified, the free rebuild
+    /// slots going to the waiting volumes as ranked by the rebuild queue.
     /// If a maximum is not specified, do not limit the number of rebuilds.
-    pub(crate) async fn rebuild_allowed(&self) -> Result<(), SvcError> {
+    /// The returned guard must be held until the rebuild is started, and the node's rebuild count
+    /// refreshed, otherwise concurrent callers could each observe room for "one more" rebuild.
+    pub(crate) async fn rebuild_allowed(
+        &self,
+        volume: &VolumeId,
+        priority: RebuildPriority,
+    ) -> Result<Option<MutexGuard<'_, ()>>, SvcError> {
         match self.max_rebuilds {
             Some(max_rebuilds) => {
+                let guard = self.rebuild_admission_lock.lock().await;
                 let mut num_rebuilds = 0;
                 for (_id, node_wrapper) in self.nodes.read().await.iter() {
                     num_rebuilds += node_wrapper.read().await.num_rebuilds();
                 }
 
-                if num_rebuilds < max_rebuilds {
-                    Ok(())
-                } else {
-                    Err(SvcError::MaxRebuilds { max_rebuilds })
+                let slots = max_rebuilds.saturating_sub(num_rebuilds);
+                let admission = self.rebuild_queue.lock().admit(
+                    volume,
+                    priority,
+                    slots,
+                    std::time::Instant::now(),
+                );
+                match admission {
+                    Ok(()) => Ok(Some(guard)),
+                    Err(_) if slots == 0 => Err(SvcError::MaxRebuilds { max_rebuilds }),
+                    Err(ahead) => Err(SvcError::RebuildQueued {
+                        volume: volume.to_string(),
+                        priority: priority.to_string(),
+                        ahead,
+                    }),
                 }
             }
-            None => Ok(()),
+            None => Ok(None),
+        }
+    }
+
+    /// Check if an unshare request for a resource which is not shared should be allowed to
+    /// succeed, as a no-op.
+    /// With the legacy behaviour the `NotShared` error is returned instead.
+    pub(crate) fn unshare_idempotent(&self, error: SvcError) -> Result<(), SvcError> {
+        match error {
+            SvcError::NotShared { .. } if !self.legacy_unshare => Ok(()),
+            error => Err(error),
         }
     }
 
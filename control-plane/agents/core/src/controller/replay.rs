@@ -0,0 +1,212 @@
+use common_lib::{transport_api::ResourceKind, types::v0::transport::OperationReplay};
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+/// The longest wait between two attempts at replaying the operation pending on a spec.
+const MAX_REPLAY_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// How the dirty spec reconciler retries the replay of the operations pending on the specs.
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct ReplayPolicy {
+    /// number of consecutive failed attempts after which a replay is given up on as stuck
+    max_attempts: u32,
+    /// wait before retrying a failed replay, doubled on each subsequent failure
+    backoff: Duration,
+}
+
+impl ReplayPolicy {
+    /// Return a new `Self` which gives up after `max_attempts`, the first retry waiting `backoff`.
+    pub(crate) fn new(max_attempts: u32, backoff: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            backoff,
+        }
+    }
+    /// Get the wait before the next attempt, after the given number of failed `attempts`.
+    fn backoff(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.backoff
+            .checked_mul(factor)
+            .unwrap_or(MAX_REPLAY_BACKOFF)
+            .min(MAX_REPLAY_BACKOFF)
+    }
+}
+
+/// Failed attempts at replaying the operation pending on a spec.
+#[derive(Debug, Copy, Clone)]
+struct FailedReplay {
+    attempts: u32,
+    /// when the replay may be attempted again
+    retry_at: Instant,
+}
+
+/// Change of the replay of the operation pending on a spec, which is worth logging.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum ReplayTransition {
+    /// the replay succeeded after the given number of failed attempts
+    Recovered(u32),
+    /// the replay failed again and is retried after the given wait
+    Backoff(Duration),
+    /// the replay failed too many times and is no longer attempted
+    Stuck,
+}
+
+/// The failed replays of the operations pending on the specs, kept in memory only, so that a spec
+/// whose replay keeps failing is retried with an exponential backoff rather than on every
+/// reconcile, and is given up on as stuck after too many attempts.
+/// A stuck replay is only attempted again once it's retried through the admin API.
+#[derive(Debug)]
+pub(crate) struct SpecReplays {
+    policy: ReplayPolicy,
+    failed: HashMap<(ResourceKind, String), FailedReplay>,
+}
+
+impl SpecReplays {
+    /// Return a new `Self` which retries the replays as per the `policy`
+    pub(crate) fn new(policy: ReplayPolicy) -> Self {
+        Self {
+            policy,
+            failed: HashMap::new(),
+        }
+    }
+    /// Check whether the replay of the spec `id` of the given `kind` may be attempted at `now`.
+    pub(crate) fn due(&self, kind: &ResourceKind, id: &str, now: Instant) -> bool {
+        match self.failed.get(&(kind.clone(), id.to_string())) {
+            None => true,
+            Some(failed) => failed.attempts < self.policy.max_attempts && now >= failed.retry_at,
+        }
+    }
+    /// Record whether the replay of the spec `id` of the given `kind` `succeeded` at `now`,
+    /// returning the resulting transition, if any.
+    pub(crate) fn record(
+        &mut self,
+        kind: &ResourceKind,
+        id: &str,
+        succeeded: bool,
+        now: Instant,
+    ) -> Option<ReplayTransition> {
+        let key = (kind.clone(), id.to_string());
+        if succeeded {
+            return self
+                .failed
+                .remove(&key)
+                .map(|failed| ReplayTransition::Recovered(failed.attempts));
+        }
+        let policy = self.policy;
+        let failed = self.failed.entry(key).or_insert(FailedReplay {
+            attempts: 0,
+            retry_at: now,
+        });
+        failed.attempts += 1;
+        if failed.attempts >= policy.max_attempts {
+            return Some(ReplayTransition::Stuck);
+        }
+        let backoff = policy.backoff(failed.attempts);
+        failed.retry_at = now + backoff;
+        Some(ReplayTransition::Backoff(backoff))
+    }
+    /// Get the failed attempts at replaying the spec `id` of the given `kind`, if any.
+    pub(crate) fn replay(&self, kind: &ResourceKind, id: &str) -> Option<OperationReplay> {
+        self.failed
+            .get(&(kind.clone(), id.to_string()))
+            .map(|failed| OperationReplay {
+                attempts: failed.attempts,
+                stuck: failed.attempts >= self.policy.max_attempts,
+            })
+    }
+    /// Check whether the replay of the spec `id` of the given `kind` was given up on as stuck.
+    pub(crate) fn stuck(&self, kind: &ResourceKind, id: &str) -> bool {
+        self.replay(kind, id).map(|r| r.stuck).unwrap_or_default()
+    }
+    /// Forget the failed attempts at replaying the spec `id` of the given `kind`, so it's replayed
+    /// on the next reconcile, returning what they were.
+    pub(crate) fn retry(&mut self, kind: &ResourceKind, id: &str) -> Option<OperationReplay> {
+        let replay = self.replay(kind, id);
+        self.failed.remove(&(kind.clone(), id.to_string()));
+        replay
+    }
+    /// Get the specs whose replay has failed, along with their failed attempts.
+    pub(crate) fn failed(&self) -> Vec<(ResourceKind, String, OperationReplay)> {
+        self.failed
+            .keys()
+            .filter_map(|(kind, id)| Some((kind.clone(), id.clone(), self.replay(kind, id)?)))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A replay which always fails, as if its spec could never be written to the store, is backed
+    /// off exponentially until it's given up on, and is only attempted again once it's retried.
+    #[test]
+    fn stuck_replay() {
+        let policy = ReplayPolicy::new(3, Duration::from_secs(1));
+        let mut replays = SpecReplays::new(policy);
+        let kind = ResourceKind::Nexus;
+        let start = Instant::now();
+        let secs = |secs| start + Duration::from_secs(secs);
+
+        assert!(replays.due(&kind, "n1", start));
+        assert_eq!(
+            replays.record(&kind, "n1", false, start),
+            Some(ReplayTransition::Backoff(Duration::from_secs(1)))
+        );
+        assert!(!replays.due(&kind, "n1", start));
+        // other specs are not held back
+        assert!(replays.due(&kind, "n2", start));
+        assert!(replays.due(&ResourceKind::Volume, "n1", start));
+
+        assert!(replays.due(&kind, "n1", secs(1)));
+        assert_eq!(
+            replays.record(&kind, "n1", false, secs(1)),
+            Some(ReplayTransition::Backoff(Duration::from_secs(2)))
+        );
+        assert!(!replays.due(&kind, "n1", secs(2)));
+        assert!(replays.due(&kind, "n1", secs(3)));
+        assert_eq!(
+            replays.record(&kind, "n1", false, secs(3)),
+            Some(ReplayTransition::Stuck)
+        );
+        assert!(replays.stuck(&kind, "n1"));
+        assert!(!replays.due(&kind, "n1", secs(3600)));
+        assert_eq!(
+            replays.failed(),
+            vec![(
+                kind.clone(),
+                "n1".to_string(),
+                OperationReplay {
+                    attempts: 3,
+                    stuck: true
+                }
+            )]
+        );
+
+        // the manual retry resets the attempts
+        assert_eq!(replays.retry(&kind, "n1").map(|r| r.stuck), Some(true));
+        assert!(!replays.stuck(&kind, "n1"));
+        assert!(replays.due(&kind, "n1", secs(3600)));
+        assert_eq!(
+            replays.record(&kind, "n1", false, secs(3600)),
+            Some(ReplayTransition::Backoff(Duration::from_secs(1)))
+        );
+        assert_eq!(
+            replays.record(&kind, "n1", true, secs(3601)),
+            Some(ReplayTransition::Recovered(1))
+        );
+        assert_eq!(replays.replay(&kind, "n1"), None);
+        assert_eq!(replays.record(&kind, "n1", true, secs(3602)), None);
+    }
+
+    #[test]
+    fn backoff_is_capped() {
+        let policy = ReplayPolicy::new(100, Duration::from_secs(1));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(4), Duration::from_secs(8));
+        assert_eq!(policy.backoff(20), MAX_REPLAY_BACKOFF);
+        assert_eq!(policy.backoff(64), MAX_REPLAY_BACKOFF);
+    }
+}
@@ -1,14 +1,26 @@
 pub(crate) mod nexus;
+pub(crate) mod rebuilds;
 pub(crate) mod resources;
+pub(crate) mod trace;
 pub(crate) mod volume;
 
-use crate::controller::scheduling::{
-    nexus::{GetPersistedNexusChildrenCtx, GetSuitableNodesContext},
-    resources::{ChildItem, NodeItem, PoolItem, ReplicaItem},
-    volume::{GetSuitablePoolsContext, VolumeReplicasForNexusCtx},
+use crate::controller::{
+    registry::Registry,
+    scheduling::{
+        nexus::{GetPersistedNexusChildrenCtx, GetSuitableNodesContext},
+        resources::{ChildItem, NodeItem, PoolItem, ReplicaItem},
+        volume::{GetSuitablePoolsContext, VolumeReplicasForNexusCtx},
+    },
+};
+use common_lib::types::v0::{
+    store::pool::PoolSpec,
+    transport::{PoolStatus, PoolTopology},
+};
+use std::{
+    cmp::Ordering,
+    collections::{HashMap, HashSet},
+    future::Future,
 };
-use common_lib::types::v0::transport::{PoolStatus, PoolTopology};
-use std::{cmp::Ordering, collections::HashMap, future::Future};
 
 #[async_trait::async_trait(?Send)]
 pub(crate) trait ResourceFilter: Sized {
@@ -73,14 +85,74 @@ impl NodeFilters {
     pub(crate) fn online(_request: &GetSuitableNodesContext, item: &NodeItem) -> bool {
         item.node_wrapper().is_online()
     }
+    /// Should only attempt to use nodes whose value of the spread label of the topology is not
+    /// used by another replica of the volume, so its replicas are spread across the values.
+    /// Pools without the label are not constrained. When there are fewer distinct values than
+    /// replicas, the replicas can't all be spread so none of the nodes are rejected.
+    pub(crate) fn topology_spread(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let key = match request.topology.as_ref().and_then(|t| t.spread.as_ref()) {
+            Some(key) => key,
+            None => return true,
+        };
+        let registry = request.registry();
+        let specs = registry.specs();
+        let value = match specs.get_pool(&item.pool.id) {
+            Ok(pool) => match spread_label(registry, key, &pool) {
+                Some(value) => value,
+                None => return true,
+            },
+            Err(_) => return true,
+        };
+        let values = specs
+            .get_pools()
+            .iter()
+            .filter_map(|pool| spread_label(registry, key, pool))
+            .collect::<HashSet<_>>();
+        if values.len() < request.desired_num_replicas() as usize {
+            return true;
+        }
+        !specs
+            .get_cloned_volume_replicas(&request.uuid)
+            .iter()
+            .filter(|replica| replica.pool != item.pool.id)
+            .filter_map(|replica| specs.get_pool(&replica.pool).ok())
+            .any(|pool| spread_label(registry, key, &pool).as_ref() == Some(&value))
+    }
+}
+
+/// Get the value of the spread label `key` of the `pool`, or else of its node
+pub(crate) fn spread_label(registry: &Registry, key: &str, pool: &PoolSpec) -> Option<String> {
+    let pool_value = pool
+        .labels
+        .as_ref()
+        .and_then(|labels| labels.get(key).cloned());
+    pool_value.or_else(|| {
+        let node = registry.specs().get_node(&pool.node).ok()?;
+        node.labels().get(key).cloned()
+    })
 }
 
 /// Filter pools used for replica creation
 pub(crate) struct PoolFilters {}
 impl PoolFilters {
-    /// Should only attempt to use pools with sufficient free space
+    /// Should only attempt to use pools with sufficient free space for the replica, whose size is
+    /// rounded up to a whole number of the clusters of the pool
     pub(crate) fn free_space(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
-        item.pool.free_space() > request.size
+        item.pool.free_space() > item.pool.replica_size(request.size)
+    }
+    /// Should only attempt to use pools whose replica specs, with the replica, don't add up to
+    /// more than the overcommit factor of their capacity, if any
+    pub(crate) fn overcommit_aware(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let factor = match request.overcommit_factor() {
+            Some(factor) => factor,
+            None => return true,
+        };
+        let committed = request
+            .registry()
+            .specs()
+            .get_pool_committed_size(&item.pool.id);
+        let size = committed.saturating_add(item.pool.replica_size(request.size));
+        size as f64 <= item.pool.capacity as f64 * factor
     }
     /// Should only attempt to use usable (not faulted) pools
     pub(crate) fn usable(_: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
@@ -117,6 +189,32 @@ impl PoolFilters {
             Err(_) => false,
         };
     }
+    /// Should only attempt to use pools which don't hold replicas of the volumes which the
+    /// topology doesn't allow this volume to be co-located with
+    pub(crate) fn anti_colocation(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        let volumes = match &request.topology {
+            Some(topology) => &topology.anti_colocation,
+            None => return true,
+        };
+        let specs = request.registry().specs();
+        !volumes.iter().any(|volume| {
+            specs
+                .get_cloned_volume_replicas(volume)
+                .iter()
+                .any(|replica| replica.pool == item.pool.id)
+        })
+    }
+    /// Should only attempt to use pools on nodes which aren't already the destination of the
+    /// maximum number of rebuilds, when placing replacement replicas
+    pub(crate) fn rebuild_destinations(request: &GetSuitablePoolsContext, item: &PoolItem) -> bool {
+        match request.rebuild_load() {
+            Some(load) => load.destination_allowed(
+                &item.pool.node,
+                request.registry().max_rebuild_destinations(),
+            ),
+            None => true,
+        }
+    }
 }
 
 /// Sort the pools used for replica creation
@@ -126,6 +224,20 @@ impl PoolSorters {
     pub(crate) fn sort_by_replica_count(a: &PoolItem, b: &PoolItem) -> std::cmp::Ordering {
         a.pool.cmp(&b.pool)
     }
+    /// Sort pools by the number of rebuilds which their nodes take part in, when placing
+    /// replacement replicas, and then by their number of allocated replicas
+    pub(crate) fn sort_by_rebuild_load(
+        request: &GetSuitablePoolsContext,
+        a: &PoolItem,
+        b: &PoolItem,
+    ) -> std::cmp::Ordering {
+        match request.rebuild_load() {
+            Some(load) => load
+                .cmp(&a.pool.node, &b.pool.node)
+                .then_with(|| Self::sort_by_replica_count(a, b)),
+            None => Self::sort_by_replica_count(a, b),
+        }
+    }
 }
 
 /// Sort the nexus children for removal when decreasing a volume's replica count
@@ -224,12 +336,17 @@ impl ReplicaFilters {
         item.state().online()
     }
 
-    /// Should only allow children with corresponding replicas with enough size
+    /// Should only allow children with corresponding replicas with enough size for the nexus,
+    /// including when it's recreated, so that an undersized replica is never the source of the
+    /// rebuild of the other children
     pub(crate) fn size(request: &GetPersistedNexusChildrenCtx, item: &ChildItem) -> bool {
-        match request.vol_spec() {
-            Some(volume) => item.state().size >= volume.size,
-            None => true,
-        }
+        item.state().size >= request.size()
+    }
+
+    /// Should only allow replicas which were not left out-of-sync by an interrupted rebuild, as
+    /// these may only be added back to the nexus as rebuild destinations
+    pub(crate) fn in_sync(_request: &GetPersistedNexusChildrenCtx, item: &ChildItem) -> bool {
+        !item.spec().out_of_sync
     }
 }
 
@@ -251,6 +368,18 @@ impl ChildItemSorters {
             (_, _) => std::cmp::Ordering::Equal,
         }
     }
+    /// Sort ChildItem's for volume nexus creation
+    /// Prefer the replica designated as the source of record of the volume, as when only one
+    /// child may be used, the others are rebuilt from it
+    pub(crate) fn sort_by_source(
+        request: &GetPersistedNexusChildrenCtx,
+        a: &ChildItem,
+        b: &ChildItem,
+    ) -> std::cmp::Ordering {
+        let a_is_source = Some(&a.spec().uuid) == request.source_replica();
+        let b_is_source = Some(&b.spec().uuid) == request.source_replica();
+        b_is_source.cmp(&a_is_source)
+    }
 }
 
 /// Filter replicas when selecting the best candidates to add to a nexus
@@ -308,4 +437,17 @@ impl NodeSorters {
             .len()
             .cmp(&b.node_wrapper().nexus_states().len())
     }
+    /// Sort nodes by the aggregate round-trip time to the nodes of the replicas of the volume.
+    /// The lower the round-trip time, the more would be the selection priority of a node.
+    /// Nodes are left as they are if no round-trip times were measured.
+    pub(crate) fn replica_latency(
+        request: &GetSuitableNodesContext,
+        a: &NodeItem,
+        b: &NodeItem,
+    ) -> std::cmp::Ordering {
+        let latencies = request.latencies();
+        let a_rtt = latencies.aggregate(a.node_wrapper().id(), request.replica_nodes());
+        let b_rtt = latencies.aggregate(b.node_wrapper().id(), request.replica_nodes());
+        a_rtt.cmp(&b_rtt)
+    }
 }
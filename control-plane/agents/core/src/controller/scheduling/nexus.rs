@@ -1,4 +1,5 @@
 use crate::controller::{
+    latency::LatencyMap,
     registry::Registry,
     scheduling::{
         resources::{ChildItem, NodeItem},
@@ -9,7 +10,7 @@ use crate::controller::{
 use common::errors::SvcError;
 use common_lib::types::v0::{
     store::{nexus::NexusSpec, nexus_persistence::NexusInfo, volume::VolumeSpec},
-    transport::{ChildUri, NexusId, NodeId, VolumeId},
+    transport::{ChildUri, NexusId, NodeId, ReplicaId, VolumeId},
 };
 use itertools::Itertools;
 use std::{collections::HashMap, ops::Deref};
@@ -39,6 +40,13 @@ impl GetPersistedNexusChildren {
             Self::ReCreate(_) => None,
         }
     }
+    /// Get the size of the nexus to be created/recreated
+    pub(crate) fn size(&self) -> u64 {
+        match self {
+            Self::Create((spec, _)) => spec.size,
+            Self::ReCreate(nexus) => nexus.size,
+        }
+    }
     /// Get the target node where the nexus will be created/recreated on
     pub(crate) fn target_node(&self) -> &NodeId {
         match self {
@@ -72,6 +80,7 @@ pub(crate) struct GetPersistedNexusChildrenCtx {
     request: GetPersistedNexusChildren,
     registry: Registry,
     nexus_info: Option<NexusInfo>,
+    source_replica: Option<ReplicaId>,
 }
 
 impl GetPersistedNexusChildrenCtx {
@@ -79,6 +88,10 @@ impl GetPersistedNexusChildrenCtx {
     pub(crate) fn vol_spec(&self) -> Option<&VolumeSpec> {
         self.request.vol_spec()
     }
+    /// Get the size of the nexus to be created
+    pub(crate) fn size(&self) -> u64 {
+        self.request.size()
+    }
     /// Get the target node where the nexus will be created on
     pub(crate) fn target_node(&self) -> &NodeId {
         self.request.target_node()
@@ -87,6 +100,10 @@ impl GetPersistedNexusChildrenCtx {
     pub(crate) fn nexus_info(&self) -> &Option<NexusInfo> {
         &self.nexus_info
     }
+    /// Get the replica designated as the source of record of the volume, if any
+    pub(crate) fn source_replica(&self) -> Option<&ReplicaId> {
+        self.source_replica.as_ref()
+    }
 }
 
 impl GetPersistedNexusChildrenCtx {
@@ -97,11 +114,19 @@ impl GetPersistedNexusChildrenCtx {
         let nexus_info = registry
             .get_nexus_info(request.volume_id(), request.nexus_info_id(), false)
             .await?;
+        let source_replica = match request {
+            GetPersistedNexusChildren::Create((vol_spec, _)) => vol_spec.source_replica.clone(),
+            GetPersistedNexusChildren::ReCreate(_) => request
+                .volume_id()
+                .and_then(|volume_id| registry.specs().get_volume(volume_id).ok())
+                .and_then(|vol_spec| vol_spec.source_replica),
+        };
 
         Ok(Self {
             registry: registry.clone(),
             request: request.clone(),
             nexus_info,
+            source_replica,
         })
     }
     async fn list(&self) -> Vec<ChildItem> {
@@ -177,7 +202,11 @@ impl CreateVolumeNexus {
     /// criteria (any order):
     /// 1. if it's a nexus recreation, then use only children marked as healthy by the io-engine
     /// 2. use only replicas which report the status of online by their state
-    /// 3. use only replicas which are large enough for the volume
+    /// 3. use only replicas which are large enough for the nexus, as the children are rebuilt from
+    /// these
+    /// 4. use only replicas whose last rebuild was not interrupted
+    /// The children are sorted by locality, though the source replica of the volume, if
+    /// designated, comes first.
     pub(crate) async fn builder_with_defaults(
         request: &GetPersistedNexusChildren,
         registry: &Registry,
@@ -187,7 +216,9 @@ impl CreateVolumeNexus {
             .filter(ChildInfoFilters::healthy)
             .filter(ReplicaFilters::online)
             .filter(ReplicaFilters::size)
-            .sort_ctx(ChildItemSorters::sort_by_locality))
+            .filter(ReplicaFilters::in_sync)
+            .sort_ctx(ChildItemSorters::sort_by_locality)
+            .sort_ctx(ChildItemSorters::sort_by_source))
     }
 }
 
@@ -254,6 +285,21 @@ pub(crate) struct GetSuitableNodesContext {
     #[allow(dead_code)]
     registry: Registry,
     spec: VolumeSpec,
+    /// nodes of the replicas of the volume
+    replica_nodes: Vec<NodeId>,
+    /// round-trip times between the nodes, as of the start of the selection
+    latencies: LatencyMap,
+}
+
+impl GetSuitableNodesContext {
+    /// Get the nodes of the replicas of the volume
+    pub(crate) fn replica_nodes(&self) -> &[NodeId] {
+        &self.replica_nodes
+    }
+    /// Get the round-trip times between the nodes
+    pub(crate) fn latencies(&self) -> &LatencyMap {
+        &self.latencies
+    }
 }
 
 impl Deref for GetSuitableNodesContext {
@@ -300,6 +346,19 @@ impl ResourceFilter for NexusTargetNode {
         self
     }
 
+    fn sort_ctx<P: FnMut(&Self::Request, &Self::Item, &Self::Item) -> std::cmp::Ordering>(
+        mut self,
+        mut sort: P,
+    ) -> Self {
+        let context = self.context.clone();
+        self.list = self
+            .list
+            .into_iter()
+            .sorted_by(|a, b| sort(&context, a, b))
+            .collect();
+        self
+    }
+
     fn collect(self) -> Vec<Self::Item> {
         self.list
     }
@@ -312,6 +371,16 @@ impl NexusTargetNode {
             context: GetSuitableNodesContext {
                 registry: registry.clone(),
                 spec: request.spec.clone(),
+                replica_nodes: registry
+                    .specs()
+                    .get_volume_replicas(&request.spec.uuid)
+                    .into_iter()
+                    .filter_map(|replica| {
+                        let pool = replica.lock().pool.clone();
+                        registry.specs().get_pool(&pool).ok().map(|pool| pool.node)
+                    })
+                    .collect(),
+                latencies: registry.latencies().lock().clone(),
             },
             list: {
                 let current_target = request.spec.target;
@@ -337,7 +406,9 @@ impl NexusTargetNode {
 
     /// Get `Self` with a default set of filters for nodes following the criteria (any order):
     /// 1. The target node should be online.
-    /// 2. Give preference to nodes which have lesser number of active nexuses, for
+    /// 2. Give preference to nodes with the lowest round-trip times to the replicas of the
+    /// volume, when measured.
+    /// 3. Otherwise give preference to nodes which have lesser number of active nexuses, for
     /// proper distribution.
     pub(crate) async fn builder_with_defaults(
         request: impl Into<GetSuitableNodes>,
@@ -347,5 +418,6 @@ impl NexusTargetNode {
             .await
             .filter(NodeFilters::online)
             .sort(NodeSorters::number_targets)
+            .sort_ctx(NodeSorters::replica_latency)
     }
 }
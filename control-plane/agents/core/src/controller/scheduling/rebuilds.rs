@@ -0,0 +1,181 @@
+use common_lib::types::v0::transport::{Child, ChildState, Nexus, NodeId, Replica};
+use std::{cmp::Ordering, collections::HashMap};
+
+/// The in-progress rebuilds which the nodes take part in, either as the source or the destination.
+/// The io-engine doesn't report which child a rebuild reads from, so every online child of a nexus
+/// is deemed to be a source of each of its rebuilds.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct RebuildLoad {
+    sources: HashMap<NodeId, usize>,
+    destinations: HashMap<NodeId, usize>,
+}
+
+impl RebuildLoad {
+    /// Get the rebuild load of the nodes from the children of the `nexuses`, which are located
+    /// through their `replicas`.
+    pub(crate) fn new(nexuses: &[Nexus], replicas: &[Replica]) -> Self {
+        let child_node = |child: &Child| {
+            let uuid = child.uri.uuid_str();
+            replicas
+                .iter()
+                .find(|r| r.uri == child.uri.as_str() || uuid == Some(r.uuid.to_string()))
+                .map(|r| r.node.clone())
+        };
+        let mut load = Self::default();
+        for nexus in nexuses {
+            let rebuilds = nexus.children.iter().filter(|c| c.rebuilding()).count();
+            if rebuilds == 0 {
+                continue;
+            }
+            for child in &nexus.children {
+                let node = match child_node(child) {
+                    Some(node) => node,
+                    None => continue,
+                };
+                if child.rebuilding() {
+                    *load.destinations.entry(node).or_default() += 1;
+                } else if child.state == ChildState::Online {
+                    *load.sources.entry(node).or_default() += rebuilds;
+                }
+            }
+        }
+        load
+    }
+    /// Number of the rebuilds which the `node` is the destination of.
+    pub(crate) fn destinations(&self, node: &NodeId) -> usize {
+        self.destinations.get(node).copied().unwrap_or_default()
+    }
+    /// Number of the rebuilds which the `node` takes part in, either as the source or destination.
+    pub(crate) fn rebuilds(&self, node: &NodeId) -> usize {
+        self.sources.get(node).copied().unwrap_or_default() + self.destinations(node)
+    }
+    /// Order the nodes by their rebuild load, the least loaded first.
+    pub(crate) fn cmp(&self, a: &NodeId, b: &NodeId) -> Ordering {
+        self.rebuilds(a).cmp(&self.rebuilds(b))
+    }
+    /// Check if the `node` may be the destination of yet another rebuild, as per the `max`
+    /// destinations allowed per node, if any.
+    pub(crate) fn destination_allowed(&self, node: &NodeId, max: Option<u32>) -> bool {
+        match max {
+            Some(max) => self.destinations(node) < max as usize,
+            None => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::{ChildUri, ReplicaId};
+
+    fn replica(node: &NodeId) -> Replica {
+        let uuid = ReplicaId::new();
+        Replica {
+            node: node.clone(),
+            uri: format!(
+                "nvmf://{}:8420/nqn.2019-05.io.openebs:{}?uuid={}",
+                node, uuid, uuid
+            ),
+            uuid,
+            ..Default::default()
+        }
+    }
+    fn child(replica: &Replica, rebuilding: bool) -> Child {
+        Child {
+            uri: ChildUri::from(replica.uri.as_str()),
+            state: if rebuilding {
+                ChildState::Degraded
+            } else {
+                ChildState::Online
+            },
+            rebuild_progress: if rebuilding { Some(0) } else { None },
+            ..Default::default()
+        }
+    }
+
+    /// Heal a volume whose healthy replica is on the `source` node, by rebuilding a new replica
+    /// on the preferred candidate, if any is allowed.
+    fn heal(
+        candidates: &[NodeId],
+        source: &NodeId,
+        max_destinations: Option<u32>,
+        nexuses: &mut Vec<Nexus>,
+        replicas: &mut Vec<Replica>,
+    ) -> Option<NodeId> {
+        let load = RebuildLoad::new(nexuses, replicas);
+        // candidates are listed in their order of preference otherwise, eg: by free space
+        let node = candidates
+            .iter()
+            .filter(|node| load.destination_allowed(node, max_destinations))
+            .min_by(|a, b| load.cmp(a, b))?
+            .clone();
+
+        let healthy = replica(source);
+        let rebuilt = replica(&node);
+        nexuses.push(Nexus {
+            node: source.clone(),
+            children: vec![child(&healthy, false), child(&rebuilt, true)],
+            rebuilds: 1,
+            ..Default::default()
+        });
+        replicas.extend(vec![healthy, rebuilt]);
+        Some(node)
+    }
+
+    #[test]
+    fn concurrent_healings_are_spread() {
+        let candidates = vec![NodeId::from("node-1"), NodeId::from("node-2")];
+        let source = NodeId::from("node-3");
+        let (mut nexuses, mut replicas) = (vec![], vec![]);
+
+        let picked = (0 .. 3)
+            .map(|_| heal(&candidates, &source, None, &mut nexuses, &mut replicas))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            picked,
+            vec![
+                Some(candidates[0].clone()),
+                Some(candidates[1].clone()),
+                Some(candidates[0].clone())
+            ]
+        );
+
+        let load = RebuildLoad::new(&nexuses, &replicas);
+        assert_eq!(load.destinations(&candidates[0]), 2);
+        assert_eq!(load.destinations(&candidates[1]), 1);
+        assert_eq!(load.destinations(&source), 0);
+        assert_eq!(load.rebuilds(&source), 3);
+    }
+
+    #[test]
+    fn rebuild_sources_are_penalized() {
+        let candidates = vec![NodeId::from("node-1"), NodeId::from("node-2")];
+        let (mut nexuses, mut replicas) = (vec![], vec![]);
+
+        // the preferred candidate is already busy as the source of another rebuild
+        let other = NodeId::from("node-3");
+        heal(&[other.clone()], &candidates[0], None, &mut nexuses, &mut replicas);
+
+        let picked = heal(&candidates, &other, None, &mut nexuses, &mut replicas);
+        assert_eq!(picked, Some(candidates[1].clone()));
+    }
+
+    #[test]
+    fn rebuild_destinations_are_capped() {
+        let candidates = vec![NodeId::from("node-1"), NodeId::from("node-2")];
+        let source = NodeId::from("node-3");
+        let (mut nexuses, mut replicas) = (vec![], vec![]);
+
+        let picked = (0 .. 3)
+            .map(|_| heal(&candidates, &source, Some(1), &mut nexuses, &mut replicas))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            picked,
+            vec![
+                Some(candidates[0].clone()),
+                Some(candidates[1].clone()),
+                None
+            ]
+        );
+    }
+}
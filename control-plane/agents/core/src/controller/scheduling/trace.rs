@@ -0,0 +1,329 @@
+use crate::controller::scheduling::volume::PlacementPurpose;
+use common_lib::types::v0::{
+    store::volume::VolumeSpec,
+    transport::{NodeId, PoolId, SchedulingAttempt, SchedulingTrace, VolumeId},
+};
+use itertools::Itertools;
+use std::{
+    collections::{HashMap, VecDeque},
+    time::SystemTime,
+};
+
+/// Maximum number of ranked pools which are listed by a scheduling event.
+const MAX_EVENT_CANDIDATES: usize = 20;
+
+/// The traces of the scheduling of the replicas of the latest volume creations.
+/// A trace is started when the replicas of a volume are about to be scheduled, each pass of the
+/// scheduler over the pools is then added to it, and it's retained once the scheduling is over.
+/// Only the latest traces are retained, up to the capacity of the log.
+#[derive(Debug)]
+pub(crate) struct SchedulingTraceLog {
+    capacity: usize,
+    /// traces of the volumes whose replicas are being scheduled
+    pending: HashMap<VolumeId, SchedulingTrace>,
+    /// traces of the latest volume creations, the oldest first
+    traces: VecDeque<SchedulingTrace>,
+}
+
+impl SchedulingTraceLog {
+    /// Return a new `Self` retaining up to `capacity` traces, none if 0
+    pub(crate) fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            pending: HashMap::new(),
+            traces: VecDeque::with_capacity(capacity),
+        }
+    }
+    /// Start the trace of the scheduling of the replicas of the `volume`
+    pub(crate) fn start(&mut self, volume: &VolumeId) {
+        if self.capacity == 0 {
+            return;
+        }
+        let trace = SchedulingTrace {
+            volume: volume.clone(),
+            started_at: SystemTime::now(),
+            attempts: vec![],
+            error: None,
+        };
+        self.pending.insert(volume.clone(), trace);
+    }
+    /// Add a pass of the scheduler to the trace of the `volume`, if it's being traced
+    pub(crate) fn record(&mut self, volume: &VolumeId, attempt: SchedulingAttempt) {
+        if let Some(trace) = self.pending.get_mut(volume) {
+            trace.attempts.push(attempt);
+        }
+    }
+    /// Finish the trace of the `volume`, along with the `error` which the scheduling failed with,
+    /// evicting the oldest trace if the log is full
+    pub(crate) fn finish(&mut self, volume: &VolumeId, error: Option<String>) {
+        if let Some(mut trace) = self.pending.remove(volume) {
+            trace.error = error;
+            if self.traces.len() >= self.capacity {
+                self.traces.pop_front();
+            }
+            self.traces.push_back(trace);
+        }
+    }
+    /// Get the retained traces, the oldest first, only those of the `volume` if specified
+    pub(crate) fn traces(&self, volume: Option<&VolumeId>) -> Vec<SchedulingTrace> {
+        self.traces
+            .iter()
+            .filter(|trace| volume.map_or(true, |volume| &trace.volume == volume))
+            .cloned()
+            .collect()
+    }
+}
+
+/// A pool which was not rejected by the scheduler, along with what it was ranked on.
+#[derive(Debug, Clone)]
+pub(crate) struct RankedPool {
+    pub(crate) pool: PoolId,
+    pub(crate) node: NodeId,
+    /// number of replicas allocated on the pool
+    pub(crate) replicas: usize,
+    /// free space of the pool, in bytes
+    pub(crate) free_space: u64,
+    /// number of rebuilds which the node of the pool takes part in, only for replacements
+    pub(crate) rebuilds: Option<usize>,
+}
+
+impl std::fmt::Display for RankedPool {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}@{}(replicas={} free={}",
+            self.pool, self.node, self.replicas, self.free_space
+        )?;
+        if let Some(rebuilds) = self.rebuilds {
+            write!(f, " rebuilds={}", rebuilds)?;
+        }
+        write!(f, ")")
+    }
+}
+
+/// Emit the structured event which concludes a pass of the scheduler over the pools for the
+/// replicas of the volume `spec`.
+/// It lists every filter with the number of pools which it rejected, the `ranked` pools in order
+/// of preference (only the first few) and the `selected` pools, ie: the best ranked pools for the
+/// replicas which the volume is missing.
+pub(crate) fn scheduling_event(
+    spec: &VolumeSpec,
+    purpose: PlacementPurpose,
+    attempt: &SchedulingAttempt,
+    ranked: &[RankedPool],
+    selected: usize,
+) {
+    let drops = attempt
+        .filters
+        .iter()
+        .map(|filter| {
+            let dropped = attempt
+                .candidates
+                .iter()
+                .filter(|c| c.rejected_by.as_ref() == Some(filter))
+                .count();
+            format!("{}={}", filter, dropped)
+        })
+        .join(",");
+    let candidates = ranked.iter().take(MAX_EVENT_CANDIDATES).join(",");
+    let selected = ranked.iter().take(selected).map(|r| &r.pool).join(",");
+    tracing::info!(
+        volume.uuid = %spec.uuid,
+        volume.size = spec.size,
+        volume.replicas = spec.num_replicas,
+        volume.thin = spec.thin,
+        purpose = ?purpose,
+        pools = attempt.candidates.len(),
+        filters = %drops,
+        sorters = %attempt.sorters.join(","),
+        ranked = ranked.len(),
+        candidates = %candidates,
+        selected = %selected,
+        "Scheduled the pools for the replicas of the volume"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::PoolCandidate;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::{layer::SubscriberExt, Layer};
+
+    fn attempt(pool: &str) -> SchedulingAttempt {
+        SchedulingAttempt {
+            order: vec![PoolId::from(pool)],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn oldest_traces_are_evicted() {
+        let mut log = SchedulingTraceLog::new(2);
+        let volumes = (0 .. 3).map(|_| VolumeId::new()).collect::<Vec<_>>();
+        for volume in &volumes {
+            log.start(volume);
+            log.record(volume, attempt("p1"));
+            log.finish(volume, None);
+        }
+        let traced = log
+            .traces(None)
+            .into_iter()
+            .map(|trace| trace.volume)
+            .collect::<Vec<_>>();
+        assert_eq!(traced, volumes[1 ..].to_vec());
+        assert!(log.traces(Some(&volumes[0])).is_empty());
+    }
+
+    #[test]
+    fn only_started_traces_are_recorded() {
+        let mut log = SchedulingTraceLog::new(2);
+        let volume = VolumeId::new();
+
+        // eg: the replicas placed by the reconcilers are not traced
+        log.record(&volume, attempt("p1"));
+        log.finish(&volume, None);
+        assert!(log.traces(None).is_empty());
+
+        log.start(&volume);
+        log.record(&volume, attempt("p1"));
+        log.record(&volume, attempt("p2"));
+        assert!(log.traces(None).is_empty(), "Not retained until finished");
+        log.finish(&volume, Some("Not enough pools".to_string()));
+
+        let traces = log.traces(Some(&volume));
+        assert_eq!(traces.len(), 1);
+        assert_eq!(traces[0].attempts, vec![attempt("p1"), attempt("p2")]);
+        assert_eq!(traces[0].error.as_deref(), Some("Not enough pools"));
+    }
+
+    #[test]
+    fn disabled() {
+        let mut log = SchedulingTraceLog::new(0);
+        let volume = VolumeId::new();
+        log.start(&volume);
+        log.record(&volume, attempt("p1"));
+        log.finish(&volume, None);
+        assert!(log.traces(None).is_empty());
+    }
+
+    /// Captures the fields of the events, as strings
+    #[derive(Default, Clone)]
+    struct EventCapture {
+        events: Arc<Mutex<Vec<HashMap<String, String>>>>,
+    }
+    impl tracing::field::Visit for EventCapture {
+        fn record_debug(&mut self, field: &tracing::field::Field, value: &dyn std::fmt::Debug) {
+            let mut events = self.events.lock().unwrap();
+            if let Some(event) = events.last_mut() {
+                event.insert(field.name().to_string(), format!("{:?}", value));
+            }
+        }
+    }
+    impl<S: tracing::Subscriber> Layer<S> for EventCapture {
+        fn on_event(
+            &self,
+            event: &tracing::Event<'_>,
+            _ctx: tracing_subscriber::layer::Context<'_, S>,
+        ) {
+            self.events.lock().unwrap().push(HashMap::new());
+            event.record(&mut self.clone());
+        }
+    }
+
+    fn ranked_pool(pool: &str, node: &str, replicas: usize, rebuilds: Option<usize>) -> RankedPool {
+        RankedPool {
+            pool: pool.into(),
+            node: node.into(),
+            replicas,
+            free_space: 100,
+            rebuilds,
+        }
+    }
+
+    /// Capture the fields of the scheduling event for the given pass of the scheduler
+    fn capture_event(
+        purpose: PlacementPurpose,
+        attempt: &SchedulingAttempt,
+        ranked: &[RankedPool],
+        selected: usize,
+    ) -> HashMap<String, String> {
+        let capture = EventCapture::default();
+        let subscriber = tracing_subscriber::registry().with(capture.clone());
+        let spec = VolumeSpec {
+            uuid: VolumeId::new(),
+            size: 5242880,
+            num_replicas: 2,
+            ..Default::default()
+        };
+        tracing::subscriber::with_default(subscriber, || {
+            scheduling_event(&spec, purpose, attempt, ranked, selected)
+        });
+        let mut events = capture.events.lock().unwrap();
+        assert_eq!(events.len(), 1);
+        let event = events.remove(0);
+        assert_eq!(event["volume.uuid"], spec.uuid.to_string());
+        event
+    }
+
+    #[test]
+    fn scheduling_event_fields() {
+        // 3 nodes with a pool each, one of which is cordoned and another one is full
+        let candidate = |pool: &str, node: &str, rejected_by: Option<&str>| PoolCandidate {
+            pool: pool.into(),
+            node: node.into(),
+            rejected_by: rejected_by.map(ToString::to_string),
+        };
+        let attempt = SchedulingAttempt {
+            filters: vec![
+                "NodeFilters::cordoned".to_string(),
+                "NodeFilters::online_for_pool".to_string(),
+                "PoolFilters::free_space".to_string(),
+            ],
+            sorters: vec!["PoolSorters::sort_by_rebuild_load".to_string()],
+            candidates: vec![
+                candidate("p1", "n1", None),
+                candidate("p2", "n2", Some("NodeFilters::cordoned")),
+                candidate("p3", "n3", Some("PoolFilters::free_space")),
+            ],
+            order: vec!["p1".into()],
+        };
+        let event = capture_event(
+            PlacementPurpose::Creation,
+            &attempt,
+            &[ranked_pool("p1", "n1", 3, None)],
+            2,
+        );
+        assert_eq!(event["volume.size"], "5242880");
+        assert_eq!(event["volume.replicas"], "2");
+        assert_eq!(event["volume.thin"], "false");
+        assert_eq!(event["purpose"], "Creation");
+        assert_eq!(event["pools"], "3");
+        assert_eq!(
+            event["filters"],
+            "NodeFilters::cordoned=1,NodeFilters::online_for_pool=0,PoolFilters::free_space=1"
+        );
+        assert_eq!(event["sorters"], "PoolSorters::sort_by_rebuild_load");
+        assert_eq!(event["ranked"], "1");
+        assert_eq!(event["candidates"], "p1@n1(replicas=3 free=100)");
+        assert_eq!(event["selected"], "p1");
+    }
+
+    #[test]
+    fn scheduling_event_bounded() {
+        let ranked = (0 .. 30)
+            .map(|i| ranked_pool(&format!("p{}", i), &format!("n{}", i), i, Some(i % 2)))
+            .collect::<Vec<_>>();
+        let attempt = SchedulingAttempt {
+            order: ranked.iter().map(|r| r.pool.clone()).collect(),
+            ..Default::default()
+        };
+        let event = capture_event(PlacementPurpose::Replacement, &attempt, &ranked, 1);
+        assert_eq!(event["purpose"], "Replacement");
+        assert_eq!(event["ranked"], "30");
+        let candidates = event["candidates"].split(',').collect::<Vec<_>>();
+        assert_eq!(candidates.len(), MAX_EVENT_CANDIDATES);
+        assert_eq!(candidates[1], "p1@n1(replicas=1 free=100 rebuilds=1)");
+        assert_eq!(event["selected"], "p0");
+    }
+}
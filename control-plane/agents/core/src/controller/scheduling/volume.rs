@@ -1,7 +1,9 @@
 use crate::controller::{
     registry::Registry,
     scheduling::{
+        rebuilds::RebuildLoad,
         resources::{ChildItem, PoolItem, PoolItemLister, ReplicaItem},
+        trace::{scheduling_event, RankedPool},
         AddReplicaFilters, AddReplicaSorters, ChildSorters, NodeFilters, PoolFilters, PoolSorters,
         ResourceFilter,
     },
@@ -10,27 +12,52 @@ use crate::controller::{
 use common::errors::SvcError;
 use common_lib::types::v0::{
     store::{nexus::NexusSpec, nexus_persistence::NexusInfo, volume::VolumeSpec},
-    transport::{ChildUri, CreateVolume, VolumeState},
+    transport::{ChildUri, CreateVolume, PoolCandidate, SchedulingAttempt, VolumeState},
 };
 
 use itertools::Itertools;
 use std::{collections::HashMap, ops::Deref};
 
+/// Why the replicas of a volume are being placed.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum PlacementPurpose {
+    /// the volume is created or its replica count is increased
+    Creation,
+    /// the reconcilers replace the replicas which the volume is missing
+    Replacement,
+}
+
 #[derive(Clone)]
 pub(crate) struct GetSuitablePools {
     spec: VolumeSpec,
+    purpose: PlacementPurpose,
 }
 
+impl GetSuitablePools {
+    /// Request the pools to place the replacement replicas of the volume `spec`.
+    /// The pools on the nodes which take part in fewer rebuilds are preferred, so that several
+    /// volumes healing at once don't all rebuild onto the same node.
+    pub(crate) fn replacement(spec: &VolumeSpec) -> Self {
+        Self {
+            spec: spec.clone(),
+            purpose: PlacementPurpose::Replacement,
+        }
+    }
+}
 impl From<&CreateVolume> for GetSuitablePools {
     fn from(create: &CreateVolume) -> Self {
         Self {
             spec: create.into(),
+            purpose: PlacementPurpose::Creation,
         }
     }
 }
 impl From<&VolumeSpec> for GetSuitablePools {
     fn from(spec: &VolumeSpec) -> Self {
-        Self { spec: spec.clone() }
+        Self {
+            spec: spec.clone(),
+            purpose: PlacementPurpose::Creation,
+        }
     }
 }
 
@@ -38,12 +65,23 @@ impl From<&VolumeSpec> for GetSuitablePools {
 pub(crate) struct GetSuitablePoolsContext {
     registry: Registry,
     spec: VolumeSpec,
+    purpose: PlacementPurpose,
+    rebuild_load: Option<RebuildLoad>,
+    overcommit_factor: Option<f64>,
 }
 impl GetSuitablePoolsContext {
     /// Get the registry
     pub(crate) fn registry(&self) -> &Registry {
         &self.registry
     }
+    /// Get the rebuild load of the nodes, only taken into account when placing replacements
+    pub(crate) fn rebuild_load(&self) -> Option<&RebuildLoad> {
+        self.rebuild_load.as_ref()
+    }
+    /// Get the factor of the capacity of a pool which its replica specs may add up to, if any
+    pub(crate) fn overcommit_factor(&self) -> Option<f64> {
+        self.overcommit_factor
+    }
 }
 
 impl Deref for GetSuitablePoolsContext {
@@ -61,23 +99,95 @@ impl Deref for GetSuitablePools {
     }
 }
 
+/// Placement constraint of a volume's topology which may be relaxed when not all of the replicas
+/// can be placed otherwise.
+/// The constraints are relaxed in the order in which they're listed here.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum PlacementConstraint {
+    /// spread the replicas across the values of the spread label of the topology
+    Spread,
+    /// keep away from the replicas of the volumes which the topology lists as anti-colocated
+    AntiColocation,
+    /// use only the pools with the labels required by the topology
+    PoolLabels,
+    /// use only the nodes allowed by the topology
+    AllowedNodes,
+}
+
+impl PlacementConstraint {
+    /// All the constraints which may be relaxed, in the order in which they're relaxed.
+    pub(crate) const RELAXATION_ORDER: [PlacementConstraint; 4] = [
+        Self::Spread,
+        Self::AntiColocation,
+        Self::PoolLabels,
+        Self::AllowedNodes,
+    ];
+}
+
+impl std::fmt::Display for PlacementConstraint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Self::Spread => "spread",
+            Self::AntiColocation => "anti-colocation",
+            Self::PoolLabels => "pool-labels",
+            Self::AllowedNodes => "allowed-nodes",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Short name of the filter or sorter `F`, eg: `NodeFilters::cordoned`
+fn step_name<F>() -> String {
+    let name = std::any::type_name::<F>();
+    let mut path = name.rsplit("::");
+    match (path.next(), path.next()) {
+        (Some(function), Some(parent)) => format!("{}::{}", parent, function),
+        _ => name.to_string(),
+    }
+}
+
 /// Add replicas to a volume
 /// Selects the best pool candidates to create lvol replicas on
 #[derive(Clone)]
 pub(crate) struct AddVolumeReplica {
     context: GetSuitablePoolsContext,
     list: Vec<PoolItem>,
+    /// what became of each pool, added to the scheduling trace of the volume if it's traced
+    trace: SchedulingAttempt,
 }
 
 impl AddVolumeReplica {
     async fn builder(request: impl Into<GetSuitablePools>, registry: &Registry) -> Self {
         let request = request.into();
+        let rebuild_load = match request.purpose {
+            PlacementPurpose::Creation => None,
+            PlacementPurpose::Replacement => Some(RebuildLoad::new(
+                &registry.get_nexuses().await,
+                &registry.get_replicas().await,
+            )),
+        };
+        let list = PoolItemLister::list(registry).await;
+        let trace = SchedulingAttempt {
+            candidates: list
+                .iter()
+                .map(|item| PoolCandidate {
+                    pool: item.pool.id.clone(),
+                    node: item.pool.node.clone(),
+                    rejected_by: None,
+                })
+                .collect(),
+            ..Default::default()
+        };
         Self {
             context: GetSuitablePoolsContext {
                 registry: registry.clone(),
                 spec: request.spec.clone(),
+                purpose: request.purpose,
+                rebuild_load,
+                overcommit_factor: registry.pool_overcommit_factor(),
             },
-            list: PoolItemLister::list(registry).await,
+            list,
+            trace,
         }
     }
     /// Default rules for pool selection when creating replicas for a volume
@@ -96,15 +206,120 @@ impl AddVolumeReplica {
             // 4. ideally use only healthy(online) pools with degraded pools as a
             // fallback
             // 5. only one replica per node
+            // 6. pools should not hold replicas of the volumes which the topology
+            // doesn't allow it to be co-located with
+            // 7. replacements should not exceed the rebuild destinations allowed per node
+            // 8. the replicas placed on the pools should not commit more than the overcommit
+            // factor of their capacity
+            // 9. nodes should not share the value of the topology's spread label with the
+            // existing replicas
             .filter(NodeFilters::cordoned)
             .filter(NodeFilters::online_for_pool)
             .filter(NodeFilters::allowed)
             .filter(NodeFilters::unused)
+            .filter(NodeFilters::topology_spread)
             .filter(PoolFilters::usable)
             .filter(PoolFilters::free_space)
+            .filter(PoolFilters::overcommit_aware)
             .filter(PoolFilters::topology)
-            // sort pools in order of preference (from least to most number of replicas)
-            .sort(PoolSorters::sort_by_replica_count)
+            .filter(PoolFilters::anti_colocation)
+            .filter(PoolFilters::rebuild_destinations)
+            // sort pools in order of preference (from least to most number of replicas), for
+            // replacements from the least to the most rebuild-loaded nodes first
+            .sort_ctx(PoolSorters::sort_by_rebuild_load)
+    }
+    /// Default rules for pool selection when creating replicas for a volume, but disregarding the
+    /// `relaxed` placement constraints of the volume's topology
+    pub(crate) async fn builder_with_relaxed(
+        request: impl Into<GetSuitablePools>,
+        registry: &Registry,
+        relaxed: &[PlacementConstraint],
+    ) -> Self {
+        let mut builder = Self::builder(request, registry)
+            .await
+            .filter(NodeFilters::cordoned)
+            .filter(NodeFilters::online_for_pool)
+            .filter(NodeFilters::unused)
+            .filter(PoolFilters::usable)
+            .filter(PoolFilters::free_space)
+            .filter(PoolFilters::overcommit_aware)
+            .filter(PoolFilters::rebuild_destinations);
+        if !relaxed.contains(&PlacementConstraint::AllowedNodes) {
+            builder = builder.filter(NodeFilters::allowed);
+        }
+        if !relaxed.contains(&PlacementConstraint::PoolLabels) {
+            builder = builder.filter(PoolFilters::topology);
+        }
+        if !relaxed.contains(&PlacementConstraint::AntiColocation) {
+            builder = builder.filter(PoolFilters::anti_colocation);
+        }
+        if !relaxed.contains(&PlacementConstraint::Spread) {
+            builder = builder.filter(NodeFilters::topology_spread);
+        }
+        builder.sort_ctx(PoolSorters::sort_by_rebuild_load)
+    }
+    /// Default rules for pool selection when creating replicas for a volume, but disregarding the
+    /// volume's placement topology
+    pub(crate) async fn builder_without_topology(
+        request: impl Into<GetSuitablePools>,
+        registry: &Registry,
+    ) -> Self {
+        Self::builder(request, registry)
+            .await
+            .filter(NodeFilters::cordoned)
+            .filter(NodeFilters::online_for_pool)
+            .filter(NodeFilters::unused)
+            .filter(PoolFilters::usable)
+            .filter(PoolFilters::free_space)
+            .filter(PoolFilters::overcommit_aware)
+            .filter(PoolFilters::rebuild_destinations)
+            .sort_ctx(PoolSorters::sort_by_rebuild_load)
+    }
+    /// Rules for pools explicitly selected to host the volume replicas, which only take the
+    /// volume's placement topology into account
+    pub(crate) async fn builder_with_topology(
+        request: impl Into<GetSuitablePools>,
+        registry: &Registry,
+    ) -> Self {
+        Self::builder(request, registry)
+            .await
+            .filter(NodeFilters::allowed)
+            .filter(PoolFilters::topology)
+            .filter(PoolFilters::anti_colocation)
+            .filter(NodeFilters::topology_spread)
+    }
+
+    /// Emit the event which concludes this pass of the scheduler, where the pools selected for
+    /// the replicas are the best ranked ones, as many as the volume is missing
+    fn scheduling_event(&self) {
+        let ranked = self
+            .list
+            .iter()
+            .map(|item| RankedPool {
+                pool: item.pool.id.clone(),
+                node: item.pool.node.clone(),
+                replicas: item.pool.replicas().len(),
+                free_space: item.pool.free_space(),
+                rebuilds: self
+                    .context
+                    .rebuild_load()
+                    .map(|load| load.rebuilds(&item.pool.node)),
+            })
+            .collect::<Vec<_>>();
+        let existing = self
+            .context
+            .registry
+            .specs()
+            .get_volume_replicas(&self.context.spec.uuid)
+            .len();
+        let missing = (self.context.spec.desired_num_replicas() as usize).saturating_sub(existing);
+        scheduling_event(
+            &self.context.spec,
+            self.context.purpose,
+            &self.trace,
+            &ranked,
+            missing,
+        );
     }
 }
 
@@ -115,20 +330,55 @@ impl ResourceFilter for AddVolumeReplica {
 
     fn filter<P: FnMut(&Self::Request, &Self::Item) -> bool>(mut self, mut filter: P) -> Self {
         let request = self.context.clone();
-        self.list = self
-            .list
-            .into_iter()
-            .filter(|v| filter(&request, v))
-            .collect();
+        let name = step_name::<P>();
+        let (list, rejected): (Vec<_>, Vec<_>) =
+            self.list.into_iter().partition(|v| filter(&request, v));
+        for item in rejected {
+            if let Some(candidate) = self
+                .trace
+                .candidates
+                .iter_mut()
+                .find(|c| c.pool == item.pool.id)
+            {
+                candidate.rejected_by = Some(name.clone());
+            }
+        }
+        self.list = list;
+        self.trace.filters.push(name);
         self
     }
 
     fn sort<P: FnMut(&Self::Item, &Self::Item) -> std::cmp::Ordering>(mut self, sort: P) -> Self {
+        self.trace.sorters.push(step_name::<P>());
         self.list = self.list.into_iter().sorted_by(sort).collect();
         self
     }
 
-    fn collect(self) -> Vec<Self::Item> {
+    fn sort_ctx<P: FnMut(&Self::Request, &Self::Item, &Self::Item) -> std::cmp::Ordering>(
+        mut self,
+        mut sort: P,
+    ) -> Self {
+        self.trace.sorters.push(step_name::<P>());
+        let context = self.context.clone();
+        self.list = self
+            .list
+            .into_iter()
+            .sorted_by(|a, b| sort(&context, a, b))
+            .collect();
+        self
+    }
+
+    fn collect(mut self) -> Vec<Self::Item> {
+        self.trace.order = self.list.iter().map(|item| item.pool.id.clone()).collect();
+        // merely listing the pools which satisfy the topology doesn't rank them for placement
+        if !self.trace.sorters.is_empty() {
+            self.scheduling_event();
+        }
+        self.context
+            .registry
+            .scheduling_traces()
+            .lock()
+            .record(&self.context.spec.uuid, self.trace);
         self.list
     }
 
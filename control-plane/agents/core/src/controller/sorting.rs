@@ -0,0 +1,38 @@
+use common::errors::SvcError;
+use common_lib::transport_api::ResourceKind;
+use grpc::operations::{SortOrder, Sorting};
+use std::cmp::Ordering;
+
+/// A sortable field of a resource, along with the comparison of two resources by that field.
+pub(crate) type SortField<'a, T> = (&'a str, &'a dyn Fn(&T, &T) -> Ordering);
+
+/// Sort the `resources` of the given `kind` by the field requested in `sorting`, which must be
+/// one of the sortable `fields`.
+/// The resources are first sorted by the first of the `fields`, which must be unique (eg: the
+/// resource id), so the resources which share the same value of the requested field are always
+/// returned in the same order, keeping the page boundaries stable.
+pub(crate) fn sort<T>(
+    kind: ResourceKind,
+    resources: &mut [T],
+    sorting: &Sorting,
+    fields: &[SortField<T>],
+) -> Result<(), SvcError> {
+    let compare = match fields.iter().find(|(name, _)| *name == sorting.sort_by()) {
+        Some((_, compare)) => compare,
+        None => {
+            return Err(SvcError::InvalidSortField {
+                kind,
+                field: sorting.sort_by().to_string(),
+                allowed: fields.iter().map(|(name, _)| name.to_string()).collect(),
+            })
+        }
+    };
+    if let Some((_, unique)) = fields.first() {
+        resources.sort_by(|a, b| unique(a, b));
+    }
+    match sorting.order() {
+        SortOrder::Ascending => resources.sort_by(|a, b| compare(a, b)),
+        SortOrder::Descending => resources.sort_by(|a, b| compare(b, a)),
+    }
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use crate::controller::{registry::Registry, resource_map::ResourceMap};
+use crate::controller::{
+    registry::Registry, resource_map::ResourceMap, task_poller::PollTriggerEvent,
+    wrapper::InternalOps,
+};
 use common::errors::SvcError;
 use common_lib::{
     transport_api::ResourceKind,
@@ -6,17 +9,22 @@ use common_lib::{
         openapi::apis::Uuid,
         store::{
             definitions::{
-                key_prefix_obj, ObjectKey, StorableObject, StorableObjectType, Store, StoreError,
+                key_prefix_obj, ObjectKey, StorableObject, StorableObjectType, Store, StoreBatch,
+                StoreError,
             },
             nexus::NexusSpec,
             node::NodeSpec,
             pool::PoolSpec,
             replica::ReplicaSpec,
+            tombstone::{Tombstone, TombstoneKey},
             volume::VolumeSpec,
-            AsOperationSequencer, OperationGuardArc, OperationMode, OperationSequence, SpecStatus,
-            SpecTransaction,
+            AsOperationSequencer, OperationGuardArc, OperationMode, OperationSequence,
+            OperationSequencer, SpecStatus, SpecTimestamps, SpecTransaction,
+        },
+        transport::{
+            NexusId, NodeId, PatchSpec, PendingOperation, PendingOperations, PoolId, ReplicaId,
+            SpecPatch, VolumeId,
         },
-        transport::{NexusId, NodeId, PoolId, ReplicaId, VolumeId},
     },
 };
 
@@ -25,7 +33,15 @@ use common_lib::types::v0::store::{ResourceMutex, ResourceUuid, UpdateInnerValue
 use parking_lot::RwLock;
 use serde::de::DeserializeOwned;
 use snafu::{ResultExt, Snafu};
-use std::{fmt::Debug, ops::Deref, sync::Arc};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    convert::TryFrom,
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    ops::Deref,
+    sync::Arc,
+    time::SystemTime,
+};
 
 #[derive(Debug, Snafu)]
 #[snafu(context(suffix(false)))]
@@ -107,14 +123,46 @@ pub(crate) trait GuardedOperationsHelper:
         result: Result<R, SvcError>,
         registry: &Registry,
     ) -> Result<R, SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+    {
+        self.complete_create_with(result, registry, StoreBatch::default())
+            .await
+    }
+
+    /// Completes a create operation, as `complete_create`, storing the spec along with the
+    /// specs of the `batch`, eg: those of the resources which were created for this one.
+    /// The specs of the batch are either all stored along with this spec or none is.
+    async fn complete_create_with<O, R: Send>(
+        &self,
+        result: Result<R, SvcError>,
+        registry: &Registry,
+        mut batch: StoreBatch,
+    ) -> Result<R, SvcError>
     where
         Self::Inner: SpecTransaction<O>,
     {
         match result {
             Ok(val) => {
-                let mut spec_clone = self.lock().clone();
+                let now = SystemTime::now();
+                let mut spec_clone = {
+                    let mut spec = self.lock();
+                    spec.stamp_created(now);
+                    spec.clone()
+                };
+                let operation = persisted_operation(&spec_clone);
                 spec_clone.commit_op();
-                let stored = registry.store_obj(&spec_clone).await;
+                let stored = if batch.is_empty() {
+                    registry.store_obj(&spec_clone).await
+                } else {
+                    match batch.put_obj(&spec_clone) {
+                        Ok(()) => registry.store_batch(&batch).await,
+                        Err(error) => Err(error.into()),
+                    }
+                };
+                if stored.is_ok() {
+                    spec_clone.record_revision(registry, now, operation).await;
+                }
                 let mut spec = self.lock();
                 match stored {
                     Ok(_) => {
@@ -135,6 +183,39 @@ pub(crate) trait GuardedOperationsHelper:
         }
     }
 
+    /// Stage the completion of a create operation into the `batch`, so the spec may be stored
+    /// along with the specs of other resources, see `complete_create_with`.
+    /// Once the batch is stored, or fails to be, the operation must be completed with
+    /// `complete_staged_create`.
+    fn stage_create<O>(&self, batch: &mut StoreBatch) -> Result<(), SvcError>
+    where
+        Self::Inner: SpecTransaction<O>,
+        Self::Inner: StorableObject,
+    {
+        let mut spec_clone = {
+            let mut spec = self.lock();
+            spec.stamp_created(SystemTime::now());
+            spec.clone()
+        };
+        spec_clone.commit_op();
+        batch.put_obj(&spec_clone).map_err(Into::into)
+    }
+
+    /// Completes a create operation staged into a batch, depending on whether the batch was
+    /// `stored`. Otherwise the spec is marked accordingly and the dirty spec reconciler will
+    /// attempt to update the store when the store is back online.
+    fn complete_staged_create<O>(&self, stored: bool)
+    where
+        Self::Inner: SpecTransaction<O>,
+    {
+        let mut spec = self.lock();
+        if stored {
+            spec.commit_op();
+        } else {
+            spec.set_op_result(true);
+        }
+    }
+
     /// Validates the outcome of a create step.
     /// In case of an error, an attempt is made to delete the spec in the persistent store and
     /// registry.
@@ -215,6 +296,8 @@ pub(crate) trait GuardedOperationsHelper:
         Self::Inner: SpecTransaction<O>,
         Self::Inner: StorableObject,
     {
+        // the spec is disowned before the operation is logged, so fail before it's touched
+        registry.store_available()?;
         {
             let mut spec = self.lock();
             let _ = spec.busy()?;
@@ -348,10 +431,17 @@ pub(crate) trait GuardedOperationsHelper:
     {
         match result {
             Ok(val) => {
+                // the in-memory spec is stamped too, so that should the store fail the dirty spec
+                // reconciler keeps the time of the update rather than that of its replay
+                let now = SystemTime::now();
+                self.lock().stamp_updated(now);
+                spec_clone.stamp_updated(now);
+                let operation = persisted_operation(&spec_clone);
                 spec_clone.commit_op();
                 let stored = registry.store_obj(&spec_clone).await;
                 match stored {
                     Ok(_) => {
+                        spec_clone.record_revision(registry, now, operation).await;
                         self.complete_op();
                         Ok(val)
                     }
@@ -512,7 +602,13 @@ pub(crate) trait GuardedOperationsHelper:
 
 #[async_trait::async_trait]
 pub(crate) trait SpecOperationsHelper:
-    Clone + Debug + StorableObject + AsOperationSequencer + ResourceUuid + PartialEq<Self::Create>
+    Clone
+    + Debug
+    + StorableObject
+    + AsOperationSequencer
+    + ResourceUuid
+    + SpecTimestamps
+    + PartialEq<Self::Create>
 {
     type Create: Debug + PartialEq + Sync + Send;
     type Status: PartialEq + Sync + Send;
@@ -650,6 +746,16 @@ pub(crate) trait SpecOperationsHelper:
     ) -> Result<(), SvcError> {
         unimplemented!();
     }
+
+    /// Record the spec, as persisted at `updated_at` by the `operation`, as a new revision of its
+    /// history (not all resources keep one currently)
+    async fn record_revision(
+        &self,
+        _registry: &Registry,
+        _updated_at: SystemTime,
+        _operation: Option<serde_json::Value>,
+    ) {
+    }
 }
 
 /// Operations are locked
@@ -726,6 +832,10 @@ pub(crate) struct ResourceSpecs {
     pub(crate) nexuses: ResourceMap<NexusId, NexusSpec>,
     pub(crate) pools: ResourceMap<PoolId, PoolSpec>,
     pub(crate) replicas: ResourceMap<ReplicaId, ReplicaSpec>,
+    /// Tombstones of the recently destroyed resources.
+    pub(crate) tombstones: HashMap<TombstoneKey, Tombstone>,
+    /// Generation of the last created replica.
+    pub(crate) replica_generation: u64,
 }
 
 impl ResourceSpecsLocked {
@@ -741,6 +851,7 @@ impl ResourceSpecsLocked {
             StorableObjectType::NexusSpec,
             StorableObjectType::PoolSpec,
             StorableObjectType::ReplicaSpec,
+            StorableObjectType::Tombstone,
         ];
         for spec in &spec_types {
             if let Err(e) = self.populate_specs(store, *spec).await {
@@ -753,11 +864,39 @@ impl ResourceSpecsLocked {
         for replica in self.get_replicas() {
             let replica_uuid = replica.lock().uuid.clone();
 
-            nexuses
-                .iter()
-                .filter(|n| n.lock().contains_replica(&replica_uuid))
-                .for_each(|n| replica.lock().owners.add_owner(&n.lock().uuid));
+            for nexus in &nexuses {
+                let nexus = nexus.lock();
+                let child = nexus
+                    .children
+                    .iter()
+                    .filter_map(|c| c.as_replica())
+                    .find(|c| c.uuid() == &replica_uuid);
+                if let Some(child) = child {
+                    let mut replica = replica.lock();
+                    if replica.is_generation(child.generation()) {
+                        replica.owners.add_owner(&nexus.uuid);
+                    } else {
+                        tracing::warn!(
+                            replica.uuid = %replica_uuid,
+                            replica.generation = replica.generation,
+                            nexus.uuid = %nexus.uuid,
+                            child.generation = child.generation(),
+                            "Nexus child refers to another replica with the same uuid"
+                        );
+                    }
+                }
+            }
         }
+
+        // carry on from the newest generation, including that of the stale references
+        let generation = nexuses
+            .iter()
+            .flat_map(|n| n.lock().children.clone())
+            .filter_map(|c| c.as_replica().map(|r| r.generation()))
+            .chain(self.get_replicas().iter().map(|r| r.lock().generation))
+            .max()
+            .unwrap_or_default();
+        self.write().replica_generation = generation;
     }
 
     /// Deserialise a vector of serde_json values into specific spec types.
@@ -838,6 +977,16 @@ impl ResourceSpecsLocked {
                     })?;
                 resource_specs.replicas.populate(specs);
             }
+            StorableObjectType::Tombstone => {
+                let tombstones =
+                    Self::deserialise_specs::<Tombstone>(store_values).context(Deserialise {
+                        obj_type: StorableObjectType::Tombstone,
+                    })?;
+                resource_specs.tombstones = tombstones
+                    .into_iter()
+                    .map(|tombstone| (tombstone.key(), tombstone))
+                    .collect();
+            }
             _ => {
                 // Not all spec types are persisted in the store.
                 unimplemented!("{} not persisted in store", spec_type);
@@ -846,3 +995,576 @@ impl ResourceSpecsLocked {
         Ok(())
     }
 }
+
+impl ResourceSpecsLocked {
+    /// Leave a tombstone for the destroyed resource `id` of the given `kind`, unless the
+    /// tombstones are disabled with a zero ttl.
+    /// Failing to persist it is not fatal as the registry copy still guards this instance.
+    pub(crate) async fn add_tombstone(&self, registry: &Registry, kind: ResourceKind, id: &str) {
+        if registry.tombstone_ttl().is_zero() {
+            return;
+        }
+        let tombstone = Tombstone::new(kind, id);
+        self.write()
+            .tombstones
+            .insert(tombstone.key(), tombstone.clone());
+        if let Err(error) = registry.store_obj(&tombstone).await {
+            tracing::error!(
+                "Failed to persist the tombstone of {:?} '{}'. Error {:?}",
+                tombstone.kind,
+                tombstone.id,
+                error
+            );
+        }
+    }
+
+    /// Check if the resource `id` of the given `kind` has a tombstone which has not yet expired.
+    pub(crate) fn tombstoned(&self, registry: &Registry, kind: &ResourceKind, id: &str) -> bool {
+        self.read()
+            .tombstones
+            .get(&TombstoneKey::new(kind, id))
+            .map(|tombstone| !tombstone.expired(registry.tombstone_ttl()))
+            .unwrap_or(false)
+    }
+
+    /// Fail with `Tombstoned` if the resource `id` of the given `kind` cannot be created yet.
+    pub(crate) fn check_tombstone(
+        &self,
+        registry: &Registry,
+        kind: ResourceKind,
+        id: &str,
+    ) -> Result<(), SvcError> {
+        if self.tombstoned(registry, &kind, id) {
+            return Err(SvcError::Tombstoned {
+                kind,
+                id: id.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Clear the tombstone of the resource `id` of the given `kind`, regardless of its expiry.
+    pub(crate) async fn clear_tombstone(
+        &self,
+        registry: &Registry,
+        kind: ResourceKind,
+        id: &str,
+    ) -> Result<(), SvcError> {
+        let key = TombstoneKey::new(&kind, id);
+        if !self.read().tombstones.contains_key(&key) {
+            return Err(SvcError::TombstoneNotFound {
+                kind,
+                id: id.to_string(),
+            });
+        }
+        registry.delete_kv(&key.key()).await?;
+        self.write().tombstones.remove(&key);
+        Ok(())
+    }
+
+    /// Worker that removes the expired tombstones from the persistent store and the registry.
+    /// Returns true if any expired tombstone could not be removed.
+    pub(crate) async fn reconcile_tombstones(&self, registry: &Registry) -> bool {
+        let ttl = registry.tombstone_ttl();
+        let expired = self
+            .read()
+            .tombstones
+            .iter()
+            .filter(|(_, tombstone)| tombstone.expired(ttl))
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+
+        let mut pending = false;
+        for key in expired {
+            match registry.delete_kv(&key.key()).await {
+                Ok(_) => {
+                    self.write().tombstones.remove(&key);
+                }
+                Err(_) => pending = true,
+            }
+        }
+        pending
+    }
+}
+
+impl ResourceSpecsLocked {
+    /// Apply the JSON merge patch of the `request` to the persisted spec of the resource, or
+    /// only return the patched spec along with its confirmation token if it's a dry run.
+    pub(crate) async fn patch_spec(
+        &self,
+        registry: &Registry,
+        request: &PatchSpec,
+    ) -> Result<SpecPatch, SvcError> {
+        if !registry.allow_spec_patch() {
+            return Err(SvcError::SpecPatchDisabled {});
+        }
+        let not_found = || SvcError::NotFound {
+            kind: request.kind.clone(),
+            id: request.id.clone(),
+        };
+        match request.kind {
+            ResourceKind::Volume => {
+                let id = VolumeId::try_from(request.id.as_str()).map_err(|_| not_found())?;
+                let volume = self.get_locked_volume(&id).ok_or_else(not_found)?;
+                patch_resource_spec(registry, &volume, request, volume_patch_rules).await
+            }
+            ResourceKind::Nexus => {
+                let id = NexusId::try_from(request.id.as_str()).map_err(|_| not_found())?;
+                let nexus = self.get_nexus(&id).ok_or_else(not_found)?;
+                patch_resource_spec(registry, &nexus, request, nexus_patch_rules).await
+            }
+            ResourceKind::Pool => {
+                let pool = self
+                    .get_locked_pool(&PoolId::from(request.id.as_str()))
+                    .ok_or_else(not_found)?;
+                patch_resource_spec(registry, &pool, request, pool_patch_rules).await
+            }
+            ResourceKind::Replica => {
+                let id = ReplicaId::try_from(request.id.as_str()).map_err(|_| not_found())?;
+                let replica = self.get_replica(&id).ok_or_else(not_found)?;
+                patch_resource_spec(registry, &replica, request, replica_patch_rules).await
+            }
+            _ => Err(SvcError::InvalidSpecPatch {
+                kind: request.kind.clone(),
+                id: request.id.clone(),
+                reason: "only the volume, nexus, pool and replica specs can be patched".to_string(),
+            }),
+        }
+    }
+}
+
+/// Patch the spec of the `resource` whilst holding its operation guard, so that no other
+/// operation may modify it meanwhile.
+/// The patched spec must still deserialize into a valid spec of the same resource which abides by
+/// the `rules` of its kind, and it's only persisted if the `request` carries the token of a dry
+/// run of the same patch against the same spec.
+async fn patch_resource_spec<T: SpecOperationsHelper>(
+    registry: &Registry,
+    resource: &ResourceMutex<T>,
+    request: &PatchSpec,
+    rules: fn(&T, &T) -> Result<(), String>,
+) -> Result<SpecPatch, SvcError> {
+    let mut guard = resource.operation_guard_wait().await?;
+    let spec = guard.lock().clone();
+    let invalid = |reason: String| SvcError::InvalidSpecPatch {
+        kind: request.kind.clone(),
+        id: request.id.clone(),
+        reason,
+    };
+
+    let before = serde_json::to_value(&spec).map_err(|error| invalid(error.to_string()))?;
+    let mut merged = before.clone();
+    json_patch::merge(&mut merged, &request.patch);
+    let mut patched: T =
+        serde_json::from_value(merged).map_err(|error| invalid(error.to_string()))?;
+    if patched.uuid_str() != spec.uuid_str() {
+        return Err(invalid(
+            "the id of the resource cannot be changed".to_string(),
+        ));
+    }
+    rules(&spec, &patched).map_err(invalid)?;
+    // report the spec as it would be persisted, eg: without any unknown fields
+    let after = serde_json::to_value(&patched).map_err(|error| invalid(error.to_string()))?;
+
+    let confirm = spec_patch_token(request, &before);
+    if request.dry_run {
+        return Ok(SpecPatch {
+            before,
+            after,
+            confirm,
+            applied: false,
+        });
+    }
+    if request.confirm.as_ref() != Some(&confirm) {
+        return Err(SvcError::SpecPatchNotConfirmed {
+            kind: request.kind.clone(),
+            id: request.id.clone(),
+        });
+    }
+
+    // the operation sequence is not persisted and is still held by our guard
+    *patched.as_mut() = spec.as_ref().clone();
+    registry.store_obj(&patched).await?;
+    tracing::warn!(
+        audit = true,
+        kind = ?request.kind,
+        id = %request.id,
+        patch = %request.patch,
+        %before,
+        %after,
+        "Patched the persisted spec"
+    );
+    *guard.lock() = patched;
+    guard.update();
+
+    Ok(SpecPatch {
+        before,
+        after,
+        confirm,
+        applied: true,
+    })
+}
+
+/// The volume must keep at least one replica, and its size as its replicas are not resized.
+fn volume_patch_rules(spec: &VolumeSpec, patched: &VolumeSpec) -> Result<(), String> {
+    if patched.num_replicas == 0 {
+        return Err("the volume must have at least one replica".to_string());
+    }
+    if patched.size != spec.size {
+        return Err("the size of the volume cannot be changed".to_string());
+    }
+    Ok(())
+}
+
+/// The nexus lives on its node, with the size it was created with.
+fn nexus_patch_rules(spec: &NexusSpec, patched: &NexusSpec) -> Result<(), String> {
+    if patched.node != spec.node {
+        return Err("the node of the nexus cannot be changed".to_string());
+    }
+    if patched.size != spec.size {
+        return Err("the size of the nexus cannot be changed".to_string());
+    }
+    Ok(())
+}
+
+/// The pool lives on its disks, on its node.
+fn pool_patch_rules(spec: &PoolSpec, patched: &PoolSpec) -> Result<(), String> {
+    if patched.node != spec.node {
+        return Err("the node of the pool cannot be changed".to_string());
+    }
+    if patched.disks != spec.disks {
+        return Err("the disks of the pool cannot be changed".to_string());
+    }
+    Ok(())
+}
+
+/// The replica lives on its pool, under its name and with the size it was created with.
+fn replica_patch_rules(spec: &ReplicaSpec, patched: &ReplicaSpec) -> Result<(), String> {
+    if patched.pool != spec.pool {
+        return Err("the pool of the replica cannot be changed".to_string());
+    }
+    if patched.name != spec.name {
+        return Err("the name of the replica cannot be changed".to_string());
+    }
+    if patched.size != spec.size {
+        return Err("the size of the replica cannot be changed".to_string());
+    }
+    Ok(())
+}
+
+/// Token which ties a spec patch to the spec it was tried against, so that a patch may only be
+/// applied as it was shown by its dry run.
+fn spec_patch_token(request: &PatchSpec, before: &serde_json::Value) -> String {
+    let mut hasher = DefaultHasher::new();
+    request.kind.to_string().hash(&mut hasher);
+    request.id.hash(&mut hasher);
+    before.to_string().hash(&mut hasher);
+    request.patch.to_string().hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// What to do with the operation pending on the spec of a resource.
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum PendingOperationAction {
+    /// only report it
+    Inspect,
+    /// clear it, even if it may have taken effect when `force`d
+    Clear { force: bool },
+}
+
+impl ResourceSpecsLocked {
+    /// Get the operation pending on the spec of the resource `id` of the given `kind`, or clear
+    /// it, as per the `action`.
+    pub(crate) async fn pending_operation(
+        &self,
+        registry: &Registry,
+        kind: &ResourceKind,
+        id: &str,
+        action: PendingOperationAction,
+    ) -> Result<PendingOperation, SvcError> {
+        let not_found = || SvcError::NotFound {
+            kind: kind.clone(),
+            id: id.to_string(),
+        };
+        match kind {
+            ResourceKind::Volume => {
+                let id = VolumeId::try_from(id).map_err(|_| not_found())?;
+                let volume = self.get_locked_volume(&id).ok_or_else(not_found)?;
+                resource_pending_operation(registry, &volume, action).await
+            }
+            ResourceKind::Nexus => {
+                let id = NexusId::try_from(id).map_err(|_| not_found())?;
+                let nexus = self.get_nexus(&id).ok_or_else(not_found)?;
+                resource_pending_operation(registry, &nexus, action).await
+            }
+            ResourceKind::Pool => {
+                let pool = self
+                    .get_locked_pool(&PoolId::from(id))
+                    .ok_or_else(not_found)?;
+                resource_pending_operation(registry, &pool, action).await
+            }
+            ResourceKind::Replica => {
+                let id = ReplicaId::try_from(id).map_err(|_| not_found())?;
+                let replica = self.get_replica(&id).ok_or_else(not_found)?;
+                resource_pending_operation(registry, &replica, action).await
+            }
+            _ => Err(SvcError::InvalidArguments {}),
+        }
+    }
+}
+
+/// Get the operation pending on the spec of the `resource`, or clear it, as per the `action`.
+/// The operation is only cleared whilst holding the resource's operation guard, so that an
+/// operation which is still in progress is left alone.
+async fn resource_pending_operation<T, O>(
+    registry: &Registry,
+    resource: &ResourceMutex<T>,
+    action: PendingOperationAction,
+) -> Result<PendingOperation, SvcError>
+where
+    T: SpecOperationsHelper + SpecTransaction<O>,
+{
+    let force = match action {
+        PendingOperationAction::Inspect => {
+            let spec = resource.lock().clone();
+            let busy = spec.operation_lock().busy();
+            return Ok(spec_pending_operation(registry, &spec, busy));
+        }
+        PendingOperationAction::Clear { force } => force,
+    };
+
+    let mut guard = resource.operation_guard_wait().await?;
+    let spec = guard.lock().clone();
+    let pending = spec_pending_operation(registry, &spec, false);
+    match spec.operation_result() {
+        None => return Ok(pending),
+        // a failed operation has had no effect, though its undoing may be what's stuck
+        Some(Some(false)) => {}
+        Some(_) if force => {}
+        Some(_) => {
+            return Err(SvcError::PendingOperationNotForced {
+                kind: spec.kind(),
+                id: spec.uuid_str(),
+            })
+        }
+    }
+
+    let mut cleared = spec.clone();
+    cleared.clear_op();
+    registry.store_obj(&cleared).await?;
+    tracing::warn!(
+        kind = ?spec.kind(),
+        id = %spec.uuid_str(),
+        operation = %pending.operation.clone().unwrap_or_default(),
+        result = ?pending.result,
+        force,
+        "Cleared the pending operation of the spec"
+    );
+    guard.lock().clear_op();
+    guard.update();
+
+    Ok(pending)
+}
+
+impl ResourceSpecsLocked {
+    /// Forcibly release the operation guard of the resource `id` of the given `kind`, which was
+    /// left held by an operation abandoned without completing, returning what was pending on it.
+    /// The operation pending on the spec, if any, is marked with an unknown result and left for
+    /// the dirty spec reconciler to check against the state of the resource, as it may or may not
+    /// have taken effect.
+    pub(crate) async fn release_operation_guard(
+        &self,
+        registry: &Registry,
+        kind: &ResourceKind,
+        id: &str,
+    ) -> Result<PendingOperation, SvcError> {
+        if !registry.allow_guard_release() {
+            return Err(SvcError::GuardReleaseDisabled {});
+        }
+        let not_found = || SvcError::NotFound {
+            kind: kind.clone(),
+            id: id.to_string(),
+        };
+        let pool_node = |pool: &PoolId| self.get_locked_pool(pool).map(|p| p.lock().node.clone());
+        match kind {
+            ResourceKind::Volume => {
+                let id = VolumeId::try_from(id).map_err(|_| not_found())?;
+                let volume = self.get_locked_volume(&id).ok_or_else(not_found)?;
+                let replicas = self.get_volume_replicas(&id);
+                let nexuses = self.get_volume_nexuses(&id);
+                let nodes = replicas
+                    .iter()
+                    .filter_map(|replica| pool_node(&replica.lock().pool))
+                    .chain(nexuses.iter().map(|nexus| nexus.lock().node.clone()))
+                    .collect::<Vec<_>>();
+                release_resource_guard(registry, &volume, &nodes).await
+            }
+            ResourceKind::Nexus => {
+                let id = NexusId::try_from(id).map_err(|_| not_found())?;
+                let nexus = self.get_nexus(&id).ok_or_else(not_found)?;
+                let node = nexus.lock().node.clone();
+                release_resource_guard(registry, &nexus, &[node]).await
+            }
+            ResourceKind::Pool => {
+                let pool = self
+                    .get_locked_pool(&PoolId::from(id))
+                    .ok_or_else(not_found)?;
+                let node = pool.lock().node.clone();
+                release_resource_guard(registry, &pool, &[node]).await
+            }
+            ResourceKind::Replica => {
+                let id = ReplicaId::try_from(id).map_err(|_| not_found())?;
+                let replica = self.get_replica(&id).ok_or_else(not_found)?;
+                let node = pool_node(&replica.lock().pool);
+                release_resource_guard(registry, &replica, node.as_slice()).await
+            }
+            _ => Err(SvcError::InvalidArguments {}),
+        }
+    }
+}
+
+/// Forcibly release the operation guard of the `resource`, as long as no mutating call is in
+/// flight to any of its `nodes`, returning what was pending on it.
+/// The calls to a node are serialized with those of its polls, so this is a best-effort check
+/// which also refuses to release the guard whilst the node is being polled, or called for other
+/// resources, in which case the release may simply be retried.
+/// The calls to the `nodes` are held off until the guard is released, and the result of the
+/// pending operation, if any, is marked as unknown for the dirty spec reconciler to check it.
+async fn release_resource_guard<T, O>(
+    registry: &Registry,
+    resource: &ResourceMutex<T>,
+    nodes: &[NodeId],
+) -> Result<PendingOperation, SvcError>
+where
+    T: SpecOperationsHelper + SpecTransaction<O>,
+{
+    let spec = resource.lock().clone();
+    if !spec.operation_lock().busy() {
+        return Ok(spec_pending_operation(registry, &spec, false));
+    }
+    let mut node_locks = Vec::with_capacity(nodes.len());
+    for node in nodes {
+        let wrapper = match registry.get_node_wrapper(node).await {
+            Ok(wrapper) => wrapper,
+            Err(_) => continue,
+        };
+        match wrapper.grpc_lock().await.try_lock_owned() {
+            Ok(lock) => node_locks.push(lock),
+            Err(_) => {
+                return Err(SvcError::DataplaneCallInFlight {
+                    kind: spec.kind(),
+                    id: spec.uuid_str(),
+                    node: node.clone(),
+                })
+            }
+        }
+    }
+
+    let pending = spec_pending_operation(registry, &spec, true);
+    if let Some(Some(_)) = spec.operation_result() {
+        let mut unknown = spec.clone();
+        unknown.set_op_result_unknown();
+        registry.store_obj(&unknown).await?;
+        resource.lock().set_op_result_unknown();
+    }
+    let sequence = resource.release();
+    drop(node_locks);
+    tracing::warn!(
+        audit = true,
+        kind = ?spec.kind(),
+        id = %spec.uuid_str(),
+        sequence = ?sequence,
+        operation = %pending.operation.clone().unwrap_or_default(),
+        result = ?pending.result,
+        "Forcibly released the operation guard of the resource"
+    );
+    Ok(pending)
+}
+
+/// The operation pending on the `spec`, as it's persisted, along with the failed attempts at
+/// replaying it.
+fn spec_pending_operation<T: SpecOperationsHelper>(
+    registry: &Registry,
+    spec: &T,
+    busy: bool,
+) -> PendingOperation {
+    let status = match spec.status() {
+        SpecStatus::Creating => SpecStatus::Creating,
+        SpecStatus::Created(_) => SpecStatus::Created(()),
+        SpecStatus::Deleting => SpecStatus::Deleting,
+        SpecStatus::Deleted => SpecStatus::Deleted,
+    };
+    let operation = persisted_operation(spec);
+    PendingOperation {
+        kind: spec.kind(),
+        id: spec.uuid_str(),
+        status,
+        operation,
+        result: spec.operation_result().flatten(),
+        busy,
+        replay: registry
+            .spec_replays()
+            .lock()
+            .replay(&spec.kind(), &spec.uuid_str()),
+    }
+}
+
+impl ResourceSpecsLocked {
+    /// Get the operations pending on the specs of all the resources, including those whose
+    /// replay failed even if they're no longer pending.
+    pub(crate) fn pending_operations(&self, registry: &Registry) -> PendingOperations {
+        let mut operations = vec![];
+        pending_operations(registry, self.get_locked_volumes(), &mut operations);
+        pending_operations(registry, self.get_nexuses(), &mut operations);
+        pending_operations(registry, self.get_locked_pools(), &mut operations);
+        pending_operations(registry, self.get_replicas(), &mut operations);
+        PendingOperations { operations }
+    }
+
+    /// Retry replaying the operation pending on the spec of the resource `id` of the given
+    /// `kind`, which was given up on as stuck, returning what was pending on it.
+    /// The failed attempts are forgotten, so the replay is attempted on the next reconcile.
+    pub(crate) async fn retry_operation_replay(
+        &self,
+        registry: &Registry,
+        kind: &ResourceKind,
+        id: &str,
+    ) -> Result<PendingOperation, SvcError> {
+        let pending = self
+            .pending_operation(registry, kind, id, PendingOperationAction::Inspect)
+            .await?;
+        let replay = registry.spec_replays().lock().retry(kind, id);
+        tracing::info!(
+            audit = true,
+            ?kind,
+            %id,
+            ?replay,
+            "Retrying the replay of the pending operation of the spec"
+        );
+        registry.notify(PollTriggerEvent::ReplayRetried).await;
+        Ok(pending)
+    }
+}
+
+/// Add the operations pending on the specs of the `resources` to the `operations`, along with
+/// those whose replay failed.
+fn pending_operations<T: SpecOperationsHelper>(
+    registry: &Registry,
+    resources: Vec<ResourceMutex<T>>,
+    operations: &mut Vec<PendingOperation>,
+) {
+    for resource in resources {
+        let spec = resource.lock().clone();
+        let pending = spec_pending_operation(registry, &spec, spec.operation_lock().busy());
+        if spec.operation_result().is_some() || pending.replay.is_some() {
+            operations.push(pending);
+        }
+    }
+}
+
+/// The operation pending on the `spec`, as it's persisted, if any.
+fn persisted_operation<T: StorableObject>(spec: &T) -> Option<serde_json::Value> {
+    serde_json::to_value(spec)
+        .ok()
+        .and_then(|spec| spec.get("operation")?.get("operation").cloned())
+}
@@ -47,12 +47,46 @@ impl ResourceStates {
         self.update_nexuses(nexuses);
     }
 
-    /// Update nexus states.
+    /// Update nexus states, carrying over the latest faults of their children.
     pub(crate) fn update_nexuses(&mut self, nexuses: Vec<Nexus>) {
+        let nexuses = nexuses
+            .into_iter()
+            .map(|nexus| self.with_child_faults(nexus))
+            .collect::<Vec<_>>();
         self.nexuses.clear();
         self.nexuses.populate(nexuses);
     }
 
+    /// Carry the latest faults of the `nexus` children over from their current state, recording
+    /// the faults which are new.
+    fn with_child_faults(&self, mut nexus: Nexus) -> Nexus {
+        let previous = self
+            .nexuses
+            .get(&nexus.uuid)
+            .map(|state| state.lock().nexus.children.clone())
+            .unwrap_or_default();
+        nexus.children = nexus
+            .children
+            .into_iter()
+            .map(|child| {
+                let previous = previous.iter().find(|c| c.uri == child.uri);
+                let child = child.with_fault_history(previous);
+                if child.last_fault != previous.and_then(|p| p.last_fault.clone()) {
+                    if let Some(fault) = &child.last_fault {
+                        tracing::warn!(
+                            nexus.uuid = %nexus.uuid,
+                            child.uri = %child.uri,
+                            child.fault = ?fault.reason,
+                            "Nexus child faulted"
+                        );
+                    }
+                }
+                child
+            })
+            .collect();
+        nexus
+    }
+
     /// Returns a vector of cloned nexus states.
     pub(crate) fn get_cloned_nexus_states(&self) -> Vec<NexusState> {
         Self::cloned_inner_states(self.nexuses.values())
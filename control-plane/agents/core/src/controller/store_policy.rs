@@ -0,0 +1,117 @@
+use std::{
+    str::FromStr,
+    time::{Duration, Instant},
+};
+
+/// How the agent operates whilst the persistent store is unavailable.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum StoreUnavailablePolicy {
+    /// Keep serving every request, the operations which write to the store failing once their
+    /// store timeout expires.
+    Wait,
+    /// Degrade to read-only operation: the requests which get resources are served from the
+    /// registry whilst the mutations fail fast, and the reconciler is suspended.
+    ReadOnly,
+}
+
+impl Default for StoreUnavailablePolicy {
+    fn default() -> Self {
+        Self::Wait
+    }
+}
+
+impl FromStr for StoreUnavailablePolicy {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "wait" => Ok(Self::Wait),
+            "read-only" => Ok(Self::ReadOnly),
+            _ => Err(format!(
+                "Invalid store unavailable policy '{}', expected one of: wait, read-only",
+                source
+            )),
+        }
+    }
+}
+
+/// Change of the availability of the persistent store, as seen by its health checks.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum StoreTransition {
+    /// The store can no longer be reached.
+    Lost,
+    /// The store can be reached again, after being unavailable for the given duration.
+    Recovered(Duration),
+}
+
+/// Availability of the persistent store, as per its latest health check.
+#[derive(Debug, Default)]
+pub(crate) struct StoreAvailability {
+    /// since when the store has been unavailable, if it is
+    unavailable_since: Option<Instant>,
+}
+
+impl StoreAvailability {
+    /// Check whether the store is currently unavailable.
+    pub(crate) fn unavailable(&self) -> bool {
+        self.unavailable_since.is_some()
+    }
+    /// Record the result of a health check of the store, returning the transition if its
+    /// availability changed.
+    pub(crate) fn update(&mut self, available: bool, now: Instant) -> Option<StoreTransition> {
+        match (available, self.unavailable_since) {
+            (false, None) => {
+                self.unavailable_since = Some(now);
+                Some(StoreTransition::Lost)
+            }
+            (true, Some(since)) => {
+                self.unavailable_since = None;
+                Some(StoreTransition::Recovered(
+                    now.saturating_duration_since(since),
+                ))
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn policy_from_str() {
+        assert_eq!(
+            StoreUnavailablePolicy::from_str("wait"),
+            Ok(StoreUnavailablePolicy::Wait)
+        );
+        assert_eq!(
+            StoreUnavailablePolicy::from_str("read-only"),
+            Ok(StoreUnavailablePolicy::ReadOnly)
+        );
+        assert!(StoreUnavailablePolicy::from_str("readonly").is_err());
+    }
+
+    #[test]
+    fn transitions() {
+        let start = Instant::now();
+        let mut availability = StoreAvailability::default();
+        assert!(!availability.unavailable());
+        assert_eq!(availability.update(true, start), None);
+
+        assert_eq!(
+            availability.update(false, start),
+            Some(StoreTransition::Lost)
+        );
+        assert!(availability.unavailable());
+        // the outage is timed from the first failed health check
+        let later = start + Duration::from_secs(5);
+        assert_eq!(availability.update(false, later), None);
+        assert_eq!(
+            availability.update(true, later),
+            Some(StoreTransition::Recovered(Duration::from_secs(5)))
+        );
+        assert!(!availability.unavailable());
+        assert_eq!(availability.update(true, later), None);
+    }
+}
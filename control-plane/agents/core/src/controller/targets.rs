@@ -0,0 +1,69 @@
+use common_lib::types::v0::transport::{VolumeId, VolumeTargetChanged};
+use std::collections::HashMap;
+
+/// The device uri of the target of each volume, as last observed.
+/// A volume which is unpublished keeps the uri of its last target, so its republish is observed as
+/// a change of its target, whereas the first publish of a volume is not.
+#[derive(Debug, Default)]
+pub(crate) struct VolumeTargets {
+    uris: HashMap<VolumeId, String>,
+}
+
+impl VolumeTargets {
+    /// Observe the device `uri` of the target of the `volume`, if it's published, returning the
+    /// change from the previously observed uri, if any.
+    pub(crate) fn observe(
+        &mut self,
+        volume: &VolumeId,
+        uri: Option<String>,
+    ) -> Option<VolumeTargetChanged> {
+        let uri = uri.filter(|uri| !uri.is_empty())?;
+        match self.uris.insert(volume.clone(), uri.clone()) {
+            Some(old_uri) if old_uri != uri => Some(VolumeTargetChanged {
+                volume: volume.clone(),
+                old_uri,
+                new_uri: uri,
+            }),
+            _ => None,
+        }
+    }
+    /// Forget the uris of the volumes which don't satisfy `keep`, eg: deleted volumes
+    pub(crate) fn retain(&mut self, keep: impl Fn(&VolumeId) -> bool) {
+        self.uris.retain(|volume, _| keep(volume));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn republish() {
+        let mut targets = VolumeTargets::default();
+        let volume = VolumeId::new();
+        let uri = |node: &str| Some(format!("nvmf://{}:8420/nqn:{}", node, volume));
+
+        // the first publish is not a change
+        assert_eq!(targets.observe(&volume, uri("n1")), None);
+        assert_eq!(targets.observe(&volume, uri("n1")), None);
+
+        // unpublished, and then republished elsewhere
+        assert_eq!(targets.observe(&volume, None), None);
+        assert_eq!(targets.observe(&volume, Some(String::new())), None);
+        let changed = targets.observe(&volume, uri("n2"));
+        assert_eq!(
+            changed,
+            Some(VolumeTargetChanged {
+                volume: volume.clone(),
+                old_uri: uri("n1").unwrap(),
+                new_uri: uri("n2").unwrap(),
+            })
+        );
+        // the change is only observed once
+        assert_eq!(targets.observe(&volume, uri("n2")), None);
+
+        // a deleted volume is forgotten
+        targets.retain(|_| false);
+        assert_eq!(targets.observe(&volume, uri("n1")), None);
+    }
+}
@@ -1,8 +1,9 @@
 use crate::controller::{registry::Registry, specs::ResourceSpecsLocked};
 use common::errors::SvcError;
+use common_lib::types::v0::transport::NodeId;
 
 /// Poll Event that identifies why a poll is running
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum PollEvent {
     /// Poller period elapsed
     TimedRun,
@@ -15,15 +16,20 @@ pub(crate) enum PollEvent {
 }
 
 /// Poll Trigger source
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub(crate) enum PollTriggerEvent {
-    /// A node state has changed to Online
-    NodeStateChangeOnline,
+    /// The state of the given node has changed to Online
+    NodeStateChangeOnline(NodeId),
     /// A volume has been published in a Degraded state
     /// eg: may need replicas to be carved and/or added
     VolumeDegraded,
     /// The Agent is starting up
     Start,
+    /// The persistent store is available again, after the agent was degraded to read-only
+    /// operation, so the specs left dirty may be replayed
+    StoreAvailable,
+    /// The replay of an operation pending on a spec, given up on as stuck, is to be retried
+    ReplayRetried,
 }
 
 /// State of a poller
@@ -82,16 +88,17 @@ impl PollTimer {
 
 /// Poll Context passed around the poll handlers
 pub(crate) struct PollContext {
-    /// Event that triggered this poll
-    event: PollEvent,
+    /// Events that triggered this poll, more than one if a burst of events was coalesced
+    events: Vec<PollEvent>,
     /// Core Registry
     registry: Registry,
 }
 impl PollContext {
-    /// Create a context for a `PollEvent` with the global `Registry`
-    pub(crate) fn from(event: &PollEvent, registry: &Registry) -> Self {
+    /// Create a context for the coalesced `PollEvent`'s with the global `Registry`
+    pub(crate) fn from(events: Vec<PollEvent>, registry: &Registry) -> Self {
+        assert!(!events.is_empty(), "a poll is always triggered by an event");
         Self {
-            event: event.clone(),
+            events,
             registry: registry.clone(),
         }
     }
@@ -104,10 +111,13 @@ impl PollContext {
         self.registry.specs()
     }
 
-    #[allow(dead_code)]
-    /// Get a reference to the event that triggered this poll
+    /// Get a reference to the first event that triggered this poll
     pub(crate) fn event(&self) -> &PollEvent {
-        &self.event
+        &self.events[0]
+    }
+    /// Get all the events that triggered this poll
+    pub(crate) fn events(&self) -> &[PollEvent] {
+        &self.events
     }
 }
 
@@ -0,0 +1,32 @@
+use std::str::FromStr;
+
+/// How to handle a volume which is missing replicas which can't be placed as per its topology,
+/// eg: after the removal of the nodes which the topology allows the replicas on.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum TopologyPolicy {
+    /// Keep the volume degraded rather than placing replicas outside of its topology.
+    Strict,
+    /// Place the missing replicas regardless of the topology, to maintain the redundancy.
+    Relaxed,
+}
+
+impl Default for TopologyPolicy {
+    fn default() -> Self {
+        Self::Strict
+    }
+}
+
+impl FromStr for TopologyPolicy {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "strict" => Ok(Self::Strict),
+            "relaxed" => Ok(Self::Relaxed),
+            _ => Err(format!(
+                "Invalid topology policy '{}', expected one of: strict, relaxed",
+                source
+            )),
+        }
+    }
+}
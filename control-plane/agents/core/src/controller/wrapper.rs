@@ -1,10 +1,19 @@
-use super::{super::node::watchdog::Watchdog, grpc::GrpcContext};
+use super::{
+    super::node::{
+        circuit_breaker::{CircuitBreaker, CircuitBreakerConfig},
+        watchdog::Watchdog,
+    },
+    grpc::GrpcContext,
+};
 use crate::{
     controller::{
         grpc::{GrpcClient, GrpcClientLocked},
         states::{ResourceStates, ResourceStatesLocked},
     },
-    node::service::NodeCommsTimeout,
+    node::{
+        service::NodeCommsTimeout,
+        timeouts::{self, OperationClass},
+    },
     NumRebuilds,
 };
 
@@ -18,10 +27,12 @@ use common_lib::{
         store,
         store::{nexus::NexusState, replica::ReplicaState},
         transport::{
-            AddNexusChild, Child, CreateNexus, CreatePool, CreateReplica, DestroyNexus,
-            DestroyPool, DestroyReplica, MessageIdVs, Nexus, NexusId, NodeId, NodeState,
-            NodeStatus, PoolId, PoolState, PoolStatus, Protocol, RemoveNexusChild, Replica,
-            ReplicaId, ShareNexus, ShareReplica, UnshareNexus, UnshareReplica,
+            AddNexusChild, Child, ChildUri, CreateNexus, CreatePool, CreateReplica, DestroyNexus,
+            DestroyPool, DestroyReplica, DiscoverPools, DiscoveredPool, MessageIdVs, Nexus,
+            NexusId, NodeId, NodeState, NodeStatus, NvmeReservationType, OfflineNexusChild,
+            OnlineNexusChild, PauseNexus, PauseNexusRebuild, PoolId, PoolState, PoolStatus,
+            Protocol, RemoveNexusChild, Replica, ReplicaId, ResumeNexus, ResumeNexusRebuild,
+            ShareNexus, ShareReplica, UnshareNexus, UnshareReplica,
         },
     },
 };
@@ -39,10 +50,11 @@ use std::{
     cmp::Ordering,
     ops::{Deref, DerefMut},
     sync::Arc,
+    time::Duration,
 };
 use tracing::debug;
 
-type NodeResourceStates = (Vec<Replica>, Vec<PoolState>, Vec<Nexus>);
+pub(crate) type NodeResourceStates = (Vec<Replica>, Vec<PoolState>, Vec<Nexus>);
 
 /// Default timeout for GET* gRPC requests (ex: GetPools, GetNexuses, etc..)
 const GETS_TIMEOUT: MessageId = MessageId::v0(MessageIdVs::Default);
@@ -75,16 +87,22 @@ pub(crate) struct NodeWrapper {
     comms_timeouts: NodeCommsTimeout,
     /// runtime state information
     states: ResourceStatesLocked,
+    /// the runtime states last reported before the node went offline
+    last_states: NodeResourceStates,
     /// number of rebuilds in progress on the node
     num_rebuilds: Arc<RwLock<NumRebuilds>>,
+    /// fails fast the calls to the node whilst it keeps failing
+    circuit_breaker: CircuitBreaker,
 }
 
 impl NodeWrapper {
     /// Create a new wrapper for a `Node` with a `deadline` for its watchdog
+    /// and a circuit-breaker for its calls, configured by `breaker`
     pub(crate) fn new(
         node: &NodeState,
         deadline: std::time::Duration,
         comms_timeouts: NodeCommsTimeout,
+        breaker: CircuitBreakerConfig,
     ) -> Self {
         tracing::debug!("Creating new node {:?}", node);
         Self {
@@ -94,7 +112,9 @@ impl NodeWrapper {
             lock: Default::default(),
             comms_timeouts,
             states: ResourceStatesLocked::new(),
+            last_states: Default::default(),
             num_rebuilds: Arc::new(RwLock::new(0)),
+            circuit_breaker: CircuitBreaker::new(&node.id, breaker),
         }
     }
 
@@ -198,8 +218,8 @@ impl NodeWrapper {
     }
 
     /// Whether the watchdog deadline has expired
-    pub(crate) fn registration_expired(&self) -> bool {
-        self.watchdog.timestamp().elapsed() > self.watchdog.deadline()
+    pub(crate) fn registration_expired(&mut self) -> bool {
+        self.watchdog.expired()
     }
 
     /// "Pet" the node to meet the node's watchdog timer deadline
@@ -217,9 +237,10 @@ impl NodeWrapper {
         if self.registration_expired() {
             if !self.missed_deadline {
                 tracing::error!(
-                    "Node id '{}' missed the registration deadline of {:?}",
+                    "Node id '{}' missed the registration deadline of {:?}, last seen at {}",
                     self.id(),
-                    self.watchdog.deadline()
+                    self.watchdog.deadline(),
+                    humantime::format_rfc3339_seconds(self.watchdog.last_seen())
                 );
             }
 
@@ -318,11 +339,25 @@ impl NodeWrapper {
         previous
     }
 
-    /// Clear all states from the node
+    /// Clear all states from the node, keeping a copy of them as its last known states
     fn clear_states(&mut self) {
+        let (replicas, pools, nexuses) = (self.replicas(), self.pools(), self.nexuses());
+        if !replicas.is_empty() || !pools.is_empty() || !nexuses.is_empty() {
+            self.last_states = (replicas, pools, nexuses);
+        }
         self.resources_mut().clear_all();
     }
 
+    /// Get the states of the node's resources, or their last known states if the node is not
+    /// online, along with whether they are the last known ones
+    pub(crate) fn known_states(&self) -> (NodeResourceStates, bool) {
+        if self.is_online() {
+            ((self.replicas(), self.pools(), self.nexuses()), false)
+        } else {
+            (self.last_states.clone(), true)
+        }
+    }
+
     /// Get the inner states
     fn resources(&self) -> parking_lot::RwLockReadGuard<ResourceStates> {
         self.states.read()
@@ -596,6 +631,11 @@ impl NodeWrapper {
     pub(crate) fn num_rebuilds(&self) -> NumRebuilds {
         *self.num_rebuilds.read()
     }
+
+    /// Get the circuit-breaker of the calls to this node.
+    pub(crate) fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
 }
 
 /// Fetches node state from the dataplane.
@@ -658,6 +698,11 @@ pub(crate) trait ClientOps {
     async fn create_pool(&self, request: &CreatePool) -> Result<PoolState, SvcError>;
     /// Destroy a pool on the node via gRPC
     async fn destroy_pool(&self, request: &DestroyPool) -> Result<(), SvcError>;
+    /// Discover the pools which could be imported from the disks of the node via gRPC
+    async fn discover_pools(
+        &self,
+        request: &DiscoverPools,
+    ) -> Result<Vec<DiscoveredPool>, SvcError>;
     /// Create a replica on the pool via gRPC
     async fn create_replica(&self, request: &CreateReplica) -> Result<Replica, SvcError>;
     /// Share a replica on the pool via gRPC
@@ -675,10 +720,24 @@ pub(crate) trait ClientOps {
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError>;
     /// Unshare a nexus on the node via gRPC
     async fn unshare_nexus(&self, request: &UnshareNexus) -> Result<(), SvcError>;
+    /// Pause the IO of a nexus on the node via gRPC
+    async fn pause_nexus(&self, request: &PauseNexus) -> Result<(), SvcError>;
+    /// Resume the IO of a nexus on the node via gRPC
+    async fn resume_nexus(&self, request: &ResumeNexus) -> Result<(), SvcError>;
+    /// Pause the rebuild of a nexus child on the node via gRPC
+    async fn pause_rebuild(&self, request: &PauseNexusRebuild) -> Result<(), SvcError>;
+    /// Resume the paused rebuild of a nexus child on the node via gRPC
+    async fn resume_rebuild(&self, request: &ResumeNexusRebuild) -> Result<(), SvcError>;
     /// Add a child to a nexus via gRPC
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError>;
     /// Remove a child from its parent nexus via gRPC
     async fn remove_child(&self, request: &RemoveNexusChild) -> Result<(), SvcError>;
+    /// Bring an offline child of a nexus back online via gRPC
+    async fn online_child(&self, request: &OnlineNexusChild) -> Result<(), SvcError>;
+    /// Take a child of a nexus offline, without removing it from the nexus, via gRPC
+    async fn offline_child(&self, request: &OfflineNexusChild) -> Result<(), SvcError>;
+    /// Probe whether the node can connect to the nexus child `uri` within the `timeout`, via gRPC
+    async fn probe_child(&self, uri: &ChildUri, timeout: Duration) -> Result<(), SvcError>;
 }
 
 /// Internal Operations on a io-engine locked `NodeWrapper` for the implementor
@@ -698,6 +757,13 @@ pub(crate) trait InternalOps {
     /// OnRegister callback when a node is re-registered with the registry via its heartbeat
     /// On success returns where it's reset the node as online or not.
     async fn on_register(&self, node_state: NodeState) -> Result<bool, SvcError>;
+    /// Record the `result` of a call to the node with its circuit-breaker, returning it
+    /// Timeouts are logged with the class of the `request` operation
+    async fn record_call<T: Send>(
+        &self,
+        request: MessageId,
+        result: Result<T, SvcError>,
+    ) -> Result<T, SvcError>;
 }
 
 /// Getter operations on a io-engine locked `NodeWrapper` to get copies of its
@@ -801,6 +867,26 @@ impl InternalOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             Ok(false)
         }
     }
+
+    async fn record_call<T: Send>(
+        &self,
+        request: MessageId,
+        result: Result<T, SvcError>,
+    ) -> Result<T, SvcError> {
+        let node = self.read().await;
+        if let Err(error) = &result {
+            if timeouts::timed_out(error) {
+                tracing::warn!(
+                    node.id = %node.id(),
+                    request = %request.to_string(),
+                    timeout.class = %OperationClass::from(&request),
+                    timeout = ?node.comms_timeouts.request_for(request.clone()),
+                    "The dataplane request timed out"
+                );
+            }
+        }
+        node.circuit_breaker().record(result)
+    }
 }
 
 #[async_trait]
@@ -811,13 +897,26 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
                 node: self.read().await.id().clone(),
             });
         }
-        let ctx = self.read().await.grpc_context_ext(request)?;
-        ctx.connect_locked().await.map_err(|(_, error)| error)
+        let (ctx, breaker) = {
+            let node = self.read().await;
+            (
+                node.grpc_context_ext(request)?,
+                node.circuit_breaker().clone(),
+            )
+        };
+        // fail fast rather than queueing on the lock behind the calls which are timing out
+        breaker.check()?;
+        match ctx.connect_locked().await {
+            Ok(client) => Ok(client),
+            Err((_, error)) => breaker.record(Err(error)),
+        }
     }
 
     async fn create_pool(&self, request: &CreatePool) -> Result<PoolState, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let pool = dataplane.create_pool(request).await?;
+        let pool = self
+            .record_call(request.id(), dataplane.create_pool(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
@@ -826,12 +925,25 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a pool on the node via gRPC
     async fn destroy_pool(&self, request: &DestroyPool) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let _ = dataplane.destroy_pool(request).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.destroy_pool(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
         Ok(())
     }
 
+    /// Discover the pools which could be imported from the disks of the node via gRPC
+    /// Nothing is changed on the node, so the gRPC lock isn't taken.
+    async fn discover_pools(
+        &self,
+        request: &DiscoverPools,
+    ) -> Result<Vec<DiscoveredPool>, SvcError> {
+        let grpc = self.read().await.grpc_context_ext(request.id())?;
+        let client = grpc.connect().await?;
+        client.discover_pools(request).await
+    }
+
     /// Create a replica on the pool via gRPC
     async fn create_replica(&self, request: &CreateReplica) -> Result<Replica, SvcError> {
         if request.uuid == ReplicaId::default() {
@@ -841,7 +953,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             });
         }
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let replica = dataplane.create_replica(request).await?;
+        let replica = self
+            .record_call(request.id(), dataplane.create_replica(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         self.update_pool_states(ctx.deref_mut()).await?;
@@ -851,7 +965,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Share a replica on the pool via gRPC
     async fn share_replica(&self, request: &ShareReplica) -> Result<String, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let share = dataplane.share_replica(request).await?;
+        let share = self
+            .record_call(request.id(), dataplane.share_replica(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         Ok(share)
@@ -860,7 +976,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Unshare a replica on the pool via gRPC
     async fn unshare_replica(&self, request: &UnshareReplica) -> Result<String, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let local_uri = dataplane.unshare_replica(request).await?;
+        let local_uri = self
+            .record_call(request.id(), dataplane.unshare_replica(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         Ok(local_uri)
@@ -869,7 +987,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a replica on the pool via gRPC
     async fn destroy_replica(&self, request: &DestroyReplica) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let _ = dataplane.destroy_replica(request).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.destroy_replica(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_replica_states(ctx.deref_mut()).await?;
         // todo: remove when CAS-1107 is resolved
@@ -893,7 +1013,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             });
         }
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let mut nexus = dataplane.create_nexus(request).await?;
+        let mut nexus = self
+            .record_call(request.id(), dataplane.create_nexus(request).await)
+            .await?;
         // CAS-1107 - create_nexus_v2 returns NexusV1...
         nexus.name = request.name();
         nexus.uuid = request.uuid.clone();
@@ -905,7 +1027,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Destroy a nexus on the node via gRPC
     async fn destroy_nexus(&self, request: &DestroyNexus) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let _ = dataplane.destroy_nexus(request).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.destroy_nexus(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(())
@@ -914,7 +1038,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Share a nexus on the node via gRPC
     async fn share_nexus(&self, request: &ShareNexus) -> Result<String, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let share = dataplane.share_nexus(request).await?;
+        let share = self
+            .record_call(request.id(), dataplane.share_nexus(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(share)
@@ -923,7 +1049,53 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Unshare a nexus on the node via gRPC
     async fn unshare_nexus(&self, request: &UnshareNexus) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let _ = dataplane.unshare_nexus(request).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.unshare_nexus(request).await)
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(())
+    }
+
+    /// Pause the IO of a nexus on the node via gRPC
+    async fn pause_nexus(&self, request: &PauseNexus) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.pause_nexus(request).await)
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(())
+    }
+
+    /// Resume the IO of a nexus on the node via gRPC
+    async fn resume_nexus(&self, request: &ResumeNexus) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.resume_nexus(request).await)
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(())
+    }
+
+    /// Pause the rebuild of a nexus child on the node via gRPC
+    async fn pause_rebuild(&self, request: &PauseNexusRebuild) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.pause_rebuild(request).await)
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(())
+    }
+
+    /// Resume the paused rebuild of a nexus child on the node via gRPC
+    async fn resume_rebuild(&self, request: &ResumeNexusRebuild) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.resume_rebuild(request).await)
+            .await?;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         Ok(())
@@ -932,7 +1104,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Add a child to a nexus via gRPC
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.add_child(request).await;
+        let result = self
+            .record_call(request.id(), dataplane.add_child(request).await)
+            .await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -959,7 +1133,9 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
     /// Remove a child from its parent nexus via gRPC
     async fn remove_child(&self, request: &RemoveNexusChild) -> Result<(), SvcError> {
         let dataplane = self.grpc_client_locked(request.id()).await?;
-        let result = dataplane.remove_child(request).await;
+        let result = self
+            .record_call(request.id(), dataplane.remove_child(request).await)
+            .await;
         let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
         self.update_nexus_states(ctx.deref_mut()).await?;
         match result {
@@ -979,6 +1155,44 @@ impl ClientOps for Arc<tokio::sync::RwLock<NodeWrapper>> {
             }
         }
     }
+
+    /// Bring an offline child of a nexus back online via gRPC
+    async fn online_child(&self, request: &OnlineNexusChild) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.online_child(request).await)
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(())
+    }
+
+    /// Take a child of a nexus offline via gRPC
+    async fn offline_child(&self, request: &OfflineNexusChild) -> Result<(), SvcError> {
+        let dataplane = self.grpc_client_locked(request.id()).await?;
+        let _ = self
+            .record_call(request.id(), dataplane.offline_child(request).await)
+            .await?;
+        let mut ctx = dataplane.reconnect(GETS_TIMEOUT).await?;
+        self.update_nexus_states(ctx.deref_mut()).await?;
+        Ok(())
+    }
+
+    /// Probe whether the node can connect to the nexus child `uri`, via gRPC
+    /// The probe doesn't take the node's gRPC lock, so the children may be probed concurrently.
+    async fn probe_child(&self, uri: &ChildUri, timeout: Duration) -> Result<(), SvcError> {
+        let ctx = {
+            let node = self.read().await;
+            if !node.is_online() {
+                return Err(SvcError::NodeNotOnline {
+                    node: node.id().clone(),
+                });
+            }
+            let timeouts = NodeCommsTimeout::new(node.comms_timeouts.connect(), timeout, true);
+            node.grpc_context_timeout(timeouts)?
+        };
+        ctx.connect().await?.probe_child(uri).await
+    }
 }
 
 #[async_trait]
@@ -1104,6 +1318,20 @@ impl ClientOps for GrpcClientLocked {
     async fn create_nexus(&self, request: &CreateNexus) -> Result<Nexus, SvcError> {
         match self.api_version() {
             APIVersion::V0 => {
+                // the v0 api only carries the reservation key, with the io-engine always
+                // acquiring a write exclusive, all registrants reservation
+                if let Some(reservation_type) = request
+                    .config
+                    .as_ref()
+                    .and_then(|config| config.reservation_type())
+                {
+                    if reservation_type != NvmeReservationType::WriteExclusiveAllRegs {
+                        return Err(SvcError::NvmeReservationUnsupported {
+                            node_id: request.node.clone(),
+                            reservation_type,
+                        });
+                    }
+                }
                 let rpc_nexus = self
                     .client_v0()?
                     .create_nexus_v2(request.to_rpc())
@@ -1179,6 +1407,94 @@ impl ClientOps for GrpcClientLocked {
         }
     }
 
+    async fn pause_nexus(&self, request: &PauseNexus) -> Result<(), SvcError> {
+        match self.api_version() {
+            APIVersion::V0 => {
+                let _ = self
+                    .client_v0()?
+                    .pause_nexus(request.to_rpc())
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Nexus,
+                        request: "pause_nexus",
+                    })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.node().clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Nexus,
+                request: "pause_nexus".to_string(),
+            }),
+        }
+    }
+
+    async fn resume_nexus(&self, request: &ResumeNexus) -> Result<(), SvcError> {
+        match self.api_version() {
+            APIVersion::V0 => {
+                let _ = self
+                    .client_v0()?
+                    .resume_nexus(request.to_rpc())
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Nexus,
+                        request: "resume_nexus",
+                    })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.node().clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Nexus,
+                request: "resume_nexus".to_string(),
+            }),
+        }
+    }
+
+    async fn pause_rebuild(&self, request: &PauseNexusRebuild) -> Result<(), SvcError> {
+        match self.api_version() {
+            APIVersion::V0 => {
+                let _ = self
+                    .client_v0()?
+                    .pause_rebuild(request.to_rpc())
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Child,
+                        request: "pause_rebuild",
+                    })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.node().clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Child,
+                request: "pause_rebuild".to_string(),
+            }),
+        }
+    }
+
+    async fn resume_rebuild(&self, request: &ResumeNexusRebuild) -> Result<(), SvcError> {
+        match self.api_version() {
+            APIVersion::V0 => {
+                let _ = self
+                    .client_v0()?
+                    .resume_rebuild(request.to_rpc())
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Child,
+                        request: "resume_rebuild",
+                    })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.node().clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Child,
+                request: "resume_rebuild".to_string(),
+            }),
+        }
+    }
+
     async fn add_child(&self, request: &AddNexusChild) -> Result<Child, SvcError> {
         match self.api_version() {
             APIVersion::V0 => {
@@ -1216,6 +1532,68 @@ impl ClientOps for GrpcClientLocked {
             }
         }
     }
+
+    async fn online_child(&self, request: &OnlineNexusChild) -> Result<(), SvcError> {
+        match self.api_version() {
+            APIVersion::V0 => {
+                let _ = self
+                    .client_v0()?
+                    .child_operation(request.to_rpc())
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Child,
+                        request: "online_child",
+                    })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.node().clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Child,
+                request: "online_child".to_string(),
+            }),
+        }
+    }
+
+    async fn offline_child(&self, request: &OfflineNexusChild) -> Result<(), SvcError> {
+        match self.api_version() {
+            APIVersion::V0 => {
+                let _ = self
+                    .client_v0()?
+                    .child_operation(request.to_rpc())
+                    .await
+                    .context(GrpcRequestError {
+                        resource: ResourceKind::Child,
+                        request: "offline_child",
+                    })?;
+                Ok(())
+            }
+            APIVersion::V1 => Err(SvcError::UnsupportedApiRequest {
+                node_id: self.node().clone(),
+                api_version: APIVersion::V1,
+                resource: ResourceKind::Child,
+                request: "offline_child".to_string(),
+            }),
+        }
+    }
+
+    async fn discover_pools(
+        &self,
+        request: &DiscoverPools,
+    ) -> Result<Vec<DiscoveredPool>, SvcError> {
+        self.deref().discover_pools(request).await
+    }
+
+    async fn probe_child(&self, uri: &ChildUri, timeout: Duration) -> Result<(), SvcError> {
+        match tokio::time::timeout(timeout, self.deref().probe_child(uri)).await {
+            Ok(result) => result,
+            Err(_) => Err(SvcError::GrpcRequestError {
+                resource: ResourceKind::Child,
+                request: "probe_child".to_string(),
+                source: tonic::Status::deadline_exceeded(format!("Timed out after {:?}", timeout)),
+            }),
+        }
+    }
 }
 
 /// convert rpc pool to a agent pool
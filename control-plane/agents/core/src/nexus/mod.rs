@@ -1,6 +1,11 @@
 use super::controller::registry::Registry;
+use common::errors::SvcError;
+use common_lib::types::v0::{
+    store::nexus_child::NexusChild,
+    transport::{ChildUri, CreateNexus, Nexus, NodeId, Replica},
+};
 use grpc::operations::nexus::server::NexusServer;
-use std::sync::Arc;
+use std::{str::FromStr, sync::Arc};
 
 mod operations;
 mod registry;
@@ -16,3 +21,129 @@ pub(crate) fn configure(builder: common::Service) -> common::Service {
     let nexus_service = NexusServer::new(new_service);
     builder.with_shared_state(nexus_service)
 }
+
+/// Whether a nexus may be created when some of its children are on nodes which are offline, or
+/// fail to attach.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(crate) enum NexusChildrenPolicy {
+    /// All the children must be attached, otherwise the creation fails and whatever was created
+    /// on the node is destroyed.
+    All,
+    /// The nexus is created degraded with the children which can be attached, and the others are
+    /// added by the reconciler, once their nodes are back online.
+    BestEffort,
+}
+
+impl Default for NexusChildrenPolicy {
+    fn default() -> Self {
+        Self::All
+    }
+}
+
+impl FromStr for NexusChildrenPolicy {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        match source {
+            "all" => Ok(Self::All),
+            "best-effort" => Ok(Self::BestEffort),
+            _ => Err(format!(
+                "Invalid nexus children policy '{}', expected one of: all, best-effort",
+                source
+            )),
+        }
+    }
+}
+
+impl NexusChildrenPolicy {
+    /// Get the request with which to create the nexus, given the children of the `request`
+    /// which are on `offline` nodes.
+    pub(crate) fn create_request(
+        &self,
+        request: &CreateNexus,
+        offline: &[(NexusChild, NodeId)],
+    ) -> Result<CreateNexus, SvcError> {
+        let reachable = request
+            .children
+            .iter()
+            .filter(|child| !offline.iter().any(|(offline, _)| offline == *child))
+            .cloned()
+            .collect::<Vec<_>>();
+        match offline.first() {
+            None => Ok(request.clone()),
+            Some((_, node)) if self == &Self::All || reachable.is_empty() => {
+                Err(SvcError::NodeNotOnline { node: node.clone() })
+            }
+            Some(_) => Ok(CreateNexus {
+                children: reachable,
+                ..request.clone()
+            }),
+        }
+    }
+}
+
+/// Get the children of the `nexus` which are backed by one of the `replicas` smaller than the
+/// nexus, along with the size of their replica, as the IO to the tail of the nexus can't be
+/// served by them.
+pub(crate) fn undersized_children(nexus: &Nexus, replicas: &[Replica]) -> Vec<(ChildUri, u64)> {
+    nexus
+        .children
+        .iter()
+        .filter_map(|child| {
+            let uuid = child.uri.uuid_str();
+            let replica = replicas
+                .iter()
+                .find(|r| r.uri == child.uri.as_str() || uuid == Some(r.uuid.to_string()))?;
+            if replica.size < nexus.size {
+                Some((child.uri.clone(), replica.size))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common_lib::types::v0::transport::{Child, ReplicaId};
+
+    const SIZE: u64 = 10 * 1024 * 1024;
+
+    fn replica(size: u64) -> Replica {
+        let uuid = ReplicaId::new();
+        Replica {
+            uri: format!(
+                "nvmf://node:8420/nqn.2019-05.io.openebs:{}?uuid={}",
+                uuid, uuid
+            ),
+            uuid,
+            size,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn undersized_nexus_children() {
+        let replicas = vec![replica(SIZE), replica(SIZE + 4096), replica(SIZE - 4096)];
+        let nexus = Nexus {
+            size: SIZE,
+            children: replicas
+                .iter()
+                .map(|r| Child {
+                    uri: ChildUri::from(r.uri.as_str()),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        };
+        let undersized = undersized_children(&nexus, &replicas);
+        assert_eq!(
+            undersized,
+            vec![(ChildUri::from(replicas[2].uri.as_str()), SIZE - 4096)]
+        );
+
+        // children whose replica is not known can't be checked
+        assert!(undersized_children(&nexus, &replicas[.. 2]).is_empty());
+    }
+}
@@ -1,22 +1,35 @@
-use crate::controller::{
-    operations::{ResourceLifecycle, ResourceOffspring, ResourceSharing},
-    registry::Registry,
-    specs::{GuardedOperationsHelper, OperationSequenceGuard},
-    wrapper::ClientOps,
+use crate::{
+    controller::{
+        operations::{
+            ResourceLifecycle, ResourceOffspring, ResourceOffspringAvailability, ResourcePausing,
+            ResourceSharing,
+        },
+        registry::Registry,
+        specs::{GuardedOperationsHelper, OperationSequenceGuard},
+        wrapper::{ClientOps, NodeWrapper},
+    },
+    nexus::{specs::check_last_healthy_child, NexusChildrenPolicy},
 };
 use common::errors::{SvcError, SvcError::CordonedNode};
-use common_lib::types::v0::{
-    store::{
-        nexus::{NexusOperation, NexusSpec},
-        nexus_child::NexusChild,
-        OperationGuardArc,
-    },
-    transport::{
-        child::Child,
-        nexus::{CreateNexus, DestroyNexus, Nexus, ShareNexus, UnshareNexus},
-        AddNexusChild, RemoveNexusChild,
+use common_lib::{
+    transport_api::ErrorChain,
+    types::v0::{
+        store::{
+            nexus::{NexusOperation, NexusSpec},
+            nexus_child::NexusChild,
+            OperationGuardArc,
+        },
+        transport::{
+            child::Child,
+            nexus::{
+                CreateNexus, DestroyNexus, Nexus, PauseNexus, ResumeNexus, ShareNexus, UnshareNexus,
+            },
+            AddNexusChild, OfflineNexusChild, OnlineNexusChild, RemoveNexusChild,
+        },
     },
 };
+use std::sync::Arc;
+use tonic::Code;
 
 #[async_trait::async_trait]
 impl ResourceLifecycle for OperationGuardArc<NexusSpec> {
@@ -38,14 +51,56 @@ impl ResourceLifecycle for OperationGuardArc<NexusSpec> {
 
         let node = registry.get_node_wrapper(&request.node).await?;
 
+        let offline = specs
+            .offline_nexus_children(registry, &request.children)
+            .await;
+        let create = registry
+            .nexus_children_policy()
+            .create_request(request, &offline)?;
+        let (create, unreachable) = match request.skip_child_probe {
+            true => (create, vec![]),
+            false => probe_children(registry, &node, create).await?,
+        };
+
+        registry.store_available()?;
         let nexus = specs
             .get_or_create_nexus(request)
             .operation_guard_wait()
             .await?;
         let _ = nexus.start_create(registry, request).await?;
 
-        let result = node.create_nexus(request).await;
+        let mut deferred = offline
+            .into_iter()
+            .map(|(child, _)| child)
+            .chain(unreachable)
+            .collect::<Vec<_>>();
+        let result = match node.create_nexus(&create).await {
+            Err(error) if !already_exists(&error) => {
+                // the nexus may have been left behind with some of its children attached
+                destroy_partial_nexus(&node, &create).await;
+                match registry.nexus_children_policy() {
+                    NexusChildrenPolicy::BestEffort if create.children.len() > 1 => {
+                        create_with_attachable(&node, &create, error).await.map(
+                            |(nexus, failed)| {
+                                deferred.extend(failed);
+                                nexus
+                            },
+                        )
+                    }
+                    _ => Err(error),
+                }
+            }
+            result => result,
+        };
         specs.on_create_set_owners(request, &nexus, &result);
+        if result.is_ok() && !deferred.is_empty() {
+            tracing::warn!(
+                nexus.uuid = %request.uuid,
+                children = ?deferred,
+                "Created the nexus without the children which are offline or failed to attach, they'll be added by the reconciler"
+            );
+            nexus.lock().defer_children(&deferred);
+        }
 
         let nexus_state = nexus.complete_create(result, registry).await?;
         Ok((nexus, nexus_state))
@@ -60,6 +115,138 @@ impl ResourceLifecycle for OperationGuardArc<NexusSpec> {
     }
 }
 
+/// Probe concurrently whether the node can connect to each of the remote children of the
+/// `request`, failing fast with a report of the reachability of each child if any can't be
+/// reached, rather than after the long dataplane timeout of the creation.
+/// With the best-effort policy the nexus is instead created without the unreachable children,
+/// which are returned to be deferred, as long as any child is reachable.
+async fn probe_children(
+    registry: &Registry,
+    node: &Arc<tokio::sync::RwLock<NodeWrapper>>,
+    request: CreateNexus,
+) -> Result<(CreateNexus, Vec<NexusChild>), SvcError> {
+    let timeout = registry.child_probe_timeout();
+    let probes = request
+        .children
+        .iter()
+        .filter(|child| child.uri().as_str().starts_with("nvmf://"))
+        .map(|child| async move { (child, node.probe_child(&child.uri(), timeout).await) });
+    let mut report = vec![];
+    let mut unreachable = vec![];
+    for (child, result) in futures::future::join_all(probes).await {
+        match result {
+            Ok(()) => report.push(format!("'{}' is reachable", child.uri())),
+            // the node itself can't be reached, rather than the child
+            Err(error) if !matches!(error, SvcError::GrpcRequestError { .. }) => return Err(error),
+            Err(error) => {
+                report.push(format!("'{}' is unreachable: {}", child.uri(), error));
+                unreachable.push(child.clone());
+            }
+        }
+    }
+    if unreachable.is_empty() {
+        return Ok((request, unreachable));
+    }
+
+    let reachable = request
+        .children
+        .iter()
+        .filter(|child| !unreachable.contains(child))
+        .cloned()
+        .collect::<Vec<_>>();
+    match registry.nexus_children_policy() {
+        NexusChildrenPolicy::BestEffort if !reachable.is_empty() => Ok((
+            CreateNexus {
+                children: reachable,
+                ..request
+            },
+            unreachable,
+        )),
+        _ => Err(SvcError::ChildrenUnreachable {
+            nexus: request.uuid.to_string(),
+            node: request.node,
+            report: report.join(", "),
+        }),
+    }
+}
+
+/// Whether the nexus creation failed because the nexus already exists.
+fn already_exists(error: &SvcError) -> bool {
+    matches!(error, SvcError::GrpcRequestError { source, .. } if source.code() == Code::AlreadyExists)
+}
+
+/// Destroy what may be left of a nexus whose creation failed, so that its children are detached.
+async fn destroy_partial_nexus(
+    node: &Arc<tokio::sync::RwLock<NodeWrapper>>,
+    request: &CreateNexus,
+) {
+    let destroy = DestroyNexus::new(request.node.clone(), request.uuid.clone());
+    if let Err(error) = node.destroy_nexus(&destroy).await {
+        tracing::debug!(
+            nexus.uuid = %request.uuid,
+            error = %error.full_string(),
+            "Failed to destroy the partially created nexus"
+        );
+    }
+}
+
+/// Create the nexus with the children of the `request` which can be attached, once it failed to
+/// be created with all of them, returning it along with the children which were left out.
+/// The nexus is created with the first child which can be attached and the others are added to it
+/// one by one, or the creation `error` is returned if no child can be attached.
+async fn create_with_attachable(
+    node: &Arc<tokio::sync::RwLock<NodeWrapper>>,
+    request: &CreateNexus,
+    error: SvcError,
+) -> Result<(Nexus, Vec<NexusChild>), SvcError> {
+    let mut children = request.children.iter();
+    let mut failed = vec![];
+    let mut nexus = loop {
+        let child = match children.next() {
+            Some(child) => child,
+            None => return Err(error),
+        };
+        let create = CreateNexus {
+            children: vec![child.clone()],
+            ..request.clone()
+        };
+        match node.create_nexus(&create).await {
+            Ok(nexus) => break nexus,
+            Err(error) => {
+                tracing::warn!(
+                    nexus.uuid = %request.uuid,
+                    child = %child.uri(),
+                    error = %error.full_string(),
+                    "Failed to create the nexus with the child"
+                );
+                destroy_partial_nexus(node, request).await;
+                failed.push(child.clone());
+            }
+        }
+    };
+    for child in children {
+        let add = AddNexusChild {
+            node: request.node.clone(),
+            nexus: request.uuid.clone(),
+            uri: child.uri(),
+            auto_rebuild: true,
+        };
+        match node.add_child(&add).await {
+            Ok(added) => nexus.children.push(added),
+            Err(error) => {
+                tracing::warn!(
+                    nexus.uuid = %request.uuid,
+                    child = %child.uri(),
+                    error = %error.full_string(),
+                    "Failed to add the child to the nexus"
+                );
+                failed.push(child.clone());
+            }
+        }
+    }
+    Ok((nexus, failed))
+}
+
 #[async_trait::async_trait]
 impl ResourceLifecycle for Option<&mut OperationGuardArc<NexusSpec>> {
     type Create = CreateNexus;
@@ -154,9 +341,13 @@ impl ResourceSharing for Option<&mut OperationGuardArc<NexusSpec>> {
 
         if let Some(nexus) = self {
             let status = registry.get_nexus(&request.uuid).await?;
-            let spec_clone = nexus
+            let spec_clone = match nexus
                 .start_update(registry, &status, NexusOperation::Unshare)
-                .await?;
+                .await
+            {
+                Ok(spec_clone) => spec_clone,
+                Err(error) => return registry.unshare_idempotent(error),
+            };
 
             let result = node.unshare_nexus(request).await;
             nexus.complete_update(registry, result, spec_clone).await
@@ -166,6 +357,42 @@ impl ResourceSharing for Option<&mut OperationGuardArc<NexusSpec>> {
     }
 }
 
+#[async_trait::async_trait]
+impl ResourcePausing for OperationGuardArc<NexusSpec> {
+    type Pause = PauseNexus;
+    type Resume = ResumeNexus;
+
+    async fn pause(&mut self, registry: &Registry, request: &Self::Pause) -> Result<(), SvcError> {
+        let node = registry.get_node_wrapper(&request.node).await?;
+        let status = registry.get_nexus(&request.uuid).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &status,
+                NexusOperation::Pause(request.thaw_deadline),
+            )
+            .await?;
+
+        let result = node.pause_nexus(request).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+
+    async fn resume(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resume,
+    ) -> Result<(), SvcError> {
+        let node = registry.get_node_wrapper(&request.node).await?;
+        let status = registry.get_nexus(&request.uuid).await?;
+        let spec_clone = self
+            .start_update(registry, &status, NexusOperation::Resume)
+            .await?;
+
+        let result = node.resume_nexus(request).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceOffspring for OperationGuardArc<NexusSpec> {
     type Add = AddNexusChild;
@@ -228,6 +455,7 @@ impl ResourceOffspring for Option<&mut OperationGuardArc<NexusSpec>> {
 
         if let Some(nexus) = self {
             let status = registry.get_nexus(&request.nexus).await?;
+            check_last_healthy_child(&nexus.lock(), &status, &request.uri, request.force)?;
             let spec_clone = nexus
                 .start_update(
                     registry,
@@ -243,3 +471,47 @@ impl ResourceOffspring for Option<&mut OperationGuardArc<NexusSpec>> {
         }
     }
 }
+
+#[async_trait::async_trait]
+impl ResourceOffspringAvailability for OperationGuardArc<NexusSpec> {
+    type Online = OnlineNexusChild;
+    type Offline = OfflineNexusChild;
+
+    async fn online_child(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Online,
+    ) -> Result<(), SvcError> {
+        let node = registry.get_node_wrapper(&request.node).await?;
+        let status = registry.get_nexus(&request.nexus).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &status,
+                NexusOperation::OnlineChild(request.uri.clone()),
+            )
+            .await?;
+
+        let result = node.online_child(request).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+
+    async fn offline_child(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Offline,
+    ) -> Result<(), SvcError> {
+        let node = registry.get_node_wrapper(&request.node).await?;
+        let status = registry.get_nexus(&request.nexus).await?;
+        let spec_clone = self
+            .start_update(
+                registry,
+                &status,
+                NexusOperation::OfflineChild(request.uri.clone()),
+            )
+            .await?;
+
+        let result = node.offline_child(request).await;
+        self.complete_update(registry, result, spec_clone).await
+    }
+}
@@ -1,16 +1,19 @@
 use crate::controller::{
-    operations::{ResourceLifecycle, ResourceOffspring, ResourceSharing},
+    operations::{
+        ResourceLifecycle, ResourceOffspring, ResourceOffspringAvailability, ResourceSharing,
+    },
     registry::Registry,
     specs::ResourceSpecsLocked,
 };
 use common::errors::SvcError;
 use common_lib::{
-    transport_api::{v0::Nexuses, ReplyError},
+    transport_api::{v0::Nexuses, ReplyError, ResourceKind},
     types::v0::{
         store::{nexus::NexusSpec, OperationGuardArc},
         transport::{
-            AddNexusChild, Child, CreateNexus, DestroyNexus, Filter, GetNexuses, Nexus,
-            RemoveNexusChild, ShareNexus, UnshareNexus,
+            AddNexusChild, Child, ChildUri, CreateNexus, DestroyNexus, Filter, GetNexuses, Nexus,
+            NexusId, OfflineNexusChild, OnlineNexusChild, RemoveNexusChild, ShareNexus,
+            UnshareNexus,
         },
     },
 };
@@ -18,7 +21,8 @@ use grpc::{
     context::Context,
     operations::nexus::traits::{
         AddNexusChildInfo, CreateNexusInfo, DestroyNexusInfo, NexusOperations,
-        RemoveNexusChildInfo, ShareNexusInfo, UnshareNexusInfo,
+        OfflineNexusChildInfo, OnlineNexusChildInfo, RemoveNexusChildInfo, ShareNexusInfo,
+        UnshareNexusInfo,
     },
 };
 
@@ -102,6 +106,32 @@ impl NexusOperations for Service {
         Context::spawn(async move { service.remove_nexus_child(&unshare_nexus).await }).await??;
         Ok(())
     }
+
+    async fn online_nexus_child(
+        &self,
+        req: &dyn OnlineNexusChildInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Child, ReplyError> {
+        let online_nexus_child = req.into();
+        let service = self.clone();
+        let child =
+            Context::spawn(async move { service.online_nexus_child(&online_nexus_child).await })
+                .await??;
+        Ok(child)
+    }
+
+    async fn offline_nexus_child(
+        &self,
+        req: &dyn OfflineNexusChildInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Child, ReplyError> {
+        let offline_nexus_child = req.into();
+        let service = self.clone();
+        let child =
+            Context::spawn(async move { service.offline_nexus_child(&offline_nexus_child).await })
+                .await??;
+        Ok(child)
+    }
 }
 impl Service {
     /// Return new `Self`.
@@ -127,7 +157,12 @@ impl Service {
                 let nexus = self.registry.get_nexus(&nexus_id).await?;
                 vec![nexus]
             }
-            _ => return Err(SvcError::InvalidFilter { filter }),
+            _ => {
+                return Err(SvcError::InvalidFilter {
+                    resource: ResourceKind::Nexus,
+                    filter,
+                })
+            }
         };
         Ok(Nexuses(nexuses))
     }
@@ -177,4 +212,39 @@ impl Service {
         let mut nexus = self.specs().nexus_opt(&request.nexus).await?;
         nexus.as_mut().remove_child(&self.registry, request).await
     }
+
+    /// Bring an offline nexus child back online using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(nexus.uuid = %request.nexus))]
+    pub(super) async fn online_nexus_child(
+        &self,
+        request: &OnlineNexusChild,
+    ) -> Result<Child, SvcError> {
+        let mut nexus = self.specs().nexus(&request.nexus).await?;
+        nexus.online_child(&self.registry, request).await?;
+        self.nexus_child(&request.nexus, &request.uri).await
+    }
+
+    /// Take a nexus child offline using the given parameters.
+    #[tracing::instrument(level = "info", skip(self), err, fields(nexus.uuid = %request.nexus))]
+    pub(super) async fn offline_nexus_child(
+        &self,
+        request: &OfflineNexusChild,
+    ) -> Result<Child, SvcError> {
+        let mut nexus = self.specs().nexus(&request.nexus).await?;
+        nexus.offline_child(&self.registry, request).await?;
+        self.nexus_child(&request.nexus, &request.uri).await
+    }
+
+    /// Get the current state of the child `uri` of the nexus `id`.
+    async fn nexus_child(&self, id: &NexusId, uri: &ChildUri) -> Result<Child, SvcError> {
+        let nexus = self.registry.get_nexus(id).await?;
+        nexus
+            .children
+            .into_iter()
+            .find(|child| &child.uri == uri)
+            .ok_or_else(|| SvcError::ChildNotFound {
+                nexus: id.to_string(),
+                child: uri.to_string(),
+            })
+    }
 }
@@ -18,8 +18,8 @@ use common_lib::{
             OperationGuardArc, SpecStatus, SpecTransaction, TraceSpan,
         },
         transport::{
-            AddNexusReplica, Child, ChildUri, CreateNexus, Nexus, NexusId, NexusOwners,
-            NexusStatus, RemoveNexusChild, RemoveNexusReplica, ReplicaOwners,
+            AddNexusReplica, Child, ChildState, ChildUri, CreateNexus, Nexus, NexusId, NexusOwners,
+            NexusStatus, NodeId, NodeStatus, RemoveNexusChild, RemoveNexusReplica, ReplicaOwners,
         },
     },
 };
@@ -83,6 +83,40 @@ impl SpecOperationsHelper for NexusSpec {
                 })
             }
             NexusOperation::RemoveChild(_) => Ok(()),
+            NexusOperation::Pause(_) if self.frozen_until.is_some() => {
+                Err(SvcError::AlreadyFrozen {
+                    kind: ResourceKind::Nexus,
+                    id: self.uuid_str(),
+                })
+            }
+            NexusOperation::Pause(_) => Ok(()),
+            NexusOperation::Resume if self.frozen_until.is_none() => Err(SvcError::NotFrozen {
+                kind: ResourceKind::Nexus,
+                id: self.uuid_str(),
+            }),
+            NexusOperation::Resume => Ok(()),
+            NexusOperation::OnlineChild(uri) | NexusOperation::OfflineChild(uri)
+                if !self.children.iter().any(|c| &c.uri() == uri) && !state.contains_child(uri) =>
+            {
+                Err(SvcError::ChildNotFound {
+                    nexus: self.uuid_str(),
+                    child: uri.to_string(),
+                })
+            }
+            NexusOperation::OnlineChild(uri) if !self.offline_children.contains(uri) => {
+                Err(SvcError::ChildNotOffline {
+                    nexus: self.uuid_str(),
+                    child: uri.to_string(),
+                })
+            }
+            NexusOperation::OnlineChild(_) => Ok(()),
+            NexusOperation::OfflineChild(uri) if self.offline_children.contains(uri) => {
+                Err(SvcError::ChildAlreadyOffline {
+                    nexus: self.uuid_str(),
+                    child: uri.to_string(),
+                })
+            }
+            NexusOperation::OfflineChild(_) => Ok(()),
             _ => unreachable!(),
         }?;
         self.start_op(op);
@@ -129,6 +163,37 @@ impl SpecOperationsHelper for NexusSpec {
     }
 }
 
+/// Fail with `LastHealthyReplica` if the child `uri` is the last healthy child of the volume nexus,
+/// as removing it would destroy the only up to date copy of the volume data.
+/// The removal can be `force`d regardless.
+pub(super) fn check_last_healthy_child(
+    nexus: &NexusSpec,
+    state: &Nexus,
+    uri: &ChildUri,
+    force: bool,
+) -> Result<(), SvcError> {
+    let volume = match &nexus.owner {
+        Some(volume) if !force => volume,
+        _ => return Ok(()),
+    };
+    let mut healthy = state
+        .children
+        .iter()
+        .filter(|c| c.state == ChildState::Online);
+    let last_healthy = match healthy.next() {
+        Some(child) => &child.uri == uri && healthy.next().is_none(),
+        None => false,
+    };
+    if last_healthy {
+        Err(SvcError::LastHealthyReplica {
+            replica: uri.to_string(),
+            volume: volume.to_string(),
+        })
+    } else {
+        Ok(())
+    }
+}
+
 /// Implementation of the ResourceSpecs which is retrieved from the ResourceSpecsLocked
 /// During these calls, no other thread can add/remove elements from the list
 impl ResourceSpecs {
@@ -188,6 +253,37 @@ impl ResourceSpecsLocked {
             Some(nexus) => Ok(nexus.operation_guard_wait().await?),
         }
     }
+    /// Get the `children` which are replicas on nodes which are offline, along with their nodes.
+    /// Children which are not replicas are assumed to be reachable.
+    pub(crate) async fn offline_nexus_children(
+        &self,
+        registry: &Registry,
+        children: &[NexusChild],
+    ) -> Vec<(NexusChild, NodeId)> {
+        let mut offline = vec![];
+        for child in children {
+            let replica = match child.as_replica() {
+                Some(replica) => replica,
+                None => continue,
+            };
+            let pool = match self.get_replica(replica.uuid()) {
+                Some(replica) => replica.lock().pool.clone(),
+                None => continue,
+            };
+            let node = match self.get_locked_pool(&pool) {
+                Some(pool) => pool.lock().node.clone(),
+                None => continue,
+            };
+            let online = match registry.get_node_wrapper(&node).await {
+                Ok(wrapper) => wrapper.read().await.status() != NodeStatus::Offline,
+                Err(_) => false,
+            };
+            if !online {
+                offline.push((child.clone(), node));
+            }
+        }
+        offline
+    }
     /// Get or Create the protected NexusSpec for the given request
     pub(crate) fn get_or_create_nexus(&self, request: &CreateNexus) -> ResourceMutex<NexusSpec> {
         let mut specs = self.write();
@@ -215,7 +311,10 @@ impl ResourceSpecsLocked {
                 let replicas = self.get_volume_replicas(uuid);
                 replicas.into_iter().for_each(|replica_spec| {
                     let mut spec = replica_spec.lock();
-                    if nexus_replicas.iter().any(|r| r.uuid() == &spec.uuid) {
+                    if nexus_replicas
+                        .iter()
+                        .any(|r| r.uuid() == &spec.uuid && spec.is_generation(r.generation()))
+                    {
                         spec.owners.add_owner(&nexus.uuid);
                     }
                 });
@@ -244,6 +343,7 @@ impl ResourceSpecsLocked {
         let node = registry.get_node_wrapper(&request.node).await?;
 
         if let Some(nexus) = nexus {
+            self.check_replica_generation(request.replica.uuid(), request.replica.generation())?;
             let status = registry.get_nexus(&request.nexus).await?;
             let spec_clone = nexus
                 .start_update(
@@ -282,6 +382,7 @@ impl ResourceSpecsLocked {
 
         if let Some(nexus) = nexus {
             let status = registry.get_nexus(&request.nexus).await?;
+            check_last_healthy_child(&nexus.lock(), &status, request.replica.uri(), request.force)?;
             let spec_clone = nexus
                 .start_update(
                     registry,
@@ -410,12 +511,19 @@ impl ResourceSpecsLocked {
     /// Worker that reconciles dirty NexusSpecs's with the persistent store.
     /// This is useful when nexus operations are performed but we fail to
     /// update the spec with the persistent store.
+    /// A spec whose replay keeps failing is retried with a backoff, and is given up on as stuck
+    /// after too many attempts.
     pub(crate) async fn reconcile_dirty_nexuses(&self, registry: &Registry) -> bool {
         let mut pending_ops = false;
         let nexuses = self.get_nexuses();
         for nexus in nexuses {
             if let Ok(mut guard) = nexus.operation_guard() {
-                if !guard.handle_incomplete_ops(registry).await {
+                let uuid = guard.lock().uuid.to_string();
+                if !registry.replay_due(&ResourceKind::Nexus, &uuid) {
+                    continue;
+                }
+                let replayed = guard.handle_incomplete_ops(registry).await;
+                if !registry.replayed(&ResourceKind::Nexus, &uuid, replayed) {
                     // Not all pending operations could be handled.
                     pending_ops = true;
                 }
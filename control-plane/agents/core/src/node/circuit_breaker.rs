@@ -0,0 +1,139 @@
+use common::errors::SvcError;
+use common_lib::types::v0::transport::NodeId;
+use parking_lot::Mutex;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tonic::Code;
+
+/// Configuration of the circuit-breaker of the calls to each node
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CircuitBreakerConfig {
+    /// consecutive failed calls after which the breaker opens, never if None
+    threshold: Option<u32>,
+    /// how long the breaker stays open before a call is let through to probe the node
+    cooldown: Duration,
+}
+
+impl CircuitBreakerConfig {
+    /// new config which opens the breaker after `threshold` consecutive failed calls, if any,
+    /// failing fast the calls for the `cooldown` period
+    pub(crate) fn new(threshold: Option<u32>, cooldown: Duration) -> Self {
+        Self {
+            threshold: threshold.filter(|threshold| *threshold > 0),
+            cooldown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    /// the calls go through, with the number of consecutive failures so far
+    Closed { failures: u32 },
+    /// the calls fail fast until the cooldown since the last failure elapses
+    Open { since: Instant },
+    /// a single call has been let through to probe whether the node has recovered
+    HalfOpen { since: Instant },
+}
+
+/// Circuit-breaker around the calls to a node which, once the configured number of consecutive
+/// calls fail as the node cannot be reached or does not respond in time, fails fast the
+/// following calls rather than having each wait for its timeout.
+/// After the cooldown a single call is let through to probe the node, and the breaker closes
+/// again as soon as the node responds.
+#[derive(Debug, Clone)]
+pub(crate) struct CircuitBreaker {
+    node_id: NodeId,
+    config: CircuitBreakerConfig,
+    state: Arc<Mutex<BreakerState>>,
+}
+
+impl CircuitBreaker {
+    /// new closed circuit-breaker for the node `node_id`
+    pub(crate) fn new(node_id: &NodeId, config: CircuitBreakerConfig) -> Self {
+        Self {
+            node_id: node_id.clone(),
+            config,
+            state: Arc::new(Mutex::new(BreakerState::Closed { failures: 0 })),
+        }
+    }
+
+    /// Check whether a call may go through, failing fast whilst the breaker is open.
+    /// Once the cooldown elapses the call is let through to probe the node.
+    pub(crate) fn check(&self) -> Result<(), SvcError> {
+        let mut state = self.state.lock();
+        match *state {
+            BreakerState::Closed { .. } => Ok(()),
+            BreakerState::Open { since } | BreakerState::HalfOpen { since } => {
+                let elapsed = since.elapsed();
+                if elapsed >= self.config.cooldown {
+                    tracing::info!(node.id = %self.node_id, "Probing the node whose calls were failing fast");
+                    *state = BreakerState::HalfOpen {
+                        since: Instant::now(),
+                    };
+                    Ok(())
+                } else {
+                    Err(SvcError::NodeCircuitOpen {
+                        node: self.node_id.clone(),
+                        retry_in: self.config.cooldown - elapsed,
+                    })
+                }
+            }
+        }
+    }
+
+    /// Record the `result` of a call which went through, returning it.
+    pub(crate) fn record<T>(&self, result: Result<T, SvcError>) -> Result<T, SvcError> {
+        let threshold = match self.config.threshold {
+            Some(threshold) => threshold,
+            None => return result,
+        };
+        let mut state = self.state.lock();
+        match &result {
+            Err(error) if Self::node_failure(error) => {
+                let failures = match *state {
+                    BreakerState::Closed { failures } => failures + 1,
+                    _ => threshold,
+                };
+                if failures < threshold {
+                    *state = BreakerState::Closed { failures };
+                } else {
+                    if let BreakerState::Closed { .. } = *state {
+                        tracing::warn!(
+                            node.id = %self.node_id,
+                            error = %error,
+                            "Failing fast the calls to the node for {:?} after {} consecutive failures",
+                            self.config.cooldown,
+                            failures
+                        );
+                    }
+                    *state = BreakerState::Open {
+                        since: Instant::now(),
+                    };
+                }
+            }
+            // any reply means the node is responding, even if the request itself failed
+            Ok(_) | Err(SvcError::GrpcRequestError { .. }) => {
+                if !matches!(*state, BreakerState::Closed { .. }) {
+                    tracing::info!(node.id = %self.node_id, "The node is responding again");
+                }
+                *state = BreakerState::Closed { failures: 0 };
+            }
+            Err(_) => {}
+        }
+        result
+    }
+
+    /// Whether the `error` is the node failing to respond to a call, rather than its reply.
+    fn node_failure(error: &SvcError) -> bool {
+        match error {
+            SvcError::GrpcConnectTimeout { .. } | SvcError::GrpcConnect { .. } => true,
+            SvcError::GrpcRequestError { source, .. } => matches!(
+                source.code(),
+                Code::DeadlineExceeded | Code::Unavailable | Code::Cancelled
+            ),
+            _ => false,
+        }
+    }
+}
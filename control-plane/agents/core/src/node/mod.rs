@@ -1,11 +1,16 @@
+/// circuit-breaker to fail fast the calls to the nodes which keep failing
+pub(crate) mod circuit_breaker;
 mod registry;
 /// Node Service
 pub(super) mod service;
 mod specs;
+/// request timeouts of each class of dataplane operation
+pub(crate) mod timeouts;
 /// node watchdog to keep track of a node's liveness
 pub(crate) mod watchdog;
 
 use super::{controller::registry::Registry, CliArgs};
+use circuit_breaker::CircuitBreakerConfig;
 use common::Service;
 use common_lib::{
     transport_api::{v0::*, *},
@@ -13,6 +18,7 @@ use common_lib::{
 };
 use grpc::operations::{node::server::NodeServer, registration::server::RegistrationServer};
 use std::sync::Arc;
+use timeouts::ClassTimeouts;
 
 /// Configure the Service and return the builder.
 pub(crate) async fn configure(builder: Service) -> Service {
@@ -29,8 +35,27 @@ async fn create_node_service(builder: &Service) -> service::Service {
     let registry = builder.shared_state::<Registry>().clone();
     let deadline = CliArgs::args().deadline.into();
     let request = CliArgs::args().request_timeout.into();
+    let classes = ClassTimeouts::with_defaults(
+        request,
+        CliArgs::args().fast_request_timeout.map(Into::into),
+        CliArgs::args().medium_request_timeout.map(Into::into),
+        CliArgs::args().slow_request_timeout.map(Into::into),
+    );
     let connect = CliArgs::args().connect_timeout.into();
     let no_min = CliArgs::args().no_min_timeouts;
+    let breaker = CircuitBreakerConfig::new(
+        CliArgs::args().node_failure_threshold,
+        CliArgs::args().node_failure_cooldown.into(),
+    );
 
-    service::Service::new(registry.clone(), deadline, request, connect, no_min).await
+    service::Service::new(
+        registry.clone(),
+        deadline,
+        request,
+        connect,
+        classes,
+        no_min,
+        breaker,
+    )
+    .await
 }
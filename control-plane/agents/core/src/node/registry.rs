@@ -61,6 +61,25 @@ impl Registry {
         }
     }
 
+    /// Remove the node wrapper of the node with the given ID, if any, stopping its watchdog and
+    /// forgetting the round-trip times to and from the node.
+    /// The node must not be online, otherwise it's left as it is.
+    pub(crate) async fn remove_node_wrapper(&self, node_id: &NodeId) -> Result<(), SvcError> {
+        let mut nodes = self.nodes().write().await;
+        if let Some(node) = nodes.get(node_id).cloned() {
+            let mut node = node.write().await;
+            if node.is_online() {
+                return Err(SvcError::NodeOnline {
+                    node: node_id.to_owned(),
+                });
+            }
+            node.watchdog_mut().disarm();
+            nodes.remove(node_id);
+        }
+        self.latencies().lock().remove(node_id);
+        Ok(())
+    }
+
     /// Register new NodeSpec for the given `Register` Request
     pub(super) async fn register_node_spec(&self, request: &Register) {
         if self.config().node_registration().automatic() {
@@ -5,12 +5,16 @@ use crate::controller::{
 };
 use common::errors::SvcError;
 use common_lib::types::v0::transport::{
-    Deregister, Filter, Node, NodeId, NodeState, NodeStatus, Register,
+    Deregister, DiscoverPools, Filter, Node, NodeId, NodeState, NodeStatus, Register,
 };
 
-use crate::controller::wrapper::InternalOps;
+use super::{
+    circuit_breaker::CircuitBreakerConfig,
+    timeouts::{ClassTimeouts, OperationClass},
+};
+use crate::controller::wrapper::{ClientOps, InternalOps};
 use grpc::{
-    context::Context,
+    context::{timeout_grpc, Context},
     operations::{
         node::traits::{GetBlockDeviceInfo, NodeOperations},
         registration::traits::{DeregisterInfo, RegisterInfo, RegistrationOperations},
@@ -26,6 +30,8 @@ pub(crate) struct Service {
     deadline: std::time::Duration,
     /// node communication timeouts
     comms_timeouts: NodeCommsTimeout,
+    /// circuit-breaker configuration of the calls to each node
+    breaker: CircuitBreakerConfig,
 }
 
 /// Node communication Timeouts for establishing the connection to a node and
@@ -34,6 +40,8 @@ pub(crate) struct Service {
 pub(crate) struct NodeCommsTimeout {
     /// timeout options
     opts: TimeoutOptions,
+    /// request timeouts of each class of operation
+    classes: ClassTimeouts,
 }
 
 impl NodeCommsTimeout {
@@ -52,7 +60,15 @@ impl NodeCommsTimeout {
                 TimeoutOptions::default().request_min_timeout().cloned()
             });
 
-        Self { opts }
+        Self {
+            opts,
+            classes: ClassTimeouts::new(request),
+        }
+    }
+    /// return `Self` with the given request timeouts for each class of operation
+    pub(crate) fn with_classes(mut self, classes: ClassTimeouts) -> Self {
+        self.classes = classes;
+        self
     }
     /// timeout to establish connection to the node
     pub(crate) fn connect(&self) -> std::time::Duration {
@@ -62,9 +78,15 @@ impl NodeCommsTimeout {
     pub(crate) fn request(&self) -> std::time::Duration {
         self.opts.base_timeout()
     }
-    /// timeout opts.
-    pub(crate) fn opts(&self) -> &TimeoutOptions {
-        &self.opts
+    /// timeout for the `request`, as per its class of operation
+    /// The request specific minimum timeouts still apply, unless disabled.
+    pub(crate) fn request_for(&self, request: MessageId) -> std::time::Duration {
+        let class = OperationClass::from(&request);
+        let opts = self
+            .opts
+            .clone()
+            .with_req_timeout(self.classes.timeout(class));
+        timeout_grpc(request, opts)
     }
 }
 
@@ -89,8 +111,13 @@ impl NodeOperations for Service {
         Ok(blockdevices)
     }
 
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
-        let node = self.cordon(id, label).await?;
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expiry: Option<std::time::Duration>,
+    ) -> Result<Node, ReplyError> {
+        let node = self.cordon(id, label, expiry).await?;
         Ok(node)
     }
 
@@ -98,6 +125,30 @@ impl NodeOperations for Service {
         let node = self.uncordon(id, label).await?;
         Ok(node)
     }
+
+    async fn put_maintenance(
+        &self,
+        id: NodeId,
+        duration: std::time::Duration,
+    ) -> Result<Node, ReplyError> {
+        let node = self.put_maintenance(id, duration).await?;
+        Ok(node)
+    }
+
+    async fn delete_maintenance(&self, id: NodeId) -> Result<Node, ReplyError> {
+        let node = self.delete_maintenance(id).await?;
+        Ok(node)
+    }
+
+    async fn delete(&self, id: NodeId, force: bool) -> Result<(), ReplyError> {
+        self.delete(id, force).await?;
+        Ok(())
+    }
+
+    async fn discover_pools(&self, id: NodeId) -> Result<DiscoveredPools, ReplyError> {
+        let pools = self.discover_pools(&DiscoverPools { node: id }).await?;
+        Ok(pools)
+    }
 }
 
 #[tonic::async_trait]
@@ -125,12 +176,15 @@ impl Service {
         deadline: std::time::Duration,
         request: std::time::Duration,
         connect: std::time::Duration,
+        classes: ClassTimeouts,
         no_min: bool,
+        breaker: CircuitBreakerConfig,
     ) -> Self {
         let service = Self {
             registry,
             deadline,
-            comms_timeouts: NodeCommsTimeout::new(connect, request, no_min),
+            comms_timeouts: NodeCommsTimeout::new(connect, request, no_min).with_classes(classes),
+            breaker,
         };
         // attempt to reload the node state based on the specification
         for node in service.registry.specs().get_nodes() {
@@ -140,6 +194,7 @@ impl Service {
                         id: node.id().clone(),
                         grpc_endpoint: node.endpoint().to_string(),
                         api_versions: None,
+                        peer_latencies: vec![],
                     },
                     true,
                 )
@@ -160,6 +215,11 @@ impl Service {
         };
 
         if let Some(node) = node {
+            // a node in maintenance may be restarted, so it's not deemed offline meanwhile
+            if registry.specs().node_in_maintenance(id) {
+                tracing::debug!(node.id = %id, "Node in maintenance missed the registration deadline");
+                return;
+            }
             let mut node = node.write().await;
             if node.is_online() {
                 node.update_liveness().await;
@@ -167,8 +227,13 @@ impl Service {
         }
     }
 
-    /// Register a new node through the register information
+    /// Register a new node through the register information, along with the round-trip times
+    /// which the node measured to its peers
     pub(super) async fn register(&self, registration: &Register) {
+        self.registry
+            .latencies()
+            .lock()
+            .update(&registration.id, &registration.peer_latencies);
         self.registry.register_node_spec(registration).await;
         self.register_state(registration, false).await;
     }
@@ -188,8 +253,12 @@ impl Service {
         let node = nodes.write().await.get_mut(&node_state.id).cloned();
         let send_event = match node {
             None => {
-                let mut node =
-                    NodeWrapper::new(&node_state, self.deadline, self.comms_timeouts.clone());
+                let mut node = NodeWrapper::new(
+                    &node_state,
+                    self.deadline,
+                    self.comms_timeouts.clone(),
+                    self.breaker.clone(),
+                );
 
                 // On startup api version is not known, thus probe all apiversions
                 let result = match startup {
@@ -230,13 +299,22 @@ impl Service {
                     }
                 }
             }
-            Some(node) => matches!(node.on_register(node_state).await, Ok(true)),
+            Some(node) => matches!(node.on_register(node_state.clone()).await, Ok(true)),
         };
 
+        if send_event {
+            // the node may report pools or replicas which differ from their specs
+            self.registry.resolve_node_conflicts(node_state.id()).await;
+        }
+
         // don't send these events on startup as the reconciler will start working afterwards anyway
         if send_event && !startup {
+            // the node's resources have just been reloaded, so the volume conditions may be stale
+            self.registry.check_volume_conditions().await;
             self.registry
-                .notify(PollTriggerEvent::NodeStateChangeOnline)
+                .notify(PollTriggerEvent::NodeStateChangeOnline(
+                    node_state.id().clone(),
+                ))
                 .await;
         }
     }
@@ -250,6 +328,9 @@ impl Service {
             // part of the daemonset, but we just don't have that kind of
             // information at this level :(
             // maybe nodes should also be registered/deregistered via REST?
+            Some(_) if self.registry.specs().node_in_maintenance(&node.id) => {
+                tracing::info!(node.id = %node.id, "Node in maintenance has deregistered");
+            }
             Some(node) => {
                 node.write().await.set_status(NodeStatus::Unknown);
             }
@@ -298,6 +379,7 @@ impl Service {
                 }
             }
             _ => Err(SvcError::InvalidFilter {
+                resource: ResourceKind::Node,
                 filter: request.filter().clone(),
             }),
         }
@@ -310,16 +392,21 @@ impl Service {
     ) -> Result<BlockDevices, SvcError> {
         let node = self.registry.get_node_wrapper(&request.node).await?;
 
-        let grpc = node.read().await.grpc_context()?;
+        let grpc = node.read().await.grpc_context_ext(request.id())?;
         let client = grpc.connect().await?;
         client.list_blockdevices(request).await
     }
 
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, SvcError> {
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expiry: Option<std::time::Duration>,
+    ) -> Result<Node, SvcError> {
         let spec = self
             .registry
             .specs()
-            .cordon_node(&self.registry, &id, label)
+            .cordon_node(&self.registry, &id, label, expiry)
             .await?;
         let state = self.registry.get_node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
@@ -334,4 +421,86 @@ impl Service {
         let state = self.registry.get_node_state(&id).await.ok();
         Ok(Node::new(id, Some(spec), state))
     }
+
+    async fn put_maintenance(
+        &self,
+        id: NodeId,
+        duration: std::time::Duration,
+    ) -> Result<Node, SvcError> {
+        if duration.is_zero() {
+            return Err(SvcError::InvalidArguments {});
+        }
+        let spec = self
+            .registry
+            .specs()
+            .put_node_maintenance(&self.registry, &id, duration)
+            .await?;
+        let state = self.registry.get_node_state(&id).await.ok();
+        Ok(Node::new(id, Some(spec), state))
+    }
+
+    async fn delete_maintenance(&self, id: NodeId) -> Result<Node, SvcError> {
+        let spec = self
+            .registry
+            .specs()
+            .end_node_maintenance(&self.registry, &id)
+            .await?;
+        let state = self.registry.get_node_state(&id).await.ok();
+        Ok(Node::new(id, Some(spec), state))
+    }
+
+    /// Delete the node with the given ID, which must not be online, along with its spec.
+    /// Unless `force`d, the node must not host any resource with a spec, whereas a forced deletion
+    /// disowns them instead.
+    /// Should the node register again, it's then registered as a brand new node.
+    async fn delete(&self, id: NodeId, force: bool) -> Result<(), SvcError> {
+        let node = self.registry.nodes().read().await.get(&id).cloned();
+        match node {
+            None if self.specs().get_node(&id).is_err() => {
+                return Err(SvcError::NodeNotFound { node_id: id });
+            }
+            Some(node) if node.read().await.is_online() => {
+                return Err(SvcError::NodeOnline { node: id });
+            }
+            _ => {}
+        }
+
+        let resources = self.specs().get_node_resources(&id);
+        if !resources.is_empty() {
+            if !force {
+                return Err(SvcError::NodeInUse {
+                    node: id,
+                    blockers: resources.describe(),
+                });
+            }
+            self.specs()
+                .disown_node_resources(&self.registry, &resources)
+                .await?;
+            tracing::warn!(
+                audit = true,
+                node.id = %id,
+                resources = %resources.describe().join(", "),
+                "Forcibly deleting the node, its resources were disowned"
+            );
+        }
+
+        self.registry.remove_node_wrapper(&id).await?;
+        self.specs().delete_node_spec(&self.registry, &id).await?;
+        tracing::info!(node.id = %id, force, "Node deleted");
+        Ok(())
+    }
+
+    /// Discover the pools which could be imported from the disks of a node, flagging those which
+    /// the control plane already has a spec for.
+    pub(crate) async fn discover_pools(
+        &self,
+        request: &DiscoverPools,
+    ) -> Result<DiscoveredPools, SvcError> {
+        let node = self.registry.get_node_wrapper(&request.node).await?;
+        let mut pools = node.discover_pools(request).await?;
+        for pool in &mut pools {
+            pool.spec_exists = self.specs().get_pool(&pool.id).is_ok();
+        }
+        Ok(DiscoveredPools(pools))
+    }
 }
@@ -2,12 +2,14 @@ use crate::controller::{registry::Registry, specs::ResourceSpecsLocked};
 use common::errors::{NodeNotFound, SvcError};
 use common_lib::types::v0::{
     store::{
+        definitions::{ObjectKey, StorableObject},
         node::{NodeLabels, NodeSpec},
-        ResourceMutex,
+        ResourceMutex, SpecTimestamps,
     },
-    transport::{NodeId, Register},
+    transport::{NexusId, NodeId, PoolId, Register, ReplicaId},
 };
 use snafu::OptionExt;
+use std::time::{Duration, SystemTime};
 
 impl ResourceSpecsLocked {
     /// Create a node spec for the register request
@@ -24,15 +26,19 @@ impl ResourceSpecsLocked {
                     let changed = node_spec.endpoint() != node.grpc_endpoint;
 
                     node_spec.set_endpoint(node.grpc_endpoint.clone());
+                    if changed {
+                        node_spec.stamp_updated(SystemTime::now());
+                    }
                     (changed, node_spec.clone())
                 }
                 None => {
-                    let node = NodeSpec::new(
+                    let mut node = NodeSpec::new(
                         node.id.clone(),
                         node.grpc_endpoint.clone(),
                         NodeLabels::new(),
                         None,
                     );
+                    node.stamp_created(SystemTime::now());
                     specs.nodes.insert(node.clone());
                     (true, node)
                 }
@@ -76,13 +82,14 @@ impl ResourceSpecsLocked {
             .collect()
     }
 
-    /// Cordon the node with the given ID.
+    /// Cordon the node with the given ID, until the `expiry` elapses, if any.
     /// Return the NodeSpec after cordoning.
     pub(crate) async fn cordon_node(
         &self,
         registry: &Registry,
         node_id: &NodeId,
         label: String,
+        expiry: Option<Duration>,
     ) -> Result<NodeSpec, SvcError> {
         let node = self.get_locked_node(node_id)?;
         let cordoned_node_spec = {
@@ -94,7 +101,9 @@ impl ResourceSpecsLocked {
                     label,
                 });
             }
-            locked_node.cordon(label);
+            let now = SystemTime::now();
+            locked_node.cordon(label, expiry.map(|expiry| now + expiry));
+            locked_node.stamp_updated(now);
             locked_node.clone()
         };
         registry.store_obj(&cordoned_node_spec).await?;
@@ -120,12 +129,189 @@ impl ResourceSpecsLocked {
         let uncordoned_node_spec = {
             let mut locked_node = node.lock();
             locked_node.uncordon(label);
+            locked_node.stamp_updated(SystemTime::now());
             locked_node.clone()
         };
         registry.store_obj(&uncordoned_node_spec).await?;
         Ok(uncordoned_node_spec.clone())
     }
 
+    /// Uncordon the cordon labels of the node with the given ID which have expired.
+    /// Return the NodeSpec after uncordoning, if any label had expired.
+    pub(crate) async fn uncordon_expired(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+    ) -> Result<Option<NodeSpec>, SvcError> {
+        let node = self.get_locked_node(node_id)?;
+        let uncordoned_node_spec = {
+            let mut locked_node = node.lock();
+            let now = SystemTime::now();
+            let expired = locked_node.expired_cordon_labels(now);
+            if expired.is_empty() {
+                return Ok(None);
+            }
+            for label in expired {
+                tracing::info!(node.id = %node_id, label = %label, "Cordon label has expired");
+                locked_node.uncordon(label);
+            }
+            locked_node.stamp_updated(now);
+            locked_node.clone()
+        };
+        registry.store_obj(&uncordoned_node_spec).await?;
+        Ok(Some(uncordoned_node_spec))
+    }
+
+    /// Put the node with the given ID in maintenance for at most the given `duration`.
+    /// Return the NodeSpec after putting it in maintenance.
+    pub(crate) async fn put_node_maintenance(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+        duration: Duration,
+    ) -> Result<NodeSpec, SvcError> {
+        let node = self.get_locked_node(node_id)?;
+        let node_spec = {
+            let mut locked_node = node.lock();
+            let now = SystemTime::now();
+            locked_node.set_maintenance(now + duration);
+            locked_node.stamp_updated(now);
+            locked_node.clone()
+        };
+        registry.store_obj(&node_spec).await?;
+        tracing::info!(node.id = %node_id, ?duration, "Node put in maintenance");
+        Ok(node_spec)
+    }
+
+    /// Take the node with the given ID out of maintenance, which is a no-op if it's not in
+    /// maintenance. Return the NodeSpec after ending the maintenance.
+    pub(crate) async fn end_node_maintenance(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+    ) -> Result<NodeSpec, SvcError> {
+        let node = self.get_locked_node(node_id)?;
+        let node_spec = {
+            let mut locked_node = node.lock();
+            if locked_node.maintenance_until().is_none() {
+                return Ok(locked_node.clone());
+            }
+            locked_node.end_maintenance();
+            locked_node.stamp_updated(SystemTime::now());
+            locked_node.clone()
+        };
+        registry.store_obj(&node_spec).await?;
+        tracing::info!(node.id = %node_id, "Node taken out of maintenance");
+        Ok(node_spec)
+    }
+
+    /// End the maintenance of the node with the given ID if its maintenance window has expired.
+    /// Return the NodeSpec after ending the maintenance, if it had expired.
+    pub(crate) async fn end_expired_maintenance(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+    ) -> Result<Option<NodeSpec>, SvcError> {
+        let node = self.get_locked_node(node_id)?;
+        let node_spec = {
+            let mut locked_node = node.lock();
+            let now = SystemTime::now();
+            if locked_node.maintenance_until().is_none() || locked_node.in_maintenance(now) {
+                return Ok(None);
+            }
+            locked_node.end_maintenance();
+            locked_node.stamp_updated(now);
+            locked_node.clone()
+        };
+        registry.store_obj(&node_spec).await?;
+        Ok(Some(node_spec))
+    }
+
+    /// Check whether the node with the given ID is within its maintenance window.
+    pub(crate) fn node_in_maintenance(&self, node_id: &NodeId) -> bool {
+        match self.get_locked_node(node_id) {
+            Ok(node) => node.lock().in_maintenance(SystemTime::now()),
+            Err(_) => false,
+        }
+    }
+
+    /// Get the pools, replicas and nexuses with a spec which are hosted by the node with the
+    /// given ID.
+    pub(crate) fn get_node_resources(&self, node_id: &NodeId) -> NodeResources {
+        let specs = self.read();
+        let pools = specs
+            .pools
+            .values()
+            .filter(|pool| &pool.lock().node == node_id)
+            .map(|pool| pool.lock().id.clone())
+            .collect::<Vec<_>>();
+        let replicas = specs
+            .replicas
+            .values()
+            .filter(|replica| pools.contains(&replica.lock().pool))
+            .map(|replica| replica.lock().uuid.clone())
+            .collect();
+        let nexuses = specs
+            .nexuses
+            .values()
+            .filter(|nexus| &nexus.lock().node == node_id)
+            .map(|nexus| nexus.lock().uuid.clone())
+            .collect();
+        NodeResources {
+            pools,
+            replicas,
+            nexuses,
+        }
+    }
+
+    /// Disown the replicas and nexuses of a node which is being deleted, so the volumes replace
+    /// them and the garbage collector destroys them should the node ever return.
+    /// The pools keep their specs as they have no owners.
+    pub(crate) async fn disown_node_resources(
+        &self,
+        registry: &Registry,
+        resources: &NodeResources,
+    ) -> Result<(), SvcError> {
+        for nexus in &resources.nexuses {
+            if let Some(nexus) = self.nexus_opt(nexus).await? {
+                if nexus.lock().owner.is_some() {
+                    self.disown_nexus(registry, &nexus).await?;
+                }
+            }
+        }
+        for replica in &resources.replicas {
+            if let Some(replica) = self.replica_opt(replica).await? {
+                let replica_spec = {
+                    let mut replica_spec = replica.lock();
+                    if !replica_spec.owned() {
+                        continue;
+                    }
+                    replica_spec.owners.disown_all();
+                    replica_spec.clone()
+                };
+                registry.store_obj(&replica_spec).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete the spec of the node with the given ID, if it has one, from the persistent store
+    /// and the registry.
+    pub(crate) async fn delete_node_spec(
+        &self,
+        registry: &Registry,
+        node_id: &NodeId,
+    ) -> Result<(), SvcError> {
+        let node = match self.get_locked_node(node_id) {
+            Ok(node) => node,
+            Err(_) => return Ok(()),
+        };
+        let key = node.lock().key();
+        registry.delete_kv(&key.key()).await?;
+        self.write().nodes.remove(node_id);
+        Ok(())
+    }
+
     /// Get all cordoned nodes.
     pub(crate) fn get_cordoned_nodes(&self) -> Vec<NodeSpec> {
         self.read()
@@ -142,3 +328,25 @@ impl ResourceSpecsLocked {
             .collect()
     }
 }
+
+/// The resources with a spec which are hosted by a node.
+#[derive(Debug, Default)]
+pub(crate) struct NodeResources {
+    pools: Vec<PoolId>,
+    replicas: Vec<ReplicaId>,
+    nexuses: Vec<NexusId>,
+}
+
+impl NodeResources {
+    /// Whether the node hosts no resources.
+    pub(crate) fn is_empty(&self) -> bool {
+        self.pools.is_empty() && self.replicas.is_empty() && self.nexuses.is_empty()
+    }
+    /// Describe each resource by its kind and id, eg: `pool 'p0'`.
+    pub(crate) fn describe(&self) -> Vec<String> {
+        let pools = self.pools.iter().map(|id| format!("pool '{}'", id));
+        let replicas = self.replicas.iter().map(|id| format!("replica '{}'", id));
+        let nexuses = self.nexuses.iter().map(|id| format!("nexus '{}'", id));
+        pools.chain(replicas).chain(nexuses).collect()
+    }
+}
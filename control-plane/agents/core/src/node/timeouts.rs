@@ -0,0 +1,158 @@
+use common::errors::SvcError;
+use common_lib::{transport_api::MessageId, types::v0::transport::MessageIdVs};
+use std::time::Duration;
+use tonic::Code;
+
+/// Class of a dataplane operation, as per how long it's expected to take, which determines the
+/// timeout of its requests
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum OperationClass {
+    /// lists and gets, eg: list the replicas
+    Fast,
+    /// operations on existing resources, eg: share a replica or add a child to a nexus
+    Medium,
+    /// creation and destruction of resources, eg: create a pool on a large disk
+    Slow,
+}
+
+impl std::fmt::Display for OperationClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Fast => "fast",
+            Self::Medium => "medium",
+            Self::Slow => "slow",
+        })
+    }
+}
+
+impl From<&MessageId> for OperationClass {
+    fn from(request: &MessageId) -> Self {
+        match request {
+            MessageId::v0(id) => match id {
+                MessageIdVs::Default
+                | MessageIdVs::Liveness
+                | MessageIdVs::GetNodes
+                | MessageIdVs::GetPools
+                | MessageIdVs::GetReplicas
+                | MessageIdVs::GetNexuses
+                | MessageIdVs::GetBlockDevices
+                | MessageIdVs::GetSpecs
+                | MessageIdVs::GetStates => Self::Fast,
+
+                MessageIdVs::CreatePool
+                | MessageIdVs::DestroyPool
+                | MessageIdVs::CreateReplica
+                | MessageIdVs::DestroyReplica
+                | MessageIdVs::CreateNexus
+                | MessageIdVs::DestroyNexus => Self::Slow,
+
+                _ => Self::Medium,
+            },
+        }
+    }
+}
+
+/// Whether the `error` is a dataplane request which timed out
+pub(crate) fn timed_out(error: &SvcError) -> bool {
+    match error {
+        SvcError::GrpcRequestError { source, .. } => {
+            matches!(source.code(), Code::DeadlineExceeded | Code::Cancelled)
+        }
+        _ => false,
+    }
+}
+
+/// Request timeouts of each `OperationClass`
+#[derive(Debug, Clone)]
+pub(crate) struct ClassTimeouts {
+    fast: Duration,
+    medium: Duration,
+    slow: Duration,
+}
+
+impl ClassTimeouts {
+    /// Use the same `timeout` for all classes
+    pub(crate) fn new(timeout: Duration) -> Self {
+        Self {
+            fast: timeout,
+            medium: timeout,
+            slow: timeout,
+        }
+    }
+    /// Use the given timeouts for the classes which have one, and `timeout` for the others
+    pub(crate) fn with_defaults(
+        timeout: Duration,
+        fast: Option<Duration>,
+        medium: Option<Duration>,
+        slow: Option<Duration>,
+    ) -> Self {
+        Self {
+            fast: fast.unwrap_or(timeout),
+            medium: medium.unwrap_or(timeout),
+            slow: slow.unwrap_or(timeout),
+        }
+    }
+    /// The request timeout of the given `class`
+    pub(crate) fn timeout(&self, class: OperationClass) -> Duration {
+        match class {
+            OperationClass::Fast => self.fast,
+            OperationClass::Medium => self.medium,
+            OperationClass::Slow => self.slow,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn class(id: &MessageIdVs) -> OperationClass {
+        OperationClass::from(&MessageId::from(id.clone()))
+    }
+
+    #[test]
+    fn operation_class() {
+        for id in &[
+            MessageIdVs::Default,
+            MessageIdVs::GetPools,
+            MessageIdVs::GetReplicas,
+            MessageIdVs::GetNexuses,
+            MessageIdVs::GetBlockDevices,
+        ] {
+            assert_eq!(class(id), OperationClass::Fast, "{:?}", id);
+        }
+        for id in &[
+            MessageIdVs::ShareReplica,
+            MessageIdVs::UnshareReplica,
+            MessageIdVs::ShareNexus,
+            MessageIdVs::UnshareNexus,
+            MessageIdVs::AddNexusChild,
+            MessageIdVs::RemoveNexusChild,
+        ] {
+            assert_eq!(class(id), OperationClass::Medium, "{:?}", id);
+        }
+        for id in &[
+            MessageIdVs::CreatePool,
+            MessageIdVs::DestroyPool,
+            MessageIdVs::CreateReplica,
+            MessageIdVs::DestroyReplica,
+            MessageIdVs::CreateNexus,
+            MessageIdVs::DestroyNexus,
+        ] {
+            assert_eq!(class(id), OperationClass::Slow, "{:?}", id);
+        }
+    }
+
+    #[test]
+    fn class_timeouts() {
+        let timeout = Duration::from_secs(5);
+        let timeouts =
+            ClassTimeouts::with_defaults(timeout, Some(Duration::from_secs(1)), None, None);
+        assert_eq!(
+            timeouts.timeout(OperationClass::Fast),
+            Duration::from_secs(1)
+        );
+        assert_eq!(timeouts.timeout(OperationClass::Medium), timeout);
+        assert_eq!(timeouts.timeout(OperationClass::Slow), timeout);
+    }
+}
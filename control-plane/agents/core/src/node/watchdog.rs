@@ -1,37 +1,135 @@
 use crate::node::service::Service;
 use common_lib::types::v0::transport::NodeId;
+use std::{
+    sync::Arc,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// Source of the time used by the watchdog, which may be replaced to simulate clock jumps
+pub(crate) trait WatchdogClock: std::fmt::Debug + Send + Sync {
+    /// the monotonic time, used for the deadline math
+    fn monotonic(&self) -> Instant;
+    /// the wall-clock time, only used for display and to detect clock jumps
+    fn wall_clock(&self) -> SystemTime;
+}
+
+/// The system's monotonic and wall clocks
+#[derive(Debug, Default)]
+pub(crate) struct SystemClock;
+
+impl WatchdogClock for SystemClock {
+    fn monotonic(&self) -> Instant {
+        Instant::now()
+    }
+    fn wall_clock(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// Reading of both the monotonic and the wall clocks, taken at the same time
+#[derive(Debug, Clone, Copy)]
+struct ClockReading {
+    monotonic: Instant,
+    wall_clock: SystemTime,
+}
+
+impl ClockReading {
+    fn new(clock: &dyn WatchdogClock) -> Self {
+        Self {
+            monotonic: clock.monotonic(),
+            wall_clock: clock.wall_clock(),
+        }
+    }
+    /// How far the wall clock drifted from the monotonic clock since the `earlier` reading,
+    /// whichever the direction
+    fn divergence(&self, earlier: &Self) -> Duration {
+        let monotonic = self.monotonic.saturating_duration_since(earlier.monotonic);
+        match self.wall_clock.duration_since(earlier.wall_clock) {
+            Ok(wall_clock) if wall_clock >= monotonic => wall_clock - monotonic,
+            Ok(wall_clock) => monotonic - wall_clock,
+            Err(backwards) => monotonic + backwards.duration(),
+        }
+    }
+}
 
 /// Watchdog which must be pet within the deadline, otherwise
 /// it triggers the `on_timeout` callback from the node `Service`
+/// The deadline is measured with the monotonic clock, so it's not affected by the wall clock
+/// being stepped, eg: by NTP. However, if the two clocks diverge by more than the deadline, eg: as
+/// the monotonic clock stood still whilst the host was suspended or the VM paused, the node may
+/// not have had a chance to re-register, so it's given a grace period of another deadline.
 #[derive(Debug, Clone)]
 pub(crate) struct Watchdog {
     node_id: NodeId,
-    deadline: std::time::Duration,
-    timestamp: std::time::Instant,
+    deadline: Duration,
+    clock: Arc<dyn WatchdogClock>,
+    /// when the node was last seen
+    seen: ClockReading,
+    /// latest reading of the clocks, to detect their divergence
+    reading: ClockReading,
+    /// the deadline is not enforced until then, following a clock jump
+    grace_until: Option<Instant>,
     pet_chan: Option<tokio::sync::mpsc::Sender<()>>,
     service: Option<Service>,
 }
 
 impl Watchdog {
     /// new empty watchdog with a deadline timeout for node `node_id`
-    pub(crate) fn new(node_id: &NodeId, deadline: std::time::Duration) -> Self {
+    pub(crate) fn new(node_id: &NodeId, deadline: Duration) -> Self {
+        Self::new_with_clock(node_id, deadline, Arc::new(SystemClock))
+    }
+
+    /// new empty watchdog with a deadline timeout for node `node_id`, timed by `clock`
+    fn new_with_clock(node_id: &NodeId, deadline: Duration, clock: Arc<dyn WatchdogClock>) -> Self {
+        let reading = ClockReading::new(clock.as_ref());
         Self {
             deadline,
             node_id: node_id.clone(),
-            timestamp: std::time::Instant::now(),
+            clock,
+            seen: reading,
+            reading,
+            grace_until: None,
             pet_chan: None,
             service: None,
         }
     }
 
     /// the set deadline
-    pub(crate) fn deadline(&self) -> std::time::Duration {
+    pub(crate) fn deadline(&self) -> Duration {
         self.deadline
     }
 
-    /// last time the node was seen
-    pub(crate) fn timestamp(&self) -> std::time::Instant {
-        self.timestamp
+    /// last time the node was seen, as per the wall clock
+    pub(crate) fn last_seen(&self) -> SystemTime {
+        self.seen.wall_clock
+    }
+
+    /// Whether the deadline has expired since the node was last seen, unless the node is within
+    /// the grace period granted when the clocks jump.
+    pub(crate) fn expired(&mut self) -> bool {
+        let now = self.read_clocks();
+        match self.grace_until {
+            Some(until) if now.monotonic < until => false,
+            _ => now.monotonic.saturating_duration_since(self.seen.monotonic) > self.deadline,
+        }
+    }
+
+    /// Read the clocks, granting the node a grace period if they diverged by more than the
+    /// deadline since they were last read.
+    fn read_clocks(&mut self) -> ClockReading {
+        let now = ClockReading::new(self.clock.as_ref());
+        let divergence = now.divergence(&self.reading);
+        if divergence > self.deadline {
+            tracing::warn!(
+                node.id = %self.node_id,
+                "Clocks diverged by {:?}, eg: the host was suspended, granting {:?} to re-register",
+                divergence,
+                self.deadline
+            );
+            self.grace_until = Some(now.monotonic + self.deadline);
+        }
+        self.reading = now;
+        now
     }
 
     /// arm watchdog with self timeout and execute error callback if
@@ -60,7 +158,8 @@ impl Watchdog {
 
     /// meet the deadline
     pub(crate) async fn pet(&mut self) -> Result<(), tokio::sync::mpsc::error::SendError<()>> {
-        self.timestamp = std::time::Instant::now();
+        self.seen = self.read_clocks();
+        self.grace_until = None;
         if let Some(chan) = &mut self.pet_chan {
             chan.send(()).await
         } else {
@@ -77,3 +176,146 @@ impl Watchdog {
         let _ = self.pet_chan.take();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use parking_lot::Mutex;
+
+    const DEADLINE: Duration = Duration::from_secs(10);
+
+    /// Clock which only moves when told to, each clock independently of the other
+    #[derive(Debug)]
+    struct TestClock {
+        now: Mutex<ClockReading>,
+    }
+    impl TestClock {
+        fn new() -> Arc<Self> {
+            Arc::new(Self {
+                now: Mutex::new(ClockReading::new(&SystemClock)),
+            })
+        }
+        /// time passes, as per both clocks
+        fn advance(&self, secs: u64) {
+            let mut now = self.now.lock();
+            now.monotonic += Duration::from_secs(secs);
+            now.wall_clock += Duration::from_secs(secs);
+        }
+        /// the wall clock is stepped forward or backwards, eg: by NTP
+        fn step_wall_clock(&self, secs: i64) {
+            let mut now = self.now.lock();
+            let step = Duration::from_secs(secs.unsigned_abs());
+            if secs < 0 {
+                now.wall_clock -= step;
+            } else {
+                now.wall_clock += step;
+            }
+        }
+    }
+    impl WatchdogClock for TestClock {
+        fn monotonic(&self) -> Instant {
+            self.now.lock().monotonic
+        }
+        fn wall_clock(&self) -> SystemTime {
+            self.now.lock().wall_clock
+        }
+    }
+
+    fn watchdog(clock: &Arc<TestClock>) -> Watchdog {
+        Watchdog::new_with_clock(&NodeId::from("node"), DEADLINE, clock.clone())
+    }
+
+    /// The node is pet every 2s and then stops being pet: it must not expire before the deadline,
+    /// and it must expire by the time `expires_by` more seconds elapse.
+    async fn check_liveness(
+        clock: &TestClock,
+        watchdog: &mut Watchdog,
+        mut step: impl FnMut(u64),
+        expires_by: u64,
+    ) {
+        for second in 0 .. 30 {
+            clock.advance(1);
+            step(second);
+            assert!(!watchdog.expired(), "Spurious expiry at {}s", second);
+            if second % 2 == 0 {
+                watchdog.pet().await.unwrap();
+            }
+        }
+        let mut missed = 0;
+        while !watchdog.expired() {
+            clock.advance(1);
+            missed += 1;
+            assert!(missed <= expires_by, "Real failure masked");
+        }
+        assert!(missed > DEADLINE.as_secs() - 1);
+    }
+
+    #[tokio::test]
+    async fn deadline() {
+        let clock = TestClock::new();
+        let mut watchdog = watchdog(&clock);
+        check_liveness(&clock, &mut watchdog, |_| {}, DEADLINE.as_secs() + 1).await;
+    }
+
+    #[tokio::test]
+    async fn ntp_step_forward() {
+        let clock = TestClock::new();
+        let mut watchdog = watchdog(&clock);
+        let step = |second: u64| {
+            if second == 15 {
+                clock.step_wall_clock(3600);
+            }
+        };
+        check_liveness(&clock, &mut watchdog, step, DEADLINE.as_secs() + 1).await;
+
+        // a step whilst the node is not being pet delays its expiry by no more than a deadline
+        clock.step_wall_clock(3600);
+        assert!(!watchdog.expired());
+        clock.advance(DEADLINE.as_secs());
+        assert!(watchdog.expired());
+    }
+
+    #[tokio::test]
+    async fn ntp_step_backwards() {
+        let clock = TestClock::new();
+        let mut watchdog = watchdog(&clock);
+        let step = |second: u64| {
+            if second == 15 {
+                clock.step_wall_clock(-3600);
+            }
+        };
+        check_liveness(&clock, &mut watchdog, step, DEADLINE.as_secs() + 1).await;
+
+        // the time the node was last seen is displayed as per the stepped wall clock
+        let elapsed = clock.monotonic() - watchdog.seen.monotonic;
+        assert_eq!(watchdog.last_seen() + elapsed, clock.wall_clock());
+    }
+
+    #[tokio::test]
+    async fn vm_paused() {
+        let clock = TestClock::new();
+        let mut watchdog = watchdog(&clock);
+        watchdog.pet().await.unwrap();
+        clock.advance(DEADLINE.as_secs() - 1);
+
+        // the monotonic clock stands still whilst the VM is paused, though the wall clock is
+        // caught up on resume, so the node had no chance to re-register in time
+        clock.step_wall_clock(600);
+        clock.advance(2);
+        assert!(!watchdog.expired(), "Spurious expiry on resume");
+
+        // the node is given another deadline to re-register
+        clock.advance(DEADLINE.as_secs() - 3);
+        assert!(!watchdog.expired());
+        watchdog.pet().await.unwrap();
+        clock.advance(DEADLINE.as_secs());
+        assert!(!watchdog.expired());
+
+        // but it's not spared if it doesn't re-register within the grace period
+        clock.step_wall_clock(600);
+        clock.advance(1);
+        assert!(!watchdog.expired());
+        clock.advance(DEADLINE.as_secs());
+        assert!(watchdog.expired());
+    }
+}
@@ -7,6 +7,8 @@ mod specs;
 use super::controller::registry::Registry;
 use std::sync::Arc;
 
+pub(crate) use replica_operations::create_staged_replica;
+
 use common::Service;
 use grpc::operations::{pool::server::PoolServer, replica::server::ReplicaServer};
 
@@ -5,9 +5,12 @@ use crate::controller::{
     wrapper::ClientOps,
 };
 use common::errors::{SvcError, SvcError::CordonedNode};
-use common_lib::types::v0::{
-    store::{pool::PoolSpec, OperationGuardArc},
-    transport::{CreatePool, DestroyPool, Pool},
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{pool::PoolSpec, OperationGuardArc},
+        transport::{CreatePool, DestroyPool, Pool},
+    },
 };
 
 #[async_trait::async_trait]
@@ -28,7 +31,11 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
             });
         }
 
+        specs.check_tombstone(registry, ResourceKind::Pool, request.id.as_str())?;
+        registry.check_pool_conflict(&request.id)?;
+
         let node = registry.get_node_wrapper(&request.node).await?;
+        registry.store_available()?;
         let pool = specs
             .get_or_create_pool(request)
             .operation_guard_wait()
@@ -39,7 +46,9 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
 
         let pool_state = pool.complete_create(result, registry).await?;
         let spec = pool.lock().clone();
-        Ok(Pool::new(spec, pool_state))
+        Ok(registry
+            .with_pools_conditions(vec![Pool::new(spec, pool_state)])
+            .remove(0))
     }
 
     async fn destroy(
@@ -47,6 +56,7 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
         registry: &Registry,
         request: &Self::Destroy,
     ) -> Result<(), SvcError> {
+        registry.check_pool_conflict(&request.id)?;
         // what if the node is never coming back?
         // do we need a way to forcefully "delete" things?
         let node = registry.get_node_wrapper(&request.node).await?;
@@ -54,7 +64,12 @@ impl ResourceLifecycle for OperationGuardArc<PoolSpec> {
         self.start_destroy(registry).await?;
 
         let result = node.destroy_pool(request).await;
-        self.complete_destroy(result, registry).await
+        self.complete_destroy(result, registry).await?;
+        registry
+            .specs()
+            .add_tombstone(registry, ResourceKind::Pool, request.id.as_str())
+            .await;
+        Ok(())
     }
 }
 
@@ -1,10 +1,17 @@
 use crate::controller::{
+    conditions::pool_conditions,
+    conflicts::ConflictPolicy,
     registry::Registry,
+    specs::OperationSequenceGuard,
     wrapper::{GetterOps, *},
 };
 use common::errors::{self, SvcError, SvcError::PoolNotFound};
-use common_lib::types::v0::transport::{NodeId, Pool, PoolId, PoolState, Replica, ReplicaId};
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::transport::{NodeId, Pool, PoolId, PoolState, Replica, ReplicaId},
+};
 use snafu::OptionExt;
+use std::{collections::HashSet, time::SystemTime};
 
 /// Pool helpers
 impl Registry {
@@ -33,7 +40,7 @@ impl Registry {
                     .collect::<Vec<_>>();
 
                 pools.extend(pools_from_spec);
-                Ok(pools)
+                Ok(self.with_pools_conditions(pools))
             }
             Some(node_id) => {
                 let mut pools = vec![];
@@ -59,11 +66,39 @@ impl Registry {
                     .collect::<Vec<_>>();
 
                 pools.extend(pools_from_spec);
-                Ok(pools)
+                Ok(self.with_pools_conditions(pools))
             }
         }
     }
 
+    /// Add the conditions of each of the `pools` to it.
+    pub(crate) fn with_pools_conditions(&self, pools: Vec<Pool>) -> Vec<Pool> {
+        let now = SystemTime::now();
+        let mut transitions = self.pool_transitions().lock();
+        pools
+            .into_iter()
+            .map(|pool| {
+                let conditions = pool_conditions(pool.state().as_ref(), now);
+                let conditions = transitions.observe(pool.id(), conditions);
+                pool.with_conditions(conditions)
+            })
+            .collect()
+    }
+
+    /// Observe the conditions of all the pools, so the time of their last transition is kept up
+    /// to date even when they're not queried, and forget those of the deleted pools.
+    pub(crate) async fn refresh_pool_transitions(&self) {
+        if let Ok(pools) = self.get_node_opt_pools(None).await {
+            let pools = pools
+                .iter()
+                .map(|pool| pool.id().clone())
+                .collect::<HashSet<_>>();
+            self.pool_transitions()
+                .lock()
+                .retain(|pool| pools.contains(pool));
+        }
+    }
+
     /// Get pool wrappers for the pool ID.
     pub(crate) async fn get_node_pool_wrapper(
         &self,
@@ -120,13 +155,14 @@ impl Registry {
 
     /// Get the pool object corresponding to the id.
     pub(crate) async fn get_pool(&self, id: &PoolId) -> Result<Pool, SvcError> {
-        Pool::try_new(
+        let pool = Pool::try_new(
             self.specs().get_pool(id).ok(),
             self.get_pool_state(id).await.ok(),
         )
         .ok_or(PoolNotFound {
             pool_id: id.to_owned(),
-        })
+        })?;
+        Ok(self.with_pools_conditions(vec![pool]).remove(0))
     }
 }
 
@@ -164,3 +200,118 @@ impl Registry {
         Ok(node.replicas().await)
     }
 }
+
+/// Conflict helpers
+impl Registry {
+    /// Compare the pools and replicas reported by the node `node_id` against their specs and
+    /// resolve any differences according to the configured `ConflictPolicy`.
+    pub(crate) async fn resolve_node_conflicts(&self, node_id: &NodeId) {
+        self.conflicts().lock().clear_node(node_id);
+        let policy = self.conflict_policy();
+
+        for pool in self.get_node_pools(node_id).await.unwrap_or_default() {
+            let spec = match self.specs().get_locked_pool(&pool.id) {
+                Some(spec) => spec,
+                None => continue,
+            };
+            let spec_node = spec.lock().node.clone();
+            if spec_node == pool.node {
+                continue;
+            }
+            tracing::warn!(
+                pool.uuid = %pool.id,
+                spec.node = %spec_node,
+                state.node = %pool.node,
+                policy = ?policy,
+                "Pool reported by a node other than the one in its spec"
+            );
+            match policy {
+                ConflictPolicy::PreferStore => {}
+                ConflictPolicy::PreferNode => {
+                    let guard = match spec.operation_guard() {
+                        Ok(guard) => guard,
+                        Err(error) => {
+                            tracing::error!(pool.uuid = %pool.id, error = %error, "Failed to update the conflicting pool spec");
+                            continue;
+                        }
+                    };
+                    let mut spec_clone = guard.lock().clone();
+                    spec_clone.node = pool.node.clone();
+                    match self.store_obj(&spec_clone).await {
+                        Ok(_) => guard.lock().node = pool.node.clone(),
+                        Err(error) => {
+                            tracing::error!(pool.uuid = %pool.id, error = %error, "Failed to persist the conflicting pool spec");
+                        }
+                    }
+                }
+                ConflictPolicy::Manual => self.conflicts().lock().flag_pool(&pool.id, node_id),
+            }
+        }
+
+        for replica in self.get_node_replicas(node_id).await.unwrap_or_default() {
+            let spec = match self.specs().get_replica(&replica.uuid) {
+                Some(spec) => spec,
+                None => continue,
+            };
+            let spec_pool = spec.lock().pool.clone();
+            if spec_pool == replica.pool {
+                continue;
+            }
+            tracing::warn!(
+                replica.uuid = %replica.uuid,
+                spec.pool = %spec_pool,
+                state.pool = %replica.pool,
+                node.id = %node_id,
+                policy = ?policy,
+                "Replica reported on a pool other than the one in its spec"
+            );
+            match policy {
+                ConflictPolicy::PreferStore => {}
+                ConflictPolicy::PreferNode => {
+                    let guard = match spec.operation_guard() {
+                        Ok(guard) => guard,
+                        Err(error) => {
+                            tracing::error!(replica.uuid = %replica.uuid, error = %error, "Failed to update the conflicting replica spec");
+                            continue;
+                        }
+                    };
+                    let mut spec_clone = guard.lock().clone();
+                    spec_clone.pool = replica.pool.clone();
+                    match self.store_obj(&spec_clone).await {
+                        Ok(_) => guard.lock().pool = replica.pool.clone(),
+                        Err(error) => {
+                            tracing::error!(replica.uuid = %replica.uuid, error = %error, "Failed to persist the conflicting replica spec");
+                        }
+                    }
+                }
+                ConflictPolicy::Manual => {
+                    self.conflicts().lock().flag_replica(&replica.uuid, node_id)
+                }
+            }
+        }
+    }
+
+    /// Fail with `UnresolvedConflict` if the pool is flagged for manual resolution.
+    pub(crate) fn check_pool_conflict(&self, pool: &PoolId) -> Result<(), SvcError> {
+        match self.conflicts().lock().pool(pool) {
+            Some(node) => Err(SvcError::UnresolvedConflict {
+                kind: ResourceKind::Pool,
+                id: pool.to_string(),
+                node: node.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Fail with `UnresolvedConflict` if the replica is flagged for manual resolution.
+    pub(crate) fn check_replica_conflict(&self, replica: &ReplicaId) -> Result<(), SvcError> {
+        match self.conflicts().lock().replica(replica) {
+            Some(node) => Err(SvcError::UnresolvedConflict {
+                kind: ResourceKind::Replica,
+                id: replica.to_string(),
+                node: node.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+}
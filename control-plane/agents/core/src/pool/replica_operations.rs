@@ -2,15 +2,18 @@ use crate::controller::{
     operations::{ResourceLifecycle, ResourceSharing},
     registry::Registry,
     specs::{GuardedOperationsHelper, OperationSequenceGuard},
-    wrapper::ClientOps,
+    wrapper::{ClientOps, GetterOps},
 };
 use common::errors::{SvcError, SvcError::CordonedNode};
-use common_lib::types::v0::{
-    store::{
-        replica::{ReplicaOperation, ReplicaSpec},
-        OperationGuardArc,
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::{
+        store::{
+            replica::{ReplicaOperation, ReplicaSpec},
+            OperationGuardArc,
+        },
+        transport::{CreateReplica, DestroyReplica, Replica, ShareReplica, UnshareReplica},
     },
-    transport::{CreateReplica, DestroyReplica, Replica, ShareReplica, UnshareReplica},
 };
 
 #[async_trait::async_trait]
@@ -23,23 +26,7 @@ impl ResourceLifecycle for OperationGuardArc<ReplicaSpec> {
         registry: &Registry,
         request: &Self::Create,
     ) -> Result<Self::CreateOutput, SvcError> {
-        let specs = registry.specs();
-
-        if registry.node_cordoned(&request.node)? {
-            return Err(CordonedNode {
-                node_id: request.node.to_string(),
-            });
-        }
-
-        let node = registry.get_node_wrapper(&request.node).await?;
-
-        let replica = specs
-            .get_or_create_replica(request)
-            .operation_guard_wait()
-            .await?;
-        let _ = replica.start_create(registry, request).await?;
-
-        let result = node.create_replica(request).await;
+        let (replica, result) = create_replica(registry, request).await?;
         replica.complete_create(result, registry).await
     }
 
@@ -52,6 +39,67 @@ impl ResourceLifecycle for OperationGuardArc<ReplicaSpec> {
     }
 }
 
+/// Create the replica on its node, leaving its create operation to be completed.
+async fn create_replica(
+    registry: &Registry,
+    request: &CreateReplica,
+) -> Result<(OperationGuardArc<ReplicaSpec>, Result<Replica, SvcError>), SvcError> {
+    let specs = registry.specs();
+
+    if registry.node_cordoned(&request.node)? {
+        return Err(CordonedNode {
+            node_id: request.node.to_string(),
+        });
+    }
+
+    specs.check_tombstone(registry, ResourceKind::Replica, &request.uuid.to_string())?;
+    registry.check_pool_conflict(&request.pool)?;
+
+    let node = registry.get_node_wrapper(&request.node).await?;
+    registry.store_available()?;
+
+    // the replicas take up a whole number of the clusters of their pool, so they're created with
+    // the size they actually take, for the capacity of the pools to add up
+    let requested_size = request.size;
+    let request = &match node.pool(&request.pool).await {
+        Some(pool) => CreateReplica {
+            size: pool.replica_size(request.size),
+            ..request.clone()
+        },
+        None => request.clone(),
+    };
+
+    let replica = specs
+        .get_or_create_replica(request)
+        .operation_guard_wait()
+        .await?;
+    {
+        let mut spec = replica.lock();
+        if spec.status.creating() && request.size != requested_size {
+            spec.requested_size = Some(requested_size);
+        }
+    }
+    let _ = replica.start_create(registry, request).await?;
+
+    let result = node.create_replica(request).await;
+    Ok((replica, result))
+}
+
+/// Create a replica whose spec is to be stored along with the specs of the resource it's
+/// created for, eg: its volume.
+/// On success, the create operation is returned pending and must be completed with
+/// `stage_create` and `complete_staged_create`, or with `complete_create`.
+pub(crate) async fn create_staged_replica(
+    registry: &Registry,
+    request: &CreateReplica,
+) -> Result<(OperationGuardArc<ReplicaSpec>, Replica), SvcError> {
+    let (replica, result) = create_replica(registry, request).await?;
+    match result {
+        Ok(state) => Ok((replica, state)),
+        Err(error) => replica.complete_create(Err(error), registry).await,
+    }
+}
+
 #[async_trait::async_trait]
 impl ResourceLifecycle for Option<&mut OperationGuardArc<ReplicaSpec>> {
     type Create = CreateReplica;
@@ -73,12 +121,21 @@ impl ResourceLifecycle for Option<&mut OperationGuardArc<ReplicaSpec>> {
         let node = registry.get_node_wrapper(&request.node).await?;
 
         if let Some(replica) = self {
+            registry.check_replica_conflict(&request.uuid)?;
+            registry
+                .specs()
+                .check_replica_generation(&request.uuid, request.disowners.generation())?;
             replica
                 .start_destroy_by(registry, &request.disowners)
                 .await?;
 
             let result = node.destroy_replica(request).await;
-            replica.complete_destroy(result, registry).await
+            replica.complete_destroy(result, registry).await?;
+            registry
+                .specs()
+                .add_tombstone(registry, ResourceKind::Replica, &request.uuid.to_string())
+                .await;
+            Ok(())
         } else {
             node.destroy_replica(request).await
         }
@@ -124,6 +181,7 @@ impl ResourceSharing for Option<&mut OperationGuardArc<ReplicaSpec>> {
         let node = registry.get_node_wrapper(&request.node).await?;
 
         if let Some(replica) = self {
+            registry.check_replica_conflict(&request.uuid)?;
             let status = registry.get_replica(&request.uuid).await?;
             let spec_clone = replica
                 .start_update(registry, &status, ReplicaOperation::Share(request.protocol))
@@ -144,10 +202,18 @@ impl ResourceSharing for Option<&mut OperationGuardArc<ReplicaSpec>> {
         let node = registry.get_node_wrapper(&request.node).await?;
 
         if let Some(replica) = self {
+            registry.check_replica_conflict(&request.uuid)?;
             let status = registry.get_replica(&request.uuid).await?;
-            let spec_clone = replica
+            let spec_clone = match replica
                 .start_update(registry, &status, ReplicaOperation::Unshare)
-                .await?;
+                .await
+            {
+                Ok(spec_clone) => spec_clone,
+                Err(error) => {
+                    registry.unshare_idempotent(error)?;
+                    return Ok(status.uri);
+                }
+            };
 
             let result = node.unshare_replica(request).await;
             replica.complete_update(registry, result, spec_clone).await
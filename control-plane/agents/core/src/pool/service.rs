@@ -1,6 +1,7 @@
 use crate::controller::{
     operations::{ResourceLifecycle, ResourceSharing},
     registry::Registry,
+    sorting::sort,
     specs::{OperationSequenceGuard, ResourceSpecsLocked},
     wrapper::GetterOps,
 };
@@ -8,7 +9,7 @@ use common::errors::{PoolNotFound, ReplicaNotFound, SvcError};
 use common_lib::{
     transport_api::{
         v0::{Pools, Replicas},
-        ReplyError,
+        ReplyError, ResourceKind,
     },
     types::v0::{
         store::{pool::PoolSpec, replica::ReplicaSpec, OperationGuardArc, ResourceMutex},
@@ -26,10 +27,12 @@ use grpc::{
             CreateReplicaInfo, DestroyReplicaInfo, ReplicaOperations, ShareReplicaInfo,
             UnshareReplicaInfo,
         },
+        Sorting,
     },
 };
 
 use snafu::OptionExt;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
 pub(super) struct Service {
@@ -60,9 +63,17 @@ impl PoolOperations for Service {
         Ok(())
     }
 
-    async fn get(&self, filter: Filter, _ctx: Option<Context>) -> Result<Pools, ReplyError> {
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        _ctx: Option<Context>,
+    ) -> Result<Pools, ReplyError> {
         let req = GetPools { filter };
-        let pools = self.get_pools(&req).await?;
+        let mut pools = self.get_pools(&req).await?;
+        if let Some(sorting) = sorting {
+            sort_pools(&mut pools.0, &sorting)?;
+        }
         Ok(pools)
     }
 }
@@ -81,9 +92,17 @@ impl ReplicaOperations for Service {
         Ok(replica)
     }
 
-    async fn get(&self, filter: Filter, _ctx: Option<Context>) -> Result<Replicas, ReplyError> {
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        _ctx: Option<Context>,
+    ) -> Result<Replicas, ReplyError> {
         let req = GetReplicas { filter };
-        let replicas = self.get_replicas(&req).await?;
+        let mut replicas = self.get_replicas(&req).await?;
+        if let Some(sorting) = sorting {
+            self.sort_replicas(&mut replicas.0, &sorting)?;
+        }
         Ok(replicas)
     }
 
@@ -122,6 +141,34 @@ impl ReplicaOperations for Service {
     }
 }
 
+/// Sort the given pools by one of their sortable fields.
+fn sort_pools(pools: &mut [Pool], sorting: &Sorting) -> Result<(), SvcError> {
+    let state = |pool: &Pool| pool.state().unwrap_or_default();
+    sort(
+        ResourceKind::Pool,
+        pools,
+        sorting,
+        &[
+            ("id", &|a, b| a.id().as_str().cmp(b.id().as_str())),
+            ("node", &|a, b| a.node().as_str().cmp(b.node().as_str())),
+            ("status", &|a, b| {
+                state(a)
+                    .status
+                    .to_string()
+                    .cmp(&state(b).status.to_string())
+            }),
+            ("capacity", &|a, b| {
+                state(a).capacity.cmp(&state(b).capacity)
+            }),
+            ("used", &|a, b| state(a).used.cmp(&state(b).used)),
+            ("created_at", &|a, b| {
+                let created_at = |pool: &Pool| pool.spec().and_then(|spec| spec.created_at);
+                created_at(a).cmp(&created_at(b))
+            }),
+        ],
+    )
+}
+
 impl Service {
     pub(super) fn new(registry: Registry) -> Self {
         Self { registry }
@@ -130,6 +177,34 @@ impl Service {
         self.registry.specs()
     }
 
+    /// Sort the given replicas by one of their sortable fields.
+    fn sort_replicas(&self, replicas: &mut [Replica], sorting: &Sorting) -> Result<(), SvcError> {
+        let created_at = replicas
+            .iter()
+            .map(|replica| {
+                let spec = self.specs().get_replica(&replica.uuid);
+                let created_at = spec.as_ref().and_then(|spec| spec.lock().created_at);
+                (replica.uuid.clone(), created_at)
+            })
+            .collect::<HashMap<_, _>>();
+        let created_at = |replica: &Replica| created_at.get(&replica.uuid).cloned().flatten();
+        sort(
+            ResourceKind::Replica,
+            replicas,
+            sorting,
+            &[
+                ("uuid", &|a, b| a.uuid.as_str().cmp(b.uuid.as_str())),
+                ("node", &|a, b| a.node.as_str().cmp(b.node.as_str())),
+                ("pool", &|a, b| a.pool.as_str().cmp(b.pool.as_str())),
+                ("size", &|a, b| a.size.cmp(&b.size)),
+                ("status", &|a, b| {
+                    a.status.to_string().cmp(&b.status.to_string())
+                }),
+                ("created_at", &|a, b| created_at(a).cmp(&created_at(b))),
+            ],
+        )
+    }
+
     /// Get pools according to the filter
     #[tracing::instrument(level = "info", skip(self), err, fields(pool.uuid))]
     pub(super) async fn get_pools(&self, request: &GetPools) -> Result<Pools, SvcError> {
@@ -145,7 +220,10 @@ impl Service {
                 tracing::Span::current().record("pool.uuid", &pool_id.as_str());
                 self.node_pools(None, Some(pool_id)).await
             }
-            _ => Err(SvcError::InvalidFilter { filter }),
+            _ => Err(SvcError::InvalidFilter {
+                resource: ResourceKind::Pool,
+                filter,
+            }),
         }
     }
 
@@ -236,7 +314,10 @@ impl Service {
                     .collect();
                 Ok(replicas)
             }
-            _ => Err(SvcError::InvalidFilter { filter }),
+            _ => Err(SvcError::InvalidFilter {
+                resource: ResourceKind::Replica,
+                filter,
+            }),
         }
         .map(Replicas)
     }
@@ -282,6 +363,22 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(replica.uuid = %request.uuid))]
     pub(super) async fn destroy_replica(&self, request: &DestroyReplica) -> Result<(), SvcError> {
         let mut replica = self.specs().replica_opt(&request.uuid).await?;
+        // this is a manual removal, the volumes remove their replicas internally
+        let _redundancy = match &replica {
+            Some(replica) => {
+                let spec = replica.lock().clone();
+                let redundancy = self
+                    .registry
+                    .check_redundancy(
+                        "replica destruction",
+                        &[spec],
+                        request.override_redundancy_check,
+                    )
+                    .await?;
+                Some(redundancy)
+            }
+            None => None,
+        };
         replica.as_mut().destroy(&self.registry, request).await
     }
 
@@ -264,7 +264,34 @@ impl ResourceSpecsLocked {
         if let Some(replica) = specs.replicas.get(&request.uuid) {
             replica.clone()
         } else {
-            specs.replicas.insert(ReplicaSpec::from(request))
+            specs.replica_generation += 1;
+            let mut replica = ReplicaSpec::from(request);
+            replica.set_generation(specs.replica_generation);
+            specs.replicas.insert(replica)
+        }
+    }
+    /// Fail with `ReplicaGenerationMismatch` if a reference to the given `generation` of the
+    /// replica `id` refers to another replica previously created with the same uuid.
+    /// Replicas without a spec have no generation to check against.
+    pub(crate) fn check_replica_generation(
+        &self,
+        id: &ReplicaId,
+        generation: u64,
+    ) -> Result<(), SvcError> {
+        match self.get_replica(id) {
+            Some(replica) => {
+                let replica = replica.lock();
+                if replica.is_generation(generation) {
+                    Ok(())
+                } else {
+                    Err(SvcError::ReplicaGenerationMismatch {
+                        replica_id: id.clone(),
+                        generation: replica.generation,
+                        referenced: generation,
+                    })
+                }
+            }
+            None => Ok(()),
         }
     }
     /// Get a protected ReplicaSpec for the given replica `id`, if it exists
@@ -308,6 +335,22 @@ impl ResourceSpecsLocked {
         let pools = self.get_locked_pools();
         pools.into_iter().map(|p| p.lock().clone()).collect()
     }
+    /// Get the size committed to the pool `id` by its replica specs, including those whose
+    /// replica is not in the state of the pool, but not those being deleted
+    pub(crate) fn get_pool_committed_size(&self, id: &PoolId) -> u64 {
+        let specs = self.read();
+        specs
+            .get_pool_replicas(id)
+            .into_iter()
+            .map(|replica| {
+                let replica = replica.lock();
+                match replica.status.deleting() || replica.status.deleted() {
+                    true => 0,
+                    false => replica.size,
+                }
+            })
+            .sum()
+    }
     /// Check if the given pool `id` has any replicas
     fn pool_has_replicas(&self, id: &PoolId) -> bool {
         let specs = self.read();
@@ -350,7 +393,15 @@ impl ResourceSpecsLocked {
         let pools = self.get_locked_pools();
         for pool in pools {
             if let Ok(mut guard) = pool.operation_guard() {
-                if !guard.handle_incomplete_ops(registry).await {
+                let id = guard.lock().id.clone();
+                if self.tombstoned(registry, &ResourceKind::Pool, id.as_str()) {
+                    continue;
+                }
+                if !registry.replay_due(&ResourceKind::Pool, id.as_str()) {
+                    continue;
+                }
+                let replayed = guard.handle_incomplete_ops(registry).await;
+                if !registry.replayed(&ResourceKind::Pool, id.as_str(), replayed) {
                     // Not all pending operations could be handled.
                     pending_ops = true;
                 }
@@ -368,7 +419,15 @@ impl ResourceSpecsLocked {
         let replicas = self.get_replicas();
         for replica in replicas {
             if let Ok(mut guard) = replica.operation_guard() {
-                if !guard.handle_incomplete_ops(registry).await {
+                let uuid = guard.lock().uuid.to_string();
+                if self.tombstoned(registry, &ResourceKind::Replica, &uuid) {
+                    continue;
+                }
+                if !registry.replay_due(&ResourceKind::Replica, &uuid) {
+                    continue;
+                }
+                let replayed = guard.handle_incomplete_ops(registry).await;
+                if !registry.replayed(&ResourceKind::Replica, &uuid, replayed) {
                     // Not all pending operations could be handled.
                     pending_ops = true;
                 }
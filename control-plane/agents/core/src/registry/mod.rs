@@ -1,12 +1,14 @@
 mod service;
 use crate::controller::registry::Registry;
 use common::Service;
-use grpc::operations::registry::server::RegistryServer;
+use grpc::{operations::registry::server::RegistryServer, rate_limit::RateLimiter};
 use std::sync::Arc;
 
 /// Configure the registry service
 pub(crate) fn configure(builder: Service) -> Service {
     let registry = builder.shared_state::<Registry>().clone();
-    let registry_service = RegistryServer::new(Arc::new(service::Service::new(registry)));
+    let rate_limiter = builder.shared_state::<Arc<RateLimiter>>().clone();
+    let registry_service =
+        RegistryServer::new(Arc::new(service::Service::new(registry, rate_limiter)));
     builder.with_shared_state(registry_service)
 }
@@ -1,18 +1,46 @@
-use crate::{controller, controller::specs::ResourceSpecsLocked};
+use crate::{
+    controller,
+    controller::{
+        orphans::OrphanSnapshot,
+        specs::{PendingOperationAction, ResourceSpecsLocked},
+    },
+};
 use common::errors::SvcError;
 use common_lib::{
     transport_api::ReplyError,
-    types::v0::transport::{GetSpecs, GetStates, Specs, States},
+    types::v0::{
+        store::defaults::ClusterDefaults,
+        transport::{
+            ClearPendingOperation, ClearTombstone, GetClusterDefaults, GetLatencyMatrix,
+            GetOrphans, GetPendingOperation, GetPendingOperations, GetRateLimits,
+            GetReconcilerPasses, GetSchedulingTraces, GetSpecs, GetStates, GetStoreHealth,
+            GetVolumeQuotas, LatencyMatrix, NodeRefresh, OrphanReport, PatchSpec, PendingOperation,
+            PendingOperations, RateLimits, ReconcilerPassStates, RefreshRegistry, RegistryRefresh,
+            ReleaseOperationGuard, RetryOperationReplay, SchedulingTraces, SetClusterDefaults,
+            SetReconcilerPass, SetVolumeQuotas, SpecPatch, Specs, States, StoreHealth,
+            VolumeQuotasUsage,
+        },
+    },
 };
 use grpc::{
     context::Context,
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
+    operations::registry::traits::{
+        ClearPendingOperationInfo, ClearTombstoneInfo, GetClusterDefaultsInfo,
+        GetLatencyMatrixInfo, GetOrphansInfo, GetPendingOperationInfo, GetPendingOperationsInfo,
+        GetRateLimitsInfo, GetReconcilerPassesInfo, GetSchedulingTracesInfo, GetSpecsInfo,
+        GetStatesInfo, GetStoreHealthInfo, GetVolumeQuotasInfo, PatchSpecInfo, RefreshRegistryInfo,
+        RegistryOperations, ReleaseOperationGuardInfo, RetryOperationReplayInfo,
+        SetClusterDefaultsInfo, SetReconcilerPassInfo, SetVolumeQuotasInfo,
+    },
+    rate_limit::RateLimiter,
 };
+use std::{sync::Arc, time::SystemTime};
 
 /// Registry Service
 #[derive(Debug, Clone)]
 pub(super) struct Service {
     registry: controller::registry::Registry,
+    rate_limiter: Arc<RateLimiter>,
 }
 
 #[tonic::async_trait]
@@ -36,12 +64,208 @@ impl RegistryOperations for Service {
         let states = self.get_states(&req).await?;
         Ok(states)
     }
+
+    async fn clear_tombstone(
+        &self,
+        request: &dyn ClearTombstoneInfo,
+        _ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = request.into();
+        self.clear_tombstone(&req).await?;
+        Ok(())
+    }
+
+    async fn refresh_registry(
+        &self,
+        request: &dyn RefreshRegistryInfo,
+        _ctx: Option<Context>,
+    ) -> Result<RegistryRefresh, ReplyError> {
+        let req = request.into();
+        let refresh = self.refresh_registry(&req).await?;
+        Ok(refresh)
+    }
+
+    async fn get_cluster_defaults(
+        &self,
+        request: &dyn GetClusterDefaultsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<ClusterDefaults, ReplyError> {
+        let req = request.into();
+        let defaults = self.get_cluster_defaults(&req).await?;
+        Ok(defaults)
+    }
+
+    async fn set_cluster_defaults(
+        &self,
+        request: &dyn SetClusterDefaultsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<ClusterDefaults, ReplyError> {
+        let req = request.into();
+        let defaults = self.set_cluster_defaults(&req).await?;
+        Ok(defaults)
+    }
+
+    async fn get_store_health(
+        &self,
+        request: &dyn GetStoreHealthInfo,
+        _ctx: Option<Context>,
+    ) -> Result<StoreHealth, ReplyError> {
+        let req = request.into();
+        let health = self.get_store_health(&req).await?;
+        Ok(health)
+    }
+
+    async fn patch_spec(
+        &self,
+        request: &dyn PatchSpecInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SpecPatch, ReplyError> {
+        let req = request.into();
+        let patch = self.patch_spec(&req).await?;
+        Ok(patch)
+    }
+
+    async fn get_pending_operation(
+        &self,
+        request: &dyn GetPendingOperationInfo,
+        _ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = request.into();
+        let operation = self.get_pending_operation(&req).await?;
+        Ok(operation)
+    }
+
+    async fn clear_pending_operation(
+        &self,
+        request: &dyn ClearPendingOperationInfo,
+        _ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = request.into();
+        let operation = self.clear_pending_operation(&req).await?;
+        Ok(operation)
+    }
+
+    async fn release_operation_guard(
+        &self,
+        request: &dyn ReleaseOperationGuardInfo,
+        _ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = request.into();
+        let operation = self.release_operation_guard(&req).await?;
+        Ok(operation)
+    }
+
+    async fn get_pending_operations(
+        &self,
+        request: &dyn GetPendingOperationsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<PendingOperations, ReplyError> {
+        let req = request.into();
+        let operations = self.get_pending_operations(&req).await?;
+        Ok(operations)
+    }
+
+    async fn retry_operation_replay(
+        &self,
+        request: &dyn RetryOperationReplayInfo,
+        _ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = request.into();
+        let operation = self.retry_operation_replay(&req).await?;
+        Ok(operation)
+    }
+
+    async fn get_volume_quotas(
+        &self,
+        request: &dyn GetVolumeQuotasInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeQuotasUsage, ReplyError> {
+        let req = request.into();
+        let usage = self.get_volume_quotas(&req).await?;
+        Ok(usage)
+    }
+
+    async fn set_volume_quotas(
+        &self,
+        request: &dyn SetVolumeQuotasInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeQuotasUsage, ReplyError> {
+        let req = request.into();
+        let usage = self.set_volume_quotas(&req).await?;
+        Ok(usage)
+    }
+
+    async fn get_reconciler_passes(
+        &self,
+        request: &dyn GetReconcilerPassesInfo,
+        _ctx: Option<Context>,
+    ) -> Result<ReconcilerPassStates, ReplyError> {
+        let req = request.into();
+        let passes = self.get_reconciler_passes(&req).await?;
+        Ok(passes)
+    }
+
+    async fn set_reconciler_pass(
+        &self,
+        request: &dyn SetReconcilerPassInfo,
+        _ctx: Option<Context>,
+    ) -> Result<ReconcilerPassStates, ReplyError> {
+        let req = request.into();
+        let passes = self.set_reconciler_pass(&req).await?;
+        Ok(passes)
+    }
+
+    async fn get_rate_limits(
+        &self,
+        request: &dyn GetRateLimitsInfo,
+        _ctx: Option<Context>,
+    ) -> Result<RateLimits, ReplyError> {
+        let req = request.into();
+        let limits = self.get_rate_limits(&req).await?;
+        Ok(limits)
+    }
+
+    async fn get_scheduling_traces(
+        &self,
+        request: &dyn GetSchedulingTracesInfo,
+        _ctx: Option<Context>,
+    ) -> Result<SchedulingTraces, ReplyError> {
+        let req = request.into();
+        let traces = self.get_scheduling_traces(&req).await?;
+        Ok(traces)
+    }
+
+    async fn get_latency_matrix(
+        &self,
+        request: &dyn GetLatencyMatrixInfo,
+        _ctx: Option<Context>,
+    ) -> Result<LatencyMatrix, ReplyError> {
+        let req = request.into();
+        let matrix = self.get_latency_matrix(&req).await?;
+        Ok(matrix)
+    }
+
+    async fn get_orphans(
+        &self,
+        request: &dyn GetOrphansInfo,
+        _ctx: Option<Context>,
+    ) -> Result<OrphanReport, ReplyError> {
+        let req = request.into();
+        let report = self.get_orphans(&req).await?;
+        Ok(report)
+    }
 }
 
 impl Service {
     /// Invoke a new Registry Service
-    pub(super) fn new(registry: controller::registry::Registry) -> Self {
-        Self { registry }
+    pub(super) fn new(
+        registry: controller::registry::Registry,
+        rate_limiter: Arc<RateLimiter>,
+    ) -> Self {
+        Self {
+            registry,
+            rate_limiter,
+        }
     }
 
     fn specs(&self) -> &ResourceSpecsLocked {
@@ -59,6 +283,230 @@ impl Service {
         })
     }
 
+    /// Clear the tombstone of a destroyed resource.
+    pub(crate) async fn clear_tombstone(&self, request: &ClearTombstone) -> Result<(), SvcError> {
+        self.specs()
+            .clear_tombstone(&self.registry, request.kind.clone(), &request.id)
+            .await
+    }
+
+    /// Patch the persisted spec of a resource, to repair it by hand.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn patch_spec(&self, request: &PatchSpec) -> Result<SpecPatch, SvcError> {
+        self.specs().patch_spec(&self.registry, request).await
+    }
+
+    /// Get the operation pending on the persisted spec of a resource.
+    pub(crate) async fn get_pending_operation(
+        &self,
+        request: &GetPendingOperation,
+    ) -> Result<PendingOperation, SvcError> {
+        self.specs()
+            .pending_operation(
+                &self.registry,
+                &request.kind,
+                &request.id,
+                PendingOperationAction::Inspect,
+            )
+            .await
+    }
+
+    /// Clear the operation pending on the persisted spec of a resource, which the control-plane
+    /// is unable to recover by itself.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn clear_pending_operation(
+        &self,
+        request: &ClearPendingOperation,
+    ) -> Result<PendingOperation, SvcError> {
+        self.specs()
+            .pending_operation(
+                &self.registry,
+                &request.kind,
+                &request.id,
+                PendingOperationAction::Clear {
+                    force: request.force,
+                },
+            )
+            .await
+    }
+
+    /// Forcibly release the operation guard of a resource, which was left held by an operation
+    /// abandoned without completing.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn release_operation_guard(
+        &self,
+        request: &ReleaseOperationGuard,
+    ) -> Result<PendingOperation, SvcError> {
+        self.specs()
+            .release_operation_guard(&self.registry, &request.kind, &request.id)
+            .await
+    }
+
+    /// Get the operations pending on the specs of all the resources, along with the failed
+    /// attempts at replaying them.
+    pub(crate) async fn get_pending_operations(
+        &self,
+        _request: &GetPendingOperations,
+    ) -> Result<PendingOperations, SvcError> {
+        Ok(self.specs().pending_operations(&self.registry))
+    }
+
+    /// Retry replaying the operation pending on the spec of a resource, which was given up on
+    /// as stuck.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn retry_operation_replay(
+        &self,
+        request: &RetryOperationReplay,
+    ) -> Result<PendingOperation, SvcError> {
+        self.specs()
+            .retry_operation_replay(&self.registry, &request.kind, &request.id)
+            .await
+    }
+
+    /// Get the cluster-wide default parameters of the volumes.
+    pub(crate) async fn get_cluster_defaults(
+        &self,
+        _request: &GetClusterDefaults,
+    ) -> Result<ClusterDefaults, SvcError> {
+        Ok(self.registry.cluster_defaults())
+    }
+
+    /// Replace the cluster-wide default parameters of the volumes.
+    /// The existing volumes are not affected as the defaults only apply on creation.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn set_cluster_defaults(
+        &self,
+        request: &SetClusterDefaults,
+    ) -> Result<ClusterDefaults, SvcError> {
+        if request.defaults.replicas == 0 {
+            return Err(SvcError::InvalidArguments {});
+        }
+        self.registry
+            .set_cluster_defaults(request.defaults.clone())
+            .await
+    }
+
+    /// Get the per label quotas of the volumes, along with the capacity they provision.
+    pub(crate) async fn get_volume_quotas(
+        &self,
+        _request: &GetVolumeQuotas,
+    ) -> Result<VolumeQuotasUsage, SvcError> {
+        Ok(self.registry.volume_quotas_usage())
+    }
+
+    /// Replace the per label quotas of the volumes.
+    /// The existing volumes are left as they are, even if they're over their new quota.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn set_volume_quotas(
+        &self,
+        request: &SetVolumeQuotas,
+    ) -> Result<VolumeQuotasUsage, SvcError> {
+        let quotas = &request.quotas.quotas;
+        let duplicate = quotas.iter().enumerate().any(|(index, quota)| {
+            quotas[.. index]
+                .iter()
+                .any(|other| other.key == quota.key && other.value == quota.value)
+        });
+        if duplicate || quotas.iter().any(|quota| quota.key.is_empty()) {
+            return Err(SvcError::InvalidArguments {});
+        }
+        self.registry
+            .set_volume_quotas(request.quotas.clone())
+            .await?;
+        Ok(self.registry.volume_quotas_usage())
+    }
+
+    /// Get whether each reconciler pass is enabled, along with the work it skipped.
+    pub(crate) async fn get_reconciler_passes(
+        &self,
+        _request: &GetReconcilerPasses,
+    ) -> Result<ReconcilerPassStates, SvcError> {
+        Ok(self.registry.reconciler_passes())
+    }
+
+    /// Enable or disable a reconciler pass, until it's switched back, even across restarts.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(crate) async fn set_reconciler_pass(
+        &self,
+        request: &SetReconcilerPass,
+    ) -> Result<ReconcilerPassStates, SvcError> {
+        self.registry
+            .set_reconciler_pass(request.pass, request.enabled)
+            .await?;
+        Ok(self.registry.reconciler_passes())
+    }
+
+    /// Get the rate limits of the gRPC requests, along with the callers which were throttled.
+    pub(crate) async fn get_rate_limits(
+        &self,
+        _request: &GetRateLimits,
+    ) -> Result<RateLimits, SvcError> {
+        Ok(self.rate_limiter.limits())
+    }
+
+    /// Get the scheduling traces of the latest volume creations.
+    pub(crate) async fn get_scheduling_traces(
+        &self,
+        request: &GetSchedulingTraces,
+    ) -> Result<SchedulingTraces, SvcError> {
+        let traces = self.registry.scheduling_traces().lock();
+        Ok(SchedulingTraces {
+            traces: traces.traces(request.volume.as_ref()),
+        })
+    }
+
+    /// Get the round-trip times which the nodes measured to their peers.
+    pub(crate) async fn get_latency_matrix(
+        &self,
+        _request: &GetLatencyMatrix,
+    ) -> Result<LatencyMatrix, SvcError> {
+        Ok(self.registry.latencies().lock().matrix())
+    }
+
+    /// Get the replicas and nexuses which only exist as a spec or as a dataplane object, from a
+    /// single snapshot of the specs and of the states of the online nodes.
+    pub(crate) async fn get_orphans(
+        &self,
+        _request: &GetOrphans,
+    ) -> Result<OrphanReport, SvcError> {
+        let snapshot = OrphanSnapshot::new(&self.registry).await;
+        let mut report = snapshot.report();
+        self.registry
+            .orphan_sightings()
+            .lock()
+            .observe(&mut report, SystemTime::now());
+        Ok(report)
+    }
+
+    /// Get the health of the persistent store endpoints.
+    pub(crate) async fn get_store_health(
+        &self,
+        _request: &GetStoreHealth,
+    ) -> Result<StoreHealth, SvcError> {
+        Ok(self.registry.store_health().await)
+    }
+
+    /// Reload the resources of all the online nodes into the registry.
+    pub(crate) async fn refresh_registry(
+        &self,
+        _request: &RefreshRegistry,
+    ) -> Result<RegistryRefresh, SvcError> {
+        let polls = self.registry.poll_nodes().await;
+        for (node, _) in polls.iter().filter(|(_, result)| result.is_ok()) {
+            self.registry.resolve_node_conflicts(node).await;
+        }
+        self.registry.check_volume_conditions().await;
+        let mut nodes = polls
+            .into_iter()
+            .map(|(node, result)| NodeRefresh {
+                node,
+                error: result.err().map(|error| error.to_string()),
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.node.as_str().cmp(b.node.as_str()));
+        Ok(RegistryRefresh { nodes })
+    }
+
     /// Get state information for all resources.
     pub(crate) async fn get_states(&self, _request: &GetStates) -> Result<States, SvcError> {
         let mut nexuses = vec![];
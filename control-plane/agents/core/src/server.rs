@@ -7,11 +7,22 @@ mod service;
 pub(crate) mod volume;
 pub(crate) mod watch;
 
-use controller::registry::NumRebuilds;
+use common_lib::types::v0::transport::{RateLimit, Topology};
+use controller::{
+    conflicts::ConflictPolicy,
+    registry::{NumRebuilds, Registry, RegistryConfig},
+    replay::ReplayPolicy,
+    store_policy::StoreUnavailablePolicy,
+    topology::TopologyPolicy,
+};
+use grpc::rate_limit::RateLimiter;
+use nexus::NexusChildrenPolicy;
 use utils::{version_info_str, DEFAULT_GRPC_SERVER_ADDR};
 
 use http::Uri;
+use ipnetwork::IpNetwork;
 use opentelemetry::{trace::TracerProvider, KeyValue};
+use std::{num::NonZeroU32, sync::Arc};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
@@ -30,12 +41,18 @@ pub(crate) struct CliArgs {
     #[structopt(long, default_value = "10s")]
     pub(crate) reconcile_period: humantime::Duration,
 
+    /// The window within which a burst of events which trigger the reconcile loop is coalesced
+    /// into a single run, measured from the first event of the burst (0 to disable)
+    #[structopt(long, default_value = "100ms")]
+    pub(crate) reconcile_debounce: humantime::Duration,
+
     /// Deadline for the io-engine instance keep alive registration
     #[structopt(long, short, default_value = "10s")]
     pub(crate) deadline: humantime::Duration,
 
-    /// The Persistent Store URLs to connect to
+    /// The Persistent Store URLs to connect to, comma-separated
     /// (supports the http/https schema)
+    /// The store operations fail over to the next URL when the current one cannot be reached
     #[structopt(long, short, default_value = "http://localhost:2379")]
     pub(crate) store: String,
 
@@ -47,6 +64,22 @@ pub(crate) struct CliArgs {
     #[structopt(long, default_value = utils::STORE_LEASE_LOCK_TTL)]
     pub(crate) store_lease_ttl: humantime::Duration,
 
+    /// How to operate whilst the persistent store is unavailable: wait keeps serving every
+    /// request, the mutations failing once the store timeout expires, whereas read-only serves
+    /// the requests which get resources and fails the mutations fast, suspending the reconciler
+    /// until the store is back.
+    #[structopt(long, default_value = "wait")]
+    pub(crate) store_unavailable_policy: StoreUnavailablePolicy,
+
+    /// The number of consecutive failed attempts at replaying the operation left pending on a
+    /// spec after which it's given up on as stuck, until it's retried through the admin API.
+    #[structopt(long, default_value = "10")]
+    max_replay_attempts: u32,
+    /// How long to wait before retrying a failed replay of the operation pending on a spec,
+    /// doubled on each subsequent failure, up to 5 minutes.
+    #[structopt(long, default_value = "1s")]
+    replay_backoff: humantime::Duration,
+
     /// The timeout for every node connection (gRPC)
     #[structopt(long, default_value = utils::DEFAULT_CONN_TIMEOUT)]
     pub(crate) connect_timeout: humantime::Duration,
@@ -55,6 +88,23 @@ pub(crate) struct CliArgs {
     #[structopt(long, short, default_value = utils::DEFAULT_REQ_TIMEOUT)]
     pub(crate) request_timeout: humantime::Duration,
 
+    /// The timeout for the node requests which list or get resources (gRPC), eg: list replicas.
+    /// If `None` the request timeout is used.
+    #[structopt(long)]
+    pub(crate) fast_request_timeout: Option<humantime::Duration>,
+
+    /// The timeout for the node requests which operate on existing resources (gRPC), eg: share a
+    /// replica or add a nexus child.
+    /// If `None` the request timeout is used.
+    #[structopt(long)]
+    pub(crate) medium_request_timeout: Option<humantime::Duration>,
+
+    /// The timeout for the node requests which create or destroy resources (gRPC), eg: create a
+    /// pool or a nexus.
+    /// If `None` the request timeout is used.
+    #[structopt(long)]
+    pub(crate) slow_request_timeout: Option<humantime::Duration>,
+
     /// Add process service tags to the traces
     #[structopt(short, long, env = "TRACING_TAGS", value_delimiter=",", parse(try_from_str = utils::tracing_telemetry::parse_key_value))]
     tracing_tags: Vec<KeyValue>,
@@ -69,10 +119,159 @@ pub(crate) struct CliArgs {
     /// (supports the http/https schema)
     #[structopt(long, short, default_value = DEFAULT_GRPC_SERVER_ADDR)]
     pub(crate) grpc_server_addr: Uri,
-    /// The maximum number of system-wide rebuilds permitted at any given time.
+    /// The maximum number of system-wide rebuilds permitted at any given time, regardless of which
+    /// nodes they run on. Rebuilds beyond it are held back until a running one completes.
     /// If `None` do not limit the number of rebuilds.
     #[structopt(long)]
     max_rebuilds: Option<NumRebuilds>,
+    /// The maximum number of rebuilds which a node may be the destination of for a replacement
+    /// replica to be placed on it. Replacements are otherwise spread across the nodes taking part
+    /// in the fewest rebuilds, without a hard limit.
+    #[structopt(long)]
+    max_rebuild_destinations: Option<u32>,
+    /// How long a volume may wait for a rebuild, when the maximum number of rebuilds is reached,
+    /// before it's deemed of the highest rebuild priority, so that the volumes of low priority
+    /// are not held back indefinitely by those of higher priority.
+    #[structopt(long, default_value = "10m")]
+    rebuild_aging: humantime::Duration,
+    /// Fail with NotShared when unsharing a resource which is not shared.
+    /// By default, unsharing an unshared resource is a no-op which succeeds.
+    #[structopt(long)]
+    legacy_unshare: bool,
+    /// How long the id of a destroyed pool or replica is kept from being reused, preventing
+    /// stale state of the destroyed resource from resurrecting it.
+    /// By default no tombstones are kept, and the ids may be reused straight away.
+    #[structopt(long, default_value = "0s")]
+    tombstone_ttl: humantime::Duration,
+    /// The topology applied to the volumes which are created without one, as json.
+    /// Volumes created with a topology keep their own.
+    #[structopt(long, parse(try_from_str = serde_json::from_str))]
+    default_topology: Option<Topology>,
+    /// How to resolve a pool or replica which a registering node reports differently from its
+    /// spec: prefer-store keeps the spec, prefer-node updates the spec to match the node and
+    /// manual flags the resource, refusing any operation on it until it's resolved by hand.
+    #[structopt(long, default_value = "prefer-store")]
+    conflict_policy: ConflictPolicy,
+    /// The maximum size of a volume, eg: 10TiB.
+    /// Volume creation requests for a larger size are rejected before any replica is scheduled.
+    /// If `None` do not limit the size of the volumes.
+    #[structopt(long, parse(try_from_str = parse_size::parse_size))]
+    max_volume_size: Option<u64>,
+    /// The minimum size of a volume, eg: 1MiB.
+    /// Volume creation requests for a smaller size are rejected, whilst the sizes which are not
+    /// a multiple of the block size are rounded up.
+    #[structopt(long, default_value = "1MiB", parse(try_from_str = parse_size::parse_size))]
+    min_volume_size: u64,
+    /// How long a nexus child which faulted because its device timed out is left in place,
+    /// as the device may come back, before it's replaced.
+    /// Children faulted for any other reason, eg: I/O errors, are replaced right away.
+    #[structopt(long, default_value = "10s")]
+    timed_out_child_grace_period: humantime::Duration,
+    /// How long a managed nexus may be left without an owner, eg: once its volume is deleted or
+    /// has been republished elsewhere, before it's destroyed.
+    /// A nexus which regains an owner within the grace period is spared.
+    #[structopt(long, default_value = "0s")]
+    disowned_nexus_grace_period: humantime::Duration,
+    /// Whether a nexus may be created when some of its replicas are on nodes which are offline,
+    /// or fail to attach: all fails the creation, whereas best-effort creates the nexus with the
+    /// replicas which can be attached and adds the others later.
+    #[structopt(long, default_value = "all")]
+    nexus_children_policy: NexusChildrenPolicy,
+    /// How long a node may take to connect to a remote child of a nexus it's about to create,
+    /// before the child is deemed unreachable and the creation fails fast.
+    #[structopt(long, default_value = "2s")]
+    child_probe_timeout: humantime::Duration,
+    /// The number of consecutive calls to a node which may fail because it cannot be reached or
+    /// does not respond in time, after which the calls to it fail fast for the cooldown period.
+    /// If `None` the calls to the nodes are never failed fast.
+    #[structopt(long)]
+    node_failure_threshold: Option<u32>,
+    /// How long the calls to a node which keeps failing fail fast before a call is let through
+    /// to probe whether it has recovered.
+    #[structopt(long, default_value = "30s")]
+    node_failure_cooldown: humantime::Duration,
+    /// How long the poll of a node may take before it's given up on, so a slow node doesn't
+    /// delay the cache refresh of the others.
+    /// If `None` the cache period is used.
+    #[structopt(long)]
+    node_poll_timeout: Option<humantime::Duration>,
+    /// The number of times a failed poll of a node is retried, in the background, before
+    /// waiting for the next cache period.
+    #[structopt(long, default_value = "3")]
+    node_poll_retries: u32,
+    /// How long to wait before the first retry of a failed node poll, doubled on each retry.
+    #[structopt(long, default_value = "1s")]
+    node_poll_backoff: humantime::Duration,
+    /// The minimum number of healthy replicas which a volume must be left with by the operations
+    /// which remove its replicas, eg: scaling it down.
+    /// The check can be overridden on each operation.
+    #[structopt(long, default_value = "1")]
+    min_replication_factor: u8,
+    /// How to handle a volume missing replicas which can't be placed as per its topology, eg:
+    /// after node removals: strict keeps the volume degraded and raises the
+    /// TopologyUnsatisfiable condition whilst relaxed places the replicas regardless of the
+    /// topology, raising the TopologyRelaxed condition.
+    #[structopt(long, default_value = "strict")]
+    topology_policy: TopologyPolicy,
+    /// The number of revisions retained in the history of each volume spec, the oldest of which
+    /// are pruned first.
+    #[structopt(long, default_value = "10")]
+    volume_spec_revisions: NonZeroU32,
+    /// How long a replica may be absent from the state reported by its node, once it's created,
+    /// before it's deemed missing, as the node may not have reported it yet.
+    /// Beyond this grace period, the absent replica is handled as faulted.
+    #[structopt(long, default_value = "10s")]
+    replica_creation_grace_period: humantime::Duration,
+    /// The number of the latest volume creations whose scheduling trace is retained, showing
+    /// how each pool fared through the filters of the scheduler, for troubleshooting.
+    /// No trace is retained if 0.
+    #[structopt(long, default_value = "10")]
+    scheduling_traces: usize,
+    /// Retire the nexus children which are backed by a replica smaller than their nexus, as long
+    /// as the volume has other healthy children left, so they can be replaced by the reconcilers.
+    /// By default such children are only reported through the ChildSizeMismatch volume condition.
+    #[structopt(long)]
+    retire_undersized_children: bool,
+    /// Allow the operation guard of a resource to be released forcibly through the admin API,
+    /// when the operation holding it was abandoned without completing, leaving the resource
+    /// wedged until the agent is restarted.
+    #[structopt(long)]
+    allow_guard_release: bool,
+    /// Allow the persisted specs to be patched through the admin API, to repair a spec which the
+    /// control-plane cannot recover from by itself.
+    #[structopt(long)]
+    allow_spec_patch: bool,
+    /// The number of mutating gRPC requests, eg: CreateVolume, which each caller may make per
+    /// second. Callers are identified by their tenant, or their address if they have none or
+    /// they're not within the trusted networks.
+    /// Requests made by the control-plane itself are never limited.
+    /// If `None` do not limit the mutations.
+    #[structopt(long)]
+    grpc_mutation_rate_limit: Option<NonZeroU32>,
+    /// The number of mutating gRPC requests which each caller may make in a single burst.
+    /// If `None` the mutation rate limit is used.
+    #[structopt(long)]
+    grpc_mutation_burst: Option<NonZeroU32>,
+    /// The number of gRPC requests which get resources, eg: GetVolumes, which each caller may
+    /// make per second.
+    /// If `None` do not limit the reads.
+    #[structopt(long)]
+    grpc_read_rate_limit: Option<NonZeroU32>,
+    /// The number of gRPC requests which get resources which each caller may make in a single
+    /// burst.
+    /// If `None` the read rate limit is used.
+    #[structopt(long)]
+    grpc_read_burst: Option<NonZeroU32>,
+    /// The networks of the gRPC callers which are trusted to tell their tenant, and whether
+    /// they're the control-plane itself, eg: the network of the control-plane pods.
+    /// The requests of any other caller are limited as made by its address.
+    #[structopt(long, value_delimiter = ",")]
+    grpc_trusted_networks: Vec<IpNetwork>,
+    /// The factor of the capacity of a pool which the sizes of the replicas placed on it may add
+    /// up to, eg: 2.0 allows thin provisioned replicas to commit twice the capacity of a pool.
+    /// If `None` the replicas are placed as long as the pool has enough free space.
+    #[structopt(long, parse(try_from_str = parse_overcommit_factor))]
+    pool_overcommit_factor: Option<f64>,
 }
 impl CliArgs {
     fn args() -> Self {
@@ -80,6 +279,60 @@ impl CliArgs {
     }
 }
 
+impl From<&CliArgs> for RegistryConfig {
+    fn from(cli_args: &CliArgs) -> Self {
+        Self {
+            cache_period: cli_args.cache_period.into(),
+            store_url: cli_args.store.clone(),
+            store_timeout: cli_args.store_timeout.into(),
+            store_lease_ttl: cli_args.store_lease_ttl.into(),
+            reconcile_period: cli_args.reconcile_period.into(),
+            reconcile_idle_period: cli_args.reconcile_idle_period.into(),
+            reconcile_debounce: cli_args.reconcile_debounce.into(),
+            max_rebuilds: cli_args.max_rebuilds,
+            max_rebuild_destinations: cli_args.max_rebuild_destinations,
+            legacy_unshare: cli_args.legacy_unshare,
+            tombstone_ttl: cli_args.tombstone_ttl.into(),
+            default_topology: cli_args.default_topology.clone(),
+            conflict_policy: cli_args.conflict_policy,
+            max_volume_size: cli_args.max_volume_size,
+            min_volume_size: cli_args.min_volume_size,
+            timed_out_child_grace_period: cli_args.timed_out_child_grace_period.into(),
+            disowned_nexus_grace_period: cli_args.disowned_nexus_grace_period.into(),
+            nexus_children_policy: cli_args.nexus_children_policy,
+            child_probe_timeout: cli_args.child_probe_timeout.into(),
+            node_poll_timeout: cli_args.node_poll_timeout.map(Into::into),
+            node_poll_retries: cli_args.node_poll_retries,
+            node_poll_backoff: cli_args.node_poll_backoff.into(),
+            min_replication_factor: cli_args.min_replication_factor,
+            topology_policy: cli_args.topology_policy,
+            spec_revisions: cli_args.volume_spec_revisions.get() as usize,
+            replica_creation_grace_period: cli_args.replica_creation_grace_period.into(),
+            scheduling_traces: cli_args.scheduling_traces,
+            retire_undersized_children: cli_args.retire_undersized_children,
+            allow_guard_release: cli_args.allow_guard_release,
+            allow_spec_patch: cli_args.allow_spec_patch,
+            rebuild_aging: cli_args.rebuild_aging.into(),
+            store_unavailable_policy: cli_args.store_unavailable_policy,
+            replay_policy: ReplayPolicy::new(
+                cli_args.max_replay_attempts,
+                cli_args.replay_backoff.into(),
+            ),
+            pool_overcommit_factor: cli_args.pool_overcommit_factor,
+        }
+    }
+}
+
+fn parse_overcommit_factor(source: &str) -> Result<f64, String> {
+    match source.parse::<f64>() {
+        Ok(factor) if factor.is_finite() && factor > 0.0 => Ok(factor),
+        _ => Err(format!(
+            "Invalid pool overcommit factor '{}', expected a positive number",
+            source
+        )),
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let cli_args = CliArgs::args();
@@ -95,16 +348,18 @@ async fn main() {
 
 async fn server(cli_args: CliArgs) {
     common_lib::init_cluster_info_or_panic().await;
-    let registry = controller::registry::Registry::new(
-        cli_args.cache_period.into(),
-        cli_args.store.clone(),
-        cli_args.store_timeout.into(),
-        cli_args.store_lease_ttl.into(),
-        cli_args.reconcile_period.into(),
-        cli_args.reconcile_idle_period.into(),
-        cli_args.max_rebuilds,
-    )
-    .await;
+    let registry = Registry::new(RegistryConfig::from(&cli_args)).await;
+    let rate_limiter = Arc::new(
+        RateLimiter::new(
+            cli_args
+                .grpc_mutation_rate_limit
+                .map(|rate| RateLimit::new(rate, cli_args.grpc_mutation_burst)),
+            cli_args
+                .grpc_read_rate_limit
+                .map(|rate| RateLimit::new(rate, cli_args.grpc_read_burst)),
+        )
+        .with_trusted_networks(cli_args.grpc_trusted_networks.clone()),
+    );
 
     let base_service = common::Service::builder()
         .with_shared_state(opentelemetry::global::tracer_provider().versioned_tracer(
@@ -114,6 +369,7 @@ async fn server(cli_args: CliArgs) {
         ))
         .with_shared_state(registry.clone())
         .with_shared_state(cli_args.grpc_server_addr.clone())
+        .with_shared_state(rate_limiter)
         .configure_async(node::configure)
         .await
         .configure(pool::configure)
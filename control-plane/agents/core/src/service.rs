@@ -8,9 +8,11 @@ use grpc::{
         registration::server::RegistrationServer, registry::server::RegistryServer,
         replica::server::ReplicaServer, volume::server::VolumeServer, watch::server::WatchServer,
     },
+    rate_limit::{RateLimitLayer, RateLimiter},
     tracing::OpenTelServer,
 };
 use http::Uri;
+use std::sync::Arc;
 use tracing::error;
 
 /// the gprc service that encapsulates the base_service and the server for rpc
@@ -39,11 +41,13 @@ impl Service {
         let registry_service = self.base_service.shared_state::<RegistryServer>().clone();
         let nexus_service = self.base_service.shared_state::<NexusServer>().clone();
         let watch_service = self.base_service.shared_state::<WatchServer>().clone();
+        let rate_limiter = self.base_service.shared_state::<Arc<RateLimiter>>().clone();
 
         let tonic_router = self
             .base_service
             .tonic_server()
             .layer(OpenTelServer::new())
+            .layer(RateLimitLayer::new(rate_limiter))
             .add_service(pool_service.into_grpc_server())
             .add_service(replica_service.into_grpc_server())
             .add_service(volume_service.into_grpc_server())
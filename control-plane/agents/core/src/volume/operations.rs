@@ -1,30 +1,37 @@
 use crate::{
     controller::{
-        operations::{ResourceLifecycle, ResourcePublishing, ResourceReplicas, ResourceSharing},
+        fault_injection::{crash_point, wedge_point, VOLUME_CREATE_REPLICAS, VOLUME_SHARE},
+        operations::{
+            ResourceLifecycle, ResourcePausing, ResourcePublishing, ResourceReplicas,
+            ResourceSharing,
+        },
         reconciler::PollTriggerEvent,
         registry::Registry,
         specs::{GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecsLocked},
     },
+    pool::create_staged_replica,
     volume::specs::{get_create_volume_replicas, get_volume_target_node},
 };
 use common::errors::SvcError;
 use common_lib::{
-    transport_api::ErrorChain,
+    transport_api::{ErrorChain, ResourceKind},
     types::v0::{
         store::{
+            definitions::StoreBatch,
             nexus_persistence::NexusInfoKey,
             replica::ReplicaSpec,
             volume::{VolumeOperation, VolumeSpec},
             OperationGuardArc, TraceSpan, TraceStrLog,
         },
         transport::{
-            CreateVolume, DestroyNexus, DestroyReplica, DestroyVolume, NexusId, Protocol,
-            PublishVolume, Replica, ReplicaOwners, SetVolumeReplica, ShareNexus, ShareVolume,
-            UnpublishVolume, UnshareNexus, UnshareVolume, Volume,
+            CreateVolume, DestroyNexus, DestroyReplica, DestroyVolume, FreezeVolume, NexusId,
+            PauseNexus, PoolId, Protocol, PublishVolume, Replica, ReplicaOwners, ResumeNexus,
+            SetVolumeReplica, ShareNexus, ShareVolume, ThawVolume, UnpublishVolume, UnshareNexus,
+            UnshareVolume, Volume, VolumePlacementPolicy,
         },
     },
 };
-use std::ops::Deref;
+use std::{ops::Deref, time::SystemTime};
 
 #[async_trait::async_trait]
 impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
@@ -36,6 +43,8 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
         registry: &Registry,
         request: &Self::Create,
     ) -> Result<Self::CreateOutput, SvcError> {
+        // fail before the spec is added to the registry, as it couldn't be stored
+        registry.store_available()?;
         let specs = registry.specs();
         let volume = specs
             .get_or_create_volume(request)
@@ -45,14 +54,30 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
 
         // todo: pick nodes and pools using the Node&Pool Topology
         // todo: virtually increase the pool usage to avoid a race for space with concurrent calls
+        registry.scheduling_traces().lock().start(&request.uuid);
         let result = get_create_volume_replicas(registry, request).await;
+        let error = result.as_ref().err().map(|error| error.full_string());
+        registry
+            .scheduling_traces()
+            .lock()
+            .finish(&request.uuid, error);
         let create_replicas = volume.validate_create_step(registry, result).await?;
 
-        let mut replicas = Vec::<Replica>::new();
+        // only replicas which are explicitly pinned to a pool may share a node, and only if
+        // the request allows it, so that the scheduler never stacks replicas on a single node
+        let same_node_allowed =
+            |pool: &PoolId| request.allow_same_node && request.pools.contains(pool);
+
+        // the specs of the replicas are stored as created along with the volume's, so a crash
+        // midway through leaves no created replicas behind for a volume which is not
+        let mut replicas = Vec::<(OperationGuardArc<ReplicaSpec>, Replica)>::new();
         for replica in &create_replicas {
             if replicas.len() >= request.replicas as usize {
                 break;
-            } else if replicas.iter().any(|r| r.node == replica.node) {
+            } else if replicas.iter().any(|(_, r)| {
+                r.node == replica.node
+                    && !(same_node_allowed(&r.pool) && same_node_allowed(&replica.pool))
+            }) {
                 // don't reuse the same node
                 continue;
             }
@@ -64,7 +89,7 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
             } else {
                 replica.clone()
             };
-            match OperationGuardArc::<ReplicaSpec>::create(registry, &replica).await {
+            match create_staged_replica(registry, &replica).await {
                 Ok(replica) => {
                     replicas.push(replica);
                 }
@@ -79,12 +104,21 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
             };
         }
 
+        let best_effort = request.placement == VolumePlacementPolicy::BestEffort;
+        if best_effort && !replicas.is_empty() && replicas.len() < request.replicas as usize {
+            volume_clone.warn(&format!(
+                "Created {} out of {} replicas, the rest will be created by the reconcilers",
+                replicas.len(),
+                request.replicas
+            ));
+        }
+
         // we can't fulfil the required replication factor, so let the caller
         // decide what to do next
-        let result = if replicas.len() < request.replicas as usize {
-            for replica_state in replicas {
-                let result = match specs.replica(&replica_state.uuid).await {
-                    Ok(mut replica) => {
+        if replicas.is_empty() || (!best_effort && replicas.len() < request.replicas as usize) {
+            for (mut replica, replica_state) in replicas {
+                let result = match replica.complete_create(Ok(()), registry).await {
+                    Ok(()) => {
                         let request = DestroyReplica::from(replica_state.clone());
                         replica.destroy(registry, &request.with_disown_all()).await
                     }
@@ -98,14 +132,22 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
                     ));
                 }
             }
-            Err(SvcError::ReplicaCreateNumber {
+            let result = Err(SvcError::ReplicaCreateNumber {
                 id: request.uuid.to_string(),
-            })
-        } else {
-            Ok(())
-        };
+            });
+            return volume.complete_create(result, registry).await;
+        }
 
-        volume.complete_create(result, registry).await?;
+        let mut batch = StoreBatch::default();
+        let staged = replicas
+            .iter()
+            .try_for_each(|(replica, _)| replica.stage_create(&mut batch));
+        crash_point(VOLUME_CREATE_REPLICAS);
+        let result = volume.complete_create_with(staged, registry, batch).await;
+        for (replica, _) in &replicas {
+            replica.complete_staged_create(result.is_ok());
+        }
+        result?;
         Ok(volume)
     }
 
@@ -188,7 +230,11 @@ impl ResourceLifecycle for OperationGuardArc<VolumeSpec> {
             }
         }
 
-        self.complete_destroy(Ok(()), registry).await
+        self.complete_destroy(Ok(()), registry).await?;
+        registry
+            .delete_spec_history(&ResourceKind::Volume, &request.uuid.to_string())
+            .await;
+        Ok(())
     }
 }
 
@@ -210,6 +256,7 @@ impl ResourceSharing for OperationGuardArc<VolumeSpec> {
         let spec_clone = self
             .start_update(registry, &state, VolumeOperation::Share(request.protocol))
             .await?;
+        wedge_point(VOLUME_SHARE, self)?;
 
         let target = state.target.expect("already validated");
         let result = match specs.nexus(&target.uuid).await {
@@ -235,9 +282,13 @@ impl ResourceSharing for OperationGuardArc<VolumeSpec> {
         let specs = registry.specs();
         let state = registry.get_volume_state(&request.uuid).await?;
 
-        let spec_clone = self
+        let spec_clone = match self
             .start_update(registry, &state, VolumeOperation::Unshare)
-            .await?;
+            .await
+        {
+            Ok(spec_clone) => spec_clone,
+            Err(error) => return registry.unshare_idempotent(error),
+        };
 
         let target = state.target.expect("Already validated");
         let result = match specs.nexus(&target.uuid).await {
@@ -264,14 +315,26 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
         let state = registry.get_volume_state(&request.uuid).await?;
         let nexus_node = get_volume_target_node(registry, &state, request).await?;
         let nexus_id = NexusId::new();
-
-        let operation =
-            VolumeOperation::Publish((nexus_node.clone(), nexus_id.clone(), request.share));
+        // re-apply the reservation of a previous publish so the fencing is never dropped
+        let reservation = request.reservation.or_else(|| self.lock().reservation);
+
+        let operation = VolumeOperation::Publish((
+            nexus_node.clone(),
+            nexus_id.clone(),
+            request.share,
+            reservation,
+        ));
         let spec_clone = self.start_update(registry, &state, operation).await?;
 
         // Create a Nexus on the requested or auto-selected node
         let result = specs
-            .volume_create_nexus(registry, &nexus_node, &nexus_id, &spec_clone)
+            .volume_create_nexus(
+                registry,
+                &nexus_node,
+                &nexus_id,
+                &spec_clone,
+                reservation.as_ref(),
+            )
             .await;
 
         let (mut nexus, nexus_state) = self
@@ -334,6 +397,16 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
             .start_update(registry, &state, VolumeOperation::Unpublish)
             .await?;
 
+        // destroying the target interrupts any rebuild in progress, so the replicas being
+        // rebuilt must not be trusted with the volume data until they're rebuilt again
+        if let Some(target) = &state.target {
+            let result = specs
+                .mark_rebuilding_replicas_out_of_sync(registry, target)
+                .await;
+            self.validate_update_step(registry, result, &spec_clone)
+                .await?;
+        }
+
         let volume_target = spec_clone.target.as_ref().expect("already validated");
         let result = match specs.nexus_opt(volume_target.nexus()).await? {
             None => Ok(()),
@@ -365,7 +438,72 @@ impl ResourcePublishing for OperationGuardArc<VolumeSpec> {
             }
         };
 
-        self.complete_update(registry, result, spec_clone).await
+        self.complete_update(registry, result, spec_clone).await?;
+
+        // the target is gone, so the reservation can now be released if requested
+        if request.release_reservation() && self.lock().reservation.is_some() {
+            let state = registry.get_volume_state(&request.uuid).await?;
+            let spec_clone = self
+                .start_update(registry, &state, VolumeOperation::ReleaseReservation)
+                .await?;
+            self.complete_update(registry, Ok(()), spec_clone).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl ResourcePausing for OperationGuardArc<VolumeSpec> {
+    type Pause = FreezeVolume;
+    type Resume = ThawVolume;
+
+    async fn pause(&mut self, registry: &Registry, request: &Self::Pause) -> Result<(), SvcError> {
+        if request.timeout.is_zero() {
+            return Err(SvcError::InvalidArguments {});
+        }
+        let spec = self.lock().clone();
+        let mut nexus = match registry
+            .specs()
+            .get_volume_target_nexus_guard(&spec)
+            .await?
+        {
+            Some(nexus) => nexus,
+            None => {
+                return Err(SvcError::VolumeNotPublished {
+                    vol_id: request.uuid.to_string(),
+                })
+            }
+        };
+
+        let pause = {
+            let nexus_spec = nexus.lock();
+            let thaw_deadline = SystemTime::now() + request.timeout;
+            PauseNexus::new(&nexus_spec.node, &nexus_spec.uuid, thaw_deadline)
+        };
+        nexus.pause(registry, &pause).await
+    }
+
+    async fn resume(
+        &mut self,
+        registry: &Registry,
+        request: &Self::Resume,
+    ) -> Result<(), SvcError> {
+        let spec = self.lock().clone();
+        let mut nexus = match registry
+            .specs()
+            .get_volume_target_nexus_guard(&spec)
+            .await?
+        {
+            Some(nexus) => nexus,
+            None => {
+                return Err(SvcError::VolumeNotPublished {
+                    vol_id: request.uuid.to_string(),
+                })
+            }
+        };
+
+        let resume = ResumeNexus::from(nexus.lock().deref());
+        nexus.resume(registry, &resume).await
     }
 }
 
@@ -392,7 +530,13 @@ impl ResourceReplicas for OperationGuardArc<VolumeSpec> {
                 .await?;
         } else {
             specs
-                .decrease_volume_replica(self, registry, state, spec_clone)
+                .decrease_volume_replica(
+                    self,
+                    registry,
+                    state,
+                    spec_clone,
+                    request.override_redundancy_check,
+                )
                 .await?;
         }
         Ok(())
@@ -1,13 +1,147 @@
-use crate::controller::registry::Registry;
+use crate::{
+    controller::{
+        conditions::volume_conditions,
+        history::spec_revision_diff,
+        orphans::creation_grace_left,
+        redundancy::RedundancyGuard,
+        registry::Registry,
+        wrapper::{NodeResourceStates, NodeWrapper},
+    },
+    volume::scheduling,
+};
 use common::errors::SvcError;
-use common_lib::types::v0::transport::{
-    NexusStatus, ReplicaTopology, Volume, VolumeId, VolumeState, VolumeStatus,
+use common_lib::{
+    transport_api::ResourceKind,
+    types::v0::transport::{
+        ChildState, ChildTopologyTree, CreateVolume, Nexus, NexusId, NexusStatus,
+        NexusTopologyTree, NodeId, NodeStatus, NodeTopologyTree, PoolId, PoolTopologyTree,
+        ReplicaId, ReplicaStatus, ReplicaTopology, ReplicaTopologyTree, Volume, VolumeCondition,
+        VolumeHistory, VolumeId, VolumeQuotaUsage, VolumeQuotasUsage, VolumeRevision,
+        VolumeRevisionDiff, VolumeState, VolumeStatus, VolumeStatusEntry, VolumeTopologyTree,
+    },
 };
 
 use crate::controller::reconciler::PollTriggerEvent;
-use common_lib::types::v0::store::{replica::ReplicaSpec, volume::VolumeSpec};
+use common_lib::types::v0::store::{
+    replica::ReplicaSpec, volume::VolumeSpec, OperationSequenceState, OperationSequencer,
+    ResourceMutex, SpecTransaction, TraceSpan,
+};
 use grpc::operations::{PaginatedResult, Pagination};
-use std::collections::HashMap;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::SystemTime,
+};
+use tokio::sync::{MutexGuard, RwLock};
+
+/// Get the status of the volume from the state of its target nexus, if it has one, or otherwise
+/// from the number of its replicas.
+fn volume_status(
+    volume_spec: &VolumeSpec,
+    nexus_state: Option<&Nexus>,
+    replicas: usize,
+) -> VolumeStatus {
+    match nexus_state {
+        Some(nexus_state) => match nexus_state.status {
+            NexusStatus::Online
+                if nexus_state.children.len() != volume_spec.num_replicas as usize =>
+            {
+                VolumeStatus::Degraded
+            }
+            _ => nexus_state.status.clone(),
+        },
+        None if volume_spec.target.is_none() => {
+            if replicas >= volume_spec.num_replicas as usize {
+                VolumeStatus::Online
+            } else if replicas == 0 {
+                VolumeStatus::Faulted
+            } else {
+                VolumeStatus::Degraded
+            }
+        }
+        None => VolumeStatus::Unknown,
+    }
+}
+
+/// The status of each node along with the states of its resources, which are the last known ones
+/// if the node is not online.
+struct KnownNodes(HashMap<NodeId, (NodeStatus, NodeResourceStates, bool)>);
+
+impl KnownNodes {
+    async fn new(nodes: Vec<Arc<RwLock<NodeWrapper>>>) -> Self {
+        let mut known = HashMap::with_capacity(nodes.len());
+        for node in nodes {
+            let node = node.read().await;
+            let (states, last_known) = node.known_states();
+            known.insert(node.id().clone(), (node.status(), states, last_known));
+        }
+        Self(known)
+    }
+
+    fn node(&self, id: &NodeId) -> NodeTopologyTree {
+        NodeTopologyTree {
+            id: id.clone(),
+            status: match self.0.get(id) {
+                Some((status, ..)) => status.clone(),
+                None => NodeStatus::Unknown,
+            },
+        }
+    }
+
+    /// The node where the replica was last seen.
+    fn replica_node(&self, id: &ReplicaId) -> Option<NodeId> {
+        self.0
+            .iter()
+            .find(|(_, (_, (replicas, ..), _))| replicas.iter().any(|r| &r.uuid == id))
+            .map(|(node, _)| node.clone())
+    }
+
+    fn pool(&self, id: &PoolId, node: &NodeId) -> PoolTopologyTree {
+        let known = self.0.get(node).and_then(|(_, (_, pools, _), last_known)| {
+            pools
+                .iter()
+                .find(|p| &p.id == id)
+                .map(|p| (p.status.clone(), *last_known))
+        });
+        let (status, last_known) = known.unwrap_or_default();
+        PoolTopologyTree {
+            id: id.clone(),
+            status,
+            last_known,
+            node: self.node(node),
+        }
+    }
+
+    fn replica(&self, id: &ReplicaId, pool: &PoolId, node: &NodeId) -> ReplicaTopologyTree {
+        let known = self
+            .0
+            .get(node)
+            .and_then(|(_, (replicas, ..), last_known)| {
+                replicas
+                    .iter()
+                    .find(|r| &r.uuid == id)
+                    .map(|r| (r.status.clone(), *last_known))
+            });
+        let (status, last_known) = known.unwrap_or_default();
+        ReplicaTopologyTree {
+            uuid: id.clone(),
+            status,
+            last_known,
+            pool: self.pool(pool, node),
+        }
+    }
+
+    fn nexus(&self, id: &NexusId, node: &NodeId) -> Option<(&Nexus, bool)> {
+        self.0
+            .get(node)
+            .and_then(|(_, (_, _, nexuses), last_known)| {
+                nexuses
+                    .iter()
+                    .find(|n| &n.uuid == id)
+                    .map(|n| (n, *last_known))
+            })
+    }
+}
 
 impl Registry {
     /// Get the volume state for the specified volume.
@@ -35,6 +169,9 @@ impl Registry {
             .collect::<Vec<_>>();
 
         let nexus_spec = self.specs().get_volume_target_nexus(volume_spec);
+        let frozen_until = nexus_spec
+            .as_ref()
+            .and_then(|spec| spec.lock().frozen_until);
         let nexus_state = match nexus_spec {
             None => None,
             Some(spec) => {
@@ -56,38 +193,183 @@ impl Registry {
             VolumeState {
                 uuid: volume_spec.uuid.to_owned(),
                 size: nexus_state.size,
-                status: match nexus_state.status {
-                    NexusStatus::Online
-                        if nexus_state.children.len() != volume_spec.num_replicas as usize =>
-                    {
-                        VolumeStatus::Degraded
-                    }
-                    _ => nexus_state.status.clone(),
-                },
+                status: volume_status(volume_spec, Some(&nexus_state), replica_specs.len()),
                 target: Some(nexus_state),
                 replica_topology,
+                conditions: self.volume_conditions(&volume_spec.uuid),
+                frozen_until,
             }
         } else {
             VolumeState {
                 uuid: volume_spec.uuid.to_owned(),
                 size: volume_spec.size,
-                status: if volume_spec.target.is_none() {
-                    if replica_specs.len() >= volume_spec.num_replicas as usize {
-                        VolumeStatus::Online
-                    } else if replica_specs.is_empty() {
-                        VolumeStatus::Faulted
-                    } else {
-                        VolumeStatus::Degraded
-                    }
-                } else {
-                    VolumeStatus::Unknown
-                },
+                status: volume_status(volume_spec, None, replica_specs.len()),
                 target: None,
                 replica_topology,
+                conditions: self.volume_conditions(&volume_spec.uuid),
+                frozen_until,
             }
         })
     }
 
+    /// Get the status of each of the given volumes, in the order given, with a single pass over
+    /// the replicas and nexuses rather than building the whole state of each volume.
+    /// The volumes which do not exist are reported without a status.
+    pub(crate) async fn get_volumes_status(&self, volumes: &[VolumeId]) -> Vec<VolumeStatusEntry> {
+        let mut replicas = HashMap::<VolumeId, usize>::new();
+        for replica in self.specs().get_cloned_replicas() {
+            if let Some(volume) = replica.owners.volume() {
+                *replicas.entry(volume.clone()).or_default() += 1;
+            }
+        }
+        let nexuses = self
+            .get_nexuses()
+            .await
+            .into_iter()
+            .map(|nexus| (nexus.uuid.clone(), nexus))
+            .collect::<HashMap<_, _>>();
+
+        volumes
+            .iter()
+            .map(|uuid| {
+                let status = self.specs().get_volume(uuid).ok().map(|spec| {
+                    let nexus_state = self
+                        .specs()
+                        .get_volume_target_nexus(&spec)
+                        .and_then(|nexus| nexuses.get(&nexus.lock().uuid));
+                    let replicas = replicas.get(uuid).cloned().unwrap_or_default();
+                    volume_status(&spec, nexus_state, replicas)
+                });
+                VolumeStatusEntry {
+                    uuid: uuid.clone(),
+                    status,
+                }
+            })
+            .collect()
+    }
+
+    /// Get the topology tree of the volume, from its target nexuses down to the nodes of its
+    /// replicas. The components on nodes which are not online carry their last known states.
+    pub(crate) async fn get_volume_topology(
+        &self,
+        volume_uuid: &VolumeId,
+    ) -> Result<VolumeTopologyTree, SvcError> {
+        let volume_spec = self.specs().get_volume(volume_uuid)?;
+        let status = self.get_volume_state(volume_uuid).await?.status;
+        let nodes = KnownNodes::new(self.get_node_wrappers().await).await;
+
+        let mut replicas = self
+            .specs()
+            .get_cloned_volume_replicas(volume_uuid)
+            .into_iter()
+            .map(|spec| {
+                let node = nodes
+                    .replica_node(&spec.uuid)
+                    .or_else(|| self.specs().get_pool(&spec.pool).ok().map(|p| p.node))
+                    .unwrap_or_default();
+                nodes.replica(&spec.uuid, &spec.pool, &node)
+            })
+            .collect::<Vec<_>>();
+
+        let mut nexuses = self
+            .specs()
+            .get_volume_nexuses(volume_uuid)
+            .into_iter()
+            .map(|nexus| nexus.lock().clone())
+            .collect::<Vec<_>>();
+        // the current target goes first, followed by any older ones which still linger
+        let target = volume_spec.target.as_ref().map(|t| t.nexus());
+        nexuses.sort_by_key(|nexus| Some(&nexus.uuid) != target);
+
+        let mut children = HashSet::new();
+        let targets = nexuses
+            .into_iter()
+            .map(|nexus| {
+                let state = nodes.nexus(&nexus.uuid, &nexus.node);
+                NexusTopologyTree {
+                    status: state
+                        .map(|(state, _)| state.status.clone())
+                        .unwrap_or_default(),
+                    last_known: state.map(|(_, last_known)| last_known).unwrap_or_default(),
+                    node: nodes.node(&nexus.node),
+                    children: nexus
+                        .children
+                        .iter()
+                        .map(|child| {
+                            let uri = child.uri();
+                            let replica = child.as_replica().and_then(|replica| {
+                                children.insert(replica.uuid().clone());
+                                replicas.iter().find(|r| &r.uuid == replica.uuid()).cloned()
+                            });
+                            ChildTopologyTree {
+                                state: state
+                                    .and_then(|(state, _)| {
+                                        state.children.iter().find(|c| c.uri == uri)
+                                    })
+                                    .map(|c| c.state.clone())
+                                    .unwrap_or_default(),
+                                uri,
+                                replica,
+                            }
+                        })
+                        .collect(),
+                    uuid: nexus.uuid,
+                }
+            })
+            .collect();
+        replicas.retain(|replica| !children.contains(&replica.uuid));
+
+        Ok(VolumeTopologyTree {
+            uuid: volume_uuid.clone(),
+            status,
+            targets,
+            replicas,
+        })
+    }
+
+    /// Get the revisions of the volume spec which are retained in its history, the oldest first.
+    pub(crate) async fn get_volume_history(
+        &self,
+        volume_uuid: &VolumeId,
+    ) -> Result<VolumeHistory, SvcError> {
+        self.specs().get_volume(volume_uuid)?;
+        let history = self
+            .spec_history(&ResourceKind::Volume, volume_uuid.as_str())
+            .await?;
+        let revisions = history
+            .revisions
+            .into_iter()
+            .map(|revision| VolumeRevision {
+                revision: revision.revision,
+                updated_at: revision.updated_at,
+                operation: revision.operation,
+            })
+            .collect();
+        Ok(VolumeHistory {
+            uuid: volume_uuid.clone(),
+            revisions,
+        })
+    }
+
+    /// Get the changes made to the volume spec by its `revision`, against the previous revision.
+    pub(crate) async fn get_volume_revision_diff(
+        &self,
+        volume_uuid: &VolumeId,
+        revision: u64,
+    ) -> Result<VolumeRevisionDiff, SvcError> {
+        self.specs().get_volume(volume_uuid)?;
+        let history = self
+            .spec_history(&ResourceKind::Volume, volume_uuid.as_str())
+            .await?;
+        let (previous, diff) = spec_revision_diff(&history, revision)?;
+        Ok(VolumeRevisionDiff {
+            uuid: volume_uuid.clone(),
+            revision,
+            previous,
+            diff,
+        })
+    }
+
     /// Construct a replica topology from a replica spec.
     /// If the replica cannot be found, return the default replica topology.
     async fn replica_topology(&self, spec: &ReplicaSpec) -> ReplicaTopology {
@@ -107,7 +389,7 @@ impl Registry {
         let mut volumes = Vec::with_capacity(volume_specs.len());
         for spec in volume_specs {
             if let Ok(state) = self.get_volume_state_with_replicas(&spec, &replicas).await {
-                volumes.push(Volume::new(spec, state));
+                volumes.push(self.volume_with_conditions(spec, state));
             }
         }
         volumes
@@ -123,7 +405,7 @@ impl Registry {
         let last = volume_specs.last();
         for spec in volume_specs.result() {
             if let Ok(state) = self.get_volume_state(&spec.uuid).await {
-                volumes.push(Volume::new(spec, state));
+                volumes.push(self.volume_with_conditions(spec, state));
             }
         }
         PaginatedResult::new(volumes, last)
@@ -131,16 +413,459 @@ impl Registry {
 
     /// Return a volume object corresponding to the ID.
     pub(crate) async fn get_volume(&self, id: &VolumeId) -> Result<Volume, SvcError> {
-        Ok(Volume::new(
+        Ok(self.volume_with_conditions(
             self.specs().get_volume(id)?,
             self.get_volume_state(id).await?,
         ))
     }
 
+    /// Construct the volume object from its `spec` and `state`, with its conditions.
+    fn volume_with_conditions(&self, spec: VolumeSpec, state: VolumeState) -> Volume {
+        let conditions = volume_conditions(&state.status, &state.conditions, SystemTime::now());
+        let conditions = self
+            .volume_transitions()
+            .lock()
+            .observe(&spec.uuid, conditions);
+        Volume::new(spec, state).with_conditions(conditions)
+    }
+
+    /// Observe the conditions of all the volumes, so the time of their last transition is kept
+    /// up to date even when they're not queried, and forget those of the deleted volumes.
+    pub(crate) async fn refresh_volume_transitions(&self) {
+        let volumes = self
+            .specs()
+            .get_volumes()
+            .into_iter()
+            .map(|spec| spec.uuid)
+            .collect::<Vec<_>>();
+        let now = SystemTime::now();
+        let observed = self
+            .get_volumes_status(&volumes)
+            .await
+            .into_iter()
+            .filter_map(|entry| {
+                let status = entry.status?;
+                let conditions =
+                    volume_conditions(&status, &self.volume_conditions(&entry.uuid), now);
+                Some((entry.uuid, conditions))
+            })
+            .collect::<Vec<_>>();
+
+        let mut transitions = self.volume_transitions().lock();
+        for (volume, conditions) in observed {
+            transitions.observe(&volume, conditions);
+        }
+        let volumes = volumes.into_iter().collect::<HashSet<_>>();
+        transitions.retain(|volume| volumes.contains(volume));
+    }
+
+    /// Get the device uri of the target of the volume, if it's published and its target exists
+    async fn volume_target_uri(&self, volume: &VolumeId) -> Option<String> {
+        let state = self.get_volume_state(volume).await.ok()?;
+        state.target.map(|target| target.device_uri)
+    }
+
+    /// Observe the device uri of the target of the volume, publishing its change, if any.
+    /// The uri is fetched whilst the targets are locked, so each change is only published once
+    /// regardless of how many concurrent observers see it.
+    pub(crate) async fn check_volume_target(&self, volume: &VolumeId) {
+        let mut targets = self.volume_targets().lock().await;
+        let uri = self.volume_target_uri(volume).await;
+        if let Some(event) = targets.observe(volume, uri) {
+            self.publish_volume_target_change(event);
+        }
+    }
+
+    /// Observe the device uris of the targets of all the volumes, publishing their changes, and
+    /// forget those of the deleted volumes.
+    pub(crate) async fn refresh_volume_targets(&self) {
+        let volumes = self
+            .specs()
+            .get_volumes()
+            .into_iter()
+            .map(|spec| spec.uuid)
+            .collect::<HashSet<_>>();
+        let mut targets = self.volume_targets().lock().await;
+        for volume in &volumes {
+            let uri = self.volume_target_uri(volume).await;
+            if let Some(event) = targets.observe(volume, uri) {
+                self.publish_volume_target_change(event);
+            }
+        }
+        targets.retain(|volume| volumes.contains(volume));
+    }
+
     /// Notify the reconcilers if the volume is degraded
     pub(crate) async fn notify_if_degraded(&self, volume: &Volume, event: PollTriggerEvent) {
         if volume.status() == Some(VolumeStatus::Degraded) {
             self.notify(event).await;
         }
     }
+
+    /// Check each volume spec against the runtime state, updating the conditions of the volumes.
+    /// Conditions which are raised or cleared since the previous check are logged.
+    pub(crate) async fn check_volume_conditions(&self) {
+        let replicas = self.specs().get_cloned_replicas();
+        let mut conditions = HashMap::new();
+        for volume in self.specs().get_locked_volumes() {
+            let volume_conditions = self.volume_divergence(&volume, &replicas).await;
+            if !volume_conditions.is_empty() {
+                conditions.insert(volume.immutable_peek().uuid.clone(), volume_conditions);
+            }
+        }
+
+        let previous = self.replace_volume_conditions(conditions.clone());
+        for (volume, raised) in &conditions {
+            let previous = previous.get(volume);
+            for condition in raised {
+                if !previous.map(|p| p.contains(condition)).unwrap_or(false) {
+                    tracing::warn!(volume.uuid = %volume, condition = %condition.to_string(), "Volume condition raised");
+                }
+            }
+        }
+        for (volume, cleared) in &previous {
+            let current = conditions.get(volume);
+            for condition in cleared {
+                if !current.map(|c| c.contains(condition)).unwrap_or(false) {
+                    tracing::info!(volume.uuid = %volume, condition = %condition.to_string(), "Volume condition cleared");
+                }
+            }
+        }
+    }
+
+    /// Get the conditions where the runtime state of the volume diverges from its spec.
+    /// Volumes which are being created or deleted have no conditions.
+    async fn volume_divergence(
+        &self,
+        volume: &ResourceMutex<VolumeSpec>,
+        replicas: &[ReplicaSpec],
+    ) -> Vec<VolumeCondition> {
+        let volume_spec = volume.lock().clone();
+        if !volume_spec.status.created() {
+            return vec![];
+        }
+        let mut conditions = vec![];
+
+        let nexus_spec = self.specs().get_volume_target_nexus(&volume_spec);
+        if volume_spec.target.is_some() {
+            let nexus_state = match &nexus_spec {
+                Some(nexus) => {
+                    let nexus_id = nexus.lock().uuid.clone();
+                    self.get_nexus(&nexus_id).await.ok()
+                }
+                None => None,
+            };
+            match nexus_state {
+                Some(nexus) => {
+                    if self
+                        .child_size_divergence(&volume_spec, &nexus, replicas)
+                        .await
+                    {
+                        conditions.push(VolumeCondition::ChildSizeMismatch);
+                    }
+                }
+                None => conditions.push(VolumeCondition::TargetMissing),
+            }
+        }
+
+        for replica in replicas
+            .iter()
+            .filter(|r| r.owners.owned_by(&volume_spec.uuid))
+        {
+            if self.get_replica(&replica.uuid).await.is_err()
+                && creation_grace_left(
+                    replica.created_at,
+                    self.replica_creation_grace_period(),
+                    SystemTime::now(),
+                )
+                .is_none()
+            {
+                conditions.push(VolumeCondition::ReplicaMissing);
+                break;
+            }
+        }
+
+        let nexus_uuid = nexus_spec
+            .as_ref()
+            .map(|nexus| nexus.lock().uuid.to_string());
+        // a pending operation is only left behind if no operation is still in progress
+        let volume_dirty =
+            volume_spec.pending_op() && volume.valid(OperationSequenceState::Exclusive);
+        let nexus_dirty = nexus_spec
+            .map(|nexus| {
+                nexus.lock().pending_op() && nexus.valid(OperationSequenceState::Exclusive)
+            })
+            .unwrap_or(false);
+        if volume_dirty || nexus_dirty {
+            conditions.push(VolumeCondition::SpecDirty);
+        }
+        let replay_stuck = {
+            let replays = self.spec_replays().lock();
+            replays.stuck(&ResourceKind::Volume, &volume_spec.uuid.to_string())
+                || nexus_uuid
+                    .map(|nexus| replays.stuck(&ResourceKind::Nexus, &nexus))
+                    .unwrap_or(false)
+        };
+        if replay_stuck {
+            conditions.push(VolumeCondition::ReplayStuck);
+        }
+
+        conditions.extend(self.topology_divergence(&volume_spec, replicas).await);
+        conditions
+    }
+
+    /// Check whether any child of the volume's target `nexus` is backed by a replica smaller than
+    /// the nexus, logging each such child when the mismatch is first found.
+    async fn child_size_divergence(
+        &self,
+        volume_spec: &VolumeSpec,
+        nexus: &Nexus,
+        replicas: &[ReplicaSpec],
+    ) -> bool {
+        let mut replica_states = vec![];
+        for replica in replicas
+            .iter()
+            .filter(|r| r.owners.owned_by(&volume_spec.uuid))
+        {
+            if let Ok(state) = self.get_replica(&replica.uuid).await {
+                replica_states.push(state);
+            }
+        }
+        let undersized = crate::nexus::undersized_children(nexus, &replica_states);
+        if undersized.is_empty() {
+            return false;
+        }
+        let raised = self
+            .volume_conditions(&volume_spec.uuid)
+            .contains(&VolumeCondition::ChildSizeMismatch);
+        if !raised {
+            for (child, size) in &undersized {
+                volume_spec.warn_span(|| {
+                    tracing::warn!(
+                        child.uri = %child,
+                        child.size = size,
+                        nexus.uuid = %nexus.uuid,
+                        nexus.size = nexus.size,
+                        "Nexus child is backed by a replica smaller than the nexus"
+                    )
+                });
+            }
+        }
+        true
+    }
+
+    /// Get the conditions of the volume's placement topology: whether the missing replicas of the
+    /// volume can only be placed regardless of its topology, and whether any of its existing
+    /// replicas already has been.
+    async fn topology_divergence(
+        &self,
+        volume_spec: &VolumeSpec,
+        replicas: &[ReplicaSpec],
+    ) -> Vec<VolumeCondition> {
+        let mut conditions = vec![];
+        if volume_spec.topology.is_none() {
+            return conditions;
+        }
+        let volume_replicas = replicas
+            .iter()
+            .filter(|r| r.owners.owned_by(&volume_spec.uuid))
+            .collect::<Vec<_>>();
+
+        if volume_replicas.len() < volume_spec.num_replicas as usize
+            && scheduling::get_volume_pool_candidates(volume_spec, self)
+                .await
+                .is_empty()
+            && !scheduling::get_volume_relaxed_pool_candidates(volume_spec, self)
+                .await
+                .is_empty()
+        {
+            conditions.push(VolumeCondition::TopologyUnsatisfiable);
+        }
+
+        let topology_pools = scheduling::get_volume_topology_pools(volume_spec, self).await;
+        for replica in volume_replicas {
+            // the pools of the missing replicas are not known to the scheduler
+            if self.get_replica(&replica.uuid).await.is_err() {
+                continue;
+            }
+            if !topology_pools.contains(&replica.pool) {
+                conditions.push(VolumeCondition::TopologyRelaxed);
+                break;
+            }
+        }
+        conditions
+    }
+
+    /// Get the capacity provisioned by the volumes tagged with the label of each quota.
+    pub(crate) fn volume_quotas_usage(&self) -> VolumeQuotasUsage {
+        let volumes = self.specs().get_volumes();
+        let quotas = self
+            .volume_quotas()
+            .quotas
+            .into_iter()
+            .map(|quota| VolumeQuotaUsage {
+                used: volumes
+                    .iter()
+                    .filter(|volume| quota.applies_to(&volume.labels))
+                    .map(|volume| volume.size)
+                    .sum(),
+                quota,
+            })
+            .collect();
+        VolumeQuotasUsage { quotas }
+    }
+
+    /// Check that the volume to be created does not take the capacity provisioned by the volumes
+    /// tagged with the label of any quota over that quota.
+    /// When a quota applies, the returned guard must be held until the volume spec is created, so
+    /// that concurrent creations can't fit within the quota each on their own yet exceed it
+    /// together.
+    pub(crate) async fn check_volume_quotas(
+        &self,
+        request: &CreateVolume,
+    ) -> Result<Option<MutexGuard<'_, ()>>, SvcError> {
+        if !self
+            .volume_quotas()
+            .quotas
+            .iter()
+            .any(|quota| quota.applies_to(&request.labels))
+        {
+            return Ok(None);
+        }
+        let guard = self.volume_quotas_lock().lock().await;
+        let volumes = self.specs().get_volumes();
+        for quota in self.volume_quotas().quotas {
+            if !quota.applies_to(&request.labels) {
+                continue;
+            }
+            // a retried creation must not count the volume twice
+            let used = volumes
+                .iter()
+                .filter(|volume| volume.uuid != request.uuid && quota.applies_to(&volume.labels))
+                .map(|volume| volume.size)
+                .sum::<u64>();
+            if used + request.size > quota.capacity {
+                return Err(SvcError::VolumeQuotaExceeded {
+                    id: request.uuid.to_string(),
+                    size: request.size,
+                    key: quota.key,
+                    value: quota.value,
+                    used,
+                    capacity: quota.capacity,
+                });
+            }
+        }
+        Ok(Some(guard))
+    }
+
+    /// Get the replicas which hold an up to date copy of the volume data: the online replicas
+    /// which, when the state of the volume target is known, are healthy children of it.
+    async fn healthy_volume_replicas(&self, volume: &VolumeSpec) -> HashSet<ReplicaId> {
+        let target = match self.specs().get_volume_target_nexus(volume) {
+            Some(nexus) => {
+                let nexus = nexus.lock().clone();
+                self.get_nexus(&nexus.uuid)
+                    .await
+                    .ok()
+                    .map(|state| (nexus, state))
+            }
+            None => None,
+        };
+        let mut healthy = HashSet::new();
+        for replica in self.specs().get_cloned_volume_replicas(&volume.uuid) {
+            let online = match self.get_replica(&replica.uuid).await {
+                Ok(state) => state.status == ReplicaStatus::Online,
+                Err(_) => false,
+            };
+            let in_sync = match &target {
+                None => true,
+                Some((nexus, state)) => nexus
+                    .children
+                    .iter()
+                    .filter_map(|child| child.as_replica())
+                    .find(|child| child.uuid() == &replica.uuid)
+                    .map_or(false, |child| {
+                        state
+                            .children
+                            .iter()
+                            .any(|c| &c.uri == child.uri() && c.state == ChildState::Online)
+                    }),
+            };
+            if online && in_sync {
+                healthy.insert(replica.uuid);
+            }
+        }
+        healthy
+    }
+
+    /// Check that removing the `replicas` leaves each of their volumes with at least the minimum
+    /// replication factor of healthy replicas, accounting for the replicas which other in-flight
+    /// operations are removing. Replicas which don't belong to a volume are ignored.
+    /// The removals are reserved until the returned guard is dropped, so it must be held until the
+    /// `operation` completes. With `override_check` the removals go ahead regardless, but are
+    /// logged.
+    pub(crate) async fn check_redundancy(
+        &self,
+        operation: &str,
+        replicas: &[ReplicaSpec],
+        override_check: bool,
+    ) -> Result<RedundancyGuard, SvcError> {
+        let mut removals = HashMap::<VolumeId, HashSet<ReplicaId>>::new();
+        for replica in replicas {
+            if let Some(volume) = replica.owners.volume() {
+                removals
+                    .entry(volume.clone())
+                    .or_default()
+                    .insert(replica.uuid.clone());
+            }
+        }
+        let mut healthy = HashMap::new();
+        for volume in removals.keys() {
+            if let Ok(spec) = self.specs().get_volume(volume) {
+                healthy.insert(volume.clone(), self.healthy_volume_replicas(&spec).await);
+            }
+        }
+
+        let min = self.min_replication_factor() as usize;
+        let mut pending = self.pending_removals().lock();
+        let mut below = removals
+            .iter()
+            .filter_map(|(volume, removed)| {
+                let healthy = healthy.get(volume)?;
+                let pending = pending.volume(volume);
+                let is_pending = |r: &ReplicaId| pending.map_or(false, |p| p.contains(r));
+                // removing replicas which are not healthy, or which are already being removed,
+                // doesn't lower the redundancy of the volume any further
+                let lowers = healthy
+                    .iter()
+                    .any(|r| removed.contains(r) && !is_pending(r));
+                let remaining = healthy
+                    .iter()
+                    .filter(|r| !removed.contains(r) && !is_pending(r))
+                    .count();
+                (lowers && remaining < min).then(|| volume.to_string())
+            })
+            .collect::<Vec<_>>();
+
+        if !below.is_empty() {
+            below.sort();
+            if !override_check {
+                return Err(SvcError::BelowMinReplicationFactor {
+                    operation: operation.to_string(),
+                    volumes: below,
+                    min: self.min_replication_factor(),
+                });
+            }
+            tracing::warn!(
+                operation,
+                volumes = ?below,
+                min_replication_factor = min,
+                "Overriding the redundancy check, the volumes are left below the minimum replication factor"
+            );
+        }
+        for (volume, replicas) in &removals {
+            pending.add(volume, replicas);
+        }
+        Ok(RedundancyGuard::new(self, removals))
+    }
 }
@@ -4,14 +4,18 @@ use crate::controller::{
         nexus,
         nexus::GetPersistedNexusChildren,
         resources::HealthyChildItems,
-        volume,
-        volume::{GetChildForRemoval, GetSuitablePools},
+        spread_label, volume,
+        volume::{GetChildForRemoval, GetSuitablePools, PlacementConstraint},
         ResourceFilter,
     },
     wrapper::PoolWrapper,
 };
 use common::errors::SvcError;
-use common_lib::types::v0::store::{nexus::NexusSpec, volume::VolumeSpec};
+use common_lib::types::v0::{
+    store::{nexus::NexusSpec, volume::VolumeSpec},
+    transport::PoolId,
+};
+use std::collections::HashSet;
 
 /// Return a list of pre sorted pools to be used by a volume
 pub(crate) async fn get_volume_pool_candidates(
@@ -26,6 +30,79 @@ pub(crate) async fn get_volume_pool_candidates(
         .collect()
 }
 
+/// Return a list of pre sorted pools to be used by a volume, disregarding its placement topology
+pub(crate) async fn get_volume_relaxed_pool_candidates(
+    request: impl Into<GetSuitablePools>,
+    registry: &Registry,
+) -> Vec<PoolWrapper> {
+    volume::AddVolumeReplica::builder_without_topology(request, registry)
+        .await
+        .collect()
+        .into_iter()
+        .map(|e| e.collect())
+        .collect()
+}
+
+/// Return a list of pre sorted pools to be used by a volume, disregarding the `relaxed` placement
+/// constraints of its topology
+pub(crate) async fn get_volume_constrained_pool_candidates(
+    request: impl Into<GetSuitablePools>,
+    registry: &Registry,
+    relaxed: &[PlacementConstraint],
+) -> Vec<PoolWrapper> {
+    volume::AddVolumeReplica::builder_with_relaxed(request, registry, relaxed)
+        .await
+        .collect()
+        .into_iter()
+        .map(|e| e.collect())
+        .collect()
+}
+
+/// Order the `pools` so that the first pool of each value of the spread label of the volume's
+/// topology, which no replica of the volume uses yet, comes first, followed by the others in their
+/// order. The replicas placed in a single pass are then spread across the values, as far as there
+/// are enough of them.
+pub(crate) fn spread_pool_candidates(
+    registry: &Registry,
+    spec: &VolumeSpec,
+    pools: Vec<PoolWrapper>,
+) -> Vec<PoolWrapper> {
+    let key = match spec.topology.as_ref().and_then(|t| t.spread.as_ref()) {
+        Some(key) => key,
+        None => return pools,
+    };
+    let specs = registry.specs();
+    let value = |pool: &PoolId| {
+        let pool = specs.get_pool(pool).ok()?;
+        spread_label(registry, key, &pool)
+    };
+    let mut used = specs
+        .get_cloned_volume_replicas(&spec.uuid)
+        .iter()
+        .filter_map(|replica| value(&replica.pool))
+        .collect::<HashSet<_>>();
+    let (spread, rest): (Vec<_>, Vec<_>) =
+        pools.into_iter().partition(|pool| match value(&pool.id) {
+            Some(value) => used.insert(value),
+            None => false,
+        });
+    spread.into_iter().chain(rest).collect()
+}
+
+/// Return the pools which satisfy the volume's placement topology, irrespective of their
+/// capacity or of the state of their nodes
+pub(crate) async fn get_volume_topology_pools(
+    request: impl Into<GetSuitablePools>,
+    registry: &Registry,
+) -> Vec<PoolId> {
+    volume::AddVolumeReplica::builder_with_topology(request, registry)
+        .await
+        .collect()
+        .into_iter()
+        .map(|e| e.collect().id)
+        .collect()
+}
+
 /// Return a volume child candidate to be removed from a volume
 /// This list includes healthy and non_healthy candidates, so care must be taken to
 /// make sure we don't remove "too many healthy" candidates and make the volume degraded
@@ -1,16 +1,31 @@
-use crate::controller::{
-    operations::{ResourceLifecycle, ResourcePublishing, ResourceReplicas, ResourceSharing},
-    registry::Registry,
-    specs::ResourceSpecsLocked,
+use crate::{
+    controller::{
+        operations::{
+            ResourceLifecycle, ResourcePausing, ResourcePublishing, ResourceReplicas,
+            ResourceSharing,
+        },
+        reconciler::PollTriggerEvent,
+        registry::Registry,
+        sorting::sort,
+        specs::ResourceSpecsLocked,
+    },
+    volume::specs::{
+        check_create_volume_allowed_nodes, check_create_volume_pools, check_create_volume_quorum,
+        check_create_volume_replicas,
+    },
 };
 use common::errors::SvcError;
 use common_lib::{
-    transport_api::{v0::Volumes, ReplyError},
+    transport_api::{v0::Volumes, ReplyError, ResourceKind},
     types::v0::{
         store::{volume::VolumeSpec, OperationGuardArc},
         transport::{
-            CreateVolume, DestroyVolume, Filter, GetVolumes, PublishVolume, SetVolumeReplica,
-            ShareVolume, UnpublishVolume, UnshareVolume, Volume,
+            CreateVolume, DestroyVolume, Filter, FreezeVolume, GetVolumeHistory,
+            GetVolumeRevisionDiff, GetVolumeTopology, GetVolumes, GetVolumesStatus, PatchVolume,
+            PauseVolumeRebuild, PublishVolume, ResumeVolumeRebuild, SetVolumeReplica,
+            SetVolumeSourceReplica, ShareVolume, ThawVolume, UnpublishVolume, UnshareVolume,
+            Volume, VolumeHistory, VolumeId, VolumeRevisionDiff, VolumeTopologyTree,
+            VolumeViolation, VolumeViolations, VolumesStatus,
         },
     },
 };
@@ -18,13 +33,110 @@ use grpc::{
     context::Context,
     operations::{
         volume::traits::{
-            CreateVolumeInfo, DestroyVolumeInfo, PublishVolumeInfo, SetVolumeReplicaInfo,
-            ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
+            CreateVolumeInfo, DestroyVolumeInfo, FreezeVolumeInfo, GetVolumeHistoryInfo,
+            GetVolumeRevisionDiffInfo, GetVolumeTopologyInfo, GetVolumesStatusInfo,
+            PatchVolumeInfo, PauseVolumeRebuildInfo, PublishVolumeInfo, ResumeVolumeRebuildInfo,
+            SetVolumeReplicaInfo, SetVolumeSourceReplicaInfo, ShareVolumeInfo, ThawVolumeInfo,
+            UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
         },
-        Pagination,
+        PaginatedResult, Pagination, Sorting,
     },
 };
 
+/// Block size which the volume sizes are rounded up to a multiple of.
+/// The pools don't report their block size, so the largest one they may have is assumed, which is
+/// a multiple of the smaller one.
+const VOLUME_BLOCK_SIZE: u64 = 4096;
+
+/// Get the effective size of the volume `id` for the requested `size`, rounded up to a multiple
+/// of the block size, rejecting the sizes smaller than `min_size` or larger than `max_size`.
+fn effective_volume_size(
+    id: &VolumeId,
+    size: u64,
+    min_size: u64,
+    max_size: Option<u64>,
+) -> Result<u64, SvcError> {
+    if size < min_size {
+        return Err(SvcError::VolumeTooSmall {
+            id: id.to_string(),
+            size,
+            min_size,
+        });
+    }
+    let rounded = size
+        .checked_add(VOLUME_BLOCK_SIZE - 1)
+        .map(|size| size / VOLUME_BLOCK_SIZE * VOLUME_BLOCK_SIZE);
+    match rounded {
+        Some(rounded) if max_size.map_or(true, |max_size| rounded <= max_size) => Ok(rounded),
+        _ => Err(SvcError::VolumeTooLarge {
+            id: id.to_string(),
+            size,
+            max_size: max_size.unwrap_or(u64::MAX),
+        }),
+    }
+}
+
+/// Check the volume creation `request` against every rule which doesn't depend on the state of
+/// the cluster, returning the field which violates each of the failed rules with its error.
+fn create_volume_violations(
+    registry: &Registry,
+    request: &CreateVolume,
+) -> Vec<(&'static str, SvcError)> {
+    let size = effective_volume_size(
+        &request.uuid,
+        request.size,
+        registry.min_volume_size(),
+        registry.max_volume_size(),
+    )
+    .map(|_| ());
+    vec![
+        ("size", size),
+        ("replicas", check_create_volume_replicas(request)),
+        ("quorum", check_create_volume_quorum(request)),
+        ("pools", check_create_volume_pools(request)),
+        ("topology", check_create_volume_allowed_nodes(request)),
+    ]
+    .into_iter()
+    .filter_map(|(field, result)| result.err().map(|error| (field, error)))
+    .collect()
+}
+
+/// Sort the given volumes by one of their sortable fields.
+fn sort_volumes(volumes: &mut [Volume], sorting: &Sorting) -> Result<(), SvcError> {
+    sort(
+        ResourceKind::Volume,
+        volumes,
+        sorting,
+        &[
+            ("uuid", &|a, b| a.uuid().as_str().cmp(b.uuid().as_str())),
+            ("size", &|a, b| a.spec().size.cmp(&b.spec().size)),
+            ("replicas", &|a, b| {
+                a.spec().num_replicas.cmp(&b.spec().num_replicas)
+            }),
+            ("status", &|a, b| {
+                let status = |volume: &Volume| volume.status().map(|status| status.to_string());
+                status(a).cmp(&status(b))
+            }),
+            ("created_at", &|a, b| {
+                a.spec().created_at.cmp(&b.spec().created_at)
+            }),
+        ],
+    )
+}
+
+/// Get the page of the given volumes selected by the `pagination`.
+fn paginate(volumes: Vec<Volume>, pagination: &Pagination) -> PaginatedResult<Volume> {
+    let num_volumes = volumes.len() as u64;
+    let offset = std::cmp::min(pagination.starting_token(), num_volumes);
+    let last_result = offset + pagination.max_entries() >= num_volumes;
+    let volumes = volumes
+        .into_iter()
+        .skip(offset as usize)
+        .take(pagination.max_entries() as usize)
+        .collect();
+    PaginatedResult::new(volumes, last_result)
+}
+
 #[derive(Debug, Clone)]
 pub(super) struct Service {
     registry: Registry,
@@ -44,17 +156,68 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
-    async fn get(
+    async fn validate_create(
+        &self,
+        req: &dyn CreateVolumeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeViolations, ReplyError> {
+        let create_volume = req.into();
+        let violations = self.validate_create_volume(&create_volume).await?;
+        Ok(violations)
+    }
+
+    async fn get_sorted(
         &self,
         filter: Filter,
+        sorting: Option<Sorting>,
         pagination: Option<Pagination>,
         _ctx: Option<Context>,
     ) -> Result<Volumes, ReplyError> {
         let req = GetVolumes { filter };
-        let volumes = self.get_volumes(&req, pagination).await?;
+        let volumes = self.get_volumes(&req, sorting, pagination).await?;
         Ok(volumes)
     }
 
+    async fn get_status(
+        &self,
+        req: &dyn GetVolumesStatusInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumesStatus, ReplyError> {
+        let req = req.into();
+        let status = self.get_volumes_status(&req).await?;
+        Ok(status)
+    }
+
+    async fn get_topology(
+        &self,
+        req: &dyn GetVolumeTopologyInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeTopologyTree, ReplyError> {
+        let req = req.into();
+        let topology = self.get_volume_topology(&req).await?;
+        Ok(topology)
+    }
+
+    async fn get_history(
+        &self,
+        req: &dyn GetVolumeHistoryInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeHistory, ReplyError> {
+        let req = req.into();
+        let history = self.get_volume_history(&req).await?;
+        Ok(history)
+    }
+
+    async fn get_revision_diff(
+        &self,
+        req: &dyn GetVolumeRevisionDiffInfo,
+        _ctx: Option<Context>,
+    ) -> Result<VolumeRevisionDiff, ReplyError> {
+        let req = req.into();
+        let diff = self.get_volume_revision_diff(&req).await?;
+        Ok(diff)
+    }
+
     async fn destroy(
         &self,
         req: &dyn DestroyVolumeInfo,
@@ -127,6 +290,83 @@ impl VolumeOperations for Service {
         Ok(volume)
     }
 
+    async fn set_source_replica(
+        &self,
+        req: &dyn SetVolumeSourceReplicaInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let set_source_replica = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(
+                async move { service.set_volume_source_replica(&set_source_replica).await },
+            )
+            .await??;
+        Ok(volume)
+    }
+
+    async fn patch(
+        &self,
+        req: &dyn PatchVolumeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let patch_volume = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.patch_volume(&patch_volume).await }).await??;
+        Ok(volume)
+    }
+
+    async fn freeze(
+        &self,
+        req: &dyn FreezeVolumeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let freeze_volume = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.freeze_volume(&freeze_volume).await }).await??;
+        Ok(volume)
+    }
+
+    async fn thaw(
+        &self,
+        req: &dyn ThawVolumeInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let thaw_volume = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.thaw_volume(&thaw_volume).await }).await??;
+        Ok(volume)
+    }
+
+    async fn pause_rebuild(
+        &self,
+        req: &dyn PauseVolumeRebuildInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let pause_rebuild = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.pause_volume_rebuild(&pause_rebuild).await })
+                .await??;
+        Ok(volume)
+    }
+
+    async fn resume_rebuild(
+        &self,
+        req: &dyn ResumeVolumeRebuildInfo,
+        _ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let resume_rebuild = req.into();
+        let service = self.clone();
+        let volume =
+            Context::spawn(async move { service.resume_volume_rebuild(&resume_rebuild).await })
+                .await??;
+        Ok(volume)
+    }
+
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         return Ok(true);
     }
@@ -145,6 +385,7 @@ impl Service {
     pub(super) async fn get_volumes(
         &self,
         request: &GetVolumes,
+        sorting: Option<Sorting>,
         pagination: Option<Pagination>,
     ) -> Result<Volumes, SvcError> {
         // The last result can only ever be false if using pagination.
@@ -152,20 +393,39 @@ impl Service {
 
         // The filter criteria is matched against a volume state.
         let filtered_volumes = match &request.filter {
-            Filter::None => match &pagination {
-                Some(p) => {
+            Filter::None => match (&sorting, &pagination) {
+                // all the volumes must be sorted before the page is taken out of them
+                (Some(sorting), pagination) => {
+                    let mut volumes = self.registry.get_volumes().await;
+                    sort_volumes(&mut volumes, sorting)?;
+                    match pagination {
+                        Some(p) => {
+                            let paginated_volumes = paginate(volumes, p);
+                            last_result = paginated_volumes.last();
+                            paginated_volumes.result()
+                        }
+                        None => volumes,
+                    }
+                }
+                (None, Some(p)) => {
                     let paginated_volumes = self.registry.get_paginated_volume(p).await;
                     last_result = paginated_volumes.last();
                     paginated_volumes.result()
                 }
-                None => self.registry.get_volumes().await,
+                (None, None) => self.registry.get_volumes().await,
             },
             Filter::Volume(volume_id) => {
                 tracing::Span::current().record("volume.uuid", &volume_id.as_str());
-                vec![self.registry.get_volume(volume_id).await?]
+                let mut volumes = vec![self.registry.get_volume(volume_id).await?];
+                // a single volume is trivially sorted, but the sort field must still be valid
+                if let Some(sorting) = &sorting {
+                    sort_volumes(&mut volumes, sorting)?;
+                }
+                volumes
             }
             filter => {
                 return Err(SvcError::InvalidFilter {
+                    resource: ResourceKind::Volume,
                     filter: filter.clone(),
                 })
             }
@@ -180,11 +440,111 @@ impl Service {
         })
     }
 
+    /// Get the status of the requested volumes, or of all the volumes if none are requested.
+    #[tracing::instrument(level = "info", skip(self), err)]
+    pub(super) async fn get_volumes_status(
+        &self,
+        request: &GetVolumesStatus,
+    ) -> Result<VolumesStatus, SvcError> {
+        let volumes = match request.volumes.is_empty() {
+            true => self
+                .specs()
+                .get_volumes()
+                .into_iter()
+                .map(|volume| volume.uuid)
+                .collect(),
+            false => request.volumes.clone(),
+        };
+        let entries = self.registry.get_volumes_status(&volumes).await;
+        Ok(VolumesStatus { entries })
+    }
+
+    /// Get the topology tree of a volume.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn get_volume_topology(
+        &self,
+        request: &GetVolumeTopology,
+    ) -> Result<VolumeTopologyTree, SvcError> {
+        self.registry.get_volume_topology(&request.uuid).await
+    }
+
+    /// Get the revisions of a volume spec which are retained in its history.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn get_volume_history(
+        &self,
+        request: &GetVolumeHistory,
+    ) -> Result<VolumeHistory, SvcError> {
+        self.registry.get_volume_history(&request.uuid).await
+    }
+
+    /// Get the changes made to a volume spec by one of its revisions.
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn get_volume_revision_diff(
+        &self,
+        request: &GetVolumeRevisionDiff,
+    ) -> Result<VolumeRevisionDiff, SvcError> {
+        self.registry
+            .get_volume_revision_diff(&request.uuid, request.revision)
+            .await
+    }
+
+    /// Apply the cluster defaults and the default topology to the volume creation `request`.
+    fn create_volume_request(&self, request: &CreateVolume) -> CreateVolume {
+        let mut request = request
+            .clone()
+            .with_defaults(&self.registry.cluster_defaults());
+        if request.topology.is_none() {
+            request.topology = self.registry.default_topology().cloned();
+        }
+        request
+    }
+
+    /// Validate the volume creation `request` without creating the volume, returning all the
+    /// rules which it violates rather than only the first one.
+    #[tracing::instrument(level = "debug", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn validate_create_volume(
+        &self,
+        request: &CreateVolume,
+    ) -> Result<VolumeViolations, SvcError> {
+        let request = self.create_volume_request(request);
+        let violations = create_volume_violations(&self.registry, &request)
+            .into_iter()
+            .map(|(field, error)| VolumeViolation {
+                field: field.to_string(),
+                message: error.to_string(),
+            })
+            .collect();
+        Ok(VolumeViolations { violations })
+    }
+
     /// Create a volume using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn create_volume(&self, request: &CreateVolume) -> Result<Volume, SvcError> {
+        let mut request = self.create_volume_request(request);
+        if let Some((_, error)) = create_volume_violations(&self.registry, &request)
+            .into_iter()
+            .next()
+        {
+            return Err(error);
+        }
+        let size = effective_volume_size(
+            &request.uuid,
+            request.size,
+            self.registry.min_volume_size(),
+            self.registry.max_volume_size(),
+        )?;
+        request.requested_size = Some(request.size);
+        request.size = size;
+        let request = &request;
+        let quota_guard = self.registry.check_volume_quotas(request).await?;
         OperationGuardArc::<VolumeSpec>::create(&self.registry, request).await?;
-        self.registry.get_volume(&request.uuid).await
+        drop(quota_guard);
+        let volume = self.registry.get_volume(&request.uuid).await?;
+        // a best effort placement may leave the volume with fewer replicas than requested
+        self.registry
+            .notify_if_degraded(&volume, PollTriggerEvent::VolumeDegraded)
+            .await;
+        Ok(volume)
     }
 
     /// Destroy a volume using the given parameters.
@@ -199,21 +559,27 @@ impl Service {
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn share_volume(&self, request: &ShareVolume) -> Result<String, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
-        volume.share(&self.registry, request).await
+        let uri = volume.share(&self.registry, request).await?;
+        self.registry.check_volume_target(&request.uuid).await;
+        Ok(uri)
     }
 
     /// Unshare a volume using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn unshare_volume(&self, request: &UnshareVolume) -> Result<(), SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
-        volume.unshare(&self.registry, request).await
+        volume.unshare(&self.registry, request).await?;
+        self.registry.check_volume_target(&request.uuid).await;
+        Ok(())
     }
 
     /// Publish a volume using the given parameters.
     #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
     pub(super) async fn publish_volume(&self, request: &PublishVolume) -> Result<Volume, SvcError> {
         let mut volume = self.specs().volume(&request.uuid).await?;
-        volume.publish(&self.registry, request).await
+        let volume = volume.publish(&self.registry, request).await?;
+        self.registry.check_volume_target(&request.uuid).await;
+        Ok(volume)
     }
 
     /// Unpublish a volume using the given parameters.
@@ -237,4 +603,110 @@ impl Service {
         volume.set_replica(&self.registry, request).await?;
         self.registry.get_volume(&request.uuid).await
     }
+
+    /// Set the source replica of the volume
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn set_volume_source_replica(
+        &self,
+        request: &SetVolumeSourceReplica,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        self.specs()
+            .set_volume_source_replica(&mut volume, &self.registry, request)
+            .await
+    }
+
+    /// Update the mutable parameters of the volume
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn patch_volume(&self, request: &PatchVolume) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        self.specs()
+            .patch_volume(&mut volume, &self.registry, request)
+            .await
+    }
+
+    /// Freeze the IO of the volume
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn freeze_volume(&self, request: &FreezeVolume) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.pause(&self.registry, request).await?;
+        self.registry.get_volume(&request.uuid).await
+    }
+
+    /// Thaw the frozen IO of the volume
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn thaw_volume(&self, request: &ThawVolume) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        volume.resume(&self.registry, request).await?;
+        self.registry.get_volume(&request.uuid).await
+    }
+
+    /// Pause the rebuilds of the volume replicas
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn pause_volume_rebuild(
+        &self,
+        request: &PauseVolumeRebuild,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        self.specs()
+            .pause_volume_rebuild(&mut volume, &self.registry, request)
+            .await
+    }
+
+    /// Resume the paused rebuilds of the volume replicas
+    #[tracing::instrument(level = "info", skip(self), err, fields(volume.uuid = %request.uuid))]
+    pub(super) async fn resume_volume_rebuild(
+        &self,
+        request: &ResumeVolumeRebuild,
+    ) -> Result<Volume, SvcError> {
+        let mut volume = self.specs().volume(&request.uuid).await?;
+        self.specs()
+            .resume_volume_rebuild(&mut volume, &self.registry, request)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MIB: u64 = 1024 * 1024;
+
+    #[test]
+    fn volume_size_rounding() {
+        let id = VolumeId::new();
+        let size = |size| effective_volume_size(&id, size, 1, None).unwrap();
+        assert_eq!(size(1), VOLUME_BLOCK_SIZE);
+        assert_eq!(size(512), VOLUME_BLOCK_SIZE);
+        assert_eq!(size(VOLUME_BLOCK_SIZE), VOLUME_BLOCK_SIZE);
+        assert_eq!(size(10 * MIB), 10 * MIB);
+        assert_eq!(size(10 * MIB + 1), 10 * MIB + VOLUME_BLOCK_SIZE);
+    }
+
+    #[test]
+    fn volume_min_size() {
+        let id = VolumeId::new();
+        let error = effective_volume_size(&id, MIB - 1, MIB, None).unwrap_err();
+        assert!(matches!(
+            error,
+            SvcError::VolumeTooSmall { size, min_size: MIB, .. } if size == MIB - 1
+        ));
+        assert_eq!(effective_volume_size(&id, MIB, MIB, None).unwrap(), MIB);
+    }
+
+    #[test]
+    fn volume_max_size() {
+        let id = VolumeId::new();
+        let max = Some(100 * MIB);
+        assert_eq!(
+            effective_volume_size(&id, 100 * MIB, MIB, max).unwrap(),
+            100 * MIB
+        );
+        let error = effective_volume_size(&id, 100 * MIB + 1, MIB, max).unwrap_err();
+        assert!(matches!(error, SvcError::VolumeTooLarge { .. }));
+
+        // sizes which would overflow once rounded up are rejected too, even without a maximum
+        let error = effective_volume_size(&id, u64::MAX - 1, MIB, None).unwrap_err();
+        assert!(matches!(error, SvcError::VolumeTooLarge { .. }));
+    }
 }
@@ -6,7 +6,7 @@ use crate::{
             nexus::GetPersistedNexusChildren,
             resources::{ChildItem, HealthyChildItems, ReplicaItem},
             volume::{
-                AddVolumeNexusReplicas, GetChildForRemoval, GetSuitablePools,
+                AddVolumeNexusReplicas, GetChildForRemoval, GetSuitablePools, PlacementConstraint,
                 ReplicaRemovalCandidates,
             },
             ResourceFilter,
@@ -15,6 +15,8 @@ use crate::{
             GuardedOperationsHelper, OperationSequenceGuard, ResourceSpecs, ResourceSpecsLocked,
             SpecOperationsHelper,
         },
+        topology::TopologyPolicy,
+        wrapper::{ClientOps, PoolWrapper},
     },
     nexus::scheduling::get_target_node_candidate,
     volume::scheduling,
@@ -39,17 +41,19 @@ use common_lib::{
             OperationGuardArc, ResourceMutex, SpecStatus, SpecTransaction, TraceSpan, TraceStrLog,
         },
         transport::{
-            AddNexusReplica, ChildUri, CreateNexus, CreateReplica, CreateVolume, DestroyReplica,
-            Nexus, NexusId, NodeId, PoolId, Protocol, PublishVolume, RemoveNexusReplica, Replica,
-            ReplicaId, ReplicaName, ReplicaOwners, Volume, VolumeId, VolumeShareProtocol,
-            VolumeState, VolumeStatus,
+            AddNexusReplica, ChildState, ChildUri, CreateNexus, CreateReplica, CreateVolume,
+            DestroyReplica, Nexus, NexusId, NexusNvmfConfig, NodeId, NvmeReservation, PatchVolume,
+            PauseNexusRebuild, PauseVolumeRebuild, PoolId, PoolStatus, Protocol, PublishVolume,
+            RemoveNexusReplica, Replica, ReplicaId, ReplicaName, ReplicaOwners, ResumeNexusRebuild,
+            ResumeVolumeRebuild, SetVolumeSourceReplica, Volume, VolumeId, VolumePlacementPolicy,
+            VolumePolicy, VolumeShareProtocol, VolumeState, VolumeStatus,
         },
     },
 };
 use grpc::operations::{PaginatedResult, Pagination};
 
 use snafu::OptionExt;
-use std::convert::From;
+use std::{collections::HashSet, convert::From, time::SystemTime};
 
 /// Select a replica to be removed from the volume
 pub(crate) async fn get_volume_replica_remove_candidate(
@@ -140,12 +144,85 @@ pub(crate) async fn get_volume_replica_candidates(
 ) -> Result<Vec<CreateReplica>, SvcError> {
     let request = request.into();
     let pools = scheduling::get_volume_pool_candidates(request.clone(), registry).await;
+    volume_replica_candidates(registry, &request, pools)
+}
+
+/// Return a list of appropriate requests which can be used to create a replica on a pool,
+/// disregarding the volume's placement topology
+pub(crate) async fn get_volume_relaxed_replica_candidates(
+    registry: &Registry,
+    request: impl Into<GetSuitablePools>,
+) -> Result<Vec<CreateReplica>, SvcError> {
+    let request = request.into();
+    let pools = scheduling::get_volume_relaxed_pool_candidates(request.clone(), registry).await;
+    volume_replica_candidates(registry, &request, pools)
+}
+
+/// Return a list of appropriate requests which can be used to create replicas on `need` different
+/// nodes, relaxing the placement constraints of the volume's topology one at a time until there
+/// are enough candidates, with a warning which lists the constraints which had to be relaxed.
+/// The candidates which satisfy all of the constraints come first, followed by those found as
+/// each constraint is relaxed.
+pub(crate) async fn get_volume_graduated_replica_candidates(
+    registry: &Registry,
+    request: impl Into<GetSuitablePools>,
+    need: usize,
+) -> Result<Vec<CreateReplica>, SvcError> {
+    let request = request.into();
+    let nodes = |pools: &[PoolWrapper]| {
+        pools
+            .iter()
+            .map(|pool| pool.node.clone())
+            .collect::<HashSet<_>>()
+            .len()
+    };
+    let mut pools = scheduling::get_volume_pool_candidates(request.clone(), registry).await;
+    let mut relaxing = vec![];
+    let mut relaxed = vec![];
+    for constraint in PlacementConstraint::RELAXATION_ORDER.iter() {
+        if nodes(&pools) >= need {
+            break;
+        }
+        relaxing.push(*constraint);
+        let candidates = scheduling::get_volume_constrained_pool_candidates(
+            request.clone(),
+            registry,
+            &relaxing,
+        )
+        .await;
+        let before = nodes(&pools);
+        for candidate in candidates {
+            if !pools.iter().any(|pool| pool.id == candidate.id) {
+                pools.push(candidate);
+            }
+        }
+        if nodes(&pools) > before {
+            relaxed = relaxing.clone();
+        }
+    }
 
+    if !relaxed.is_empty() {
+        request.warn_span(|| {
+            tracing::warn!(
+                relaxed = %relaxed.iter().map(ToString::to_string).collect::<Vec<_>>().join(","),
+                "Relaxed the placement constraints of the topology to place the replicas"
+            )
+        });
+    }
+    volume_replica_candidates(registry, &request, pools)
+}
+
+fn volume_replica_candidates(
+    registry: &Registry,
+    request: &GetSuitablePools,
+    pools: Vec<PoolWrapper>,
+) -> Result<Vec<CreateReplica>, SvcError> {
     if pools.is_empty() {
         return Err(SvcError::NotEnoughResources {
             source: NotEnough::OfPools { have: 0, need: 1 },
         });
     }
+    let pools = scheduling::spread_pool_candidates(registry, request, pools);
 
     request.trace(&format!(
         "Creation pool candidates for volume: {:?}",
@@ -154,39 +231,228 @@ pub(crate) async fn get_volume_replica_candidates(
 
     Ok(pools
         .iter()
-        .map(|p| {
-            let replica_uuid = ReplicaId::new();
-            CreateReplica {
-                node: p.node.clone(),
-                name: Some(ReplicaName::new(&replica_uuid, Some(&request.uuid))),
-                uuid: replica_uuid,
-                pool: p.id.clone(),
-                size: request.size,
-                thin: false,
-                share: Protocol::None,
-                managed: true,
-                owners: ReplicaOwners::from_volume(&request.uuid),
-            }
-        })
+        .map(|p| volume_replica_request(&request.uuid, request.size, &p.node, &p.id))
         .collect::<Vec<_>>())
 }
 
+/// Return a request to create a replica of the given volume on a pool
+fn volume_replica_request(
+    volume: &VolumeId,
+    size: u64,
+    node: &NodeId,
+    pool: &PoolId,
+) -> CreateReplica {
+    let replica_uuid = ReplicaId::new();
+    CreateReplica {
+        node: node.clone(),
+        name: Some(ReplicaName::new(&replica_uuid, Some(volume))),
+        uuid: replica_uuid,
+        pool: pool.clone(),
+        size,
+        thin: false,
+        share: Protocol::None,
+        managed: true,
+        owners: ReplicaOwners::from_volume(volume),
+    }
+}
+
+/// Check that the volume creation `request` has at least one replica.
+pub(crate) fn check_create_volume_replicas(request: &CreateVolume) -> Result<(), SvcError> {
+    match request.replicas {
+        0 => Err(SvcError::InvalidVolumeRequest {
+            field: "replicas".to_string(),
+            reason: "the volume must have at least one replica".to_string(),
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Check that the quorum of the volume creation `request`, if any, is within its replica count.
+pub(crate) fn check_create_volume_quorum(request: &CreateVolume) -> Result<(), SvcError> {
+    check_volume_quorum(&request.policy, request.replicas)
+}
+
+/// Check that the quorum of the volume `policy`, if any, is at least one replica and doesn't
+/// exceed the `replicas` count of the volume.
+fn check_volume_quorum(policy: &VolumePolicy, replicas: u64) -> Result<(), SvcError> {
+    match policy.quorum {
+        Some(quorum) if quorum == 0 || quorum as u64 > replicas => {
+            Err(SvcError::InvalidVolumeRequest {
+                field: "quorum".to_string(),
+                reason: format!(
+                    "the quorum {} must be between 1 and the {} replicas of the volume",
+                    quorum, replicas
+                ),
+            })
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Check that the volume creation `request` doesn't select more pools than it has replicas.
+pub(crate) fn check_create_volume_pools(request: &CreateVolume) -> Result<(), SvcError> {
+    if request.pools.len() as u64 > request.replicas {
+        return Err(SvcError::InvalidVolumeRequest {
+            field: "pools".to_string(),
+            reason: format!(
+                "{} pools are selected for {} replicas",
+                request.pools.len(),
+                request.replicas
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Check that the topology of the volume creation `request` allows enough nodes for its replicas
+/// to be on different nodes, unless its pools are selected explicitly or its placement relaxes
+/// the topology.
+pub(crate) fn check_create_volume_allowed_nodes(request: &CreateVolume) -> Result<(), SvcError> {
+    let allowed_nodes = request.allowed_nodes();
+    if request.pools.is_empty()
+        && request.placement != VolumePlacementPolicy::Relaxed
+        && !allowed_nodes.is_empty()
+        && request.replicas > allowed_nodes.len() as u64
+    {
+        return Err(SvcError::InvalidVolumeRequest {
+            field: "topology".to_string(),
+            reason: format!(
+                "{} nodes are allowed for {} replicas",
+                allowed_nodes.len(),
+                request.replicas
+            ),
+        });
+    }
+    Ok(())
+}
+
+/// Return a list of requests which can be used to create the replicas on the pools explicitly
+/// selected by the volume creation request, bypassing the pool scheduler.
+/// Each pool must exist, be usable, have enough free space for a replica and satisfy the volume's
+/// topology, and unless `allow_same_node` is set, no two pools may be on the same node.
+/// Replicas which are not pinned to a pool are placed by the scheduler, on nodes other than
+/// those of the selected pools.
+async fn get_create_volume_pool_replicas(
+    registry: &Registry,
+    request: &CreateVolume,
+) -> Result<Vec<CreateReplica>, SvcError> {
+    check_create_volume_pools(request)?;
+
+    let cordoned_nodes = registry.specs().get_cordoned_nodes();
+    let topology_pools = scheduling::get_volume_topology_pools(request, registry).await;
+    let mut replicas = Vec::<CreateReplica>::with_capacity(request.replicas as usize);
+    for pool_id in &request.pools {
+        let unsuitable = |reason: String| SvcError::UnsuitablePool {
+            pool_id: pool_id.clone(),
+            reason,
+        };
+        registry.specs().get_pool(pool_id)?;
+        if replicas.iter().any(|r| &r.pool == pool_id) {
+            return Err(unsuitable(
+                "the pool is selected more than once".to_string(),
+            ));
+        }
+        let pool = registry
+            .get_node_pool_wrapper(pool_id.clone())
+            .await
+            .map_err(|_| unsuitable("the pool's state is not available".to_string()))?;
+
+        let node_online = match registry.get_node_wrapper(&pool.node).await {
+            Ok(node) => node.read().await.is_online(),
+            Err(_) => false,
+        };
+        if !node_online {
+            return Err(unsuitable(format!("node '{}' is not online", pool.node)));
+        }
+        if cordoned_nodes.iter().any(|node| node.id() == &pool.node) {
+            return Err(unsuitable(format!("node '{}' is cordoned", pool.node)));
+        }
+        if pool.status == PoolStatus::Faulted || pool.status == PoolStatus::Unknown {
+            return Err(unsuitable(format!("the pool is {:?}", pool.status)));
+        }
+        let replica_size = pool.replica_size(request.size);
+        if pool.free_space() <= replica_size {
+            return Err(unsuitable(format!(
+                "the pool has {} free bytes but the volume's replica takes {} bytes",
+                pool.free_space(),
+                replica_size
+            )));
+        }
+        if !topology_pools.contains(pool_id) {
+            return Err(unsuitable(
+                "the pool does not satisfy the volume's topology".to_string(),
+            ));
+        }
+        if !request.allow_same_node && replicas.iter().any(|r| r.node == pool.node) {
+            return Err(unsuitable(format!(
+                "node '{}' already hosts another of the selected pools",
+                pool.node
+            )));
+        }
+
+        replicas.push(volume_replica_request(
+            &request.uuid,
+            request.size,
+            &pool.node,
+            pool_id,
+        ));
+    }
+
+    if replicas.len() as u64 == request.replicas {
+        return Ok(replicas);
+    }
+
+    let pinned = replicas.len() as u64;
+    // having no other candidates is handled below, as per the placement policy
+    let candidates = match get_volume_replica_candidates(registry, request).await {
+        Err(SvcError::NotEnoughResources { .. }) => vec![],
+        candidates => candidates?,
+    };
+    for candidate in candidates {
+        if !replicas.iter().any(|r| r.node == candidate.node) {
+            replicas.push(candidate);
+        }
+    }
+
+    let have = replicas.len() as u64;
+    if request.placement == VolumePlacementPolicy::Strict && have < request.replicas {
+        return Err(SvcError::from(NotEnough::OfPools {
+            have: have - pinned,
+            need: request.replicas - pinned,
+        }));
+    }
+    Ok(replicas)
+}
+
 /// Return a list of appropriate requests which can be used to create a a replica on a pool
 /// This can be used when creating a volume
 pub(crate) async fn get_create_volume_replicas(
     registry: &Registry,
     request: &CreateVolume,
 ) -> Result<Vec<CreateReplica>, SvcError> {
-    if !request.allowed_nodes().is_empty()
-        && request.replicas > request.allowed_nodes().len() as u64
-    {
-        // oops, how would this even work mr requester?
-        return Err(SvcError::InvalidArguments {});
+    if !request.pools.is_empty() {
+        return get_create_volume_pool_replicas(registry, request).await;
     }
 
-    let node_replicas = get_volume_replica_candidates(registry, request).await?;
+    check_create_volume_allowed_nodes(request)?;
 
-    if request.replicas > node_replicas.len() as u64 {
+    let node_replicas = match request.placement {
+        VolumePlacementPolicy::Relaxed => {
+            let need = request.replicas as usize;
+            get_volume_graduated_replica_candidates(registry, request, need).await?
+        }
+        VolumePlacementPolicy::Strict | VolumePlacementPolicy::BestEffort => {
+            get_volume_replica_candidates(registry, request).await?
+        }
+    };
+
+    // with best effort placement we can make do with any candidate, the reconcilers will create
+    // the missing replicas once more resources become available
+    let required = match request.placement {
+        VolumePlacementPolicy::Strict | VolumePlacementPolicy::Relaxed => request.replicas,
+        VolumePlacementPolicy::BestEffort => 1,
+    };
+    if required > node_replicas.len() as u64 {
         Err(SvcError::from(NotEnough::OfPools {
             have: node_replicas.len() as u64,
             need: request.replicas,
@@ -421,6 +687,7 @@ impl ResourceSpecsLocked {
             uuid: spec.uuid,
             name: spec.name.into(),
             disowners: by,
+            override_redundancy_check: false,
         }
     }
 
@@ -473,6 +740,10 @@ impl ResourceSpecsLocked {
     }
 
     /// Create `count` replicas for the given volume using the provided list of candidates, in order
+    /// When no pool satisfies the volume's topology, the replicas are placed regardless of it if
+    /// the topology policy is relaxed.
+    /// These replace the replicas which the volume is missing, so they're placed away from the
+    /// nodes which are busy with other rebuilds.
     pub(crate) async fn create_volume_replicas(
         &self,
         registry: &Registry,
@@ -481,10 +752,27 @@ impl ResourceSpecsLocked {
     ) -> Result<Vec<ReplicaId>, SvcError> {
         let mut created_replicas = Vec::with_capacity(count);
         let mut candidate_error = None;
+        let request = GetSuitablePools::replacement(volume_spec);
 
         for iter in 0 .. count {
-            let candidates = match get_volume_replica_candidates(registry, volume_spec).await {
+            let candidates = match get_volume_replica_candidates(registry, request.clone()).await {
                 Ok(candidates) => candidates,
+                Err(error) if registry.topology_policy() == TopologyPolicy::Relaxed => {
+                    match get_volume_relaxed_replica_candidates(registry, request.clone()).await {
+                        Ok(candidates) => {
+                            volume_spec.warn_span(|| {
+                                tracing::warn!(
+                                    "Relaxing the unsatisfiable topology to place a replica"
+                                )
+                            });
+                            candidates
+                        }
+                        Err(_) => {
+                            candidate_error = Some(error);
+                            break;
+                        }
+                    }
+                }
                 Err(error) => {
                     candidate_error = Some(error);
                     break;
@@ -594,6 +882,7 @@ impl ResourceSpecsLocked {
                             node: nexus_spec.node,
                             nexus: nexus_spec.uuid,
                             replica: ReplicaUri::new(&remove.spec().uuid, child_uri),
+                            force: false,
                         },
                     )
                     .await
@@ -606,12 +895,15 @@ impl ResourceSpecsLocked {
 
     /// Decrement the replica count of the given volume by 1
     /// Removes the replica from all volume nexuses
+    /// Unless `override_redundancy_check` is set, the volume must be left with at least the
+    /// minimum replication factor of healthy replicas.
     pub(crate) async fn decrease_volume_replica(
         &self,
         volume: &mut OperationGuardArc<VolumeSpec>,
         registry: &Registry,
         state: VolumeState,
         spec_clone: VolumeSpec,
+        override_redundancy_check: bool,
     ) -> Result<Volume, SvcError> {
         // Determine which replica is most suitable to be removed
         let result = get_volume_replica_remove_candidate(&spec_clone, &state, registry).await;
@@ -626,6 +918,18 @@ impl ResourceSpecsLocked {
                 .validate_update_step(registry, result, &spec_clone)
                 .await?;
 
+            // Held until the replica is destroyed, so concurrent removals account for this one
+            let result = registry
+                .check_redundancy(
+                    "scale down",
+                    &[remove.spec().clone()],
+                    override_redundancy_check,
+                )
+                .await;
+            let _redundancy = volume
+                .validate_update_step(registry, result, &spec_clone)
+                .await?;
+
             // Remove the replica from its nexus (where it exists as a child)
             let result = self
                 .remove_volume_child_candidate(&spec_clone, registry, &remove)
@@ -650,6 +954,128 @@ impl ResourceSpecsLocked {
         registry.get_volume(&state.uuid).await
     }
 
+    /// Designate the replica `request.replica` as the source of record of the volume.
+    /// The other replicas are removed from the target nexus so that, once the reconcilers add
+    /// them back, they're rebuilt from the designated replica.
+    pub(crate) async fn set_volume_source_replica(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        request: &SetVolumeSourceReplica,
+    ) -> Result<Volume, SvcError> {
+        let state = registry.get_volume_state(&request.uuid).await?;
+
+        let operation = VolumeOperation::SetSourceReplica(request.replica.clone());
+        let spec_clone = volume.start_update(registry, &state, operation).await?;
+        volume.complete_update(registry, Ok(()), spec_clone).await?;
+
+        let spec = volume.lock().clone();
+        if let (Some(mut nexus), Some(target)) = (
+            self.get_volume_target_nexus_guard(&spec).await?,
+            state.target,
+        ) {
+            let stale_children = nexus
+                .lock()
+                .children
+                .iter()
+                .filter_map(|child| child.as_replica())
+                .filter(|replica| replica.uuid() != &request.replica)
+                .collect::<Vec<_>>();
+            for replica in stale_children {
+                let remove = RemoveNexusReplica::new(&target.node, &target.uuid, &replica);
+                if let Err(error) = self
+                    .remove_nexus_replica(Some(&mut nexus), registry, &remove)
+                    .await
+                {
+                    spec.warn_span(|| {
+                        tracing::warn!(
+                            replica.uuid = %replica.uuid(),
+                            error = %error.full_string(),
+                            "Failed to remove the replica from the nexus for it to be rebuilt"
+                        )
+                    });
+                }
+            }
+        }
+
+        registry.get_volume(&request.uuid).await
+    }
+
+    /// Update the mutable parameters of the volume which are specified by the `request`,
+    /// leaving the spec untouched if they already have the requested values.
+    pub(crate) async fn patch_volume(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        request: &PatchVolume,
+    ) -> Result<Volume, SvcError> {
+        if let Some(priority) = request.rebuild_priority {
+            if volume.lock().rebuild_priority != priority {
+                let state = registry.get_volume_state(&request.uuid).await?;
+                let operation = VolumeOperation::SetRebuildPriority(priority);
+                let spec_clone = volume.start_update(registry, &state, operation).await?;
+                volume.complete_update(registry, Ok(()), spec_clone).await?;
+            }
+        }
+        registry.get_volume(&request.uuid).await
+    }
+
+    /// Pause the rebuilds of the children of the volume target which are being rebuilt.
+    /// The paused intent is recorded in the spec, so the rebuilds which start afterwards, eg:
+    /// once the target is recreated, are paused as well by the reconcilers.
+    pub(crate) async fn pause_volume_rebuild(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        request: &PauseVolumeRebuild,
+    ) -> Result<Volume, SvcError> {
+        let state = registry.get_volume_state(&request.uuid).await?;
+        let spec_clone = volume
+            .start_update(registry, &state, VolumeOperation::PauseRebuild)
+            .await?;
+
+        let mut result = Ok(());
+        if let Some(target) = &state.target {
+            let node = registry.get_node_wrapper(&target.node).await?;
+            for child in target.children.iter().filter(|c| c.rebuilding()) {
+                let pause = PauseNexusRebuild::new(&target.node, &target.uuid, &child.uri);
+                result = node.pause_rebuild(&pause).await;
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        volume.complete_update(registry, result, spec_clone).await?;
+        registry.get_volume(&request.uuid).await
+    }
+
+    /// Resume the paused rebuilds of the children of the volume target.
+    pub(crate) async fn resume_volume_rebuild(
+        &self,
+        volume: &mut OperationGuardArc<VolumeSpec>,
+        registry: &Registry,
+        request: &ResumeVolumeRebuild,
+    ) -> Result<Volume, SvcError> {
+        let state = registry.get_volume_state(&request.uuid).await?;
+        let spec_clone = volume
+            .start_update(registry, &state, VolumeOperation::ResumeRebuild)
+            .await?;
+
+        let mut result = Ok(());
+        if let Some(target) = &state.target {
+            let node = registry.get_node_wrapper(&target.node).await?;
+            for child in target.children.iter().filter(|c| c.rebuilding()) {
+                let resume = ResumeNexusRebuild::new(&target.node, &target.uuid, &child.uri);
+                result = node.resume_rebuild(&resume).await;
+                if result.is_err() {
+                    break;
+                }
+            }
+        }
+        volume.complete_update(registry, result, spec_clone).await?;
+        registry.get_volume(&request.uuid).await
+    }
+
     /// Make the replica accessible on the specified `NodeId`
     /// This means the replica might have to be shared/unshared so it can be open through
     /// the correct protocol (loopback locally, and nvmf remotely)
@@ -686,6 +1112,7 @@ impl ResourceSpecsLocked {
         target_node: &NodeId,
         nexus_id: &NexusId,
         vol_spec: &VolumeSpec,
+        reservation: Option<&NvmeReservation>,
     ) -> Result<(OperationGuardArc<NexusSpec>, Nexus), SvcError> {
         let children = get_healthy_volume_replicas(vol_spec, target_node, registry).await?;
         let (count, items) = match children {
@@ -707,10 +1134,10 @@ impl ResourceSpecsLocked {
                 .make_replica_accessible(registry, item.state(), target_node)
                 .await
             {
-                nexus_replicas.push(NexusChild::Replica(ReplicaUri::new(
-                    &item.spec().uuid,
-                    &uri,
-                )));
+                nexus_replicas.push(NexusChild::Replica(
+                    ReplicaUri::new(&item.spec().uuid, &uri)
+                        .with_generation(item.spec().generation),
+                ));
                 nodes.push(item.state().node.clone());
             }
         }
@@ -726,7 +1153,8 @@ impl ResourceSpecsLocked {
                 &nexus_replicas,
                 true,
                 Some(&vol_spec.uuid),
-                None,
+                reservation
+                    .map(|reservation| NexusNvmfConfig::default().with_reservation(reservation)),
             ),
         )
         .await?;
@@ -755,12 +1183,21 @@ impl ResourceSpecsLocked {
         replica: &Replica,
     ) -> Result<(), SvcError> {
         // Adding a replica to a nexus will initiate a rebuild.
-        // First check that we are able to start a rebuild.
-        registry.rebuild_allowed().await?;
+        // First check that we are able to start a rebuild, holding off any other rebuild until
+        // this one has been started and accounted for.
+        let priority = self
+            .get_locked_volume(volume_uuid)
+            .map(|volume| volume.lock().rebuild_priority)
+            .unwrap_or_default();
+        let _rebuild_admission = registry.rebuild_allowed(volume_uuid, priority).await?;
 
         let uri = self
             .make_replica_accessible(registry, replica, &nexus.node)
             .await?;
+        let generation = self
+            .get_replica(&replica.uuid)
+            .map(|spec| spec.lock().generation)
+            .unwrap_or_default();
         match self
             .add_nexus_replica(
                 Some(nexus_guard),
@@ -768,7 +1205,7 @@ impl ResourceSpecsLocked {
                 &AddNexusReplica {
                     node: nexus.node.clone(),
                     nexus: nexus.uuid.clone(),
-                    replica: ReplicaUri::new(&replica.uuid, &uri),
+                    replica: ReplicaUri::new(&replica.uuid, &uri).with_generation(generation),
                     auto_rebuild: true,
                 },
             )
@@ -1003,6 +1440,49 @@ impl ResourceSpecsLocked {
         registry.store_obj(&clone).await
     }
 
+    /// Mark the replica as out-of-sync, or clear the marker once it's been rebuilt
+    /// fixme: not explicitly guarded
+    pub(crate) async fn set_replica_out_of_sync(
+        &self,
+        registry: &Registry,
+        replica: &ResourceMutex<ReplicaSpec>,
+        out_of_sync: bool,
+    ) -> Result<(), SvcError> {
+        let mut clone = replica.lock().clone();
+        if clone.out_of_sync == out_of_sync {
+            return Ok(());
+        }
+        clone.out_of_sync = out_of_sync;
+        registry.store_obj(&clone).await?;
+        replica.lock().out_of_sync = out_of_sync;
+        Ok(())
+    }
+
+    /// Mark the replicas which are being rebuilt by the given nexus as out-of-sync, as their
+    /// rebuild is about to be interrupted, eg: by the destruction of the nexus
+    pub(crate) async fn mark_rebuilding_replicas_out_of_sync(
+        &self,
+        registry: &Registry,
+        nexus_state: &Nexus,
+    ) -> Result<(), SvcError> {
+        let nexus_spec = match self.get_nexus(&nexus_state.uuid) {
+            Some(nexus_spec) => nexus_spec.lock().clone(),
+            None => return Ok(()),
+        };
+        for child in nexus_state.children.iter().filter(|c| c.rebuilding()) {
+            let replica = nexus_spec
+                .children
+                .iter()
+                .filter_map(|c| c.as_replica())
+                .find(|r| r.uri() == child);
+            if let Some(replica) = replica.and_then(|r| self.get_replica(r.uuid())) {
+                self.set_replica_out_of_sync(registry, &replica, true)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     /// Disown nexus from its owner
     /// fixme: not explicitly guarded
     pub(crate) async fn disown_nexus(
@@ -1090,7 +1570,12 @@ impl ResourceSpecsLocked {
         let volumes = self.get_locked_volumes();
         for volume_spec in volumes {
             if let Ok(mut guard) = volume_spec.operation_guard() {
-                if !guard.handle_incomplete_ops(registry).await {
+                let uuid = guard.lock().uuid.to_string();
+                if !registry.replay_due(&ResourceKind::Volume, &uuid) {
+                    continue;
+                }
+                let replayed = guard.handle_incomplete_ops(registry).await;
+                if !registry.replayed(&ResourceKind::Volume, &uuid, replayed) {
                     // Not all pending operations could be handled.
                     pending_ops = true;
                 }
@@ -1170,7 +1655,12 @@ impl SpecOperationsHelper for VolumeSpec {
     ) -> Result<(), SvcError> {
         if !matches!(
             &operation,
-            VolumeOperation::Publish(..) | VolumeOperation::Unpublish
+            VolumeOperation::Publish(..)
+                | VolumeOperation::Unpublish
+                | VolumeOperation::SetRebuildPriority(_)
+                | VolumeOperation::PauseRebuild
+                | VolumeOperation::ResumeRebuild
+                | VolumeOperation::ReleaseReservation
         ) {
             // don't attempt to modify the volume parameters if the nexus target is not "stable"
             if self.target.is_some() != state.target.is_some() {
@@ -1181,6 +1671,18 @@ impl SpecOperationsHelper for VolumeSpec {
             }
         }
 
+        if matches!(
+            &operation,
+            VolumeOperation::Publish(..) | VolumeOperation::Share(_)
+        ) && self.policy.below_quorum(state.healthy_replicas())
+        {
+            // don't expose the volume for IO while it's below its quorum
+            return Err(SvcError::from(NotEnough::OfReplicas {
+                have: state.healthy_replicas() as u64,
+                need: self.policy.quorum.unwrap_or_default() as u64,
+            }));
+        }
+
         match &operation {
             VolumeOperation::Share(protocol) => match protocol {
                 VolumeShareProtocol::Nvmf => match &self.target {
@@ -1213,7 +1715,18 @@ impl SpecOperationsHelper for VolumeSpec {
                 }),
                 _ => Ok(()),
             },
-            VolumeOperation::Publish((_, _, protocol)) => match protocol {
+            VolumeOperation::Publish((_, _, _, Some(reservation)))
+                if self
+                    .reservation
+                    .map_or(false, |current| current.key != reservation.key) =>
+            {
+                // the volume stays fenced with the key it was last published with
+                Err(SvcError::NvmeReservationConflict {
+                    vol_id: self.uuid_str(),
+                    key: reservation.key,
+                })
+            }
+            VolumeOperation::Publish((_, _, protocol, _)) => match protocol {
                 None => Ok(()),
                 Some(protocol) => match protocol {
                     VolumeShareProtocol::Nvmf => {
@@ -1242,6 +1755,8 @@ impl SpecOperationsHelper for VolumeSpec {
             VolumeOperation::Unpublish => Ok(()),
 
             VolumeOperation::SetReplica(replica_count) => {
+                // the replica count may not be reduced below the quorum
+                let quorum = check_volume_quorum(&self.policy, *replica_count as u64);
                 if *replica_count == self.num_replicas {
                     Err(SvcError::ReplicaCountAchieved {
                         id: self.uuid_str(),
@@ -1254,6 +1769,8 @@ impl SpecOperationsHelper for VolumeSpec {
                     })
                 } else if (*replica_count as i16 - self.num_replicas as i16).abs() > 1 {
                     Err(SvcError::ReplicaChangeCount {})
+                } else if quorum.is_err() {
+                    quorum
                 } else if state.status != VolumeStatus::Online
                     && (*replica_count > self.num_replicas)
                 {
@@ -1316,6 +1833,75 @@ impl SpecOperationsHelper for VolumeSpec {
                 }
             }
 
+            VolumeOperation::SetSourceReplica(uuid) => {
+                let target = state.target.as_ref().context(errors::VolumeNotPublished {
+                    vol_id: self.uuid_str(),
+                })?;
+                if !registry
+                    .specs()
+                    .get_volume_replicas(&self.uuid)
+                    .iter()
+                    .any(|r| &r.lock().uuid == uuid)
+                {
+                    return Err(SvcError::ReplicaNotFound {
+                        replica_id: uuid.clone(),
+                    });
+                }
+                // the source of record must be an up to date child of the target
+                let child_uri = registry
+                    .specs()
+                    .get_volume_target_nexus(self)
+                    .and_then(|nexus| {
+                        let nexus = nexus.lock();
+                        nexus
+                            .children
+                            .iter()
+                            .filter_map(|child| child.as_replica())
+                            .find(|replica| &replica.uuid == uuid)
+                            .map(|replica| replica.uri().clone())
+                    });
+                let healthy = child_uri.map(|uri| {
+                    target
+                        .children
+                        .iter()
+                        .any(|child| child.uri == uri && child.state == ChildState::Online)
+                });
+                if healthy.unwrap_or_default() {
+                    Ok(())
+                } else {
+                    Err(SvcError::UnhealthyReplica {
+                        replica: uuid.to_string(),
+                        volume: self.uuid_str(),
+                    })
+                }
+            }
+
+            VolumeOperation::SetRebuildPriority(_) => Ok(()),
+
+            VolumeOperation::PauseRebuild if self.rebuild_paused => {
+                Err(SvcError::RebuildAlreadyPaused {
+                    vol_id: self.uuid_str(),
+                })
+            }
+            VolumeOperation::PauseRebuild => Ok(()),
+            VolumeOperation::ResumeRebuild if !self.rebuild_paused => {
+                Err(SvcError::RebuildNotPaused {
+                    vol_id: self.uuid_str(),
+                })
+            }
+            VolumeOperation::ResumeRebuild => Ok(()),
+
+            // the reservation may only be released once the volume is unpublished, as the target
+            // would otherwise keep on fencing with it
+            VolumeOperation::ReleaseReservation => match &self.target {
+                Some(target) => Err(SvcError::VolumeAlreadyPublished {
+                    vol_id: self.uuid_str(),
+                    node: target.node().to_string(),
+                    protocol: format!("{:?}", target.protocol()),
+                }),
+                None => Ok(()),
+            },
+
             VolumeOperation::Create => unreachable!(),
             VolumeOperation::Destroy => unreachable!(),
         }?;
@@ -1346,4 +1932,14 @@ impl SpecOperationsHelper for VolumeSpec {
     fn operation_result(&self) -> Option<Option<bool>> {
         self.operation.as_ref().map(|r| r.result)
     }
+    async fn record_revision(
+        &self,
+        registry: &Registry,
+        updated_at: SystemTime,
+        operation: Option<serde_json::Value>,
+    ) {
+        registry
+            .record_spec_revision(self, updated_at, operation)
+            .await;
+    }
 }
@@ -8,7 +8,8 @@ use common_lib::{
             WatchEvent,
         },
         transport::{
-            CreateWatch, DeleteWatch, GetWatches, Watch, WatchCallback, WatchResourceId, WatchType,
+            CreateWatch, DeleteWatch, GetWatches, VolumeId, VolumeTargetChanged, Watch,
+            WatchCallback, WatchResourceId, WatchType,
         },
     },
 };
@@ -21,7 +22,10 @@ use std::{
     time::Duration,
 };
 use tokio::{
-    sync::{broadcast::error::TryRecvError, Mutex},
+    sync::{
+        broadcast::error::{RecvError, TryRecvError},
+        Mutex,
+    },
     task::JoinHandle,
 };
 
@@ -136,18 +140,29 @@ impl WatchCfg {
     }
 
     /// Add a new watch element to this watch
-    async fn add(
-        &mut self,
-        watch: &WatchParams,
-        store: Arc<Mutex<impl Store + 'static>>,
-    ) -> Result<(), SvcError> {
+    async fn add(&mut self, watch: &WatchParams, registry: &Registry) -> Result<(), SvcError> {
         if self.watches.iter().any(|item| &item.params == watch) {
             return Err(SvcError::WatchAlreadyExists {});
         }
-
-        {
+        if watch.type_ == WatchType::TargetChange {
+            // only the volumes have a target, whose changes are observed by the registry
+            match &self.watch_id.id {
+                WatchResourceId::Volume(volume) => {
+                    if registry.specs().get_volume(volume).is_err() {
+                        return Err(SvcError::WatchResourceNotFound {
+                            kind: ResourceKind::Volume,
+                        });
+                    }
+                }
+                id => {
+                    return Err(SvcError::WatchTypeNotSupported {
+                        kind: Self::resource_to_kind(id),
+                    })
+                }
+            }
+        } else {
             // make sure the target resource exists
-            let mut store = store.lock().await;
+            let mut store = registry.store().lock().await;
             match store.get_kv(&self.watch_id.id.key()).await {
                 Ok(_) => Ok(()),
                 Err(StoreError::MissingEntry { .. }) => Err(SvcError::WatchResourceNotFound {
@@ -157,7 +172,7 @@ impl WatchCfg {
             }?;
         }
 
-        let handle = self.watch(watch, store).await?;
+        let handle = self.watch(watch, registry).await?;
 
         let watch = WatchParamsCfg {
             params: watch.clone(),
@@ -190,14 +205,27 @@ impl WatchCfg {
         }
     }
 
-    /// Register a callback for the element using the store's watch feature
+    /// Register a callback for the element using the store's watch feature, or the changes of the
+    /// volume targets published by the registry for a `TargetChange` watch
     async fn watch(
         &self,
         watch: &WatchParams,
-        store_arc: Arc<Mutex<impl Store + 'static>>,
+        registry: &Registry,
     ) -> Result<WatchHandle, SvcError> {
+        let store_arc = registry.store().clone();
         let mut store = store_arc.lock().await;
-        let handle = {
+        let handle = if let (WatchType::TargetChange, WatchResourceId::Volume(volume)) =
+            (&watch.type_, &self.watch_id.id)
+        {
+            let events = registry.subscribe_volume_targets();
+            let watch = watch.clone();
+            let volume = volume.clone();
+            let (cancel_sender, cancel) = tokio::sync::broadcast::channel(1);
+            let thread = tokio::spawn(async move {
+                Self::target_worker(cancel, events, watch, volume).await;
+            });
+            Arc::new((cancel_sender, thread))
+        } else {
             // start watching before writing to the store
             let channel = store.watch_obj(&self.watch_id.id).await?;
             let watch = watch.clone();
@@ -240,7 +268,7 @@ impl WatchCfg {
                                 if Some(&chan.0) != last_seen.as_ref() {
                                     // we can't know if we missed any event so just
                                     // compare the latest with last seen
-                                    Self::notify(&mut cancel, &params.callback).await;
+                                    Self::notify(&mut cancel, &params.callback, None).await;
                                 }
                                 last_seen = Some(chan.0);
                                 channel = chan.1;
@@ -263,7 +291,7 @@ impl WatchCfg {
                                     return;
                                 }
                             }
-                            Self::notify(&mut cancel, &params.callback).await;
+                            Self::notify(&mut cancel, &params.callback, None).await;
                         }
                     }
                 }
@@ -271,8 +299,48 @@ impl WatchCfg {
         }
     }
 
-    /// Notify the watch using its callback
-    async fn notify(cancel: &mut tokio::sync::broadcast::Receiver<()>, callback: &WatchCallback) {
+    /// Worker thread which listens for the changes of the volume targets published by the
+    /// registry, notifying those of the given `volume` along with its old and new target uris.
+    async fn target_worker(
+        mut cancel: tokio::sync::broadcast::Receiver<()>,
+        mut events: tokio::sync::broadcast::Receiver<VolumeTargetChanged>,
+        params: WatchParams,
+        volume: VolumeId,
+    ) {
+        loop {
+            tokio::select! {
+                _cancel = cancel.recv() => {
+                    // the watch has been cancelled
+                    return;
+                },
+                event = events.recv() => {
+                    match event {
+                        Ok(event) if event.volume == volume => {
+                            let body = serde_json::to_value(&event).ok();
+                            Self::notify(&mut cancel, &params.callback, body.as_ref()).await;
+                        }
+                        Ok(_) => {}
+                        Err(RecvError::Lagged(missed)) => {
+                            tracing::warn!(
+                                volume.uuid = %volume,
+                                "Missed {} volume target changes whilst notifying",
+                                missed
+                            );
+                        }
+                        // the registry is gone
+                        Err(RecvError::Closed) => return,
+                    }
+                }
+            }
+        }
+    }
+
+    /// Notify the watch using its callback, with the json `body`, if any
+    async fn notify(
+        cancel: &mut tokio::sync::broadcast::Receiver<()>,
+        callback: &WatchCallback,
+        body: Option<&serde_json::Value>,
+    ) {
         let mut tries = 0;
         let mut log_failure = true;
         loop {
@@ -284,10 +352,15 @@ impl WatchCfg {
 
             match &callback {
                 WatchCallback::Uri(uri) => {
-                    let request = reqwest::Client::new()
+                    let mut request = reqwest::Client::new()
                         .put(uri)
-                        .timeout(std::time::Duration::from_secs(1))
-                        .send();
+                        .timeout(std::time::Duration::from_secs(1));
+                    if let Some(body) = body {
+                        request = request
+                            .header(reqwest::header::CONTENT_TYPE, "application/json")
+                            .body(body.to_string());
+                    }
+                    let request = request.send();
                     match request.await {
                         Ok(resp) if resp.status().is_success() => {
                             // notification complete
@@ -441,6 +514,10 @@ impl StoreWatch {
         callback: &WatchCallback,
         type_: &WatchType,
     ) -> Result<(), SvcError> {
+        // the watch is set up through the store, which can't be reached
+        if self.registry.store_read_only() {
+            return Err(SvcError::StoreUnavailable {});
+        }
         let watch_cfg = self.get_or_create_watch_cfg(watch_id).await;
         let watch = WatchParams {
             callback: callback.clone(),
@@ -448,7 +525,7 @@ impl StoreWatch {
         };
 
         let mut watch_cfg = watch_cfg.lock().await;
-        watch_cfg.add(&watch, self.registry.store().clone()).await?;
+        watch_cfg.add(&watch, &self.registry).await?;
         Ok(())
     }
 
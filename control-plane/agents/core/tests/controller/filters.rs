@@ -0,0 +1,194 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::transport::{CreateVolume, Filter, PublishVolume, VolumeId},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    nexus::traits::NexusOperations, node::traits::NodeOperations, pool::traits::PoolOperations,
+    replica::traits::ReplicaOperations, volume::traits::VolumeOperations,
+};
+
+/// Check the `result` of listing `resource` with the `filter`: the supported filters must only
+/// return the matching entries, whilst the others must be rejected rather than ignored.
+fn check_filter<T: std::fmt::Debug>(
+    resource: ResourceKind,
+    filter: &Filter,
+    supported: bool,
+    result: Result<Vec<T>, ReplyError>,
+    matches: impl Fn(&T) -> bool,
+) {
+    match result {
+        Ok(entries) if supported => {
+            assert!(!entries.is_empty(), "{:?} by {:?}", resource, filter);
+            assert!(
+                entries.iter().all(matches),
+                "{:?} by {:?}: {:?}",
+                resource,
+                filter,
+                entries
+            );
+        }
+        Err(error) if !supported => {
+            assert_eq!(
+                error.kind,
+                ReplyErrorKind::InvalidArgument,
+                "{:?} by {:?}",
+                resource,
+                filter
+            );
+            assert_eq!(error.resource.to_string(), resource.to_string());
+        }
+        result => panic!("{:?} by {:?}: {:?}", resource, filter, result),
+    }
+}
+
+#[tokio::test]
+async fn list_filter_matrix() {
+    // a single node, so that every resource can be found by its node
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.grpc_client();
+    let volume = client
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = client
+        .volume()
+        .publish(
+            &PublishVolume::new(volume.spec().uuid.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+    let nexus = volume.state().target.unwrap();
+    let replicas = client
+        .replica()
+        .get(Filter::Volume(volume_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    let replica = replicas.first().unwrap().clone();
+
+    let filters = vec![
+        Filter::None,
+        Filter::Node(replica.node.clone()),
+        Filter::Pool(replica.pool.clone()),
+        Filter::NodePool(replica.node.clone(), replica.pool.clone()),
+        Filter::NodeReplica(replica.node.clone(), replica.uuid.clone()),
+        Filter::NodePoolReplica(
+            replica.node.clone(),
+            replica.pool.clone(),
+            replica.uuid.clone(),
+        ),
+        Filter::PoolReplica(replica.pool.clone(), replica.uuid.clone()),
+        Filter::Replica(replica.uuid.clone()),
+        Filter::NodeNexus(nexus.node.clone(), nexus.uuid.clone()),
+        Filter::Nexus(nexus.uuid.clone()),
+        Filter::Volume(volume_id.clone()),
+    ];
+
+    for filter in &filters {
+        let supported = matches!(filter, Filter::None | Filter::Node(_));
+        let result = client.node().get(filter.clone(), None).await;
+        check_filter(
+            ResourceKind::Node,
+            filter,
+            supported,
+            result.map(|n| n.into_inner()),
+            |node| match filter {
+                Filter::Node(id) => node.id() == id,
+                _ => true,
+            },
+        );
+
+        let supported = matches!(
+            filter,
+            Filter::None | Filter::Node(_) | Filter::Pool(_) | Filter::NodePool(_, _)
+        );
+        let result = client.pool().get(filter.clone(), None).await;
+        check_filter(
+            ResourceKind::Pool,
+            filter,
+            supported,
+            result.map(|p| p.into_inner()),
+            |pool| match filter {
+                Filter::Node(node) => &pool.node() == node,
+                Filter::Pool(id) => pool.id() == id,
+                Filter::NodePool(node, id) => &pool.node() == node && pool.id() == id,
+                _ => true,
+            },
+        );
+
+        let supported = !matches!(filter, Filter::NodeNexus(_, _) | Filter::Nexus(_));
+        let result = client.replica().get(filter.clone(), None).await;
+        check_filter(
+            ResourceKind::Replica,
+            filter,
+            supported,
+            result.map(|r| r.into_inner()),
+            |r| match filter {
+                Filter::Node(node) => &r.node == node,
+                Filter::Pool(pool) => &r.pool == pool,
+                Filter::NodePool(node, pool) => &r.node == node && &r.pool == pool,
+                Filter::NodeReplica(node, id) => &r.node == node && &r.uuid == id,
+                Filter::NodePoolReplica(node, pool, id) => {
+                    &r.node == node && &r.pool == pool && &r.uuid == id
+                }
+                Filter::PoolReplica(pool, id) => &r.pool == pool && &r.uuid == id,
+                Filter::Replica(id) => &r.uuid == id,
+                Filter::Volume(_) => replicas.iter().any(|v| v.uuid == r.uuid),
+                _ => true,
+            },
+        );
+
+        let supported = matches!(
+            filter,
+            Filter::None | Filter::Node(_) | Filter::NodeNexus(_, _) | Filter::Nexus(_)
+        );
+        let result = client.nexus().get(filter.clone(), None).await;
+        check_filter(
+            ResourceKind::Nexus,
+            filter,
+            supported,
+            result.map(|n| n.into_inner()),
+            |n| match filter {
+                Filter::Node(node) => &n.node == node,
+                Filter::NodeNexus(node, id) => &n.node == node && &n.uuid == id,
+                Filter::Nexus(id) => &n.uuid == id,
+                _ => true,
+            },
+        );
+
+        let supported = matches!(filter, Filter::None | Filter::Volume(_));
+        let result = client.volume().get(filter.clone(), None, None).await;
+        check_filter(
+            ResourceKind::Volume,
+            filter,
+            supported,
+            result.map(|v| v.entries),
+            |v| match filter {
+                Filter::Volume(id) => &v.spec().uuid == id,
+                _ => true,
+            },
+        );
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::{
+            definitions::{StorableObject, Store},
+            volume::{VolumeOperation, VolumeOperationState, VolumeSpec},
+            SpecStatus,
+        },
+        transport::{Filter, VolumeId, VolumeStatus},
+    },
+};
+use deployer_cluster::{fixtures, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::time::Duration;
+
+/// Test that the dirty specs left behind in the persistent store, eg: by a core agent which
+/// crashed halfway through an operation, are resolved when the core agent starts up, including
+/// those which were stored in an older format.
+#[tokio::test]
+async fn dirty_specs_on_startup() {
+    let creating = VolumeSpec {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        num_replicas: 1,
+        status: SpecStatus::Creating,
+        operation: Some(VolumeOperationState {
+            operation: VolumeOperation::Create,
+            result: None,
+        }),
+        ..Default::default()
+    };
+    let updating = VolumeSpec {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        num_replicas: 1,
+        status: SpecStatus::Created(VolumeStatus::Online),
+        operation: Some(VolumeOperationState {
+            operation: VolumeOperation::SetReplica(2),
+            result: None,
+        }),
+        ..Default::default()
+    };
+
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_store_fixtures(vec![
+            fixtures::spec(&creating),
+            fixtures::legacy::volume_spec(&updating),
+        ])
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let mut store = Etcd::new("0.0.0.0:2379").await.unwrap();
+
+    let start = std::time::Instant::now();
+    loop {
+        let created = store.get_obj::<VolumeSpec>(&creating.key()).await.is_ok();
+        let updated: VolumeSpec = store.get_obj(&updating.key()).await.unwrap();
+        if !created && updated.operation.is_none() {
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The dirty specs were not resolved"
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    // the incomplete creation is undone
+    let error = volume_client
+        .get(Filter::Volume(creating.uuid.clone()), None, None)
+        .await
+        .expect_err("The volume creation never completed");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    // whilst the incomplete update is assumed to have failed
+    let volume = volume_client
+        .get(Filter::Volume(updating.uuid.clone()), None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    assert_eq!(volume.spec().num_replicas, 1);
+}
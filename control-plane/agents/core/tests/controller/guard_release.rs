@@ -0,0 +1,107 @@
+#![cfg(test)]
+
+use super::spec_patch::create_volume;
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        GetPendingOperation, PendingOperation, PublishVolume, ReleaseOperationGuard, ShareVolume,
+        UnpublishVolume, VolumeId, VolumeShareProtocol,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+async fn cluster(allow_release: bool) -> Cluster {
+    ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| {
+            o.with_agents_env("CORE_FAULT_INJECTION", "volume-share")
+                .with_allow_guard_release(allow_release)
+        })
+        .build()
+        .await
+        .unwrap()
+}
+
+/// Publish the volume and start sharing it, which is abandoned whilst holding the volume's guard.
+async fn wedge_volume(cluster: &Cluster, volume: &VolumeId) {
+    let volume_client = cluster.grpc_client().volume();
+    let publish = PublishVolume::new(volume.clone(), Some(cluster.node(0)), None);
+    volume_client.publish(&publish, None).await.unwrap();
+
+    let share = ShareVolume::new(volume.clone(), VolumeShareProtocol::Nvmf);
+    let error = volume_client.share(&share, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Internal);
+}
+
+async fn release(cluster: &Cluster, volume: &VolumeId) -> Result<PendingOperation, ReplyError> {
+    let request = ReleaseOperationGuard::new(ResourceKind::Volume, volume.as_str());
+    let registry = cluster.grpc_client().registry();
+    // the node may be polled, with its calls serialized, at the time of the release
+    let mut tries = 10;
+    loop {
+        match registry.release_operation_guard(&request, None).await {
+            Err(error) if error.kind == ReplyErrorKind::Conflict && tries > 0 => {
+                tries -= 1;
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[tokio::test]
+async fn guard_release() {
+    let cluster = cluster(true).await;
+    let registry = cluster.grpc_client().registry();
+    let volume_client = cluster.grpc_client().volume();
+    let volume = create_volume(&cluster).await;
+    wedge_volume(&cluster, &volume).await;
+
+    // the volume is wedged, with its share operation pending
+    let get = GetPendingOperation::new(ResourceKind::Volume, volume.as_str());
+    let pending = registry.get_pending_operation(&get, None).await.unwrap();
+    assert!(pending.busy);
+    assert!(pending.operation.is_some());
+    assert_eq!(pending.result, None);
+    let unpublish = UnpublishVolume::new(&volume, false);
+    let error = volume_client.unpublish(&unpublish, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Conflict);
+
+    let released = release(&cluster, &volume).await.unwrap();
+    assert_eq!(released, pending);
+
+    // the pending operation is then recovered by the dirty spec reconciler
+    let start = std::time::Instant::now();
+    loop {
+        let pending = registry.get_pending_operation(&get, None).await.unwrap();
+        if pending.operation.is_none() {
+            assert!(!pending.busy);
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            panic!("Timeout waiting for the pending operation to be recovered");
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+    volume_client.unpublish(&unpublish, None).await.unwrap();
+
+    // releasing a guard which isn't held has no effect
+    let released = release(&cluster, &volume).await.unwrap();
+    assert!(!released.busy);
+}
+
+#[tokio::test]
+async fn guard_release_disabled() {
+    let cluster = cluster(false).await;
+    let volume = create_volume(&cluster).await;
+    wedge_volume(&cluster, &volume).await;
+
+    let error = release(&cluster, &volume).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::PermissionDenied);
+}
@@ -1,3 +1,15 @@
+mod filters;
+mod fixtures;
+#[cfg(feature = "fault-injection")]
+mod guard_release;
+mod node_poll;
+mod orphans;
+mod pending_operation;
+mod rate_limit;
+mod spec_patch;
+mod store;
+mod timestamps;
+
 use common_lib::{
     store::etcd::Etcd,
     types::v0::{
@@ -50,7 +62,7 @@ async fn bootstrap_registry() {
 
     // Restart the core agent with the expectation that the registry will have all its resource
     // specs loaded from the persistent store.
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
 
     // Wait for core service to restart.
     cluster
@@ -202,3 +214,69 @@ async fn core_agent_lease_lock() {
     tracing::info!("core: {:?}", core.state);
     assert_eq!(Some(false), core.state.unwrap().running);
 }
+
+/// Test that refreshing the registry reloads the resources of the nodes straight away, rather than
+/// on the next cache period.
+#[tokio::test]
+async fn refresh_registry() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_cache_period("10m")
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.rest_v00();
+    let pool = transport::PoolId::from("refresh-pool");
+
+    // create a pool behind the back of the control plane
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    rpc_handle
+        .io_engine
+        .create_pool(rpc::io_engine::CreatePoolRequest {
+            name: pool.to_string(),
+            disks: vec!["malloc:///refresh?size_mb=50".into()],
+        })
+        .await
+        .unwrap();
+
+    // which the registry won't know about until the next cache period
+    client
+        .pools_api()
+        .get_pool(pool.as_str())
+        .await
+        .expect_err("The pool should not be cached yet");
+
+    let refresh = client.specs_api().put_specs_refresh().await.unwrap();
+    assert_eq!(refresh.nodes.len(), 2);
+    assert!(
+        refresh.nodes.iter().all(|n| n.error.is_none()),
+        "{:?}",
+        refresh
+    );
+
+    let cached = client.pools_api().get_pool(pool.as_str()).await.unwrap();
+    assert!(cached.state.is_some(), "{:?}", cached);
+
+    // a node which can't be reached is reported, without failing the refresh of the others
+    cluster
+        .composer()
+        .kill(cluster.node(1).as_str())
+        .await
+        .unwrap();
+    let refresh = client.specs_api().put_specs_refresh().await.unwrap();
+    let results = refresh
+        .nodes
+        .iter()
+        .map(|n| (n.node.as_str(), n.error.is_none()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        results,
+        vec![
+            (cluster.node(0).as_str(), true),
+            (cluster.node(1).as_str(), false)
+        ]
+    );
+}
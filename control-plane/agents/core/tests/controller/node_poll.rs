@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{Filter, PoolId};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::pool::traits::PoolOperations;
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn slow_node_poll() {
+    let request_timeout = Duration::from_secs(20);
+    // the slow node must remain online whilst it's paused, for it to be polled
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_node_deadline("60s")
+        .with_cache_period("1s")
+        .with_req_timeouts(Duration::from_millis(250), request_timeout)
+        .with_options(|o| o.with_node_poll_timeout(Duration::from_secs(1)))
+        .build()
+        .await
+        .unwrap();
+    let pool = PoolId::from("poll-pool");
+
+    cluster
+        .composer()
+        .pause(cluster.node(1).as_str())
+        .await
+        .unwrap();
+    // let a poll cycle get stuck on the slow node
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    // create a pool behind the back of the control plane
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    rpc_handle
+        .io_engine
+        .create_pool(rpc::io_engine::CreatePoolRequest {
+            name: pool.to_string(),
+            disks: vec!["malloc:///poll?size_mb=50".into()],
+        })
+        .await
+        .unwrap();
+    let created = Instant::now();
+
+    // which is picked up by the next cache refresh, without waiting on the slow node
+    let client = cluster.grpc_client().pool();
+    loop {
+        let pools = client
+            .get(Filter::Pool(pool.clone()), None)
+            .await
+            .map(|pools| pools.into_inner())
+            .unwrap_or_default();
+        if !pools.is_empty() {
+            break;
+        }
+        assert!(
+            created.elapsed() < request_timeout / 2,
+            "The pool was not cached within {:?}",
+            created.elapsed()
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    cluster
+        .composer()
+        .thaw(cluster.node(1).as_str())
+        .await
+        .unwrap();
+}
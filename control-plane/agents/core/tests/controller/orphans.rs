@@ -0,0 +1,193 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    types::v0::{
+        openapi::models,
+        store::{definitions::Store, nexus::NexusSpec, replica::ReplicaSpec, SpecStatus},
+        transport::{
+            CreateVolume, GetOrphans, NexusId, NexusStatus, Orphan, OrphanCategory,
+            OrphanRemediation, OrphanReport, ReplicaId, ReplicaName, ReplicaOwners, ReplicaStatus,
+            VolumeId,
+        },
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+async fn orphans(cluster: &Cluster) -> OrphanReport {
+    cluster
+        .grpc_client()
+        .registry()
+        .get_orphans(&GetOrphans {}, None)
+        .await
+        .unwrap()
+}
+
+fn find<'a>(orphans: &'a [Orphan], id: &str) -> &'a Orphan {
+    orphans
+        .iter()
+        .find(|orphan| orphan.id == id)
+        .unwrap_or_else(|| panic!("Orphan {} not found in {:?}", id, orphans))
+}
+
+/// Construct an orphan of each kind and category, behind the control plane's back, and check that
+/// they're all reported as such, whilst the replicas of a healthy volume are not.
+#[tokio::test]
+async fn orphan_report() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core", "jsongrpc"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let node = cluster.node(0);
+    let pool = cluster.pool(0, 0);
+
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // specs whose dataplane object does not exist, seeded in the store
+    let missing_replica = ReplicaId::new();
+    let missing_nexus = NexusId::new();
+    let mut store = Etcd::new("0.0.0.0:2379").await.unwrap();
+    store
+        .put_obj(&ReplicaSpec {
+            name: ReplicaName::from_uuid(&missing_replica),
+            uuid: missing_replica.clone(),
+            size: 5242880,
+            pool: pool.clone(),
+            status: SpecStatus::Created(ReplicaStatus::Online),
+            managed: false,
+            owners: ReplicaOwners::default(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    store
+        .put_obj(&NexusSpec {
+            uuid: missing_nexus.clone(),
+            name: missing_nexus.to_string(),
+            node: node.clone(),
+            size: 5242880,
+            spec_status: SpecStatus::Created(NexusStatus::Online),
+            managed: false,
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+
+    // dataplane objects which have no spec
+    let unexpected_replica = ReplicaId::new();
+    cluster
+        .rest_v00()
+        .json_grpc_api()
+        .put_node_jsongrpc(
+            node.as_str(),
+            "bdev_lvol_create",
+            serde_json::json!({
+                "lvs_name": pool.as_str(),
+                "lvol_name": unexpected_replica.as_str(),
+                "size_in_mib": 8,
+                "thin_provision": false,
+            }),
+        )
+        .await
+        .expect("Failed to create the replica");
+    let unexpected_nexus = NexusId::new();
+    let mut rpc_handle = cluster.grpc_handle(node.as_str()).await.unwrap();
+    rpc_handle
+        .io_engine
+        .create_nexus(rpc::io_engine::CreateNexusRequest {
+            uuid: unexpected_nexus.to_string(),
+            size: 5242880,
+            children: vec!["malloc:///orphan?size_mb=8".into()],
+        })
+        .await
+        .unwrap();
+
+    // wait for the node state to be refreshed with the unexpected objects
+    let start = std::time::Instant::now();
+    let report = loop {
+        let report = orphans(&cluster).await;
+        if report.replicas.dataplane.len() == 1 && report.nexuses.dataplane.len() == 1 {
+            break report;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The orphans were not all reported: {:#?}",
+            report
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    };
+
+    let orphan = find(&report.replicas.control_plane, missing_replica.as_str());
+    assert_eq!(orphan.category, OrphanCategory::ControlPlane);
+    assert_eq!(orphan.remediation, OrphanRemediation::Destroy);
+    assert_eq!(orphan.node.as_ref(), Some(&node));
+    assert_eq!(orphan.pool.as_ref(), Some(&pool));
+    let orphan = find(&report.nexuses.control_plane, missing_nexus.as_str());
+    assert_eq!(orphan.remediation, OrphanRemediation::Destroy);
+    assert_eq!(orphan.node.as_ref(), Some(&node));
+    assert_eq!(orphan.pool, None);
+
+    let orphan = find(&report.replicas.dataplane, unexpected_replica.as_str());
+    assert_eq!(orphan.category, OrphanCategory::Dataplane);
+    assert_eq!(orphan.remediation, OrphanRemediation::Ignore);
+    assert_eq!(orphan.pool.as_ref(), Some(&pool));
+    let orphan = find(&report.nexuses.dataplane, unexpected_nexus.as_str());
+    assert_eq!(orphan.remediation, OrphanRemediation::Ignore);
+    let age = orphan.age_secs.unwrap();
+
+    // the replica of the healthy volume is not an orphan
+    assert_eq!(report.replicas.control_plane.len(), 1);
+    assert_eq!(report.nexuses.control_plane.len(), 1);
+    let volume_replicas = volume.state().replica_topology;
+    assert!(volume_replicas
+        .keys()
+        .all(|id| report.replicas.iter().all(|o| o.id != id.as_str())));
+
+    // the dataplane orphans age from the first report which they appeared in
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    let report = orphans(&cluster).await;
+    let orphan = find(&report.nexuses.dataplane, unexpected_nexus.as_str());
+    assert!(orphan.age_secs.unwrap() >= age + 1, "{:?}", orphan);
+
+    // the same report is served over REST
+    let report = cluster
+        .rest_v00()
+        .admin_api()
+        .get_admin_orphans()
+        .await
+        .unwrap();
+    let orphan = report
+        .replicas
+        .control_plane
+        .iter()
+        .find(|orphan| orphan.id == missing_replica.as_str())
+        .unwrap();
+    assert_eq!(orphan.category, models::OrphanCategory::ControlPlane);
+    assert_eq!(orphan.remediation, models::OrphanRemediation::Destroy);
+    assert_eq!(report.nexuses.dataplane.len(), 1);
+}
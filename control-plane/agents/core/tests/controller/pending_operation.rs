@@ -0,0 +1,95 @@
+#![cfg(test)]
+
+use super::spec_patch::{cluster, create_volume, patch_volume, volume_spec};
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::SpecStatus,
+        transport::{ClearPendingOperation, GetPendingOperation, PublishVolume, VolumeId},
+    },
+};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use serde_json::json;
+
+#[tokio::test]
+async fn pending_operation_clear() {
+    let cluster = cluster().await;
+    let registry = cluster.grpc_client().registry();
+    let volume_client = cluster.grpc_client().volume();
+    let volume = create_volume(&cluster).await;
+
+    let get = GetPendingOperation::new(ResourceKind::Volume, volume.as_str());
+    let pending = registry.get_pending_operation(&get, None).await.unwrap();
+    assert_eq!(pending.status, SpecStatus::Created(()));
+    assert_eq!(pending.operation, None);
+    assert!(!pending.busy);
+
+    // leave the volume with an operation whose outcome is unknown, as if the core agent crashed
+    // before it could record its result
+    let stuck = json!({ "operation": { "operation": "Unshare", "result": null } });
+    patch_volume(&cluster, &volume, stuck).await;
+    let pending = registry.get_pending_operation(&get, None).await.unwrap();
+    assert_eq!(pending.operation, Some(json!("Unshare")));
+    assert_eq!(pending.result, None);
+    assert!(!pending.busy);
+    let publish = PublishVolume::new(volume.clone(), Some(cluster.node(0)), None);
+    let error = volume_client.publish(&publish, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPersist);
+
+    // it may have taken effect, so it's only cleared when forced
+    let clear = ClearPendingOperation::new(ResourceKind::Volume, volume.as_str(), false);
+    let error = registry
+        .clear_pending_operation(&clear, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert!(volume_spec(&cluster, &volume).await.operation.is_some());
+
+    let force = ClearPendingOperation::new(ResourceKind::Volume, volume.as_str(), true);
+    let cleared = registry
+        .clear_pending_operation(&force, None)
+        .await
+        .unwrap();
+    assert_eq!(cleared.operation, Some(json!("Unshare")));
+    assert_eq!(volume_spec(&cluster, &volume).await.operation, None);
+    let pending = registry.get_pending_operation(&get, None).await.unwrap();
+    assert_eq!(pending.operation, None);
+
+    // whereas an operation which is known to have failed is cleared straight away
+    let failed = json!({ "operation": { "operation": "Unshare", "result": false } });
+    patch_volume(&cluster, &volume, failed).await;
+    let cleared = registry
+        .clear_pending_operation(&clear, None)
+        .await
+        .unwrap();
+    assert_eq!(cleared.result, Some(false));
+
+    // the cleared spec is persisted
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    assert_eq!(volume_spec(&cluster, &volume).await.operation, None);
+    volume_client.publish(&publish, None).await.unwrap();
+}
+
+#[tokio::test]
+async fn pending_operation_invalid() {
+    let cluster = cluster().await;
+    let registry = cluster.grpc_client().registry();
+
+    let get = GetPendingOperation::new(ResourceKind::Volume, VolumeId::new().as_str());
+    let error = registry
+        .get_pending_operation(&get, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let clear = ClearPendingOperation::new(ResourceKind::Node, cluster.node(0).as_str(), true);
+    let error = registry
+        .clear_pending_operation(&clear, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+}
@@ -0,0 +1,124 @@
+use common_lib::{
+    transport_api::{ClientId, ReplyErrorKind, TimeoutOptions},
+    types::v0::{
+        openapi::{
+            apis::{StatusCode, Uuid},
+            clients::tower::Error,
+            models,
+        },
+        transport::{CreateVolume, DestroyVolume, VolumeId},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::{client::CoreClient, operations::volume::traits::VolumeOperations};
+use std::time::Duration;
+
+const SIZE: u64 = 5242880;
+const RATE: u32 = 1;
+const BURST: u32 = 2;
+const REQUESTS: usize = 10;
+/// The network of the test cluster, which is trusted to mark its requests as internal.
+const CLUSTER_NETWORK: &str = "10.1.0.0/16";
+
+/// Hammer the volume creation through the REST server, whose requests are all made on behalf of
+/// the same caller, and check that those beyond the burst are rejected with 429 Too Many Requests,
+/// whilst the reads and the requests of the control-plane itself are not limited.
+#[tokio::test]
+async fn grpc_rate_limit() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_options(|o| {
+            o.with_grpc_mutation_rate_limit(RATE, Some(BURST))
+                .with_grpc_trusted_networks(CLUSTER_NETWORK)
+        })
+        .build()
+        .await
+        .unwrap();
+    let client = cluster.rest_v00();
+    let volumes_api = client.volumes_api();
+    let body = models::CreateVolumeBody::new(models::VolumePolicy::default(), SIZE);
+
+    let (mut created, mut throttled) = (0, 0);
+    for _ in 0 .. REQUESTS {
+        match volumes_api.put_volume(&Uuid::new_v4(), body.clone()).await {
+            Ok(_) => created += 1,
+            Err(Error::Response(response)) => {
+                assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+                throttled += 1;
+            }
+            Err(error) => panic!("Unexpected error: {:?}", error),
+        }
+    }
+    assert!(
+        created >= BURST as usize,
+        "Created only {} volumes",
+        created
+    );
+    assert!(
+        throttled > 0,
+        "None of the {} requests were throttled",
+        REQUESTS
+    );
+
+    // the reads have no limit
+    for _ in 0 .. REQUESTS {
+        volumes_api.get_volumes(0, None, None, None).await.unwrap();
+    }
+
+    let limits = client.admin_api().get_admin_rate_limits().await.unwrap();
+    assert_eq!(limits.mutations, Some(models::RateLimit::new(BURST, RATE)));
+    assert_eq!(limits.reads, None);
+    assert_eq!(limits.throttled.len(), 1, "{:?}", limits);
+    assert_eq!(limits.throttled[0].class, models::MethodClass::Mutation);
+    assert_eq!(limits.throttled[0].throttled, throttled as u64);
+
+    // the bucket is refilled over time
+    tokio::time::sleep(Duration::from_secs(BURST as u64 / RATE as u64)).await;
+    volumes_api
+        .put_volume(&Uuid::new_v4(), body.clone())
+        .await
+        .unwrap();
+
+    // whereas the gRPC requests marked as internal are never limited
+    let uri = format!("https://{}:50051", cluster.composer().container_ip("core"));
+    let internal = CoreClient::new(
+        uri.parse().unwrap(),
+        TimeoutOptions::new().with_client(ClientId::ClusterAgent),
+    )
+    .await;
+    let mut volumes = vec![];
+    for _ in 0 .. REQUESTS {
+        let volume = internal
+            .volume()
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: SIZE,
+                    replicas: 1,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        volumes.push(volume.spec().uuid);
+    }
+
+    // unlike those which are not
+    let mut unmarked_throttled = 0;
+    for volume in &volumes {
+        let result = cluster
+            .grpc_client()
+            .volume()
+            .destroy(&DestroyVolume::new(volume), None)
+            .await;
+        if let Err(error) = result {
+            assert_eq!(error.kind, ReplyErrorKind::RateLimited, "{:?}", error);
+            unmarked_throttled += 1;
+        }
+    }
+    assert!(unmarked_throttled > 0);
+}
@@ -0,0 +1,214 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::volume::VolumeSpec,
+        transport::{CreateVolume, Filter, PatchSpec, PublishVolume, SpecPatch, VolumeId},
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use serde_json::json;
+use std::time::Duration;
+
+pub(super) async fn volume_spec(cluster: &Cluster, volume: &VolumeId) -> VolumeSpec {
+    let volumes = cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume.clone()), None, None)
+        .await
+        .unwrap();
+    volumes.entries.first().unwrap().spec()
+}
+
+pub(super) async fn cluster() -> Cluster {
+    patch_cluster(true).await
+}
+
+async fn patch_cluster(allow_patch: bool) -> Cluster {
+    // the reconcilers must not act on the patched specs whilst the test checks them
+    let reconcile_period = Duration::from_secs(60);
+    ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_options(|o| o.with_allow_spec_patch(allow_patch))
+        .build()
+        .await
+        .unwrap()
+}
+
+pub(super) async fn create_volume(cluster: &Cluster) -> VolumeId {
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    volume.spec().uuid
+}
+
+/// Apply the `patch` to the volume spec, confirming it with the token of its dry run.
+pub(super) async fn patch_volume(
+    cluster: &Cluster,
+    volume: &VolumeId,
+    patch: serde_json::Value,
+) -> SpecPatch {
+    let registry = cluster.grpc_client().registry();
+    let dry_run = PatchSpec::dry_run(ResourceKind::Volume, volume.as_str(), patch.clone());
+    let result = registry.patch_spec(&dry_run, None).await.unwrap();
+    let confirmed = PatchSpec::confirmed(
+        ResourceKind::Volume,
+        volume.as_str(),
+        patch,
+        &result.confirm,
+    );
+    registry.patch_spec(&confirmed, None).await.unwrap()
+}
+
+#[tokio::test]
+async fn spec_patch_dry_run() {
+    let cluster = cluster().await;
+    let registry = cluster.grpc_client().registry();
+    let volume = create_volume(&cluster).await;
+    let patch = json!({ "labels": { "restored": "true" } });
+
+    let dry_run = PatchSpec::dry_run(ResourceKind::Volume, volume.as_str(), patch.clone());
+    let result = registry.patch_spec(&dry_run, None).await.unwrap();
+    assert!(!result.applied);
+    assert_eq!(result.before["labels"], serde_json::Value::Null);
+    assert_eq!(result.after["labels"], patch["labels"]);
+    assert_eq!(volume_spec(&cluster, &volume).await.labels, None);
+
+    // the patch may only be applied with the token of its dry run
+    let unconfirmed = PatchSpec {
+        dry_run: false,
+        ..dry_run.clone()
+    };
+    let error = registry.patch_spec(&unconfirmed, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    let other_patch = json!({ "labels": { "restored": "false" } });
+    let mismatched = PatchSpec::confirmed(
+        ResourceKind::Volume,
+        volume.as_str(),
+        other_patch,
+        &result.confirm,
+    );
+    let error = registry.patch_spec(&mismatched, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(volume_spec(&cluster, &volume).await.labels, None);
+
+    let confirmed = PatchSpec::confirmed(
+        ResourceKind::Volume,
+        volume.as_str(),
+        patch,
+        &result.confirm,
+    );
+    let applied = registry.patch_spec(&confirmed, None).await.unwrap();
+    assert!(applied.applied);
+    assert_eq!(applied.after, result.after);
+    let labels = volume_spec(&cluster, &volume).await.labels.unwrap();
+    assert_eq!(labels.get("restored"), Some(&"true".to_string()));
+
+    // the spec has since changed, so the token no longer applies
+    let error = registry.patch_spec(&confirmed, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // the patched spec is persisted
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let labels = volume_spec(&cluster, &volume).await.labels.unwrap();
+    assert_eq!(labels.get("restored"), Some(&"true".to_string()));
+}
+
+#[tokio::test]
+async fn spec_patch_invalid() {
+    let cluster = cluster().await;
+    let registry = cluster.grpc_client().registry();
+    let volume = create_volume(&cluster).await;
+    let before = volume_spec(&cluster, &volume).await;
+
+    let patches = vec![
+        json!({ "uuid": VolumeId::new() }),
+        json!({ "status": "Bogus" }),
+        json!({ "num_replicas": null }),
+        json!({ "num_replicas": -1 }),
+        json!({ "num_replicas": 0 }),
+        json!({ "size": 10485760 }),
+    ];
+    for patch in patches {
+        let dry_run = PatchSpec::dry_run(ResourceKind::Volume, volume.as_str(), patch.clone());
+        let error = registry.patch_spec(&dry_run, None).await.unwrap_err();
+        assert_eq!(error.kind, ReplyErrorKind::InvalidArgument, "{}", patch);
+        assert_eq!(error.resource, ResourceKind::Volume);
+    }
+    assert_eq!(volume_spec(&cluster, &volume).await, before);
+
+    let dry_run = PatchSpec::dry_run(ResourceKind::Volume, VolumeId::new().as_str(), json!({}));
+    let error = registry.patch_spec(&dry_run, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let dry_run = PatchSpec::dry_run(ResourceKind::Node, cluster.node(0).as_str(), json!({}));
+    let error = registry.patch_spec(&dry_run, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    let pool = cluster.pool(0, 0);
+    let patches = vec![
+        json!({ "node": "bogus" }),
+        json!({ "disks": ["malloc:///bogus?size_mb=100"] }),
+    ];
+    for patch in patches {
+        let dry_run = PatchSpec::dry_run(ResourceKind::Pool, pool.as_str(), patch.clone());
+        let error = registry.patch_spec(&dry_run, None).await.unwrap_err();
+        assert_eq!(error.kind, ReplyErrorKind::InvalidArgument, "{}", patch);
+        assert_eq!(error.resource, ResourceKind::Pool);
+    }
+}
+
+#[tokio::test]
+async fn spec_patch_disabled() {
+    let cluster = patch_cluster(false).await;
+    let registry = cluster.grpc_client().registry();
+    let volume = create_volume(&cluster).await;
+
+    let patch = json!({ "labels": { "restored": "true" } });
+    let dry_run = PatchSpec::dry_run(ResourceKind::Volume, volume.as_str(), patch);
+    let error = registry.patch_spec(&dry_run, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::PermissionDenied);
+    assert_eq!(volume_spec(&cluster, &volume).await.labels, None);
+}
+
+#[tokio::test]
+async fn spec_patch_stuck_operation() {
+    let cluster = cluster().await;
+    let volume_client = cluster.grpc_client().volume();
+    let volume = create_volume(&cluster).await;
+
+    // leave the volume with an operation which never completes, as if the core agent crashed
+    // before it could record its result
+    let stuck = json!({ "operation": { "operation": "Unshare", "result": null } });
+    let patched = patch_volume(&cluster, &volume, stuck).await;
+    assert!(patched.applied);
+    let publish = PublishVolume::new(volume.clone(), Some(cluster.node(0)), None);
+    let error = volume_client.publish(&publish, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPersist);
+
+    let patched = patch_volume(&cluster, &volume, json!({ "operation": null })).await;
+    assert!(patched.applied);
+    assert_eq!(volume_spec(&cluster, &volume).await.operation, None);
+    volume_client.publish(&publish, None).await.unwrap();
+}
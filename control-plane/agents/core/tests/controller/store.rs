@@ -0,0 +1,228 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        openapi::{
+            apis::{StatusCode, Uuid},
+            clients::tower::Error,
+            models,
+        },
+        store::defaults::ClusterDefaults,
+        transport::{
+            CreatePool, CreateVolume, DestroyVolume, Filter, PoolId, SetClusterDefaults, VolumeId,
+        },
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    pool::traits::PoolOperations, registry::traits::RegistryOperations,
+    volume::traits::VolumeOperations,
+};
+use std::time::{Duration, Instant};
+
+/// Test that the core agent fails over to another etcd member when the active one is stopped.
+#[tokio::test]
+async fn store_endpoints_failover() {
+    let store_timeout = Duration::from_secs(3);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_store_timeout(store_timeout)
+        .with_options(|o| o.with_etcd_members(3))
+        .build()
+        .await
+        .unwrap();
+
+    let admin_api = cluster.rest_v00().admin_api();
+    let health = admin_api.get_admin_store_health().await.unwrap();
+    assert_eq!(health.endpoints.len(), 3, "{:?}", health);
+    assert!(health.endpoints.iter().all(|e| e.reachable), "{:?}", health);
+    let active = health.active;
+
+    let member = active.split('.').next().unwrap();
+    cluster.composer().stop(member).await.unwrap();
+
+    // the store lease may have to be re-acquired through another member, after which the spec
+    // writes go through
+    let registry = cluster.grpc_client().registry();
+    let set_defaults = SetClusterDefaults {
+        defaults: ClusterDefaults::default(),
+    };
+    let start = std::time::Instant::now();
+    loop {
+        let attempt = std::time::Instant::now();
+        match registry.set_cluster_defaults(&set_defaults, None).await {
+            Ok(_) => break,
+            Err(error) if start.elapsed() < Duration::from_secs(30) => {
+                // a single attempt is bounded by the store timeout, not by a timeout per member
+                assert!(
+                    attempt.elapsed() < store_timeout * 2,
+                    "{:?}",
+                    attempt.elapsed()
+                );
+                tracing::info!("Spec write failed: {}", error);
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+            Err(error) => panic!("The spec writes did not resume: {:?}", error),
+        }
+    }
+
+    cluster
+        .grpc_client()
+        .pool()
+        .create(
+            &CreatePool {
+                node: cluster.node(0),
+                id: PoolId::from("failover-pool"),
+                disks: vec!["malloc:///disk0?size_mb=100".into()],
+                labels: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let health = admin_api.get_admin_store_health().await.unwrap();
+    assert_ne!(health.active, active);
+    let stopped = health
+        .endpoints
+        .iter()
+        .find(|e| e.endpoint == active)
+        .expect("the stopped member should be reported");
+    assert!(!stopped.reachable);
+    assert!(stopped.error.is_some());
+}
+
+fn create_volume() -> CreateVolume {
+    CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 1,
+        ..Default::default()
+    }
+}
+
+/// Stop the store with the read-only policy and check that the reads are still served from the
+/// registry whilst the writes fail fast, and that the writes go through again once it's back.
+#[tokio::test]
+async fn store_unavailable_read_only() {
+    let store_timeout = Duration::from_secs(5);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_store_timeout(store_timeout)
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_store_unavailable_policy("read-only"))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let registry = cluster.grpc_client().registry();
+    let set_defaults = SetClusterDefaults {
+        defaults: ClusterDefaults::default(),
+    };
+    let volume = volume_client.create(&create_volume(), None).await.unwrap();
+    let volume_id = volume.spec().uuid;
+
+    cluster.composer().stop("etcd").await.unwrap();
+
+    // once the store health check notices, the writes fail fast
+    let start = Instant::now();
+    loop {
+        let attempt = Instant::now();
+        match registry.set_cluster_defaults(&set_defaults, None).await {
+            Err(error) if error.kind == ReplyErrorKind::Unavailable => {
+                assert!(attempt.elapsed() < store_timeout, "{:?}", attempt.elapsed());
+                break;
+            }
+            result => {
+                assert!(
+                    start.elapsed() < Duration::from_secs(30),
+                    "The agent did not switch to read-only: {:?}",
+                    result
+                );
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        }
+    }
+    for _ in 0 .. 3 {
+        let attempt = Instant::now();
+        let error = volume_client
+            .create(&create_volume(), None)
+            .await
+            .expect_err("the store is unavailable");
+        assert_eq!(error.kind, ReplyErrorKind::Unavailable, "{:?}", error);
+        let error = volume_client
+            .destroy(&DestroyVolume::new(&volume_id), None)
+            .await
+            .expect_err("the store is unavailable");
+        assert_eq!(error.kind, ReplyErrorKind::Unavailable, "{:?}", error);
+        assert!(attempt.elapsed() < store_timeout, "{:?}", attempt.elapsed());
+    }
+    let error = cluster
+        .rest_v00()
+        .volumes_api()
+        .put_volume(
+            &Uuid::new_v4(),
+            models::CreateVolumeBody {
+                replicas: Some(1),
+                ..models::CreateVolumeBody::new(models::VolumePolicy::default(), 5242880u64)
+            },
+        )
+        .await
+        .expect_err("the store is unavailable");
+    match error {
+        Error::Response(response) => {
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE)
+        }
+        error => panic!("Unexpected error: {:?}", error),
+    }
+
+    // the reads are served from the registry, which the failed writes left untouched
+    let volumes = volume_client.get(Filter::None, None, None).await.unwrap();
+    assert_eq!(volumes.entries.len(), 1, "{:?}", volumes);
+    assert_eq!(volumes.entries[0].spec(), volume.spec());
+    let volumes = cluster
+        .rest_v00()
+        .volumes_api()
+        .get_volumes(0, None, None, None)
+        .await
+        .unwrap();
+    assert_eq!(volumes.entries.len(), 1);
+
+    // the writes go through once the store is back
+    cluster.composer().start("etcd").await.unwrap();
+    let start = Instant::now();
+    while let Err(error) = registry.set_cluster_defaults(&set_defaults, None).await {
+        assert!(
+            start.elapsed() < Duration::from_secs(30),
+            "The agent did not recover: {:?}",
+            error
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    volume_client.create(&create_volume(), None).await.unwrap();
+    volume_client
+        .destroy(&DestroyVolume::new(&volume_id), None)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,82 @@
+#![cfg(test)]
+
+use common_lib::types::v0::{
+    openapi::models,
+    transport::{CreateVolume, PublishVolume, VolumeId},
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::time::Duration;
+
+async fn volume_spec(cluster: &Cluster, volume: &VolumeId) -> models::VolumeSpec {
+    let specs = cluster.rest_v00().specs_api().get_specs().await.unwrap();
+    specs
+        .volumes
+        .into_iter()
+        .find(|spec| spec.uuid.to_string() == volume.to_string())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn spec_timestamps() {
+    // the reconcilers must not update the specs whilst the test checks them
+    let reconcile_period = Duration::from_secs(60);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+
+    let created = volume_spec(&cluster, &volume_id).await;
+    assert!(created.created_at.is_some());
+    assert_eq!(created.updated_at, created.created_at);
+    let specs = cluster.rest_v00().specs_api().get_specs().await.unwrap();
+    assert!(specs.replicas.iter().all(|r| r.created_at.is_some()));
+    assert!(specs.pools.iter().all(|p| p.created_at.is_some()));
+
+    // the times are exposed in seconds, so let one go by before updating the volume
+    tokio::time::sleep(Duration::from_millis(1500)).await;
+    volume_client
+        .publish(
+            &PublishVolume::new(volume_id.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let published = volume_spec(&cluster, &volume_id).await;
+    assert_eq!(published.created_at, created.created_at);
+    assert!(published.updated_at > created.updated_at);
+    let specs = cluster.rest_v00().specs_api().get_specs().await.unwrap();
+    let nexus = specs.nexuses.first().unwrap();
+    assert!(nexus.created_at >= published.created_at);
+    assert!(nexus.updated_at.is_some());
+
+    // the times are persisted along with the specs
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let restart_specs = cluster.rest_v00().specs_api().get_specs().await.unwrap();
+    assert_eq!(specs, restart_specs);
+}
@@ -0,0 +1,244 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        openapi::{apis::StatusCode, clients::tower::Error, models},
+        transport::{
+            cluster_agent::NodeAgentInfo, CreateVolume, FailedPath, Filter, NodeId, PathSeverity,
+            PathStats, PublishVolume, ReportFailedPaths, ReportPathStats, TargetPathStats,
+            VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    ha_node::{client::ClusterAgentClient, traits::ClusterAgentOperations},
+    volume::traits::VolumeOperations,
+};
+use std::time::Duration;
+use utils::NVME_TARGET_NQN_PREFIX;
+
+fn nqn(volume_id: &VolumeId) -> String {
+    format!("{}{}", NVME_TARGET_NQN_PREFIX, volume_id)
+}
+
+async fn target_node(cluster: &Cluster, volume_id: &VolumeId) -> Option<NodeId> {
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume_id.clone()), None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    volume.spec().target.map(|target| target.node().clone())
+}
+
+#[tokio::test]
+async fn path_stats() {
+    let cluster = ClusterBuilder::builder()
+        .with_agents(vec!["core", "clusteragent"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_volumes(1, 5 * 1024 * 1024, 1)
+        .build()
+        .await
+        .unwrap();
+    let volume_id = cluster.volume(0);
+    let ha_api = cluster.rest_v00().ha_api();
+
+    // fake node agent which reports synthetic statistics for a few application nodes
+    let node_agent =
+        ClusterAgentClient::new("https://localhost:11500".parse().unwrap(), None).await;
+
+    let node_1 = PathStats {
+        total_paths: 2,
+        live_paths: 1,
+        failed_paths: 1,
+        reconnects: 1,
+        mean_reconnect_latency: Duration::from_millis(100),
+    };
+    let ignored = node_agent
+        .report_path_stats(&ReportPathStats::new(
+            "app-node-1".into(),
+            vec![
+                TargetPathStats::new(nqn(&volume_id), node_1),
+                TargetPathStats::new(nqn(&VolumeId::new()), PathStats::default()),
+                TargetPathStats::new("nqn.2014-08.org.nvmexpress:other".into(), node_1),
+            ],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(ignored, 2, "Unknown volumes should be ignored");
+
+    let node_2 = PathStats {
+        total_paths: 1,
+        live_paths: 1,
+        failed_paths: 0,
+        reconnects: 3,
+        mean_reconnect_latency: Duration::from_millis(300),
+    };
+    let ignored = node_agent
+        .report_path_stats(&ReportPathStats::new(
+            "app-node-2".into(),
+            vec![TargetPathStats::new(nqn(&volume_id), node_2)],
+        ))
+        .await
+        .unwrap();
+    assert_eq!(ignored, 0);
+
+    let stats = ha_api.get_path_stats(&volume_id).await.unwrap();
+    assert_eq!(stats.volume_id.to_string(), volume_id.to_string());
+    let node_1 = models::PathStats::from(node_1);
+    let node_2 = models::PathStats::from(node_2);
+    // the mean latency is weighted by the reconnects of each node
+    let aggregated = models::PathStats::new(1, 2, 250_000, 4, 3);
+    assert_eq!(stats.current, aggregated);
+    assert_eq!(
+        stats.nodes,
+        vec![
+            models::NodePathStats::new("app-node-1", node_1.clone()),
+            models::NodePathStats::new("app-node-2", node_2.clone()),
+        ]
+    );
+    let history = stats
+        .history
+        .into_iter()
+        .map(|s| s.stats)
+        .collect::<Vec<_>>();
+    assert_eq!(history, vec![node_1, aggregated.clone()]);
+
+    // the first node no longer has paths to the volume
+    node_agent
+        .report_path_stats(&ReportPathStats::new("app-node-1".into(), vec![]))
+        .await
+        .unwrap();
+
+    let stats = ha_api.get_path_stats(&volume_id).await.unwrap();
+    assert_eq!(stats.current, node_2);
+    assert_eq!(stats.nodes.len(), 1);
+    let history = stats
+        .history
+        .into_iter()
+        .map(|s| s.stats)
+        .collect::<Vec<_>>();
+    assert_eq!(history.last(), Some(&node_2));
+    assert_eq!(history.len(), 3);
+
+    let error = ha_api.get_path_stats(&VolumeId::new()).await.unwrap_err();
+    match error {
+        Error::Response(response) => assert_eq!(response.status(), StatusCode::NOT_FOUND),
+        error => panic!("Unexpected error: {:?}", error),
+    }
+}
+
+#[tokio::test]
+async fn node_agent_registration_ttl() {
+    let ttl = Duration::from_secs(9);
+    let _cluster = ClusterBuilder::builder()
+        .with_agents(vec!["core", "clusteragent"])
+        .with_io_engines(1)
+        .with_options(|o| o.with_node_agent_ttl(ttl))
+        .build()
+        .await
+        .unwrap();
+
+    // fake node agent which registers with the cluster agent
+    let node_agent =
+        ClusterAgentClient::new("https://localhost:11500".parse().unwrap(), None).await;
+    let registration = node_agent
+        .register(&NodeAgentInfo::new(
+            "app-node-1".into(),
+            "10.1.0.5:11600".parse().unwrap(),
+        ))
+        .await
+        .unwrap();
+    assert_eq!(registration.ttl(), ttl);
+    assert_eq!(registration.heartbeat_interval(), Duration::from_secs(3));
+}
+
+/// Report failed paths of a published volume and check that its target is failed over to another
+/// node when the path failed hard, whereas a path with a degraded latency is merely logged.
+#[tokio::test]
+async fn failed_path_severities() {
+    let cluster = ClusterBuilder::builder()
+        .with_agents(vec!["core", "clusteragent"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5 * 1024 * 1024,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(0)),
+                Some(VolumeShareProtocol::Nvmf),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+
+    // fake node agent which reports the failed paths of an application node
+    let node_agent =
+        ClusterAgentClient::new("https://localhost:11500".parse().unwrap(), None).await;
+    let report = |severity| {
+        ReportFailedPaths::new(
+            "app-node-1".into(),
+            vec![FailedPath::new(nqn(&volume_id)).with_severity(severity)],
+        )
+    };
+
+    node_agent
+        .report_failed_nvme_paths(&report(PathSeverity::DegradedLatency))
+        .await
+        .unwrap();
+    assert_eq!(
+        target_node(&cluster, &volume_id).await,
+        Some(cluster.node(0))
+    );
+
+    node_agent
+        .report_failed_nvme_paths(&report(PathSeverity::Failed))
+        .await
+        .unwrap();
+    assert_eq!(
+        target_node(&cluster, &volume_id).await,
+        Some(cluster.node(1))
+    );
+}
+
+/// A report without any failed path is refused as invalid.
+#[tokio::test]
+async fn failed_paths_empty() {
+    let _cluster = ClusterBuilder::builder()
+        .with_agents(vec!["core", "clusteragent"])
+        .with_io_engines(0)
+        .build()
+        .await
+        .unwrap();
+
+    let node_agent =
+        ClusterAgentClient::new("https://localhost:11500".parse().unwrap(), None).await;
+    let error = node_agent
+        .report_failed_nvme_paths(&ReportFailedPaths::new("app-node-1".into(), vec![]))
+        .await
+        .expect_err("There are no failed paths");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+}
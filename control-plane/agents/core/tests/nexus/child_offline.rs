@@ -0,0 +1,106 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        ChildState, ChildUri, CreateNexus, Filter, GetSpecs, NexusId, OfflineNexusChild,
+        OnlineNexusChild,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{nexus::traits::NexusOperations, registry::traits::RegistryOperations};
+
+async fn offline_children(cluster: &Cluster, nexus: &NexusId) -> Vec<ChildUri> {
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    let spec = specs
+        .nexuses
+        .into_iter()
+        .find(|n| &n.uuid == nexus)
+        .unwrap();
+    spec.offline_children
+}
+
+/// A child taken offline is kept in the nexus, with the intent recorded in its spec, until it's
+/// brought back online.
+#[tokio::test]
+async fn nexus_child_offline() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .build()
+        .await
+        .unwrap();
+    let nexus_client = cluster.grpc_client().nexus();
+
+    let child: ChildUri =
+        "malloc:///child-1?size_mb=12&uuid=0ab1a7ec-2f0b-4e52-a5b4-2c4bb0c6c0b1".into();
+    let other: ChildUri =
+        "malloc:///child-2?size_mb=12&uuid=4a7b0566-8ec6-49e0-a8b2-1d9a292cf59b".into();
+    let nexus = nexus_client
+        .create(
+            &CreateNexus {
+                node: cluster.node(0),
+                uuid: NexusId::new(),
+                size: 5242880,
+                children: vec![child.clone().into(), other.into()],
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let online = OnlineNexusChild::new(&nexus.node, &nexus.uuid, &child);
+    let offline = OfflineNexusChild::new(&nexus.node, &nexus.uuid, &child);
+
+    let error = nexus_client
+        .online_nexus_child(&online, None)
+        .await
+        .expect_err("The child is not offline");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Child);
+
+    let offline_child = nexus_client
+        .offline_nexus_child(&offline, None)
+        .await
+        .unwrap();
+    assert_eq!(offline_child.uri, child);
+    assert_ne!(offline_child.state, ChildState::Online);
+    assert_eq!(
+        offline_children(&cluster, &nexus.uuid).await,
+        vec![child.clone()]
+    );
+
+    let error = nexus_client
+        .offline_nexus_child(&offline, None)
+        .await
+        .expect_err("The child is already offline");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // the child is still part of the nexus
+    let nexus_state = nexus_client
+        .get(Filter::Nexus(nexus.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(nexus_state[0].contains_child(&child));
+
+    nexus_client
+        .online_nexus_child(&online, None)
+        .await
+        .unwrap();
+    assert!(offline_children(&cluster, &nexus.uuid).await.is_empty());
+
+    let unknown = OfflineNexusChild::new(&nexus.node, &nexus.uuid, &"malloc:///unknown".into());
+    let error = nexus_client
+        .offline_nexus_child(&unknown, None)
+        .await
+        .expect_err("The child is not part of the nexus");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
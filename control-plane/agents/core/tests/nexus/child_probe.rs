@@ -0,0 +1,106 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::{nexus::ReplicaUri, nexus_child::NexusChild},
+        transport::{CreateNexus, CreateReplica, Filter, GetSpecs, NexusId, Protocol, ReplicaId},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    nexus::traits::NexusOperations, registry::traits::RegistryOperations,
+    replica::traits::ReplicaOperations,
+};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn nexus_child_probe() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+    let nexus_client = cluster.grpc_client().nexus();
+
+    let replica = cluster
+        .grpc_client()
+        .replica()
+        .create(
+            &CreateReplica {
+                node: cluster.node(1),
+                uuid: ReplicaId::new(),
+                pool: cluster.pool(1, 0),
+                size: 12582912,
+                thin: true,
+                share: Protocol::Nvmf,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let reachable = replica.uri.clone();
+    // a target within TEST-NET-1, which can't be routed to
+    let unroutable =
+        "nvmf://192.0.2.1:8420/nqn.2019-05.io.openebs:5bd4b4a6-2e11-4b9b-a4a5-2a4c49f4e1c4";
+    let create = CreateNexus {
+        node: cluster.node(0),
+        uuid: NexusId::new(),
+        size: 5242880,
+        children: vec![
+            NexusChild::Replica(ReplicaUri::new(&replica.uuid, &reachable.clone().into())),
+            unroutable.into(),
+        ],
+        ..Default::default()
+    };
+
+    let start = Instant::now();
+    let error = nexus_client
+        .create(&create, None)
+        .await
+        .expect_err("The unroutable child can't be reached");
+    assert!(
+        start.elapsed() < Duration::from_secs(10),
+        "{:?}",
+        start.elapsed()
+    );
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Child);
+    assert!(
+        error
+            .extra
+            .contains(&format!("'{}' is reachable", reachable)),
+        "{}",
+        error.extra
+    );
+    assert!(
+        error
+            .extra
+            .contains(&format!("'{}' is unreachable", unroutable)),
+        "{}",
+        error.extra
+    );
+
+    // the probe fails before the spec operation is started
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    assert!(specs.nexuses.is_empty());
+
+    // and the probe of the reachable child doesn't get in the way of its attachment
+    let create = CreateNexus {
+        children: vec![create.children[0].clone()],
+        ..create
+    };
+    let nexus = nexus_client.create(&create, None).await.unwrap();
+    assert_eq!(nexus.children.len(), 1);
+    let nexuses = nexus_client.get(Filter::None, None).await.unwrap().0;
+    assert_eq!(nexuses.len(), 1);
+}
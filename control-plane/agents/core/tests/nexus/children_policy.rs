@@ -0,0 +1,205 @@
+#![cfg(test)]
+
+use crate::node::helpers::wait_node_status;
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::{
+            nexus::{NexusSpec, ReplicaUri},
+            nexus_child::NexusChild,
+        },
+        transport::{
+            CreateNexus, CreateReplica, Filter, GetSpecs, NexusId, NodeStatus, Protocol, Replica,
+            ReplicaId,
+        },
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    nexus::traits::NexusOperations, registry::traits::RegistryOperations,
+    replica::traits::ReplicaOperations,
+};
+use std::time::Duration;
+
+async fn nexus_spec(cluster: &Cluster, nexus: &NexusId) -> NexusSpec {
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    specs
+        .nexuses
+        .into_iter()
+        .find(|n| &n.uuid == nexus)
+        .unwrap()
+}
+
+/// Build a cluster with the given nexus children policy, with a shared replica on the second
+/// node which is then taken offline.
+async fn cluster_with_offline_replica(policy: &str) -> (Cluster, Replica) {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(2)
+        .with_node_deadline("2s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_nexus_children_policy(policy))
+        .build()
+        .await
+        .unwrap();
+
+    let replica = cluster
+        .grpc_client()
+        .replica()
+        .create(
+            &CreateReplica {
+                node: cluster.node(1),
+                uuid: ReplicaId::new(),
+                pool: cluster.pool(1, 0),
+                size: 12582912,
+                thin: true,
+                share: Protocol::Nvmf,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let node = cluster.node(1);
+    cluster.composer().pause(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Offline).await;
+    (cluster, replica)
+}
+
+fn create_nexus(cluster: &Cluster, replica: &Replica) -> CreateNexus {
+    let local = "malloc:///local?size_mb=12&uuid=4a7b0566-8ec6-49e0-a8b2-1d9a292cf59b".into();
+    CreateNexus {
+        node: cluster.node(0),
+        uuid: NexusId::new(),
+        size: 5242880,
+        children: vec![
+            local,
+            NexusChild::Replica(ReplicaUri::new(&replica.uuid, &replica.uri.clone().into())),
+        ],
+        ..Default::default()
+    }
+}
+
+#[tokio::test]
+async fn nexus_children_policy_all() {
+    let (cluster, replica) = cluster_with_offline_replica("all").await;
+    let nexus_client = cluster.grpc_client().nexus();
+
+    let create = create_nexus(&cluster, &replica);
+    let error = nexus_client.create(&create, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Node);
+    let nexuses = nexus_client.get(Filter::None, None).await.unwrap().0;
+    assert!(nexuses.is_empty());
+}
+
+#[tokio::test]
+async fn nexus_children_policy_best_effort() {
+    let (cluster, replica) = cluster_with_offline_replica("best-effort").await;
+    let nexus_client = cluster.grpc_client().nexus();
+
+    let create = create_nexus(&cluster, &replica);
+    let nexus = nexus_client.create(&create, None).await.unwrap();
+    assert_eq!(nexus.children.len(), 1);
+    let spec = nexus_spec(&cluster, &create.uuid).await;
+    assert_eq!(spec.children.len(), 1);
+    assert_eq!(spec.deferred_children, vec![create.children[1].clone()]);
+
+    let node = cluster.node(1);
+    cluster.composer().thaw(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Online).await;
+
+    // the reconciler adds the deferred child once its node is back
+    let start = std::time::Instant::now();
+    loop {
+        let spec = nexus_spec(&cluster, &create.uuid).await;
+        let nexus = nexus_client
+            .get(Filter::Nexus(create.uuid.clone()), None)
+            .await
+            .unwrap()
+            .0;
+        let children = nexus.first().map(|n| n.children.len());
+        if spec.deferred_children.is_empty() && children == Some(2) {
+            assert_eq!(spec.children, create.children);
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!("The deferred child was not added: {:?}", spec);
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
+
+/// Build a single node cluster with the given nexus children policy, and the request to create a
+/// nexus with a local child and a child which fails to attach.
+async fn cluster_with_missing_child(policy: &str) -> (Cluster, CreateNexus) {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_nexus_children_policy(policy))
+        .build()
+        .await
+        .unwrap();
+    let create = CreateNexus {
+        node: cluster.node(0),
+        uuid: NexusId::new(),
+        size: 5242880,
+        children: vec![
+            "malloc:///attach?size_mb=12&uuid=1d5d3bb8-9c3e-4c4c-9d5c-8a3e0b6a4f11".into(),
+            "bdev:///missing".into(),
+        ],
+        ..Default::default()
+    };
+    (cluster, create)
+}
+
+#[tokio::test]
+async fn nexus_children_policy_all_attach() {
+    let (cluster, create) = cluster_with_missing_child("all").await;
+    let nexus_client = cluster.grpc_client().nexus();
+
+    nexus_client
+        .create(&create, None)
+        .await
+        .expect_err("The missing child can't be attached");
+    let nexuses = nexus_client.get(Filter::None, None).await.unwrap().0;
+    assert!(nexuses.is_empty());
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    assert!(specs.nexuses.is_empty());
+
+    // the local child was released, so it can be attached again
+    let create = CreateNexus {
+        children: vec![create.children[0].clone()],
+        ..create
+    };
+    let nexus = nexus_client.create(&create, None).await.unwrap();
+    assert_eq!(nexus.children.len(), 1);
+}
+
+#[tokio::test]
+async fn nexus_children_policy_best_effort_attach() {
+    let (cluster, create) = cluster_with_missing_child("best-effort").await;
+    let nexus_client = cluster.grpc_client().nexus();
+
+    let nexus = nexus_client.create(&create, None).await.unwrap();
+    assert_eq!(nexus.children.len(), 1);
+    assert_eq!(nexus.children[0].uri, create.children[0].uri());
+    let spec = nexus_spec(&cluster, &create.uuid).await;
+    assert_eq!(spec.children, vec![create.children[0].clone()]);
+    assert_eq!(spec.deferred_children, vec![create.children[1].clone()]);
+}
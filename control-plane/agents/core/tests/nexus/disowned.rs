@@ -0,0 +1,70 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{CreateNexus, GetSpecs, NexusId};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{nexus::traits::NexusOperations, registry::traits::RegistryOperations};
+use std::time::{Duration, Instant};
+
+/// Whether the spec of the nexus `nexus` still exists.
+async fn nexus_exists(cluster: &Cluster, nexus: &NexusId) -> bool {
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    specs.nexuses.iter().any(|n| &n.uuid == nexus)
+}
+
+#[tokio::test]
+async fn disowned_nexus_grace_period() {
+    let reconcile_period = Duration::from_millis(200);
+    let grace_period = Duration::from_secs(6);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_options(|o| o.with_disowned_nexus_grace_period(grace_period))
+        .build()
+        .await
+        .unwrap();
+
+    let nexus = cluster
+        .grpc_client()
+        .nexus()
+        .create(
+            &CreateNexus {
+                node: cluster.node(0),
+                uuid: NexusId::new(),
+                size: 5242880,
+                children: vec![
+                    "malloc:///disowned?size_mb=10&uuid=d6b2e5a4-0f1c-4b7e-9a53-3c8e1f2d4b60"
+                        .into(),
+                ],
+                managed: true,
+                owner: None,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let created = Instant::now();
+
+    // the nexus is left in place within the grace period, as it may yet regain an owner
+    tokio::time::sleep(grace_period / 2).await;
+    assert!(nexus_exists(&cluster, &nexus.uuid).await);
+
+    // but it's destroyed once the grace period elapses
+    let timeout = grace_period * 3;
+    while nexus_exists(&cluster, &nexus.uuid).await {
+        assert!(
+            created.elapsed() < timeout,
+            "The disowned nexus was not destroyed within {:?}",
+            timeout
+        );
+        tokio::time::sleep(reconcile_period).await;
+    }
+    assert!(created.elapsed() >= grace_period, "{:?}", created.elapsed());
+}
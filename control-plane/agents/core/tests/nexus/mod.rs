@@ -1,3 +1,9 @@
+mod child_offline;
+mod child_probe;
+mod children_policy;
+mod disowned;
+mod pause;
+
 use common_lib::{
     transport_api::*,
     types::v0::{
@@ -230,6 +236,16 @@ async fn nexus_share_transaction() {
         nexus_spec(&nexus, &registry_client).await.unwrap().share,
         Protocol::None
     );
+
+    // unsharing an unshared nexus is a no-op
+    nexus_client
+        .unshare(&UnshareNexus::from(&nexus), None)
+        .await
+        .expect("Unshare should be idempotent");
+    assert_eq!(
+        nexus_spec(&nexus, &registry_client).await.unwrap().share,
+        Protocol::None
+    );
 }
 
 /// Tests Store Write Failures for Nexus Child Operations
@@ -315,10 +331,13 @@ async fn nexus_child_op_transaction_store(
             .expect_err("operation already performed");
     }
     if unshare_request.is_some() {
+        // unsharing an unshared nexus is idempotent
         nexus_client
             .unshare(&unshare_request.unwrap(), None)
             .await
-            .expect_err("operation already performed");
+            .expect("already unshared");
+        let spec = nexus_spec(nexus, &registry_client).await.unwrap();
+        assert_eq!(spec.share, Protocol::None);
     }
     if add_nexus_child_request.is_some() {
         nexus_client
@@ -444,6 +463,7 @@ async fn nexus_child_transaction() {
         node: io_engine.clone(),
         nexus: nexus.uuid.clone(),
         uri: child2.into(),
+        force: false,
     };
 
     async fn check_child_operation(
@@ -580,6 +600,7 @@ async fn nexus_child_transaction_store() {
         node: io_engine.clone(),
         nexus: nexus.uuid.clone(),
         uri: child2.into(),
+        force: false,
     };
     nexus_child_op_transaction_store(
         &nexus,
@@ -0,0 +1,111 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::nexus::NexusSpec,
+        transport::{
+            CreateVolume, FreezeVolume, GetSpecs, NexusId, PublishVolume, ThawVolume,
+            UnpublishVolume, VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+async fn nexus_spec(nexus: &NexusId, client: &dyn RegistryOperations) -> Option<NexusSpec> {
+    client
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap()
+        .nexuses
+        .into_iter()
+        .find(|n| &n.uuid == nexus)
+}
+
+/// The IO of the target nexus of a volume is paused when the volume is frozen, and resumed when
+/// it's thawed.
+#[tokio::test]
+async fn nexus_pause_resume() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let registry_client = cluster.grpc_client().registry();
+
+    let volume_id = VolumeId::new();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume_id.clone(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(0)),
+                Some(VolumeShareProtocol::Nvmf),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.expect("the volume is published").uuid;
+
+    let freeze = FreezeVolume::new(volume_id.clone(), Duration::from_secs(60));
+    let volume = volume_client.freeze(&freeze, None).await.unwrap();
+    assert!(volume.state().frozen_until.is_some());
+    let spec = nexus_spec(&nexus, &registry_client).await.unwrap();
+    assert_eq!(spec.frozen_until, volume.state().frozen_until);
+
+    let error = volume_client
+        .freeze(&freeze, None)
+        .await
+        .expect_err("the nexus is already paused");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Nexus);
+
+    let thaw = ThawVolume::new(volume_id.clone());
+    let volume = volume_client.thaw(&thaw, None).await.unwrap();
+    assert!(volume.state().frozen_until.is_none());
+    let spec = nexus_spec(&nexus, &registry_client).await.unwrap();
+    assert!(spec.frozen_until.is_none());
+
+    let error = volume_client
+        .thaw(&thaw, None)
+        .await
+        .expect_err("the nexus is not paused");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Nexus);
+
+    // without a target nexus there is nothing to pause or resume
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    assert!(nexus_spec(&nexus, &registry_client).await.is_none());
+    let error = volume_client
+        .freeze(&freeze, None)
+        .await
+        .expect_err("the volume has no target nexus");
+    assert_eq!(error.kind, ReplyErrorKind::NotPublished);
+    let error = volume_client
+        .thaw(&thaw, None)
+        .await
+        .expect_err("the volume has no target nexus");
+    assert_eq!(error.kind, ReplyErrorKind::NotPublished);
+}
@@ -0,0 +1,92 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind, TimeoutOptions},
+    types::v0::transport::{CreateReplica, Protocol, ReplicaId, ShareReplica, UnshareReplica},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::{context::Context, operations::replica::traits::ReplicaOperations};
+use std::time::{Duration, Instant};
+
+/// Share the replica, expecting the call to fail fast as the breaker of its node is open.
+async fn share_fails_fast(
+    client: &impl ReplicaOperations,
+    share: &ShareReplica,
+    request_timeout: Duration,
+) {
+    let start = Instant::now();
+    let error = client
+        .share(share, Some(Context::new(None)))
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Unavailable, "{:?}", error);
+    assert_eq!(error.resource, ResourceKind::Node);
+    assert!(start.elapsed() < request_timeout, "{:?}", start.elapsed());
+}
+
+#[tokio::test]
+async fn circuit_breaker() {
+    let request_timeout = Duration::from_millis(500);
+    let cooldown = Duration::from_secs(3);
+    // the node must remain online whilst it's paused, for its calls to go through the breaker
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_node_deadline("60s")
+        .with_req_timeouts(Duration::from_millis(250), request_timeout)
+        .with_grpc_timeouts(
+            TimeoutOptions::default()
+                .with_max_retries(0)
+                .with_req_timeout(Duration::from_secs(5)),
+        )
+        .with_options(|o| {
+            o.with_node_failure_threshold(2)
+                .with_node_failure_cooldown(cooldown)
+        })
+        .build()
+        .await
+        .unwrap();
+    let io_engine = cluster.node(0);
+    let rep_client = cluster.grpc_client().replica();
+
+    let replica = rep_client
+        .create(
+            &CreateReplica {
+                node: io_engine.clone(),
+                uuid: ReplicaId::new(),
+                pool: cluster.pool(0, 0),
+                size: 12582912,
+                thin: false,
+                share: Protocol::None,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let share = ShareReplica::from(&replica);
+    let ctx = || Some(Context::new(None));
+
+    cluster.composer().pause(io_engine.as_str()).await.unwrap();
+
+    // each call waits for its timeout until the threshold is reached
+    for _ in 0 .. 2 {
+        let error = rep_client.share(&share, ctx()).await.unwrap_err();
+        assert_ne!(error.resource, ResourceKind::Node, "{:?}", error);
+    }
+
+    // and from then on they fail fast, even once the node has recovered
+    share_fails_fast(&rep_client, &share, request_timeout).await;
+    cluster.composer().thaw(io_engine.as_str()).await.unwrap();
+    share_fails_fast(&rep_client, &share, request_timeout).await;
+
+    // until the cooldown elapses and the probe finds the node responding, closing the breaker
+    tokio::time::sleep(cooldown).await;
+    rep_client.share(&share, ctx()).await.unwrap();
+    rep_client
+        .unshare(&UnshareReplica::from(&replica), ctx())
+        .await
+        .unwrap();
+}
@@ -0,0 +1,108 @@
+#![cfg(test)]
+
+use super::helpers::{get_node, wait_node_status};
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{CreateVolume, Filter, NodeStatus, VolumeId},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    node::traits::NodeOperations, replica::traits::ReplicaOperations,
+    volume::traits::VolumeOperations,
+};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn node_delete() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_node_deadline("1s")
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let replica_client = cluster.grpc_client().replica();
+
+    let volume_id = VolumeId::new();
+    cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: volume_id.clone(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let replicas = replica_client
+        .get(Filter::Volume(volume_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    let replica = replicas.first().cloned().unwrap();
+    let node = replica.node.clone();
+
+    let cordoned = node_client
+        .cordon(node.clone(), "decommission".into(), None)
+        .await
+        .unwrap();
+    let registered_at = cordoned.spec().unwrap().created_at().unwrap();
+
+    // an online node is not deleted
+    let error = node_client.delete(node.clone(), false).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+
+    // nor is a node which still hosts resources, which are all listed
+    cluster.composer().stop(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Offline).await;
+    let error = node_client.delete(node.clone(), false).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InUse);
+    assert!(error.extra.contains(replica.pool.as_str()), "{:?}", error);
+    assert!(
+        error.extra.contains(&replica.uuid.to_string()),
+        "{:?}",
+        error
+    );
+    assert!(get_node(&cluster, &node).await.is_some());
+
+    // unless the deletion is forced
+    node_client.delete(node.clone(), true).await.unwrap();
+    assert!(get_node(&cluster, &node).await.is_none());
+    let nodes = node_client.get(Filter::None, None).await.unwrap();
+    assert!(nodes.0.iter().all(|n| n.id() != &node), "{:?}", nodes);
+
+    // the node registers again as a brand new node, which is no longer cordoned
+    cluster.composer().start(node.as_str()).await.unwrap();
+    let returned = wait_node_status(&cluster, &node, NodeStatus::Online).await;
+    let spec = returned.spec().expect("The node spec is created anew");
+    assert!(!spec.cordoned());
+    assert!(spec.created_at().unwrap() > registered_at);
+
+    // and its disowned replica is garbage collected
+    let start = Instant::now();
+    loop {
+        let replicas = replica_client
+            .get(Filter::Node(node.clone()), None)
+            .await
+            .unwrap()
+            .into_inner();
+        if !replicas.iter().any(|r| r.uuid == replica.uuid) {
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(20),
+            "Timeout waiting for the disowned replica to be destroyed: {:?}",
+            replicas
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
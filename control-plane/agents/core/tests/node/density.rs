@@ -0,0 +1,58 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{Filter, NodeStatus};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::node::traits::NodeOperations;
+use std::collections::HashSet;
+
+/// Pack several io-engines in each container and check they still register as distinct nodes.
+#[tokio::test]
+async fn packed_io_engines() {
+    let io_engines = 8;
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(io_engines)
+        .with_io_engines_per_host(4)
+        .build()
+        .await
+        .unwrap();
+
+    let nodes = cluster
+        .grpc_client()
+        .node()
+        .get(Filter::None, None)
+        .await
+        .unwrap()
+        .0;
+    tracing::info!("Nodes: {:?}", nodes);
+    assert_eq!(nodes.len(), io_engines as usize);
+
+    let states = nodes
+        .iter()
+        .map(|node| node.state().cloned().unwrap())
+        .collect::<Vec<_>>();
+    assert!(states.iter().all(|s| s.status == NodeStatus::Online));
+
+    let ids = states.iter().map(|s| s.id.clone()).collect::<HashSet<_>>();
+    let expected = (0 .. io_engines)
+        .map(|i| cluster.node(i))
+        .collect::<HashSet<_>>();
+    assert_eq!(ids, expected);
+
+    let endpoints = states
+        .iter()
+        .map(|s| s.grpc_endpoint.clone())
+        .collect::<HashSet<_>>();
+    assert_eq!(endpoints.len(), io_engines as usize);
+
+    // whilst sharing just the 2 containers
+    let hosts = (0 .. io_engines)
+        .map(|i| cluster.node_ip(i).unwrap())
+        .collect::<HashSet<_>>();
+    assert_eq!(hosts.len(), 2);
+    for state in &states {
+        let host = state.grpc_endpoint.split(':').next().unwrap().to_string();
+        assert!(hosts.contains(&host));
+    }
+}
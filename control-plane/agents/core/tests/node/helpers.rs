@@ -0,0 +1,42 @@
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{Filter, Node, NodeId, NodeStatus},
+};
+use deployer_cluster::Cluster;
+use grpc::operations::node::traits::NodeOperations;
+use std::time::{Duration, Instant};
+
+/// Get the node `node`, if it's listed.
+pub(crate) async fn get_node(cluster: &Cluster, node: &NodeId) -> Option<Node> {
+    let nodes = cluster
+        .grpc_client()
+        .node()
+        .get(Filter::Node(node.clone()), None)
+        .await;
+    match nodes {
+        Ok(nodes) => nodes.0.first().cloned(),
+        Err(error) if error.kind == ReplyErrorKind::NotFound => None,
+        Err(error) => panic!("Failed to get the node: {:?}", error),
+    }
+}
+
+/// Wait until the node `node` is listed with the given `status`, and return it.
+pub(crate) async fn wait_node_status(cluster: &Cluster, node: &NodeId, status: NodeStatus) -> Node {
+    let start = Instant::now();
+    loop {
+        let found = get_node(cluster, node).await;
+        let current = found
+            .as_ref()
+            .and_then(|n| n.state().map(|s| s.status.clone()));
+        if let (Some(found), true) = (found, current.as_ref() == Some(&status)) {
+            return found;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!(
+                "Node {} did not become {:?}, it's {:?}",
+                node, status, current
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
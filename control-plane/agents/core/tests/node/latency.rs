@@ -0,0 +1,62 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    CreateVolume, GetLatencyMatrix, LatencyMatrix, PublishVolume, VolumeId,
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+
+#[tokio::test]
+async fn latency_matrix() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+
+    // the io-engines don't measure the round-trip times to their peers, so none are reported
+    let matrix = cluster
+        .grpc_client()
+        .registry()
+        .get_latency_matrix(&GetLatencyMatrix {}, None)
+        .await
+        .unwrap();
+    assert_eq!(matrix, LatencyMatrix::default());
+    let matrix = cluster
+        .rest_v00()
+        .admin_api()
+        .get_admin_latency()
+        .await
+        .unwrap();
+    assert!(matrix.nodes.is_empty(), "{:?}", matrix);
+
+    // and the missing measurements don't prevent the selection of a target node
+    let volume_client = cluster.grpc_client().volume();
+    let volume_id = VolumeId::new();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume_id.clone(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(volume.state().target.is_some(), "{:?}", volume);
+}
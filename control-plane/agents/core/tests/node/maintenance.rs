@@ -0,0 +1,122 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    CreateVolume, Filter, NodeId, NodeStatus, PublishVolume, VolumeId, VolumeStatus,
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{node::traits::NodeOperations, volume::traits::VolumeOperations};
+use std::time::{Duration, Instant};
+
+async fn node_status(cluster: &Cluster, node: &NodeId) -> Option<NodeStatus> {
+    let nodes = cluster
+        .grpc_client()
+        .node()
+        .get(Filter::Node(node.clone()), None)
+        .await
+        .unwrap();
+    nodes.0.first().unwrap().state().map(|s| s.status.clone())
+}
+
+async fn volume_status(cluster: &Cluster, volume: &VolumeId) -> VolumeStatus {
+    let volumes = cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume.clone()), None, None)
+        .await
+        .unwrap();
+    volumes.entries.first().unwrap().state().status
+}
+
+#[tokio::test]
+async fn node_maintenance() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_node_deadline("1s")
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+    let node = cluster.node(0);
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume.spec().uuid.clone(),
+                target_node: Some(node.clone()),
+                share: None,
+                reservation: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid.clone();
+
+    let maintenance = node_client
+        .put_maintenance(node.clone(), Duration::from_secs(60))
+        .await
+        .unwrap();
+    let spec = maintenance.spec().unwrap();
+    assert!(spec.maintenance_until().is_some());
+    assert!(spec.cordoned(), "a node in maintenance is not schedulable");
+
+    // the node is restarted well past its registration deadline but it's not deemed offline
+    cluster.composer().stop(node.as_str()).await.unwrap();
+    tokio::time::sleep(Duration::from_secs(4)).await;
+    assert_eq!(node_status(&cluster, &node).await, Some(NodeStatus::Online));
+    assert_eq!(
+        volume_status(&cluster, &volume_id).await,
+        VolumeStatus::Online
+    );
+    cluster.composer().start(node.as_str()).await.unwrap();
+
+    let node_spec = node_client.delete_maintenance(node.clone()).await.unwrap();
+    assert_eq!(node_spec.spec().unwrap().maintenance_until(), None);
+    assert!(!node_spec.spec().unwrap().cordoned());
+
+    // once the maintenance window expires the node is handled as usual again
+    node_client
+        .put_maintenance(node.clone(), Duration::from_secs(2))
+        .await
+        .unwrap();
+    let start = Instant::now();
+    cluster.composer().stop(node.as_str()).await.unwrap();
+    while node_status(&cluster, &node).await == Some(NodeStatus::Online) {
+        assert!(
+            start.elapsed() < Duration::from_secs(15),
+            "The node was not deemed offline after its maintenance expired"
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    assert!(
+        start.elapsed() >= Duration::from_secs(1),
+        "{:?}",
+        start.elapsed()
+    );
+    let nodes = node_client
+        .get(Filter::Node(node.clone()), None)
+        .await
+        .unwrap();
+    assert_eq!(
+        nodes.0.first().unwrap().spec().unwrap().maintenance_until(),
+        None
+    );
+}
@@ -1,26 +1,34 @@
+mod circuit_breaker;
+mod delete;
+mod density;
+pub(crate) mod helpers;
+mod latency;
+mod maintenance;
+mod returned;
+mod timeouts;
+
 use common_lib::types::v0::{
     store::node::{NodeLabels, NodeSpec},
-    transport::{APIVersion, Filter, Node, NodeId, NodeState, NodeStatus},
+    transport::{APIVersion, CreateVolume, Filter, Node, NodeId, NodeState, NodeStatus, VolumeId},
 };
 use deployer_cluster::ClusterBuilder;
-use grpc::operations::node::traits::NodeOperations;
-use std::time::Duration;
+use grpc::operations::{node::traits::NodeOperations, volume::traits::VolumeOperations};
+use std::time::{Duration, SystemTime};
 
-/// Get new `Node` from the given parameters
+/// Get new `Node` from the given parameters, its spec registered at `registered_at`
 fn new_node(
     id: NodeId,
     endpoint: String,
     status: NodeStatus,
     api_versions: Option<Vec<APIVersion>>,
+    registered_at: Option<SystemTime>,
 ) -> Node {
     Node::new(
         id.clone(),
-        Some(NodeSpec::new(
-            id.clone(),
-            endpoint.clone(),
-            NodeLabels::new(),
-            None,
-        )),
+        Some(
+            NodeSpec::new(id.clone(), endpoint.clone(), NodeLabels::new(), None)
+                .with_timestamps(registered_at, registered_at),
+        ),
         Some(NodeState::new(id, endpoint, status, api_versions)),
     )
 }
@@ -40,14 +48,22 @@ async fn node() {
         .with_max_retries(6);
 
     let maya_name = cluster.node(0);
-    let grpc = format!("{}:10124", cluster.node_ip(0));
+    let grpc = format!("{}:10124", cluster.node_ip(0).unwrap());
     let node_client = cluster.grpc_client().node();
     let nodes = node_client.get(Filter::None, None).await.unwrap();
     tracing::info!("Nodes: {:?}", nodes);
     assert_eq!(nodes.0.len(), 1);
+    let registered_at = nodes.0.first().unwrap().spec().unwrap().created_at();
+    assert!(registered_at.is_some());
     assert_eq!(
         nodes.0.first().unwrap(),
-        &new_node(maya_name.clone(), grpc.clone(), NodeStatus::Online, None)
+        &new_node(
+            maya_name.clone(),
+            grpc.clone(),
+            NodeStatus::Online,
+            None,
+            registered_at
+        )
     );
     tokio::time::sleep(std::time::Duration::from_secs(2)).await;
     let nodes = node_client.get(Filter::None, None).await.unwrap();
@@ -56,7 +72,13 @@ async fn node() {
     // still Online because the node is reachable via gRPC!
     assert_eq!(
         nodes.0.first().unwrap(),
-        &new_node(maya_name.clone(), grpc.clone(), NodeStatus::Online, None)
+        &new_node(
+            maya_name.clone(),
+            grpc.clone(),
+            NodeStatus::Online,
+            None,
+            registered_at
+        )
     );
 
     cluster.composer().kill(maya_name.as_str()).await.unwrap();
@@ -66,12 +88,18 @@ async fn node() {
     assert_eq!(nodes.0.len(), 1);
     assert_eq!(
         nodes.0.first().unwrap(),
-        &new_node(maya_name.clone(), grpc.clone(), NodeStatus::Offline, None)
+        &new_node(
+            maya_name.clone(),
+            grpc.clone(),
+            NodeStatus::Offline,
+            None,
+            registered_at
+        )
     );
     cluster.composer().start(maya_name.as_str()).await.unwrap();
 
     let node = nodes.0.first().cloned().unwrap();
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
     cluster
         .node_service_liveness(Some(bus_timeout.clone()))
         .await
@@ -82,11 +110,17 @@ async fn node() {
     assert_eq!(nodes.0.len(), 1);
     assert_eq!(
         nodes.0.first().unwrap(),
-        &new_node(maya_name.clone(), grpc.clone(), NodeStatus::Online, None)
+        &new_node(
+            maya_name.clone(),
+            grpc.clone(),
+            NodeStatus::Online,
+            None,
+            registered_at
+        )
     );
 
     cluster.composer().stop(maya_name.as_str()).await.unwrap();
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
     cluster
         .node_service_liveness(Some(bus_timeout.clone()))
         .await
@@ -118,7 +152,7 @@ async fn large_cluster() {
     tracing::info!("Nodes: {:?}", nodes);
     assert_eq!(nodes.0.len(), expected_nodes);
 
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
     cluster
         .node_service_liveness(None)
         .await
@@ -128,3 +162,76 @@ async fn large_cluster() {
     tracing::info!("Nodes: {:?}", nodes);
     assert_eq!(nodes.0.len(), expected_nodes);
 }
+
+#[tokio::test]
+async fn cordon_expiry() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let node_client = cluster.grpc_client().node();
+    let volume_client = cluster.grpc_client().volume();
+    let node_id = cluster.node(0);
+
+    let node = node_client
+        .cordon(node_id.clone(), "permanent".into(), None)
+        .await
+        .unwrap();
+    assert!(node.spec().unwrap().cordon_expiry().is_empty());
+    let node = node_client
+        .cordon(
+            node_id.clone(),
+            "maintenance".into(),
+            Some(Duration::from_secs(2)),
+        )
+        .await
+        .unwrap();
+    let spec = node.spec().unwrap();
+    assert_eq!(spec.cordon_labels(), vec!["permanent", "maintenance"]);
+    assert!(spec.cordon_expiry().contains_key("maintenance"));
+
+    // only the expiring cordon label is removed
+    let start = std::time::Instant::now();
+    loop {
+        let nodes = node_client
+            .get(Filter::Node(node_id.clone()), None)
+            .await
+            .unwrap();
+        let spec = nodes.0.first().unwrap().spec().unwrap().clone();
+        if spec.cordon_labels() == vec!["permanent"] {
+            assert!(spec.cordon_expiry().is_empty());
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "Timeout waiting for the cordon to expire"
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+
+    let volume = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 1,
+        ..Default::default()
+    };
+    volume_client
+        .create(&volume, None)
+        .await
+        .expect_err("the node is still cordoned");
+
+    node_client
+        .uncordon(node_id.clone(), "permanent".into())
+        .await
+        .unwrap();
+    volume_client
+        .create(&volume, None)
+        .await
+        .expect("the node should be schedulable again");
+}
@@ -0,0 +1,79 @@
+#![cfg(test)]
+
+use super::helpers::wait_node_status;
+use common_lib::types::v0::transport::{CreateVolume, Filter, NodeStatus, VolumeId};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+#[tokio::test]
+async fn returned_node_missing_replica() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_node_deadline("2s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let replica_client = cluster.grpc_client().replica();
+    let replicas = replica_client
+        .get(Filter::Volume(volume.spec().uuid), None)
+        .await
+        .unwrap()
+        .into_inner();
+    let replica = replicas.first().cloned().unwrap();
+
+    // destroy the replica behind the control plane's back, before the node goes away
+    let mut node0 = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    node0
+        .io_engine
+        .destroy_replica(rpc::io_engine::DestroyReplicaRequest {
+            uuid: replica.uuid.to_string(),
+        })
+        .await
+        .unwrap();
+
+    let node = cluster.node(0);
+    cluster.composer().pause(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Offline).await;
+    cluster.composer().thaw(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Online).await;
+
+    // the managed replica is recreated on the node as it was before
+    let start = std::time::Instant::now();
+    loop {
+        let replicas = replica_client
+            .get(Filter::Replica(replica.uuid.clone()), None)
+            .await
+            .map(|r| r.into_inner())
+            .unwrap_or_default();
+        if let Some(recreated) = replicas.first() {
+            assert_eq!(recreated.pool, replica.pool);
+            assert_eq!(recreated.size, replica.size);
+            assert_eq!(recreated.share, replica.share);
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(30) {
+            panic!("The missing replica was not recreated");
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
@@ -0,0 +1,79 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::TimeoutOptions,
+    types::v0::transport::{CreatePool, GetBlockDevices},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::{
+    context::Context,
+    operations::{node::traits::NodeOperations, pool::traits::PoolOperations},
+};
+use std::time::{Duration, Instant};
+
+#[tokio::test]
+async fn operation_class_timeouts() {
+    let fast_timeout = Duration::from_millis(500);
+    let slow_timeout = Duration::from_secs(10);
+    let stall = Duration::from_secs(3);
+    // the node must remain online whilst it's paused, for its calls to go through
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_node_deadline("60s")
+        .with_cache_period("60s")
+        .with_req_timeouts(Duration::from_millis(250), Duration::from_secs(1))
+        .with_grpc_timeouts(
+            TimeoutOptions::default()
+                .with_max_retries(0)
+                .with_req_timeout(Duration::from_secs(15)),
+        )
+        .with_options(|o| {
+            o.with_fast_req_timeout(fast_timeout)
+                .with_slow_req_timeout(slow_timeout)
+        })
+        .build()
+        .await
+        .unwrap();
+    let io_engine = cluster.node(0);
+    let ctx = || Some(Context::new(None));
+
+    // a stalled dataplane fails a list fast
+    cluster.composer().pause(io_engine.as_str()).await.unwrap();
+    let start = Instant::now();
+    cluster
+        .grpc_client()
+        .node()
+        .get_block_devices(
+            &GetBlockDevices {
+                node: io_engine.clone(),
+                all: true,
+            },
+            ctx(),
+        )
+        .await
+        .expect_err("The dataplane is stalled");
+    assert!(start.elapsed() < stall, "{:?}", start.elapsed());
+
+    // whilst a pool creation is given the time to complete once the dataplane recovers
+    let thaw = async {
+        tokio::time::sleep(stall).await;
+        cluster.composer().thaw(io_engine.as_str()).await.unwrap();
+    };
+    let start = Instant::now();
+    let (pool, _) = tokio::join!(
+        cluster.grpc_client().pool().create(
+            &CreatePool {
+                node: io_engine.clone(),
+                id: "slowpool".into(),
+                disks: vec!["malloc:///slowdisk?size_mb=100".into()],
+                labels: None,
+            },
+            ctx(),
+        ),
+        thaw
+    );
+    pool.unwrap();
+    assert!(start.elapsed() >= stall, "{:?}", start.elapsed());
+}
@@ -0,0 +1,123 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    CreateReplica, Filter, GetSpecs, Protocol, ReplicaId, DEFAULT_POOL_CLUSTER_SIZE,
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    pool::traits::PoolOperations, registry::traits::RegistryOperations,
+    replica::traits::ReplicaOperations,
+};
+use std::time::Duration;
+
+async fn pool_used(cluster: &Cluster) -> u64 {
+    let pools = cluster
+        .grpc_client()
+        .pool()
+        .get(Filter::Pool(cluster.pool(0, 0)), None)
+        .await
+        .unwrap();
+    pools.into_inner()[0].state().unwrap().used
+}
+
+/// A replica whose size is not a multiple of the cluster size of its pool is rounded up to it, so
+/// that its spec accounts for the space which it really takes from the pool.
+#[tokio::test]
+async fn replica_cluster_size() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    cluster
+        .wait_pools_online(Duration::from_secs(10))
+        .await
+        .unwrap();
+    let used = pool_used(&cluster).await;
+
+    let requested = DEFAULT_POOL_CLUSTER_SIZE + 512;
+    let rounded = 2 * DEFAULT_POOL_CLUSTER_SIZE;
+    let replica = cluster
+        .grpc_client()
+        .replica()
+        .create(
+            &CreateReplica {
+                node: cluster.node(0),
+                uuid: ReplicaId::new(),
+                pool: cluster.pool(0, 0),
+                size: requested,
+                thin: false,
+                share: Protocol::None,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(replica.size, rounded);
+
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    let spec = specs
+        .replicas
+        .iter()
+        .find(|spec| spec.uuid == replica.uuid)
+        .unwrap();
+    assert_eq!(spec.size, rounded);
+    assert_eq!(spec.requested_size, Some(requested));
+
+    // the pool state is refreshed by the next poll of its node
+    let start = std::time::Instant::now();
+    loop {
+        let used_now = pool_used(&cluster).await;
+        if used_now != used {
+            assert_eq!(used_now - used, rounded);
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The pool's used bytes were not refreshed"
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+
+    // an aligned size is kept as it is
+    let replica = cluster
+        .grpc_client()
+        .replica()
+        .create(
+            &CreateReplica {
+                node: cluster.node(0),
+                uuid: ReplicaId::new(),
+                pool: cluster.pool(0, 0),
+                size: rounded,
+                thin: false,
+                share: Protocol::None,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    let spec = specs
+        .replicas
+        .iter()
+        .find(|spec| spec.uuid == replica.uuid)
+        .unwrap();
+    assert_eq!(spec.size, rounded);
+    assert_eq!(spec.requested_size, None);
+}
@@ -0,0 +1,150 @@
+#![cfg(test)]
+
+use crate::node::helpers::wait_node_status;
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        CreatePool, CreateReplica, GetSpecs, NodeId, NodeStatus, PoolId, RefreshRegistry, ReplicaId,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    pool::traits::PoolOperations, registry::traits::RegistryOperations,
+    replica::traits::ReplicaOperations,
+};
+use std::time::Duration;
+
+const POOL: &str = "conflict-pool";
+
+/// Create the pool on the first node and then move it behind the control plane's back to the
+/// second node, which then re-registers with the core agent.
+/// Returns the cluster along with the node which the pool spec points to.
+async fn conflicting_pool(policy: &str) -> (Cluster, NodeId) {
+    let reconcile_period = Duration::from_secs(60);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_node_deadline("2s")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_options(|o| o.with_conflict_policy(policy))
+        .build()
+        .await
+        .unwrap();
+
+    cluster
+        .grpc_client()
+        .pool()
+        .create(
+            &CreatePool {
+                node: cluster.node(0),
+                id: PoolId::from(POOL),
+                disks: vec!["malloc:///disk0?size_mb=100".into()],
+                labels: None,
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let mut node0 = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    node0
+        .io_engine
+        .destroy_pool(rpc::io_engine::DestroyPoolRequest {
+            name: POOL.to_string(),
+        })
+        .await
+        .unwrap();
+    let mut node1 = cluster.grpc_handle(cluster.node(1).as_str()).await.unwrap();
+    node1
+        .io_engine
+        .create_pool(rpc::io_engine::CreatePoolRequest {
+            name: POOL.to_string(),
+            disks: vec!["malloc:///disk1?size_mb=100".into()],
+        })
+        .await
+        .unwrap();
+
+    let node = cluster.node(1);
+    cluster.composer().pause(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Offline).await;
+    cluster.composer().thaw(node.as_str()).await.unwrap();
+    wait_node_status(&cluster, &node, NodeStatus::Online).await;
+
+    let spec_node = pool_spec_node(&cluster).await;
+    (cluster, spec_node)
+}
+
+/// Get the node of the conflicting pool's spec.
+async fn pool_spec_node(cluster: &Cluster) -> NodeId {
+    cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap()
+        .pools
+        .into_iter()
+        .find(|p| p.id.as_str() == POOL)
+        .expect("the pool spec should exist")
+        .node
+}
+
+/// Create a replica on the conflicting pool.
+async fn create_replica(cluster: &Cluster, node: NodeId) -> Result<(), ReplyError> {
+    let request = CreateReplica {
+        node,
+        uuid: ReplicaId::new(),
+        pool: PoolId::from(POOL),
+        size: 5 * 1024 * 1024,
+        thin: false,
+        ..Default::default()
+    };
+    cluster
+        .grpc_client()
+        .replica()
+        .create(&request, None)
+        .await
+        .map(|_| ())
+}
+
+#[tokio::test]
+async fn conflict_prefer_store() {
+    let (cluster, spec_node) = conflicting_pool("prefer-store").await;
+    assert_eq!(spec_node, cluster.node(0));
+}
+
+#[tokio::test]
+async fn conflict_prefer_node() {
+    let (cluster, spec_node) = conflicting_pool("prefer-node").await;
+    assert_eq!(spec_node, cluster.node(1));
+    create_replica(&cluster, cluster.node(1)).await.unwrap();
+}
+
+#[tokio::test]
+async fn conflict_manual() {
+    let (cluster, spec_node) = conflicting_pool("manual").await;
+    assert_eq!(spec_node, cluster.node(0));
+
+    let error = create_replica(&cluster, cluster.node(1)).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Conflict);
+    assert_eq!(error.resource, ResourceKind::Pool);
+
+    // the conflict is cleared once the node reports the pool as in its spec again
+    let mut node1 = cluster.grpc_handle(cluster.node(1).as_str()).await.unwrap();
+    node1
+        .io_engine
+        .destroy_pool(rpc::io_engine::DestroyPoolRequest {
+            name: POOL.to_string(),
+        })
+        .await
+        .unwrap();
+    cluster
+        .grpc_client()
+        .registry()
+        .refresh_registry(&RefreshRegistry {}, None)
+        .await
+        .unwrap();
+    let error = create_replica(&cluster, cluster.node(1)).await.unwrap_err();
+    assert_ne!(error.kind, ReplyErrorKind::Conflict);
+}
@@ -0,0 +1,89 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::{
+        openapi::{apis::StatusCode, clients::tower::Error},
+        transport::{DestroyPool, DiscoveredPool, Filter},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{node::traits::NodeOperations, pool::traits::PoolOperations};
+
+const POOL_SIZE_BYTES: u64 = 128 * 1024 * 1024;
+
+/// The io-engine cannot scan its disks for pool metadata, and so the discovery must be reported as
+/// unsupported rather than as an empty list, whilst an entry as it'd be discovered can still be
+/// imported directly.
+#[tokio::test]
+async fn pool_discovery() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .build()
+        .await
+        .unwrap();
+    let node = cluster.node(0);
+    let pool_client = cluster.grpc_client().pool();
+
+    let error = cluster
+        .grpc_client()
+        .node()
+        .discover_pools(node.clone())
+        .await
+        .expect_err("The dataplane cannot scan for pools");
+    assert!(matches!(
+        error,
+        ReplyError {
+            kind: ReplyErrorKind::Unimplemented,
+            resource: ResourceKind::Pool,
+            ..
+        }
+    ));
+
+    let error = cluster
+        .rest_v00()
+        .pools_api()
+        .get_node_discovered_pools(node.as_str())
+        .await
+        .expect_err("The dataplane cannot scan for pools");
+    match error {
+        Error::Response(response) => {
+            assert_eq!(response.status(), StatusCode::NOT_IMPLEMENTED)
+        }
+        error => panic!("Unexpected error: {:?}", error),
+    }
+
+    let pool = pool_client
+        .get(Filter::Pool(cluster.pool(0, 0)), None)
+        .await
+        .unwrap()
+        .into_inner()
+        .remove(0);
+    let disks = pool.spec().unwrap().disks;
+    pool_client
+        .destroy(
+            &DestroyPool {
+                node: node.clone(),
+                id: pool.id().clone(),
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let discovered = DiscoveredPool {
+        node: node.clone(),
+        id: pool.id().clone(),
+        uuid: None,
+        disks: disks.clone(),
+        capacity: POOL_SIZE_BYTES,
+        spec_exists: false,
+    };
+    let imported = pool_client.create(&discovered, None).await.unwrap();
+    assert_eq!(imported.node(), node);
+    assert_eq!(imported.spec().unwrap().disks, disks);
+    assert!(imported.state().is_some());
+}
@@ -0,0 +1,73 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{CreateReplica, DestroyReplica, GetSpecs, ReplicaId, ReplicaOwners},
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{registry::traits::RegistryOperations, replica::traits::ReplicaOperations};
+
+/// Get the generation of the replica `replica`
+async fn replica_generation(cluster: &Cluster, replica: &ReplicaId) -> u64 {
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    let spec = specs.replicas.into_iter().find(|r| &r.uuid == replica);
+    spec.expect("The replica spec should exist").generation
+}
+
+#[tokio::test]
+async fn replica_uuid_reuse() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+    let replica_client = cluster.grpc_client().replica();
+
+    let replica_id = ReplicaId::new();
+    let create = CreateReplica {
+        node: cluster.node(0),
+        uuid: replica_id.clone(),
+        pool: cluster.pool(0, 0),
+        size: 5 * 1024 * 1024,
+        thin: false,
+        ..Default::default()
+    };
+    let destroy = |generation: u64| DestroyReplica {
+        node: cluster.node(0),
+        pool: cluster.pool(0, 0),
+        uuid: replica_id.clone(),
+        disowners: ReplicaOwners::default().with_generation(generation),
+        ..Default::default()
+    };
+
+    replica_client.create(&create, None).await.unwrap();
+    let stale = replica_generation(&cluster, &replica_id).await;
+    assert!(stale > 0);
+    replica_client.destroy(&destroy(stale), None).await.unwrap();
+
+    // recreate the replica with the same uuid, which is allowed as tombstones are off by default
+    replica_client.create(&create, None).await.unwrap();
+    let generation = replica_generation(&cluster, &replica_id).await;
+    assert!(generation > stale, "{} > {}", generation, stale);
+
+    // a stale reference does not bind to the new replica
+    let error = replica_client
+        .destroy(&destroy(stale), None)
+        .await
+        .expect_err("The reference is to the destroyed replica");
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Replica);
+
+    replica_client
+        .destroy(&destroy(generation), None)
+        .await
+        .unwrap();
+}
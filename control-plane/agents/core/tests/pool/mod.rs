@@ -1,3 +1,11 @@
+mod cluster_size;
+mod conflicts;
+mod discovery;
+mod generation;
+mod replica_uuids;
+mod sizes;
+mod tombstone;
+
 use common_lib::{
     store::etcd::Etcd,
     transport_api::{ReplyError, ReplyErrorKind, ResourceKind, TimeoutOptions},
@@ -19,6 +27,7 @@ use common_lib::{
         },
     },
 };
+use control_plane_client::{CreateVolumeRequest, RetryPolicy};
 use deployer_cluster::{Cluster, ClusterBuilder};
 use grpc::{
     context::Context,
@@ -319,6 +328,19 @@ async fn replica_transaction() {
             .share,
         Protocol::None
     );
+
+    // unsharing an unshared replica is a no-op
+    rep_client
+        .unshare(&UnshareReplica::from(&replica), None)
+        .await
+        .expect("Unshare should be idempotent");
+    assert_eq!(
+        replica_spec(&replica, &registry_client)
+            .await
+            .unwrap()
+            .share,
+        Protocol::None
+    );
 }
 
 /// Tests Store Write Failures for Replica Operations
@@ -387,10 +409,13 @@ async fn replica_op_transaction_store(
             .expect_err("already done");
     }
     if unshare.clone().is_some() {
+        // unsharing an unshared replica is idempotent
         rep_client
             .unshare(&unshare.as_ref().unwrap().clone(), None)
             .await
-            .expect_err("already done");
+            .expect("already unshared");
+        let spec = replica_spec(replica, &registry_client).await.unwrap();
+        assert_eq!(spec.share, Protocol::None);
     }
 }
 
@@ -485,11 +510,19 @@ async fn reconciler_missing_pool_state() {
 
     // create volume to fill up some of the pool space
     for _ in 0 .. 10 {
-        let body = CreateVolumeBody::new(VolumePolicy::default(), 1, 8388608u64, false);
+        let body = CreateVolumeBody {
+            replicas: Some(1),
+            thin: Some(false),
+            ..CreateVolumeBody::new(VolumePolicy::default(), 8388608u64)
+        };
         let volume = VolumeId::new();
         volumes_api.put_volume(&volume, body).await.unwrap();
     }
-    let replicas = client.replicas_api().get_replicas().await.unwrap();
+    let replicas = client
+        .replicas_api()
+        .get_replicas(None, None)
+        .await
+        .unwrap();
 
     let pool = pools_api
         .get_pool(cluster.pool(0, 0).as_str())
@@ -522,7 +555,11 @@ async fn reconciler_missing_pool_state() {
     pool_checker(&cluster, pool.state.as_ref()).await;
 
     // we should have also "imported" the same replicas, perhaps in a different order...
-    let current_replicas = client.replicas_api().get_replicas().await.unwrap();
+    let current_replicas = client
+        .replicas_api()
+        .get_replicas(None, None)
+        .await
+        .unwrap();
     assert_eq!(
         replicas
             .iter()
@@ -757,32 +794,31 @@ async fn disown_unused_replicas() {
         .await
         .unwrap();
 
-    let rest_api = cluster.rest_v00();
-    let volumes_api = rest_api.volumes_api();
+    let client = cluster.client();
     let node = cluster.node(0).to_string();
 
-    let volume = volumes_api
-        .put_volume(
-            &"1e3cf927-80c2-47a8-adf0-95c481bdd7b7".parse().unwrap(),
-            models::CreateVolumeBody::new(models::VolumePolicy::default(), 1, 5242880u64, false),
-        )
-        .await
-        .unwrap();
-
-    let volume = volumes_api
-        .put_volume_target(&volume.spec.uuid, &node, models::VolumeShareProtocol::Nvmf)
+    let request = CreateVolumeRequest::new(5242880)
+        .with_uuid("1e3cf927-80c2-47a8-adf0-95c481bdd7b7".parse().unwrap())
+        .with_policy(VolumePolicy::default())
+        .with_replicas(1)
+        .with_thin(false);
+    let volume = client.create_volume(request).await.unwrap();
+    let volume_id = volume.spec.uuid;
+    client
+        .publish(&volume_id, &node, models::VolumeShareProtocol::Nvmf)
         .await
         .unwrap();
 
     cluster.composer().pause(&node).await.unwrap();
-    volumes_api
-        .del_volume_target(&volume.spec.uuid, Some(false))
+    cluster
+        .client()
+        .with_retry(RetryPolicy::none())
+        .unpublish(&volume_id, false)
         .await
         .expect_err("io-engine is down");
     cluster.composer().kill(&node).await.unwrap();
 
-    let volume = volumes_api.get_volume(&volume.spec.uuid).await.unwrap();
-    tracing::info!("Volume: {:?}", volume);
+    let volume = client.get_volume(&volume_id).await.unwrap();
 
     assert!(volume.spec.target.is_some(), "Unpublish failed");
 
@@ -835,7 +871,7 @@ async fn test_disown_missing_replica_owners() {
     let num_replicas = cluster
         .rest_v00()
         .replicas_api()
-        .get_replicas()
+        .get_replicas(None, None)
         .await
         .expect("Failed to get replicas.")
         .len();
@@ -858,7 +894,7 @@ async fn test_disown_missing_replica_owners() {
 
     // Restart the core agent so that it reloads the modified replica spec from the persistent
     // store.
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
 
     // Allow time for the core agent to restart.
     sleep(Duration::from_secs(2));
@@ -867,7 +903,7 @@ async fn test_disown_missing_replica_owners() {
     let num_replicas = cluster
         .rest_v00()
         .replicas_api()
-        .get_replicas()
+        .get_replicas(None, None)
         .await
         .expect("Failed to get replicas.")
         .len();
@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{Filter, Protocol, ReplicaId};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::replica::traits::ReplicaOperations;
+
+const SIZE: u64 = 8 * 1024 * 1024;
+
+/// The replicas given a uuid are created on their pool alongside the generated ones.
+#[tokio::test]
+async fn replica_with_uuid() {
+    let uuid = ReplicaId::new();
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_replicas(1, SIZE, Protocol::None)
+        .with_replica_uuid(1, 0, uuid.clone(), 2 * SIZE, Protocol::Nvmf)
+        .build()
+        .await
+        .unwrap();
+
+    let replicas = cluster
+        .grpc_client()
+        .replica()
+        .get(Filter::None, None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 3);
+    let replica = replicas.iter().find(|r| r.uuid == uuid).unwrap();
+    assert_eq!(replica.node, cluster.node(1));
+    assert_eq!(replica.pool, cluster.pool(1, 0));
+    assert_eq!(replica.size, 2 * SIZE);
+    assert_eq!(replica.share, Protocol::Nvmf);
+}
+
+/// A uuid used by more than one replica of the cluster is refused before the cluster is built.
+#[tokio::test]
+async fn replica_with_uuid_duplicate() {
+    let uuid = ReplicaId::new();
+    ClusterBuilder::builder()
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_replica_uuid(0, 0, uuid.clone(), SIZE, Protocol::None)
+        .with_replica_uuid(1, 0, uuid, SIZE, Protocol::None)
+        .build()
+        .await
+        .expect_err("The uuid is used by two replicas");
+    ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_replicas(1, SIZE, Protocol::None)
+        .with_replica_uuid(0, 0, Cluster::replica(0, 0, 0), SIZE, Protocol::None)
+        .build()
+        .await
+        .expect_err("The uuid is used by a generated replica");
+    ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_replica_uuid(0, 1, ReplicaId::new(), SIZE, Protocol::None)
+        .build()
+        .await
+        .expect_err("The pool does not exist");
+}
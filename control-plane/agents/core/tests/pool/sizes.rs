@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::Filter;
+use deployer_cluster::{Cluster, ClusterBuilder, TmpDiskFile, DEFAULT_POOL_SIZE};
+use grpc::operations::pool::traits::PoolOperations;
+use std::time::Duration;
+
+/// The malloc pools of a node may be given their own size, whilst the others keep the default.
+#[tokio::test]
+async fn pool_sizes() {
+    let large = 200 * 1024 * 1024;
+    let odd = DEFAULT_POOL_SIZE + 4 * 512;
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(2)
+        .with_pool_size(1, 0, large)
+        .with_pool_size(1, 1, odd)
+        .build()
+        .await
+        .unwrap();
+    cluster
+        .wait_pools_online(Duration::from_secs(10))
+        .await
+        .unwrap();
+
+    // the capacity of a pool is that of its disk, less the metadata of the pool
+    let default = capacity(&cluster, 0, 0).await;
+    assert!(default <= DEFAULT_POOL_SIZE);
+    assert_eq!(capacity(&cluster, 0, 1).await, default);
+    let capacity_large = capacity(&cluster, 1, 0).await;
+    assert!(capacity_large > DEFAULT_POOL_SIZE && capacity_large <= large);
+    assert!(capacity(&cluster, 1, 1).await <= odd);
+    assert_eq!(cluster.pool_size(0, 0), Some(DEFAULT_POOL_SIZE));
+    assert_eq!(cluster.pool_size(1, 0), Some(large));
+    assert_eq!(cluster.pool_size(1, 1), Some(odd));
+}
+
+/// The pools may all be given a size, whilst those of a node, or a single pool, have their own.
+#[tokio::test]
+async fn pool_sizes_per_node() {
+    let small = 64 * 1024 * 1024;
+    let large = 200 * 1024 * 1024;
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools_sized(2, small)
+        .with_node_pool_size(1, large)
+        .with_pool_size(1, 1, DEFAULT_POOL_SIZE)
+        .build()
+        .await
+        .unwrap();
+    cluster
+        .wait_pools_online(Duration::from_secs(10))
+        .await
+        .unwrap();
+
+    assert_eq!(cluster.pool_size(0, 0), Some(small));
+    assert_eq!(cluster.pool_size(0, 1), Some(small));
+    assert_eq!(cluster.pool_size(1, 0), Some(large));
+    assert_eq!(cluster.pool_size(1, 1), Some(DEFAULT_POOL_SIZE));
+    assert_eq!(cluster.pool_size(0, 2), None);
+
+    assert!(capacity(&cluster, 0, 0).await <= small);
+    assert!(capacity(&cluster, 1, 0).await > DEFAULT_POOL_SIZE);
+    let capacity_default = capacity(&cluster, 1, 1).await;
+    assert!(capacity_default > small && capacity_default <= DEFAULT_POOL_SIZE);
+}
+
+async fn capacity(cluster: &Cluster, node: u32, pool: u32) -> u64 {
+    let pools = cluster
+        .grpc_client()
+        .pool()
+        .get(Filter::Pool(cluster.pool(node, pool)), None)
+        .await
+        .unwrap();
+    pools.into_inner()[0].state().unwrap().capacity
+}
+
+/// A pool size which is not a multiple of 512 bytes, or that of a pool which isn't a malloc pool,
+/// is refused before the cluster is built.
+#[tokio::test]
+async fn pool_sizes_invalid() {
+    ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_pool_size(0, 0, DEFAULT_POOL_SIZE + 100)
+        .build()
+        .await
+        .expect_err("The size is not a multiple of 512");
+    ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_pool_size(0, 1, DEFAULT_POOL_SIZE)
+        .build()
+        .await
+        .expect_err("The pool does not exist");
+    ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools_sized(1, DEFAULT_POOL_SIZE + 100)
+        .build()
+        .await
+        .expect_err("The size is not a multiple of 512");
+    ClusterBuilder::builder()
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_node_pool_size(2, DEFAULT_POOL_SIZE)
+        .build()
+        .await
+        .expect_err("The node does not exist");
+}
+
+/// A pool may be created on a tmp disk with a block size of 4096 bytes.
+#[tokio::test]
+async fn pool_4k_blk_size() {
+    let disk = TmpDiskFile::new_with_blk_size("pool-4k-blk-size", DEFAULT_POOL_SIZE, 4096);
+    assert_eq!(disk.blk_size(), 4096);
+    assert!(disk.uri().contains("blk_size=4096"));
+
+    let cluster = ClusterBuilder::builder()
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pool(0, disk.uri())
+        .build()
+        .await
+        .unwrap();
+    cluster
+        .wait_pools_online(Duration::from_secs(10))
+        .await
+        .unwrap();
+}
+
+/// Only block sizes of 512 and 4096 bytes are allowed for the tmp disks.
+#[test]
+#[should_panic(expected = "Unsupported tmp disk block size")]
+fn tmp_disk_blk_size_invalid() {
+    TmpDiskFile::new_with_blk_size("tmp-disk-blk-size-invalid", DEFAULT_POOL_SIZE, 1024);
+}
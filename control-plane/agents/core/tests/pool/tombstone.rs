@@ -0,0 +1,146 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        ClearTombstone, CreatePool, CreateReplica, DestroyPool, DestroyReplica, Filter, GetSpecs,
+        PoolId, ReplicaId,
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    pool::traits::PoolOperations, registry::traits::RegistryOperations,
+    replica::traits::ReplicaOperations,
+};
+use std::time::Duration;
+
+const TOMBSTONE_TTL: Duration = Duration::from_secs(2);
+
+#[tokio::test]
+async fn pool_tombstones() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_options(|o| o.with_tombstone_ttl(TOMBSTONE_TTL))
+        .build()
+        .await
+        .unwrap();
+
+    let pool_client = cluster.grpc_client().pool();
+    let registry_client = cluster.grpc_client().registry();
+
+    let pool_id = PoolId::from("tombstone-pool");
+    let create = CreatePool {
+        node: cluster.node(0),
+        id: pool_id.clone(),
+        disks: vec!["malloc:///disk0?size_mb=100".into()],
+        labels: None,
+    };
+    let destroy = DestroyPool {
+        node: cluster.node(0),
+        id: pool_id.clone(),
+    };
+    let clear = ClearTombstone::new(ResourceKind::Pool, pool_id.as_str());
+
+    for _ in 0 .. 10 {
+        pool_client.create(&create, None).await.unwrap();
+        pool_client.destroy(&destroy, None).await.unwrap();
+
+        // the destroyed pool may not be recreated straight away
+        let error = pool_client.create(&create, None).await.unwrap_err();
+        assert_eq!(error.kind, ReplyErrorKind::Conflict);
+        assert_eq!(error.resource, ResourceKind::Pool);
+
+        registry_client.clear_tombstone(&clear, None).await.unwrap();
+        pool_client.create(&create, None).await.unwrap();
+        pool_client.destroy(&destroy, None).await.unwrap();
+    }
+
+    // give the reconcilers a few passes to resurrect the pool, if they were to
+    tokio::time::sleep(reconcile_period * 5).await;
+    let pools = pool_client
+        .get(Filter::None, None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(pools.is_empty(), "{:?}", pools);
+    let specs = registry_client.get_specs(&GetSpecs {}, None).await.unwrap();
+    assert!(specs.pools.is_empty(), "{:?}", specs.pools);
+
+    // once the tombstone expires the pool can be created again
+    let error = pool_client.create(&create, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Conflict);
+    tokio::time::sleep(TOMBSTONE_TTL).await;
+    pool_client.create(&create, None).await.unwrap();
+
+    // a pool which was never destroyed has no tombstone
+    let error = registry_client
+        .clear_tombstone(
+            &ClearTombstone::new(ResourceKind::Pool, "no-such-pool"),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+}
+
+#[tokio::test]
+async fn replica_tombstones() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .with_options(|o| o.with_tombstone_ttl(TOMBSTONE_TTL))
+        .build()
+        .await
+        .unwrap();
+
+    let replica_client = cluster.grpc_client().replica();
+    let registry_client = cluster.grpc_client().registry();
+
+    let replica_id = ReplicaId::new();
+    let create = CreateReplica {
+        node: cluster.node(0),
+        uuid: replica_id.clone(),
+        pool: cluster.pool(0, 0),
+        size: 5 * 1024 * 1024,
+        thin: false,
+        ..Default::default()
+    };
+    let destroy = DestroyReplica {
+        node: cluster.node(0),
+        pool: cluster.pool(0, 0),
+        uuid: replica_id.clone(),
+        ..Default::default()
+    };
+    let clear = ClearTombstone::new(ResourceKind::Replica, &replica_id.to_string());
+
+    for _ in 0 .. 10 {
+        replica_client.create(&create, None).await.unwrap();
+        replica_client.destroy(&destroy, None).await.unwrap();
+
+        let error = replica_client.create(&create, None).await.unwrap_err();
+        assert_eq!(error.kind, ReplyErrorKind::Conflict);
+        assert_eq!(error.resource, ResourceKind::Replica);
+
+        registry_client.clear_tombstone(&clear, None).await.unwrap();
+        replica_client.create(&create, None).await.unwrap();
+        replica_client.destroy(&destroy, None).await.unwrap();
+    }
+
+    tokio::time::sleep(reconcile_period * 5).await;
+    let replicas = replica_client
+        .get(Filter::None, None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert!(replicas.is_empty(), "{:?}", replicas);
+    let specs = registry_client.get_specs(&GetSpecs {}, None).await.unwrap();
+    assert!(specs.replicas.is_empty(), "{:?}", specs.replicas);
+}
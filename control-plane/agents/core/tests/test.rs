@@ -1,4 +1,5 @@
 mod controller;
+mod ha;
 mod nexus;
 mod node;
 mod pool;
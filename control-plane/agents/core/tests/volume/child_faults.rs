@@ -0,0 +1,149 @@
+#![cfg(test)]
+
+use super::helpers::wait_till_volume_nexus;
+use common_lib::types::v0::{
+    openapi::models,
+    transport::{CreateVolume, PublishVolume, VolumeId},
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use rpc::io_engine::FaultNexusChildRequest;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Wait until the nexus child with the given `uri` reports a fault.
+async fn wait_child_fault(cluster: &Cluster, uri: &str) -> models::ChildFault {
+    let nexuses_api = cluster.rest_v00().nexuses_api();
+    let start = std::time::Instant::now();
+    loop {
+        let nexuses = nexuses_api.get_nexuses().await.unwrap();
+        let child = nexuses
+            .iter()
+            .flat_map(|n| n.children.iter())
+            .find(|c| c.uri == uri);
+        if let Some(fault) = child.and_then(|c| c.last_fault.clone()) {
+            return fault;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            panic!("Child '{}' did not report a fault: {:?}", uri, nexuses);
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
+#[tokio::test]
+async fn child_fault_reason() {
+    // the children are not replaced whilst the test checks their faults
+    let reconcile_period = Duration::from_secs(60);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(volume.spec().uuid.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.unwrap();
+    assert!(nexus.children.iter().all(|c| c.last_fault.is_none()));
+
+    let child = nexus.children.first().unwrap().uri.to_string();
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    rpc_handle
+        .io_engine
+        .fault_nexus_child(FaultNexusChildRequest {
+            uuid: nexus.uuid.to_string(),
+            uri: child.clone(),
+        })
+        .await
+        .unwrap();
+
+    let fault = wait_child_fault(&cluster, &child).await;
+    assert_eq!(fault.reason, models::ChildStateReason::ByClient);
+    assert!(fault.faulted_at >= before.as_secs());
+
+    // the fault is recorded once, rather than every time the child state is refreshed
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    assert_eq!(wait_child_fault(&cluster, &child).await, fault);
+}
+
+#[tokio::test]
+async fn child_fault_replaced() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_timed_out_child_grace_period(Duration::from_secs(3600)))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(volume.spec().uuid.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.unwrap();
+
+    let child = nexus.children.first().unwrap().uri.to_string();
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    rpc_handle
+        .io_engine
+        .fault_nexus_child(FaultNexusChildRequest {
+            uuid: nexus.uuid.to_string(),
+            uri: child.clone(),
+        })
+        .await
+        .unwrap();
+
+    // only the children whose device timed out are given the grace period
+    let children = wait_till_volume_nexus(
+        volume.uuid(),
+        2,
+        &child,
+        &volume_client,
+        &cluster.grpc_client().registry(),
+    )
+    .await;
+    assert!(!children.iter().any(|c| c.uri == child));
+}
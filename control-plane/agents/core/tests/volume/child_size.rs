@@ -0,0 +1,151 @@
+#![cfg(test)]
+
+use super::helpers::wait_volume_conditions;
+use common_lib::types::v0::transport::{
+    CreateVolume, Filter, PublishVolume, Replica, UnpublishVolume, VolumeCondition, VolumeId,
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+const SIZE_MIB: u64 = 12;
+const CONDITIONS_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Create a volume with 2 replicas, published on the first node, and shrink the replica on the
+/// second node behind the back of the control-plane, returning the shrunk replica.
+async fn undersized_child(retire: bool) -> (Cluster, VolumeId, Replica) {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core", "jsongrpc"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_retire_undersized_children(retire))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+
+    let volume_id = VolumeId::new();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume_id.clone(),
+                size: SIZE_MIB * 1024 * 1024,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(0)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.state().target.unwrap().children.len(), 2);
+
+    let replicas = volume_replicas(&cluster, &volume_id).await;
+    let replica = replicas
+        .into_iter()
+        .find(|r| r.node != cluster.node(0))
+        .expect("One replica is remote");
+    cluster
+        .rest_v00()
+        .json_grpc_api()
+        .put_node_jsongrpc(
+            replica.node.as_str(),
+            "bdev_lvol_resize",
+            serde_json::json!({
+                "name": format!("{}/{}", replica.pool, replica.name.as_str()),
+                "size_in_mib": SIZE_MIB / 2,
+            }),
+        )
+        .await
+        .expect("Failed to shrink the replica");
+
+    (cluster, volume_id, replica)
+}
+
+#[tokio::test]
+async fn undersized_child_condition() {
+    let (cluster, volume_id, replica) = undersized_child(false).await;
+
+    let volume = wait_volume_conditions(
+        &cluster.grpc_client().volume(),
+        &volume_id,
+        &[VolumeCondition::ChildSizeMismatch],
+        CONDITIONS_TIMEOUT,
+    )
+    .await;
+    let nexus = volume.state().target.unwrap();
+    assert!(nexus.contains_child(&replica.uri.as_str().into()));
+
+    // the undersized replica is not used when the nexus is recreated
+    let volume_client = cluster.grpc_client().volume();
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(0)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.unwrap();
+    assert_eq!(nexus.children.len(), 1, "{:?}", nexus);
+    assert!(!nexus.contains_child(&replica.uri.as_str().into()));
+}
+
+#[tokio::test]
+async fn undersized_child_retirement() {
+    let (cluster, volume_id, replica) = undersized_child(true).await;
+
+    // the undersized child is removed and replaced with a new replica
+    let start = std::time::Instant::now();
+    loop {
+        let replicas = volume_replicas(&cluster, &volume_id).await;
+        if replicas.len() == 2 && !replicas.iter().any(|r| r.uuid == replica.uuid) {
+            assert!(replicas.iter().all(|r| r.size >= SIZE_MIB * 1024 * 1024));
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(20) {
+            panic!(
+                "Timeout waiting for the undersized replica to be replaced: {:?}",
+                replicas
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    wait_volume_conditions(
+        &cluster.grpc_client().volume(),
+        &volume_id,
+        &[],
+        CONDITIONS_TIMEOUT,
+    )
+    .await;
+}
+
+async fn volume_replicas(cluster: &Cluster, volume: &VolumeId) -> Vec<Replica> {
+    cluster
+        .grpc_client()
+        .replica()
+        .get(Filter::Volume(volume.clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+}
@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use super::helpers::wait_volume_conditions;
+use common_lib::types::v0::transport::{CreateVolume, PublishVolume, VolumeCondition, VolumeId};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use std::time::Duration;
+
+#[tokio::test]
+async fn volume_target_missing() {
+    let reconcile_period = Duration::from_secs(10);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(volume.uuid().clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(volume.state().conditions.is_empty(), "{:?}", volume);
+    let nexus = volume.state().target.unwrap();
+
+    // destroy the target behind the back of the control plane
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    rpc_handle
+        .io_engine
+        .destroy_nexus(rpc::io_engine::DestroyNexusRequest {
+            uuid: nexus.uuid.to_string(),
+        })
+        .await
+        .unwrap();
+
+    // the divergence is picked up on the next cache period
+    let volume = wait_volume_conditions(
+        &volume_client,
+        volume.uuid(),
+        &[VolumeCondition::TargetMissing],
+        Duration::from_secs(5),
+    )
+    .await;
+    assert!(volume.state().target.is_none());
+
+    // and cleared once the reconciler has recreated the target
+    let volume =
+        wait_volume_conditions(&volume_client, volume.uuid(), &[], reconcile_period * 2).await;
+    assert_eq!(volume.state().target.map(|n| n.uuid), Some(nexus.uuid));
+}
@@ -0,0 +1,99 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    types::v0::{
+        store::{
+            definitions::{key_prefix_obj, StorableObjectType, Store},
+            replica::ReplicaSpec,
+            volume::VolumeSpec,
+        },
+        transport::{CreateVolume, VolumeId},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use std::time::Duration;
+
+/// The specs of the volume and of its replicas which are in the store, and whether they're created
+async fn stored_specs(store: &mut Etcd, volume_id: &VolumeId) -> Vec<(&'static str, bool)> {
+    let mut specs = vec![];
+    let volumes = key_prefix_obj(StorableObjectType::VolumeSpec);
+    for (_, value) in store.get_values_prefix(&volumes).await.unwrap() {
+        let volume: VolumeSpec = serde_json::from_value(value).unwrap();
+        if &volume.uuid == volume_id {
+            specs.push(("volume", volume.status.created()));
+        }
+    }
+    let replicas = key_prefix_obj(StorableObjectType::ReplicaSpec);
+    for (_, value) in store.get_values_prefix(&replicas).await.unwrap() {
+        let replica: ReplicaSpec = serde_json::from_value(value).unwrap();
+        if replica.owners.owned_by(volume_id) {
+            specs.push(("replica", replica.status.created()));
+        }
+    }
+    specs
+}
+
+#[tokio::test]
+async fn volume_create_crash() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_agents_env("CORE_FAULT_INJECTION", "volume-create-replicas"))
+        .build()
+        .await
+        .unwrap();
+
+    // the agent crashes once the replicas are created, but before any spec is stored as created
+    let volume_id = VolumeId::new();
+    cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: volume_id.clone(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .expect_err("The core agent crashed");
+
+    // the volume and its replicas were all left in the creating state, rather than the
+    // replicas being created for a volume which is not
+    let mut store = Etcd::new("0.0.0.0:2379")
+        .await
+        .expect("Failed to connect to etcd.");
+    let specs = stored_specs(&mut store, &volume_id).await;
+    assert_eq!(
+        specs,
+        vec![("volume", false), ("replica", false), ("replica", false)]
+    );
+
+    // so on restart they're all cleaned up the same way
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let start = std::time::Instant::now();
+    loop {
+        let specs = stored_specs(&mut store, &volume_id).await;
+        if specs.is_empty() {
+            break;
+        }
+        if start.elapsed() > Duration::from_secs(10) {
+            panic!(
+                "Timeout waiting for the specs to be cleaned up: {:?}",
+                specs
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
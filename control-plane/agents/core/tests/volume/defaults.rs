@@ -0,0 +1,111 @@
+#![cfg(test)]
+
+use common_lib::types::v0::openapi::{
+    apis::{StatusCode, Uuid},
+    clients::tower::Error,
+    models,
+};
+use deployer_cluster::ClusterBuilder;
+
+const SIZE: u64 = 5242880;
+
+/// Volume creation body which leaves all of the defaultable parameters unset
+fn unset_body() -> models::CreateVolumeBody {
+    models::CreateVolumeBody::new(models::VolumePolicy::default(), SIZE)
+}
+
+#[tokio::test]
+async fn volume_cluster_defaults() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let client = cluster.rest_v00();
+    let volumes_api = client.volumes_api();
+    let admin_api = client.admin_api();
+
+    // until set, the defaults are those of a volume created with all the parameters zeroed
+    let builtin = admin_api.get_admin_defaults().await.unwrap();
+    assert_eq!(
+        builtin,
+        models::ClusterDefaults::new(models::VolumePlacementPolicy::Strict, 1, false)
+    );
+
+    let all_defaults = vec![
+        models::VolumeDefault::Replicas,
+        models::VolumeDefault::Thin,
+        models::VolumeDefault::Placement,
+    ];
+    let before = volumes_api
+        .put_volume(&Uuid::new_v4(), unset_body())
+        .await
+        .unwrap();
+    assert_eq!(before.spec.num_replicas, 1);
+    assert!(!before.spec.thin);
+    assert_eq!(before.spec.defaults, Some(all_defaults.clone()));
+
+    let defaults = models::ClusterDefaults::new(models::VolumePlacementPolicy::Strict, 2, true);
+    let stored = admin_api
+        .put_admin_defaults(defaults.clone())
+        .await
+        .unwrap();
+    assert_eq!(stored, defaults);
+
+    let unset = volumes_api
+        .put_volume(&Uuid::new_v4(), unset_body())
+        .await
+        .unwrap();
+    assert_eq!(unset.spec.num_replicas, 2);
+    assert!(unset.spec.thin);
+    assert_eq!(unset.spec.defaults, Some(all_defaults));
+
+    // the explicit parameters take precedence over the defaults
+    let explicit = volumes_api
+        .put_volume(
+            &Uuid::new_v4(),
+            models::CreateVolumeBody {
+                replicas: Some(1),
+                thin: Some(false),
+                ..unset_body()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(explicit.spec.num_replicas, 1);
+    assert!(!explicit.spec.thin);
+    assert_eq!(
+        explicit.spec.defaults,
+        Some(vec![models::VolumeDefault::Placement])
+    );
+
+    // changing the defaults does not affect the existing volumes
+    let before = volumes_api.get_volume(&before.spec.uuid).await.unwrap();
+    assert_eq!(before.spec.num_replicas, 1);
+    assert!(!before.spec.thin);
+    assert_eq!(before.state.replica_topology.len(), 1);
+
+    // the defaults are persisted
+    cluster.restart_core().await.unwrap();
+    cluster.volume_service_liveness(None).await.unwrap();
+    assert_eq!(admin_api.get_admin_defaults().await.unwrap(), defaults);
+
+    // a volume needs at least one replica
+    let error = admin_api
+        .put_admin_defaults(models::ClusterDefaults::new(
+            models::VolumePlacementPolicy::Strict,
+            0,
+            true,
+        ))
+        .await
+        .unwrap_err();
+    match error {
+        Error::Response(response) => assert_eq!(response.status(), StatusCode::BAD_REQUEST),
+        error => panic!("Unexpected error: {:?}", error),
+    }
+}
@@ -11,6 +11,7 @@ use common_lib::types::v0::{
         CreateNexus, CreateVolume, DestroyVolume, Filter, NexusId, PublishVolume, VolumeId,
     },
 };
+use control_plane_client::CreateVolumeRequest;
 use deployer_cluster::{Cluster, ClusterBuilder};
 use grpc::operations::{
     nexus::traits::NexusOperations, node::traits::NodeOperations, volume::traits::VolumeOperations,
@@ -62,6 +63,7 @@ async fn deleting_volume_reconcile(cluster: &Cluster) {
                 uuid: volume.uuid().clone(),
                 target_node: None,
                 share: None,
+                reservation: None,
             },
             None,
         )
@@ -94,7 +96,11 @@ async fn deleting_volume_reconcile(cluster: &Cluster) {
     let nexuses = cluster.rest_v00().nexuses_api().get_nexuses().await;
     assert!(nexuses.unwrap().is_empty());
     assert!(specs.replicas.is_empty());
-    let replicas = cluster.rest_v00().replicas_api().get_replicas().await;
+    let replicas = cluster
+        .rest_v00()
+        .replicas_api()
+        .get_replicas(None, None)
+        .await;
     assert!(replicas.unwrap().is_empty());
 }
 
@@ -119,20 +125,30 @@ async fn wait_till_volume_deleted(cluster: &Cluster) {
     }
 }
 
+/// Request to create a 2-replica volume with the given `uuid` for the reconcile tests
+fn create_volume(uuid: &str) -> CreateVolumeRequest {
+    CreateVolumeRequest::new(5242880)
+        .with_uuid(uuid.parse().unwrap())
+        .with_policy(models::VolumePolicy::default())
+        .with_replicas(2)
+        .with_thin(false)
+}
+
 async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duration) {
     let rest_api = cluster.rest_v00();
-    let volumes_api = rest_api.volumes_api();
+    let client = cluster.client();
 
-    let volume = volumes_api
-        .put_volume(
-            &"1e3cf927-80c2-47a8-adf0-95c481bdd7b7".parse().unwrap(),
-            models::CreateVolumeBody::new(models::VolumePolicy::default(), 2, 5242880u64, false),
-        )
+    let volume = client
+        .create_volume(create_volume("1e3cf927-80c2-47a8-adf0-95c481bdd7b7"))
         .await
         .unwrap();
 
     let nodes = rest_api.nodes_api().get_nodes().await.unwrap();
-    let replica_nodes = rest_api.replicas_api().get_replicas().await.unwrap();
+    let replica_nodes = rest_api
+        .replicas_api()
+        .get_replicas(None, None)
+        .await
+        .unwrap();
     let replica_nodes = replica_nodes
         .into_iter()
         .map(|r| r.node)
@@ -149,20 +165,14 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
         .unwrap();
 
     // 1. publish on the node with no replicas
-    let volume = volumes_api
-        .put_volume_target(
-            &volume.spec.uuid,
-            &free_node,
-            models::VolumeShareProtocol::Nvmf,
-        )
+    let volume_id = volume.spec.uuid;
+    let volume = client
+        .publish(&volume_id, &free_node, models::VolumeShareProtocol::Nvmf)
         .await
         .unwrap();
-
     tracing::info!("Volume: {:?}", volume);
-    let volume_state = volume.state;
 
-    let volume_id = volume_state.uuid;
-    let volume = volumes_api.get_volume(&volume_id).await.unwrap();
+    let volume = client.get_volume(&volume_id).await.unwrap();
     assert_eq!(volume.state.status, models::VolumeStatus::Online);
 
     // 2. kill all replica nodes
@@ -174,7 +184,7 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
     wait_till_volume_status(cluster, &volume.spec.uuid, models::VolumeStatus::Faulted).await;
 
     // 4. restart the core-agent
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
 
     cluster.volume_service_liveness(None).await.unwrap();
     wait_till_volume_status(cluster, &volume.spec.uuid, models::VolumeStatus::Faulted).await;
@@ -182,7 +192,7 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
     // 5. After the reconcilers run, replicas should not have been disowned
     tokio::time::sleep(reconcile_period * 3).await;
 
-    let volume = volumes_api.get_volume(&volume.spec.uuid).await.unwrap();
+    let volume = client.get_volume(&volume.spec.uuid).await.unwrap();
     assert_eq!(volume.state.status, models::VolumeStatus::Faulted);
 
     let replicas = rest_api.specs_api().get_specs().await.unwrap().replicas;
@@ -195,36 +205,29 @@ async fn offline_replicas_reconcile(cluster: &Cluster, reconcile_period: Duratio
         2
     );
 
-    volumes_api.del_volume(&volume_id).await.unwrap();
+    client.delete_volume(&volume_id).await.unwrap();
 }
 
 async fn unused_nexus_reconcile(cluster: &Cluster) {
-    let rest_api = cluster.rest_v00();
-    let volumes_api = rest_api.volumes_api();
+    let client = cluster.client();
     let nexus_client = cluster.grpc_client().nexus();
 
-    let volume = volumes_api
-        .put_volume(
-            &"1e3cf927-80c2-47a8-adf0-95c481bdd7b7".parse().unwrap(),
-            models::CreateVolumeBody::new(models::VolumePolicy::default(), 2, 5242880u64, false),
-        )
+    let volume = client
+        .create_volume(create_volume("1e3cf927-80c2-47a8-adf0-95c481bdd7b7"))
         .await
         .unwrap();
-
-    let volume = volumes_api
-        .put_volume_target(
-            &volume.spec.uuid,
+    let volume_id = volume.spec.uuid;
+    let volume = client
+        .publish(
+            &volume_id,
             cluster.node(0).as_str(),
             models::VolumeShareProtocol::Nvmf,
         )
         .await
         .unwrap();
-
     tracing::info!("Volume: {:?}", volume);
-    let volume_state = volume.state;
 
-    let volume_id = volume_state.uuid;
-    let volume = volumes_api.get_volume(&volume_id).await.unwrap();
+    let volume = client.get_volume(&volume_id).await.unwrap();
     assert_eq!(volume.state.status, models::VolumeStatus::Online);
 
     let mut create_nexus = CreateNexus {
@@ -237,6 +240,7 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
         managed: true,
         owner: None,
         config: None,
+        skip_child_probe: false,
     };
     let nexus = nexus_client.create(&create_nexus, None).await.unwrap();
     let nexus = wait_till_nexus_state(cluster, &nexus.uuid, None).await;
@@ -247,20 +251,21 @@ async fn unused_nexus_reconcile(cluster: &Cluster) {
     let nexus = wait_till_nexus_state(cluster, &nexus.uuid, None).await;
     assert_eq!(nexus, None, "nexus should be gone");
 
-    volumes_api.del_volume(&volume_id).await.unwrap();
+    client.delete_volume(&volume_id).await.unwrap();
 }
 
 async fn unused_reconcile(cluster: &Cluster) {
     let rest_api = cluster.rest_v00();
-    let volumes_api = rest_api.volumes_api();
+    let client = cluster.client();
 
-    let volume = volumes_api
-        .put_volume(
-            &"22054b1f-cf32-46dc-90ff-d6a5c61429c2".parse().unwrap(),
-            models::CreateVolumeBody::new(models::VolumePolicy::new(true), 2, 5242880u64, false),
+    let volume = client
+        .create_volume(
+            create_volume("22054b1f-cf32-46dc-90ff-d6a5c61429c2")
+                .with_policy(models::VolumePolicy::new(true)),
         )
         .await
         .unwrap();
+    let volume_id = volume.spec.uuid;
 
     let data_replicas_nodes = volume
         .state
@@ -292,12 +297,9 @@ async fn unused_reconcile(cluster: &Cluster) {
         })
         .unwrap();
 
-    let volume = volumes_api
-        .put_volume_target(
-            &volume.spec.uuid,
-            nexus_node.id.as_str(),
-            models::VolumeShareProtocol::Nvmf,
-        )
+    let nvmf = models::VolumeShareProtocol::Nvmf;
+    let volume = client
+        .publish(&volume_id, &nexus_node.id, nvmf)
         .await
         .unwrap();
     tracing::info!("Volume: {:?}\nUnused Node: {}", volume, unused_node.id);
@@ -305,17 +307,10 @@ async fn unused_reconcile(cluster: &Cluster) {
     // 1. first we kill the node where the nexus is running
     cluster.composer().kill(&nexus_node.id).await.unwrap();
     // 2. now we force unpublish the volume
-    volumes_api
-        .del_volume_target(&volume.spec.uuid, Some(true))
-        .await
-        .unwrap();
+    client.unpublish(&volume_id, true).await.unwrap();
     // 3. publish on the previously unused node
-    let volume = volumes_api
-        .put_volume_target(
-            &volume.spec.uuid,
-            unused_node.id.as_str(),
-            models::VolumeShareProtocol::Nvmf,
-        )
+    let volume = client
+        .publish(&volume_id, &unused_node.id, nvmf)
         .await
         .unwrap();
     tracing::info!("Volume: {:?}", volume);
@@ -325,11 +320,14 @@ async fn unused_reconcile(cluster: &Cluster) {
 
     // 5. now wait till the volume becomes online again
     // (because we'll add a replica a rebuild)
-    wait_till_volume_status(cluster, &volume.spec.uuid, models::VolumeStatus::Online).await;
+    let timeout = Duration::from_secs(RECONCILE_TIMEOUT_SECS);
+    client
+        .wait_volume_online(&volume_id, timeout)
+        .await
+        .unwrap();
 
     // 6. Bring back the io-engine and the original nexus and replica should be deleted
     cluster.composer().start(&nexus_node.id).await.unwrap();
-    let timeout = Duration::from_secs(RECONCILE_TIMEOUT_SECS);
     let start = std::time::Instant::now();
     loop {
         let specs = cluster.rest_v00().specs_api().get_specs().await.unwrap();
@@ -343,7 +341,7 @@ async fn unused_reconcile(cluster: &Cluster) {
         tokio::time::sleep(Duration::from_millis(500)).await;
     }
 
-    volumes_api.del_volume(&volume.spec.uuid).await.unwrap();
+    client.delete_volume(&volume_id).await.unwrap();
 }
 
 async fn wait_till_replica_disowned(cluster: &Cluster, replica_id: Uuid) {
@@ -400,6 +398,8 @@ async fn missing_nexus_reconcile(cluster: &Cluster) {
             &volume.spec().uuid,
             cluster.node(0).as_str(),
             models::VolumeShareProtocol::Nvmf,
+            None,
+            None,
         )
         .await
         .unwrap();
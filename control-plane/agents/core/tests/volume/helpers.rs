@@ -1,5 +1,7 @@
 use super::RECONCILE_TIMEOUT_SECS;
-use common_lib::types::v0::transport::{Child, ChildUri, GetSpecs, GetVolumes, Volume, VolumeId};
+use common_lib::types::v0::transport::{
+    Child, ChildUri, Filter, GetSpecs, GetVolumes, Volume, VolumeCondition, VolumeId,
+};
 use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
 use std::time::Duration;
 
@@ -112,3 +114,31 @@ async fn existing_replicas(volume_id: &VolumeId, client: &dyn VolumeOperations)
         .filter(|(_id, topology)| topology.node().is_some() && topology.pool().is_some())
         .count()
 }
+
+/// Wait until the volume has exactly the given conditions, up to the specified timeout.
+pub(crate) async fn wait_volume_conditions(
+    client: &dyn VolumeOperations,
+    volume_id: &VolumeId,
+    conditions: &[VolumeCondition],
+    timeout: Duration,
+) -> Volume {
+    let start = std::time::Instant::now();
+    loop {
+        let volume = client
+            .get(Filter::Volume(volume_id.clone()), None, None)
+            .await
+            .unwrap()
+            .entries
+            .remove(0);
+        if volume.state().conditions == conditions {
+            return volume;
+        }
+        if start.elapsed() > timeout {
+            panic!(
+                "Timeout waiting for the conditions {:?} of volume {:?}",
+                conditions, volume
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
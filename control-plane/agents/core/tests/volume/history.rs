@@ -0,0 +1,198 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::{
+            definitions::{Store, StoreError},
+            history::{SpecHistory, SpecHistoryKey},
+        },
+        transport::{
+            CreateVolume, DestroyVolume, GetVolumeHistory, GetVolumeRevisionDiff, SetVolumeReplica,
+            VolumeId,
+        },
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+use serde_json::json;
+
+const REVISIONS: u32 = 3;
+
+#[tokio::test]
+async fn volume_spec_history() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_options(|o| o.with_volume_spec_revisions(REVISIONS))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+
+    let volume_id = VolumeId::new();
+    volume_client
+        .create(
+            &CreateVolume {
+                uuid: volume_id.clone(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let set_replica = |replicas: u8| SetVolumeReplica {
+        uuid: volume_id.clone(),
+        replicas,
+        override_redundancy_check: false,
+    };
+    volume_client
+        .set_replica(&set_replica(2), None)
+        .await
+        .unwrap();
+
+    let history = volume_client
+        .get_history(&GetVolumeHistory::new(&volume_id), None)
+        .await
+        .unwrap();
+    let operations = history
+        .revisions
+        .iter()
+        .map(|r| (r.revision, r.operation.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        operations,
+        vec![
+            (1, Some(json!("Create"))),
+            (2, Some(json!({ "SetReplica": 2 })))
+        ]
+    );
+    assert!(history.revisions[0].updated_at <= history.revisions[1].updated_at);
+
+    // the first revision has no previous revision, so it's diffed against an empty spec
+    let diff = volume_client
+        .get_revision_diff(&GetVolumeRevisionDiff::new(&volume_id, 1), None)
+        .await
+        .unwrap();
+    assert_eq!(diff.previous, None);
+    assert!(
+        contains_op(&diff.diff, "add", "/num_replicas", json!(1)),
+        "{}",
+        diff.diff
+    );
+
+    let diff = volume_client
+        .get_revision_diff(&GetVolumeRevisionDiff::new(&volume_id, 2), None)
+        .await
+        .unwrap();
+    assert_eq!(diff.previous, Some(1));
+    assert!(
+        contains_op(&diff.diff, "replace", "/num_replicas", json!(2)),
+        "{}",
+        diff.diff
+    );
+    assert!(
+        !contains_path(&diff.diff, "/uuid"),
+        "Only the changes are diffed: {}",
+        diff.diff
+    );
+
+    volume_client
+        .set_replica(&set_replica(1), None)
+        .await
+        .unwrap();
+    volume_client
+        .set_replica(&set_replica(2), None)
+        .await
+        .unwrap();
+    volume_client
+        .set_replica(&set_replica(1), None)
+        .await
+        .unwrap();
+
+    // the oldest revisions are pruned first
+    let history = volume_client
+        .get_history(&GetVolumeHistory::new(&volume_id), None)
+        .await
+        .unwrap();
+    let operations = history
+        .revisions
+        .iter()
+        .map(|r| (r.revision, r.operation.clone()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        operations,
+        vec![
+            (3, Some(json!({ "SetReplica": 1 }))),
+            (4, Some(json!({ "SetReplica": 2 }))),
+            (5, Some(json!({ "SetReplica": 1 })))
+        ]
+    );
+
+    let diff = volume_client
+        .get_revision_diff(&GetVolumeRevisionDiff::new(&volume_id, 5), None)
+        .await
+        .unwrap();
+    assert_eq!(diff.previous, Some(4));
+    assert!(contains_op(
+        &diff.diff,
+        "replace",
+        "/num_replicas",
+        json!(1)
+    ));
+
+    // the previous revision of the oldest retained revision was pruned
+    let diff = volume_client
+        .get_revision_diff(&GetVolumeRevisionDiff::new(&volume_id, 3), None)
+        .await
+        .unwrap();
+    assert_eq!(diff.previous, None);
+    assert!(contains_op(&diff.diff, "add", "/num_replicas", json!(1)));
+
+    let error = volume_client
+        .get_revision_diff(&GetVolumeRevisionDiff::new(&volume_id, 2), None)
+        .await
+        .expect_err("Revision was pruned");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    // the history goes away along with the volume
+    volume_client
+        .destroy(&DestroyVolume::new(&volume_id), None)
+        .await
+        .unwrap();
+    let error = volume_client
+        .get_history(&GetVolumeHistory::new(&volume_id), None)
+        .await
+        .expect_err("Volume was destroyed");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let mut store = Etcd::new("0.0.0.0:2379")
+        .await
+        .expect("Failed to connect to etcd.");
+    let key = SpecHistoryKey::new(&ResourceKind::Volume, volume_id.as_str());
+    let result = store.get_obj::<SpecHistory>(&key).await;
+    assert!(
+        matches!(result, Err(StoreError::MissingEntry { .. })),
+        "{:?}",
+        result
+    );
+}
+
+/// Check if the JSON `patch` contains the operation `op` on the `path` with the given `value`.
+fn contains_op(patch: &serde_json::Value, op: &str, path: &str, value: serde_json::Value) -> bool {
+    patch
+        .as_array()
+        .unwrap()
+        .iter()
+        .any(|o| o["op"] == op && o["path"] == path && o["value"] == value)
+}
+
+/// Check if the JSON `patch` contains any operation on the `path`.
+fn contains_path(patch: &serde_json::Value, path: &str) -> bool {
+    patch.as_array().unwrap().iter().any(|o| o["path"] == path)
+}
@@ -282,7 +282,12 @@ async fn hotspare_replica_count_spread(cluster: &Cluster) {
         nodes.len() >= 3,
         "We need enough nodes to be able to add at least 2 replicas"
     );
-    let pools = cluster.rest_v00().pools_api().get_pools().await.unwrap();
+    let pools = cluster
+        .rest_v00()
+        .pools_api()
+        .get_pools(None, None)
+        .await
+        .unwrap();
     assert!(
         pools.len() >= nodes.len() * 2,
         "We need at least 2 pools per node to be able to test the failure case"
@@ -317,7 +322,7 @@ async fn hotspare_replica_count_spread(cluster: &Cluster) {
     tracing::info!("VolumeSpec: {:?}", volume_spec);
     store.put_obj(&volume_spec).await.unwrap();
 
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
 
     let timeout_opts = TimeoutOptions::default()
         .with_max_retries(10)
@@ -473,7 +478,7 @@ async fn hotspare_nexus_replica_count(cluster: &Cluster) {
     tracing::info!("VolumeSpec: {:?}", volume_spec);
     store.put_obj(&volume_spec).await.unwrap();
 
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
 
     cluster
         .volume_service_liveness(Some(timeout_opts.clone()))
@@ -494,7 +499,7 @@ async fn hotspare_nexus_replica_count(cluster: &Cluster) {
     tracing::info!("VolumeSpec: {:?}", volume_spec);
     store.put_obj(&volume_spec).await.unwrap();
 
-    cluster.restart_core().await;
+    cluster.restart_core().await.unwrap();
 
     cluster
         .volume_service_liveness(Some(timeout_opts.clone()))
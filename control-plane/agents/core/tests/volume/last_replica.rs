@@ -0,0 +1,74 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{CreateVolume, Filter, PublishVolume, RemoveNexusChild, VolumeId},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{nexus::traits::NexusOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+#[tokio::test]
+async fn last_healthy_replica() {
+    // the reconcilers must not add a replica back to the nexus whilst the test runs
+    let reconcile_period = Duration::from_secs(60);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let nexus_client = cluster.grpc_client().nexus();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(volume.spec().uuid.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.unwrap();
+    assert_eq!(nexus.children.len(), 1);
+    let remove = |force: bool| RemoveNexusChild {
+        node: nexus.node.clone(),
+        nexus: nexus.uuid.clone(),
+        uri: nexus.children[0].uri.clone(),
+        force,
+    };
+
+    let error = nexus_client
+        .remove_nexus_child(&remove(false), None)
+        .await
+        .expect_err("The only copy of the volume data must not be removed");
+    assert_eq!(error.kind, ReplyErrorKind::LastHealthyReplica);
+    assert_eq!(error.resource, ResourceKind::Volume);
+
+    let nexuses = nexus_client
+        .get(Filter::Nexus(nexus.uuid.clone()), None)
+        .await
+        .unwrap()
+        .0;
+    assert_eq!(nexuses[0].children.len(), 1);
+
+    // unless the removal is forced
+    nexus_client
+        .remove_nexus_child(&remove(true), None)
+        .await
+        .unwrap();
+}
@@ -0,0 +1,66 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{CreateVolume, Filter, VolumeId},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+
+const MIB: u64 = 1024 * 1024;
+
+#[tokio::test]
+async fn volume_max_size() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_options(|o| o.with_max_volume_size("200MiB"))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let create = |size: u64| CreateVolume {
+        uuid: VolumeId::new(),
+        size,
+        replicas: 1,
+        ..Default::default()
+    };
+
+    let volume = volume_client.create(&create(10 * MIB), None).await.unwrap();
+    assert_eq!(volume.spec().size, 10 * MIB);
+    assert_eq!(volume.spec().requested_size, Some(10 * MIB));
+
+    // the size is rounded up to a multiple of the block size, keeping the requested size
+    let volume = volume_client
+        .create(&create(10 * MIB + 1), None)
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().size, 10 * MIB + 4096);
+    assert_eq!(volume.spec().requested_size, Some(10 * MIB + 1));
+
+    // smaller than the default minimum size of 1MiB
+    let error = volume_client.create(&create(1), None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    assert_eq!(error.resource, ResourceKind::Volume);
+
+    // rejected before any replica is scheduled, leaving no trace of the volume
+    let oversized = create(300 * MIB);
+    let error = volume_client.create(&oversized, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    assert_eq!(error.resource, ResourceKind::Volume);
+    let volumes = volume_client
+        .get(Filter::Volume(oversized.uuid.clone()), None, None)
+        .await;
+    assert!(volumes.map(|v| v.entries.is_empty()).unwrap_or(true));
+
+    // within the limit but larger than the 100MiB pool, so it's the scheduler which rejects it
+    let error = volume_client
+        .create(&create(150 * MIB), None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+    assert_eq!(error.resource, ResourceKind::Pool);
+}
@@ -1,9 +1,37 @@
 #![cfg(test)]
 
 mod capacity;
+mod child_faults;
+mod child_size;
+mod conditions;
+#[cfg(feature = "fault-injection")]
+mod create_crash;
+mod defaults;
 mod garbage_collection;
 mod helpers;
+mod history;
 mod hotspare;
+mod last_replica;
+mod max_size;
+mod overcommit;
+mod placement;
+mod pools;
+mod quorum;
+mod quotas;
+mod rebuild_interrupt;
+mod rebuild_limit;
+mod rebuild_pause;
+mod rebuild_priority;
+mod reconciler_passes;
+mod redundancy;
+mod reservation;
+mod scheduling_trace;
+mod sorting;
+mod source_replica;
+mod status;
+mod topology;
+mod topology_policy;
+mod validate;
 
 use common_lib::{
     store::etcd::Etcd,
@@ -17,8 +45,8 @@ use common_lib::{
         transport::{
             Child, ChildState, CreateVolume, DestroyVolume, Filter, GetNexuses, GetReplicas,
             GetVolumes, Nexus, NodeId, PublishVolume, SetVolumeReplica, ShareVolume, Topology,
-            UnpublishVolume, UnshareVolume, Volume, VolumeId, VolumeShareProtocol, VolumeState,
-            VolumeStatus,
+            UnpublishVolume, UnshareNexus, UnshareReplica, UnshareVolume, Volume, VolumeId,
+            VolumeShareProtocol, VolumeState, VolumeStatus,
         },
     },
 };
@@ -62,6 +90,62 @@ async fn test_volume(cluster: &Cluster) {
     nexus_persistence_test(cluster).await;
 }
 
+#[tokio::test]
+async fn legacy_unshare() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_options(|o| o.with_legacy_unshare(true))
+        .with_volume(|v| v.with_publish(None, None))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let nexus_client = cluster.grpc_client().nexus();
+    let replica_client = cluster.grpc_client().replica();
+    let volume_id = cluster.volumes().first().cloned().unwrap();
+
+    let volume = volume_client
+        .get(Filter::Volume(volume_id.clone()), None, None)
+        .await
+        .unwrap()
+        .entries
+        .first()
+        .cloned()
+        .unwrap();
+    let nexus = volume.state().target.expect("Volume should be published");
+
+    // with the legacy behaviour unsharing an unshared resource is an error
+    let error = volume_client
+        .unshare(&UnshareVolume { uuid: volume_id }, None)
+        .await
+        .expect_err("Volume is not shared");
+    assert_eq!(error.kind, ReplyErrorKind::NotShared);
+
+    let error = nexus_client
+        .unshare(&UnshareNexus::from(&nexus), None)
+        .await
+        .expect_err("Nexus is not shared");
+    assert_eq!(error.kind, ReplyErrorKind::NotShared);
+
+    let replica = replica_client
+        .get(Filter::Volume(volume.uuid().clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+        .first()
+        .cloned()
+        .unwrap();
+    let error = replica_client
+        .unshare(&UnshareReplica::from(&replica), None)
+        .await
+        .expect_err("Replica is not shared");
+    assert_eq!(error.kind, ReplyErrorKind::NotShared);
+}
+
 const RECONCILE_TIMEOUT_SECS: u64 = 7;
 
 /// Either fault the local replica, the remote, or set the nexus as having an unclean shutdown
@@ -107,6 +191,8 @@ async fn nexus_persistence_test_iteration(
                         models::ExplicitNodeTopology::new(allowed_nodes, preferred_nodes),
                     )),
                     None,
+                    None,
+                    None,
                 ))),
                 ..Default::default()
             },
@@ -123,6 +209,7 @@ async fn nexus_persistence_test_iteration(
                 // publish it on the remote first, to complicate things
                 target_node: Some(remote.clone()),
                 share: None,
+                reservation: None,
             },
             None,
         )
@@ -199,6 +286,7 @@ async fn nexus_persistence_test_iteration(
                 uuid: volume_state.uuid.clone(),
                 target_node: Some(local.clone()),
                 share: None,
+                reservation: None,
             },
             None,
         )
@@ -294,6 +382,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume.spec().uuid.clone(),
                 target_node: None,
                 share: None,
+                reservation: None,
             },
             None,
         )
@@ -349,7 +438,7 @@ async fn publishing_test(cluster: &Cluster) {
             None,
         )
         .await
-        .expect_err("Can't unshare an unshared volume");
+        .expect("Unsharing an unshared volume is a no-op");
 
     volume_client
         .publish(
@@ -357,6 +446,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 target_node: None,
                 share: None,
+                reservation: None,
             },
             None,
         )
@@ -374,6 +464,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 target_node: Some(cluster.node(0)),
                 share: Some(VolumeShareProtocol::Nvmf),
+                reservation: None,
             },
             None,
         )
@@ -407,6 +498,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 target_node: None,
                 share: Some(VolumeShareProtocol::Iscsi),
+                reservation: None,
             },
             None,
         )
@@ -419,6 +511,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 target_node: None,
                 share: Some(VolumeShareProtocol::Nvmf),
+                reservation: None,
             },
             None,
         )
@@ -436,6 +529,7 @@ async fn publishing_test(cluster: &Cluster) {
                 uuid: volume_state.uuid.clone(),
                 target_node: Some(cluster.node(1)),
                 share: None,
+                reservation: None,
             },
             None,
         )
@@ -603,6 +697,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume.spec().uuid.clone(),
                 replicas: 3,
+                ..Default::default()
             },
             None,
         )
@@ -616,6 +711,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume_state.uuid.clone(),
                 replicas: 4,
+                ..Default::default()
             },
             None,
         )
@@ -640,6 +736,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume.uuid.clone(),
                 replicas: 4,
+                ..Default::default()
             },
             None,
         )
@@ -661,6 +758,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume.uuid.clone(),
                 replicas: 2,
+                ..Default::default()
             },
             None,
         )
@@ -674,6 +772,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume_state.uuid.clone(),
                 replicas: 1,
+                ..Default::default()
             },
             None,
         )
@@ -693,6 +792,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume_state.uuid.clone(),
                 replicas: 0,
+                ..Default::default()
             },
             None,
         )
@@ -714,6 +814,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume_state.uuid.clone(),
                 replicas: 2,
+                ..Default::default()
             },
             None,
         )
@@ -732,6 +833,7 @@ async fn replica_count_test(cluster: &Cluster) {
             &SetVolumeReplica {
                 uuid: volume_state.uuid.clone(),
                 replicas: 3,
+                ..Default::default()
             },
             None,
         )
@@ -0,0 +1,71 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        store::{definitions::Store, replica::ReplicaSpec, SpecStatus},
+        transport::{CreateVolume, ReplicaId, ReplicaName, ReplicaOwners, ReplicaStatus, VolumeId},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+
+const MIB: u64 = 1024 * 1024;
+
+/// The replicas placed on a pool may not commit more than the overcommit factor of its capacity,
+/// counting the replica specs whose replica is missing from the state of the pool.
+#[tokio::test]
+async fn pool_overcommit() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_options(|o| o.with_pool_overcommit_factor(1.0))
+        .build()
+        .await
+        .unwrap();
+
+    // a stale spec, whose replica doesn't exist, seeded in the store
+    let stale = ReplicaId::new();
+    let mut store = Etcd::new("0.0.0.0:2379").await.unwrap();
+    store
+        .put_obj(&ReplicaSpec {
+            name: ReplicaName::from_uuid(&stale),
+            uuid: stale,
+            size: 48 * MIB,
+            pool: cluster.pool(0, 0),
+            status: SpecStatus::Created(ReplicaStatus::Online),
+            managed: false,
+            owners: ReplicaOwners::default(),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+
+    let volume_client = cluster.grpc_client().volume();
+    let create = |size: u64| CreateVolume {
+        uuid: VolumeId::new(),
+        size,
+        replicas: 1,
+        thin: true,
+        ..Default::default()
+    };
+
+    // the pool has a capacity a little short of 100MiB, of which the stale spec commits 48MiB
+    volume_client.create(&create(24 * MIB), None).await.unwrap();
+
+    // the thin replicas leave the pool with plenty of free space, but it's committed
+    let error = volume_client
+        .create(&create(32 * MIB), None)
+        .await
+        .expect_err("The pool would be overcommitted");
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+}
@@ -0,0 +1,460 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        CreatePool, CreateVolume, DestroyVolume, Filter, LabelledTopology, PoolId, PoolTopology,
+        SetVolumeReplica, Topology, VolumeCondition, VolumeId, VolumePlacementPolicy, VolumeStatus,
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    pool::traits::PoolOperations, replica::traits::ReplicaOperations,
+    volume::traits::VolumeOperations,
+};
+use std::{collections::HashSet, time::Duration};
+
+#[tokio::test]
+async fn volume_placement_policy() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        // don't let the reconcile fill in the missing replicas
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    // only 2 nodes, so 3 replicas cannot be placed
+    let strict = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 3,
+        placement: VolumePlacementPolicy::Strict,
+        ..Default::default()
+    };
+    let error = volume_client.create(&strict, None).await.expect_err(
+        "strict placement should not allow creating a volume with fewer replicas than requested",
+    );
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+    let error = volume_client
+        .get(Filter::Volume(strict.uuid.clone()), None, None)
+        .await
+        .expect_err("the volume should not exist");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+    assert_eq!(error.resource, ResourceKind::Volume);
+
+    let best_effort = CreateVolume {
+        uuid: VolumeId::new(),
+        placement: VolumePlacementPolicy::BestEffort,
+        ..strict
+    };
+    let volume = volume_client.create(&best_effort, None).await.unwrap();
+    assert_eq!(volume.spec().num_replicas, 3);
+    assert_eq!(volume.status(), Some(VolumeStatus::Degraded));
+
+    let replicas = replica_client
+        .get(Filter::Volume(best_effort.uuid.clone()), None)
+        .await
+        .unwrap();
+    assert_eq!(replicas.into_inner().len(), 2);
+
+    volume_client
+        .destroy(&DestroyVolume::new(&best_effort.uuid), None)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn volume_anti_colocation() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    let tenant_a = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 1,
+        ..Default::default()
+    };
+    volume_client.create(&tenant_a, None).await.unwrap();
+    let tenant_a_pools = replica_client
+        .get(Filter::Volume(tenant_a.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+        .into_iter()
+        .map(|r| r.pool)
+        .collect::<Vec<_>>();
+    assert_eq!(tenant_a_pools.len(), 1);
+
+    let topology = Topology {
+        node: None,
+        pool: None,
+        anti_colocation: vec![tenant_a.uuid.clone()],
+        spread: None,
+    };
+
+    // only 2 pools do not hold a replica of tenant_a
+    let tenant_b = CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 3,
+        topology: Some(topology.clone()),
+        ..tenant_a.clone()
+    };
+    let error = volume_client.create(&tenant_b, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+
+    let tenant_b = CreateVolume {
+        replicas: 2,
+        ..tenant_b
+    };
+    volume_client.create(&tenant_b, None).await.unwrap();
+    let replicas = replica_client
+        .get(Filter::Volume(tenant_b.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+    assert!(replicas.iter().all(|r| !tenant_a_pools.contains(&r.pool)));
+
+    // an empty constraint places the replicas anywhere
+    let tenant_c = CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 3,
+        topology: Some(Topology {
+            anti_colocation: vec![],
+            ..topology
+        }),
+        ..tenant_a.clone()
+    };
+    volume_client.create(&tenant_c, None).await.unwrap();
+}
+
+/// Get the pools which hold the replicas of the given volume
+async fn volume_pools(client: &impl ReplicaOperations, volume: &VolumeId) -> Vec<PoolId> {
+    client
+        .get(Filter::Volume(volume.clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+        .into_iter()
+        .map(|r| r.pool)
+        .collect()
+}
+
+/// Topology which only allows placing replicas on the pools of the given zone
+fn zone_topology(zone: &str) -> Topology {
+    Topology {
+        node: None,
+        pool: Some(PoolTopology::Labelled(LabelledTopology {
+            exclusion: Default::default(),
+            inclusion: vec![("zone".to_string(), zone.to_string())]
+                .into_iter()
+                .collect(),
+        })),
+        anti_colocation: vec![],
+        spread: None,
+    }
+}
+
+#[tokio::test]
+async fn volume_default_topology() {
+    let default_topology = serde_json::to_string(&zone_topology("a")).unwrap();
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_cache_period("1s")
+        .with_options(|o| o.with_default_topology(&default_topology))
+        .build()
+        .await
+        .unwrap();
+
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    for (node, zone) in [(0, "a"), (1, "b")] {
+        pool_client
+            .create(
+                &CreatePool {
+                    node: cluster.node(node),
+                    id: format!("pool-{}", zone).into(),
+                    disks: vec!["malloc:///disk0?size_mb=100".into()],
+                    labels: Some(
+                        vec![("zone".to_string(), zone.to_string())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    // a volume created without a topology inherits the default
+    let inherited = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 1,
+        ..Default::default()
+    };
+    let volume = volume_client.create(&inherited, None).await.unwrap();
+    assert_eq!(volume.spec().topology, Some(zone_topology("a")));
+    assert_eq!(
+        volume_pools(&replica_client, &inherited.uuid).await,
+        vec![PoolId::from("pool-a")]
+    );
+
+    // whilst an explicit topology overrides it
+    let explicit = CreateVolume {
+        uuid: VolumeId::new(),
+        topology: Some(zone_topology("b")),
+        ..inherited
+    };
+    let volume = volume_client.create(&explicit, None).await.unwrap();
+    assert_eq!(volume.spec().topology, Some(zone_topology("b")));
+    assert_eq!(
+        volume_pools(&replica_client, &explicit.uuid).await,
+        vec![PoolId::from("pool-b")]
+    );
+}
+
+/// Check that the relaxed placement gives up on the topology constraints one at a time, in order,
+/// only as far as needed to place all of the replicas.
+#[tokio::test]
+async fn volume_relaxed_placement() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    for (node, zone) in [(0, "a"), (1, "a"), (2, "b")] {
+        pool_client
+            .create(
+                &CreatePool {
+                    node: cluster.node(node),
+                    id: format!("pool-{}", node).into(),
+                    disks: vec!["malloc:///disk0?size_mb=100".into()],
+                    labels: Some(
+                        vec![("zone".to_string(), zone.to_string())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    let tenant = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 1,
+        pools: vec![PoolId::from("pool-0")],
+        ..Default::default()
+    };
+    volume_client.create(&tenant, None).await.unwrap();
+
+    // only pool-1 is in zone a and doesn't hold a replica of the tenant
+    let topology = Topology {
+        anti_colocation: vec![tenant.uuid.clone()],
+        ..zone_topology("a")
+    };
+    let strict = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 2,
+        topology: Some(topology),
+        placement: VolumePlacementPolicy::Strict,
+        ..Default::default()
+    };
+    let error = volume_client.create(&strict, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+
+    // relaxing the anti-colocation is enough to place 2 replicas in zone a
+    let relaxed = CreateVolume {
+        uuid: VolumeId::new(),
+        placement: VolumePlacementPolicy::Relaxed,
+        ..strict.clone()
+    };
+    volume_client.create(&relaxed, None).await.unwrap();
+    let mut pools = volume_pools(&replica_client, &relaxed.uuid).await;
+    pools.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(pools, vec![PoolId::from("pool-0"), PoolId::from("pool-1")]);
+    wait_volume_condition(&volume_client, &relaxed.uuid).await;
+
+    // whilst 3 replicas also need the zone to be relaxed, but still land on different nodes
+    let relaxed = CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 3,
+        ..relaxed
+    };
+    volume_client.create(&relaxed, None).await.unwrap();
+    let mut pools = volume_pools(&replica_client, &relaxed.uuid).await;
+    pools.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+    assert_eq!(
+        pools,
+        vec![
+            PoolId::from("pool-0"),
+            PoolId::from("pool-1"),
+            PoolId::from("pool-2")
+        ]
+    );
+    wait_volume_condition(&volume_client, &relaxed.uuid).await;
+
+    // no amount of relaxation places more replicas than there are nodes
+    let error = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                replicas: 4,
+                ..relaxed
+            },
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+}
+
+/// Wait for the volume to report that its topology was relaxed
+async fn wait_volume_condition(client: &impl VolumeOperations, volume: &VolumeId) {
+    let start = std::time::Instant::now();
+    loop {
+        let volume = client
+            .get(Filter::Volume(volume.clone()), None, None)
+            .await
+            .unwrap()
+            .entries
+            .remove(0);
+        if volume.state().conditions == vec![VolumeCondition::TopologyRelaxed] {
+            return;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The relaxation was not reported: {:?}",
+            volume
+        );
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    }
+}
+
+/// Check that the replicas are spread across the zones of their pools as far as there are enough
+/// zones, and placed regardless of the zones otherwise.
+#[tokio::test]
+async fn volume_topology_spread() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(4)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let pool_client = cluster.grpc_client().pool();
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    let zone = |node: u32| if node < 2 { "a" } else { "b" };
+    for node in 0 .. 4 {
+        pool_client
+            .create(
+                &CreatePool {
+                    node: cluster.node(node),
+                    id: format!("pool-{}", node).into(),
+                    disks: vec!["malloc:///disk0?size_mb=100".into()],
+                    labels: Some(
+                        vec![("zone".to_string(), zone(node).to_string())]
+                            .into_iter()
+                            .collect(),
+                    ),
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    let zones = |pools: Vec<PoolId>| {
+        let mut zones = pools
+            .iter()
+            .map(|pool| zone(pool.as_str()[5 ..].parse().unwrap()))
+            .collect::<Vec<_>>();
+        zones.sort_unstable();
+        zones
+    };
+    let spread = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 2,
+        topology: Some(Topology {
+            node: None,
+            pool: None,
+            anti_colocation: vec![],
+            spread: Some("zone".to_string()),
+        }),
+        ..Default::default()
+    };
+
+    // the replicas created together are spread
+    volume_client.create(&spread, None).await.unwrap();
+    let pools = volume_pools(&replica_client, &spread.uuid).await;
+    assert_eq!(zones(pools), vec!["a", "b"]);
+
+    // as is the replica added to an existing one
+    let grown = CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 1,
+        ..spread.clone()
+    };
+    volume_client.create(&grown, None).await.unwrap();
+    volume_client
+        .set_replica(
+            &SetVolumeReplica {
+                uuid: grown.uuid.clone(),
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let pools = volume_pools(&replica_client, &grown.uuid).await;
+    assert_eq!(zones(pools), vec!["a", "b"]);
+
+    // there are only 2 zones for 3 replicas, so they're placed regardless of the zones
+    let best_effort = CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 3,
+        ..spread
+    };
+    volume_client.create(&best_effort, None).await.unwrap();
+    let pools = volume_pools(&replica_client, &best_effort.uuid).await;
+    assert_eq!(pools.len(), 3);
+    assert_eq!(zones(pools).into_iter().collect::<HashSet<_>>().len(), 2);
+}
@@ -0,0 +1,175 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        CreateVolume, DestroyVolume, ExplicitNodeTopology, Filter, NodeTopology, Topology, VolumeId,
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+
+#[tokio::test]
+async fn volume_explicit_pools() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(2)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    let create = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 5242880,
+        replicas: 2,
+        pools: vec![cluster.pool(0, 1), cluster.pool(1, 1)],
+        ..Default::default()
+    };
+    volume_client.create(&create, None).await.unwrap();
+    let replicas = replica_client
+        .get(Filter::Volume(create.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+    // each replica is on one of the selected pools, and each selected pool hosts a replica
+    assert!(replicas.iter().all(|r| create.pools.contains(&r.pool)));
+    assert!(create
+        .pools
+        .iter()
+        .all(|pool| replicas.iter().any(|r| &r.pool == pool)));
+    volume_client
+        .destroy(&DestroyVolume::new(&create.uuid), None)
+        .await
+        .unwrap();
+
+    // the unpinned replicas are placed by the scheduler, on another node
+    let partial = CreateVolume {
+        uuid: VolumeId::new(),
+        pools: vec![cluster.pool(0, 1)],
+        ..create.clone()
+    };
+    volume_client.create(&partial, None).await.unwrap();
+    let replicas = replica_client
+        .get(Filter::Volume(partial.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+    assert!(replicas.iter().any(|r| r.pool == cluster.pool(0, 1)));
+    assert!(replicas.iter().any(|r| r.node == cluster.node(1)));
+    volume_client
+        .destroy(&DestroyVolume::new(&partial.uuid), None)
+        .await
+        .unwrap();
+
+    // there cannot be more pools than replicas
+    let too_many = CreateVolume {
+        uuid: VolumeId::new(),
+        replicas: 1,
+        ..create.clone()
+    };
+    let error = volume_client.create(&too_many, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // the pools must satisfy the volume's topology
+    let outside_topology = CreateVolume {
+        uuid: VolumeId::new(),
+        topology: Some(Topology {
+            node: Some(NodeTopology::Explicit(ExplicitNodeTopology {
+                allowed_nodes: vec![cluster.node(0)],
+                preferred_nodes: vec![],
+            })),
+            pool: None,
+            anti_colocation: vec![],
+            spread: None,
+        }),
+        ..create.clone()
+    };
+    let error = volume_client
+        .create(&outside_topology, None)
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Pool);
+    assert!(
+        error.extra.contains(cluster.pool(1, 1).as_str()),
+        "{:?}",
+        error
+    );
+
+    // the pools are 100MiB, so this cannot fit
+    let too_large = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 200 * 1024 * 1024,
+        pools: vec![cluster.pool(0, 0), cluster.pool(1, 0)],
+        ..create.clone()
+    };
+    let error = volume_client.create(&too_large, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Pool);
+    assert!(
+        error.extra.contains(cluster.pool(0, 0).as_str()),
+        "{:?}",
+        error
+    );
+
+    // both pools are on the same node
+    let same_node = CreateVolume {
+        uuid: VolumeId::new(),
+        pools: vec![cluster.pool(0, 0), cluster.pool(0, 1)],
+        ..create.clone()
+    };
+    let error = volume_client.create(&same_node, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::FailedPrecondition);
+    assert_eq!(error.resource, ResourceKind::Pool);
+    assert!(
+        error.extra.contains(cluster.pool(0, 1).as_str()),
+        "{:?}",
+        error
+    );
+
+    let same_node = CreateVolume {
+        allow_same_node: true,
+        ..same_node
+    };
+    volume_client.create(&same_node, None).await.unwrap();
+    let replicas = replica_client
+        .get(Filter::Volume(same_node.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+    assert!(replicas.iter().all(|r| r.node == cluster.node(0)));
+
+    // allowing the same node only applies to the pinned replicas, the scheduler still places
+    // the others on another node
+    let same_node_partial = CreateVolume {
+        uuid: VolumeId::new(),
+        pools: vec![cluster.pool(0, 0)],
+        ..same_node
+    };
+    volume_client
+        .create(&same_node_partial, None)
+        .await
+        .unwrap();
+    let replicas = replica_client
+        .get(Filter::Volume(same_node_partial.uuid.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    assert_eq!(replicas.len(), 2);
+    assert_eq!(
+        replicas
+            .iter()
+            .filter(|r| r.node == cluster.node(0))
+            .count(),
+        1
+    );
+}
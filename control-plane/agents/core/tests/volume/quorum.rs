@@ -0,0 +1,136 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, GetVolumes, Protocol, PublishVolume, SetVolumeReplica, VolumeId,
+        VolumePolicy, VolumeShareProtocol,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use rpc::io_engine::FaultNexusChildRequest;
+use std::time::Duration;
+
+#[tokio::test]
+async fn volume_quorum() {
+    let policy = VolumePolicy {
+        self_heal: true,
+        quorum: Some(2),
+    };
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(2)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_volume(|v| {
+            v.with_create(|c| CreateVolume {
+                replicas: 2,
+                policy: policy.clone(),
+                ..c
+            })
+        })
+        .build()
+        .await
+        .unwrap();
+
+    quorum_invalid(&cluster, policy.clone()).await;
+    quorum_lost_unavailable(&cluster).await;
+}
+
+/// A volume cannot be created with a quorum which is zero or exceeds its replica count, nor can
+/// its replica count be reduced below its quorum
+async fn quorum_invalid(cluster: &Cluster, policy: VolumePolicy) {
+    let volume_client = cluster.grpc_client().volume();
+    for (replicas, quorum) in [(1, 2), (2, 0)] {
+        let error = volume_client
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: 5242880,
+                    replicas,
+                    policy: VolumePolicy {
+                        quorum: Some(quorum),
+                        ..policy.clone()
+                    },
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .expect_err("Invalid quorum");
+        assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    }
+
+    let volume_id = cluster.volumes().first().cloned().unwrap();
+    let error = volume_client
+        .set_replica(&SetVolumeReplica::new(volume_id, 1), None)
+        .await
+        .expect_err("Below quorum");
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+}
+
+/// When the volume loses a replica and drops below its quorum, its target is made unavailable
+/// and once the replica is replaced the target becomes available again
+async fn quorum_lost_unavailable(cluster: &Cluster) {
+    let volume_client = cluster.grpc_client().volume();
+    let volume_id = cluster.volumes().first().cloned().unwrap();
+
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(0)),
+                Some(VolumeShareProtocol::Nvmf),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.unwrap();
+    assert_eq!(nexus.share, Protocol::Nvmf);
+
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    let fault_child = nexus.children.first().unwrap().uri.to_string();
+    rpc_handle
+        .io_engine
+        .fault_nexus_child(FaultNexusChildRequest {
+            uuid: nexus.uuid.to_string(),
+            uri: fault_child,
+        })
+        .await
+        .unwrap();
+
+    wait_till_target_share(&volume_id, Protocol::None, &volume_client).await;
+    // the hot spare reconciler replaces the faulted replica, restoring the quorum
+    wait_till_target_share(&volume_id, Protocol::Nvmf, &volume_client).await;
+}
+
+/// Wait until the volume target is shared with the given protocol
+async fn wait_till_target_share(
+    volume: &VolumeId,
+    share: Protocol,
+    volume_client: &dyn VolumeOperations,
+) {
+    let timeout = Duration::from_secs(30);
+    let start = std::time::Instant::now();
+    loop {
+        let volumes = volume_client
+            .get(GetVolumes::new(volume).filter, None, None)
+            .await
+            .unwrap();
+        let volume_state = volumes.entries.first().unwrap().state();
+        if volume_state.target.as_ref().map(|t| t.share) == Some(share) {
+            return;
+        }
+        if std::time::Instant::now() > (start + timeout) {
+            panic!(
+                "Timeout waiting for the volume target to be shared as '{:?}'! Current: {:#?}",
+                share, volume_state
+            );
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+}
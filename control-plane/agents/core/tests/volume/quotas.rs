@@ -0,0 +1,111 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        openapi::models,
+        transport::{CreateVolume, DestroyVolume, VolumeId, VolumeLabels},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+
+const MIB: u64 = 1024 * 1024;
+
+fn create(tenant: &str) -> CreateVolume {
+    CreateVolume {
+        uuid: VolumeId::new(),
+        size: 10 * MIB,
+        replicas: 1,
+        labels: Some(VolumeLabels::from([(
+            "tenant".to_string(),
+            tenant.to_string(),
+        )])),
+        ..Default::default()
+    }
+}
+
+fn quota(tenant: &str, capacity: u64) -> models::VolumeQuota {
+    models::VolumeQuota::new(capacity, "tenant", tenant)
+}
+
+#[tokio::test]
+async fn volume_quotas() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let admin_api = cluster.rest_v00().admin_api();
+    assert_eq!(
+        admin_api.get_admin_quotas().await.unwrap(),
+        models::VolumeQuotasUsage::new(vec![])
+    );
+
+    let usage = admin_api
+        .put_admin_quotas(models::VolumeQuotas::new(vec![quota("acme", 30 * MIB)]))
+        .await
+        .unwrap();
+    assert_eq!(
+        usage,
+        models::VolumeQuotasUsage::new(vec![models::VolumeQuotaUsage::new(
+            quota("acme", 30 * MIB),
+            0
+        )])
+    );
+
+    let mut volumes = vec![];
+    for _ in 0 .. 3 {
+        let volume = volume_client.create(&create("acme"), None).await.unwrap();
+        volumes.push(volume.spec().uuid);
+    }
+    let error = volume_client
+        .create(&create("acme"), None)
+        .await
+        .expect_err("Exceeds the tenant's quota");
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+    assert_eq!(error.resource, ResourceKind::Volume);
+
+    // other tenants and the volumes without a tenant are not limited by the quota
+    volume_client.create(&create("other"), None).await.unwrap();
+    volume_client
+        .create(
+            &CreateVolume {
+                labels: None,
+                ..create("acme")
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    let usage = admin_api.get_admin_quotas().await.unwrap();
+    assert_eq!(usage.quotas[0].used, 30 * MIB);
+
+    // destroying a volume makes room for another
+    let destroy = DestroyVolume::new(&volumes[0]);
+    volume_client.destroy(&destroy, None).await.unwrap();
+    volume_client.create(&create("acme"), None).await.unwrap();
+
+    // each label may only have a single quota
+    let duplicate = models::VolumeQuotas::new(vec![quota("acme", MIB), quota("acme", 2 * MIB)]);
+    admin_api
+        .put_admin_quotas(duplicate)
+        .await
+        .expect_err("Duplicate quotas");
+
+    // the quotas are persisted
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let usage = admin_api.get_admin_quotas().await.unwrap();
+    assert_eq!(usage.quotas[0].quota, quota("acme", 30 * MIB));
+    assert_eq!(usage.quotas[0].used, 30 * MIB);
+}
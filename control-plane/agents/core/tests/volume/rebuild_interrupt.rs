@@ -0,0 +1,168 @@
+#![cfg(test)]
+
+use common_lib::{
+    store::etcd::Etcd,
+    types::v0::{
+        store::{
+            definitions::Store,
+            replica::{ReplicaSpec, ReplicaSpecKey},
+        },
+        transport::{
+            CreateVolume, Filter, PublishVolume, Replica, ReplicaId, SetVolumeReplica,
+            UnpublishVolume, Volume, VolumeId,
+        },
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+const POOL_SIZE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const VOLUME_SIZE_BYTES: u64 = 1024 * 1024 * 1024;
+
+async fn get_volume(cluster: &Cluster, volume_id: &VolumeId) -> Volume {
+    cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume_id.clone()), None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0)
+}
+
+async fn volume_replicas(cluster: &Cluster, volume_id: &VolumeId) -> Vec<Replica> {
+    cluster
+        .grpc_client()
+        .replica()
+        .get(Filter::Volume(volume_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+async fn out_of_sync(store: &mut Etcd, replica: &ReplicaId) -> bool {
+    let spec: ReplicaSpec = store.get_obj(&ReplicaSpecKey::from(replica)).await.unwrap();
+    spec.out_of_sync
+}
+
+/// Interrupt the rebuild of a replica by unpublishing its volume and check that the replica is
+/// not trusted with the volume data until it has been rebuilt again.
+#[tokio::test]
+async fn unpublish_during_rebuild() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let mut store = Etcd::new("0.0.0.0:2379").await.unwrap();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: VOLUME_SIZE_BYTES,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+    volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(0)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let source = volume_replicas(&cluster, &volume_id).await.remove(0);
+
+    // the new replica is rebuilt from the existing one
+    volume_client
+        .set_replica(
+            &SetVolumeReplica {
+                uuid: volume_id.clone(),
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let start = std::time::Instant::now();
+    loop {
+        let volume = get_volume(&cluster, &volume_id).await;
+        let target = volume.state().target.unwrap();
+        if target.children.iter().any(|c| c.rebuilding()) {
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The rebuild did not start: {:?}",
+            target
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    let rebuilt = volume_replicas(&cluster, &volume_id)
+        .await
+        .into_iter()
+        .find(|r| r.uuid != source.uuid)
+        .unwrap();
+
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    assert!(out_of_sync(&mut store, &rebuilt.uuid).await);
+    assert!(!out_of_sync(&mut store, &source.uuid).await);
+
+    // the out-of-sync replica is local to the new target, yet it's not used as a child
+    assert_eq!(rebuilt.node, cluster.node(1));
+    let volume = volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(1)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let target = volume.state().target.unwrap();
+    assert_eq!(target.children.len(), 1);
+    let source = volume_replicas(&cluster, &volume_id)
+        .await
+        .into_iter()
+        .find(|r| r.uuid == source.uuid)
+        .unwrap();
+    assert_eq!(target.children[0].uri.as_str(), source.uri.as_str());
+
+    // until it's added back to the nexus and rebuilt again
+    let start = std::time::Instant::now();
+    while out_of_sync(&mut store, &rebuilt.uuid).await {
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "The replica was not rebuilt"
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    let target = get_volume(&cluster, &volume_id)
+        .await
+        .state()
+        .target
+        .unwrap();
+    assert_eq!(target.children.len(), 2);
+}
@@ -0,0 +1,122 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    ChildState, CreateVolume, Filter, Nexus, PublishVolume, VolumeId,
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use rpc::io_engine::FaultNexusChildRequest;
+use std::time::Duration;
+
+const POOL_SIZE_BYTES: u64 = 1024 * 1024 * 1024;
+const VOLUME_SIZE_BYTES: u64 = 256 * 1024 * 1024;
+const MAX_REBUILDS: u32 = 2;
+
+async fn volume_targets(cluster: &Cluster, volumes: &[VolumeId]) -> Vec<Nexus> {
+    let volume_client = cluster.grpc_client().volume();
+    let mut targets = vec![];
+    for volume in volumes {
+        let volume = volume_client
+            .get(Filter::Volume(volume.clone()), None, None)
+            .await
+            .unwrap()
+            .entries
+            .remove(0);
+        targets.push(volume.state().target.unwrap());
+    }
+    targets
+}
+
+/// Fault a child of three volumes, each published on a different node, with a system-wide limit
+/// of two rebuilds and check that the third replacement is held back until a rebuild completes,
+/// yet all volumes are eventually healed.
+#[tokio::test]
+async fn system_wide_rebuild_limit() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_max_rebuilds(Some(MAX_REBUILDS)))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+
+    let mut volumes = vec![];
+    for node in 0 .. 3 {
+        let volume = volume_client
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: VOLUME_SIZE_BYTES,
+                    replicas: 2,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        let volume = volume_client
+            .publish(
+                &PublishVolume::new(volume.spec().uuid, Some(cluster.node(node)), None),
+                None,
+            )
+            .await
+            .unwrap();
+        volumes.push(volume.spec().uuid);
+    }
+
+    let mut faulted = vec![];
+    for (node, nexus) in volume_targets(&cluster, &volumes).await.iter().enumerate() {
+        let fault_child = nexus.children.first().unwrap().uri.to_string();
+        let mut rpc_handle = cluster
+            .grpc_handle(cluster.node(node as u32).as_str())
+            .await
+            .unwrap();
+        rpc_handle
+            .io_engine
+            .fault_nexus_child(FaultNexusChildRequest {
+                uuid: nexus.uuid.to_string(),
+                uri: fault_child.clone(),
+            })
+            .await
+            .unwrap();
+        faulted.push(fault_child);
+    }
+
+    let start = std::time::Instant::now();
+    loop {
+        let targets = volume_targets(&cluster, &volumes).await;
+        let rebuilding = targets
+            .iter()
+            .flat_map(|t| t.children.iter())
+            .filter(|c| c.rebuilding())
+            .count();
+        assert!(
+            rebuilding <= MAX_REBUILDS as usize,
+            "More than {} rebuilds in progress: {:#?}",
+            MAX_REBUILDS,
+            targets
+        );
+
+        let healed = targets.iter().zip(&faulted).all(|(target, fault_child)| {
+            target.children.len() == 2
+                && target
+                    .children
+                    .iter()
+                    .all(|c| c.uri.as_str() != fault_child && c.state == ChildState::Online)
+        });
+        if healed {
+            break;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(120),
+            "The volumes were not healed: {:#?}",
+            targets
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
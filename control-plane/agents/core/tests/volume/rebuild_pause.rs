@@ -0,0 +1,159 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    Child, ChildState, CreateVolume, Filter, PublishVolume, SetVolumeReplica, Volume, VolumeId,
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use std::time::Duration;
+
+const POOL_SIZE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+const VOLUME_SIZE_BYTES: u64 = 1024 * 1024 * 1024;
+
+async fn get_volume(cluster: &Cluster, volume_id: &VolumeId) -> Volume {
+    cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume_id.clone()), None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0)
+}
+
+/// The child of the volume target which is being rebuilt, if any.
+async fn rebuilding_child(cluster: &Cluster, volume_id: &VolumeId) -> Option<Child> {
+    let volume = get_volume(cluster, volume_id).await;
+    let target = volume.state().target?;
+    target.children.into_iter().find(|c| c.rebuilding())
+}
+
+/// Check that the rebuild progress of the rebuilding child does not move for a few seconds.
+async fn assert_rebuild_held(cluster: &Cluster, volume_id: &VolumeId) {
+    let before = rebuilding_child(cluster, volume_id)
+        .await
+        .expect("The child should still be rebuilding");
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let after = rebuilding_child(cluster, volume_id)
+        .await
+        .expect("The child should still be rebuilding");
+    assert_eq!(before.uri, after.uri);
+    assert_eq!(before.rebuild_progress, after.rebuild_progress);
+}
+
+/// Pause the rebuild of a replica, check that it's held across a restart of the core agent, and
+/// that it completes once resumed.
+#[tokio::test]
+async fn pause_resume_rebuild() {
+    let cluster = ClusterBuilder::builder()
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let volumes_api = cluster.rest_v00().volumes_api();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: VOLUME_SIZE_BYTES,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+    volume_client
+        .publish(
+            &PublishVolume {
+                uuid: volume_id.clone(),
+                target_node: Some(cluster.node(0)),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+
+    // the new replica is rebuilt from the existing one
+    volume_client
+        .set_replica(
+            &SetVolumeReplica {
+                uuid: volume_id.clone(),
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let start = std::time::Instant::now();
+    while rebuilding_child(&cluster, &volume_id).await.is_none() {
+        assert!(
+            start.elapsed() < Duration::from_secs(10),
+            "The rebuild did not start"
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    let volume = volumes_api
+        .post_volume_rebuild_pause(&volume_id)
+        .await
+        .unwrap();
+    assert!(volume.spec.rebuild_paused);
+    assert_rebuild_held(&cluster, &volume_id).await;
+
+    // pausing again is rejected, as the rebuilds are already paused
+    volumes_api
+        .post_volume_rebuild_pause(&volume_id)
+        .await
+        .expect_err("The rebuilds are already paused");
+
+    // the rebuild stays paused across a restart of the core agent
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    let volume = volumes_api.get_volume(&volume_id).await.unwrap();
+    assert!(volume.spec.rebuild_paused);
+    assert_rebuild_held(&cluster, &volume_id).await;
+
+    let volume = volumes_api
+        .post_volume_rebuild_resume(&volume_id)
+        .await
+        .unwrap();
+    assert!(!volume.spec.rebuild_paused);
+
+    let start = std::time::Instant::now();
+    while rebuilding_child(&cluster, &volume_id).await.is_some() {
+        assert!(
+            start.elapsed() < Duration::from_secs(60),
+            "The rebuild did not complete"
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    let target = get_volume(&cluster, &volume_id)
+        .await
+        .state()
+        .target
+        .unwrap();
+    assert_eq!(target.children.len(), 2);
+    assert!(target
+        .children
+        .iter()
+        .all(|c| c.state == ChildState::Online));
+
+    // resuming again is rejected, as the rebuilds are no longer paused
+    volumes_api
+        .post_volume_rebuild_resume(&volume_id)
+        .await
+        .expect_err("The rebuilds are not paused");
+}
@@ -0,0 +1,159 @@
+#![cfg(test)]
+
+use common_lib::types::v0::{
+    openapi::models,
+    transport::{
+        ChildState, CreateVolume, Filter, Nexus, PatchVolume, PublishVolume, RebuildPriority,
+        VolumeId,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use rpc::io_engine::FaultNexusChildRequest;
+use std::time::Duration;
+
+const POOL_SIZE_BYTES: u64 = 1024 * 1024 * 1024;
+const VOLUME_SIZE_BYTES: u64 = 256 * 1024 * 1024;
+const REPLACEMENT_PASS: &str = "replace-faulted-children";
+
+async fn volume_target(cluster: &Cluster, volume: &VolumeId) -> Nexus {
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .get(Filter::Volume(volume.clone()), None, None)
+        .await
+        .unwrap()
+        .entries
+        .remove(0);
+    volume.state().target.unwrap()
+}
+
+async fn replacement_pass(cluster: &Cluster, enabled: bool) {
+    let state = cluster
+        .rest_v00()
+        .admin_api()
+        .put_admin_reconciler_pass(REPLACEMENT_PASS, models::ReconcilerPassSwitch::new(enabled))
+        .await
+        .unwrap();
+    assert_eq!(state.enabled, enabled);
+}
+
+/// Fault a child of a low and of a high rebuild priority volume, with a system-wide limit of a
+/// single rebuild, and check that the high priority volume is rebuilt first even though its child
+/// was faulted last, whilst the low priority volume is still eventually healed.
+#[tokio::test]
+async fn rebuild_priority_ordering() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_tmpfs_pool(POOL_SIZE_BYTES)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_max_rebuilds(Some(1)))
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+
+    let high = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: VOLUME_SIZE_BYTES,
+                replicas: 2,
+                rebuild_priority: RebuildPriority::High,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(high.spec().rebuild_priority, RebuildPriority::High);
+
+    // the priority of an existing volume may be changed
+    let low = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: VOLUME_SIZE_BYTES,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(low.spec().rebuild_priority, RebuildPriority::Normal);
+    let low = volume_client
+        .patch(
+            &PatchVolume::new(low.spec().uuid, Some(RebuildPriority::Low)),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(low.spec().rebuild_priority, RebuildPriority::Low);
+
+    // fault the child of the low priority volume first, whilst the replacements are held back
+    replacement_pass(&cluster, false).await;
+    let volumes = vec![low.spec().uuid, high.spec().uuid];
+    let mut faulted = vec![];
+    for (node, volume) in volumes.iter().enumerate() {
+        volume_client
+            .publish(
+                &PublishVolume::new(volume.clone(), Some(cluster.node(node as u32)), None),
+                None,
+            )
+            .await
+            .unwrap();
+        let nexus = volume_target(&cluster, volume).await;
+        let fault_child = nexus.children.first().unwrap().uri.to_string();
+        let mut rpc_handle = cluster
+            .grpc_handle(cluster.node(node as u32).as_str())
+            .await
+            .unwrap();
+        rpc_handle
+            .io_engine
+            .fault_nexus_child(FaultNexusChildRequest {
+                uuid: nexus.uuid.to_string(),
+                uri: fault_child.clone(),
+            })
+            .await
+            .unwrap();
+        faulted.push(fault_child);
+    }
+    replacement_pass(&cluster, true).await;
+
+    let mut healed_order = vec![];
+    let start = std::time::Instant::now();
+    while healed_order.len() < volumes.len() {
+        let mut targets = vec![];
+        for volume in &volumes {
+            targets.push(volume_target(&cluster, volume).await);
+        }
+        let rebuilding = targets
+            .iter()
+            .flat_map(|t| t.children.iter())
+            .filter(|c| c.rebuilding())
+            .count();
+        assert!(rebuilding <= 1, "More than 1 rebuild: {:#?}", targets);
+
+        for ((volume, target), fault_child) in volumes.iter().zip(&targets).zip(&faulted) {
+            let healed = target.children.len() == 2
+                && target
+                    .children
+                    .iter()
+                    .all(|c| c.uri.as_str() != fault_child && c.state == ChildState::Online);
+            if healed && !healed_order.contains(volume) {
+                healed_order.push(volume.clone());
+            }
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(120),
+            "The volumes were not healed: {:#?}",
+            targets
+        );
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+    assert_eq!(healed_order, vec![high.spec().uuid, low.spec().uuid]);
+}
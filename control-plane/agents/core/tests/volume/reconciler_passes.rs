@@ -0,0 +1,140 @@
+#![cfg(test)]
+
+use super::helpers::{volume_children, wait_till_volume_nexus};
+use common_lib::types::v0::{
+    openapi::{apis::StatusCode, clients::tower::Error, models},
+    transport::{CreateVolume, PublishVolume, VolumeId},
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::volume::traits::VolumeOperations;
+use rpc::io_engine::FaultNexusChildRequest;
+use std::time::Duration;
+
+const REPLACEMENT_PASS: &str = "replace-faulted-children";
+
+async fn replacement_pass(cluster: &Cluster) -> models::ReconcilerPassState {
+    cluster
+        .rest_v00()
+        .admin_api()
+        .get_admin_reconciler()
+        .await
+        .unwrap()
+        .passes
+        .into_iter()
+        .find(|p| p.pass == REPLACEMENT_PASS)
+        .unwrap()
+}
+
+/// Disable the replacement of the faulted children at runtime and check that a faulted child is
+/// left alone, even across a restart of the core agent, until the pass is enabled again.
+#[tokio::test]
+async fn disabled_replacement_pass() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .build()
+        .await
+        .unwrap();
+    let admin_api = cluster.rest_v00().admin_api();
+    let volume_client = cluster.grpc_client().volume();
+    let registry_client = cluster.grpc_client().registry();
+
+    let passes = admin_api.get_admin_reconciler().await.unwrap().passes;
+    assert_eq!(
+        passes.iter().map(|p| p.pass.as_str()).collect::<Vec<_>>(),
+        vec![
+            "garbage-collect-replicas",
+            "replace-faulted-children",
+            "reshare-replicas",
+            "nexus-recreate",
+            "dirty-spec-replay"
+        ]
+    );
+    assert!(passes.iter().all(|p| p.enabled && p.skipped == 0));
+
+    let error = admin_api
+        .put_admin_reconciler_pass(
+            "replace-everything",
+            models::ReconcilerPassSwitch::new(false),
+        )
+        .await
+        .expect_err("The pass does not exist");
+    match error {
+        Error::Response(response) => assert_eq!(response.status(), StatusCode::BAD_REQUEST),
+        error => panic!("Unexpected error: {:?}", error),
+    }
+
+    let state = admin_api
+        .put_admin_reconciler_pass(REPLACEMENT_PASS, models::ReconcilerPassSwitch::new(false))
+        .await
+        .unwrap();
+    assert!(!state.enabled);
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(volume.spec().uuid.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    let nexus = volume.state().target.unwrap();
+    let fault_child = nexus.children.first().unwrap().uri.to_string();
+    let mut rpc_handle = cluster.grpc_handle(cluster.node(0).as_str()).await.unwrap();
+    rpc_handle
+        .io_engine
+        .fault_nexus_child(FaultNexusChildRequest {
+            uuid: nexus.uuid.to_string(),
+            uri: fault_child.clone(),
+        })
+        .await
+        .unwrap();
+
+    // the faulted child is not replaced, though the skipped work is accounted for
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    let children = volume_children(volume.uuid(), &volume_client).await;
+    assert!(children.iter().any(|c| c.uri == fault_child));
+    assert!(replacement_pass(&cluster).await.skipped > 0);
+
+    // the pass remains disabled across restarts
+    cluster.restart_core().await.unwrap();
+    cluster
+        .volume_service_liveness(None)
+        .await
+        .expect("Should have restarted by now");
+    assert!(!replacement_pass(&cluster).await.enabled);
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    let children = volume_children(volume.uuid(), &volume_client).await;
+    assert!(children.iter().any(|c| c.uri == fault_child));
+
+    // until it's enabled again, which heals the volume
+    let state = admin_api
+        .put_admin_reconciler_pass(REPLACEMENT_PASS, models::ReconcilerPassSwitch::new(true))
+        .await
+        .unwrap();
+    assert!(state.enabled);
+    let children = wait_till_volume_nexus(
+        volume.uuid(),
+        2,
+        &fault_child,
+        &volume_client,
+        &registry_client,
+    )
+    .await;
+    assert!(!children.iter().any(|c| c.uri == fault_child));
+}
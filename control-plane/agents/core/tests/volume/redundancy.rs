@@ -0,0 +1,83 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{
+        CreateVolume, DestroyReplica, Filter, Replica, SetVolumeReplica, VolumeId,
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+#[tokio::test]
+async fn min_replication_factor() {
+    // the reconcilers must not replace the removed replicas whilst the test runs
+    let reconcile_period = Duration::from_secs(60);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+    let volume_replicas = || async {
+        replica_client
+            .get(Filter::Volume(volume_id.clone()), None)
+            .await
+            .unwrap()
+            .into_inner()
+    };
+    let replicas = volume_replicas().await;
+    assert_eq!(replicas.len(), 2);
+    let destroy = |replica: &Replica| DestroyReplica::from(replica.clone()).with_disown_all();
+
+    // each removal is safe on its own, but together they'd remove every copy of the volume data
+    let (scale_down, removal) = tokio::join!(
+        volume_client.set_replica(&SetVolumeReplica::new(volume_id.clone(), 1), None),
+        replica_client.destroy(&destroy(&replicas[0]), None)
+    );
+    tracing::info!("Scale down: {:?}, Removal: {:?}", scale_down, removal);
+    assert!(
+        scale_down.is_ok() != removal.is_ok(),
+        "Only one of the concurrent removals may go ahead"
+    );
+    let replicas = volume_replicas().await;
+    assert_eq!(replicas.len(), 1);
+
+    let error = replica_client
+        .destroy(&destroy(&replicas[0]), None)
+        .await
+        .expect_err("The last copy of the volume data must not be removed");
+    assert_eq!(error.kind, ReplyErrorKind::BelowMinReplicationFactor);
+    assert_eq!(error.resource, ResourceKind::Volume);
+    assert_eq!(volume_replicas().await.len(), 1);
+
+    // unless the check is overridden
+    replica_client
+        .destroy(
+            &destroy(&replicas[0]).with_override_redundancy_check(),
+            None,
+        )
+        .await
+        .unwrap();
+    assert!(volume_replicas().await.is_empty());
+}
@@ -0,0 +1,162 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::nexus::NexusSpec,
+        transport::{
+            CreateVolume, GetSpecs, NvmeReservation, NvmeReservationType, PublishVolume,
+            UnpublishVolume, Volume, VolumeId, VolumeShareProtocol,
+        },
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+
+/// Get the spec of the nexus which is the target of the given volume
+async fn target_nexus_spec(volume: &Volume, client: &dyn RegistryOperations) -> NexusSpec {
+    let target = volume.state().target.expect("volume should be published");
+    client
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap()
+        .nexuses
+        .into_iter()
+        .find(|n| n.uuid == target.uuid)
+        .expect("the target nexus spec should exist")
+}
+
+#[tokio::test]
+async fn volume_nvme_reservation() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let registry_client = cluster.grpc_client().registry();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 1,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+
+    let reservation = NvmeReservation::new(0x12345678, NvmeReservationType::WriteExclusiveAllRegs);
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(0)),
+                Some(VolumeShareProtocol::Nvmf),
+            )
+            .with_reservation(reservation),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().reservation, Some(reservation));
+    let nexus = target_nexus_spec(&volume, &registry_client).await;
+    let config = nexus.config.expect("the nexus should have the nvmf config");
+    assert_eq!(config.reservation_key(), reservation.key);
+    assert_eq!(
+        config.reservation_type(),
+        Some(reservation.reservation_type)
+    );
+
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+
+    // the volume remains fenced, so another host may not take it over with a different key
+    let error = volume_client
+        .publish(
+            &PublishVolume::new(volume_id.clone(), Some(cluster.node(1)), None).with_reservation(
+                NvmeReservation::new(0x87654321, NvmeReservationType::WriteExclusiveAllRegs),
+            ),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Conflict);
+    assert_eq!(error.resource, ResourceKind::Volume);
+
+    // republishing without a reservation must keep the existing fencing
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(1)),
+                Some(VolumeShareProtocol::Nvmf),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().reservation, Some(reservation));
+    let nexus = target_nexus_spec(&volume, &registry_client).await;
+    assert_eq!(nexus.node, cluster.node(1));
+    let config = nexus.config.expect("the nexus should have the nvmf config");
+    assert_eq!(config.reservation_key(), reservation.key);
+
+    // the io-engine can only acquire write exclusive, all registrants reservations
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    let error = volume_client
+        .publish(
+            &PublishVolume::new(volume_id.clone(), Some(cluster.node(0)), None).with_reservation(
+                NvmeReservation::new(reservation.key, NvmeReservationType::ExclusiveAccess),
+            ),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::Unimplemented);
+
+    // once released on unpublish, the volume may be fenced with another key
+    volume_client
+        .publish(
+            &PublishVolume::new(volume_id.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+    let volume = volume_client
+        .unpublish(
+            &UnpublishVolume::new(&volume_id, false).with_release_reservation(true),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().reservation, None);
+    let other = NvmeReservation::new(0x87654321, NvmeReservationType::WriteExclusiveAllRegs);
+    let volume = volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(1)),
+                Some(VolumeShareProtocol::Nvmf),
+            )
+            .with_reservation(other),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().reservation, Some(other));
+}
@@ -0,0 +1,115 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, GetSchedulingTraces, PoolCandidate, SchedulingTrace, VolumeId,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    node::traits::NodeOperations, registry::traits::RegistryOperations,
+    volume::traits::VolumeOperations,
+};
+
+const CORDONED: &str = "NodeFilters::cordoned";
+
+#[tokio::test]
+async fn volume_scheduling_trace() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+
+    cluster
+        .grpc_client()
+        .node()
+        .cordon(cluster.node(2), "trace".into(), None)
+        .await
+        .unwrap();
+
+    let volume_id = VolumeId::new();
+    volume_client
+        .create(&create_volume(&volume_id, 2), None)
+        .await
+        .unwrap();
+
+    let trace = volume_trace(&cluster, &volume_id).await;
+    assert_eq!(trace.error, None);
+    assert_eq!(trace.attempts.len(), 1, "{:#?}", trace);
+    let attempt = &trace.attempts[0];
+    assert_eq!(attempt.filters.first().map(String::as_str), Some(CORDONED));
+    assert_eq!(
+        rejected_by(&attempt.candidates, &cluster, 2),
+        Some(CORDONED.to_string())
+    );
+    for node in 0 .. 2 {
+        assert_eq!(rejected_by(&attempt.candidates, &cluster, node), None);
+        assert!(attempt.order.contains(&cluster.pool(node, 0)));
+    }
+    assert_eq!(attempt.order.len(), 2);
+    assert!(!attempt.sorters.is_empty());
+
+    // the trace of a failed creation is retained along with its error
+    let failed_id = VolumeId::new();
+    let error = volume_client
+        .create(&create_volume(&failed_id, 3), None)
+        .await
+        .expect_err("Only 2 pools are usable");
+    assert_eq!(error.kind, ReplyErrorKind::ResourceExhausted);
+
+    let trace = volume_trace(&cluster, &failed_id).await;
+    assert!(trace.error.is_some());
+    let attempt = trace.attempts.last().unwrap();
+    assert_eq!(
+        rejected_by(&attempt.candidates, &cluster, 2),
+        Some(CORDONED.to_string())
+    );
+
+    // the traces are listed the oldest first
+    let traces = cluster
+        .grpc_client()
+        .registry()
+        .get_scheduling_traces(&GetSchedulingTraces::new(None), None)
+        .await
+        .unwrap()
+        .traces;
+    let volumes = traces.into_iter().map(|t| t.volume).collect::<Vec<_>>();
+    assert_eq!(volumes, vec![volume_id, failed_id]);
+}
+
+fn create_volume(volume_id: &VolumeId, replicas: u64) -> CreateVolume {
+    CreateVolume {
+        uuid: volume_id.clone(),
+        size: 5242880,
+        replicas,
+        ..Default::default()
+    }
+}
+
+async fn volume_trace(cluster: &Cluster, volume_id: &VolumeId) -> SchedulingTrace {
+    let mut traces = cluster
+        .grpc_client()
+        .registry()
+        .get_scheduling_traces(&GetSchedulingTraces::new(Some(volume_id)), None)
+        .await
+        .unwrap()
+        .traces;
+    assert_eq!(traces.len(), 1, "{:#?}", traces);
+    traces.remove(0)
+}
+
+/// The filter which rejected the pool of the given `node`, if any.
+fn rejected_by(candidates: &[PoolCandidate], cluster: &Cluster, node: u32) -> Option<String> {
+    let pool = cluster.pool(node, 0);
+    let candidate = candidates.iter().find(|c| c.pool == pool);
+    candidate
+        .expect("All pools are candidates")
+        .rejected_by
+        .clone()
+}
@@ -0,0 +1,212 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::{ReplyErrorKind, ResourceKind},
+    types::v0::transport::{CreateReplica, CreateVolume, Filter, ReplicaId, Volume, VolumeId},
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{
+    pool::traits::PoolOperations, replica::traits::ReplicaOperations,
+    volume::traits::VolumeOperations, Pagination, SortOrder, Sorting,
+};
+
+const MB: u64 = 1024 * 1024;
+
+/// Get all the volumes sorted as given, one page of `page_size` volumes at a time
+async fn get_sorted_pages(
+    client: &impl VolumeOperations,
+    sorting: &Sorting,
+    page_size: u64,
+) -> Vec<Volume> {
+    let mut volumes = vec![];
+    let mut starting_token = Some(0);
+    while let Some(token) = starting_token {
+        let page = client
+            .get_sorted(
+                Filter::None,
+                Some(sorting.clone()),
+                Some(Pagination::new(page_size, token)),
+                None,
+            )
+            .await
+            .unwrap();
+        assert!(page.entries.len() as u64 <= page_size);
+        volumes.extend(page.entries);
+        starting_token = page.next_token;
+    }
+    volumes
+}
+
+fn uuids(volumes: &[Volume]) -> Vec<VolumeId> {
+    volumes.iter().map(|v| v.uuid().clone()).collect()
+}
+
+#[tokio::test]
+async fn volume_sorting() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+
+    // volumes which share the same size must still be returned in a stable order
+    let mut created = vec![];
+    for size in [10, 5, 15, 5, 10, 5, 20] {
+        let volume = volume_client
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: size * MB,
+                    replicas: 1,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        created.push(volume.uuid().clone());
+    }
+
+    let by_size = Sorting::new("size", SortOrder::Descending);
+    let all = volume_client
+        .get_sorted(Filter::None, Some(by_size.clone()), None, None)
+        .await
+        .unwrap()
+        .entries;
+    assert_eq!(all.len(), created.len());
+    for pair in all.windows(2) {
+        let (a, b) = (pair[0].spec(), pair[1].spec());
+        assert!(a.size > b.size || (a.size == b.size && a.uuid.as_str() < b.uuid.as_str()));
+    }
+
+    // the sorting is applied before the pagination, so the pages follow on from each other
+    for page_size in [1, 2, 3, 7, 10] {
+        let pages = get_sorted_pages(&volume_client, &by_size, page_size).await;
+        assert_eq!(uuids(&pages), uuids(&all), "page size: {}", page_size);
+    }
+
+    let by_creation = Sorting::new("created_at", SortOrder::Ascending);
+    let pages = get_sorted_pages(&volume_client, &by_creation, 3).await;
+    assert_eq!(uuids(&pages), created);
+}
+
+/// Read-only, so it may also run against an external cluster, see `Cluster::attach`.
+#[tokio::test]
+async fn volume_sorting_invalid_key() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .build()
+        .await
+        .unwrap();
+
+    let error = cluster
+        .grpc_client()
+        .volume()
+        .get_sorted(
+            Filter::None,
+            Some(Sorting::new("colour", SortOrder::Ascending)),
+            None,
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    assert_eq!(error.resource, ResourceKind::Volume);
+    assert!(error.source.contains("created_at"), "{:?}", error);
+}
+
+/// Read-only, so it may also run against an external cluster, see `Cluster::attach`, which is
+/// expected to have pools of its own.
+#[tokio::test]
+async fn pool_sorting() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(2)
+        .build()
+        .await
+        .unwrap();
+
+    let pools = cluster
+        .grpc_client()
+        .pool()
+        .get_sorted(
+            Filter::None,
+            Some(Sorting::new("id", SortOrder::Descending)),
+            None,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    if cluster.is_attached() {
+        assert!(!pools.is_empty());
+    } else {
+        assert_eq!(pools.len(), 4);
+    }
+    let ids = pools.iter().map(|p| p.id().to_string()).collect::<Vec<_>>();
+    let mut expected = ids.clone();
+    expected.sort();
+    expected.reverse();
+    assert_eq!(ids, expected);
+}
+
+#[tokio::test]
+async fn pool_replica_sorting() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(2)
+        .build()
+        .await
+        .unwrap();
+
+    let replica_client = cluster.grpc_client().replica();
+
+    for (size, pool) in [(8, 0), (4, 1), (12, 0)] {
+        replica_client
+            .create(
+                &CreateReplica {
+                    node: cluster.node(0),
+                    uuid: ReplicaId::new(),
+                    pool: cluster.pool(0, pool),
+                    size: size * MB,
+                    thin: false,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+    }
+    let replicas = replica_client
+        .get_sorted(
+            Filter::Node(cluster.node(0)),
+            Some(Sorting::new("size", SortOrder::Ascending)),
+            None,
+        )
+        .await
+        .unwrap()
+        .into_inner();
+    let sizes = replicas.iter().map(|r| r.size / MB).collect::<Vec<_>>();
+    assert_eq!(sizes, vec![4, 8, 12]);
+
+    let error = replica_client
+        .get_sorted(
+            Filter::None,
+            Some(Sorting::new("owner", SortOrder::Ascending)),
+            None,
+        )
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+    assert_eq!(error.resource, ResourceKind::Replica);
+}
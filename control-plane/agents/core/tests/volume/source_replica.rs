@@ -0,0 +1,128 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::transport::{
+        CreateVolume, GetSpecs, GetVolumes, PublishVolume, ReplicaId, SetVolumeSourceReplica,
+        Volume, VolumeId, VolumeShareProtocol, VolumeStatus,
+    },
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{registry::traits::RegistryOperations, volume::traits::VolumeOperations};
+use std::time::Duration;
+
+#[tokio::test]
+async fn volume_source_replica() {
+    let reconcile_period = Duration::from_millis(200);
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("200ms")
+        .with_reconcile_period(reconcile_period, reconcile_period)
+        .build()
+        .await
+        .unwrap();
+    let volume_client = cluster.grpc_client().volume();
+
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 2,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.uuid().clone();
+    let replicas = volume_replicas(&cluster, &volume_id).await;
+    assert_eq!(replicas.len(), 2);
+    let source = replicas.last().cloned().unwrap();
+
+    // only the replicas of a published volume can be designated
+    let error = volume_client
+        .set_source_replica(
+            &SetVolumeSourceReplica::new(volume_id.clone(), source.clone()),
+            None,
+        )
+        .await
+        .expect_err("Volume not published");
+    assert_eq!(error.kind, ReplyErrorKind::NotPublished);
+
+    volume_client
+        .publish(
+            &PublishVolume::new(
+                volume_id.clone(),
+                Some(cluster.node(0)),
+                Some(VolumeShareProtocol::Nvmf),
+            ),
+            None,
+        )
+        .await
+        .unwrap();
+    wait_volume_online(&volume_id, &volume_client).await;
+
+    let error = volume_client
+        .set_source_replica(
+            &SetVolumeSourceReplica::new(volume_id.clone(), ReplicaId::new()),
+            None,
+        )
+        .await
+        .expect_err("Not a replica of the volume");
+    assert_eq!(error.kind, ReplyErrorKind::NotFound);
+
+    let volume = volume_client
+        .set_source_replica(
+            &SetVolumeSourceReplica::new(volume_id.clone(), source.clone()),
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(volume.spec().source_replica, Some(source.clone()));
+
+    // the other replica is removed from the target, and added back to be rebuilt from the source
+    let volume = wait_volume_online(&volume_id, &volume_client).await;
+    assert_eq!(volume.spec().source_replica, Some(source.clone()));
+    assert_eq!(volume_replicas(&cluster, &volume_id).await.len(), 2);
+}
+
+/// Get the replicas of the volume
+async fn volume_replicas(cluster: &Cluster, volume_id: &VolumeId) -> Vec<ReplicaId> {
+    let specs = cluster
+        .grpc_client()
+        .registry()
+        .get_specs(&GetSpecs {}, None)
+        .await
+        .unwrap();
+    specs
+        .replicas
+        .into_iter()
+        .filter(|r| r.owners.owned_by(volume_id))
+        .map(|r| r.uuid)
+        .collect()
+}
+
+/// Wait until the volume is online with all of its replicas as children of its target
+async fn wait_volume_online(volume_id: &VolumeId, client: &dyn VolumeOperations) -> Volume {
+    let timeout = Duration::from_secs(20);
+    let start = std::time::Instant::now();
+    loop {
+        let volumes = client
+            .get(GetVolumes::new(volume_id).filter, None, None)
+            .await
+            .unwrap();
+        let volume = volumes.entries.first().cloned().unwrap();
+        let children = volume.state().target.map(|t| t.children.len());
+        if volume.state().status == VolumeStatus::Online && children == Some(2) {
+            return volume;
+        }
+        if start.elapsed() > timeout {
+            panic!("Timeout waiting for the volume to be online: {:#?}", volume);
+        }
+        tokio::time::sleep(Duration::from_millis(200)).await;
+    }
+}
@@ -0,0 +1,78 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    CreateVolume, Filter, GetVolumesStatus, PublishVolume, VolumeId, VolumeStatus,
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+
+#[tokio::test]
+async fn volumes_status() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let mut volumes = vec![];
+    for _ in 0 .. 2 {
+        let volume = volume_client
+            .create(
+                &CreateVolume {
+                    uuid: VolumeId::new(),
+                    size: 5242880,
+                    replicas: 1,
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .unwrap();
+        volumes.push(volume.spec().uuid);
+    }
+    // one with a target and one without
+    volume_client
+        .publish(
+            &PublishVolume::new(volumes[0].clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let unknown = VolumeId::new();
+    let request = GetVolumesStatus::new(vec![
+        volumes[1].clone(),
+        unknown.clone(),
+        volumes[0].clone(),
+    ]);
+    let status = volume_client.get_status(&request, None).await.unwrap();
+    let uuids = status
+        .entries
+        .iter()
+        .map(|e| e.uuid.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(uuids, request.volumes);
+    assert!(!status.entries[1].found());
+    for entry in status.entries.iter().filter(|e| e.found()) {
+        let volume = volume_client
+            .get(Filter::Volume(entry.uuid.clone()), None, None)
+            .await
+            .unwrap();
+        let volume = volume.entries.first().unwrap();
+        assert_eq!(entry.status, volume.status());
+        assert_eq!(entry.status, Some(VolumeStatus::Online));
+    }
+
+    // all the volumes are reported when none are requested
+    let status = volume_client
+        .get_status(&GetVolumesStatus::default(), None)
+        .await
+        .unwrap();
+    assert_eq!(status.entries.len(), volumes.len());
+    assert!(status.entries.iter().all(|e| volumes.contains(&e.uuid)));
+    assert!(status.entries.iter().all(|e| e.found()));
+}
@@ -0,0 +1,130 @@
+#![cfg(test)]
+
+use common_lib::types::v0::transport::{
+    ChildState, CreateVolume, Filter, GetVolumeTopology, NexusStatus, NodeStatus, PoolStatus,
+    PublishVolume, ReplicaStatus, VolumeId, VolumeStatus,
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{replica::traits::ReplicaOperations, volume::traits::VolumeOperations};
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+#[tokio::test]
+async fn volume_topology() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_node_deadline("1s")
+        .with_cache_period("1s")
+        // don't let the reconcile interfere with the tests
+        .with_reconcile_period(Duration::from_secs(1000), Duration::from_secs(1000))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let replica_client = cluster.grpc_client().replica();
+    let volume = volume_client
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 3,
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+    let request = GetVolumeTopology::new(&volume_id);
+
+    // without a target all the replicas hang off the volume
+    let topology = volume_client.get_topology(&request, None).await.unwrap();
+    assert_eq!(topology.uuid, volume_id);
+    assert!(topology.targets.is_empty());
+    assert_eq!(topology.replicas.len(), 3);
+
+    volume_client
+        .publish(
+            &PublishVolume::new(volume_id.clone(), Some(cluster.node(0)), None),
+            None,
+        )
+        .await
+        .unwrap();
+
+    let topology = volume_client.get_topology(&request, None).await.unwrap();
+    tracing::info!("Topology: {:#?}", topology);
+    assert_eq!(topology.status, VolumeStatus::Online);
+    assert!(topology.replicas.is_empty());
+    assert_eq!(topology.targets.len(), 1);
+
+    let target = topology.targets.first().unwrap();
+    assert_eq!(target.status, NexusStatus::Online);
+    assert!(!target.last_known);
+    assert_eq!(target.node.id, cluster.node(0));
+    assert_eq!(target.node.status, NodeStatus::Online);
+    assert_eq!(target.children.len(), 3);
+
+    let replicas = replica_client
+        .get(Filter::Volume(volume_id.clone()), None)
+        .await
+        .unwrap()
+        .into_inner();
+    for child in &target.children {
+        assert_eq!(child.state, ChildState::Online);
+        let replica = child
+            .replica
+            .as_ref()
+            .expect("children are volume replicas");
+        let state = replicas.iter().find(|r| r.uuid == replica.uuid).unwrap();
+        assert_eq!(replica.status, ReplicaStatus::Online);
+        assert!(!replica.last_known);
+        assert_eq!(replica.pool.id, state.pool);
+        assert_eq!(replica.pool.status, PoolStatus::Online);
+        assert_eq!(replica.pool.node.id, state.node);
+        assert_eq!(replica.pool.node.status, NodeStatus::Online);
+    }
+    let nodes = target
+        .children
+        .iter()
+        .map(|c| c.replica.as_ref().unwrap().pool.node.id.clone())
+        .collect::<HashSet<_>>();
+    assert_eq!(nodes.len(), 3, "each replica is on its own node");
+
+    // the components of an offline node carry their last known states
+    let offline = cluster.node(2);
+    cluster.composer().stop(offline.as_str()).await.unwrap();
+    let start = Instant::now();
+    let topology = loop {
+        let topology = volume_client.get_topology(&request, None).await.unwrap();
+        let target = topology.targets.first().unwrap();
+        let replica = target
+            .children
+            .iter()
+            .filter_map(|c| c.replica.as_ref())
+            .find(|r| r.pool.node.id == offline)
+            .unwrap();
+        if replica.pool.node.status != NodeStatus::Online {
+            break topology;
+        }
+        assert!(
+            start.elapsed() < Duration::from_secs(15),
+            "The node was not deemed offline"
+        );
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    };
+    let target = topology.targets.first().unwrap();
+    assert!(!target.last_known);
+    for replica in target.children.iter().filter_map(|c| c.replica.as_ref()) {
+        let offline = replica.pool.node.id == offline;
+        assert_eq!(replica.last_known, offline);
+        assert_eq!(replica.pool.last_known, offline);
+        assert_eq!(replica.status, ReplicaStatus::Online);
+        assert_eq!(replica.pool.status, PoolStatus::Online);
+    }
+}
@@ -0,0 +1,141 @@
+#![cfg(test)]
+
+use super::helpers::wait_volume_conditions;
+use common_lib::types::v0::transport::{
+    CreateVolume, DestroyReplica, ExplicitNodeTopology, Filter, NodeTopology, Replica, Topology,
+    VolumeCondition, VolumeId, VolumeStatus,
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use grpc::operations::{
+    node::traits::NodeOperations, replica::traits::ReplicaOperations,
+    volume::traits::VolumeOperations,
+};
+use std::time::Duration;
+
+const CONDITIONS_TIMEOUT: Duration = Duration::from_secs(15);
+
+/// Create a volume whose topology only allows its 2 replicas on the first 2 nodes, then cordon
+/// the second node and remove the replica on it, leaving the topology unsatisfiable.
+async fn unsatisfiable_topology(policy: &str) -> (Cluster, VolumeId) {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(3)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_reconcile_period(Duration::from_secs(1), Duration::from_secs(1))
+        .with_options(|o| o.with_topology_policy(policy))
+        .build()
+        .await
+        .unwrap();
+
+    let volume = cluster
+        .grpc_client()
+        .volume()
+        .create(
+            &CreateVolume {
+                uuid: VolumeId::new(),
+                size: 5242880,
+                replicas: 2,
+                topology: Some(Topology {
+                    node: Some(NodeTopology::Explicit(ExplicitNodeTopology {
+                        allowed_nodes: vec![cluster.node(0), cluster.node(1)],
+                        preferred_nodes: vec![],
+                    })),
+                    pool: None,
+                    anti_colocation: vec![],
+                    spread: None,
+                }),
+                ..Default::default()
+            },
+            None,
+        )
+        .await
+        .unwrap();
+    let volume_id = volume.spec().uuid;
+
+    let removed = cluster.node(1);
+    cluster
+        .grpc_client()
+        .node()
+        .cordon(removed.clone(), "removed".into(), None)
+        .await
+        .unwrap();
+    let replica = volume_replicas(&cluster, &volume_id)
+        .await
+        .into_iter()
+        .find(|r| r.node == removed)
+        .unwrap();
+    cluster
+        .grpc_client()
+        .replica()
+        .destroy(&DestroyReplica::from(replica).with_disown_all(), None)
+        .await
+        .unwrap();
+
+    (cluster, volume_id)
+}
+
+async fn volume_replicas(cluster: &Cluster, volume: &VolumeId) -> Vec<Replica> {
+    cluster
+        .grpc_client()
+        .replica()
+        .get(Filter::Volume(volume.clone()), None)
+        .await
+        .unwrap()
+        .into_inner()
+}
+
+#[tokio::test]
+async fn strict_topology_policy() {
+    let (cluster, volume_id) = unsatisfiable_topology("strict").await;
+
+    // the volume is kept degraded rather than placing a replica outside of its topology
+    let volume = wait_volume_conditions(
+        &cluster.grpc_client().volume(),
+        &volume_id,
+        &[VolumeCondition::TopologyUnsatisfiable],
+        CONDITIONS_TIMEOUT,
+    )
+    .await;
+    assert_eq!(volume.status(), Some(VolumeStatus::Degraded));
+    tokio::time::sleep(Duration::from_secs(3)).await;
+    let replicas = volume_replicas(&cluster, &volume_id).await;
+    assert_eq!(replicas.len(), 1);
+    assert_eq!(replicas[0].node, cluster.node(0));
+
+    // until the topology can be satisfied again
+    cluster
+        .grpc_client()
+        .node()
+        .uncordon(cluster.node(1), "removed".into())
+        .await
+        .unwrap();
+    wait_volume_conditions(
+        &cluster.grpc_client().volume(),
+        &volume_id,
+        &[],
+        CONDITIONS_TIMEOUT,
+    )
+    .await;
+    let replicas = volume_replicas(&cluster, &volume_id).await;
+    assert_eq!(replicas.len(), 2);
+    assert!(replicas.iter().all(|r| r.node != cluster.node(2)));
+}
+
+#[tokio::test]
+async fn relaxed_topology_policy() {
+    let (cluster, volume_id) = unsatisfiable_topology("relaxed").await;
+
+    // the missing replica is placed outside of the topology and the relaxation is flagged
+    wait_volume_conditions(
+        &cluster.grpc_client().volume(),
+        &volume_id,
+        &[VolumeCondition::TopologyRelaxed],
+        CONDITIONS_TIMEOUT,
+    )
+    .await;
+    let replicas = volume_replicas(&cluster, &volume_id).await;
+    assert_eq!(replicas.len(), 2);
+    assert!(replicas.iter().any(|r| r.node == cluster.node(2)));
+}
@@ -0,0 +1,75 @@
+#![cfg(test)]
+
+use common_lib::{
+    transport_api::ReplyErrorKind,
+    types::v0::{
+        openapi::models,
+        transport::{CreateVolume, Filter, VolumeId},
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::volume::traits::VolumeOperations;
+
+const MIB: u64 = 1024 * 1024;
+
+/// A dry-run validation reports every rule which the request violates at once, without creating
+/// the volume, and the creation of the volume fails on the first of them.
+#[tokio::test]
+async fn volume_validate_create() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_options(|o| o.with_min_volume_size("8MiB"))
+        .build()
+        .await
+        .unwrap();
+
+    let volume_client = cluster.grpc_client().volume();
+    let valid = CreateVolume {
+        uuid: VolumeId::new(),
+        size: 8 * MIB,
+        replicas: 1,
+        ..Default::default()
+    };
+    let violations = volume_client.validate_create(&valid, None).await.unwrap();
+    assert!(violations.violations.is_empty());
+
+    let invalid = CreateVolume {
+        uuid: VolumeId::new(),
+        size: MIB,
+        replicas: 0,
+        pools: vec![cluster.pool(0, 0)],
+        ..Default::default()
+    };
+    let violations = volume_client.validate_create(&invalid, None).await.unwrap();
+    let fields = violations
+        .violations
+        .iter()
+        .map(|v| v.field.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["size", "replicas", "pools"]);
+
+    // neither request was created
+    let volumes = volume_client.get(Filter::None, None, None).await.unwrap();
+    assert!(volumes.entries.is_empty());
+
+    let error = volume_client.create(&invalid, None).await.unwrap_err();
+    assert_eq!(error.kind, ReplyErrorKind::InvalidArgument);
+
+    // the same rules are served by the rest api, on the cluster defaults applied to the body
+    let body = models::CreateVolumeBody {
+        replicas: Some(2),
+        pools: Some(vec![cluster.pool(0, 0).to_string()]),
+        ..models::CreateVolumeBody::new(models::VolumePolicy::default(), MIB)
+    };
+    let violations = cluster
+        .rest_v00()
+        .volumes_api()
+        .post_volumes_validate(body)
+        .await
+        .unwrap();
+    assert_eq!(violations.violations.len(), 1);
+    assert_eq!(violations.violations[0].field, "size");
+}
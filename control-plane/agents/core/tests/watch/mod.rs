@@ -1,3 +1,5 @@
+mod target;
+
 use common_lib::{
     store::etcd::Etcd,
     types::v0::{
@@ -9,11 +11,10 @@ use common_lib::{
             models,
         },
         store::definitions::{ObjectKey, Store},
-        transport::{CreateVolume, Volume, VolumeId, WatchResourceId},
+        transport::WatchResourceId,
     },
 };
 use deployer_cluster::ClusterBuilder;
-use grpc::operations::volume::traits::VolumeOperations;
 use http::{Request, Response};
 use once_cell::sync::OnceCell;
 use std::{convert::Infallible, net::SocketAddr, str::FromStr, time::Duration};
@@ -21,20 +22,7 @@ use tokio::net::TcpStream;
 
 static CALLBACK: OnceCell<tokio::sync::mpsc::Sender<()>> = OnceCell::new();
 
-async fn setup_watch(client: &dyn VolumeOperations) -> (Volume, tokio::sync::mpsc::Receiver<()>) {
-    let volume = client
-        .create(
-            &CreateVolume {
-                uuid: VolumeId::new(),
-                size: 10 * 1024 * 1024,
-                replicas: 1,
-                ..Default::default()
-            },
-            None,
-        )
-        .await
-        .unwrap();
-
+async fn setup_watch() -> tokio::sync::mpsc::Receiver<()> {
     let (s, r) = tokio::sync::mpsc::channel(1);
     CALLBACK.set(s).unwrap();
 
@@ -54,7 +42,7 @@ async fn setup_watch(client: &dyn VolumeOperations) -> (Volume, tokio::sync::mps
     // wait until the "callback" server is running
     callback_server_liveness("10.1.0.1:8082").await;
 
-    (volume, r)
+    r
 }
 
 async fn callback_server_liveness(uri: &str) {
@@ -70,18 +58,22 @@ async fn callback_server_liveness(uri: &str) {
 
 #[tokio::test]
 async fn watch() {
-    let cluster = ClusterBuilder::builder().with_pools(1).build().await;
+    let cluster = ClusterBuilder::builder()
+        .with_pools(1)
+        .with_volumes(1, 10 * 1024 * 1024, 1)
+        .build()
+        .await;
     let cluster = cluster.unwrap();
     let client = cluster.rest_v00();
     let client = client.watches_api();
-    let volume_client = cluster.grpc_client().volume();
+    let volume_id = cluster.volume(0);
 
-    let (volume, mut callback_ch) = setup_watch(&volume_client).await;
+    let mut callback_ch = setup_watch().await;
 
-    let watch_volume = WatchResourceId::Volume(volume.spec().uuid);
+    let watch_volume = WatchResourceId::Volume(volume_id.clone());
     let callback = url::Url::parse("http://10.1.0.1:8082/test").unwrap();
 
-    let watches = client.get_watch_volume(&volume.spec().uuid).await.unwrap();
+    let watches = client.get_watch_volume(&volume_id).await.unwrap();
     assert!(watches.is_empty());
 
     let mut store = Etcd::new("0.0.0.0:2379")
@@ -89,7 +81,7 @@ async fn watch() {
         .expect("Failed to connect to etcd.");
 
     client
-        .put_watch_volume(&volume.spec().uuid, callback.as_str())
+        .put_watch_volume(&volume_id, callback.as_str())
         .await
         .expect_err("volume does not exist in the store");
 
@@ -99,11 +91,11 @@ async fn watch() {
         .unwrap();
 
     client
-        .put_watch_volume(&volume.spec().uuid, callback.as_str())
+        .put_watch_volume(&volume_id, callback.as_str())
         .await
         .unwrap();
 
-    let watches = client.get_watch_volume(&volume.spec().uuid).await.unwrap();
+    let watches = client.get_watch_volume(&volume_id).await.unwrap();
     assert_eq!(
         watches.first(),
         Some(&models::RestWatch {
@@ -123,7 +115,7 @@ async fn watch() {
         .unwrap();
 
     client
-        .del_watch_volume(&volume.spec().uuid, callback.as_str())
+        .del_watch_volume(&volume_id, callback.as_str())
         .await
         .unwrap();
 
@@ -136,6 +128,6 @@ async fn watch() {
         .await
         .expect_err("should have been deleted so no callback");
 
-    let watches = client.get_watch_volume(&volume.spec().uuid).await.unwrap();
+    let watches = client.get_watch_volume(&volume_id).await.unwrap();
     assert!(watches.is_empty());
 }
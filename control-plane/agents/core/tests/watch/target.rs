@@ -0,0 +1,117 @@
+#![cfg(test)]
+
+use common_lib::types::v0::{
+    openapi::client::hyper::{
+        body::to_bytes,
+        service::{make_service_fn, service_fn},
+        Body, Server,
+    },
+    transport::{
+        CreateWatch, PublishVolume, UnpublishVolume, VolumeShareProtocol, VolumeTargetChanged,
+        WatchCallback, WatchResourceId, WatchType,
+    },
+};
+use deployer_cluster::ClusterBuilder;
+use grpc::operations::{volume::traits::VolumeOperations, watch::traits::WatchOperations};
+use http::{Request, Response};
+use std::{convert::Infallible, net::SocketAddr, time::Duration};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver};
+
+/// Start a callback server which captures the volume target changes it's notified of.
+async fn target_callback_server(addr: SocketAddr) -> UnboundedReceiver<VolumeTargetChanged> {
+    let (sender, receiver) = unbounded_channel();
+    let make_service = make_service_fn(move |_conn| {
+        let sender = sender.clone();
+        async move {
+            Ok::<_, Infallible>(service_fn(move |req: Request<Body>| {
+                let sender = sender.clone();
+                async move {
+                    let body = to_bytes(req.into_body()).await.unwrap();
+                    sender.send(serde_json::from_slice(&body).unwrap()).unwrap();
+                    Ok::<_, Infallible>(Response::new(Body::empty()))
+                }
+            }))
+        }
+    });
+    let server = Server::bind(&addr).serve(make_service);
+    tokio::spawn(async move {
+        server.await.unwrap();
+    });
+    receiver
+}
+
+/// Republishing a volume elsewhere changes its target uri, which is notified exactly once to the
+/// target change watches of the volume, along with the previous uri.
+#[tokio::test]
+async fn watch_volume_target() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(false)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_volumes(1, 10 * 1024 * 1024, 1)
+        .with_cache_period("1s")
+        .build()
+        .await
+        .unwrap();
+    let volume_id = cluster.volume(0);
+    let volume_client = cluster.grpc_client().volume();
+    let watch_client = cluster.grpc_client().watch();
+
+    let mut events = target_callback_server(SocketAddr::from(([10, 1, 0, 1], 8083))).await;
+    let watch = |id: WatchResourceId| CreateWatch {
+        id,
+        callback: WatchCallback::Uri("http://10.1.0.1:8083/target".to_string()),
+        watch_type: WatchType::TargetChange,
+    };
+
+    watch_client
+        .create(&watch(WatchResourceId::Pool(cluster.pool(0, 0))), None)
+        .await
+        .expect_err("Only the volumes have a target");
+    watch_client
+        .create(&watch(WatchResourceId::Volume(volume_id.clone())), None)
+        .await
+        .unwrap();
+
+    let publish = |node: u32| {
+        PublishVolume::new(
+            volume_id.clone(),
+            Some(cluster.node(node)),
+            Some(VolumeShareProtocol::Nvmf),
+        )
+    };
+    let volume = volume_client.publish(&publish(0), None).await.unwrap();
+    let old_uri = volume.state().target.unwrap().device_uri;
+
+    // the first publish is not a change of the target
+    tokio::time::timeout(Duration::from_secs(2), events.recv())
+        .await
+        .expect_err("The volume was not published before");
+
+    volume_client
+        .unpublish(&UnpublishVolume::new(&volume_id, false), None)
+        .await
+        .unwrap();
+    let volume = volume_client.publish(&publish(1), None).await.unwrap();
+    let new_uri = volume.state().target.unwrap().device_uri;
+    assert_ne!(old_uri, new_uri);
+
+    let event = tokio::time::timeout(Duration::from_secs(2), events.recv())
+        .await
+        .expect("The republish should have been notified")
+        .unwrap();
+    assert_eq!(
+        event,
+        VolumeTargetChanged {
+            volume: volume_id,
+            old_uri,
+            new_uri,
+        }
+    );
+
+    // the change is not notified again by the following refreshes of the registry
+    tokio::time::timeout(Duration::from_secs(3), events.recv())
+        .await
+        .expect_err("The change should only have been notified once");
+}
@@ -1,11 +1,15 @@
 use crate::{
+    cluster_agent_client,
     path_provider::{CachedNvmePathProvider, NvmePathNameCollection},
     reporter::PathReporter,
+    stats::PathStatsCollector,
     Cli,
 };
+use common_lib::types::v0::transport::ReportPathStats;
+use grpc::operations::ha_node::traits::ClusterAgentOperations;
 use nvmeadm::nvmf_subsystem::Subsystem;
 use std::{collections::HashMap, rc::Rc};
-use tokio::time::{sleep, Duration};
+use tokio::time::{interval, sleep, Duration, MissedTickBehavior};
 
 /// Possible states of every path record.
 #[derive(Debug, Clone)]
@@ -95,9 +99,12 @@ impl PathRecord {
 #[derive(Debug)]
 pub struct PathFailureDetector {
     epoch: u64,
+    node_name: String,
     detection_period: Duration,
+    stats_report_period: Duration,
     suspected_paths: HashMap<String, PathRecord>,
     reporter: Rc<PathReporter>,
+    stats: PathStatsCollector,
 }
 
 impl PathFailureDetector {
@@ -110,20 +117,27 @@ impl PathFailureDetector {
 
         Ok(Self {
             epoch: 0,
+            node_name: args.node_name.clone(),
             detection_period: *args.detection_period,
+            stats_report_period: *args.stats_report_period,
             suspected_paths: HashMap::new(),
             reporter: Rc::new(reporter),
+            stats: PathStatsCollector::default(),
         })
     }
 
     fn rescan_paths(&mut self, path_collection: &mut NvmePathNameCollection) {
         // Update epoch before scanning controllers.
         self.epoch += 1;
+        self.stats.begin_scan();
 
         // Scan all reported NVMe paths on system and check for connectivity.
         for ctrlr in path_collection.get_entries() {
             match Subsystem::new(ctrlr.path()) {
                 Ok(subsystem) => {
+                    self.stats
+                        .record(ctrlr.path(), &subsystem.nqn, subsystem.state.as_str());
+
                     let existing_record = match subsystem.state.as_str() {
                         "connecting" => {
                             // Add a new record in case no record exists for target NQN.
@@ -160,6 +174,8 @@ impl PathFailureDetector {
             }
         }
 
+        self.stats.end_scan();
+
         // Remove all existing records that don't have underlying NVMe controllers:
         // can happen in case controller was removed after it had been identified as
         // broken/suspected. Stalled/outdated records have a different (old) epoch number
@@ -180,6 +196,18 @@ impl PathFailureDetector {
         }
     }
 
+    /// Report the path statistics of the latest rescan to HA Cluster agent.
+    /// Reports are not retransmitted as the next one supersedes them.
+    fn report_path_stats(&self) {
+        let req = ReportPathStats::new(self.node_name.clone(), self.stats.stats());
+
+        tokio::spawn(async move {
+            if let Err(e) = cluster_agent_client().report_path_stats(&req).await {
+                tracing::error!("Failed to report NVMe path statistics: {}", e);
+            }
+        });
+    }
+
     /// Start NVMe path error detection loop.
     pub async fn start(mut self) -> anyhow::Result<()> {
         let mut path_provider = CachedNvmePathProvider::new();
@@ -188,10 +216,14 @@ impl PathFailureDetector {
         tokio::pin!(start);
 
         tracing::info!(
-            "Starting NVMe path error detection loop, path detection interval: {:?}",
+            "Starting NVMe path error detection loop, path detection interval: {:?}, statistics report interval: {:?}",
             self.detection_period,
+            self.stats_report_period,
         );
 
+        let mut stats_report = interval(self.stats_report_period);
+        stats_report.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
         loop {
             tokio::select! {
                 _ = &mut start => {
@@ -199,6 +231,7 @@ impl PathFailureDetector {
                     break;
                 },
                 _ = sleep(self.detection_period) => self.rescan_paths(&mut path_collection),
+                _ = stats_report.tick() => self.report_path_stats(),
             }
         }
 
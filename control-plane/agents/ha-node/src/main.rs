@@ -3,16 +3,18 @@ use grpc::operations::ha_node::{client::ClusterAgentClient, traits::ClusterAgent
 use http::Uri;
 use once_cell::sync::OnceCell;
 use opentelemetry::KeyValue;
+use std::time::Duration;
 use structopt::StructOpt;
 use utils::{
     package_description, version_info_str, DEFAULT_CLUSTER_AGENT_CLIENT_ADDR,
     DEFAULT_NODE_AGENT_SERVER_ADDR, NVME_PATH_AGGREGATION_PERIOD, NVME_PATH_CHECK_PERIOD,
-    NVME_PATH_RETRANSMISSION_PERIOD,
+    NVME_PATH_RETRANSMISSION_PERIOD, NVME_PATH_STATS_REPORT_PERIOD,
 };
 
 mod detector;
 mod path_provider;
 mod reporter;
+mod stats;
 
 use detector::PathFailureDetector;
 
@@ -48,6 +50,10 @@ pub(crate) struct Cli {
     #[structopt(short, long, env = "AGGREGATION_PERIOD", default_value = NVME_PATH_AGGREGATION_PERIOD)]
     aggregation_period: humantime::Duration,
 
+    /// Period for reporting the NVMe path statistics.
+    #[structopt(long, env = "STATS_REPORT_PERIOD", default_value = NVME_PATH_STATS_REPORT_PERIOD)]
+    stats_report_period: humantime::Duration,
+
     /// Trace rest requests to the Jaeger endpoint agent.
     #[structopt(long, short)]
     jaeger: Option<String>,
@@ -67,6 +73,33 @@ impl Cli {
     }
 }
 
+/// Register the node-agent with the cluster-agent and keep renewing the registration at the
+/// heartbeat interval which the cluster-agent expects.
+/// When the cluster-agent can't be reached, the registration is retried after the `retry_period`.
+async fn keep_registered(node_agent: NodeAgentInfo, retry_period: Duration) {
+    loop {
+        let period = match cluster_agent_client().register(&node_agent).await {
+            Ok(registration) if registration.heartbeat_interval().is_zero() => {
+                // a cluster-agent which predates the heartbeats doesn't expect any
+                tracing::info!("HA Node agent registered, the registration does not expire");
+                return;
+            }
+            Ok(registration) => {
+                tracing::trace!(?registration, "HA Node agent registration renewed");
+                registration.heartbeat_interval()
+            }
+            Err(e) => {
+                tracing::error!(
+                    "Failed to register HA Node agent in Cluster HA agent: {:?}",
+                    e
+                );
+                retry_period
+            }
+        };
+        tokio::time::sleep(period).await;
+    }
+}
+
 #[tokio::main]
 async fn main() {
     let cli_args = Cli::args();
@@ -84,24 +117,20 @@ async fn main() {
         .ok()
         .expect("Expect to be initialized only once");
 
-    if let Err(e) = cluster_agent_client()
-        .register(&NodeAgentInfo::new(
-            cli_args.node_name.clone(),
-            cli_args
-                .grpc_endpoint
-                .authority()
-                .unwrap()
-                .to_string()
-                .parse()
-                .unwrap(),
-        ))
-        .await
-    {
-        tracing::error!(
-            "Failed to register HA Node agent in Cluster HA agent: {:?}",
-            e
-        );
-    }
+    let node_agent = NodeAgentInfo::new(
+        cli_args.node_name.clone(),
+        cli_args
+            .grpc_endpoint
+            .authority()
+            .unwrap()
+            .to_string()
+            .parse()
+            .unwrap(),
+    );
+    tokio::spawn(keep_registered(
+        node_agent,
+        cli_args.retransmission_period.into(),
+    ));
 
     // Instantiate path failure detector.
     let detector =
@@ -0,0 +1,91 @@
+use common_lib::types::v0::transport::{PathStats, TargetPathStats};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+/// Reconnect counters of an NVMe target, accumulated since the agent started.
+#[derive(Debug, Default, Clone, Copy)]
+struct ReconnectCounters {
+    reconnects: u64,
+    total_latency: Duration,
+}
+
+/// Collects the NVMe path statistics of each target during the path rescans.
+/// Path counts reflect the latest rescan, whilst reconnects and their latency are accumulated
+/// over the lifetime of the agent, so a lost report doesn't lose any reconnects.
+#[derive(Debug, Default)]
+pub(crate) struct PathStatsCollector {
+    /// Path counts of each target NQN from the latest rescan.
+    paths: HashMap<String, PathStats>,
+    /// Path counts of each target NQN of the rescan in progress.
+    scanning: HashMap<String, PathStats>,
+    /// Reconnect counters of each target NQN.
+    reconnects: HashMap<String, ReconnectCounters>,
+    /// Paths which are currently reconnecting, along with when the reconnect was noticed.
+    connecting: HashMap<PathBuf, Instant>,
+    /// Paths seen during the rescan in progress.
+    seen: Vec<PathBuf>,
+}
+
+impl PathStatsCollector {
+    /// Start a new rescan of all the paths.
+    pub(crate) fn begin_scan(&mut self) {
+        self.scanning.clear();
+        self.seen.clear();
+    }
+
+    /// Record the `state` of the NVMe controller `path` which is connected to the target `nqn`.
+    pub(crate) fn record(&mut self, path: &Path, nqn: &str, state: &str) {
+        let stats = self.scanning.entry(nqn.to_string()).or_default();
+        stats.total_paths += 1;
+        self.seen.push(path.to_path_buf());
+
+        match state {
+            "live" => {
+                stats.live_paths += 1;
+                if let Some(since) = self.connecting.remove(path) {
+                    let counters = self.reconnects.entry(nqn.to_string()).or_default();
+                    counters.reconnects += 1;
+                    counters.total_latency += since.elapsed();
+                }
+            }
+            _ => {
+                stats.failed_paths += 1;
+                if state == "connecting" {
+                    self.connecting
+                        .entry(path.to_path_buf())
+                        .or_insert_with(Instant::now);
+                }
+            }
+        }
+    }
+
+    /// Complete the rescan in progress, forgetting about the paths which no longer exist.
+    pub(crate) fn end_scan(&mut self) {
+        let seen = std::mem::take(&mut self.seen);
+        self.connecting.retain(|path, _| seen.contains(path));
+        self.paths = std::mem::take(&mut self.scanning);
+        let paths = &self.paths;
+        self.reconnects.retain(|nqn, _| paths.contains_key(nqn));
+    }
+
+    /// Get the statistics of all the targets from the latest rescan.
+    pub(crate) fn stats(&self) -> Vec<TargetPathStats> {
+        self.paths
+            .iter()
+            .map(|(nqn, paths)| {
+                let mut stats = *paths;
+                if let Some(counters) = self.reconnects.get(nqn) {
+                    stats.reconnects = counters.reconnects;
+                    stats.mean_reconnect_latency = counters
+                        .total_latency
+                        .checked_div(counters.reconnects as u32)
+                        .unwrap_or_default();
+                }
+                TargetPathStats::new(nqn.clone(), stats)
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,116 @@
+use openapi::{
+    apis::{StatusCode, Uuid},
+    clients::tower::{self, ResponseError},
+    models::{rest_json_error::Kind as RestJsonErrorKind, RestJsonError, VolumeStatus},
+};
+use snafu::Snafu;
+use std::time::Duration;
+
+/// Error returned by the operations of the `Client`
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(crate)), context(suffix(false)))]
+#[allow(missing_docs)]
+pub enum Error {
+    #[snafu(display("Failed to send the request to the REST server: {}", details))]
+    Request { details: String },
+    #[snafu(display(
+        "The REST server failed the request with '{:?}' ({}): {}, {}",
+        kind,
+        status,
+        message,
+        details
+    ))]
+    Api {
+        status: StatusCode,
+        kind: RestJsonErrorKind,
+        message: String,
+        details: String,
+    },
+    #[snafu(display("Invalid response from the REST server ({}): {}", status, details))]
+    InvalidResponse { status: StatusCode, details: String },
+    #[snafu(display("Invalid REST server url '{}': {}", url, details))]
+    InvalidUrl { url: String, details: String },
+    #[snafu(display(
+        "Timed out after '{:?}' waiting for volume '{}' to be online, last status: '{:?}'",
+        timeout,
+        volume_id,
+        status
+    ))]
+    VolumeNotOnline {
+        volume_id: Uuid,
+        timeout: Duration,
+        status: VolumeStatus,
+    },
+}
+
+impl Error {
+    /// The kind of the error reported by the REST server, if the request reached it.
+    pub fn kind(&self) -> Option<&RestJsonErrorKind> {
+        match self {
+            Self::Api { kind, .. } => Some(kind),
+            _ => None,
+        }
+    }
+    /// The http status of the response, if one was received.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Api { status, .. } | Self::InvalidResponse { status, .. } => Some(*status),
+            _ => None,
+        }
+    }
+    /// Check whether the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        matches!(self.kind(), Some(RestJsonErrorKind::NotFound))
+    }
+    /// Check whether the error is likely to go away by itself, making the request worth
+    /// retrying, eg: the server is not reachable, or the resource is busy with another
+    /// operation.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Request { .. } => true,
+            Self::Api { kind, .. } => matches!(
+                kind,
+                RestJsonErrorKind::Timeout
+                    | RestJsonErrorKind::DeadlineExceeded
+                    | RestJsonErrorKind::Unavailable
+                    | RestJsonErrorKind::Aborted
+                    | RestJsonErrorKind::Conflict
+            ),
+            Self::InvalidResponse { status, .. } => matches!(
+                *status,
+                StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            Self::InvalidUrl { .. } | Self::VolumeNotOnline { .. } => false,
+        }
+    }
+}
+
+impl From<tower::Error<RestJsonError>> for Error {
+    fn from(error: tower::Error<RestJsonError>) -> Self {
+        match error {
+            tower::Error::Request(request) => Self::Request {
+                details: request.to_string(),
+            },
+            tower::Error::Response(response) => {
+                let status = response.status();
+                match response {
+                    ResponseError::Expected(content) => {
+                        let error = content.into_body();
+                        Self::Api {
+                            status,
+                            kind: error.kind,
+                            message: error.message,
+                            details: error.details,
+                        }
+                    }
+                    response => Self::InvalidResponse {
+                        status,
+                        details: response.to_string(),
+                    },
+                }
+            }
+        }
+    }
+}
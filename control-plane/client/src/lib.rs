@@ -0,0 +1,357 @@
+#![warn(missing_docs)]
+//! Typed high-level client of the control plane REST API.
+//!
+//! It wraps the autogenerated openapi client, offering the common volume operations with the
+//! transient errors retried and the error replies of the REST server mapped into the typed
+//! [`Error`].
+//!
+//! ```no_run
+//! use control_plane_client::{Client, CreateVolumeRequest, VolumeFilter};
+//! use openapi::models::VolumeShareProtocol;
+//! use std::time::Duration;
+//!
+//! # async fn example() -> Result<(), control_plane_client::Error> {
+//! let client = Client::builder("http://localhost:8081").build()?;
+//!
+//! let request = CreateVolumeRequest::new(10 * 1024 * 1024)
+//!     .with_replicas(2)
+//!     .with_label("tenant", "acme");
+//! let volume = client.create_volume(request).await?;
+//! client
+//!     .publish(&volume.spec.uuid, "io-engine-1", VolumeShareProtocol::Nvmf)
+//!     .await?;
+//! let volume = client
+//!     .wait_volume_online(&volume.spec.uuid, Duration::from_secs(30))
+//!     .await?;
+//!
+//! let volumes = client
+//!     .list_volumes(VolumeFilter::all().with_label("tenant", "acme"))
+//!     .await?;
+//! assert_eq!(volumes, vec![volume]);
+//! # Ok(())
+//! # }
+//! ```
+
+mod error;
+mod volume;
+
+pub use error::Error;
+pub use volume::{CreateVolumeRequest, VolumeFilter};
+
+pub use openapi::models::rest_json_error::Kind as RestJsonErrorKind;
+
+use openapi::{
+    apis::{StatusCode, Uuid},
+    clients::tower::{self, Configuration, Url},
+    models,
+    tower::client::direct::ApiClient,
+};
+use std::{future::Future, time::Duration};
+
+/// How the requests which fail with a transient error are retried.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_retries: u32,
+    interval: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            interval: Duration::from_millis(500),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Policy which never retries the requests.
+    pub fn none() -> Self {
+        Self::default().with_max_retries(0)
+    }
+    /// Retry each request at most `max_retries` times.
+    #[must_use]
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+    /// Wait for `interval` before each retry.
+    #[must_use]
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// Builder of the `Client`.
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    url: String,
+    timeout: Duration,
+    bearer_token: Option<String>,
+    ca_certificate: Option<Vec<u8>>,
+    trace: bool,
+    retry: RetryPolicy,
+    poll_period: Duration,
+}
+
+impl ClientBuilder {
+    /// Timeout of each request.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+    /// Authenticate the requests with the given bearer token.
+    #[must_use]
+    pub fn with_bearer_token(mut self, token: &str) -> Self {
+        self.bearer_token = Some(token.to_string());
+        self
+    }
+    /// Certificate of the authority which signed the certificate of the https REST server.
+    #[must_use]
+    pub fn with_ca_certificate(mut self, certificate: &[u8]) -> Self {
+        self.ca_certificate = Some(certificate.to_vec());
+        self
+    }
+    /// Trace the requests with opentelemetry.
+    #[must_use]
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+    /// How the requests which fail with a transient error are retried.
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    /// How often a resource is polled whilst waiting for it to reach a state.
+    #[must_use]
+    pub fn with_poll_period(mut self, period: Duration) -> Self {
+        self.poll_period = period;
+        self
+    }
+    /// Build the `Client`.
+    pub fn build(self) -> Result<Client, Error> {
+        let invalid_url = |details: String| Error::InvalidUrl {
+            url: self.url.clone(),
+            details,
+        };
+        let url = self
+            .url
+            .parse::<Url>()
+            .map_err(|error| invalid_url(error.to_string()))?;
+        let config = Configuration::new(
+            url,
+            self.timeout,
+            self.bearer_token.clone(),
+            self.ca_certificate.as_deref(),
+            self.trace,
+        )
+        .map_err(|error| invalid_url(format!("{:?}", error)))?;
+        Ok(Client {
+            api: ApiClient::new(config),
+            retry: self.retry,
+            poll_period: self.poll_period,
+        })
+    }
+}
+
+/// Typed high-level client of the control plane REST API.
+#[derive(Clone)]
+pub struct Client {
+    api: ApiClient,
+    retry: RetryPolicy,
+    poll_period: Duration,
+}
+
+impl From<ApiClient> for Client {
+    fn from(api: ApiClient) -> Self {
+        Self {
+            api,
+            retry: RetryPolicy::default(),
+            poll_period: Duration::from_millis(500),
+        }
+    }
+}
+
+impl Client {
+    /// Builder of a client of the REST server at `url`, eg: "http://localhost:8081".
+    pub fn builder(url: &str) -> ClientBuilder {
+        ClientBuilder {
+            url: url.to_string(),
+            timeout: Duration::from_secs(5),
+            bearer_token: None,
+            ca_certificate: None,
+            trace: false,
+            retry: RetryPolicy::default(),
+            poll_period: Duration::from_millis(500),
+        }
+    }
+    /// Use the given `retry` policy from now on.
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+    /// The autogenerated openapi client, for the operations not covered by this client.
+    pub fn api(&self) -> &ApiClient {
+        &self.api
+    }
+
+    /// Create a volume, returning it once created.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, request), fields(volume.uuid = %request.uuid()), err))]
+    pub async fn create_volume(
+        &self,
+        request: CreateVolumeRequest,
+    ) -> Result<models::Volume, Error> {
+        let volumes = self.api.volumes_api();
+        let uuid = request.uuid();
+        self.retry(|attempt| {
+            let body = request.body().clone();
+            async move {
+                match volumes.put_volume(uuid, body).await {
+                    // a previous attempt may have created the volume, despite not getting a reply
+                    Err(error)
+                        if attempt > 0
+                            && Self::has_status(&error, StatusCode::UNPROCESSABLE_ENTITY) =>
+                    {
+                        volumes.get_volume(uuid).await
+                    }
+                    result => result,
+                }
+            }
+        })
+        .await
+    }
+
+    /// Get the volume `uuid`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, uuid), fields(volume.uuid = %uuid), err))]
+    pub async fn get_volume(&self, uuid: &Uuid) -> Result<models::Volume, Error> {
+        let volumes = self.api.volumes_api();
+        self.retry(|_| volumes.get_volume(uuid)).await
+    }
+
+    /// Delete the volume `uuid`, which is not an error if it does not exist.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, uuid), fields(volume.uuid = %uuid), err))]
+    pub async fn delete_volume(&self, uuid: &Uuid) -> Result<(), Error> {
+        let volumes = self.api.volumes_api();
+        match self.retry(|_| volumes.del_volume(uuid)).await {
+            Err(error) if error.is_not_found() => Ok(()),
+            result => result.map(|_| ()),
+        }
+    }
+
+    /// List the volumes which match the `filter`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), err))]
+    pub async fn list_volumes(&self, filter: VolumeFilter) -> Result<Vec<models::Volume>, Error> {
+        const PAGE_SIZE: isize = 500;
+        let volumes_api = self.api.volumes_api();
+        let mut volumes = vec![];
+        let mut next_token = Some(0);
+        while let Some(token) = next_token {
+            let page = self
+                .retry(|_| volumes_api.get_volumes(PAGE_SIZE, Some(token), None, None))
+                .await?;
+            volumes.extend(page.entries.into_iter().filter(|v| filter.matches(v)));
+            next_token = page.next_token;
+        }
+        Ok(volumes)
+    }
+
+    /// Publish the volume `uuid` on the `node` using the `protocol`, returning it once
+    /// published.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, uuid), fields(volume.uuid = %uuid), err))]
+    pub async fn publish(
+        &self,
+        uuid: &Uuid,
+        node: &str,
+        protocol: models::VolumeShareProtocol,
+    ) -> Result<models::Volume, Error> {
+        let volumes = self.api.volumes_api();
+        self.retry(|attempt| async move {
+            match volumes
+                .put_volume_target(uuid, node, protocol, None, None)
+                .await
+            {
+                // a previous attempt may have published the volume, despite not getting a reply
+                Err(error)
+                    if attempt > 0 && Self::has_status(&error, StatusCode::PRECONDITION_FAILED) =>
+                {
+                    let volume = volumes.get_volume(uuid).await?;
+                    match &volume.spec.target {
+                        Some(target) if target.node == node => Ok(volume),
+                        _ => Err(error),
+                    }
+                }
+                result => result,
+            }
+        })
+        .await
+    }
+
+    /// Unpublish the volume `uuid`, forcing it if its target node is not reachable.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, uuid), fields(volume.uuid = %uuid), err))]
+    pub async fn unpublish(&self, uuid: &Uuid, force: bool) -> Result<models::Volume, Error> {
+        let volumes = self.api.volumes_api();
+        self.retry(|_| volumes.del_volume_target(uuid, Some(force), None))
+            .await
+    }
+
+    /// Wait until the volume `uuid` is online, returning it, or fail if it's not online
+    /// within the `timeout`.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, uuid), fields(volume.uuid = %uuid), err))]
+    pub async fn wait_volume_online(
+        &self,
+        uuid: &Uuid,
+        timeout: Duration,
+    ) -> Result<models::Volume, Error> {
+        let start = std::time::Instant::now();
+        loop {
+            let volume = self.get_volume(uuid).await?;
+            if volume.state.status == models::VolumeStatus::Online {
+                return Ok(volume);
+            }
+            if start.elapsed() >= timeout {
+                return Err(Error::VolumeNotOnline {
+                    volume_id: *uuid,
+                    timeout,
+                    status: volume.state.status,
+                });
+            }
+            tokio::time::sleep(self.poll_period).await;
+        }
+    }
+
+    /// Issue the request built by `request`, retrying it as per the retry policy if it fails
+    /// with a transient error. `request` is given the number of the attempt, 0 for the first.
+    async fn retry<T, F, Fut>(&self, request: F) -> Result<T, Error>
+    where
+        F: Fn(u32) -> Fut,
+        Fut: Future<Output = Result<T, tower::Error<models::RestJsonError>>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match request(attempt).await.map_err(Error::from) {
+                Err(error) if error.is_transient() && attempt < self.retry.max_retries => {
+                    attempt += 1;
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(%error, attempt, "Retrying the request after a transient error");
+                    #[cfg(not(feature = "tracing"))]
+                    let _ = error;
+                    tokio::time::sleep(self.retry.interval).await;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Check whether the `error` is a reply of the REST server with the given `status`.
+    fn has_status(error: &tower::Error<models::RestJsonError>, status: StatusCode) -> bool {
+        match error {
+            tower::Error::Response(response) => response.status() == status,
+            tower::Error::Request(_) => false,
+        }
+    }
+}
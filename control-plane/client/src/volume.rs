@@ -0,0 +1,123 @@
+use openapi::{apis::Uuid, models};
+use std::collections::HashMap;
+
+/// Request to create a volume, with the cluster defaults used for any setting which is not
+/// specified.
+#[derive(Debug, Clone)]
+pub struct CreateVolumeRequest {
+    uuid: Uuid,
+    body: models::CreateVolumeBody,
+}
+
+impl CreateVolumeRequest {
+    /// New request to create a self-healing volume of `size` bytes with a random uuid.
+    pub fn new(size: u64) -> Self {
+        Self {
+            uuid: Uuid::new_v4(),
+            body: models::CreateVolumeBody::new(models::VolumePolicy::new(true), size),
+        }
+    }
+    /// Use the given `uuid` for the volume.
+    #[must_use]
+    pub fn with_uuid(mut self, uuid: Uuid) -> Self {
+        self.uuid = uuid;
+        self
+    }
+    /// Number of replicas of the volume.
+    #[must_use]
+    pub fn with_replicas(mut self, replicas: u8) -> Self {
+        self.body.replicas = Some(replicas);
+        self
+    }
+    /// Whether the volume is thin provisioned.
+    #[must_use]
+    pub fn with_thin(mut self, thin: bool) -> Self {
+        self.body.thin = Some(thin);
+        self
+    }
+    /// Policy which determines if and how the replicas are replaced.
+    #[must_use]
+    pub fn with_policy(mut self, policy: models::VolumePolicy) -> Self {
+        self.body.policy = policy;
+        self
+    }
+    /// Topology which determines where the replicas are placed.
+    #[must_use]
+    pub fn with_topology(mut self, topology: models::Topology) -> Self {
+        self.body.topology = Some(topology);
+        self
+    }
+    /// What to do when not all of the replicas can be placed.
+    #[must_use]
+    pub fn with_placement(mut self, placement: models::VolumePlacementPolicy) -> Self {
+        self.body.placement = Some(placement);
+        self
+    }
+    /// Add the label `key`=`value` to the volume.
+    #[must_use]
+    pub fn with_label(mut self, key: &str, value: &str) -> Self {
+        self.body
+            .labels
+            .get_or_insert_with(HashMap::new)
+            .insert(key.to_string(), value.to_string());
+        self
+    }
+    /// The uuid of the volume.
+    pub fn uuid(&self) -> &Uuid {
+        &self.uuid
+    }
+    /// The body of the request.
+    pub(crate) fn body(&self) -> &models::CreateVolumeBody {
+        &self.body
+    }
+}
+
+/// Filter of the volumes to be listed, matching all volumes unless narrowed down.
+#[derive(Debug, Clone, Default)]
+pub struct VolumeFilter {
+    labels: HashMap<String, String>,
+    status: Option<models::VolumeStatus>,
+    target_node: Option<String>,
+}
+
+impl VolumeFilter {
+    /// Filter which matches all volumes.
+    pub fn all() -> Self {
+        Self::default()
+    }
+    /// Only match the volumes with the label `key`=`value`.
+    #[must_use]
+    pub fn with_label(mut self, key: &str, value: &str) -> Self {
+        self.labels.insert(key.to_string(), value.to_string());
+        self
+    }
+    /// Only match the volumes with the given `status`.
+    #[must_use]
+    pub fn with_status(mut self, status: models::VolumeStatus) -> Self {
+        self.status = Some(status);
+        self
+    }
+    /// Only match the volumes published on the given `node`.
+    #[must_use]
+    pub fn with_target_node(mut self, node: &str) -> Self {
+        self.target_node = Some(node.to_string());
+        self
+    }
+    /// Check whether the `volume` matches the filter.
+    pub fn matches(&self, volume: &models::Volume) -> bool {
+        let labels = volume.spec.labels.as_ref();
+        let labelled = self
+            .labels
+            .iter()
+            .all(|(key, value)| labels.and_then(|labels| labels.get(key)) == Some(value));
+        let status = match &self.status {
+            Some(status) => &volume.state.status == status,
+            None => true,
+        };
+        let target_node = match &self.target_node {
+            Some(node) => volume.spec.target.as_ref().map(|target| &target.node) == Some(node),
+            None => true,
+        };
+        labelled && status && target_node
+    }
+}
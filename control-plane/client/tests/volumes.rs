@@ -0,0 +1,106 @@
+use control_plane_client::{CreateVolumeRequest, RestJsonErrorKind, RetryPolicy, VolumeFilter};
+use deployer_cluster::ClusterBuilder;
+use openapi::{apis::Uuid, models};
+use std::time::Duration;
+
+const SIZE: u64 = 5 * 1024 * 1024;
+
+#[tokio::test]
+async fn volumes() {
+    let cluster = ClusterBuilder::builder()
+        .with_io_engines(2)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+    let client = cluster.client();
+    let node = cluster.node(0);
+
+    let volume = client
+        .create_volume(
+            CreateVolumeRequest::new(SIZE)
+                .with_replicas(2)
+                .with_label("tenant", "acme"),
+        )
+        .await
+        .unwrap();
+    let uuid = volume.spec.uuid;
+    client
+        .create_volume(CreateVolumeRequest::new(SIZE).with_label("tenant", "other"))
+        .await
+        .unwrap();
+
+    let volume = client
+        .publish(&uuid, node.as_str(), models::VolumeShareProtocol::Nvmf)
+        .await
+        .unwrap();
+    assert_eq!(volume.spec.target.unwrap().node, node.as_str());
+    let volume = client
+        .wait_volume_online(&uuid, Duration::from_secs(10))
+        .await
+        .unwrap();
+
+    let acme = VolumeFilter::all().with_label("tenant", "acme");
+    assert_eq!(
+        client.list_volumes(acme.clone()).await.unwrap(),
+        vec![volume]
+    );
+    assert_eq!(
+        client
+            .list_volumes(VolumeFilter::all())
+            .await
+            .unwrap()
+            .len(),
+        2
+    );
+    let published = VolumeFilter::all().with_target_node(node.as_str());
+    assert_eq!(client.list_volumes(published).await.unwrap().len(), 1);
+
+    // the errors of the rest server are typed
+    let error = client
+        .create_volume(CreateVolumeRequest::new(SIZE).with_uuid(uuid))
+        .await
+        .unwrap_err();
+    assert_eq!(error.kind(), Some(&RestJsonErrorKind::AlreadyExists));
+    assert!(!error.is_transient());
+    let error = client.get_volume(&Uuid::new_v4()).await.unwrap_err();
+    assert!(error.is_not_found(), "{:?}", error);
+
+    client.unpublish(&uuid, false).await.unwrap();
+    client.delete_volume(&uuid).await.unwrap();
+    // deleting a volume which no longer exists is not an error
+    client.delete_volume(&uuid).await.unwrap();
+    assert!(client.list_volumes(acme).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn retries() {
+    let cluster = ClusterBuilder::builder()
+        .with_io_engines(1)
+        .with_pools(1)
+        .build()
+        .await
+        .unwrap();
+    let retry = RetryPolicy::default()
+        .with_max_retries(20)
+        .with_interval(Duration::from_millis(250));
+    let client = cluster.client().with_retry(retry);
+
+    // the request keeps being retried whilst the rest server is down
+    cluster.composer().stop("rest").await.unwrap();
+    let restart = async {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        cluster.composer().start("rest").await.unwrap();
+    };
+    let (volume, _) = tokio::join!(
+        client.create_volume(CreateVolumeRequest::new(SIZE)),
+        restart
+    );
+    let volume = volume.unwrap();
+
+    let client = cluster.client().with_retry(RetryPolicy::none());
+    cluster.composer().stop("rest").await.unwrap();
+    let error = client.get_volume(&volume.spec.uuid).await.unwrap_err();
+    assert!(error.is_transient(), "{:?}", error);
+    assert_eq!(error.kind(), None);
+}
@@ -136,7 +136,7 @@ impl IoEngineApiClient {
 
     /// List all pools available in IoEngine cluster.
     pub(crate) async fn list_pools(&self) -> Result<Vec<Pool>, ApiClientError> {
-        let response = self.rest_client.pools_api().get_pools().await?;
+        let response = self.rest_client.pools_api().get_pools(None, None).await?;
         Ok(response.into_body())
     }
 
@@ -160,7 +160,7 @@ impl IoEngineApiClient {
         let response = self
             .rest_client
             .volumes_api()
-            .get_volumes(max_entries, Some(starting_token))
+            .get_volumes(max_entries, Some(starting_token), None, None)
             .await?;
         Ok(response.into_body())
     }
@@ -184,16 +184,23 @@ impl IoEngineApiClient {
         _pinned_volume: bool,
         thin: bool,
     ) -> Result<Volume, ApiClientError> {
-        let topology =
-            Topology::new_all(volume_topology.node_topology, volume_topology.pool_topology);
+        let topology = Topology::new_all(
+            volume_topology.node_topology,
+            volume_topology.pool_topology,
+            None,
+            None,
+        );
 
         let req = CreateVolumeBody {
-            replicas,
+            replicas: Some(replicas),
             size,
-            thin,
+            thin: Some(thin),
             topology: Some(topology),
-            policy: VolumePolicy::new_all(true),
+            policy: VolumePolicy::new(true),
             labels: None,
+            placement: None,
+            pools: None,
+            allow_same_node: None,
         };
 
         let result = self
@@ -263,7 +270,7 @@ impl IoEngineApiClient {
         Self::delete_idempotent(
             self.rest_client
                 .volumes_api()
-                .del_volume_target(volume_id, Some(force))
+                .del_volume_target(volume_id, Some(force), None)
                 .await,
             true,
         )?;
@@ -282,7 +289,7 @@ impl IoEngineApiClient {
         let volume = self
             .rest_client
             .volumes_api()
-            .put_volume_target(volume_id, node, protocol)
+            .put_volume_target(volume_id, node, protocol, None, None)
             .await?;
         Ok(volume.into_body())
     }
@@ -5,14 +5,30 @@ use common_lib::{
     types::v0::transport::MessageIdVs,
 };
 use opentelemetry::trace::FutureExt;
-use std::time::Duration;
+use std::{future::Future, time::Duration};
 use tonic::{
+    metadata::MetadataValue,
     transport::{Channel, Uri},
     IntoRequest,
 };
 
 use utils::DEFAULT_REQ_TIMEOUT;
 
+/// gRPC metadata key with the tenant on behalf of which a request is made.
+pub const TENANT_KEY: &str = "x-tenant";
+/// gRPC metadata key which marks a request made by the control-plane itself.
+pub const INTERNAL_CALLER_KEY: &str = "x-internal-caller";
+
+tokio::task_local! {
+    static TENANT: Option<String>;
+}
+
+/// Run the `future` on behalf of the `tenant`, which is then sent along with each of the requests
+/// it makes.
+pub async fn with_tenant<F: Future>(tenant: Option<String>, future: F) -> F::Output {
+    TENANT.scope(tenant, future).await
+}
+
 /// Request specific minimum timeouts
 /// zeroing replicas on create/destroy takes some time (observed up to 7seconds)
 /// nexus creation by itself can take up to 4 seconds... it can take even longer if etcd is not up
@@ -180,11 +196,21 @@ impl<C: Clone> Client<C> {
         op_id: M,
     ) -> tonic::Request<T> {
         let timeout_opts = self.context.timeout_opts.clone().unwrap_or_default();
+        let internal = timeout_opts.client().internal();
         let timeout = context
             .map(|c| c.base_timeout())
             .unwrap_or_else(|| timeout_grpc(op_id.into(), timeout_opts));
         let mut request = request.into().into_request();
         request.set_timeout(timeout);
+        if internal {
+            request
+                .metadata_mut()
+                .insert(INTERNAL_CALLER_KEY, MetadataValue::from_static("true"));
+        }
+        let tenant = TENANT.try_with(Clone::clone).ok().flatten();
+        if let Some(tenant) = tenant.and_then(|tenant| MetadataValue::try_from(tenant).ok()) {
+            request.metadata_mut().insert(TENANT_KEY, tenant);
+        }
         request
     }
     /// Returns a new client.
@@ -3,6 +3,8 @@ pub mod context;
 pub mod misc;
 /// All server, client implementations and the traits
 pub mod operations;
+/// Rate limiting of the gRPC requests of each caller
+pub mod rate_limit;
 pub mod tracing;
 
 /// Common module for all the misc operations
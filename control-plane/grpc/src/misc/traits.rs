@@ -1,5 +1,11 @@
 use crate::common;
-use common_lib::transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::v0::{
+        store::{epoch_secs, from_epoch_secs},
+        transport::{ConditionStatus, ResourceCondition},
+    },
+};
 
 /// Trait to validate the Grpc type by an intermediate conversion
 pub trait ValidateRequestTypes {
@@ -84,6 +90,9 @@ impl From<ReplyErrorKind> for common::ReplyErrorKind {
             ReplyErrorKind::ReplicaCreateNumber => Self::ReplicaCreateNumber,
             ReplyErrorKind::VolumeNoReplicas => Self::VolumeNoReplicas,
             ReplyErrorKind::InUse => Self::InUse,
+            ReplyErrorKind::LastHealthyReplica => Self::LastHealthyReplica,
+            ReplyErrorKind::BelowMinReplicationFactor => Self::BelowMinReplicationFactor,
+            ReplyErrorKind::RateLimited => Self::RateLimited,
         }
     }
 }
@@ -121,6 +130,9 @@ impl From<common::ReplyErrorKind> for ReplyErrorKind {
             common::ReplyErrorKind::ReplicaCreateNumber => Self::ReplicaCreateNumber,
             common::ReplyErrorKind::VolumeNoReplicas => Self::VolumeNoReplicas,
             common::ReplyErrorKind::InUse => Self::InUse,
+            common::ReplyErrorKind::LastHealthyReplica => Self::LastHealthyReplica,
+            common::ReplyErrorKind::BelowMinReplicationFactor => Self::BelowMinReplicationFactor,
+            common::ReplyErrorKind::RateLimited => Self::RateLimited,
         }
     }
 }
@@ -156,3 +168,37 @@ impl From<crate::common::ReplyError> for ReplyError {
 /// A newtype that is similar to a google StringValue generated code
 /// for simpler conversion to uuids
 pub struct StringValue(pub Option<String>);
+
+impl From<ResourceCondition> for common::ResourceCondition {
+    fn from(condition: ResourceCondition) -> Self {
+        let status = match condition.status {
+            ConditionStatus::True => common::ConditionStatus::ConditionTrue,
+            ConditionStatus::False => common::ConditionStatus::ConditionFalse,
+            ConditionStatus::Unknown => common::ConditionStatus::ConditionUnknown,
+        };
+        Self {
+            r#type: condition.kind,
+            status: status as i32,
+            reason: condition.reason,
+            message: condition.message,
+            last_transition_time: epoch_secs(condition.last_transition_time),
+        }
+    }
+}
+
+impl From<common::ResourceCondition> for ResourceCondition {
+    fn from(condition: common::ResourceCondition) -> Self {
+        let status = match common::ConditionStatus::from_i32(condition.status) {
+            Some(common::ConditionStatus::ConditionTrue) => ConditionStatus::True,
+            Some(common::ConditionStatus::ConditionFalse) => ConditionStatus::False,
+            Some(common::ConditionStatus::ConditionUnknown) | None => ConditionStatus::Unknown,
+        };
+        Self {
+            kind: condition.r#type,
+            status,
+            reason: condition.reason,
+            message: condition.message,
+            last_transition_time: from_epoch_secs(condition.last_transition_time),
+        }
+    }
+}
@@ -1,13 +1,16 @@
 use crate::{
     context::{Client, TracedChannel},
-    ha_cluster_agent::{ha_rpc_client::HaRpcClient, HaNodeInfo},
-    operations::ha_node::traits::{ClusterAgentOperations, NodeInfo, ReportFailedPathsInfo},
+    ha_cluster_agent::{get_path_stats_reply, ha_rpc_client::HaRpcClient, HaNodeInfo},
+    operations::ha_node::traits::{
+        ClusterAgentOperations, GetPathStatsInfo, NodeInfo, ReportFailedPathsInfo,
+        ReportPathStatsInfo,
+    },
 };
 use common_lib::{
-    transport_api::{ReplyError, TimeoutOptions},
-    types::v0::transport::MessageIdVs,
+    transport_api::{ReplyError, ResourceKind, TimeoutOptions},
+    types::v0::transport::{cluster_agent::NodeAgentRegistration, MessageIdVs, VolumePathStats},
 };
-use std::ops::Deref;
+use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
 
 /// Cluster-Agent RPC client
@@ -38,8 +41,8 @@ impl ClusterAgentOperations for ClusterAgentClient {
         skip(self),
         err
     )]
-    async fn register(&self, request: &dyn NodeInfo) -> Result<(), ReplyError> {
-        let _response = self
+    async fn register(&self, request: &dyn NodeInfo) -> Result<NodeAgentRegistration, ReplyError> {
+        let response = self
             .client()
             .register_node_agent(HaNodeInfo {
                 nodename: request.node(),
@@ -47,7 +50,7 @@ impl ClusterAgentOperations for ClusterAgentClient {
             })
             .await?;
         tracing::trace!("node agent successfully registered");
-        Ok(())
+        Ok(response.into_inner().into())
     }
 
     #[tracing::instrument(
@@ -67,4 +70,54 @@ impl ClusterAgentOperations for ClusterAgentClient {
             Err(e) => Err(e.into()),
         }
     }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::report_path_stats",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    /// Report NVMe path statistics.
+    async fn report_path_stats(
+        &self,
+        request: &dyn ReportPathStatsInfo,
+    ) -> Result<u32, ReplyError> {
+        let req = self.request(request, None, MessageIdVs::ReportPathStats);
+        let response = self.client().report_path_stats(req).await?.into_inner();
+        Ok(response.ignored)
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::get_path_stats",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    /// Get the aggregated NVMe path statistics of a volume.
+    async fn get_path_stats(
+        &self,
+        request: &dyn GetPathStatsInfo,
+    ) -> Result<VolumePathStats, ReplyError> {
+        let req = self.request(request, None, MessageIdVs::GetPathStats);
+        let response = self.client().get_path_stats(req).await?.into_inner();
+        match response.reply {
+            Some(get_path_stats_reply::Reply::Stats(stats)) => {
+                Ok(VolumePathStats::try_from(stats)?)
+            }
+            Some(get_path_stats_reply::Reply::Error(err)) => Err(err.into()),
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "ClusterAgentClient::get_registrations",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    /// Get the number of node-agents whose registration has not expired.
+    async fn get_registrations(&self) -> Result<u32, ReplyError> {
+        let response = self.client().get_registrations(()).await?.into_inner();
+        Ok(response.registered)
+    }
 }
@@ -2,12 +2,16 @@ use tonic::{Response, Status};
 
 use crate::{
     ha_cluster_agent::{
+        get_path_stats_reply,
         ha_rpc_server::{HaRpc, HaRpcServer},
-        HaNodeInfo, ReportFailedNvmePathsRequest,
+        GetPathStatsReply, GetPathStatsRequest, GetRegistrationsReply, HaNodeInfo,
+        RegisterNodeAgentReply, ReportFailedNvmePathsRequest, ReportPathStatsReply,
+        ReportPathStatsRequest,
     },
-    operations::ha_node::traits::ClusterAgentOperations,
+    operations::ha_node::traits::{ClusterAgentOperations, ReportFailedPathsInfo},
 };
-use std::sync::Arc;
+use common_lib::types::v0::transport::{GetPathStats, ReportFailedPaths};
+use std::{convert::TryFrom, sync::Arc};
 
 /// RPC cluster-agent server
 pub struct ClusterAgentServer {
@@ -31,10 +35,10 @@ impl HaRpc for ClusterAgentServer {
     async fn register_node_agent(
         &self,
         request: tonic::Request<HaNodeInfo>,
-    ) -> Result<tonic::Response<()>, tonic::Status> {
+    ) -> Result<tonic::Response<RegisterNodeAgentReply>, tonic::Status> {
         let nodeinfo = request.into_inner();
         match self.service.register(&nodeinfo).await {
-            Ok(_) => Ok(Response::new(())),
+            Ok(registration) => Ok(Response::new(registration.into())),
             Err(err) => Err(Status::internal(format!(
                 "Failed to register node-agent: {:?}",
                 err
@@ -43,10 +47,69 @@ impl HaRpc for ClusterAgentServer {
     }
     async fn report_failed_nvme_paths(
         &self,
-        _request: tonic::Request<ReportFailedNvmePathsRequest>,
+        request: tonic::Request<ReportFailedNvmePathsRequest>,
     ) -> Result<tonic::Response<()>, tonic::Status> {
-        Err(Status::unimplemented(
-            "NVMe path reporting is not yet implemented",
-        ))
+        let req = request.into_inner();
+        // the same target may be reported by several of the paths of the node
+        let report = ReportFailedPaths::new(req.node(), req.failed_paths()).dedup();
+        if report.failed_paths().is_empty() {
+            return Err(Status::invalid_argument(
+                "No failed NVMe paths were reported",
+            ));
+        }
+        match self.service.report_failed_nvme_paths(&report).await {
+            Ok(()) => Ok(Response::new(())),
+            Err(err) => Err(Status::internal(format!(
+                "Failed to report failed NVMe paths: {:?}",
+                err
+            ))),
+        }
+    }
+
+    async fn report_path_stats(
+        &self,
+        request: tonic::Request<ReportPathStatsRequest>,
+    ) -> Result<tonic::Response<ReportPathStatsReply>, tonic::Status> {
+        let req = request.into_inner();
+        match self.service.report_path_stats(&req).await {
+            Ok(ignored) => Ok(Response::new(ReportPathStatsReply { ignored })),
+            Err(err) => Err(Status::internal(format!(
+                "Failed to report path statistics: {:?}",
+                err
+            ))),
+        }
+    }
+    async fn get_path_stats(
+        &self,
+        request: tonic::Request<GetPathStatsRequest>,
+    ) -> Result<tonic::Response<GetPathStatsReply>, tonic::Status> {
+        let req = match GetPathStats::try_from(request.into_inner()) {
+            Ok(req) => req,
+            Err(err) => {
+                return Ok(Response::new(GetPathStatsReply {
+                    reply: Some(get_path_stats_reply::Reply::Error(err.into())),
+                }))
+            }
+        };
+        match self.service.get_path_stats(&req).await {
+            Ok(stats) => Ok(Response::new(GetPathStatsReply {
+                reply: Some(get_path_stats_reply::Reply::Stats(stats.into())),
+            })),
+            Err(err) => Ok(Response::new(GetPathStatsReply {
+                reply: Some(get_path_stats_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_registrations(
+        &self,
+        _request: tonic::Request<()>,
+    ) -> Result<tonic::Response<GetRegistrationsReply>, tonic::Status> {
+        match self.service.get_registrations().await {
+            Ok(registered) => Ok(Response::new(GetRegistrationsReply { registered })),
+            Err(err) => Err(Status::internal(format!(
+                "Failed to get the node-agent registrations: {:?}",
+                err
+            ))),
+        }
     }
 }
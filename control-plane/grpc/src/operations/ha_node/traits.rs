@@ -1,21 +1,49 @@
-use crate::ha_cluster_agent::{FailedNvmePath, HaNodeInfo, ReportFailedNvmePathsRequest};
+use crate::{
+    ha_cluster_agent,
+    ha_cluster_agent::{
+        FailedNvmePath, GetPathStatsRequest, HaNodeInfo, RegisterNodeAgentReply,
+        ReportFailedNvmePathsRequest, ReportPathStatsRequest,
+    },
+    misc::traits::StringValue,
+};
 use common_lib::{
     transport_api::ReplyError,
-    types::v0::transport::{cluster_agent::NodeAgentInfo, FailedPath, ReportFailedPaths},
+    types::v0::transport::{
+        cluster_agent::{NodeAgentInfo, NodeAgentRegistration},
+        FailedPath, GetPathStats, NodePathStats, PathSeverity, PathStats, PathStatsSample,
+        ReportFailedPaths, ReportPathStats, TargetPathStats, VolumeId, VolumePathStats,
+    },
     IntoVec,
 };
+use std::{convert::TryFrom, time::Duration};
 
 /// ClusterAgentOperations trait implemented by client which supports cluster-agent operations
 #[tonic::async_trait]
 pub trait ClusterAgentOperations: Send + Sync {
-    /// Register node with cluster-agent
-    async fn register(&self, request: &dyn NodeInfo) -> Result<(), ReplyError>;
+    /// Register node with cluster-agent.
+    /// Returns the registration, which the node-agent must renew at its heartbeat interval.
+    async fn register(&self, request: &dyn NodeInfo) -> Result<NodeAgentRegistration, ReplyError>;
 
     /// Report failed NVMe paths.
     async fn report_failed_nvme_paths(
         &self,
         request: &dyn ReportFailedPathsInfo,
     ) -> Result<(), ReplyError>;
+
+    /// Report NVMe path statistics.
+    /// Returns the number of target statistics which were ignored as they don't belong to a
+    /// known volume.
+    async fn report_path_stats(&self, request: &dyn ReportPathStatsInfo)
+        -> Result<u32, ReplyError>;
+
+    /// Get the aggregated NVMe path statistics of a volume.
+    async fn get_path_stats(
+        &self,
+        request: &dyn GetPathStatsInfo,
+    ) -> Result<VolumePathStats, ReplyError>;
+
+    /// Get the number of node-agents whose registration has not expired.
+    async fn get_registrations(&self) -> Result<u32, ReplyError>;
 }
 
 /// NodeInfo trait for the node-agent registration to be implemented by entities which want to
@@ -47,6 +75,24 @@ impl NodeInfo for HaNodeInfo {
     }
 }
 
+impl From<NodeAgentRegistration> for RegisterNodeAgentReply {
+    fn from(registration: NodeAgentRegistration) -> Self {
+        Self {
+            ttl_ms: registration.ttl().as_millis() as u64,
+            heartbeat_interval_ms: registration.heartbeat_interval().as_millis() as u64,
+        }
+    }
+}
+
+impl From<RegisterNodeAgentReply> for NodeAgentRegistration {
+    fn from(reply: RegisterNodeAgentReply) -> Self {
+        Self::with_heartbeat_interval(
+            Duration::from_millis(reply.ttl_ms),
+            Duration::from_millis(reply.heartbeat_interval_ms),
+        )
+    }
+}
+
 /// Trait to be implemented for ReportFailedNvmePaths operation.
 pub trait ReportFailedPathsInfo: Send + Sync + std::fmt::Debug {
     /// Id of the application node.
@@ -75,10 +121,212 @@ impl From<&dyn ReportFailedPathsInfo> for ReportFailedNvmePathsRequest {
     }
 }
 
+impl ReportFailedPathsInfo for ReportFailedNvmePathsRequest {
+    fn node(&self) -> String {
+        self.nodename.clone()
+    }
+
+    fn failed_paths(&self) -> Vec<FailedPath> {
+        self.failed_paths.clone().into_vec()
+    }
+}
+
 impl From<FailedPath> for FailedNvmePath {
     fn from(path: FailedPath) -> Self {
+        let severity: ha_cluster_agent::PathSeverity = path.severity().into();
         Self {
             target_nqn: path.target_nqn().to_string(),
+            severity: severity as i32,
+        }
+    }
+}
+
+impl From<FailedNvmePath> for FailedPath {
+    fn from(path: FailedNvmePath) -> Self {
+        // a severity which is not known, eg: from a newer node-agent, is deemed a failure
+        let severity = ha_cluster_agent::PathSeverity::from_i32(path.severity)
+            .unwrap_or(ha_cluster_agent::PathSeverity::Failed);
+        Self::new(path.target_nqn).with_severity(severity.into())
+    }
+}
+
+impl From<PathSeverity> for ha_cluster_agent::PathSeverity {
+    fn from(severity: PathSeverity) -> Self {
+        match severity {
+            PathSeverity::Failed => Self::Failed,
+            PathSeverity::Timeout => Self::Timeout,
+            PathSeverity::DegradedLatency => Self::DegradedLatency,
+        }
+    }
+}
+
+impl From<ha_cluster_agent::PathSeverity> for PathSeverity {
+    fn from(severity: ha_cluster_agent::PathSeverity) -> Self {
+        match severity {
+            ha_cluster_agent::PathSeverity::Failed => Self::Failed,
+            ha_cluster_agent::PathSeverity::Timeout => Self::Timeout,
+            ha_cluster_agent::PathSeverity::DegradedLatency => Self::DegradedLatency,
+        }
+    }
+}
+
+impl From<PathStats> for ha_cluster_agent::PathStats {
+    fn from(stats: PathStats) -> Self {
+        Self {
+            total_paths: stats.total_paths,
+            live_paths: stats.live_paths,
+            failed_paths: stats.failed_paths,
+            reconnects: stats.reconnects,
+            mean_reconnect_latency_us: stats.mean_reconnect_latency.as_micros() as u64,
+        }
+    }
+}
+
+impl From<ha_cluster_agent::PathStats> for PathStats {
+    fn from(stats: ha_cluster_agent::PathStats) -> Self {
+        Self {
+            total_paths: stats.total_paths,
+            live_paths: stats.live_paths,
+            failed_paths: stats.failed_paths,
+            reconnects: stats.reconnects,
+            mean_reconnect_latency: Duration::from_micros(stats.mean_reconnect_latency_us),
         }
     }
 }
+
+/// Trait to be implemented for ReportPathStats operation.
+pub trait ReportPathStatsInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the application node.
+    fn node(&self) -> String;
+
+    /// Path statistics of each NVMe target connected to the node.
+    fn stats(&self) -> Vec<TargetPathStats>;
+}
+
+impl ReportPathStatsInfo for ReportPathStats {
+    fn node(&self) -> String {
+        self.node_name().to_string()
+    }
+
+    fn stats(&self) -> Vec<TargetPathStats> {
+        self.stats().clone()
+    }
+}
+
+impl ReportPathStatsInfo for ReportPathStatsRequest {
+    fn node(&self) -> String {
+        self.nodename.clone()
+    }
+
+    fn stats(&self) -> Vec<TargetPathStats> {
+        self.stats
+            .iter()
+            .map(|target| {
+                TargetPathStats::new(
+                    target.target_nqn.clone(),
+                    target.stats.clone().unwrap_or_default().into(),
+                )
+            })
+            .collect()
+    }
+}
+
+impl From<&dyn ReportPathStatsInfo> for ReportPathStatsRequest {
+    fn from(info: &dyn ReportPathStatsInfo) -> Self {
+        Self {
+            nodename: info.node(),
+            stats: info.stats().into_vec(),
+        }
+    }
+}
+
+impl From<TargetPathStats> for ha_cluster_agent::TargetPathStats {
+    fn from(target: TargetPathStats) -> Self {
+        Self {
+            target_nqn: target.target_nqn().to_string(),
+            stats: Some((*target.stats()).into()),
+        }
+    }
+}
+
+/// Trait to be implemented for GetPathStats operation.
+pub trait GetPathStatsInfo: Send + Sync + std::fmt::Debug {
+    /// Id of the volume.
+    fn volume_id(&self) -> VolumeId;
+}
+
+impl GetPathStatsInfo for GetPathStats {
+    fn volume_id(&self) -> VolumeId {
+        self.volume_id().clone()
+    }
+}
+
+impl From<&dyn GetPathStatsInfo> for GetPathStatsRequest {
+    fn from(info: &dyn GetPathStatsInfo) -> Self {
+        Self {
+            volume_id: info.volume_id().to_string(),
+        }
+    }
+}
+
+impl TryFrom<GetPathStatsRequest> for GetPathStats {
+    type Error = ReplyError;
+
+    fn try_from(request: GetPathStatsRequest) -> Result<Self, Self::Error> {
+        Ok(Self::new(VolumeId::try_from(StringValue(Some(
+            request.volume_id,
+        )))?))
+    }
+}
+
+impl From<VolumePathStats> for ha_cluster_agent::VolumePathStats {
+    fn from(stats: VolumePathStats) -> Self {
+        Self {
+            volume_id: stats.volume_id.to_string(),
+            current: Some(stats.current.into()),
+            nodes: stats
+                .nodes
+                .into_iter()
+                .map(|node| ha_cluster_agent::NodePathStats {
+                    nodename: node.node.to_string(),
+                    stats: Some(node.stats.into()),
+                })
+                .collect(),
+            history: stats
+                .history
+                .into_iter()
+                .map(|sample| ha_cluster_agent::PathStatsSample {
+                    timestamp: sample.timestamp,
+                    stats: Some(sample.stats.into()),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<ha_cluster_agent::VolumePathStats> for VolumePathStats {
+    type Error = ReplyError;
+
+    fn try_from(stats: ha_cluster_agent::VolumePathStats) -> Result<Self, Self::Error> {
+        Ok(Self {
+            volume_id: VolumeId::try_from(StringValue(Some(stats.volume_id)))?,
+            current: stats.current.unwrap_or_default().into(),
+            nodes: stats
+                .nodes
+                .into_iter()
+                .map(|node| NodePathStats {
+                    node: node.nodename.into(),
+                    stats: node.stats.unwrap_or_default().into(),
+                })
+                .collect(),
+            history: stats
+                .history
+                .into_iter()
+                .map(|sample| PathStatsSample {
+                    timestamp: sample.timestamp,
+                    stats: sample.stats.unwrap_or_default().into(),
+                })
+                .collect(),
+        })
+    }
+}
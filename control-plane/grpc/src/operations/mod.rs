@@ -120,3 +120,67 @@ impl From<crate::common::Pagination> for Pagination {
         }
     }
 }
+
+/// The order in which sorted entries are returned.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum SortOrder {
+    /// Smallest first.
+    Ascending,
+    /// Largest first.
+    Descending,
+}
+
+/// Sorting structure to allow retrieving the entries sorted by one of their fields.
+/// The entries are sorted before any pagination is applied.
+#[derive(Clone, Debug)]
+pub struct Sorting {
+    // Name of the field to sort the entries by.
+    sort_by: String,
+    // The order of the sorted entries.
+    order: SortOrder,
+}
+
+impl Sorting {
+    /// Create a new `Sorting` instance.
+    pub fn new(sort_by: &str, order: SortOrder) -> Self {
+        Self {
+            sort_by: sort_by.to_string(),
+            order,
+        }
+    }
+
+    /// Get the name of the field to sort by.
+    pub fn sort_by(&self) -> &str {
+        &self.sort_by
+    }
+
+    /// Get the sort order.
+    pub fn order(&self) -> SortOrder {
+        self.order
+    }
+}
+
+impl From<Sorting> for crate::common::Sorting {
+    fn from(s: Sorting) -> Self {
+        let order = match s.order {
+            SortOrder::Ascending => crate::common::SortOrder::Ascending,
+            SortOrder::Descending => crate::common::SortOrder::Descending,
+        };
+        Self {
+            sort_by: s.sort_by,
+            order: order as i32,
+        }
+    }
+}
+
+impl From<crate::common::Sorting> for Sorting {
+    fn from(s: crate::common::Sorting) -> Self {
+        Self {
+            sort_by: s.sort_by,
+            order: match crate::common::SortOrder::from_i32(s.order) {
+                Some(crate::common::SortOrder::Descending) => SortOrder::Descending,
+                _ => SortOrder::Ascending,
+            },
+        }
+    }
+}
@@ -3,11 +3,13 @@ use crate::{
     context::{Client, Context, TracedChannel},
     nexus::{
         add_nexus_child_reply, create_nexus_reply, get_nexuses_reply, get_nexuses_request,
-        nexus_grpc_client::NexusGrpcClient, share_nexus_reply, GetNexusesRequest,
+        nexus_grpc_client::NexusGrpcClient, offline_nexus_child_reply, online_nexus_child_reply,
+        share_nexus_reply, GetNexusesRequest,
     },
     operations::nexus::traits::{
         AddNexusChildInfo, CreateNexusInfo, DestroyNexusInfo, NexusOperations,
-        RemoveNexusChildInfo, ShareNexusInfo, UnshareNexusInfo,
+        OfflineNexusChildInfo, OnlineNexusChildInfo, RemoveNexusChildInfo, ShareNexusInfo,
+        UnshareNexusInfo,
     },
 };
 use common_lib::{
@@ -76,7 +78,8 @@ impl NexusOperations for NexusClient {
                     nexus_id: nexus_id.to_string(),
                 })),
             },
-            _ => GetNexusesRequest { filter: None },
+            Filter::None => GetNexusesRequest { filter: None },
+            filter => return Err(ReplyError::invalid_filter(ResourceKind::Nexus, &filter)),
         };
         let req = self.request(req, ctx, MessageIdVs::GetNexuses);
         let response = self.client().get_nexuses(req).await?.into_inner();
@@ -174,4 +177,48 @@ impl NexusOperations for NexusClient {
             Some(err) => Err(err.into()),
         }
     }
+
+    #[tracing::instrument(
+        name = "NexusClient::online_nexus_child",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn online_nexus_child(
+        &self,
+        request: &dyn OnlineNexusChildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Child, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::OnlineNexusChild);
+        let response = self.client().online_nexus_child(req).await?.into_inner();
+        match response.reply {
+            Some(online_nexus_child_reply) => match online_nexus_child_reply {
+                online_nexus_child_reply::Reply::Child(child) => Ok(Child::try_from(child)?),
+                online_nexus_child_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Child)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "NexusClient::offline_nexus_child",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn offline_nexus_child(
+        &self,
+        request: &dyn OfflineNexusChildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Child, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::OfflineNexusChild);
+        let response = self.client().offline_nexus_child(req).await?.into_inner();
+        match response.reply {
+            Some(offline_nexus_child_reply) => match offline_nexus_child_reply {
+                offline_nexus_child_reply::Reply::Child(child) => Ok(Child::try_from(child)?),
+                offline_nexus_child_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Child)),
+        }
+    }
 }
@@ -3,10 +3,12 @@ use crate::{
     nexus::{
         add_nexus_child_reply, create_nexus_reply, get_nexuses_reply,
         nexus_grpc_server::{NexusGrpc, NexusGrpcServer},
-        share_nexus_reply, AddNexusChildReply, AddNexusChildRequest, CreateNexusReply,
-        CreateNexusRequest, DestroyNexusReply, DestroyNexusRequest, GetNexusesReply,
-        GetNexusesRequest, RemoveNexusChildReply, RemoveNexusChildRequest, ShareNexusReply,
-        ShareNexusRequest, UnshareNexusReply, UnshareNexusRequest,
+        offline_nexus_child_reply, online_nexus_child_reply, share_nexus_reply, AddNexusChildReply,
+        AddNexusChildRequest, CreateNexusReply, CreateNexusRequest, DestroyNexusReply,
+        DestroyNexusRequest, GetNexusesReply, GetNexusesRequest, OfflineNexusChildReply,
+        OfflineNexusChildRequest, OnlineNexusChildReply, OnlineNexusChildRequest,
+        RemoveNexusChildReply, RemoveNexusChildRequest, ShareNexusReply, ShareNexusRequest,
+        UnshareNexusReply, UnshareNexusRequest,
     },
     operations::nexus::traits::NexusOperations,
 };
@@ -116,6 +118,34 @@ impl NexusGrpc for NexusServer {
             })),
         }
     }
+    async fn online_nexus_child(
+        &self,
+        request: tonic::Request<OnlineNexusChildRequest>,
+    ) -> Result<tonic::Response<OnlineNexusChildReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.online_nexus_child(&req, None).await {
+            Ok(child) => Ok(Response::new(OnlineNexusChildReply {
+                reply: Some(online_nexus_child_reply::Reply::Child(child.into())),
+            })),
+            Err(err) => Ok(Response::new(OnlineNexusChildReply {
+                reply: Some(online_nexus_child_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn offline_nexus_child(
+        &self,
+        request: tonic::Request<OfflineNexusChildRequest>,
+    ) -> Result<tonic::Response<OfflineNexusChildReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.offline_nexus_child(&req, None).await {
+            Ok(child) => Ok(Response::new(OfflineNexusChildReply {
+                reply: Some(offline_nexus_child_reply::Reply::Child(child.into())),
+            })),
+            Err(err) => Ok(Response::new(OfflineNexusChildReply {
+                reply: Some(offline_nexus_child_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 
     async fn get_nexuses(
         &self,
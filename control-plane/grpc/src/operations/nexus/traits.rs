@@ -5,25 +5,30 @@ use crate::{
     nexus,
     nexus::{
         get_nexuses_request, AddNexusChildRequest, CreateNexusRequest, DestroyNexusRequest,
-        RemoveNexusChildRequest, ShareNexusRequest, UnshareNexusRequest,
+        OfflineNexusChildRequest, OnlineNexusChildRequest, RemoveNexusChildRequest,
+        ShareNexusRequest, UnshareNexusRequest,
     },
 };
 use common_lib::{
     transport_api::{v0::Nexuses, ReplyError, ResourceKind},
     types::v0::{
         store::{
+            epoch_secs, from_epoch_secs,
             nexus::{NexusOperation, NexusOperationState, NexusSpec, NexusSpecStatus, ReplicaUri},
             nexus_child::NexusChild,
         },
         transport::{
-            AddNexusChild, Child, ChildState, ChildStateReason, ChildUri, CreateNexus,
+            AddNexusChild, Child, ChildFault, ChildState, ChildStateReason, ChildUri, CreateNexus,
             DestroyNexus, Filter, Nexus, NexusId, NexusNvmfConfig, NexusShareProtocol, NexusStatus,
-            NodeId, NvmfControllerIdRange, RemoveNexusChild, ReplicaId, ShareNexus, UnshareNexus,
-            VolumeId,
+            NodeId, NvmeReservation, NvmeReservationType, NvmfControllerIdRange, OfflineNexusChild,
+            OnlineNexusChild, RemoveNexusChild, ReplicaId, ShareNexus, UnshareNexus, VolumeId,
         },
     },
 };
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    time::{Duration, UNIX_EPOCH},
+};
 
 /// All nexus operations to be a part of the NexusOperations trait
 #[tonic::async_trait]
@@ -66,6 +71,18 @@ pub trait NexusOperations: Send + Sync {
         req: &dyn RemoveNexusChildInfo,
         ctx: Option<Context>,
     ) -> Result<(), ReplyError>;
+    /// Bring an offline Nexus Child back online
+    async fn online_nexus_child(
+        &self,
+        req: &dyn OnlineNexusChildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Child, ReplyError>;
+    /// Take a Nexus Child offline
+    async fn offline_nexus_child(
+        &self,
+        req: &dyn OfflineNexusChildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Child, ReplyError>;
 }
 
 impl TryFrom<nexus::Nexus> for Nexus {
@@ -261,11 +278,36 @@ impl TryFrom<nexus::Child> for Child {
             state_reason: nexus::ChildStateReason::from_i32(child_grpc_type.reason)
                 .map(ChildStateReason::from)
                 .unwrap_or(ChildStateReason::Unknown),
+            last_fault: child_grpc_type.last_fault.map(ChildFault::from),
         };
         Ok(child)
     }
 }
 
+impl From<nexus::ChildFault> for ChildFault {
+    fn from(src: nexus::ChildFault) -> Self {
+        Self {
+            reason: nexus::ChildStateReason::from_i32(src.reason)
+                .map(ChildStateReason::from)
+                .unwrap_or(ChildStateReason::Unknown),
+            faulted_at: UNIX_EPOCH + Duration::from_secs(src.faulted_at),
+        }
+    }
+}
+
+impl From<ChildFault> for nexus::ChildFault {
+    fn from(src: ChildFault) -> Self {
+        Self {
+            reason: nexus::ChildStateReason::from(src.reason).into(),
+            faulted_at: src
+                .faulted_at
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+        }
+    }
+}
+
 impl From<Child> for nexus::Child {
     fn from(child: Child) -> Self {
         nexus::Child {
@@ -273,6 +315,7 @@ impl From<Child> for nexus::Child {
             state: nexus::ChildState::from(child.state) as i32,
             rebuild_progress: child.rebuild_progress.map(|i| i.into()),
             reason: nexus::ChildStateReason::from(child.state_reason).into(),
+            last_fault: child.last_fault.map(From::from),
         }
     }
 }
@@ -355,6 +398,25 @@ impl TryFrom<nexus::NexusSpec> for NexusSpec {
                 operation: NexusOperation::Create,
                 result: op.result,
             }),
+            config: match value.config {
+                Some(config) => Some(NexusNvmfConfig::try_from(config)?),
+                None => None,
+            },
+            deferred_children: {
+                let mut children: Vec<NexusChild> = vec![];
+                for child in value.deferred_children {
+                    children.push(NexusChild::try_from(child)?)
+                }
+                children
+            },
+            created_at: value.created_at.map(from_epoch_secs),
+            updated_at: value.updated_at.map(from_epoch_secs),
+            frozen_until: value.frozen_until.map(from_epoch_secs),
+            offline_children: value
+                .offline_children
+                .into_iter()
+                .map(ChildUri::from)
+                .collect(),
         })
     }
 }
@@ -380,6 +442,20 @@ impl From<NexusSpec> for nexus::NexusSpec {
             operation: value.operation.map(|operation| common::SpecOperation {
                 result: operation.result,
             }),
+            config: value.config.map(|config| config.into()),
+            deferred_children: value
+                .deferred_children
+                .into_iter()
+                .map(|child| child.into())
+                .collect(),
+            created_at: value.created_at.map(epoch_secs),
+            updated_at: value.updated_at.map(epoch_secs),
+            frozen_until: value.frozen_until.map(epoch_secs),
+            offline_children: value
+                .offline_children
+                .into_iter()
+                .map(|uri| uri.to_string())
+                .collect(),
         }
     }
 }
@@ -412,6 +488,7 @@ impl From<NexusChild> for nexus::NexusChild {
                 child: Some(nexus::nexus_child::Child::Replica(nexus::Replica {
                     replica_id: Some(replica_uri.uuid().to_string()),
                     child_uri: replica_uri.uri().to_string(),
+                    generation: replica_uri.generation(),
                 })),
             },
             NexusChild::Uri(child_uri) => nexus::NexusChild {
@@ -429,12 +506,13 @@ impl TryFrom<nexus::NexusChild> for NexusChild {
     fn try_from(value: nexus::NexusChild) -> Result<Self, Self::Error> {
         match value.child {
             Some(child) => Ok(match child {
-                nexus::nexus_child::Child::Replica(replica) => {
-                    NexusChild::Replica(ReplicaUri::new(
+                nexus::nexus_child::Child::Replica(replica) => NexusChild::Replica(
+                    ReplicaUri::new(
                         &ReplicaId::try_from(StringValue(replica.replica_id))?,
                         &ChildUri::from(replica.child_uri),
-                    ))
-                }
+                    )
+                    .with_generation(replica.generation),
+                ),
                 nexus::nexus_child::Child::Uri(uri) => {
                     NexusChild::Uri(ChildUri::from(uri.child_uri))
                 }
@@ -451,7 +529,7 @@ impl TryFrom<nexus::NexusChild> for NexusChild {
 impl TryFrom<nexus::NexusNvmfConfig> for NexusNvmfConfig {
     type Error = ReplyError;
     fn try_from(data: nexus::NexusNvmfConfig) -> Result<Self, Self::Error> {
-        Ok(NexusNvmfConfig::new(
+        let config = NexusNvmfConfig::new(
             match data.controller_id_range {
                 Some(range) => NvmfControllerIdRange::try_from(range)?,
                 None => {
@@ -464,7 +542,65 @@ impl TryFrom<nexus::NexusNvmfConfig> for NexusNvmfConfig {
             },
             data.reservation_key,
             data.preempt_reservation_key,
-        ))
+        );
+        Ok(match data.reservation_type {
+            Some(reservation_type) => {
+                config.with_reservation(&NvmeReservation::try_from(nexus::NvmeReservation {
+                    key: data.reservation_key,
+                    reservation_type,
+                })?)
+            }
+            None => config,
+        })
+    }
+}
+
+impl TryFrom<nexus::NvmeReservation> for NvmeReservation {
+    type Error = ReplyError;
+    fn try_from(value: nexus::NvmeReservation) -> Result<Self, Self::Error> {
+        match nexus::NvmeReservationType::from_i32(value.reservation_type) {
+            Some(reservation_type) => Ok(NvmeReservation::new(value.key, reservation_type.into())),
+            None => Err(ReplyError::invalid_argument(
+                ResourceKind::Nexus,
+                "nvme_reservation.reservation_type",
+                "".to_string(),
+            )),
+        }
+    }
+}
+
+impl From<NvmeReservation> for nexus::NvmeReservation {
+    fn from(value: NvmeReservation) -> Self {
+        Self {
+            key: value.key,
+            reservation_type: nexus::NvmeReservationType::from(value.reservation_type) as i32,
+        }
+    }
+}
+
+impl From<nexus::NvmeReservationType> for NvmeReservationType {
+    fn from(src: nexus::NvmeReservationType) -> Self {
+        match src {
+            nexus::NvmeReservationType::WriteExclusive => Self::WriteExclusive,
+            nexus::NvmeReservationType::ExclusiveAccess => Self::ExclusiveAccess,
+            nexus::NvmeReservationType::WriteExclusiveRegsOnly => Self::WriteExclusiveRegsOnly,
+            nexus::NvmeReservationType::ExclusiveAccessRegsOnly => Self::ExclusiveAccessRegsOnly,
+            nexus::NvmeReservationType::WriteExclusiveAllRegs => Self::WriteExclusiveAllRegs,
+            nexus::NvmeReservationType::ExclusiveAccessAllRegs => Self::ExclusiveAccessAllRegs,
+        }
+    }
+}
+
+impl From<NvmeReservationType> for nexus::NvmeReservationType {
+    fn from(src: NvmeReservationType) -> Self {
+        match src {
+            NvmeReservationType::WriteExclusive => Self::WriteExclusive,
+            NvmeReservationType::ExclusiveAccess => Self::ExclusiveAccess,
+            NvmeReservationType::WriteExclusiveRegsOnly => Self::WriteExclusiveRegsOnly,
+            NvmeReservationType::ExclusiveAccessRegsOnly => Self::ExclusiveAccessRegsOnly,
+            NvmeReservationType::WriteExclusiveAllRegs => Self::WriteExclusiveAllRegs,
+            NvmeReservationType::ExclusiveAccessAllRegs => Self::ExclusiveAccessAllRegs,
+        }
     }
 }
 
@@ -481,6 +617,9 @@ impl From<NexusNvmfConfig> for nexus::NexusNvmfConfig {
             controller_id_range: Some(data.controller_id_range().into()),
             reservation_key: data.reservation_key(),
             preempt_reservation_key: data.preempt_reservation_key(),
+            reservation_type: data
+                .reservation_type()
+                .map(|t| nexus::NvmeReservationType::from(t) as i32),
         }
     }
 }
@@ -514,6 +653,8 @@ pub trait CreateNexusInfo: Send + Sync + std::fmt::Debug {
     fn owner(&self) -> Option<VolumeId>;
     /// Nexus Nvmf Configuration
     fn config(&self) -> Option<NexusNvmfConfig>;
+    /// Don't probe whether the node can connect to the remote children
+    fn skip_child_probe(&self) -> bool;
 }
 
 /// Intermediate structure that validates the conversion to CreateNexusRequest type
@@ -554,6 +695,10 @@ impl CreateNexusInfo for CreateNexus {
     fn config(&self) -> Option<NexusNvmfConfig> {
         self.config.clone()
     }
+
+    fn skip_child_probe(&self) -> bool {
+        self.skip_child_probe
+    }
 }
 
 impl CreateNexusInfo for ValidatedCreateNexusRequest {
@@ -584,6 +729,10 @@ impl CreateNexusInfo for ValidatedCreateNexusRequest {
     fn config(&self) -> Option<NexusNvmfConfig> {
         self.config.clone()
     }
+
+    fn skip_child_probe(&self) -> bool {
+        self.inner.skip_child_probe
+    }
 }
 
 impl ValidateRequestTypes for CreateNexusRequest {
@@ -623,6 +772,7 @@ impl From<&dyn CreateNexusInfo> for CreateNexus {
             managed: data.managed(),
             owner: data.owner(),
             config: data.config(),
+            skip_child_probe: data.skip_child_probe(),
         }
     }
 }
@@ -641,6 +791,7 @@ impl From<&dyn CreateNexusInfo> for CreateNexusRequest {
             managed: data.managed(),
             owner: data.owner().map(|owner| owner.to_string()),
             config: data.config().map(|config| config.into()),
+            skip_child_probe: data.skip_child_probe(),
         }
     }
 }
@@ -1021,6 +1172,8 @@ pub trait RemoveNexusChildInfo: Send + Sync + std::fmt::Debug {
     fn nexus(&self) -> NexusId;
     /// URI of the child device to be added
     fn uri(&self) -> ChildUri;
+    /// Remove the child even if it's the last healthy replica of the volume
+    fn force(&self) -> bool;
 }
 
 impl RemoveNexusChildInfo for RemoveNexusChild {
@@ -1035,6 +1188,10 @@ impl RemoveNexusChildInfo for RemoveNexusChild {
     fn uri(&self) -> ChildUri {
         self.uri.clone()
     }
+
+    fn force(&self) -> bool {
+        self.force
+    }
 }
 
 /// Intermediate structure that validates the conversion to RemoveNexusChildRequest type
@@ -1056,6 +1213,10 @@ impl RemoveNexusChildInfo for ValidatedRemoveNexusChildRequest {
     fn uri(&self) -> ChildUri {
         ChildUri::from(self.inner.uri.clone())
     }
+
+    fn force(&self) -> bool {
+        self.inner.force
+    }
 }
 
 impl ValidateRequestTypes for RemoveNexusChildRequest {
@@ -1074,12 +1235,166 @@ impl From<&dyn RemoveNexusChildInfo> for RemoveNexusChildRequest {
             node_id: data.node().to_string(),
             nexus_id: Some(data.nexus().to_string()),
             uri: data.uri().to_string(),
+            force: data.force(),
         }
     }
 }
 
 impl From<&dyn RemoveNexusChildInfo> for RemoveNexusChild {
     fn from(data: &dyn RemoveNexusChildInfo) -> Self {
+        Self {
+            node: data.node(),
+            nexus: data.nexus(),
+            uri: data.uri(),
+            force: data.force(),
+        }
+    }
+}
+
+/// OnlineNexusChildInfo trait for the online nexus child to be implemented by entities which want
+/// to use this operation
+pub trait OnlineNexusChildInfo: Send + Sync + std::fmt::Debug {
+    /// id of the io-engine instance
+    fn node(&self) -> NodeId;
+    /// uuid of the nexus
+    fn nexus(&self) -> NexusId;
+    /// URI of the child device to be brought online
+    fn uri(&self) -> ChildUri;
+}
+
+impl OnlineNexusChildInfo for OnlineNexusChild {
+    fn node(&self) -> NodeId {
+        self.node.clone()
+    }
+
+    fn nexus(&self) -> NexusId {
+        self.nexus.clone()
+    }
+
+    fn uri(&self) -> ChildUri {
+        self.uri.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to OnlineNexusChildRequest type
+#[derive(Debug)]
+pub struct ValidatedOnlineNexusChildRequest {
+    inner: OnlineNexusChildRequest,
+    nexus: NexusId,
+}
+
+impl OnlineNexusChildInfo for ValidatedOnlineNexusChildRequest {
+    fn node(&self) -> NodeId {
+        self.inner.node_id.clone().into()
+    }
+
+    fn nexus(&self) -> NexusId {
+        self.nexus.clone()
+    }
+
+    fn uri(&self) -> ChildUri {
+        ChildUri::from(self.inner.uri.clone())
+    }
+}
+
+impl ValidateRequestTypes for OnlineNexusChildRequest {
+    type Validated = ValidatedOnlineNexusChildRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedOnlineNexusChildRequest {
+            nexus: NexusId::try_from(StringValue(self.nexus_id.clone()))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn OnlineNexusChildInfo> for OnlineNexusChildRequest {
+    fn from(data: &dyn OnlineNexusChildInfo) -> Self {
+        Self {
+            node_id: data.node().to_string(),
+            nexus_id: Some(data.nexus().to_string()),
+            uri: data.uri().to_string(),
+        }
+    }
+}
+
+impl From<&dyn OnlineNexusChildInfo> for OnlineNexusChild {
+    fn from(data: &dyn OnlineNexusChildInfo) -> Self {
+        Self {
+            node: data.node(),
+            nexus: data.nexus(),
+            uri: data.uri(),
+        }
+    }
+}
+
+/// OfflineNexusChildInfo trait for the offline nexus child to be implemented by entities which want
+/// to use this operation
+pub trait OfflineNexusChildInfo: Send + Sync + std::fmt::Debug {
+    /// id of the io-engine instance
+    fn node(&self) -> NodeId;
+    /// uuid of the nexus
+    fn nexus(&self) -> NexusId;
+    /// URI of the child device to be taken offline
+    fn uri(&self) -> ChildUri;
+}
+
+impl OfflineNexusChildInfo for OfflineNexusChild {
+    fn node(&self) -> NodeId {
+        self.node.clone()
+    }
+
+    fn nexus(&self) -> NexusId {
+        self.nexus.clone()
+    }
+
+    fn uri(&self) -> ChildUri {
+        self.uri.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to OfflineNexusChildRequest type
+#[derive(Debug)]
+pub struct ValidatedOfflineNexusChildRequest {
+    inner: OfflineNexusChildRequest,
+    nexus: NexusId,
+}
+
+impl OfflineNexusChildInfo for ValidatedOfflineNexusChildRequest {
+    fn node(&self) -> NodeId {
+        self.inner.node_id.clone().into()
+    }
+
+    fn nexus(&self) -> NexusId {
+        self.nexus.clone()
+    }
+
+    fn uri(&self) -> ChildUri {
+        ChildUri::from(self.inner.uri.clone())
+    }
+}
+
+impl ValidateRequestTypes for OfflineNexusChildRequest {
+    type Validated = ValidatedOfflineNexusChildRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedOfflineNexusChildRequest {
+            nexus: NexusId::try_from(StringValue(self.nexus_id.clone()))?,
+            inner: self,
+        })
+    }
+}
+
+impl From<&dyn OfflineNexusChildInfo> for OfflineNexusChildRequest {
+    fn from(data: &dyn OfflineNexusChildInfo) -> Self {
+        Self {
+            node_id: data.node().to_string(),
+            nexus_id: Some(data.nexus().to_string()),
+            uri: data.uri().to_string(),
+        }
+    }
+}
+
+impl From<&dyn OfflineNexusChildInfo> for OfflineNexusChild {
+    fn from(data: &dyn OfflineNexusChildInfo) -> Self {
         Self {
             node: data.node(),
             nexus: data.nexus(),
@@ -3,19 +3,22 @@ use crate::{
     common::NodeFilter,
     context::{Client, Context, TracedChannel},
     node::{
-        cordon_node_reply, get_nodes_reply, get_nodes_request, node_grpc_client::NodeGrpcClient,
-        uncordon_node_reply, CordonNodeRequest, GetNodesRequest, ProbeRequest, UncordonNodeRequest,
+        cordon_node_reply, delete_node_maintenance_reply, discover_pools_reply, get_nodes_reply,
+        get_nodes_request, node_grpc_client::NodeGrpcClient, put_node_maintenance_reply,
+        uncordon_node_reply, CordonNodeRequest, DeleteNodeMaintenanceRequest, DeleteNodeRequest,
+        DiscoverPoolsRequest, GetNodesRequest, ProbeRequest, PutNodeMaintenanceRequest,
+        UncordonNodeRequest,
     },
     operations::node::traits::{GetBlockDeviceInfo, NodeOperations},
 };
 use common_lib::{
     transport_api::{
-        v0::{BlockDevices, Nodes},
+        v0::{BlockDevices, DiscoveredPools, Nodes},
         ReplyError, ResourceKind, TimeoutOptions,
     },
     types::v0::transport::{Filter, MessageIdVs, Node, NodeId},
 };
-use std::{convert::TryFrom, ops::Deref};
+use std::{convert::TryFrom, ops::Deref, time::Duration};
 use tonic::transport::Uri;
 
 /// RPC Node Client
@@ -48,7 +51,8 @@ impl NodeOperations for NodeClient {
                     node_id: id.into(),
                 })),
             },
-            _ => GetNodesRequest { filter: None },
+            Filter::None => GetNodesRequest { filter: None },
+            filter => return Err(ReplyError::invalid_filter(ResourceKind::Node, &filter)),
         };
         let req = self.request(req, ctx, MessageIdVs::GetNodes);
         let response = self.client().get_nodes(req).await?.into_inner();
@@ -86,10 +90,16 @@ impl NodeOperations for NodeClient {
     }
 
     #[tracing::instrument(name = "NodeClient::cordon", level = "debug", skip(self), err)]
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError> {
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expiry: Option<Duration>,
+    ) -> Result<Node, ReplyError> {
         let req = CordonNodeRequest {
             node_id: id.to_string(),
             label,
+            expiry: expiry.map(|expiry| expiry.as_secs()),
         };
         let response = self.client().cordon_node(req).await?.into_inner();
         match response.reply {
@@ -116,4 +126,72 @@ impl NodeOperations for NodeClient {
             None => Err(ReplyError::invalid_response(ResourceKind::Node)),
         }
     }
+
+    #[tracing::instrument(name = "NodeClient::put_maintenance", level = "debug", skip(self), err)]
+    async fn put_maintenance(&self, id: NodeId, duration: Duration) -> Result<Node, ReplyError> {
+        let req = PutNodeMaintenanceRequest {
+            node_id: id.to_string(),
+            duration: duration.as_secs(),
+        };
+        let response = self.client().put_node_maintenance(req).await?.into_inner();
+        match response.reply {
+            Some(put_node_maintenance_reply) => match put_node_maintenance_reply {
+                put_node_maintenance_reply::Reply::Node(node) => Ok(Node::try_from(node)?),
+                put_node_maintenance_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "NodeClient::delete_maintenance",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn delete_maintenance(&self, id: NodeId) -> Result<Node, ReplyError> {
+        let req = DeleteNodeMaintenanceRequest {
+            node_id: id.to_string(),
+        };
+        let response = self
+            .client()
+            .delete_node_maintenance(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(delete_node_maintenance_reply) => match delete_node_maintenance_reply {
+                delete_node_maintenance_reply::Reply::Node(node) => Ok(Node::try_from(node)?),
+                delete_node_maintenance_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
+
+    #[tracing::instrument(name = "NodeClient::delete", level = "debug", skip(self), err)]
+    async fn delete(&self, id: NodeId, force: bool) -> Result<(), ReplyError> {
+        let req = DeleteNodeRequest {
+            node_id: id.to_string(),
+            force,
+        };
+        let response = self.client().delete_node(req).await?.into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    #[tracing::instrument(name = "NodeClient::discover_pools", level = "debug", skip(self), err)]
+    async fn discover_pools(&self, id: NodeId) -> Result<DiscoveredPools, ReplyError> {
+        let req = DiscoverPoolsRequest {
+            node_id: id.to_string(),
+        };
+        let response = self.client().discover_pools(req).await?.into_inner();
+        match response.reply {
+            Some(discover_pools_reply) => match discover_pools_reply {
+                discover_pools_reply::Reply::Pools(pools) => Ok(pools.into()),
+                discover_pools_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Pool)),
+        }
+    }
 }
@@ -2,14 +2,17 @@ use crate::{
     blockdevice::{get_block_devices_reply, GetBlockDevicesReply, GetBlockDevicesRequest},
     node,
     node::{
-        cordon_node_reply, get_nodes_reply,
+        cordon_node_reply, delete_node_maintenance_reply, discover_pools_reply, get_nodes_reply,
         node_grpc_server::{NodeGrpc, NodeGrpcServer},
-        uncordon_node_reply, CordonNodeReply, CordonNodeRequest, GetNodesReply, GetNodesRequest,
-        ProbeRequest, ProbeResponse, UncordonNodeReply, UncordonNodeRequest,
+        put_node_maintenance_reply, uncordon_node_reply, CordonNodeReply, CordonNodeRequest,
+        DeleteNodeMaintenanceReply, DeleteNodeMaintenanceRequest, DeleteNodeReply,
+        DeleteNodeRequest, DiscoverPoolsReply, DiscoverPoolsRequest, GetNodesReply,
+        GetNodesRequest, ProbeRequest, ProbeResponse, PutNodeMaintenanceReply,
+        PutNodeMaintenanceRequest, UncordonNodeReply, UncordonNodeRequest,
     },
     operations::node::traits::NodeOperations,
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tonic::{Request, Response};
 
 /// gRPC Node Server
@@ -77,7 +80,12 @@ impl NodeGrpc for NodeServer {
         request: tonic::Request<CordonNodeRequest>,
     ) -> Result<tonic::Response<CordonNodeReply>, tonic::Status> {
         let req: CordonNodeRequest = request.into_inner();
-        match self.service.cordon(req.node_id.into(), req.label).await {
+        let expiry = req.expiry.map(Duration::from_secs);
+        match self
+            .service
+            .cordon(req.node_id.into(), req.label, expiry)
+            .await
+        {
             Ok(node) => Ok(Response::new(CordonNodeReply {
                 reply: Some(cordon_node_reply::Reply::Node(node.into())),
             })),
@@ -101,4 +109,67 @@ impl NodeGrpc for NodeServer {
             })),
         }
     }
+
+    async fn put_node_maintenance(
+        &self,
+        request: tonic::Request<PutNodeMaintenanceRequest>,
+    ) -> Result<tonic::Response<PutNodeMaintenanceReply>, tonic::Status> {
+        let req: PutNodeMaintenanceRequest = request.into_inner();
+        let duration = Duration::from_secs(req.duration);
+        match self
+            .service
+            .put_maintenance(req.node_id.into(), duration)
+            .await
+        {
+            Ok(node) => Ok(Response::new(PutNodeMaintenanceReply {
+                reply: Some(put_node_maintenance_reply::Reply::Node(node.into())),
+            })),
+            Err(err) => Ok(Response::new(PutNodeMaintenanceReply {
+                reply: Some(put_node_maintenance_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn delete_node_maintenance(
+        &self,
+        request: tonic::Request<DeleteNodeMaintenanceRequest>,
+    ) -> Result<tonic::Response<DeleteNodeMaintenanceReply>, tonic::Status> {
+        let req: DeleteNodeMaintenanceRequest = request.into_inner();
+        match self.service.delete_maintenance(req.node_id.into()).await {
+            Ok(node) => Ok(Response::new(DeleteNodeMaintenanceReply {
+                reply: Some(delete_node_maintenance_reply::Reply::Node(node.into())),
+            })),
+            Err(err) => Ok(Response::new(DeleteNodeMaintenanceReply {
+                reply: Some(delete_node_maintenance_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+
+    async fn delete_node(
+        &self,
+        request: tonic::Request<DeleteNodeRequest>,
+    ) -> Result<tonic::Response<DeleteNodeReply>, tonic::Status> {
+        let req: DeleteNodeRequest = request.into_inner();
+        match self.service.delete(req.node_id.into(), req.force).await {
+            Ok(()) => Ok(Response::new(DeleteNodeReply { error: None })),
+            Err(err) => Ok(Response::new(DeleteNodeReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
+
+    async fn discover_pools(
+        &self,
+        request: tonic::Request<DiscoverPoolsRequest>,
+    ) -> Result<tonic::Response<DiscoverPoolsReply>, tonic::Status> {
+        let req: DiscoverPoolsRequest = request.into_inner();
+        match self.service.discover_pools(req.node_id.into()).await {
+            Ok(pools) => Ok(Response::new(DiscoverPoolsReply {
+                reply: Some(discover_pools_reply::Reply::Pools(pools.into())),
+            })),
+            Err(err) => Ok(Response::new(DiscoverPoolsReply {
+                reply: Some(discover_pools_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -7,18 +7,21 @@ use crate::{
 };
 use common_lib::{
     transport_api::{
-        v0::{BlockDevices, Nodes},
+        v0::{BlockDevices, DiscoveredPools, Nodes},
         ReplyError, ResourceKind,
     },
     types::v0::{
-        store::node::NodeSpec,
+        store::{epoch_secs, from_epoch_secs, node::NodeSpec},
         transport::{
-            BlockDevice, Filesystem, Filter, GetBlockDevices, Node, NodeId, NodeState, NodeStatus,
-            Partition,
+            BlockDevice, DiscoveredPool, Filesystem, Filter, GetBlockDevices, Node, NodeId,
+            NodeState, NodeStatus, Partition,
         },
     },
 };
-use std::convert::TryFrom;
+use std::{
+    convert::TryFrom,
+    time::{Duration, UNIX_EPOCH},
+};
 
 /// Trait implemented by services which support node operations.
 #[tonic::async_trait]
@@ -34,20 +37,48 @@ pub trait NodeOperations: Send + Sync {
         ctx: Option<Context>,
     ) -> Result<BlockDevices, ReplyError>;
     /// Cordon the node with the given ID and associate the label with the cordoned node.
-    async fn cordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    /// The label is removed after the `expiry`, if any.
+    async fn cordon(
+        &self,
+        id: NodeId,
+        label: String,
+        expiry: Option<Duration>,
+    ) -> Result<Node, ReplyError>;
     /// Uncordon the node with the given ID by removing the associated label.
     async fn uncordon(&self, id: NodeId, label: String) -> Result<Node, ReplyError>;
+    /// Put the node with the given ID in maintenance for at most the given `duration`.
+    async fn put_maintenance(&self, id: NodeId, duration: Duration) -> Result<Node, ReplyError>;
+    /// Take the node with the given ID out of maintenance.
+    async fn delete_maintenance(&self, id: NodeId) -> Result<Node, ReplyError>;
+    /// Delete the node with the given ID, which must be offline, disowning its resources if
+    /// `force`d, otherwise it must not host any.
+    async fn delete(&self, id: NodeId, force: bool) -> Result<(), ReplyError>;
+    /// Discover the pools which could be imported from the disks of the node with the given ID.
+    async fn discover_pools(&self, id: NodeId) -> Result<DiscoveredPools, ReplyError>;
 }
 
 impl TryFrom<node::Node> for Node {
     type Error = ReplyError;
     fn try_from(node_grpc_type: node::Node) -> Result<Self, Self::Error> {
         let node_spec = node_grpc_type.spec.map(|spec| {
+            let cordon = spec.cordon.unwrap_or_default();
             NodeSpec::new(
                 spec.node_id.into(),
                 spec.endpoint,
                 spec.labels.unwrap_or_default().value,
-                spec.cordon.map(|cordon| cordon.label),
+                Some(cordon.label),
+            )
+            .with_cordon_expiry(
+                cordon
+                    .expiry
+                    .into_iter()
+                    .map(|(label, expiry)| (label, UNIX_EPOCH + Duration::from_secs(expiry)))
+                    .collect(),
+            )
+            .with_maintenance_until(spec.maintenance_until.map(from_epoch_secs))
+            .with_timestamps(
+                spec.created_at.map(from_epoch_secs),
+                spec.updated_at.map(from_epoch_secs),
             )
         });
         let node_state = match node_grpc_type.state {
@@ -90,7 +121,18 @@ impl From<Node> for node::Node {
             }),
             cordon: Some(NodeCordon {
                 label: spec.cordon_labels(),
+                expiry: spec
+                    .cordon_expiry()
+                    .iter()
+                    .map(|(label, expiry)| {
+                        let expiry = expiry.duration_since(UNIX_EPOCH).unwrap_or_default();
+                        (label.clone(), expiry.as_secs())
+                    })
+                    .collect(),
             }),
+            created_at: spec.created_at().map(epoch_secs),
+            updated_at: spec.updated_at().map(epoch_secs),
+            maintenance_until: spec.maintenance_until().map(epoch_secs),
         });
         let node_state = match node.state() {
             None => None,
@@ -312,3 +354,43 @@ impl From<BlockDevices> for blockdevice::BlockDevices {
         }
     }
 }
+
+impl From<node::DiscoveredPool> for DiscoveredPool {
+    fn from(pool: node::DiscoveredPool) -> Self {
+        Self {
+            node: pool.node_id.into(),
+            id: pool.pool_id.into(),
+            uuid: pool.pool_uuid,
+            disks: pool.disks.into_iter().map(From::from).collect(),
+            capacity: pool.capacity,
+            spec_exists: pool.spec_exists,
+        }
+    }
+}
+
+impl From<DiscoveredPool> for node::DiscoveredPool {
+    fn from(pool: DiscoveredPool) -> Self {
+        Self {
+            node_id: pool.node.to_string(),
+            pool_id: pool.id.to_string(),
+            pool_uuid: pool.uuid,
+            disks: pool.disks.into_iter().map(From::from).collect(),
+            capacity: pool.capacity,
+            spec_exists: pool.spec_exists,
+        }
+    }
+}
+
+impl From<node::DiscoveredPools> for DiscoveredPools {
+    fn from(pools: node::DiscoveredPools) -> Self {
+        DiscoveredPools(pools.pools.into_iter().map(From::from).collect())
+    }
+}
+
+impl From<DiscoveredPools> for node::DiscoveredPools {
+    fn from(pools: DiscoveredPools) -> Self {
+        node::DiscoveredPools {
+            pools: pools.into_inner().into_iter().map(From::from).collect(),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use crate::{
     common::{NodeFilter, NodePoolFilter, PoolFilter},
     context::{Client, Context, TracedChannel},
-    operations::pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+    operations::{
+        pool::traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+        Sorting,
+    },
     pool::{
         create_pool_reply, get_pools_reply, get_pools_request, pool_grpc_client::PoolGrpcClient,
         GetPoolsRequest,
@@ -69,26 +72,32 @@ impl PoolOperations for PoolClient {
         }
     }
 
-    #[tracing::instrument(name = "PoolClient::get", level = "debug", skip(self), err)]
-    async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError> {
-        let req: GetPoolsRequest = match filter {
-            Filter::Node(id) => GetPoolsRequest {
-                filter: Some(get_pools_request::Filter::Node(NodeFilter {
-                    node_id: id.into(),
-                })),
-            },
-            Filter::Pool(id) => GetPoolsRequest {
-                filter: Some(get_pools_request::Filter::Pool(PoolFilter {
-                    pool_id: id.into(),
-                })),
-            },
-            Filter::NodePool(node_id, pool_id) => GetPoolsRequest {
-                filter: Some(get_pools_request::Filter::NodePool(NodePoolFilter {
+    #[tracing::instrument(name = "PoolClient::get_sorted", level = "debug", skip(self), err)]
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        ctx: Option<Context>,
+    ) -> Result<Pools, ReplyError> {
+        let filter = match filter {
+            Filter::Node(id) => Some(get_pools_request::Filter::Node(NodeFilter {
+                node_id: id.into(),
+            })),
+            Filter::Pool(id) => Some(get_pools_request::Filter::Pool(PoolFilter {
+                pool_id: id.into(),
+            })),
+            Filter::NodePool(node_id, pool_id) => {
+                Some(get_pools_request::Filter::NodePool(NodePoolFilter {
                     node_id: node_id.into(),
                     pool_id: pool_id.into(),
-                })),
-            },
-            _ => GetPoolsRequest { filter: None },
+                }))
+            }
+            Filter::None => None,
+            filter => return Err(ReplyError::invalid_filter(ResourceKind::Pool, &filter)),
+        };
+        let req = GetPoolsRequest {
+            filter,
+            sorting: sorting.map(Into::into),
         };
         let req = self.request(req, ctx, MessageIdVs::GetPools);
         let response = self.client().get_pools(req).await?.into_inner();
@@ -98,9 +98,12 @@ mod test {
     mod server {
         use crate::{
             context::Context,
-            operations::pool::{
-                test::TimeoutTester,
-                traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+            operations::{
+                pool::{
+                    test::TimeoutTester,
+                    traits::{CreatePoolInfo, DestroyPoolInfo, PoolOperations},
+                },
+                Sorting,
             },
         };
         use common_lib::{
@@ -126,9 +129,10 @@ mod test {
             ) -> Result<(), ReplyError> {
                 todo!()
             }
-            async fn get(
+            async fn get_sorted(
                 &self,
                 _filter: Filter,
+                _sorting: Option<Sorting>,
                 _ctx: Option<Context>,
             ) -> Result<Pools, ReplyError> {
                 let tester = TimeoutTester::new();
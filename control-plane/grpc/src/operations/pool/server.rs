@@ -65,7 +65,8 @@ impl PoolGrpc for PoolServer {
     ) -> Result<tonic::Response<pool::GetPoolsReply>, tonic::Status> {
         let req: GetPoolsRequest = request.into_inner();
         let filter = req.filter.map(Into::into).unwrap_or_default();
-        match self.service.get(filter, None).await {
+        let sorting = req.sorting.map(Into::into);
+        match self.service.get_sorted(filter, sorting, None).await {
             Ok(pools) => Ok(Response::new(GetPoolsReply {
                 reply: Some(get_pools_reply::Reply::Pools(pools.into())),
             })),
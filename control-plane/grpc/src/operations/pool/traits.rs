@@ -1,16 +1,21 @@
 use crate::{
     common,
     context::Context,
+    operations::Sorting,
     pool,
     pool::{get_pools_request, CreatePoolRequest, DestroyPoolRequest},
 };
 use common_lib::{
     transport_api::{v0::Pools, ReplyError, ResourceKind},
     types::v0::{
-        store::pool::{PoolLabel, PoolSpec, PoolSpecStatus},
+        store::{
+            epoch_secs, from_epoch_secs,
+            pool::{PoolLabel, PoolSpec, PoolSpecStatus},
+        },
         transport,
         transport::{
-            CreatePool, DestroyPool, Filter, NodeId, Pool, PoolDeviceUri, PoolId, PoolState,
+            CreatePool, DestroyPool, DiscoveredPool, Filter, NodeId, Pool, PoolDeviceUri, PoolId,
+            PoolState, DEFAULT_POOL_CLUSTER_SIZE,
         },
     },
 };
@@ -32,7 +37,16 @@ pub trait PoolOperations: Send + Sync {
         ctx: Option<Context>,
     ) -> Result<(), ReplyError>;
     /// Get pools based on the filters
-    async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError>;
+    async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Pools, ReplyError> {
+        self.get_sorted(filter, None, ctx).await
+    }
+    /// Get pools based on the filters, sorted by the given field
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        ctx: Option<Context>,
+    ) -> Result<Pools, ReplyError>;
 }
 
 impl TryFrom<pool::PoolDefinition> for PoolSpec {
@@ -78,6 +92,8 @@ impl TryFrom<pool::PoolDefinition> for PoolSpec {
             },
             sequencer: Default::default(),
             operation: None,
+            created_at: pool_meta.created_at.map(from_epoch_secs),
+            updated_at: pool_meta.updated_at.map(from_epoch_secs),
         })
     }
 }
@@ -102,6 +118,11 @@ impl TryFrom<pool::PoolState> for PoolState {
             },
             capacity: pool_state.capacity,
             used: pool_state.used,
+            // not set by the agents which predate the cluster size
+            cluster_size: match pool_state.cluster_size {
+                0 => DEFAULT_POOL_CLUSTER_SIZE,
+                cluster_size => cluster_size,
+            },
         })
     }
 }
@@ -117,8 +138,9 @@ impl TryFrom<pool::Pool> for Pool {
             None => None,
             Some(pool_definition) => Some(PoolSpec::try_from(pool_definition)?),
         };
+        let conditions = pool.conditions.into_iter().map(From::from).collect();
         match Pool::try_new(pool_spec, pool_state) {
-            Some(pool) => Ok(pool),
+            Some(pool) => Ok(pool.with_conditions(conditions)),
             None => Err(ReplyError::missing_argument(
                 ResourceKind::Pool,
                 "pool.spec and pool.state",
@@ -142,6 +164,8 @@ impl From<PoolSpec> for pool::PoolDefinition {
             metadata: Some(pool::Metadata {
                 uuid: None,
                 spec_status: spec_status as i32,
+                created_at: pool_spec.created_at.map(epoch_secs),
+                updated_at: pool_spec.updated_at.map(epoch_secs),
             }),
         }
     }
@@ -156,6 +180,7 @@ impl From<PoolState> for pool::PoolState {
             status: pool_state.status as i32,
             capacity: pool_state.capacity,
             used: pool_state.used,
+            cluster_size: pool_state.cluster_size,
         }
     }
 }
@@ -167,6 +192,7 @@ impl From<Pool> for pool::Pool {
         pool::Pool {
             definition: pool_definition,
             state: pool_state,
+            conditions: pool.conditions().iter().cloned().map(From::from).collect(),
         }
     }
 }
@@ -251,6 +277,25 @@ impl CreatePoolInfo for CreatePool {
     }
 }
 
+/// A discovered pool is imported by creating it on the disks which it was found on.
+impl CreatePoolInfo for DiscoveredPool {
+    fn pool_id(&self) -> PoolId {
+        self.id.clone()
+    }
+
+    fn node_id(&self) -> NodeId {
+        self.node.clone()
+    }
+
+    fn disks(&self) -> Vec<PoolDeviceUri> {
+        self.disks.clone()
+    }
+
+    fn labels(&self) -> Option<PoolLabel> {
+        None
+    }
+}
+
 impl CreatePoolInfo for CreatePoolRequest {
     fn pool_id(&self) -> PoolId {
         self.pool_id.clone().into()
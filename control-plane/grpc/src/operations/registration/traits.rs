@@ -1,6 +1,6 @@
 use common_lib::{
     transport_api::ReplyError,
-    types::v0::transport::{APIVersion, Deregister, NodeId, Register},
+    types::v0::transport::{APIVersion, Deregister, NodeId, PeerLatency, Register},
 };
 use rpc::v1::registration::{DeregisterRequest, RegisterRequest};
 
@@ -24,6 +24,8 @@ pub trait RegisterInfo: Send + Sync {
     fn grpc_endpoint(&self) -> String;
     /// api-version supported by the dataplane
     fn api_version(&self) -> Option<Vec<APIVersion>>;
+    /// Round-trip times measured by the IoEngine instance to its peers
+    fn peer_latencies(&self) -> Vec<PeerLatency>;
 }
 
 /// Trait to be implemented for Register operation
@@ -44,6 +46,10 @@ impl RegisterInfo for Register {
     fn api_version(&self) -> Option<Vec<APIVersion>> {
         self.api_versions.clone()
     }
+
+    fn peer_latencies(&self) -> Vec<PeerLatency> {
+        self.peer_latencies.clone()
+    }
 }
 
 impl RegisterInfo for RegisterRequest {
@@ -67,6 +73,11 @@ impl RegisterInfo for RegisterRequest {
                 .collect(),
         )
     }
+
+    fn peer_latencies(&self) -> Vec<PeerLatency> {
+        // the dataplane registration does not carry any round-trip times yet
+        vec![]
+    }
 }
 
 impl DeregisterInfo for Deregister {
@@ -87,6 +98,7 @@ impl From<&dyn RegisterInfo> for Register {
             id: register.node_id(),
             grpc_endpoint: register.grpc_endpoint(),
             api_versions: register.api_version(),
+            peer_latencies: register.peer_latencies(),
         }
     }
 }
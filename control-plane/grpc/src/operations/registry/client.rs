@@ -1,11 +1,31 @@
 use crate::{
     context::{Client, Context, TracedChannel},
-    operations::registry::traits::{GetSpecsInfo, GetStatesInfo, RegistryOperations},
-    registry::{get_specs_reply, get_states_reply, registry_grpc_client::RegistryGrpcClient},
+    operations::registry::traits::{
+        ClearPendingOperationInfo, ClearTombstoneInfo, GetClusterDefaultsInfo,
+        GetLatencyMatrixInfo, GetOrphansInfo, GetPendingOperationInfo, GetPendingOperationsInfo,
+        GetRateLimitsInfo, GetReconcilerPassesInfo, GetSchedulingTracesInfo, GetSpecsInfo,
+        GetStatesInfo, GetStoreHealthInfo, GetVolumeQuotasInfo, PatchSpecInfo, RefreshRegistryInfo,
+        RegistryOperations, ReleaseOperationGuardInfo, RetryOperationReplayInfo,
+        SetClusterDefaultsInfo, SetReconcilerPassInfo, SetVolumeQuotasInfo,
+    },
+    registry::{
+        cluster_defaults_reply, get_latency_matrix_reply, get_orphans_reply,
+        get_pending_operations_reply, get_rate_limits_reply, get_scheduling_traces_reply,
+        get_specs_reply, get_states_reply, get_store_health_reply, patch_spec_reply,
+        pending_operation_reply, reconciler_passes_reply, refresh_registry_reply,
+        registry_grpc_client::RegistryGrpcClient, volume_quotas_reply,
+    },
 };
 use common_lib::{
     transport_api::{ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{MessageIdVs, Specs, States},
+    types::v0::{
+        store::defaults::ClusterDefaults,
+        transport::{
+            LatencyMatrix, MessageIdVs, OrphanReport, PendingOperation, PendingOperations,
+            RateLimits, ReconcilerPassStates, RegistryRefresh, SchedulingTraces, SpecPatch, Specs,
+            States, StoreHealth, VolumeQuotasUsage,
+        },
+    },
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -64,4 +84,341 @@ impl RegistryOperations for RegistryClient {
             None => Err(ReplyError::invalid_response(ResourceKind::State)),
         }
     }
+
+    async fn clear_tombstone(
+        &self,
+        request: &dyn ClearTombstoneInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ClearTombstone);
+        let response = self.client().clear_tombstone(req).await?.into_inner();
+        match response.error {
+            None => Ok(()),
+            Some(err) => Err(err.into()),
+        }
+    }
+
+    async fn refresh_registry(
+        &self,
+        request: &dyn RefreshRegistryInfo,
+        ctx: Option<Context>,
+    ) -> Result<RegistryRefresh, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::RefreshRegistry);
+        let response = self.client().refresh_registry(req).await?.into_inner();
+        match response.reply {
+            Some(refresh_registry_reply) => match refresh_registry_reply {
+                refresh_registry_reply::Reply::Refresh(refresh) => Ok(refresh.into()),
+                refresh_registry_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
+
+    async fn get_cluster_defaults(
+        &self,
+        request: &dyn GetClusterDefaultsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ClusterDefaults, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetClusterDefaults);
+        let response = self.client().get_cluster_defaults(req).await?.into_inner();
+        match response.reply {
+            Some(cluster_defaults_reply) => match cluster_defaults_reply {
+                cluster_defaults_reply::Reply::Defaults(defaults) => {
+                    Ok(ClusterDefaults::try_from(defaults)?)
+                }
+                cluster_defaults_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    async fn set_cluster_defaults(
+        &self,
+        request: &dyn SetClusterDefaultsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ClusterDefaults, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::SetClusterDefaults);
+        let response = self.client().set_cluster_defaults(req).await?.into_inner();
+        match response.reply {
+            Some(cluster_defaults_reply) => match cluster_defaults_reply {
+                cluster_defaults_reply::Reply::Defaults(defaults) => {
+                    Ok(ClusterDefaults::try_from(defaults)?)
+                }
+                cluster_defaults_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    async fn get_store_health(
+        &self,
+        request: &dyn GetStoreHealthInfo,
+        ctx: Option<Context>,
+    ) -> Result<StoreHealth, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetStoreHealth);
+        let response = self.client().get_store_health(req).await?.into_inner();
+        match response.reply {
+            Some(get_store_health_reply) => match get_store_health_reply {
+                get_store_health_reply::Reply::Health(health) => Ok(health.into()),
+                get_store_health_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
+
+    async fn patch_spec(
+        &self,
+        request: &dyn PatchSpecInfo,
+        ctx: Option<Context>,
+    ) -> Result<SpecPatch, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::PatchSpec);
+        let response = self.client().patch_spec(req).await?.into_inner();
+        match response.reply {
+            Some(patch_spec_reply) => match patch_spec_reply {
+                patch_spec_reply::Reply::Patch(patch) => Ok(SpecPatch::try_from(patch)?),
+                patch_spec_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn get_pending_operation(
+        &self,
+        request: &dyn GetPendingOperationInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetPendingOperation);
+        let response = self.client().get_pending_operation(req).await?.into_inner();
+        match response.reply {
+            Some(pending_operation_reply) => match pending_operation_reply {
+                pending_operation_reply::Reply::Operation(operation) => {
+                    Ok(PendingOperation::try_from(operation)?)
+                }
+                pending_operation_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn clear_pending_operation(
+        &self,
+        request: &dyn ClearPendingOperationInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ClearPendingOperation);
+        let response = self
+            .client()
+            .clear_pending_operation(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(pending_operation_reply) => match pending_operation_reply {
+                pending_operation_reply::Reply::Operation(operation) => {
+                    Ok(PendingOperation::try_from(operation)?)
+                }
+                pending_operation_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn release_operation_guard(
+        &self,
+        request: &dyn ReleaseOperationGuardInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ReleaseOperationGuard);
+        let response = self
+            .client()
+            .release_operation_guard(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(pending_operation_reply) => match pending_operation_reply {
+                pending_operation_reply::Reply::Operation(operation) => {
+                    Ok(PendingOperation::try_from(operation)?)
+                }
+                pending_operation_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn get_pending_operations(
+        &self,
+        request: &dyn GetPendingOperationsInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperations, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetPendingOperations);
+        let response = self
+            .client()
+            .get_pending_operations(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(get_pending_operations_reply) => match get_pending_operations_reply {
+                get_pending_operations_reply::Reply::Operations(operations) => {
+                    Ok(PendingOperations::try_from(operations)?)
+                }
+                get_pending_operations_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn retry_operation_replay(
+        &self,
+        request: &dyn RetryOperationReplayInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::RetryOperationReplay);
+        let response = self
+            .client()
+            .retry_operation_replay(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(pending_operation_reply) => match pending_operation_reply {
+                pending_operation_reply::Reply::Operation(operation) => {
+                    Ok(PendingOperation::try_from(operation)?)
+                }
+                pending_operation_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn get_volume_quotas(
+        &self,
+        request: &dyn GetVolumeQuotasInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeQuotasUsage, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetVolumeQuotas);
+        let response = self.client().get_volume_quotas(req).await?.into_inner();
+        match response.reply {
+            Some(volume_quotas_reply) => match volume_quotas_reply {
+                volume_quotas_reply::Reply::Usage(usage) => Ok(VolumeQuotasUsage::try_from(usage)?),
+                volume_quotas_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    async fn set_volume_quotas(
+        &self,
+        request: &dyn SetVolumeQuotasInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeQuotasUsage, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::SetVolumeQuotas);
+        let response = self.client().set_volume_quotas(req).await?.into_inner();
+        match response.reply {
+            Some(volume_quotas_reply) => match volume_quotas_reply {
+                volume_quotas_reply::Reply::Usage(usage) => Ok(VolumeQuotasUsage::try_from(usage)?),
+                volume_quotas_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    async fn get_reconciler_passes(
+        &self,
+        request: &dyn GetReconcilerPassesInfo,
+        ctx: Option<Context>,
+    ) -> Result<ReconcilerPassStates, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetReconcilerPasses);
+        let response = self.client().get_reconciler_passes(req).await?.into_inner();
+        match response.reply {
+            Some(reconciler_passes_reply) => match reconciler_passes_reply {
+                reconciler_passes_reply::Reply::Passes(passes) => {
+                    Ok(ReconcilerPassStates::try_from(passes)?)
+                }
+                reconciler_passes_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn set_reconciler_pass(
+        &self,
+        request: &dyn SetReconcilerPassInfo,
+        ctx: Option<Context>,
+    ) -> Result<ReconcilerPassStates, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::SetReconcilerPass);
+        let response = self.client().set_reconciler_pass(req).await?.into_inner();
+        match response.reply {
+            Some(reconciler_passes_reply) => match reconciler_passes_reply {
+                reconciler_passes_reply::Reply::Passes(passes) => {
+                    Ok(ReconcilerPassStates::try_from(passes)?)
+                }
+                reconciler_passes_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        }
+    }
+
+    async fn get_rate_limits(
+        &self,
+        request: &dyn GetRateLimitsInfo,
+        ctx: Option<Context>,
+    ) -> Result<RateLimits, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetRateLimits);
+        let response = self.client().get_rate_limits(req).await?.into_inner();
+        match response.reply {
+            Some(get_rate_limits_reply) => match get_rate_limits_reply {
+                get_rate_limits_reply::Reply::Limits(limits) => Ok(RateLimits::try_from(limits)?),
+                get_rate_limits_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
+
+    async fn get_scheduling_traces(
+        &self,
+        request: &dyn GetSchedulingTracesInfo,
+        ctx: Option<Context>,
+    ) -> Result<SchedulingTraces, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetSchedulingTraces);
+        let response = self.client().get_scheduling_traces(req).await?.into_inner();
+        match response.reply {
+            Some(get_scheduling_traces_reply) => match get_scheduling_traces_reply {
+                get_scheduling_traces_reply::Reply::Traces(traces) => {
+                    Ok(SchedulingTraces::try_from(traces)?)
+                }
+                get_scheduling_traces_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    async fn get_latency_matrix(
+        &self,
+        request: &dyn GetLatencyMatrixInfo,
+        ctx: Option<Context>,
+    ) -> Result<LatencyMatrix, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetLatencyMatrix);
+        let response = self.client().get_latency_matrix(req).await?.into_inner();
+        match response.reply {
+            Some(get_latency_matrix_reply) => match get_latency_matrix_reply {
+                get_latency_matrix_reply::Reply::Matrix(matrix) => Ok(matrix.into()),
+                get_latency_matrix_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Node)),
+        }
+    }
+
+    async fn get_orphans(
+        &self,
+        request: &dyn GetOrphansInfo,
+        ctx: Option<Context>,
+    ) -> Result<OrphanReport, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetOrphans);
+        let response = self.client().get_orphans(req).await?.into_inner();
+        match response.reply {
+            Some(get_orphans_reply) => match get_orphans_reply {
+                get_orphans_reply::Reply::Report(report) => Ok(OrphanReport::try_from(report)?),
+                get_orphans_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Unknown)),
+        }
+    }
 }
@@ -1,9 +1,23 @@
 use crate::{
+    misc::traits::ValidateRequestTypes,
     operations::registry::traits::RegistryOperations,
     registry::{
-        get_specs_reply, get_states_reply,
+        cluster_defaults_reply, get_latency_matrix_reply, get_orphans_reply,
+        get_pending_operations_reply, get_rate_limits_reply, get_scheduling_traces_reply,
+        get_specs_reply, get_states_reply, get_store_health_reply, patch_spec_reply,
+        pending_operation_reply, reconciler_passes_reply, refresh_registry_reply,
         registry_grpc_server::{RegistryGrpc, RegistryGrpcServer},
-        GetSpecsReply, GetSpecsRequest, GetStatesReply, GetStatesRequest,
+        volume_quotas_reply, ClearPendingOperationRequest, ClearTombstoneReply,
+        ClearTombstoneRequest, ClusterDefaultsReply, GetClusterDefaultsRequest,
+        GetLatencyMatrixReply, GetLatencyMatrixRequest, GetOrphansReply, GetOrphansRequest,
+        GetPendingOperationRequest, GetPendingOperationsReply, GetPendingOperationsRequest,
+        GetRateLimitsReply, GetRateLimitsRequest, GetReconcilerPassesRequest,
+        GetSchedulingTracesReply, GetSchedulingTracesRequest, GetSpecsReply, GetSpecsRequest,
+        GetStatesReply, GetStatesRequest, GetStoreHealthReply, GetStoreHealthRequest,
+        GetVolumeQuotasRequest, PatchSpecReply, PatchSpecRequest, PendingOperationReply,
+        ReconcilerPassesReply, RefreshRegistryReply, RefreshRegistryRequest,
+        ReleaseOperationGuardRequest, RetryOperationReplayRequest, SetClusterDefaultsRequest,
+        SetReconcilerPassRequest, SetVolumeQuotasRequest, VolumeQuotasReply,
     },
 };
 use std::sync::Arc;
@@ -57,4 +71,270 @@ impl RegistryGrpc for RegistryServer {
             })),
         }
     }
+    async fn clear_tombstone(
+        &self,
+        request: tonic::Request<ClearTombstoneRequest>,
+    ) -> Result<tonic::Response<ClearTombstoneReply>, tonic::Status> {
+        let req: ClearTombstoneRequest = request.into_inner();
+        match self.service.clear_tombstone(&req, None).await {
+            Ok(()) => Ok(Response::new(ClearTombstoneReply { error: None })),
+            Err(err) => Ok(Response::new(ClearTombstoneReply {
+                error: Some(err.into()),
+            })),
+        }
+    }
+    async fn refresh_registry(
+        &self,
+        request: tonic::Request<RefreshRegistryRequest>,
+    ) -> Result<tonic::Response<RefreshRegistryReply>, tonic::Status> {
+        let req: RefreshRegistryRequest = request.into_inner();
+        match self.service.refresh_registry(&req, None).await {
+            Ok(refresh) => Ok(Response::new(RefreshRegistryReply {
+                reply: Some(refresh_registry_reply::Reply::Refresh(refresh.into())),
+            })),
+            Err(err) => Ok(Response::new(RefreshRegistryReply {
+                reply: Some(refresh_registry_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_cluster_defaults(
+        &self,
+        request: tonic::Request<GetClusterDefaultsRequest>,
+    ) -> Result<tonic::Response<ClusterDefaultsReply>, tonic::Status> {
+        let req: GetClusterDefaultsRequest = request.into_inner();
+        match self.service.get_cluster_defaults(&req, None).await {
+            Ok(defaults) => Ok(Response::new(ClusterDefaultsReply {
+                reply: Some(cluster_defaults_reply::Reply::Defaults(defaults.into())),
+            })),
+            Err(err) => Ok(Response::new(ClusterDefaultsReply {
+                reply: Some(cluster_defaults_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn set_cluster_defaults(
+        &self,
+        request: tonic::Request<SetClusterDefaultsRequest>,
+    ) -> Result<tonic::Response<ClusterDefaultsReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.set_cluster_defaults(&req, None).await {
+            Ok(defaults) => Ok(Response::new(ClusterDefaultsReply {
+                reply: Some(cluster_defaults_reply::Reply::Defaults(defaults.into())),
+            })),
+            Err(err) => Ok(Response::new(ClusterDefaultsReply {
+                reply: Some(cluster_defaults_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_store_health(
+        &self,
+        request: tonic::Request<GetStoreHealthRequest>,
+    ) -> Result<tonic::Response<GetStoreHealthReply>, tonic::Status> {
+        let req: GetStoreHealthRequest = request.into_inner();
+        match self.service.get_store_health(&req, None).await {
+            Ok(health) => Ok(Response::new(GetStoreHealthReply {
+                reply: Some(get_store_health_reply::Reply::Health(health.into())),
+            })),
+            Err(err) => Ok(Response::new(GetStoreHealthReply {
+                reply: Some(get_store_health_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn patch_spec(
+        &self,
+        request: tonic::Request<PatchSpecRequest>,
+    ) -> Result<tonic::Response<PatchSpecReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.patch_spec(&req, None).await {
+            Ok(patch) => Ok(Response::new(PatchSpecReply {
+                reply: Some(patch_spec_reply::Reply::Patch(patch.into())),
+            })),
+            Err(err) => Ok(Response::new(PatchSpecReply {
+                reply: Some(patch_spec_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_pending_operation(
+        &self,
+        request: tonic::Request<GetPendingOperationRequest>,
+    ) -> Result<tonic::Response<PendingOperationReply>, tonic::Status> {
+        let req: GetPendingOperationRequest = request.into_inner();
+        match self.service.get_pending_operation(&req, None).await {
+            Ok(operation) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Operation(operation.into())),
+            })),
+            Err(err) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn clear_pending_operation(
+        &self,
+        request: tonic::Request<ClearPendingOperationRequest>,
+    ) -> Result<tonic::Response<PendingOperationReply>, tonic::Status> {
+        let req: ClearPendingOperationRequest = request.into_inner();
+        match self.service.clear_pending_operation(&req, None).await {
+            Ok(operation) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Operation(operation.into())),
+            })),
+            Err(err) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn release_operation_guard(
+        &self,
+        request: tonic::Request<ReleaseOperationGuardRequest>,
+    ) -> Result<tonic::Response<PendingOperationReply>, tonic::Status> {
+        let req: ReleaseOperationGuardRequest = request.into_inner();
+        match self.service.release_operation_guard(&req, None).await {
+            Ok(operation) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Operation(operation.into())),
+            })),
+            Err(err) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_pending_operations(
+        &self,
+        request: tonic::Request<GetPendingOperationsRequest>,
+    ) -> Result<tonic::Response<GetPendingOperationsReply>, tonic::Status> {
+        let req: GetPendingOperationsRequest = request.into_inner();
+        match self.service.get_pending_operations(&req, None).await {
+            Ok(operations) => Ok(Response::new(GetPendingOperationsReply {
+                reply: Some(get_pending_operations_reply::Reply::Operations(
+                    operations.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(GetPendingOperationsReply {
+                reply: Some(get_pending_operations_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn retry_operation_replay(
+        &self,
+        request: tonic::Request<RetryOperationReplayRequest>,
+    ) -> Result<tonic::Response<PendingOperationReply>, tonic::Status> {
+        let req: RetryOperationReplayRequest = request.into_inner();
+        match self.service.retry_operation_replay(&req, None).await {
+            Ok(operation) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Operation(operation.into())),
+            })),
+            Err(err) => Ok(Response::new(PendingOperationReply {
+                reply: Some(pending_operation_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_volume_quotas(
+        &self,
+        request: tonic::Request<GetVolumeQuotasRequest>,
+    ) -> Result<tonic::Response<VolumeQuotasReply>, tonic::Status> {
+        let req: GetVolumeQuotasRequest = request.into_inner();
+        match self.service.get_volume_quotas(&req, None).await {
+            Ok(usage) => Ok(Response::new(VolumeQuotasReply {
+                reply: Some(volume_quotas_reply::Reply::Usage(usage.into())),
+            })),
+            Err(err) => Ok(Response::new(VolumeQuotasReply {
+                reply: Some(volume_quotas_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn set_volume_quotas(
+        &self,
+        request: tonic::Request<SetVolumeQuotasRequest>,
+    ) -> Result<tonic::Response<VolumeQuotasReply>, tonic::Status> {
+        let req: SetVolumeQuotasRequest = request.into_inner();
+        match self.service.set_volume_quotas(&req, None).await {
+            Ok(usage) => Ok(Response::new(VolumeQuotasReply {
+                reply: Some(volume_quotas_reply::Reply::Usage(usage.into())),
+            })),
+            Err(err) => Ok(Response::new(VolumeQuotasReply {
+                reply: Some(volume_quotas_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_reconciler_passes(
+        &self,
+        request: tonic::Request<GetReconcilerPassesRequest>,
+    ) -> Result<tonic::Response<ReconcilerPassesReply>, tonic::Status> {
+        let req: GetReconcilerPassesRequest = request.into_inner();
+        match self.service.get_reconciler_passes(&req, None).await {
+            Ok(passes) => Ok(Response::new(ReconcilerPassesReply {
+                reply: Some(reconciler_passes_reply::Reply::Passes(passes.into())),
+            })),
+            Err(err) => Ok(Response::new(ReconcilerPassesReply {
+                reply: Some(reconciler_passes_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn set_reconciler_pass(
+        &self,
+        request: tonic::Request<SetReconcilerPassRequest>,
+    ) -> Result<tonic::Response<ReconcilerPassesReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.set_reconciler_pass(&req, None).await {
+            Ok(passes) => Ok(Response::new(ReconcilerPassesReply {
+                reply: Some(reconciler_passes_reply::Reply::Passes(passes.into())),
+            })),
+            Err(err) => Ok(Response::new(ReconcilerPassesReply {
+                reply: Some(reconciler_passes_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_rate_limits(
+        &self,
+        request: tonic::Request<GetRateLimitsRequest>,
+    ) -> Result<tonic::Response<GetRateLimitsReply>, tonic::Status> {
+        let req: GetRateLimitsRequest = request.into_inner();
+        match self.service.get_rate_limits(&req, None).await {
+            Ok(limits) => Ok(Response::new(GetRateLimitsReply {
+                reply: Some(get_rate_limits_reply::Reply::Limits(limits.into())),
+            })),
+            Err(err) => Ok(Response::new(GetRateLimitsReply {
+                reply: Some(get_rate_limits_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_scheduling_traces(
+        &self,
+        request: tonic::Request<GetSchedulingTracesRequest>,
+    ) -> Result<tonic::Response<GetSchedulingTracesReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_scheduling_traces(&req, None).await {
+            Ok(traces) => Ok(Response::new(GetSchedulingTracesReply {
+                reply: Some(get_scheduling_traces_reply::Reply::Traces(traces.into())),
+            })),
+            Err(err) => Ok(Response::new(GetSchedulingTracesReply {
+                reply: Some(get_scheduling_traces_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_latency_matrix(
+        &self,
+        request: tonic::Request<GetLatencyMatrixRequest>,
+    ) -> Result<tonic::Response<GetLatencyMatrixReply>, tonic::Status> {
+        let req: GetLatencyMatrixRequest = request.into_inner();
+        match self.service.get_latency_matrix(&req, None).await {
+            Ok(matrix) => Ok(Response::new(GetLatencyMatrixReply {
+                reply: Some(get_latency_matrix_reply::Reply::Matrix(matrix.into())),
+            })),
+            Err(err) => Ok(Response::new(GetLatencyMatrixReply {
+                reply: Some(get_latency_matrix_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_orphans(
+        &self,
+        request: tonic::Request<GetOrphansRequest>,
+    ) -> Result<tonic::Response<GetOrphansReply>, tonic::Status> {
+        let req: GetOrphansRequest = request.into_inner();
+        match self.service.get_orphans(&req, None).await {
+            Ok(report) => Ok(Response::new(GetOrphansReply {
+                reply: Some(get_orphans_reply::Reply::Report(report.into())),
+            })),
+            Err(err) => Ok(Response::new(GetOrphansReply {
+                reply: Some(get_orphans_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
 }
@@ -1,18 +1,51 @@
 use crate::{
+    common,
     context::Context,
+    misc::traits::{StringValue, ValidateRequestTypes},
     registry,
-    registry::{GetSpecsRequest, GetStatesRequest},
+    registry::{
+        ClearPendingOperationRequest, ClearTombstoneRequest, GetClusterDefaultsRequest,
+        GetLatencyMatrixRequest, GetOrphansRequest, GetPendingOperationRequest,
+        GetPendingOperationsRequest, GetRateLimitsRequest, GetReconcilerPassesRequest,
+        GetSchedulingTracesRequest, GetSpecsRequest, GetStatesRequest, GetStoreHealthRequest,
+        GetVolumeQuotasRequest, PatchSpecRequest, RefreshRegistryRequest,
+        ReleaseOperationGuardRequest, RetryOperationReplayRequest, SetClusterDefaultsRequest,
+        SetReconcilerPassRequest, SetVolumeQuotasRequest,
+    },
+    volume,
 };
 use common_lib::{
-    transport_api::ReplyError,
+    transport_api::{ReplyError, ResourceKind},
     types::v0::{
         store,
-        store::{nexus::NexusSpec, pool::PoolSpec, replica::ReplicaSpec, volume::VolumeSpec},
+        store::{
+            defaults::ClusterDefaults,
+            epoch_secs, from_epoch_secs,
+            nexus::NexusSpec,
+            pool::PoolSpec,
+            quota::{VolumeQuota, VolumeQuotas},
+            reconciler::ReconcilerPass,
+            replica::ReplicaSpec,
+            volume::VolumeSpec,
+            SpecStatus,
+        },
         transport,
-        transport::{GetSpecs, GetStates, Specs},
+        transport::{
+            CallerThrottles, ClearPendingOperation, ClearTombstone, GetClusterDefaults,
+            GetLatencyMatrix, GetOrphans, GetPendingOperation, GetPendingOperations, GetRateLimits,
+            GetReconcilerPasses, GetSchedulingTraces, GetSpecs, GetStates, GetStoreHealth,
+            GetVolumeQuotas, LatencyMatrix, MethodClass, NodeId, NodeLatencies, NodeRefresh,
+            OperationReplay, Orphan, OrphanCategory, OrphanRemediation, OrphanReport, PatchSpec,
+            PeerLatency, PendingOperation, PendingOperations, PoolCandidate, PoolId, RateLimit,
+            RateLimits, ReconcilerPassState, ReconcilerPassStates, RefreshRegistry,
+            RegistryRefresh, ReleaseOperationGuard, ResourceOrphans, RetryOperationReplay,
+            SchedulingAttempt, SchedulingTrace, SchedulingTraces, SetClusterDefaults,
+            SetReconcilerPass, SetVolumeQuotas, SpecPatch, Specs, StoreEndpointHealth, StoreHealth,
+            VolumeId, VolumeQuotaUsage, VolumeQuotasUsage,
+        },
     },
 };
-use std::convert::TryFrom;
+use std::{convert::TryFrom, str::FromStr};
 
 /// Trait implemented by services which support registry operations.
 #[tonic::async_trait]
@@ -29,6 +62,121 @@ pub trait RegistryOperations: Send + Sync {
         get_spec: &dyn GetStatesInfo,
         ctx: Option<Context>,
     ) -> Result<transport::States, ReplyError>;
+    /// Clear the tombstone of a destroyed resource
+    async fn clear_tombstone(
+        &self,
+        request: &dyn ClearTombstoneInfo,
+        ctx: Option<Context>,
+    ) -> Result<(), ReplyError>;
+    /// Reload the resources of all the online nodes into the registry, returning once complete
+    async fn refresh_registry(
+        &self,
+        request: &dyn RefreshRegistryInfo,
+        ctx: Option<Context>,
+    ) -> Result<RegistryRefresh, ReplyError>;
+    /// Get the cluster-wide default parameters of the volumes
+    async fn get_cluster_defaults(
+        &self,
+        request: &dyn GetClusterDefaultsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ClusterDefaults, ReplyError>;
+    /// Replace the cluster-wide default parameters of the volumes, returning the new defaults
+    async fn set_cluster_defaults(
+        &self,
+        request: &dyn SetClusterDefaultsInfo,
+        ctx: Option<Context>,
+    ) -> Result<ClusterDefaults, ReplyError>;
+    /// Get the health of the persistent store endpoints
+    async fn get_store_health(
+        &self,
+        request: &dyn GetStoreHealthInfo,
+        ctx: Option<Context>,
+    ) -> Result<StoreHealth, ReplyError>;
+    /// Apply a JSON merge patch to the persisted spec of a resource, or only try it if dry run
+    async fn patch_spec(
+        &self,
+        request: &dyn PatchSpecInfo,
+        ctx: Option<Context>,
+    ) -> Result<SpecPatch, ReplyError>;
+    /// Get the operation pending on the persisted spec of a resource
+    async fn get_pending_operation(
+        &self,
+        request: &dyn GetPendingOperationInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError>;
+    /// Clear the operation pending on the persisted spec of a resource, returning what was cleared
+    async fn clear_pending_operation(
+        &self,
+        request: &dyn ClearPendingOperationInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError>;
+    /// Forcibly release the operation guard of a resource, returning what was pending on it
+    async fn release_operation_guard(
+        &self,
+        request: &dyn ReleaseOperationGuardInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError>;
+    /// Get the operations pending on the specs of all the resources
+    async fn get_pending_operations(
+        &self,
+        request: &dyn GetPendingOperationsInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperations, ReplyError>;
+    /// Retry replaying the operation pending on the spec of a resource, which was given up on as
+    /// stuck, returning what was pending on it
+    async fn retry_operation_replay(
+        &self,
+        request: &dyn RetryOperationReplayInfo,
+        ctx: Option<Context>,
+    ) -> Result<PendingOperation, ReplyError>;
+    /// Get the per label quotas of the volumes, along with their usage
+    async fn get_volume_quotas(
+        &self,
+        request: &dyn GetVolumeQuotasInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeQuotasUsage, ReplyError>;
+    /// Replace the per label quotas of the volumes, returning the usage of the new quotas
+    async fn set_volume_quotas(
+        &self,
+        request: &dyn SetVolumeQuotasInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeQuotasUsage, ReplyError>;
+    /// Get the runtime state of the reconciler passes
+    async fn get_reconciler_passes(
+        &self,
+        request: &dyn GetReconcilerPassesInfo,
+        ctx: Option<Context>,
+    ) -> Result<ReconcilerPassStates, ReplyError>;
+    /// Enable or disable a reconciler pass, returning the state of all the passes
+    async fn set_reconciler_pass(
+        &self,
+        request: &dyn SetReconcilerPassInfo,
+        ctx: Option<Context>,
+    ) -> Result<ReconcilerPassStates, ReplyError>;
+    /// Get the rate limits of the gRPC requests, along with the callers which were throttled
+    async fn get_rate_limits(
+        &self,
+        request: &dyn GetRateLimitsInfo,
+        ctx: Option<Context>,
+    ) -> Result<RateLimits, ReplyError>;
+    /// Get the scheduling traces of the latest volume creations
+    async fn get_scheduling_traces(
+        &self,
+        request: &dyn GetSchedulingTracesInfo,
+        ctx: Option<Context>,
+    ) -> Result<SchedulingTraces, ReplyError>;
+    /// Get the round-trip times which the nodes measured to their peers
+    async fn get_latency_matrix(
+        &self,
+        request: &dyn GetLatencyMatrixInfo,
+        ctx: Option<Context>,
+    ) -> Result<LatencyMatrix, ReplyError>;
+    /// Get the replicas and nexuses which only exist as a spec or as a dataplane object
+    async fn get_orphans(
+        &self,
+        request: &dyn GetOrphansInfo,
+        ctx: Option<Context>,
+    ) -> Result<OrphanReport, ReplyError>;
 }
 
 /// GetSpecsInfo trait for the get_specs operation
@@ -45,6 +193,1427 @@ impl GetStatesInfo for GetStates {}
 
 impl GetStatesInfo for GetStatesRequest {}
 
+/// RefreshRegistryInfo trait for the refresh_registry operation
+pub trait RefreshRegistryInfo: Send + Sync {}
+
+impl RefreshRegistryInfo for RefreshRegistry {}
+
+impl RefreshRegistryInfo for RefreshRegistryRequest {}
+
+impl From<&dyn RefreshRegistryInfo> for RefreshRegistryRequest {
+    fn from(_: &dyn RefreshRegistryInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn RefreshRegistryInfo> for RefreshRegistry {
+    fn from(_: &dyn RefreshRegistryInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<registry::NodeRefresh> for NodeRefresh {
+    fn from(value: registry::NodeRefresh) -> Self {
+        Self {
+            node: value.node.into(),
+            error: value.error,
+        }
+    }
+}
+
+impl From<NodeRefresh> for registry::NodeRefresh {
+    fn from(value: NodeRefresh) -> Self {
+        Self {
+            node: value.node.to_string(),
+            error: value.error,
+        }
+    }
+}
+
+impl From<registry::RegistryRefresh> for RegistryRefresh {
+    fn from(value: registry::RegistryRefresh) -> Self {
+        Self {
+            nodes: value.nodes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<RegistryRefresh> for registry::RegistryRefresh {
+    fn from(value: RegistryRefresh) -> Self {
+        Self {
+            nodes: value.nodes.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// GetClusterDefaultsInfo trait for the get_cluster_defaults operation
+pub trait GetClusterDefaultsInfo: Send + Sync {}
+
+impl GetClusterDefaultsInfo for GetClusterDefaults {}
+
+impl GetClusterDefaultsInfo for GetClusterDefaultsRequest {}
+
+impl From<&dyn GetClusterDefaultsInfo> for GetClusterDefaultsRequest {
+    fn from(_: &dyn GetClusterDefaultsInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetClusterDefaultsInfo> for GetClusterDefaults {
+    fn from(_: &dyn GetClusterDefaultsInfo) -> Self {
+        Self {}
+    }
+}
+
+/// SetClusterDefaultsInfo trait for the set_cluster_defaults operation
+pub trait SetClusterDefaultsInfo: Send + Sync + std::fmt::Debug {
+    /// The new defaults
+    fn defaults(&self) -> ClusterDefaults;
+}
+
+impl SetClusterDefaultsInfo for SetClusterDefaults {
+    fn defaults(&self) -> ClusterDefaults {
+        self.defaults.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to SetClusterDefaultsRequest type
+#[derive(Debug)]
+pub struct ValidatedSetClusterDefaultsRequest {
+    defaults: ClusterDefaults,
+}
+
+impl SetClusterDefaultsInfo for ValidatedSetClusterDefaultsRequest {
+    fn defaults(&self) -> ClusterDefaults {
+        self.defaults.clone()
+    }
+}
+
+impl ValidateRequestTypes for SetClusterDefaultsRequest {
+    type Validated = ValidatedSetClusterDefaultsRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        match self.defaults {
+            Some(defaults) => Ok(ValidatedSetClusterDefaultsRequest {
+                defaults: ClusterDefaults::try_from(defaults)?,
+            }),
+            None => Err(ReplyError::missing_argument(
+                ResourceKind::Volume,
+                "set_cluster_defaults_request.defaults",
+            )),
+        }
+    }
+}
+
+impl From<&dyn SetClusterDefaultsInfo> for SetClusterDefaultsRequest {
+    fn from(data: &dyn SetClusterDefaultsInfo) -> Self {
+        Self {
+            defaults: Some(data.defaults().into()),
+        }
+    }
+}
+
+impl From<&dyn SetClusterDefaultsInfo> for SetClusterDefaults {
+    fn from(data: &dyn SetClusterDefaultsInfo) -> Self {
+        Self {
+            defaults: data.defaults(),
+        }
+    }
+}
+
+impl TryFrom<registry::ClusterDefaults> for ClusterDefaults {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::ClusterDefaults) -> Result<Self, Self::Error> {
+        Ok(Self {
+            replicas: value.replicas,
+            thin: value.thin,
+            placement: match volume::VolumePlacementPolicy::from_i32(value.placement) {
+                Some(placement) => placement.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "cluster_defaults.placement",
+                        "".to_string(),
+                    ))
+                }
+            },
+        })
+    }
+}
+
+impl From<ClusterDefaults> for registry::ClusterDefaults {
+    fn from(value: ClusterDefaults) -> Self {
+        Self {
+            replicas: value.replicas,
+            thin: value.thin,
+            placement: volume::VolumePlacementPolicy::from(value.placement) as i32,
+        }
+    }
+}
+
+/// GetStoreHealthInfo trait for the get_store_health operation
+pub trait GetStoreHealthInfo: Send + Sync {}
+
+impl GetStoreHealthInfo for GetStoreHealth {}
+
+impl GetStoreHealthInfo for GetStoreHealthRequest {}
+
+impl From<&dyn GetStoreHealthInfo> for GetStoreHealthRequest {
+    fn from(_: &dyn GetStoreHealthInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetStoreHealthInfo> for GetStoreHealth {
+    fn from(_: &dyn GetStoreHealthInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<registry::StoreEndpointHealth> for StoreEndpointHealth {
+    fn from(value: registry::StoreEndpointHealth) -> Self {
+        Self {
+            endpoint: value.endpoint,
+            reachable: value.reachable,
+            error: value.error,
+        }
+    }
+}
+
+impl From<StoreEndpointHealth> for registry::StoreEndpointHealth {
+    fn from(value: StoreEndpointHealth) -> Self {
+        Self {
+            endpoint: value.endpoint,
+            reachable: value.reachable,
+            error: value.error,
+        }
+    }
+}
+
+impl From<registry::StoreHealth> for StoreHealth {
+    fn from(value: registry::StoreHealth) -> Self {
+        Self {
+            active: value.active,
+            endpoints: value.endpoints.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<StoreHealth> for registry::StoreHealth {
+    fn from(value: StoreHealth) -> Self {
+        Self {
+            active: value.active,
+            endpoints: value.endpoints.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// GetVolumeQuotasInfo trait for the get_volume_quotas operation
+pub trait GetVolumeQuotasInfo: Send + Sync {}
+
+impl GetVolumeQuotasInfo for GetVolumeQuotas {}
+
+impl GetVolumeQuotasInfo for GetVolumeQuotasRequest {}
+
+impl From<&dyn GetVolumeQuotasInfo> for GetVolumeQuotasRequest {
+    fn from(_: &dyn GetVolumeQuotasInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetVolumeQuotasInfo> for GetVolumeQuotas {
+    fn from(_: &dyn GetVolumeQuotasInfo) -> Self {
+        Self {}
+    }
+}
+
+/// SetVolumeQuotasInfo trait for the set_volume_quotas operation
+pub trait SetVolumeQuotasInfo: Send + Sync + std::fmt::Debug {
+    /// The new quotas
+    fn quotas(&self) -> VolumeQuotas;
+}
+
+impl SetVolumeQuotasInfo for SetVolumeQuotas {
+    fn quotas(&self) -> VolumeQuotas {
+        self.quotas.clone()
+    }
+}
+
+impl SetVolumeQuotasInfo for SetVolumeQuotasRequest {
+    fn quotas(&self) -> VolumeQuotas {
+        VolumeQuotas {
+            quotas: self.quotas.iter().cloned().map(From::from).collect(),
+        }
+    }
+}
+
+impl From<&dyn SetVolumeQuotasInfo> for SetVolumeQuotasRequest {
+    fn from(data: &dyn SetVolumeQuotasInfo) -> Self {
+        Self {
+            quotas: data.quotas().quotas.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl From<&dyn SetVolumeQuotasInfo> for SetVolumeQuotas {
+    fn from(data: &dyn SetVolumeQuotasInfo) -> Self {
+        Self {
+            quotas: data.quotas(),
+        }
+    }
+}
+
+impl From<registry::VolumeQuota> for VolumeQuota {
+    fn from(value: registry::VolumeQuota) -> Self {
+        Self {
+            key: value.key,
+            value: value.value,
+            capacity: value.capacity,
+        }
+    }
+}
+
+impl From<VolumeQuota> for registry::VolumeQuota {
+    fn from(value: VolumeQuota) -> Self {
+        Self {
+            key: value.key,
+            value: value.value,
+            capacity: value.capacity,
+        }
+    }
+}
+
+impl TryFrom<registry::VolumeQuotasUsage> for VolumeQuotasUsage {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::VolumeQuotasUsage) -> Result<Self, Self::Error> {
+        let quotas = value
+            .quotas
+            .into_iter()
+            .map(|usage| match usage.quota {
+                Some(quota) => Ok(VolumeQuotaUsage {
+                    quota: quota.into(),
+                    used: usage.used,
+                }),
+                None => Err(ReplyError::missing_argument(
+                    ResourceKind::Volume,
+                    "volume_quota_usage.quota",
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { quotas })
+    }
+}
+
+impl From<VolumeQuotasUsage> for registry::VolumeQuotasUsage {
+    fn from(value: VolumeQuotasUsage) -> Self {
+        Self {
+            quotas: value
+                .quotas
+                .into_iter()
+                .map(|usage| registry::VolumeQuotaUsage {
+                    quota: Some(usage.quota.into()),
+                    used: usage.used,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GetReconcilerPassesInfo trait for the get_reconciler_passes operation
+pub trait GetReconcilerPassesInfo: Send + Sync {}
+
+impl GetReconcilerPassesInfo for GetReconcilerPasses {}
+
+impl GetReconcilerPassesInfo for GetReconcilerPassesRequest {}
+
+impl From<&dyn GetReconcilerPassesInfo> for GetReconcilerPassesRequest {
+    fn from(_: &dyn GetReconcilerPassesInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetReconcilerPassesInfo> for GetReconcilerPasses {
+    fn from(_: &dyn GetReconcilerPassesInfo) -> Self {
+        Self {}
+    }
+}
+
+/// SetReconcilerPassInfo trait for the set_reconciler_pass operation
+pub trait SetReconcilerPassInfo: Send + Sync + std::fmt::Debug {
+    /// The reconciler pass
+    fn pass(&self) -> ReconcilerPass;
+    /// Whether the pass should run
+    fn enabled(&self) -> bool;
+}
+
+impl SetReconcilerPassInfo for SetReconcilerPass {
+    fn pass(&self) -> ReconcilerPass {
+        self.pass
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+/// Intermediate structure that validates the conversion to SetReconcilerPassRequest type
+#[derive(Debug)]
+pub struct ValidatedSetReconcilerPassRequest {
+    pass: ReconcilerPass,
+    enabled: bool,
+}
+
+impl SetReconcilerPassInfo for ValidatedSetReconcilerPassRequest {
+    fn pass(&self) -> ReconcilerPass {
+        self.pass
+    }
+
+    fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+impl ValidateRequestTypes for SetReconcilerPassRequest {
+    type Validated = ValidatedSetReconcilerPassRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        match ReconcilerPass::from_str(&self.pass) {
+            Ok(pass) => Ok(ValidatedSetReconcilerPassRequest {
+                pass,
+                enabled: self.enabled,
+            }),
+            Err(error) => Err(ReplyError::invalid_argument(
+                ResourceKind::Spec,
+                "set_reconciler_pass_request.pass",
+                error,
+            )),
+        }
+    }
+}
+
+impl From<&dyn SetReconcilerPassInfo> for SetReconcilerPassRequest {
+    fn from(data: &dyn SetReconcilerPassInfo) -> Self {
+        Self {
+            pass: data.pass().to_string(),
+            enabled: data.enabled(),
+        }
+    }
+}
+
+impl From<&dyn SetReconcilerPassInfo> for SetReconcilerPass {
+    fn from(data: &dyn SetReconcilerPassInfo) -> Self {
+        Self {
+            pass: data.pass(),
+            enabled: data.enabled(),
+        }
+    }
+}
+
+impl TryFrom<registry::ReconcilerPassStates> for ReconcilerPassStates {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::ReconcilerPassStates) -> Result<Self, Self::Error> {
+        let passes = value
+            .passes
+            .into_iter()
+            .map(|state| match ReconcilerPass::from_str(&state.pass) {
+                Ok(pass) => Ok(ReconcilerPassState {
+                    pass,
+                    enabled: state.enabled,
+                    skipped: state.skipped,
+                }),
+                Err(error) => Err(ReplyError::invalid_argument(
+                    ResourceKind::Spec,
+                    "reconciler_pass_state.pass",
+                    error,
+                )),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self { passes })
+    }
+}
+
+impl From<ReconcilerPassStates> for registry::ReconcilerPassStates {
+    fn from(value: ReconcilerPassStates) -> Self {
+        Self {
+            passes: value
+                .passes
+                .into_iter()
+                .map(|state| registry::ReconcilerPassState {
+                    pass: state.pass.to_string(),
+                    enabled: state.enabled,
+                    skipped: state.skipped,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GetRateLimitsInfo trait for the get_rate_limits operation
+pub trait GetRateLimitsInfo: Send + Sync {}
+
+impl GetRateLimitsInfo for GetRateLimits {}
+
+impl GetRateLimitsInfo for GetRateLimitsRequest {}
+
+impl From<&dyn GetRateLimitsInfo> for GetRateLimitsRequest {
+    fn from(_: &dyn GetRateLimitsInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetRateLimitsInfo> for GetRateLimits {
+    fn from(_: &dyn GetRateLimitsInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<registry::RateLimit> for RateLimit {
+    fn from(value: registry::RateLimit) -> Self {
+        Self {
+            rate: value.rate,
+            burst: value.burst,
+        }
+    }
+}
+
+impl From<RateLimit> for registry::RateLimit {
+    fn from(value: RateLimit) -> Self {
+        Self {
+            rate: value.rate,
+            burst: value.burst,
+        }
+    }
+}
+
+impl From<registry::MethodClass> for MethodClass {
+    fn from(value: registry::MethodClass) -> Self {
+        match value {
+            registry::MethodClass::Mutation => Self::Mutation,
+            registry::MethodClass::Read => Self::Read,
+        }
+    }
+}
+
+impl From<MethodClass> for registry::MethodClass {
+    fn from(value: MethodClass) -> Self {
+        match value {
+            MethodClass::Mutation => Self::Mutation,
+            MethodClass::Read => Self::Read,
+        }
+    }
+}
+
+impl TryFrom<registry::RateLimits> for RateLimits {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::RateLimits) -> Result<Self, Self::Error> {
+        let throttled = value
+            .throttled
+            .into_iter()
+            .map(
+                |caller| match registry::MethodClass::from_i32(caller.class) {
+                    Some(class) => Ok(CallerThrottles {
+                        identity: caller.identity,
+                        class: class.into(),
+                        throttled: caller.throttled,
+                    }),
+                    None => Err(ReplyError::invalid_argument(
+                        ResourceKind::Unknown,
+                        "caller_throttles.class",
+                        caller.class.to_string(),
+                    )),
+                },
+            )
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            mutations: value.mutations.map(Into::into),
+            reads: value.reads.map(Into::into),
+            throttled,
+        })
+    }
+}
+
+impl From<RateLimits> for registry::RateLimits {
+    fn from(value: RateLimits) -> Self {
+        Self {
+            mutations: value.mutations.map(Into::into),
+            reads: value.reads.map(Into::into),
+            throttled: value
+                .throttled
+                .into_iter()
+                .map(|caller| registry::CallerThrottles {
+                    identity: caller.identity,
+                    class: registry::MethodClass::from(caller.class) as i32,
+                    throttled: caller.throttled,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GetSchedulingTracesInfo trait for the get_scheduling_traces operation
+pub trait GetSchedulingTracesInfo: Send + Sync + std::fmt::Debug {
+    /// Only get the traces of this volume, if specified
+    fn volume(&self) -> Option<VolumeId>;
+}
+
+impl GetSchedulingTracesInfo for GetSchedulingTraces {
+    fn volume(&self) -> Option<VolumeId> {
+        self.volume.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetSchedulingTracesRequest type
+#[derive(Debug)]
+pub struct ValidatedGetSchedulingTracesRequest {
+    volume: Option<VolumeId>,
+}
+
+impl GetSchedulingTracesInfo for ValidatedGetSchedulingTracesRequest {
+    fn volume(&self) -> Option<VolumeId> {
+        self.volume.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetSchedulingTracesRequest {
+    type Validated = ValidatedGetSchedulingTracesRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetSchedulingTracesRequest {
+            volume: match self.volume_id {
+                Some(volume_id) => Some(VolumeId::try_from(StringValue(Some(volume_id)))?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl From<&dyn GetSchedulingTracesInfo> for GetSchedulingTracesRequest {
+    fn from(data: &dyn GetSchedulingTracesInfo) -> Self {
+        Self {
+            volume_id: data.volume().map(|volume| volume.to_string()),
+        }
+    }
+}
+
+impl From<&dyn GetSchedulingTracesInfo> for GetSchedulingTraces {
+    fn from(data: &dyn GetSchedulingTracesInfo) -> Self {
+        Self {
+            volume: data.volume(),
+        }
+    }
+}
+
+impl From<registry::SchedulingAttempt> for SchedulingAttempt {
+    fn from(value: registry::SchedulingAttempt) -> Self {
+        Self {
+            filters: value.filters,
+            sorters: value.sorters,
+            candidates: value
+                .candidates
+                .into_iter()
+                .map(|candidate| PoolCandidate {
+                    pool: PoolId::from(candidate.pool),
+                    node: NodeId::from(candidate.node),
+                    rejected_by: candidate.rejected_by,
+                })
+                .collect(),
+            order: value.order.into_iter().map(PoolId::from).collect(),
+        }
+    }
+}
+
+impl From<SchedulingAttempt> for registry::SchedulingAttempt {
+    fn from(value: SchedulingAttempt) -> Self {
+        Self {
+            filters: value.filters,
+            sorters: value.sorters,
+            candidates: value
+                .candidates
+                .into_iter()
+                .map(|candidate| registry::PoolCandidate {
+                    pool: candidate.pool.to_string(),
+                    node: candidate.node.to_string(),
+                    rejected_by: candidate.rejected_by,
+                })
+                .collect(),
+            order: value
+                .order
+                .into_iter()
+                .map(|pool| pool.to_string())
+                .collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::SchedulingTraces> for SchedulingTraces {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::SchedulingTraces) -> Result<Self, Self::Error> {
+        let traces = value
+            .traces
+            .into_iter()
+            .map(|trace| {
+                Ok(SchedulingTrace {
+                    volume: VolumeId::try_from(StringValue(Some(trace.volume_id)))?,
+                    started_at: from_epoch_secs(trace.started_at),
+                    attempts: trace.attempts.into_iter().map(Into::into).collect(),
+                    error: trace.error,
+                })
+            })
+            .collect::<Result<Vec<_>, ReplyError>>()?;
+        Ok(Self { traces })
+    }
+}
+
+impl From<SchedulingTraces> for registry::SchedulingTraces {
+    fn from(value: SchedulingTraces) -> Self {
+        Self {
+            traces: value
+                .traces
+                .into_iter()
+                .map(|trace| registry::SchedulingTrace {
+                    volume_id: trace.volume.to_string(),
+                    started_at: epoch_secs(trace.started_at),
+                    attempts: trace.attempts.into_iter().map(Into::into).collect(),
+                    error: trace.error,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GetLatencyMatrixInfo trait for the get_latency_matrix operation
+pub trait GetLatencyMatrixInfo: Send + Sync {}
+
+impl GetLatencyMatrixInfo for GetLatencyMatrix {}
+
+impl GetLatencyMatrixInfo for GetLatencyMatrixRequest {}
+
+impl From<&dyn GetLatencyMatrixInfo> for GetLatencyMatrixRequest {
+    fn from(_: &dyn GetLatencyMatrixInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetLatencyMatrixInfo> for GetLatencyMatrix {
+    fn from(_: &dyn GetLatencyMatrixInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<registry::LatencyMatrix> for LatencyMatrix {
+    fn from(value: registry::LatencyMatrix) -> Self {
+        Self {
+            nodes: value
+                .nodes
+                .into_iter()
+                .map(|node| NodeLatencies {
+                    node: NodeId::from(node.node_id),
+                    peers: node
+                        .peers
+                        .into_iter()
+                        .map(|peer| PeerLatency {
+                            node: NodeId::from(peer.node_id),
+                            rtt_us: peer.rtt_us,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<LatencyMatrix> for registry::LatencyMatrix {
+    fn from(value: LatencyMatrix) -> Self {
+        Self {
+            nodes: value
+                .nodes
+                .into_iter()
+                .map(|node| registry::NodeLatencies {
+                    node_id: node.node.to_string(),
+                    peers: node
+                        .peers
+                        .into_iter()
+                        .map(|peer| registry::PeerLatency {
+                            node_id: peer.node.to_string(),
+                            rtt_us: peer.rtt_us,
+                        })
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// GetOrphansInfo trait for the get_orphans operation
+pub trait GetOrphansInfo: Send + Sync {}
+
+impl GetOrphansInfo for GetOrphans {}
+
+impl GetOrphansInfo for GetOrphansRequest {}
+
+impl From<&dyn GetOrphansInfo> for GetOrphansRequest {
+    fn from(_: &dyn GetOrphansInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetOrphansInfo> for GetOrphans {
+    fn from(_: &dyn GetOrphansInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<registry::OrphanCategory> for OrphanCategory {
+    fn from(value: registry::OrphanCategory) -> Self {
+        match value {
+            registry::OrphanCategory::ControlPlane => Self::ControlPlane,
+            registry::OrphanCategory::Dataplane => Self::Dataplane,
+        }
+    }
+}
+
+impl From<OrphanCategory> for registry::OrphanCategory {
+    fn from(value: OrphanCategory) -> Self {
+        match value {
+            OrphanCategory::ControlPlane => Self::ControlPlane,
+            OrphanCategory::Dataplane => Self::Dataplane,
+        }
+    }
+}
+
+impl From<registry::OrphanRemediation> for OrphanRemediation {
+    fn from(value: registry::OrphanRemediation) -> Self {
+        match value {
+            registry::OrphanRemediation::Recreate => Self::Recreate,
+            registry::OrphanRemediation::Adopt => Self::Adopt,
+            registry::OrphanRemediation::Destroy => Self::Destroy,
+            registry::OrphanRemediation::Ignore => Self::Ignore,
+        }
+    }
+}
+
+impl From<OrphanRemediation> for registry::OrphanRemediation {
+    fn from(value: OrphanRemediation) -> Self {
+        match value {
+            OrphanRemediation::Recreate => Self::Recreate,
+            OrphanRemediation::Adopt => Self::Adopt,
+            OrphanRemediation::Destroy => Self::Destroy,
+            OrphanRemediation::Ignore => Self::Ignore,
+        }
+    }
+}
+
+impl TryFrom<registry::Orphan> for Orphan {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::Orphan) -> Result<Self, Self::Error> {
+        let category = match registry::OrphanCategory::from_i32(value.category) {
+            Some(category) => category.into(),
+            None => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::Unknown,
+                    "orphan.category",
+                    value.category.to_string(),
+                ))
+            }
+        };
+        let remediation = match registry::OrphanRemediation::from_i32(value.remediation) {
+            Some(remediation) => remediation.into(),
+            None => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::Unknown,
+                    "orphan.remediation",
+                    value.remediation.to_string(),
+                ))
+            }
+        };
+        Ok(Self {
+            id: value.id,
+            category,
+            node: value.node_id.map(NodeId::from),
+            pool: value.pool_id.map(PoolId::from),
+            age_secs: value.age_secs,
+            remediation,
+            reason: value.reason,
+        })
+    }
+}
+
+impl From<Orphan> for registry::Orphan {
+    fn from(value: Orphan) -> Self {
+        Self {
+            id: value.id,
+            category: registry::OrphanCategory::from(value.category) as i32,
+            node_id: value.node.map(|node| node.to_string()),
+            pool_id: value.pool.map(|pool| pool.to_string()),
+            age_secs: value.age_secs,
+            remediation: registry::OrphanRemediation::from(value.remediation) as i32,
+            reason: value.reason,
+        }
+    }
+}
+
+impl TryFrom<registry::ResourceOrphans> for ResourceOrphans {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::ResourceOrphans) -> Result<Self, Self::Error> {
+        Ok(Self {
+            control_plane: value
+                .control_plane
+                .into_iter()
+                .map(Orphan::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            dataplane: value
+                .dataplane
+                .into_iter()
+                .map(Orphan::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<ResourceOrphans> for registry::ResourceOrphans {
+    fn from(value: ResourceOrphans) -> Self {
+        Self {
+            control_plane: value.control_plane.into_iter().map(Into::into).collect(),
+            dataplane: value.dataplane.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::OrphanReport> for OrphanReport {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::OrphanReport) -> Result<Self, Self::Error> {
+        Ok(Self {
+            replicas: value
+                .replicas
+                .map(ResourceOrphans::try_from)
+                .transpose()?
+                .unwrap_or_default(),
+            nexuses: value
+                .nexuses
+                .map(ResourceOrphans::try_from)
+                .transpose()?
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl From<OrphanReport> for registry::OrphanReport {
+    fn from(value: OrphanReport) -> Self {
+        Self {
+            replicas: Some(value.replicas.into()),
+            nexuses: Some(value.nexuses.into()),
+        }
+    }
+}
+
+/// ClearTombstoneInfo trait for the clear_tombstone operation
+pub trait ClearTombstoneInfo: Send + Sync + std::fmt::Debug {
+    /// Kind of the destroyed resource
+    fn kind(&self) -> ResourceKind;
+    /// Id of the destroyed resource
+    fn id(&self) -> String;
+}
+
+impl ClearTombstoneInfo for ClearTombstone {
+    fn kind(&self) -> ResourceKind {
+        self.kind.clone()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl ClearTombstoneInfo for ClearTombstoneRequest {
+    fn kind(&self) -> ResourceKind {
+        common::ResourceKind::from_i32(self.kind)
+            .map(Into::into)
+            .unwrap_or(ResourceKind::Unknown)
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl From<&dyn ClearTombstoneInfo> for ClearTombstoneRequest {
+    fn from(data: &dyn ClearTombstoneInfo) -> Self {
+        let kind: common::ResourceKind = data.kind().into();
+        Self {
+            kind: kind as i32,
+            id: data.id(),
+        }
+    }
+}
+
+impl From<&dyn ClearTombstoneInfo> for ClearTombstone {
+    fn from(data: &dyn ClearTombstoneInfo) -> Self {
+        Self {
+            kind: data.kind(),
+            id: data.id(),
+        }
+    }
+}
+
+/// PatchSpecInfo trait for the patch_spec operation
+pub trait PatchSpecInfo: Send + Sync + std::fmt::Debug {
+    /// Kind of the resource
+    fn kind(&self) -> ResourceKind;
+    /// Id of the resource
+    fn id(&self) -> String;
+    /// The JSON merge patch
+    fn patch(&self) -> serde_json::Value;
+    /// Only return the patched spec, without applying it
+    fn dry_run(&self) -> bool;
+    /// Token returned by the dry run of the same patch
+    fn confirm(&self) -> Option<String>;
+}
+
+impl PatchSpecInfo for PatchSpec {
+    fn kind(&self) -> ResourceKind {
+        self.kind.clone()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn patch(&self) -> serde_json::Value {
+        self.patch.clone()
+    }
+
+    fn dry_run(&self) -> bool {
+        self.dry_run
+    }
+
+    fn confirm(&self) -> Option<String> {
+        self.confirm.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to PatchSpecRequest type
+#[derive(Debug)]
+pub struct ValidatedPatchSpecRequest {
+    inner: PatchSpecRequest,
+    patch: serde_json::Value,
+}
+
+impl PatchSpecInfo for ValidatedPatchSpecRequest {
+    fn kind(&self) -> ResourceKind {
+        common::ResourceKind::from_i32(self.inner.kind)
+            .map(Into::into)
+            .unwrap_or(ResourceKind::Unknown)
+    }
+
+    fn id(&self) -> String {
+        self.inner.id.clone()
+    }
+
+    fn patch(&self) -> serde_json::Value {
+        self.patch.clone()
+    }
+
+    fn dry_run(&self) -> bool {
+        self.inner.dry_run
+    }
+
+    fn confirm(&self) -> Option<String> {
+        self.inner.confirm.clone()
+    }
+}
+
+impl ValidateRequestTypes for PatchSpecRequest {
+    type Validated = ValidatedPatchSpecRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        match serde_json::from_str(&self.patch) {
+            Ok(patch) => Ok(ValidatedPatchSpecRequest { inner: self, patch }),
+            Err(error) => Err(ReplyError::invalid_argument(
+                ResourceKind::Spec,
+                "patch_spec_request.patch",
+                error.to_string(),
+            )),
+        }
+    }
+}
+
+impl From<&dyn PatchSpecInfo> for PatchSpecRequest {
+    fn from(data: &dyn PatchSpecInfo) -> Self {
+        let kind: common::ResourceKind = data.kind().into();
+        Self {
+            kind: kind as i32,
+            id: data.id(),
+            patch: data.patch().to_string(),
+            dry_run: data.dry_run(),
+            confirm: data.confirm(),
+        }
+    }
+}
+
+impl From<&dyn PatchSpecInfo> for PatchSpec {
+    fn from(data: &dyn PatchSpecInfo) -> Self {
+        Self {
+            kind: data.kind(),
+            id: data.id(),
+            patch: data.patch(),
+            dry_run: data.dry_run(),
+            confirm: data.confirm(),
+        }
+    }
+}
+
+impl TryFrom<registry::SpecPatch> for SpecPatch {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::SpecPatch) -> Result<Self, Self::Error> {
+        let json = |value: &str| {
+            serde_json::from_str(value)
+                .map_err(|_| ReplyError::invalid_response(ResourceKind::Spec))
+        };
+        Ok(Self {
+            before: json(&value.before)?,
+            after: json(&value.after)?,
+            confirm: value.confirm,
+            applied: value.applied,
+        })
+    }
+}
+
+impl From<SpecPatch> for registry::SpecPatch {
+    fn from(value: SpecPatch) -> Self {
+        Self {
+            before: value.before.to_string(),
+            after: value.after.to_string(),
+            confirm: value.confirm,
+            applied: value.applied,
+        }
+    }
+}
+
+/// GetPendingOperationInfo trait for the get_pending_operation operation
+pub trait GetPendingOperationInfo: Send + Sync + std::fmt::Debug {
+    /// Kind of the resource
+    fn kind(&self) -> ResourceKind;
+    /// Id of the resource
+    fn id(&self) -> String;
+}
+
+impl GetPendingOperationInfo for GetPendingOperation {
+    fn kind(&self) -> ResourceKind {
+        self.kind.clone()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl GetPendingOperationInfo for GetPendingOperationRequest {
+    fn kind(&self) -> ResourceKind {
+        common::ResourceKind::from_i32(self.kind)
+            .map(Into::into)
+            .unwrap_or(ResourceKind::Unknown)
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl From<&dyn GetPendingOperationInfo> for GetPendingOperationRequest {
+    fn from(data: &dyn GetPendingOperationInfo) -> Self {
+        let kind: common::ResourceKind = data.kind().into();
+        Self {
+            kind: kind as i32,
+            id: data.id(),
+        }
+    }
+}
+
+impl From<&dyn GetPendingOperationInfo> for GetPendingOperation {
+    fn from(data: &dyn GetPendingOperationInfo) -> Self {
+        Self {
+            kind: data.kind(),
+            id: data.id(),
+        }
+    }
+}
+
+/// ClearPendingOperationInfo trait for the clear_pending_operation operation
+pub trait ClearPendingOperationInfo: Send + Sync + std::fmt::Debug {
+    /// Kind of the resource
+    fn kind(&self) -> ResourceKind;
+    /// Id of the resource
+    fn id(&self) -> String;
+    /// Clear the operation even if it may have taken effect
+    fn force(&self) -> bool;
+}
+
+impl ClearPendingOperationInfo for ClearPendingOperation {
+    fn kind(&self) -> ResourceKind {
+        self.kind.clone()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn force(&self) -> bool {
+        self.force
+    }
+}
+
+impl ClearPendingOperationInfo for ClearPendingOperationRequest {
+    fn kind(&self) -> ResourceKind {
+        common::ResourceKind::from_i32(self.kind)
+            .map(Into::into)
+            .unwrap_or(ResourceKind::Unknown)
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+
+    fn force(&self) -> bool {
+        self.force
+    }
+}
+
+impl From<&dyn ClearPendingOperationInfo> for ClearPendingOperationRequest {
+    fn from(data: &dyn ClearPendingOperationInfo) -> Self {
+        let kind: common::ResourceKind = data.kind().into();
+        Self {
+            kind: kind as i32,
+            id: data.id(),
+            force: data.force(),
+        }
+    }
+}
+
+impl From<&dyn ClearPendingOperationInfo> for ClearPendingOperation {
+    fn from(data: &dyn ClearPendingOperationInfo) -> Self {
+        Self {
+            kind: data.kind(),
+            id: data.id(),
+            force: data.force(),
+        }
+    }
+}
+
+/// ReleaseOperationGuardInfo trait for the release_operation_guard operation
+pub trait ReleaseOperationGuardInfo: Send + Sync + std::fmt::Debug {
+    /// Kind of the resource
+    fn kind(&self) -> ResourceKind;
+    /// Id of the resource
+    fn id(&self) -> String;
+}
+
+impl ReleaseOperationGuardInfo for ReleaseOperationGuard {
+    fn kind(&self) -> ResourceKind {
+        self.kind.clone()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl ReleaseOperationGuardInfo for ReleaseOperationGuardRequest {
+    fn kind(&self) -> ResourceKind {
+        common::ResourceKind::from_i32(self.kind)
+            .map(Into::into)
+            .unwrap_or(ResourceKind::Unknown)
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl From<&dyn ReleaseOperationGuardInfo> for ReleaseOperationGuardRequest {
+    fn from(data: &dyn ReleaseOperationGuardInfo) -> Self {
+        let kind: common::ResourceKind = data.kind().into();
+        Self {
+            kind: kind as i32,
+            id: data.id(),
+        }
+    }
+}
+
+impl From<&dyn ReleaseOperationGuardInfo> for ReleaseOperationGuard {
+    fn from(data: &dyn ReleaseOperationGuardInfo) -> Self {
+        Self {
+            kind: data.kind(),
+            id: data.id(),
+        }
+    }
+}
+
+/// GetPendingOperationsInfo trait for the get_pending_operations operation
+pub trait GetPendingOperationsInfo: Send + Sync {}
+
+impl GetPendingOperationsInfo for GetPendingOperations {}
+
+impl GetPendingOperationsInfo for GetPendingOperationsRequest {}
+
+impl From<&dyn GetPendingOperationsInfo> for GetPendingOperationsRequest {
+    fn from(_: &dyn GetPendingOperationsInfo) -> Self {
+        Self {}
+    }
+}
+
+impl From<&dyn GetPendingOperationsInfo> for GetPendingOperations {
+    fn from(_: &dyn GetPendingOperationsInfo) -> Self {
+        Self {}
+    }
+}
+
+/// RetryOperationReplayInfo trait for the retry_operation_replay operation
+pub trait RetryOperationReplayInfo: Send + Sync + std::fmt::Debug {
+    /// Kind of the resource
+    fn kind(&self) -> ResourceKind;
+    /// Id of the resource
+    fn id(&self) -> String;
+}
+
+impl RetryOperationReplayInfo for RetryOperationReplay {
+    fn kind(&self) -> ResourceKind {
+        self.kind.clone()
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl RetryOperationReplayInfo for RetryOperationReplayRequest {
+    fn kind(&self) -> ResourceKind {
+        common::ResourceKind::from_i32(self.kind)
+            .map(Into::into)
+            .unwrap_or(ResourceKind::Unknown)
+    }
+
+    fn id(&self) -> String {
+        self.id.clone()
+    }
+}
+
+impl From<&dyn RetryOperationReplayInfo> for RetryOperationReplayRequest {
+    fn from(data: &dyn RetryOperationReplayInfo) -> Self {
+        let kind: common::ResourceKind = data.kind().into();
+        Self {
+            kind: kind as i32,
+            id: data.id(),
+        }
+    }
+}
+
+impl From<&dyn RetryOperationReplayInfo> for RetryOperationReplay {
+    fn from(data: &dyn RetryOperationReplayInfo) -> Self {
+        Self {
+            kind: data.kind(),
+            id: data.id(),
+        }
+    }
+}
+
+impl From<registry::OperationReplay> for OperationReplay {
+    fn from(value: registry::OperationReplay) -> Self {
+        Self {
+            attempts: value.attempts,
+            stuck: value.stuck,
+        }
+    }
+}
+
+impl From<OperationReplay> for registry::OperationReplay {
+    fn from(value: OperationReplay) -> Self {
+        Self {
+            attempts: value.attempts,
+            stuck: value.stuck,
+        }
+    }
+}
+
+impl TryFrom<registry::PendingOperations> for PendingOperations {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::PendingOperations) -> Result<Self, Self::Error> {
+        Ok(Self {
+            operations: value
+                .operations
+                .into_iter()
+                .map(PendingOperation::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<PendingOperations> for registry::PendingOperations {
+    fn from(value: PendingOperations) -> Self {
+        Self {
+            operations: value.operations.into_iter().map(From::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<registry::PendingOperation> for PendingOperation {
+    type Error = ReplyError;
+
+    fn try_from(value: registry::PendingOperation) -> Result<Self, Self::Error> {
+        let status = match common::SpecStatus::from_i32(value.status) {
+            Some(common::SpecStatus::Creating) => SpecStatus::Creating,
+            Some(common::SpecStatus::Created) => SpecStatus::Created(()),
+            Some(common::SpecStatus::Deleting) => SpecStatus::Deleting,
+            Some(common::SpecStatus::Deleted) => SpecStatus::Deleted,
+            None => return Err(ReplyError::invalid_response(ResourceKind::Spec)),
+        };
+        let operation = match value.operation {
+            Some(operation) => Some(
+                serde_json::from_str(&operation)
+                    .map_err(|_| ReplyError::invalid_response(ResourceKind::Spec))?,
+            ),
+            None => None,
+        };
+        Ok(Self {
+            kind: common::ResourceKind::from_i32(value.kind)
+                .map(Into::into)
+                .unwrap_or(ResourceKind::Unknown),
+            id: value.id,
+            status,
+            operation,
+            result: value.result,
+            busy: value.busy,
+            replay: value.replay.map(From::from),
+        })
+    }
+}
+
+impl From<PendingOperation> for registry::PendingOperation {
+    fn from(value: PendingOperation) -> Self {
+        let kind: common::ResourceKind = value.kind.into();
+        let status = match value.status {
+            SpecStatus::Creating => common::SpecStatus::Creating,
+            SpecStatus::Created(_) => common::SpecStatus::Created,
+            SpecStatus::Deleting => common::SpecStatus::Deleting,
+            SpecStatus::Deleted => common::SpecStatus::Deleted,
+        };
+        Self {
+            kind: kind as i32,
+            id: value.id,
+            status: status as i32,
+            operation: value.operation.map(|operation| operation.to_string()),
+            result: value.result,
+            busy: value.busy,
+            replay: value.replay.map(From::from),
+        }
+    }
+}
+
 impl From<&dyn GetSpecsInfo> for GetSpecsRequest {
     fn from(_: &dyn GetSpecsInfo) -> Self {
         Self {}
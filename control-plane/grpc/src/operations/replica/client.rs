@@ -4,7 +4,7 @@ use crate::{
         PoolReplicaFilter, ReplicaFilter, VolumeFilter,
     },
     context::{Client, Context, TracedChannel},
-    operations::replica::traits::ReplicaOperations,
+    operations::{replica::traits::ReplicaOperations, Sorting},
     replica::{
         create_replica_reply, get_replicas_reply, get_replicas_request,
         replica_grpc_client::ReplicaGrpcClient, share_replica_reply, GetReplicasRequest,
@@ -60,61 +60,59 @@ impl ReplicaOperations for ReplicaClient {
         }
     }
 
-    #[tracing::instrument(name = "ReplicaClient::get", level = "debug", skip(self), err)]
-    async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Replicas, ReplyError> {
-        let req: GetReplicasRequest = match filter {
-            Filter::Node(id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::Node(NodeFilter {
-                    node_id: id.into(),
-                })),
-            },
-            Filter::Pool(id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::Pool(PoolFilter {
-                    pool_id: id.into(),
-                })),
-            },
-            Filter::NodePool(node_id, pool_id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::NodePool(NodePoolFilter {
+    #[tracing::instrument(name = "ReplicaClient::get_sorted", level = "debug", skip(self), err)]
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        ctx: Option<Context>,
+    ) -> Result<Replicas, ReplyError> {
+        let filter = match filter {
+            Filter::Node(id) => Some(get_replicas_request::Filter::Node(NodeFilter {
+                node_id: id.into(),
+            })),
+            Filter::Pool(id) => Some(get_replicas_request::Filter::Pool(PoolFilter {
+                pool_id: id.into(),
+            })),
+            Filter::NodePool(node_id, pool_id) => {
+                Some(get_replicas_request::Filter::NodePool(NodePoolFilter {
+                    node_id: node_id.into(),
+                    pool_id: pool_id.into(),
+                }))
+            }
+            Filter::NodePoolReplica(node_id, pool_id, replica_id) => Some(
+                get_replicas_request::Filter::NodePoolReplica(NodePoolReplicaFilter {
                     node_id: node_id.into(),
                     pool_id: pool_id.into(),
-                })),
-            },
-            Filter::NodePoolReplica(node_id, pool_id, replica_id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::NodePoolReplica(
-                    NodePoolReplicaFilter {
-                        node_id: node_id.into(),
-                        pool_id: pool_id.into(),
-                        replica_id: replica_id.to_string(),
-                    },
-                )),
-            },
-            Filter::NodeReplica(node_id, replica_id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::NodeReplica(
-                    NodeReplicaFilter {
-                        node_id: node_id.into(),
-                        replica_id: replica_id.to_string(),
-                    },
-                )),
-            },
-            Filter::PoolReplica(pool_id, replica_id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::PoolReplica(
-                    PoolReplicaFilter {
-                        pool_id: pool_id.into(),
-                        replica_id: replica_id.to_string(),
-                    },
-                )),
-            },
-            Filter::Replica(replica_id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::Replica(ReplicaFilter {
                     replica_id: replica_id.to_string(),
-                })),
-            },
-            Filter::Volume(volume_id) => GetReplicasRequest {
-                filter: Some(get_replicas_request::Filter::Volume(VolumeFilter {
-                    volume_id: volume_id.to_string(),
-                })),
-            },
-            _ => GetReplicasRequest { filter: None },
+                }),
+            ),
+            Filter::NodeReplica(node_id, replica_id) => Some(
+                get_replicas_request::Filter::NodeReplica(NodeReplicaFilter {
+                    node_id: node_id.into(),
+                    replica_id: replica_id.to_string(),
+                }),
+            ),
+            Filter::PoolReplica(pool_id, replica_id) => Some(
+                get_replicas_request::Filter::PoolReplica(PoolReplicaFilter {
+                    pool_id: pool_id.into(),
+                    replica_id: replica_id.to_string(),
+                }),
+            ),
+            Filter::Replica(replica_id) => {
+                Some(get_replicas_request::Filter::Replica(ReplicaFilter {
+                    replica_id: replica_id.to_string(),
+                }))
+            }
+            Filter::Volume(volume_id) => Some(get_replicas_request::Filter::Volume(VolumeFilter {
+                volume_id: volume_id.to_string(),
+            })),
+            Filter::None => None,
+            filter => return Err(ReplyError::invalid_filter(ResourceKind::Replica, &filter)),
+        };
+        let req = GetReplicasRequest {
+            filter,
+            sorting: sorting.map(Into::into),
         };
         let req = self.request(req, ctx, MessageIdVs::GetReplicas);
         let response = self.client().get_replicas(req).await?.into_inner();
@@ -69,7 +69,8 @@ impl ReplicaGrpc for ReplicaServer {
             Some(filter) => Filter::try_from(filter)?,
             None => Filter::None,
         };
-        match self.service.get(filter, None).await {
+        let sorting = req.sorting.map(Into::into);
+        match self.service.get_sorted(filter, sorting, None).await {
             Ok(replicas) => Ok(Response::new(GetReplicasReply {
                 reply: Some(get_replicas_reply::Reply::Replicas(replicas.into())),
             })),
@@ -2,6 +2,7 @@ use crate::{
     common,
     context::Context,
     misc::traits::{StringValue, ValidateRequestTypes},
+    operations::Sorting,
     replica,
     replica::{
         get_replicas_request, CreateReplicaRequest, DestroyReplicaRequest, ShareReplicaRequest,
@@ -11,7 +12,10 @@ use crate::{
 use common_lib::{
     transport_api::{v0::Replicas, ReplyError, ResourceKind},
     types::v0::{
-        store::replica::{ReplicaOperation, ReplicaOperationState, ReplicaSpec, ReplicaSpecStatus},
+        store::{
+            epoch_secs, from_epoch_secs,
+            replica::{ReplicaOperation, ReplicaOperationState, ReplicaSpec, ReplicaSpecStatus},
+        },
         transport,
         transport::{
             CreateReplica, DestroyReplica, Filter, NexusId, NodeId, PoolId, Replica, ReplicaId,
@@ -31,7 +35,16 @@ pub trait ReplicaOperations: Send + Sync {
         ctx: Option<Context>,
     ) -> Result<Replica, ReplyError>;
     /// Get replicas based on filters
-    async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Replicas, ReplyError>;
+    async fn get(&self, filter: Filter, ctx: Option<Context>) -> Result<Replicas, ReplyError> {
+        self.get_sorted(filter, None, ctx).await
+    }
+    /// Get replicas based on filters, sorted by the given field
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        ctx: Option<Context>,
+    ) -> Result<Replicas, ReplyError>;
     /// Destroy a replica
     async fn destroy(
         &self,
@@ -357,6 +370,8 @@ pub trait DestroyReplicaInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> ReplicaId;
     /// Delete by owners
     fn disowners(&self) -> ReplicaOwners;
+    /// Destroy even if the volume is left below the minimum replication factor
+    fn override_redundancy_check(&self) -> bool;
 }
 
 impl DestroyReplicaInfo for DestroyReplica {
@@ -379,6 +394,10 @@ impl DestroyReplicaInfo for DestroyReplica {
     fn disowners(&self) -> ReplicaOwners {
         self.disowners.clone()
     }
+
+    fn override_redundancy_check(&self) -> bool {
+        self.override_redundancy_check
+    }
 }
 
 /// Intermediate structure that validates the conversion to DestroyVolumeRequest type
@@ -409,6 +428,10 @@ impl DestroyReplicaInfo for ValidatedDestroyReplicaRequest {
     fn disowners(&self) -> ReplicaOwners {
         self.disowners.clone()
     }
+
+    fn override_redundancy_check(&self) -> bool {
+        self.inner.override_redundancy_check
+    }
 }
 
 impl ValidateRequestTypes for DestroyReplicaRequest {
@@ -624,6 +647,7 @@ impl From<&dyn DestroyReplicaInfo> for DestroyReplicaRequest {
             name: data.name().map(|name| name.to_string()),
             replica_id: Some(data.uuid().to_string()),
             disowners: Some(data.disowners().into()),
+            override_redundancy_check: data.override_redundancy_check(),
         }
     }
 }
@@ -636,6 +660,7 @@ impl From<&dyn DestroyReplicaInfo> for DestroyReplica {
             uuid: data.uuid(),
             name: data.name(),
             disowners: data.disowners(),
+            override_redundancy_check: data.override_redundancy_check(),
         }
     }
 }
@@ -795,7 +820,8 @@ impl TryFrom<replica::ReplicaOwners> for ReplicaOwners {
                 }
                 nexuses
             },
-        ))
+        )
+        .with_generation(value.generation))
     }
 }
 
@@ -804,6 +830,7 @@ impl From<ReplicaOwners> for replica::ReplicaOwners {
         replica::ReplicaOwners {
             volume: owners.volume().map(|id| id.to_string()),
             nexuses: owners.nexuses().iter().map(|id| id.to_string()).collect(),
+            generation: owners.generation(),
         }
     }
 }
@@ -826,6 +853,7 @@ impl TryFrom<replica::ReplicaSpec> for ReplicaSpec {
             name: ReplicaName::from_string(value.name),
             uuid: ReplicaId::try_from(StringValue(value.replica_id))?,
             size: value.size,
+            requested_size: value.requested_size,
             pool: value.pool_id.into(),
             share: match common::Protocol::from_i32(value.share) {
                 Some(share) => share.into(),
@@ -854,6 +882,10 @@ impl TryFrom<replica::ReplicaSpec> for ReplicaSpec {
                 operation: ReplicaOperation::Create,
                 result: op.result,
             }),
+            generation: value.generation,
+            created_at: value.created_at.map(from_epoch_secs),
+            updated_at: value.updated_at.map(from_epoch_secs),
+            out_of_sync: value.out_of_sync,
         })
     }
 }
@@ -875,6 +907,11 @@ impl From<ReplicaSpec> for replica::ReplicaSpec {
             operation: value.operation.map(|operation| common::SpecOperation {
                 result: operation.result,
             }),
+            created_at: value.created_at.map(epoch_secs),
+            updated_at: value.updated_at.map(epoch_secs),
+            generation: value.generation,
+            out_of_sync: value.out_of_sync,
+            requested_size: value.requested_size,
         }
     }
 }
@@ -3,20 +3,30 @@ use crate::{
     context::{Client, Context, TracedChannel},
     operations::{
         volume::traits::{
-            CreateVolumeInfo, DestroyVolumeInfo, PublishVolumeInfo, SetVolumeReplicaInfo,
-            ShareVolumeInfo, UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
+            CreateVolumeInfo, DestroyVolumeInfo, FreezeVolumeInfo, GetVolumeHistoryInfo,
+            GetVolumeRevisionDiffInfo, GetVolumeTopologyInfo, GetVolumesStatusInfo,
+            PatchVolumeInfo, PauseVolumeRebuildInfo, PublishVolumeInfo, ResumeVolumeRebuildInfo,
+            SetVolumeReplicaInfo, SetVolumeSourceReplicaInfo, ShareVolumeInfo, ThawVolumeInfo,
+            UnpublishVolumeInfo, UnshareVolumeInfo, VolumeOperations,
         },
-        Pagination,
+        Pagination, Sorting,
     },
     volume::{
-        create_volume_reply, get_volumes_reply, get_volumes_request, publish_volume_reply,
-        set_volume_replica_reply, share_volume_reply, unpublish_volume_reply,
+        create_volume_reply, freeze_volume_reply, get_volume_history_reply,
+        get_volume_revision_diff_reply, get_volume_topology_reply, get_volumes_reply,
+        get_volumes_request, get_volumes_status_reply, patch_volume_reply,
+        pause_volume_rebuild_reply, publish_volume_reply, resume_volume_rebuild_reply,
+        set_volume_replica_reply, set_volume_source_replica_reply, share_volume_reply,
+        thaw_volume_reply, unpublish_volume_reply, validate_create_volume_reply,
         volume_grpc_client::VolumeGrpcClient, GetVolumesRequest, ProbeRequest,
     },
 };
 use common_lib::{
     transport_api::{v0::Volumes, ReplyError, ResourceKind, TimeoutOptions},
-    types::v0::transport::{Filter, MessageIdVs, Volume},
+    types::v0::transport::{
+        Filter, MessageIdVs, Volume, VolumeHistory, VolumeRevisionDiff, VolumeTopologyTree,
+        VolumeViolations, VolumesStatus,
+    },
 };
 use std::{convert::TryFrom, ops::Deref};
 use tonic::transport::Uri;
@@ -63,24 +73,54 @@ impl VolumeOperations for VolumeClient {
         }
     }
 
-    #[tracing::instrument(name = "VolumeClient::get", level = "debug", skip(self), err)]
-    async fn get(
+    #[tracing::instrument(
+        name = "VolumeClient::validate_create",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn validate_create(
+        &self,
+        request: &dyn CreateVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeViolations, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ValidateCreateVolume);
+        let response = self
+            .client()
+            .validate_create_volume(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(validate_create_volume_reply) => match validate_create_volume_reply {
+                validate_create_volume_reply::Reply::Violations(violations) => {
+                    Ok(violations.into())
+                }
+                validate_create_volume_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::get_sorted", level = "debug", skip(self), err)]
+    async fn get_sorted(
         &self,
         filter: Filter,
+        sorting: Option<Sorting>,
         pagination: Option<Pagination>,
         ctx: Option<Context>,
     ) -> Result<Volumes, ReplyError> {
-        let req: GetVolumesRequest = match filter {
-            Filter::Volume(volume_id) => GetVolumesRequest {
-                filter: Some(get_volumes_request::Filter::Volume(VolumeFilter {
-                    volume_id: volume_id.to_string(),
-                })),
-                pagination: pagination.map(|p| p.into()),
-            },
-            _ => GetVolumesRequest {
-                filter: None,
-                pagination: pagination.map(|p| p.into()),
+        let req = GetVolumesRequest {
+            filter: match filter {
+                Filter::Volume(volume_id) => {
+                    Some(get_volumes_request::Filter::Volume(VolumeFilter {
+                        volume_id: volume_id.to_string(),
+                    }))
+                }
+                Filter::None => None,
+                filter => return Err(ReplyError::invalid_filter(ResourceKind::Volume, &filter)),
             },
+            pagination: pagination.map(|p| p.into()),
+            sorting: sorting.map(|s| s.into()),
         };
         let req = self.request(req, ctx, MessageIdVs::GetVolumes);
         let response = self.client().get_volumes(req).await?.into_inner();
@@ -93,6 +133,91 @@ impl VolumeOperations for VolumeClient {
         }
     }
 
+    #[tracing::instrument(name = "VolumeClient::get_status", level = "debug", skip(self), err)]
+    async fn get_status(
+        &self,
+        request: &dyn GetVolumesStatusInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumesStatus, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetVolumesStatus);
+        let response = self.client().get_volumes_status(req).await?.into_inner();
+        match response.reply {
+            Some(get_volumes_status_reply) => match get_volumes_status_reply {
+                get_volumes_status_reply::Reply::Status(status) => {
+                    Ok(VolumesStatus::try_from(status)?)
+                }
+                get_volumes_status_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::get_topology", level = "debug", skip(self), err)]
+    async fn get_topology(
+        &self,
+        request: &dyn GetVolumeTopologyInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeTopologyTree, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetVolumeTopology);
+        let response = self.client().get_volume_topology(req).await?.into_inner();
+        match response.reply {
+            Some(get_volume_topology_reply) => match get_volume_topology_reply {
+                get_volume_topology_reply::Reply::Topology(topology) => {
+                    Ok(VolumeTopologyTree::try_from(topology)?)
+                }
+                get_volume_topology_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::get_history", level = "debug", skip(self), err)]
+    async fn get_history(
+        &self,
+        request: &dyn GetVolumeHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeHistory, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetVolumeHistory);
+        let response = self.client().get_volume_history(req).await?.into_inner();
+        match response.reply {
+            Some(get_volume_history_reply) => match get_volume_history_reply {
+                get_volume_history_reply::Reply::History(history) => {
+                    Ok(VolumeHistory::try_from(history)?)
+                }
+                get_volume_history_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::get_revision_diff",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn get_revision_diff(
+        &self,
+        request: &dyn GetVolumeRevisionDiffInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeRevisionDiff, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::GetVolumeRevisionDiff);
+        let response = self
+            .client()
+            .get_volume_revision_diff(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(get_volume_revision_diff_reply) => match get_volume_revision_diff_reply {
+                get_volume_revision_diff_reply::Reply::Diff(diff) => {
+                    Ok(VolumeRevisionDiff::try_from(diff)?)
+                }
+                get_volume_revision_diff_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
     #[tracing::instrument(name = "VolumeClient::destroy", level = "debug", skip(self), err)]
     async fn destroy(
         &self,
@@ -189,6 +314,124 @@ impl VolumeOperations for VolumeClient {
         }
     }
 
+    #[tracing::instrument(
+        name = "VolumeClient::set_source_replica",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn set_source_replica(
+        &self,
+        request: &dyn SetVolumeSourceReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::SetVolumeSourceReplica);
+        let response = self
+            .client()
+            .set_volume_source_replica(req)
+            .await?
+            .into_inner();
+        match response.reply {
+            Some(set_volume_source_replica_reply) => match set_volume_source_replica_reply {
+                set_volume_source_replica_reply::Reply::Volume(volume) => {
+                    Ok(Volume::try_from(volume)?)
+                }
+                set_volume_source_replica_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::patch", level = "debug", skip(self), err)]
+    async fn patch(
+        &self,
+        request: &dyn PatchVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::PatchVolume);
+        let response = self.client().patch_volume(req).await?.into_inner();
+        match response.reply {
+            Some(patch_volume_reply) => match patch_volume_reply {
+                patch_volume_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                patch_volume_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::freeze", level = "debug", skip(self), err)]
+    async fn freeze(
+        &self,
+        request: &dyn FreezeVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::FreezeVolume);
+        let response = self.client().freeze_volume(req).await?.into_inner();
+        match response.reply {
+            Some(freeze_volume_reply) => match freeze_volume_reply {
+                freeze_volume_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                freeze_volume_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::thaw", level = "debug", skip(self), err)]
+    async fn thaw(
+        &self,
+        request: &dyn ThawVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ThawVolume);
+        let response = self.client().thaw_volume(req).await?.into_inner();
+        match response.reply {
+            Some(thaw_volume_reply) => match thaw_volume_reply {
+                thaw_volume_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                thaw_volume_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(name = "VolumeClient::pause_rebuild", level = "debug", skip(self), err)]
+    async fn pause_rebuild(
+        &self,
+        request: &dyn PauseVolumeRebuildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::PauseVolumeRebuild);
+        let response = self.client().pause_volume_rebuild(req).await?.into_inner();
+        match response.reply {
+            Some(pause_volume_rebuild_reply) => match pause_volume_rebuild_reply {
+                pause_volume_rebuild_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                pause_volume_rebuild_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
+    #[tracing::instrument(
+        name = "VolumeClient::resume_rebuild",
+        level = "debug",
+        skip(self),
+        err
+    )]
+    async fn resume_rebuild(
+        &self,
+        request: &dyn ResumeVolumeRebuildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError> {
+        let req = self.request(request, ctx, MessageIdVs::ResumeVolumeRebuild);
+        let response = self.client().resume_volume_rebuild(req).await?.into_inner();
+        match response.reply {
+            Some(resume_volume_rebuild_reply) => match resume_volume_rebuild_reply {
+                resume_volume_rebuild_reply::Reply::Volume(volume) => Ok(Volume::try_from(volume)?),
+                resume_volume_rebuild_reply::Reply::Error(err) => Err(err.into()),
+            },
+            None => Err(ReplyError::invalid_response(ResourceKind::Volume)),
+        }
+    }
+
     #[tracing::instrument(name = "VolumeClient::probe", level = "debug", skip(self))]
     async fn probe(&self, _ctx: Option<Context>) -> Result<bool, ReplyError> {
         match self.client().probe(ProbeRequest {}).await {
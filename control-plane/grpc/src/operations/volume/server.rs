@@ -1,15 +1,25 @@
 use crate::{
     misc::traits::ValidateRequestTypes,
-    operations::{volume::traits::VolumeOperations, Pagination},
+    operations::{volume::traits::VolumeOperations, Pagination, Sorting},
     volume::{
-        create_volume_reply, get_volumes_reply, publish_volume_reply, set_volume_replica_reply,
-        share_volume_reply, unpublish_volume_reply,
+        create_volume_reply, freeze_volume_reply, get_volume_history_reply,
+        get_volume_revision_diff_reply, get_volume_topology_reply, get_volumes_reply,
+        get_volumes_status_reply, patch_volume_reply, pause_volume_rebuild_reply,
+        publish_volume_reply, resume_volume_rebuild_reply, set_volume_replica_reply,
+        set_volume_source_replica_reply, share_volume_reply, thaw_volume_reply,
+        unpublish_volume_reply, validate_create_volume_reply,
         volume_grpc_server::{VolumeGrpc, VolumeGrpcServer},
         CreateVolumeReply, CreateVolumeRequest, DestroyVolumeReply, DestroyVolumeRequest,
-        GetVolumesReply, GetVolumesRequest, ProbeRequest, ProbeResponse, PublishVolumeReply,
-        PublishVolumeRequest, SetVolumeReplicaReply, SetVolumeReplicaRequest, ShareVolumeReply,
-        ShareVolumeRequest, UnpublishVolumeReply, UnpublishVolumeRequest, UnshareVolumeReply,
-        UnshareVolumeRequest,
+        FreezeVolumeReply, FreezeVolumeRequest, GetVolumeHistoryReply, GetVolumeHistoryRequest,
+        GetVolumeRevisionDiffReply, GetVolumeRevisionDiffRequest, GetVolumeTopologyReply,
+        GetVolumeTopologyRequest, GetVolumesReply, GetVolumesRequest, GetVolumesStatusReply,
+        GetVolumesStatusRequest, PatchVolumeReply, PatchVolumeRequest, PauseVolumeRebuildReply,
+        PauseVolumeRebuildRequest, ProbeRequest, ProbeResponse, PublishVolumeReply,
+        PublishVolumeRequest, ResumeVolumeRebuildReply, ResumeVolumeRebuildRequest,
+        SetVolumeReplicaReply, SetVolumeReplicaRequest, SetVolumeSourceReplicaReply,
+        SetVolumeSourceReplicaRequest, ShareVolumeReply, ShareVolumeRequest, ThawVolumeReply,
+        ThawVolumeRequest, UnpublishVolumeReply, UnpublishVolumeRequest, UnshareVolumeReply,
+        UnshareVolumeRequest, ValidateCreateVolumeReply,
     },
 };
 use common_lib::types::v0::transport::Filter;
@@ -51,6 +61,22 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn validate_create_volume(
+        &self,
+        request: tonic::Request<CreateVolumeRequest>,
+    ) -> Result<tonic::Response<ValidateCreateVolumeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.validate_create(&req, None).await {
+            Ok(violations) => Ok(Response::new(ValidateCreateVolumeReply {
+                reply: Some(validate_create_volume_reply::Reply::Violations(
+                    violations.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(ValidateCreateVolumeReply {
+                reply: Some(validate_create_volume_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn destroy_volume(
         &self,
         request: tonic::Request<DestroyVolumeRequest>,
@@ -81,7 +107,12 @@ impl VolumeGrpc for VolumeServer {
         };
 
         let pagination: Option<Pagination> = req.pagination.map(|p| p.into());
-        match self.service.get(filter, pagination, None).await {
+        let sorting: Option<Sorting> = req.sorting.map(|s| s.into());
+        match self
+            .service
+            .get_sorted(filter, sorting, pagination, None)
+            .await
+        {
             Ok(volumes) => Ok(Response::new(GetVolumesReply {
                 reply: Some(get_volumes_reply::Reply::Volumes(volumes.into())),
             })),
@@ -90,6 +121,62 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn get_volumes_status(
+        &self,
+        request: tonic::Request<GetVolumesStatusRequest>,
+    ) -> Result<tonic::Response<GetVolumesStatusReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_status(&req, None).await {
+            Ok(status) => Ok(Response::new(GetVolumesStatusReply {
+                reply: Some(get_volumes_status_reply::Reply::Status(status.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumesStatusReply {
+                reply: Some(get_volumes_status_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_volume_topology(
+        &self,
+        request: tonic::Request<GetVolumeTopologyRequest>,
+    ) -> Result<tonic::Response<GetVolumeTopologyReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_topology(&req, None).await {
+            Ok(topology) => Ok(Response::new(GetVolumeTopologyReply {
+                reply: Some(get_volume_topology_reply::Reply::Topology(topology.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumeTopologyReply {
+                reply: Some(get_volume_topology_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_volume_history(
+        &self,
+        request: tonic::Request<GetVolumeHistoryRequest>,
+    ) -> Result<tonic::Response<GetVolumeHistoryReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_history(&req, None).await {
+            Ok(history) => Ok(Response::new(GetVolumeHistoryReply {
+                reply: Some(get_volume_history_reply::Reply::History(history.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumeHistoryReply {
+                reply: Some(get_volume_history_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn get_volume_revision_diff(
+        &self,
+        request: tonic::Request<GetVolumeRevisionDiffRequest>,
+    ) -> Result<tonic::Response<GetVolumeRevisionDiffReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.get_revision_diff(&req, None).await {
+            Ok(diff) => Ok(Response::new(GetVolumeRevisionDiffReply {
+                reply: Some(get_volume_revision_diff_reply::Reply::Diff(diff.into())),
+            })),
+            Err(err) => Ok(Response::new(GetVolumeRevisionDiffReply {
+                reply: Some(get_volume_revision_diff_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn publish_volume(
         &self,
         request: tonic::Request<PublishVolumeRequest>,
@@ -158,6 +245,92 @@ impl VolumeGrpc for VolumeServer {
             })),
         }
     }
+    async fn set_volume_source_replica(
+        &self,
+        request: tonic::Request<SetVolumeSourceReplicaRequest>,
+    ) -> Result<tonic::Response<SetVolumeSourceReplicaReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.set_source_replica(&req, None).await {
+            Ok(volume) => Ok(Response::new(SetVolumeSourceReplicaReply {
+                reply: Some(set_volume_source_replica_reply::Reply::Volume(
+                    volume.into(),
+                )),
+            })),
+            Err(err) => Ok(Response::new(SetVolumeSourceReplicaReply {
+                reply: Some(set_volume_source_replica_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn patch_volume(
+        &self,
+        request: tonic::Request<PatchVolumeRequest>,
+    ) -> Result<tonic::Response<PatchVolumeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.patch(&req, None).await {
+            Ok(volume) => Ok(Response::new(PatchVolumeReply {
+                reply: Some(patch_volume_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(PatchVolumeReply {
+                reply: Some(patch_volume_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn freeze_volume(
+        &self,
+        request: tonic::Request<FreezeVolumeRequest>,
+    ) -> Result<tonic::Response<FreezeVolumeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.freeze(&req, None).await {
+            Ok(volume) => Ok(Response::new(FreezeVolumeReply {
+                reply: Some(freeze_volume_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(FreezeVolumeReply {
+                reply: Some(freeze_volume_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn thaw_volume(
+        &self,
+        request: tonic::Request<ThawVolumeRequest>,
+    ) -> Result<tonic::Response<ThawVolumeReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.thaw(&req, None).await {
+            Ok(volume) => Ok(Response::new(ThawVolumeReply {
+                reply: Some(thaw_volume_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(ThawVolumeReply {
+                reply: Some(thaw_volume_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn pause_volume_rebuild(
+        &self,
+        request: tonic::Request<PauseVolumeRebuildRequest>,
+    ) -> Result<tonic::Response<PauseVolumeRebuildReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.pause_rebuild(&req, None).await {
+            Ok(volume) => Ok(Response::new(PauseVolumeRebuildReply {
+                reply: Some(pause_volume_rebuild_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(PauseVolumeRebuildReply {
+                reply: Some(pause_volume_rebuild_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
+    async fn resume_volume_rebuild(
+        &self,
+        request: tonic::Request<ResumeVolumeRebuildRequest>,
+    ) -> Result<tonic::Response<ResumeVolumeRebuildReply>, tonic::Status> {
+        let req = request.into_inner().validated()?;
+        match self.service.resume_rebuild(&req, None).await {
+            Ok(volume) => Ok(Response::new(ResumeVolumeRebuildReply {
+                reply: Some(resume_volume_rebuild_reply::Reply::Volume(volume.into())),
+            })),
+            Err(err) => Ok(Response::new(ResumeVolumeRebuildReply {
+                reply: Some(resume_volume_rebuild_reply::Reply::Error(err.into())),
+            })),
+        }
+    }
     async fn probe(
         &self,
         _request: tonic::Request<ProbeRequest>,
@@ -2,28 +2,41 @@ use crate::{
     common,
     context::Context,
     misc::traits::{StringValue, ValidateRequestTypes},
-    nexus,
-    operations::Pagination,
-    replica, volume,
+    nexus, node,
+    operations::{Pagination, Sorting},
+    pool, replica, volume,
     volume::{
-        get_volumes_request, CreateVolumeRequest, DestroyVolumeRequest, PublishVolumeRequest,
-        SetVolumeReplicaRequest, ShareVolumeRequest, UnpublishVolumeRequest, UnshareVolumeRequest,
+        get_volumes_request, CreateVolumeRequest, DestroyVolumeRequest, FreezeVolumeRequest,
+        GetVolumeHistoryRequest, GetVolumeRevisionDiffRequest, GetVolumeTopologyRequest,
+        GetVolumesStatusRequest, PatchVolumeRequest, PauseVolumeRebuildRequest,
+        PublishVolumeRequest, ResumeVolumeRebuildRequest, SetVolumeReplicaRequest,
+        SetVolumeSourceReplicaRequest, ShareVolumeRequest, ThawVolumeRequest,
+        UnpublishVolumeRequest, UnshareVolumeRequest,
     },
 };
 use common_lib::{
     transport_api::{v0::Volumes, ReplyError, ResourceKind},
     types::v0::{
-        store::volume::{VolumeSpec, VolumeTarget},
+        store::{
+            epoch_secs, from_epoch_secs,
+            volume::{VolumeSpec, VolumeTarget},
+        },
         transport::{
-            CreateVolume, DestroyVolume, ExplicitNodeTopology, Filter, LabelledTopology, Nexus,
-            NexusId, NodeId, NodeTopology, PoolTopology, PublishVolume, ReplicaId, ReplicaStatus,
-            ReplicaTopology, SetVolumeReplica, ShareVolume, Topology, UnpublishVolume,
-            UnshareVolume, Volume, VolumeId, VolumeLabels, VolumePolicy, VolumeShareProtocol,
-            VolumeState,
+            ChildTopologyTree, ChildUri, CreateVolume, DestroyVolume, ExplicitNodeTopology, Filter,
+            FreezeVolume, GetVolumeHistory, GetVolumeRevisionDiff, GetVolumeTopology,
+            GetVolumesStatus, LabelledTopology, Nexus, NexusId, NexusTopologyTree, NodeId,
+            NodeTopology, NodeTopologyTree, NvmeReservation, PatchVolume, PauseVolumeRebuild,
+            PoolId, PoolTopology, PoolTopologyTree, PublishVolume, RebuildPriority, ReplicaId,
+            ReplicaStatus, ReplicaTopology, ReplicaTopologyTree, ResumeVolumeRebuild,
+            SetVolumeReplica, SetVolumeSourceReplica, ShareVolume, ThawVolume, Topology,
+            UnpublishVolume, UnshareVolume, Volume, VolumeCondition, VolumeDefault, VolumeHistory,
+            VolumeId, VolumeLabels, VolumePlacementPolicy, VolumePolicy, VolumeRevision,
+            VolumeRevisionDiff, VolumeShareProtocol, VolumeState, VolumeStatusEntry,
+            VolumeTopologyTree, VolumeViolation, VolumeViolations, VolumesStatus,
         },
     },
 };
-use std::{collections::HashMap, convert::TryFrom};
+use std::{collections::HashMap, convert::TryFrom, time::Duration};
 
 /// All volume crud operations to be a part of the VolumeOperations trait
 #[tonic::async_trait]
@@ -34,13 +47,54 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn CreateVolumeInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Check a volume creation request against the rules which don't depend on the state of the
+    /// cluster, without creating the volume
+    async fn validate_create(
+        &self,
+        req: &dyn CreateVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeViolations, ReplyError>;
     /// Get volumes
     async fn get(
         &self,
         filter: Filter,
         pagination: Option<Pagination>,
         ctx: Option<Context>,
+    ) -> Result<Volumes, ReplyError> {
+        self.get_sorted(filter, None, pagination, ctx).await
+    }
+    /// Get volumes, sorted before any pagination is applied
+    async fn get_sorted(
+        &self,
+        filter: Filter,
+        sorting: Option<Sorting>,
+        pagination: Option<Pagination>,
+        ctx: Option<Context>,
     ) -> Result<Volumes, ReplyError>;
+    /// Get the status of many volumes at once
+    async fn get_status(
+        &self,
+        req: &dyn GetVolumesStatusInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumesStatus, ReplyError>;
+    /// Get the topology tree of a volume
+    async fn get_topology(
+        &self,
+        req: &dyn GetVolumeTopologyInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeTopologyTree, ReplyError>;
+    /// Get the revisions of a volume spec which are retained in its history
+    async fn get_history(
+        &self,
+        req: &dyn GetVolumeHistoryInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeHistory, ReplyError>;
+    /// Get the changes made to a volume spec by one of its revisions
+    async fn get_revision_diff(
+        &self,
+        req: &dyn GetVolumeRevisionDiffInfo,
+        ctx: Option<Context>,
+    ) -> Result<VolumeRevisionDiff, ReplyError>;
     /// Destroy a volume
     async fn destroy(
         &self,
@@ -77,6 +131,42 @@ pub trait VolumeOperations: Send + Sync {
         req: &dyn SetVolumeReplicaInfo,
         ctx: Option<Context>,
     ) -> Result<Volume, ReplyError>;
+    /// Designate the source replica of the volume, which the other replicas are rebuilt from
+    async fn set_source_replica(
+        &self,
+        req: &dyn SetVolumeSourceReplicaInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Update the mutable parameters of a volume
+    async fn patch(
+        &self,
+        req: &dyn PatchVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Freeze the IO of a published volume, until it's thawed or the timeout elapses
+    async fn freeze(
+        &self,
+        req: &dyn FreezeVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Thaw the frozen IO of a volume
+    async fn thaw(
+        &self,
+        req: &dyn ThawVolumeInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Pause the rebuilds of the volume replicas, until they're resumed
+    async fn pause_rebuild(
+        &self,
+        req: &dyn PauseVolumeRebuildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
+    /// Resume the paused rebuilds of the volume replicas
+    async fn resume_rebuild(
+        &self,
+        req: &dyn ResumeVolumeRebuildInfo,
+        ctx: Option<Context>,
+    ) -> Result<Volume, ReplyError>;
     /// Liveness probe for volume service
     async fn probe(&self, ctx: Option<Context>) -> Result<bool, ReplyError>;
 }
@@ -97,9 +187,24 @@ impl From<VolumeSpec> for volume::VolumeDefinition {
                 topology: volume_spec.topology.map(|topology| topology.into()),
                 last_nexus_id: volume_spec.last_nexus_id.map(|id| id.to_string()),
                 thin: volume_spec.thin,
+                reservation: volume_spec
+                    .reservation
+                    .map(|reservation| reservation.into()),
+                defaults: volume_spec
+                    .defaults
+                    .into_iter()
+                    .map(|default| volume::VolumeDefault::from(default) as i32)
+                    .collect(),
+                source_replica: volume_spec.source_replica.map(|id| id.to_string()),
+                requested_size: volume_spec.requested_size,
+                rebuild_priority: volume::RebuildPriority::from(volume_spec.rebuild_priority)
+                    as i32,
+                rebuild_paused: volume_spec.rebuild_paused,
             }),
             metadata: Some(volume::Metadata {
                 spec_status: spec_status as i32,
+                created_at: volume_spec.created_at.map(epoch_secs),
+                updated_at: volume_spec.updated_at.map(epoch_secs),
             }),
         }
     }
@@ -115,10 +220,23 @@ impl From<Volume> for volume::Volume {
             status: status as i32,
             target: volume.state().target.map(|target| target.into()),
             replica_topology: to_grpc_replica_topology_map(volume.state().replica_topology),
+            conditions: volume
+                .state()
+                .conditions
+                .into_iter()
+                .map(|condition| volume::VolumeCondition::from(condition) as i32)
+                .collect(),
+            frozen_until: volume.state().frozen_until.map(epoch_secs),
         };
         volume::Volume {
             definition: Some(volume_definition),
             state: Some(volume_state),
+            conditions: volume
+                .conditions()
+                .iter()
+                .cloned()
+                .map(From::from)
+                .collect(),
         }
     }
 }
@@ -178,7 +296,7 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
                 None => None,
             },
             policy: match volume_spec.policy {
-                Some(policy) => policy.into(),
+                Some(policy) => VolumePolicy::try_from(policy)?,
                 None => {
                     return Err(ReplyError::missing_argument(
                         ResourceKind::Volume,
@@ -215,6 +333,35 @@ impl TryFrom<volume::VolumeDefinition> for VolumeSpec {
             },
             operation: None,
             thin: volume_spec.thin,
+            reservation: match volume_spec.reservation {
+                Some(reservation) => Some(NvmeReservation::try_from(reservation)?),
+                None => None,
+            },
+            created_at: volume_meta.created_at.map(from_epoch_secs),
+            defaults: volume_spec
+                .defaults
+                .into_iter()
+                .filter_map(volume::VolumeDefault::from_i32)
+                .map(From::from)
+                .collect(),
+            updated_at: volume_meta.updated_at.map(from_epoch_secs),
+            source_replica: match volume_spec.source_replica {
+                Some(id) => Some(ReplicaId::try_from(StringValue(Some(id)))?),
+                None => None,
+            },
+            requested_size: volume_spec.requested_size,
+            rebuild_priority: match volume::RebuildPriority::from_i32(volume_spec.rebuild_priority)
+            {
+                Some(priority) => priority.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "volume.definition.spec.rebuild_priority",
+                        "".to_string(),
+                    ))
+                }
+            },
+            rebuild_paused: volume_spec.rebuild_paused,
         };
         Ok(volume_spec)
     }
@@ -278,8 +425,20 @@ impl TryFrom<volume::Volume> for Volume {
                     ))
                 }
             },
+            conditions: grpc_volume_state
+                .conditions
+                .into_iter()
+                .filter_map(volume::VolumeCondition::from_i32)
+                .map(From::from)
+                .collect(),
+            frozen_until: grpc_volume_state.frozen_until.map(from_epoch_secs),
         };
-        Ok(Volume::new(volume_spec, volume_state))
+        let conditions = volume_grpc_type
+            .conditions
+            .into_iter()
+            .map(From::from)
+            .collect();
+        Ok(Volume::new(volume_spec, volume_state).with_conditions(conditions))
     }
 }
 
@@ -372,6 +531,12 @@ impl TryFrom<volume::Topology> for Topology {
                 },
                 None => None,
             },
+            anti_colocation: topology_grpc_type
+                .anti_colocation
+                .into_iter()
+                .map(|volume| VolumeId::try_from(StringValue(Some(volume))))
+                .collect::<Result<_, _>>()?,
+            spread: topology_grpc_type.spread,
         };
         Ok(topo)
     }
@@ -382,6 +547,12 @@ impl From<Topology> for volume::Topology {
         volume::Topology {
             node: topology.node.map(|topo| topo.into()),
             pool: topology.pool.map(|topo| topo.into()),
+            anti_colocation: topology
+                .anti_colocation
+                .into_iter()
+                .map(|volume| volume.to_string())
+                .collect(),
+            spread: topology.spread,
         }
     }
 }
@@ -514,11 +685,25 @@ impl From<ExplicitNodeTopology> for volume::ExplicitNodeTopology {
     }
 }
 
-impl From<volume::VolumePolicy> for VolumePolicy {
-    fn from(policy_grpc_type: volume::VolumePolicy) -> Self {
-        VolumePolicy {
+impl TryFrom<volume::VolumePolicy> for VolumePolicy {
+    type Error = ReplyError;
+    fn try_from(policy_grpc_type: volume::VolumePolicy) -> Result<Self, Self::Error> {
+        Ok(VolumePolicy {
             self_heal: policy_grpc_type.self_heal,
-        }
+            quorum: match policy_grpc_type.quorum {
+                Some(quorum) => match u8::try_from(quorum) {
+                    Ok(quorum) => Some(quorum),
+                    Err(error) => {
+                        return Err(ReplyError::invalid_argument(
+                            ResourceKind::Volume,
+                            "volume_policy.quorum",
+                            error.to_string(),
+                        ))
+                    }
+                },
+                None => None,
+            },
+        })
     }
 }
 
@@ -526,6 +711,7 @@ impl From<VolumePolicy> for volume::VolumePolicy {
     fn from(policy: VolumePolicy) -> Self {
         volume::VolumePolicy {
             self_heal: policy.self_heal,
+            quorum: policy.quorum.map(|quorum| quorum as u32),
         }
     }
 }
@@ -588,6 +774,94 @@ impl From<VolumeShareProtocol> for volume::VolumeShareProtocol {
     }
 }
 
+impl From<volume::VolumeCondition> for VolumeCondition {
+    fn from(src: volume::VolumeCondition) -> Self {
+        match src {
+            volume::VolumeCondition::TargetMissing => Self::TargetMissing,
+            volume::VolumeCondition::ReplicaMissing => Self::ReplicaMissing,
+            volume::VolumeCondition::SpecDirty => Self::SpecDirty,
+            volume::VolumeCondition::TopologyUnsatisfiable => Self::TopologyUnsatisfiable,
+            volume::VolumeCondition::TopologyRelaxed => Self::TopologyRelaxed,
+            volume::VolumeCondition::ChildSizeMismatch => Self::ChildSizeMismatch,
+            volume::VolumeCondition::ReplayStuck => Self::ReplayStuck,
+        }
+    }
+}
+
+impl From<VolumeCondition> for volume::VolumeCondition {
+    fn from(src: VolumeCondition) -> Self {
+        match src {
+            VolumeCondition::TargetMissing => Self::TargetMissing,
+            VolumeCondition::ReplicaMissing => Self::ReplicaMissing,
+            VolumeCondition::SpecDirty => Self::SpecDirty,
+            VolumeCondition::TopologyUnsatisfiable => Self::TopologyUnsatisfiable,
+            VolumeCondition::TopologyRelaxed => Self::TopologyRelaxed,
+            VolumeCondition::ChildSizeMismatch => Self::ChildSizeMismatch,
+            VolumeCondition::ReplayStuck => Self::ReplayStuck,
+        }
+    }
+}
+
+impl From<volume::VolumeDefault> for VolumeDefault {
+    fn from(src: volume::VolumeDefault) -> Self {
+        match src {
+            volume::VolumeDefault::Replicas => Self::Replicas,
+            volume::VolumeDefault::Thin => Self::Thin,
+            volume::VolumeDefault::Placement => Self::Placement,
+        }
+    }
+}
+
+impl From<VolumeDefault> for volume::VolumeDefault {
+    fn from(src: VolumeDefault) -> Self {
+        match src {
+            VolumeDefault::Replicas => Self::Replicas,
+            VolumeDefault::Thin => Self::Thin,
+            VolumeDefault::Placement => Self::Placement,
+        }
+    }
+}
+
+impl From<volume::VolumePlacementPolicy> for VolumePlacementPolicy {
+    fn from(src: volume::VolumePlacementPolicy) -> Self {
+        match src {
+            volume::VolumePlacementPolicy::Strict => Self::Strict,
+            volume::VolumePlacementPolicy::BestEffort => Self::BestEffort,
+            volume::VolumePlacementPolicy::Relaxed => Self::Relaxed,
+        }
+    }
+}
+
+impl From<VolumePlacementPolicy> for volume::VolumePlacementPolicy {
+    fn from(src: VolumePlacementPolicy) -> Self {
+        match src {
+            VolumePlacementPolicy::Strict => Self::Strict,
+            VolumePlacementPolicy::BestEffort => Self::BestEffort,
+            VolumePlacementPolicy::Relaxed => Self::Relaxed,
+        }
+    }
+}
+
+impl From<volume::RebuildPriority> for RebuildPriority {
+    fn from(src: volume::RebuildPriority) -> Self {
+        match src {
+            volume::RebuildPriority::High => Self::High,
+            volume::RebuildPriority::Normal => Self::Normal,
+            volume::RebuildPriority::Low => Self::Low,
+        }
+    }
+}
+
+impl From<RebuildPriority> for volume::RebuildPriority {
+    fn from(src: RebuildPriority) -> Self {
+        match src {
+            RebuildPriority::High => Self::High,
+            RebuildPriority::Normal => Self::Normal,
+            RebuildPriority::Low => Self::Low,
+        }
+    }
+}
+
 impl TryFrom<get_volumes_request::Filter> for Filter {
     type Error = ReplyError;
     fn try_from(filter: get_volumes_request::Filter) -> Result<Self, Self::Error> {
@@ -615,6 +889,16 @@ pub trait CreateVolumeInfo: Send + Sync + std::fmt::Debug {
     fn labels(&self) -> Option<VolumeLabels>;
     /// Flag indicating whether the volume should be thin provisioned
     fn thin(&self) -> bool;
+    /// Placement policy to use when not all of the replicas can be placed
+    fn placement(&self) -> VolumePlacementPolicy;
+    /// Pools explicitly selected to host the replicas
+    fn pools(&self) -> Vec<PoolId>;
+    /// Allow more than one of the selected pools to be on the same node
+    fn allow_same_node(&self) -> bool;
+    /// Parameters left unset, to be taken from the cluster defaults
+    fn defaults(&self) -> Vec<VolumeDefault>;
+    /// Priority of the rebuilds of the volume replicas
+    fn rebuild_priority(&self) -> RebuildPriority;
 }
 
 impl CreateVolumeInfo for CreateVolume {
@@ -645,6 +929,26 @@ impl CreateVolumeInfo for CreateVolume {
     fn thin(&self) -> bool {
         self.thin
     }
+
+    fn placement(&self) -> VolumePlacementPolicy {
+        self.placement
+    }
+
+    fn pools(&self) -> Vec<PoolId> {
+        self.pools.clone()
+    }
+
+    fn allow_same_node(&self) -> bool {
+        self.allow_same_node
+    }
+
+    fn defaults(&self) -> Vec<VolumeDefault> {
+        self.defaults.clone()
+    }
+
+    fn rebuild_priority(&self) -> RebuildPriority {
+        self.rebuild_priority
+    }
 }
 
 /// Intermediate structure that validates the conversion to CreateVolumeRequest type
@@ -653,6 +957,9 @@ pub struct ValidatedCreateVolumeRequest {
     inner: CreateVolumeRequest,
     uuid: VolumeId,
     topology: Option<Topology>,
+    policy: VolumePolicy,
+    placement: VolumePlacementPolicy,
+    rebuild_priority: RebuildPriority,
 }
 
 impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
@@ -669,10 +976,7 @@ impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
     }
 
     fn policy(&self) -> VolumePolicy {
-        match self.inner.policy.clone() {
-            Some(policy) => policy.into(),
-            None => VolumePolicy::default(),
-        }
+        self.policy.clone()
     }
 
     fn topology(&self) -> Option<Topology> {
@@ -689,6 +993,31 @@ impl CreateVolumeInfo for ValidatedCreateVolumeRequest {
     fn thin(&self) -> bool {
         self.inner.thin
     }
+
+    fn placement(&self) -> VolumePlacementPolicy {
+        self.placement
+    }
+
+    fn pools(&self) -> Vec<PoolId> {
+        self.inner.pools.iter().cloned().map(PoolId::from).collect()
+    }
+
+    fn allow_same_node(&self) -> bool {
+        self.inner.allow_same_node
+    }
+
+    fn defaults(&self) -> Vec<VolumeDefault> {
+        self.inner
+            .defaults
+            .iter()
+            .filter_map(|default| volume::VolumeDefault::from_i32(*default))
+            .map(From::from)
+            .collect()
+    }
+
+    fn rebuild_priority(&self) -> RebuildPriority {
+        self.rebuild_priority
+    }
 }
 
 impl ValidateRequestTypes for CreateVolumeRequest {
@@ -709,6 +1038,30 @@ impl ValidateRequestTypes for CreateVolumeRequest {
                 },
                 None => None,
             },
+            policy: match self.policy.clone() {
+                Some(policy) => VolumePolicy::try_from(policy)?,
+                None => VolumePolicy::default(),
+            },
+            placement: match volume::VolumePlacementPolicy::from_i32(self.placement) {
+                Some(placement) => placement.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "create_volume_request.placement",
+                        "".to_string(),
+                    ))
+                }
+            },
+            rebuild_priority: match volume::RebuildPriority::from_i32(self.rebuild_priority) {
+                Some(priority) => priority.into(),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "create_volume_request.rebuild_priority",
+                        "".to_string(),
+                    ))
+                }
+            },
             inner: self,
         })
     }
@@ -724,6 +1077,12 @@ impl From<&dyn CreateVolumeInfo> for CreateVolume {
             topology: data.topology(),
             labels: data.labels(),
             thin: data.thin(),
+            placement: data.placement(),
+            pools: data.pools(),
+            allow_same_node: data.allow_same_node(),
+            defaults: data.defaults(),
+            requested_size: None,
+            rebuild_priority: data.rebuild_priority(),
         }
     }
 }
@@ -740,6 +1099,19 @@ impl From<&dyn CreateVolumeInfo> for CreateVolumeRequest {
                 .labels()
                 .map(|labels| crate::common::StringMapValue { value: labels }),
             thin: data.thin(),
+            placement: volume::VolumePlacementPolicy::from(data.placement()) as i32,
+            pools: data
+                .pools()
+                .into_iter()
+                .map(|pool| pool.to_string())
+                .collect(),
+            allow_same_node: data.allow_same_node(),
+            defaults: data
+                .defaults()
+                .into_iter()
+                .map(|default| volume::VolumeDefault::from(default) as i32)
+                .collect(),
+            rebuild_priority: volume::RebuildPriority::from(data.rebuild_priority()) as i32,
         }
     }
 }
@@ -919,6 +1291,8 @@ pub trait PublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn target_node(&self) -> Option<NodeId>;
     /// The protocol over which volume be published
     fn share(&self) -> Option<VolumeShareProtocol>;
+    /// The NVMe reservation which the volume target should hold on its replicas
+    fn reservation(&self) -> Option<NvmeReservation>;
 }
 
 impl PublishVolumeInfo for PublishVolume {
@@ -933,6 +1307,10 @@ impl PublishVolumeInfo for PublishVolume {
     fn share(&self) -> Option<VolumeShareProtocol> {
         self.share
     }
+
+    fn reservation(&self) -> Option<NvmeReservation> {
+        self.reservation
+    }
 }
 
 /// Intermediate structure that validates the conversion to PublishVolumeRequest type
@@ -941,6 +1319,7 @@ pub struct ValidatedPublishVolumeRequest {
     inner: PublishVolumeRequest,
     uuid: VolumeId,
     share: Option<VolumeShareProtocol>,
+    reservation: Option<NvmeReservation>,
 }
 
 impl PublishVolumeInfo for ValidatedPublishVolumeRequest {
@@ -958,6 +1337,10 @@ impl PublishVolumeInfo for ValidatedPublishVolumeRequest {
     fn share(&self) -> Option<VolumeShareProtocol> {
         self.share
     }
+
+    fn reservation(&self) -> Option<NvmeReservation> {
+        self.reservation
+    }
 }
 
 impl ValidateRequestTypes for PublishVolumeRequest {
@@ -978,6 +1361,10 @@ impl ValidateRequestTypes for PublishVolumeRequest {
                 },
                 None => None,
             },
+            reservation: match self.reservation.clone() {
+                Some(reservation) => Some(NvmeReservation::try_from(reservation)?),
+                None => None,
+            },
             inner: self,
         })
     }
@@ -989,6 +1376,7 @@ impl From<&dyn PublishVolumeInfo> for PublishVolume {
             uuid: data.uuid(),
             target_node: data.target_node(),
             share: data.share(),
+            reservation: data.reservation(),
         }
     }
 }
@@ -1006,6 +1394,7 @@ impl From<&dyn PublishVolumeInfo> for PublishVolumeRequest {
             uuid: Some(data.uuid().to_string()),
             target_node: data.target_node().map(|node_id| node_id.to_string()),
             share,
+            reservation: data.reservation().map(|reservation| reservation.into()),
         }
     }
 }
@@ -1016,6 +1405,8 @@ pub trait UnpublishVolumeInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// Force unpublish
     fn force(&self) -> bool;
+    /// Release the NVMe reservation of the volume
+    fn release_reservation(&self) -> bool;
 }
 
 impl UnpublishVolumeInfo for UnpublishVolume {
@@ -1026,6 +1417,10 @@ impl UnpublishVolumeInfo for UnpublishVolume {
     fn force(&self) -> bool {
         self.force()
     }
+
+    fn release_reservation(&self) -> bool {
+        self.release_reservation()
+    }
 }
 
 /// Intermediate structure that validates the conversion to UnpublishVolumeRequest type
@@ -1042,6 +1437,9 @@ impl UnpublishVolumeInfo for ValidatedUnpublishVolumeRequest {
     fn force(&self) -> bool {
         self.inner.force
     }
+    fn release_reservation(&self) -> bool {
+        self.inner.release_reservation
+    }
 }
 
 impl ValidateRequestTypes for UnpublishVolumeRequest {
@@ -1057,6 +1455,7 @@ impl ValidateRequestTypes for UnpublishVolumeRequest {
 impl From<&dyn UnpublishVolumeInfo> for UnpublishVolume {
     fn from(data: &dyn UnpublishVolumeInfo) -> Self {
         UnpublishVolume::new(&data.uuid(), data.force())
+            .with_release_reservation(data.release_reservation())
     }
 }
 
@@ -1065,6 +1464,7 @@ impl From<&dyn UnpublishVolumeInfo> for UnpublishVolumeRequest {
         Self {
             uuid: Some(data.uuid().to_string()),
             force: data.force(),
+            release_reservation: data.release_reservation(),
         }
     }
 }
@@ -1075,6 +1475,8 @@ pub trait SetVolumeReplicaInfo: Send + Sync + std::fmt::Debug {
     fn uuid(&self) -> VolumeId;
     /// No of replicas we want to set for the volume
     fn replicas(&self) -> u8;
+    /// Decrease the replicas even if the volume is left below the minimum replication factor
+    fn override_redundancy_check(&self) -> bool;
 }
 
 impl SetVolumeReplicaInfo for SetVolumeReplica {
@@ -1085,6 +1487,10 @@ impl SetVolumeReplicaInfo for SetVolumeReplica {
     fn replicas(&self) -> u8 {
         self.replicas
     }
+
+    fn override_redundancy_check(&self) -> bool {
+        self.override_redundancy_check
+    }
 }
 
 /// Intermediate structure that validates the conversion to SetVolumeReplicaRequest type
@@ -1101,6 +1507,9 @@ impl SetVolumeReplicaInfo for ValidatedSetVolumeReplicaRequest {
     fn replicas(&self) -> u8 {
         self.inner.replicas as u8
     }
+    fn override_redundancy_check(&self) -> bool {
+        self.inner.override_redundancy_check
+    }
 }
 
 impl ValidateRequestTypes for SetVolumeReplicaRequest {
@@ -1118,6 +1527,7 @@ impl From<&dyn SetVolumeReplicaInfo> for SetVolumeReplica {
         Self {
             uuid: data.uuid(),
             replicas: data.replicas(),
+            override_redundancy_check: data.override_redundancy_check(),
         }
     }
 }
@@ -1127,6 +1537,918 @@ impl From<&dyn SetVolumeReplicaInfo> for SetVolumeReplicaRequest {
         Self {
             uuid: Some(data.uuid().to_string()),
             replicas: data.replicas().into(),
+            override_redundancy_check: data.override_redundancy_check(),
+        }
+    }
+}
+
+/// Trait to be implemented for SetVolumeSourceReplica operation
+pub trait SetVolumeSourceReplicaInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// Uuid of the replica which the other replicas are rebuilt from
+    fn replica(&self) -> ReplicaId;
+}
+
+impl SetVolumeSourceReplicaInfo for SetVolumeSourceReplica {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to SetVolumeSourceReplicaRequest type
+#[derive(Debug)]
+pub struct ValidatedSetVolumeSourceReplicaRequest {
+    uuid: VolumeId,
+    replica: ReplicaId,
+}
+
+impl SetVolumeSourceReplicaInfo for ValidatedSetVolumeSourceReplicaRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn replica(&self) -> ReplicaId {
+        self.replica.clone()
+    }
+}
+
+impl ValidateRequestTypes for SetVolumeSourceReplicaRequest {
+    type Validated = ValidatedSetVolumeSourceReplicaRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedSetVolumeSourceReplicaRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+            replica: ReplicaId::try_from(StringValue(self.replica))?,
+        })
+    }
+}
+
+impl From<&dyn SetVolumeSourceReplicaInfo> for SetVolumeSourceReplica {
+    fn from(data: &dyn SetVolumeSourceReplicaInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            replica: data.replica(),
+        }
+    }
+}
+
+impl From<&dyn SetVolumeSourceReplicaInfo> for SetVolumeSourceReplicaRequest {
+    fn from(data: &dyn SetVolumeSourceReplicaInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            replica: Some(data.replica().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for PatchVolume operation
+pub trait PatchVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// Priority of the rebuilds of the volume replicas, unchanged if None
+    fn rebuild_priority(&self) -> Option<RebuildPriority>;
+}
+
+impl PatchVolumeInfo for PatchVolume {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn rebuild_priority(&self) -> Option<RebuildPriority> {
+        self.rebuild_priority
+    }
+}
+
+/// Intermediate structure that validates the conversion to PatchVolumeRequest type
+#[derive(Debug)]
+pub struct ValidatedPatchVolumeRequest {
+    uuid: VolumeId,
+    rebuild_priority: Option<RebuildPriority>,
+}
+
+impl PatchVolumeInfo for ValidatedPatchVolumeRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn rebuild_priority(&self) -> Option<RebuildPriority> {
+        self.rebuild_priority
+    }
+}
+
+impl ValidateRequestTypes for PatchVolumeRequest {
+    type Validated = ValidatedPatchVolumeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedPatchVolumeRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+            rebuild_priority: match self.rebuild_priority {
+                Some(priority) => match volume::RebuildPriority::from_i32(priority) {
+                    Some(priority) => Some(priority.into()),
+                    None => {
+                        return Err(ReplyError::invalid_argument(
+                            ResourceKind::Volume,
+                            "patch_volume_request.rebuild_priority",
+                            "".to_string(),
+                        ))
+                    }
+                },
+                None => None,
+            },
+        })
+    }
+}
+
+impl From<&dyn PatchVolumeInfo> for PatchVolume {
+    fn from(data: &dyn PatchVolumeInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            rebuild_priority: data.rebuild_priority(),
+        }
+    }
+}
+
+impl From<&dyn PatchVolumeInfo> for PatchVolumeRequest {
+    fn from(data: &dyn PatchVolumeInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            rebuild_priority: data
+                .rebuild_priority()
+                .map(|priority| volume::RebuildPriority::from(priority) as i32),
+        }
+    }
+}
+
+/// Trait to be implemented for FreezeVolume operation
+pub trait FreezeVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+    /// The IO is thawed by the control plane once this has elapsed
+    fn timeout(&self) -> Duration;
+}
+
+impl FreezeVolumeInfo for FreezeVolume {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+/// Intermediate structure that validates the conversion to FreezeVolumeRequest type
+#[derive(Debug)]
+pub struct ValidatedFreezeVolumeRequest {
+    uuid: VolumeId,
+    timeout: Duration,
+}
+
+impl FreezeVolumeInfo for ValidatedFreezeVolumeRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn timeout(&self) -> Duration {
+        self.timeout
+    }
+}
+
+impl ValidateRequestTypes for FreezeVolumeRequest {
+    type Validated = ValidatedFreezeVolumeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedFreezeVolumeRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+            timeout: Duration::from_millis(self.timeout_ms),
+        })
+    }
+}
+
+impl From<&dyn FreezeVolumeInfo> for FreezeVolume {
+    fn from(data: &dyn FreezeVolumeInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            timeout: data.timeout(),
+        }
+    }
+}
+
+impl From<&dyn FreezeVolumeInfo> for FreezeVolumeRequest {
+    fn from(data: &dyn FreezeVolumeInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+            timeout_ms: data.timeout().as_millis() as u64,
+        }
+    }
+}
+
+/// Trait to be implemented for ThawVolume operation
+pub trait ThawVolumeInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+}
+
+impl ThawVolumeInfo for ThawVolume {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to ThawVolumeRequest type
+#[derive(Debug)]
+pub struct ValidatedThawVolumeRequest {
+    uuid: VolumeId,
+}
+
+impl ThawVolumeInfo for ValidatedThawVolumeRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+impl ValidateRequestTypes for ThawVolumeRequest {
+    type Validated = ValidatedThawVolumeRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedThawVolumeRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+        })
+    }
+}
+
+impl From<&dyn ThawVolumeInfo> for ThawVolume {
+    fn from(data: &dyn ThawVolumeInfo) -> Self {
+        Self { uuid: data.uuid() }
+    }
+}
+
+impl From<&dyn ThawVolumeInfo> for ThawVolumeRequest {
+    fn from(data: &dyn ThawVolumeInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for PauseVolumeRebuild operation
+pub trait PauseVolumeRebuildInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+}
+
+impl PauseVolumeRebuildInfo for PauseVolumeRebuild {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to PauseVolumeRebuildRequest type
+#[derive(Debug)]
+pub struct ValidatedPauseVolumeRebuildRequest {
+    uuid: VolumeId,
+}
+
+impl PauseVolumeRebuildInfo for ValidatedPauseVolumeRebuildRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+impl ValidateRequestTypes for PauseVolumeRebuildRequest {
+    type Validated = ValidatedPauseVolumeRebuildRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedPauseVolumeRebuildRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+        })
+    }
+}
+
+impl From<&dyn PauseVolumeRebuildInfo> for PauseVolumeRebuild {
+    fn from(data: &dyn PauseVolumeRebuildInfo) -> Self {
+        Self { uuid: data.uuid() }
+    }
+}
+
+impl From<&dyn PauseVolumeRebuildInfo> for PauseVolumeRebuildRequest {
+    fn from(data: &dyn PauseVolumeRebuildInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for ResumeVolumeRebuild operation
+pub trait ResumeVolumeRebuildInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the concerned volume
+    fn uuid(&self) -> VolumeId;
+}
+
+impl ResumeVolumeRebuildInfo for ResumeVolumeRebuild {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to ResumeVolumeRebuildRequest type
+#[derive(Debug)]
+pub struct ValidatedResumeVolumeRebuildRequest {
+    uuid: VolumeId,
+}
+
+impl ResumeVolumeRebuildInfo for ValidatedResumeVolumeRebuildRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+impl ValidateRequestTypes for ResumeVolumeRebuildRequest {
+    type Validated = ValidatedResumeVolumeRebuildRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedResumeVolumeRebuildRequest {
+            uuid: VolumeId::try_from(StringValue(self.uuid))?,
+        })
+    }
+}
+
+impl From<&dyn ResumeVolumeRebuildInfo> for ResumeVolumeRebuild {
+    fn from(data: &dyn ResumeVolumeRebuildInfo) -> Self {
+        Self { uuid: data.uuid() }
+    }
+}
+
+impl From<&dyn ResumeVolumeRebuildInfo> for ResumeVolumeRebuildRequest {
+    fn from(data: &dyn ResumeVolumeRebuildInfo) -> Self {
+        Self {
+            uuid: Some(data.uuid().to_string()),
+        }
+    }
+}
+
+/// Trait to be implemented for GetVolumesStatus operation
+pub trait GetVolumesStatusInfo: Send + Sync + std::fmt::Debug {
+    /// Uuids of the volumes, or all the volumes if empty
+    fn volumes(&self) -> Vec<VolumeId>;
+}
+
+impl GetVolumesStatusInfo for GetVolumesStatus {
+    fn volumes(&self) -> Vec<VolumeId> {
+        self.volumes.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetVolumesStatusRequest type
+#[derive(Debug)]
+pub struct ValidatedGetVolumesStatusRequest {
+    volumes: Vec<VolumeId>,
+}
+
+impl GetVolumesStatusInfo for ValidatedGetVolumesStatusRequest {
+    fn volumes(&self) -> Vec<VolumeId> {
+        self.volumes.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetVolumesStatusRequest {
+    type Validated = ValidatedGetVolumesStatusRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        let volumes = self
+            .volume_ids
+            .into_iter()
+            .map(|id| {
+                VolumeId::try_from(id).map_err(|error| {
+                    ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "volume_ids",
+                        error.to_string(),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ValidatedGetVolumesStatusRequest { volumes })
+    }
+}
+
+impl From<&dyn GetVolumesStatusInfo> for GetVolumesStatus {
+    fn from(data: &dyn GetVolumesStatusInfo) -> Self {
+        Self {
+            volumes: data.volumes(),
+        }
+    }
+}
+
+impl From<&dyn GetVolumesStatusInfo> for GetVolumesStatusRequest {
+    fn from(data: &dyn GetVolumesStatusInfo) -> Self {
+        Self {
+            volume_ids: data.volumes().iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeStatusEntry> for VolumeStatusEntry {
+    type Error = ReplyError;
+    fn try_from(entry: volume::VolumeStatusEntry) -> Result<Self, Self::Error> {
+        let status = match entry.status {
+            Some(status) => match nexus::NexusStatus::from_i32(status) {
+                Some(status) => Some(status.into()),
+                None => {
+                    return Err(ReplyError::invalid_argument(
+                        ResourceKind::Volume,
+                        "volume_status.status",
+                        "".to_string(),
+                    ))
+                }
+            },
+            None => None,
+        };
+        Ok(Self {
+            uuid: VolumeId::try_from(StringValue(Some(entry.uuid)))?,
+            status,
+        })
+    }
+}
+
+impl From<VolumeStatusEntry> for volume::VolumeStatusEntry {
+    fn from(entry: VolumeStatusEntry) -> Self {
+        Self {
+            uuid: entry.uuid.to_string(),
+            status: entry.status.map(|status| {
+                let status: nexus::NexusStatus = status.into();
+                status as i32
+            }),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumesStatus> for VolumesStatus {
+    type Error = ReplyError;
+    fn try_from(status: volume::VolumesStatus) -> Result<Self, Self::Error> {
+        Ok(Self {
+            entries: status
+                .entries
+                .into_iter()
+                .map(VolumeStatusEntry::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<VolumesStatus> for volume::VolumesStatus {
+    fn from(status: VolumesStatus) -> Self {
+        Self {
+            entries: status.entries.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<volume::VolumeViolations> for VolumeViolations {
+    fn from(violations: volume::VolumeViolations) -> Self {
+        Self {
+            violations: violations
+                .violations
+                .into_iter()
+                .map(|violation| VolumeViolation {
+                    field: violation.field,
+                    message: violation.message,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<VolumeViolations> for volume::VolumeViolations {
+    fn from(violations: VolumeViolations) -> Self {
+        Self {
+            violations: violations
+                .violations
+                .into_iter()
+                .map(|violation| volume::VolumeViolation {
+                    field: violation.field,
+                    message: violation.message,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Trait to be implemented for GetVolumeTopology operation
+pub trait GetVolumeTopologyInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the volume
+    fn uuid(&self) -> VolumeId;
+}
+
+impl GetVolumeTopologyInfo for GetVolumeTopology {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetVolumeTopologyRequest type
+#[derive(Debug)]
+pub struct ValidatedGetVolumeTopologyRequest {
+    uuid: VolumeId,
+}
+
+impl GetVolumeTopologyInfo for ValidatedGetVolumeTopologyRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetVolumeTopologyRequest {
+    type Validated = ValidatedGetVolumeTopologyRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetVolumeTopologyRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.volume_id)))?,
+        })
+    }
+}
+
+impl From<&dyn GetVolumeTopologyInfo> for GetVolumeTopology {
+    fn from(data: &dyn GetVolumeTopologyInfo) -> Self {
+        Self { uuid: data.uuid() }
+    }
+}
+
+impl From<&dyn GetVolumeTopologyInfo> for GetVolumeTopologyRequest {
+    fn from(data: &dyn GetVolumeTopologyInfo) -> Self {
+        Self {
+            volume_id: data.uuid().to_string(),
+        }
+    }
+}
+
+/// A helper to convert a status of a topology tree from its grpc value
+fn topology_status<G: Into<T>, T>(status: Option<G>, field: &str) -> Result<T, ReplyError> {
+    match status {
+        Some(status) => Ok(status.into()),
+        None => Err(ReplyError::invalid_argument(
+            ResourceKind::Volume,
+            field,
+            "".to_string(),
+        )),
+    }
+}
+
+impl TryFrom<volume::NodeTopologyTree> for NodeTopologyTree {
+    type Error = ReplyError;
+    fn try_from(tree: volume::NodeTopologyTree) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: tree.id.into(),
+            status: topology_status(node::NodeStatus::from_i32(tree.status), "node.status")?,
+        })
+    }
+}
+
+impl From<NodeTopologyTree> for volume::NodeTopologyTree {
+    fn from(tree: NodeTopologyTree) -> Self {
+        let status: node::NodeStatus = tree.status.into();
+        Self {
+            id: tree.id.to_string(),
+            status: status as i32,
+        }
+    }
+}
+
+impl TryFrom<volume::PoolTopologyTree> for PoolTopologyTree {
+    type Error = ReplyError;
+    fn try_from(tree: volume::PoolTopologyTree) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: tree.id.into(),
+            status: topology_status(pool::PoolStatus::from_i32(tree.status), "pool.status")?,
+            last_known: tree.last_known,
+            node: match tree.node {
+                Some(node) => node.try_into()?,
+                None => {
+                    return Err(ReplyError::missing_argument(
+                        ResourceKind::Volume,
+                        "pool.node",
+                    ))
+                }
+            },
+        })
+    }
+}
+
+impl From<PoolTopologyTree> for volume::PoolTopologyTree {
+    fn from(tree: PoolTopologyTree) -> Self {
+        let status: pool::PoolStatus = tree.status.into();
+        Self {
+            id: tree.id.to_string(),
+            status: status as i32,
+            last_known: tree.last_known,
+            node: Some(tree.node.into()),
+        }
+    }
+}
+
+impl TryFrom<volume::ReplicaTopologyTree> for ReplicaTopologyTree {
+    type Error = ReplyError;
+    fn try_from(tree: volume::ReplicaTopologyTree) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uuid: ReplicaId::try_from(StringValue(Some(tree.uuid)))?,
+            status: topology_status(
+                replica::ReplicaStatus::from_i32(tree.status),
+                "replica.status",
+            )?,
+            last_known: tree.last_known,
+            pool: match tree.pool {
+                Some(pool) => pool.try_into()?,
+                None => {
+                    return Err(ReplyError::missing_argument(
+                        ResourceKind::Volume,
+                        "replica.pool",
+                    ))
+                }
+            },
+        })
+    }
+}
+
+impl From<ReplicaTopologyTree> for volume::ReplicaTopologyTree {
+    fn from(tree: ReplicaTopologyTree) -> Self {
+        let status: replica::ReplicaStatus = tree.status.into();
+        Self {
+            uuid: tree.uuid.to_string(),
+            status: status as i32,
+            last_known: tree.last_known,
+            pool: Some(tree.pool.into()),
+        }
+    }
+}
+
+impl TryFrom<volume::ChildTopologyTree> for ChildTopologyTree {
+    type Error = ReplyError;
+    fn try_from(tree: volume::ChildTopologyTree) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uri: ChildUri::from(tree.uri),
+            state: topology_status(nexus::ChildState::from_i32(tree.state), "child.state")?,
+            replica: match tree.replica {
+                Some(replica) => Some(replica.try_into()?),
+                None => None,
+            },
+        })
+    }
+}
+
+impl From<ChildTopologyTree> for volume::ChildTopologyTree {
+    fn from(tree: ChildTopologyTree) -> Self {
+        let state: nexus::ChildState = tree.state.into();
+        Self {
+            uri: tree.uri.to_string(),
+            state: state as i32,
+            replica: tree.replica.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<volume::NexusTopologyTree> for NexusTopologyTree {
+    type Error = ReplyError;
+    fn try_from(tree: volume::NexusTopologyTree) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uuid: NexusId::try_from(StringValue(Some(tree.uuid)))?,
+            status: topology_status(nexus::NexusStatus::from_i32(tree.status), "nexus.status")?,
+            last_known: tree.last_known,
+            node: match tree.node {
+                Some(node) => node.try_into()?,
+                None => {
+                    return Err(ReplyError::missing_argument(
+                        ResourceKind::Volume,
+                        "nexus.node",
+                    ))
+                }
+            },
+            children: tree
+                .children
+                .into_iter()
+                .map(ChildTopologyTree::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<NexusTopologyTree> for volume::NexusTopologyTree {
+    fn from(tree: NexusTopologyTree) -> Self {
+        let status: nexus::NexusStatus = tree.status.into();
+        Self {
+            uuid: tree.uuid.to_string(),
+            status: status as i32,
+            last_known: tree.last_known,
+            node: Some(tree.node.into()),
+            children: tree.children.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeTopologyTree> for VolumeTopologyTree {
+    type Error = ReplyError;
+    fn try_from(tree: volume::VolumeTopologyTree) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uuid: VolumeId::try_from(StringValue(Some(tree.uuid)))?,
+            status: topology_status(nexus::NexusStatus::from_i32(tree.status), "volume.status")?,
+            targets: tree
+                .targets
+                .into_iter()
+                .map(NexusTopologyTree::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            replicas: tree
+                .replicas
+                .into_iter()
+                .map(ReplicaTopologyTree::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<VolumeTopologyTree> for volume::VolumeTopologyTree {
+    fn from(tree: VolumeTopologyTree) -> Self {
+        let status: nexus::NexusStatus = tree.status.into();
+        Self {
+            uuid: tree.uuid.to_string(),
+            status: status as i32,
+            targets: tree.targets.into_iter().map(Into::into).collect(),
+            replicas: tree.replicas.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Trait to be implemented for GetVolumeHistory operation
+pub trait GetVolumeHistoryInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the volume
+    fn uuid(&self) -> VolumeId;
+}
+
+impl GetVolumeHistoryInfo for GetVolumeHistory {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetVolumeHistoryRequest type
+#[derive(Debug)]
+pub struct ValidatedGetVolumeHistoryRequest {
+    uuid: VolumeId,
+}
+
+impl GetVolumeHistoryInfo for ValidatedGetVolumeHistoryRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+}
+
+impl ValidateRequestTypes for GetVolumeHistoryRequest {
+    type Validated = ValidatedGetVolumeHistoryRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetVolumeHistoryRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.volume_id)))?,
+        })
+    }
+}
+
+impl From<&dyn GetVolumeHistoryInfo> for GetVolumeHistory {
+    fn from(data: &dyn GetVolumeHistoryInfo) -> Self {
+        Self { uuid: data.uuid() }
+    }
+}
+
+impl From<&dyn GetVolumeHistoryInfo> for GetVolumeHistoryRequest {
+    fn from(data: &dyn GetVolumeHistoryInfo) -> Self {
+        Self {
+            volume_id: data.uuid().to_string(),
+        }
+    }
+}
+
+/// Trait to be implemented for GetVolumeRevisionDiff operation
+pub trait GetVolumeRevisionDiffInfo: Send + Sync + std::fmt::Debug {
+    /// Uuid of the volume
+    fn uuid(&self) -> VolumeId;
+    /// Number of the revision
+    fn revision(&self) -> u64;
+}
+
+impl GetVolumeRevisionDiffInfo for GetVolumeRevisionDiff {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn revision(&self) -> u64 {
+        self.revision
+    }
+}
+
+/// Intermediate structure that validates the conversion to GetVolumeRevisionDiffRequest type
+#[derive(Debug)]
+pub struct ValidatedGetVolumeRevisionDiffRequest {
+    uuid: VolumeId,
+    revision: u64,
+}
+
+impl GetVolumeRevisionDiffInfo for ValidatedGetVolumeRevisionDiffRequest {
+    fn uuid(&self) -> VolumeId {
+        self.uuid.clone()
+    }
+    fn revision(&self) -> u64 {
+        self.revision
+    }
+}
+
+impl ValidateRequestTypes for GetVolumeRevisionDiffRequest {
+    type Validated = ValidatedGetVolumeRevisionDiffRequest;
+    fn validated(self) -> Result<Self::Validated, ReplyError> {
+        Ok(ValidatedGetVolumeRevisionDiffRequest {
+            uuid: VolumeId::try_from(StringValue(Some(self.volume_id)))?,
+            revision: self.revision,
+        })
+    }
+}
+
+impl From<&dyn GetVolumeRevisionDiffInfo> for GetVolumeRevisionDiff {
+    fn from(data: &dyn GetVolumeRevisionDiffInfo) -> Self {
+        Self {
+            uuid: data.uuid(),
+            revision: data.revision(),
+        }
+    }
+}
+
+impl From<&dyn GetVolumeRevisionDiffInfo> for GetVolumeRevisionDiffRequest {
+    fn from(data: &dyn GetVolumeRevisionDiffInfo) -> Self {
+        Self {
+            volume_id: data.uuid().to_string(),
+            revision: data.revision(),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeRevision> for VolumeRevision {
+    type Error = ReplyError;
+    fn try_from(value: volume::VolumeRevision) -> Result<Self, Self::Error> {
+        let operation = match value.operation {
+            Some(operation) => Some(serde_json::from_str(&operation).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, "operation", error.to_string())
+            })?),
+            None => None,
+        };
+        Ok(Self {
+            revision: value.revision,
+            updated_at: from_epoch_secs(value.updated_at),
+            operation,
+        })
+    }
+}
+
+impl From<VolumeRevision> for volume::VolumeRevision {
+    fn from(value: VolumeRevision) -> Self {
+        Self {
+            revision: value.revision,
+            updated_at: epoch_secs(value.updated_at),
+            operation: value.operation.map(|operation| operation.to_string()),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeHistory> for VolumeHistory {
+    type Error = ReplyError;
+    fn try_from(value: volume::VolumeHistory) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uuid: VolumeId::try_from(StringValue(Some(value.uuid)))?,
+            revisions: value
+                .revisions
+                .into_iter()
+                .map(VolumeRevision::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
+impl From<VolumeHistory> for volume::VolumeHistory {
+    fn from(value: VolumeHistory) -> Self {
+        Self {
+            uuid: value.uuid.to_string(),
+            revisions: value.revisions.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<volume::VolumeRevisionDiff> for VolumeRevisionDiff {
+    type Error = ReplyError;
+    fn try_from(value: volume::VolumeRevisionDiff) -> Result<Self, Self::Error> {
+        Ok(Self {
+            uuid: VolumeId::try_from(StringValue(Some(value.uuid)))?,
+            revision: value.revision,
+            previous: value.previous,
+            diff: serde_json::from_str(&value.diff).map_err(|error| {
+                ReplyError::invalid_argument(ResourceKind::Volume, "diff", error.to_string())
+            })?,
+        })
+    }
+}
+
+impl From<VolumeRevisionDiff> for volume::VolumeRevisionDiff {
+    fn from(value: VolumeRevisionDiff) -> Self {
+        Self {
+            uuid: value.uuid.to_string(),
+            revision: value.revision,
+            previous: value.previous,
+            diff: value.diff.to_string(),
         }
     }
 }
@@ -150,6 +150,7 @@ impl From<WatchType> for watch::WatchType {
             WatchType::Desired => Self::Desired,
             WatchType::Actual => Self::Actual,
             WatchType::All => Self::All,
+            WatchType::TargetChange => Self::TargetChange,
         }
     }
 }
@@ -160,6 +161,7 @@ impl From<watch::WatchType> for WatchType {
             watch::WatchType::Desired => Self::Desired,
             watch::WatchType::Actual => Self::Actual,
             watch::WatchType::All => Self::All,
+            watch::WatchType::TargetChange => Self::TargetChange,
         }
     }
 }
@@ -0,0 +1,446 @@
+use crate::{
+    context::{INTERNAL_CALLER_KEY, TENANT_KEY},
+    tracing::{clone_service, BoxedFuture, TonicServerRequest, TonicServerResponse},
+};
+use common_lib::{
+    transport_api::RETRY_AFTER_MS_KEY,
+    types::v0::transport::{CallerThrottles, MethodClass, RateLimit, RateLimits},
+};
+use ipnetwork::IpNetwork;
+use std::{
+    collections::HashMap,
+    net::IpAddr,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use tonic::{
+    codegen::http::HeaderMap, metadata::MetadataMap, transport::server::TcpConnectInfo, Code,
+    Status,
+};
+
+/// Maximum number of callers which are tracked, beyond which the least recently seen are evicted.
+const MAX_CALLERS: usize = 1024;
+/// Callers which haven't made a request for this long are evicted, along with their throttles.
+const IDLE_CALLER_TTL: Duration = Duration::from_secs(10 * 60);
+/// The metadata which callers set about themselves, which is only honoured from trusted peers.
+const CALLER_CLAIMS: [&str; 2] = [INTERNAL_CALLER_KEY, TENANT_KEY];
+
+/// A token bucket, refilled at the rate of its limit up to its burst, which each request takes a
+/// token from.
+#[derive(Debug, Clone)]
+pub struct TokenBucket {
+    rate: f64,
+    burst: f64,
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    /// Return a new full bucket for the given `limit`.
+    pub fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            rate: limit.rate.max(1) as f64,
+            burst: limit.burst.max(1) as f64,
+            tokens: limit.burst.max(1) as f64,
+            refilled: now,
+        }
+    }
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.refilled).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate).min(self.burst);
+        self.refilled = now;
+    }
+    /// Take a token from the bucket, or return how long until a token is available.
+    pub fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.rate))
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Caller {
+    bucket: TokenBucket,
+    throttled: u64,
+    last_seen: Instant,
+}
+
+#[derive(Debug)]
+struct Callers {
+    callers: HashMap<(String, MethodClass), Caller>,
+    swept: Instant,
+}
+
+impl Callers {
+    fn new(now: Instant) -> Self {
+        Self {
+            callers: HashMap::new(),
+            swept: now,
+        }
+    }
+    /// Evict the callers which have been idle for `IDLE_CALLER_TTL`, and make room for a new
+    /// caller by evicting the least recently seen one if there are `MAX_CALLERS` already.
+    fn evict(&mut self, now: Instant, new_caller: bool) {
+        let full = self.callers.len() >= MAX_CALLERS;
+        if full || now.saturating_duration_since(self.swept) >= IDLE_CALLER_TTL {
+            self.callers.retain(|_, caller| {
+                now.saturating_duration_since(caller.last_seen) < IDLE_CALLER_TTL
+            });
+            self.swept = now;
+        }
+        if new_caller && self.callers.len() >= MAX_CALLERS {
+            let least_recent = self
+                .callers
+                .iter()
+                .min_by_key(|(_, caller)| caller.last_seen)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = least_recent {
+                self.callers.remove(&key);
+            }
+        }
+    }
+}
+
+/// Rate limits the requests of each caller, per class of method.
+#[derive(Debug)]
+pub struct RateLimiter {
+    mutations: Option<RateLimit>,
+    reads: Option<RateLimit>,
+    trusted_networks: Vec<IpNetwork>,
+    callers: Mutex<Callers>,
+}
+
+impl RateLimiter {
+    /// Return a new `Self` with the given limits for the mutations and the reads, either of which
+    /// is not limited if `None`.
+    pub fn new(mutations: Option<RateLimit>, reads: Option<RateLimit>) -> Self {
+        Self {
+            mutations,
+            reads,
+            trusted_networks: vec![],
+            callers: Mutex::new(Callers::new(Instant::now())),
+        }
+    }
+    /// Trust the peers within the given networks, eg: the control-plane's own, to tell their
+    /// tenant and whether they're the control-plane itself.
+    /// The requests of any other peer are limited as made by its address, whatever they claim.
+    #[must_use]
+    pub fn with_trusted_networks(mut self, networks: Vec<IpNetwork>) -> Self {
+        self.trusted_networks = networks;
+        self
+    }
+    /// Check if the `peer` is within the trusted networks.
+    fn trusts(&self, peer: Option<IpAddr>) -> bool {
+        match peer {
+            Some(peer) => self.trusted_networks.iter().any(|n| n.contains(peer)),
+            None => false,
+        }
+    }
+    fn limit(&self, class: MethodClass) -> Option<&RateLimit> {
+        match class {
+            MethodClass::Mutation => self.mutations.as_ref(),
+            MethodClass::Read => self.reads.as_ref(),
+        }
+    }
+    /// Admit a request of the `class` from the caller `identity`, or return how long until it
+    /// may be retried.
+    pub fn admit(&self, identity: &str, class: MethodClass, now: Instant) -> Result<(), Duration> {
+        let limit = match self.limit(class) {
+            Some(limit) => limit,
+            None => return Ok(()),
+        };
+        let key = (identity.to_string(), class);
+        let mut callers = self.callers.lock().expect("not poisoned");
+        let new_caller = !callers.callers.contains_key(&key);
+        callers.evict(now, new_caller);
+        let caller = callers.callers.entry(key).or_insert_with(|| Caller {
+            bucket: TokenBucket::new(limit, now),
+            throttled: 0,
+            last_seen: now,
+        });
+        caller.last_seen = now;
+        caller.bucket.take(now).map_err(|retry_after| {
+            caller.throttled += 1;
+            retry_after
+        })
+    }
+    /// Get the limits along with the number of requests throttled per caller and class.
+    pub fn limits(&self) -> RateLimits {
+        let callers = self.callers.lock().expect("not poisoned");
+        let mut throttled = callers
+            .callers
+            .iter()
+            .filter(|(_, caller)| caller.throttled > 0)
+            .map(|((identity, class), caller)| CallerThrottles {
+                identity: identity.clone(),
+                class: *class,
+                throttled: caller.throttled,
+            })
+            .collect::<Vec<_>>();
+        throttled.sort_by(|a, b| (&a.identity, a.class).cmp(&(&b.identity, b.class)));
+        RateLimits {
+            mutations: self.mutations,
+            reads: self.reads,
+            throttled,
+        }
+    }
+}
+
+/// Rate limit the gRPC requests as per the `RateLimiter`.
+/// The requests marked as made by the control-plane itself, eg: by the HA cluster agent, are
+/// never limited, and neither are the io-engine registrations, which can't be marked.
+/// Only the marks of the trusted peers are honoured, those of any other peer are stripped.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: Arc<RateLimiter>,
+}
+impl RateLimitLayer {
+    /// Return new `Self`
+    pub fn new(limiter: Arc<RateLimiter>) -> Self {
+        Self { limiter }
+    }
+}
+impl<S> tower::Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, service: S) -> Self::Service {
+        RateLimitService {
+            service,
+            limiter: self.limiter.clone(),
+        }
+    }
+}
+
+/// Service which rejects the requests of the callers which exceed their rate limit with
+/// `ResourceExhausted`, hinting when they may be retried.
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    service: S,
+    limiter: Arc<RateLimiter>,
+}
+
+impl<S> tower::Service<TonicServerRequest> for RateLimitService<S>
+where
+    S: tower::Service<TonicServerRequest, Response = TonicServerResponse> + Send + Clone + 'static,
+    S::Future: Send,
+    S::Error: ToString,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxedFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<(), Self::Error>> {
+        self.service.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: TonicServerRequest) -> Self::Future {
+        let peer = request
+            .extensions()
+            .get::<TcpConnectInfo>()
+            .and_then(|info| info.remote_addr())
+            .map(|addr| addr.ip());
+        if !self.limiter.trusts(peer) {
+            strip_claims(request.headers_mut());
+        }
+        if let Some(class) = limited_class(&request) {
+            let identity = caller_identity(request.headers(), peer);
+            if let Err(retry_after) = self.limiter.admit(&identity, class, Instant::now()) {
+                tracing::debug!(%identity, %class, ?retry_after, "Throttled a gRPC request");
+                let response = throttled(&identity, class, retry_after).to_http();
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+        let mut service = clone_service(&mut self.service);
+        Box::pin(async move { service.call(request).await })
+    }
+}
+
+/// The class of the method of the `request`, unless it's exempt from the rate limits.
+fn limited_class(request: &TonicServerRequest) -> Option<MethodClass> {
+    if request.headers().contains_key(INTERNAL_CALLER_KEY) {
+        return None;
+    }
+    let (service, method) = request
+        .uri()
+        .path()
+        .trim_start_matches('/')
+        .split_once('/')?;
+    if service.ends_with(".registration.Registration") {
+        return None;
+    }
+    Some(MethodClass::of(method))
+}
+
+/// Strip the metadata which the caller set about itself, so neither the limits nor the services
+/// make use of it.
+fn strip_claims(headers: &mut HeaderMap) {
+    for key in CALLER_CLAIMS {
+        headers.remove(key);
+    }
+}
+
+/// The tenant of the caller, or the address of its `peer` if it has none.
+fn caller_identity(headers: &HeaderMap, peer: Option<IpAddr>) -> String {
+    if let Some(tenant) = headers
+        .get(TENANT_KEY)
+        .and_then(|tenant| tenant.to_str().ok())
+    {
+        return tenant.to_string();
+    }
+    peer.map(|peer| peer.to_string())
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+fn throttled(identity: &str, class: MethodClass, retry_after: Duration) -> Status {
+    let retry_after_ms = (retry_after.as_secs_f64() * 1000.0).ceil() as u64;
+    let mut metadata = MetadataMap::new();
+    metadata.insert(RETRY_AFTER_MS_KEY, retry_after_ms.into());
+    Status::with_metadata(
+        Code::ResourceExhausted,
+        format!(
+            "Too many {} requests from '{}', retry after {}ms",
+            class, identity, retry_after_ms
+        ),
+        metadata,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: RateLimit = RateLimit { rate: 2, burst: 4 };
+
+    #[test]
+    fn bucket_bursts_then_refills() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&LIMIT, start);
+        for _ in 0 .. LIMIT.burst {
+            bucket.take(start).unwrap();
+        }
+        // empty, with a token every 500ms
+        assert_eq!(bucket.take(start), Err(Duration::from_millis(500)));
+        let retry_after = bucket.take(start + Duration::from_millis(200)).unwrap_err();
+        assert!((retry_after.as_secs_f64() - 0.3).abs() < 1e-6);
+
+        bucket.take(start + Duration::from_millis(600)).unwrap();
+        assert!(bucket.take(start + Duration::from_millis(600)).is_err());
+    }
+
+    #[test]
+    fn bucket_is_capped_by_its_burst() {
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&LIMIT, start);
+        bucket.take(start).unwrap();
+
+        // idle for long enough to refill many times over, yet no more than a burst is admitted
+        let later = start + Duration::from_secs(60);
+        for _ in 0 .. LIMIT.burst {
+            bucket.take(later).unwrap();
+        }
+        assert!(bucket.take(later).is_err());
+    }
+
+    #[test]
+    fn callers_and_classes_are_limited_apart() {
+        let limiter = RateLimiter::new(Some(RateLimit { rate: 1, burst: 1 }), None);
+        let now = Instant::now();
+
+        limiter.admit("a", MethodClass::Mutation, now).unwrap();
+        assert!(limiter.admit("a", MethodClass::Mutation, now).is_err());
+        assert!(limiter.admit("a", MethodClass::Mutation, now).is_err());
+        // other callers have their own bucket, and the reads are not limited
+        limiter.admit("b", MethodClass::Mutation, now).unwrap();
+        for _ in 0 .. 10 {
+            limiter.admit("a", MethodClass::Read, now).unwrap();
+        }
+
+        let limits = limiter.limits();
+        assert_eq!(
+            limits.throttled,
+            vec![CallerThrottles {
+                identity: "a".to_string(),
+                class: MethodClass::Mutation,
+                throttled: 2,
+            }]
+        );
+    }
+
+    #[test]
+    fn idle_callers_are_evicted() {
+        let limiter = RateLimiter::new(Some(RateLimit { rate: 1, burst: 1 }), None);
+        let now = Instant::now();
+
+        limiter.admit("a", MethodClass::Mutation, now).unwrap();
+        assert!(limiter.admit("a", MethodClass::Mutation, now).is_err());
+        assert_eq!(limiter.limits().throttled.len(), 1);
+
+        // the throttles of a caller don't keep it around once it's idle
+        let later = now + IDLE_CALLER_TTL;
+        limiter.admit("b", MethodClass::Mutation, later).unwrap();
+        assert!(limiter.limits().throttled.is_empty());
+        assert_eq!(limiter.callers.lock().unwrap().callers.len(), 1);
+    }
+
+    #[test]
+    fn callers_are_capped() {
+        let limiter = RateLimiter::new(Some(RateLimit { rate: 1, burst: 1 }), None);
+        let now = Instant::now();
+
+        for caller in 0 .. MAX_CALLERS {
+            let at = now + Duration::from_millis(caller as u64);
+            limiter
+                .admit(&caller.to_string(), MethodClass::Mutation, at)
+                .unwrap();
+            assert!(limiter
+                .admit(&caller.to_string(), MethodClass::Mutation, at)
+                .is_err());
+        }
+        let at = now + Duration::from_millis(MAX_CALLERS as u64);
+        limiter.admit("new", MethodClass::Mutation, at).unwrap();
+
+        // the least recently seen caller makes room for the new one, throttled or not
+        let callers = limiter.callers.lock().unwrap();
+        assert_eq!(callers.callers.len(), MAX_CALLERS);
+        assert!(!callers
+            .callers
+            .contains_key(&("0".to_string(), MethodClass::Mutation)));
+    }
+
+    #[test]
+    fn only_trusted_peers_claim_their_identity() {
+        let limiter = RateLimiter::new(None, None)
+            .with_trusted_networks(vec!["10.1.0.0/16".parse().unwrap()]);
+        let trusted: IpAddr = "10.1.0.3".parse().unwrap();
+        let untrusted: IpAddr = "192.0.2.1".parse().unwrap();
+        assert!(limiter.trusts(Some(trusted)));
+        assert!(!limiter.trusts(Some(untrusted)));
+        assert!(!limiter.trusts(None));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(TENANT_KEY, "tenant".parse().unwrap());
+        headers.insert(INTERNAL_CALLER_KEY, "true".parse().unwrap());
+        assert_eq!(caller_identity(&headers, Some(trusted)), "tenant");
+
+        strip_claims(&mut headers);
+        assert!(headers.is_empty());
+        assert_eq!(caller_identity(&headers, Some(untrusted)), "192.0.2.1");
+        assert_eq!(caller_identity(&headers, None), "unknown");
+    }
+
+    #[test]
+    fn method_classes() {
+        assert_eq!(MethodClass::of("CreateVolume"), MethodClass::Mutation);
+        assert_eq!(MethodClass::of("PatchSpec"), MethodClass::Mutation);
+        assert_eq!(MethodClass::of("GetVolumes"), MethodClass::Read);
+        assert_eq!(MethodClass::of("Probe"), MethodClass::Read);
+        assert_eq!(MethodClass::of("ValidateCreateVolume"), MethodClass::Read);
+    }
+}
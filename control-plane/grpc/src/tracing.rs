@@ -41,7 +41,7 @@ impl<S> OpenTelClientService<S> {
 }
 
 type TonicClientRequest = Request<tonic::body::BoxBody>;
-type BoxedFuture<Resp, Err> = Pin<Box<dyn Future<Output = Result<Resp, Err>> + Send>>;
+pub(crate) type BoxedFuture<Resp, Err> = Pin<Box<dyn Future<Output = Result<Resp, Err>> + Send>>;
 
 impl tower::Service<TonicClientRequest> for OpenTelClientService<Channel> {
     type Response = <Channel as tower::Service<TonicClientRequest>>::Response;
@@ -105,8 +105,8 @@ impl<S> OpenTelServerService<S> {
     }
 }
 
-type TonicServerRequest = Request<tonic::transport::Body>;
-type TonicServerResponse = Response<tonic::body::BoxBody>;
+pub(crate) type TonicServerRequest = Request<tonic::transport::Body>;
+pub(crate) type TonicServerResponse = Response<tonic::body::BoxBody>;
 impl<S> tower::Service<TonicServerRequest> for OpenTelServerService<S>
 where
     S: tower::Service<TonicServerRequest, Response = TonicServerResponse> + Send + Clone + 'static,
@@ -149,7 +149,7 @@ where
 /// (see `poll_ready` ).
 /// The simple solution here is to clone the service but swap the clone with the original, so we can
 /// use the original service which is ready.
-fn clone_service<
+pub(crate) fn clone_service<
     T: tower::Service<Req, Response = Response<R>, Error = E> + Clone + Send + 'static,
     Req: Send + 'static,
     R,
@@ -110,7 +110,7 @@ impl Cordoning for Node {
     async fn cordon(id: &Self::ID, label: &str, output: &OutputFormat) {
         match RestClient::client()
             .nodes_api()
-            .put_node_cordon(id, label)
+            .put_node_cordon(id, label, None)
             .await
         {
             Ok(node) => match output {
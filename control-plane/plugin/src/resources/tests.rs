@@ -30,11 +30,14 @@ async fn setup() {
             &Uuid::parse_str(VOLUME_UUID).unwrap(),
             CreateVolumeBody {
                 policy: Default::default(),
-                replicas: 1,
+                replicas: Some(1),
                 size: 5242880,
                 topology: None,
                 labels: None,
-                thin: false,
+                thin: Some(false),
+                placement: None,
+                pools: None,
+                allow_same_node: None,
             },
         )
         .await
@@ -74,7 +77,7 @@ async fn get_volumes() {
         .await
         .rest_v00()
         .volumes_api()
-        .get_volumes(0, None)
+        .get_volumes(0, None, None, None)
         .await
         .unwrap();
     let volume_state = volumes.entries[0].state.clone();
@@ -101,11 +104,14 @@ async fn get_volumes_paginated() {
                 &Uuid::parse_str(uuid).unwrap(),
                 CreateVolumeBody {
                     policy: Default::default(),
-                    replicas: 1,
+                    replicas: Some(1),
                     size: 5242880,
-                    thin: false,
+                    thin: Some(false),
                     topology: None,
                     labels: None,
+                    placement: None,
+                    pools: None,
+                    allow_same_node: None,
                 },
             )
             .await
@@ -116,7 +122,7 @@ async fn get_volumes_paginated() {
         .await
         .rest_v00()
         .volumes_api()
-        .get_volumes(0, None)
+        .get_volumes(0, None, None, None)
         .await
         .unwrap()
         .entries
@@ -133,7 +139,7 @@ async fn get_volumes_paginated() {
             .await
             .rest_v00()
             .volumes_api()
-            .get_volumes(max_entries, starting_token)
+            .get_volumes(max_entries, starting_token, None, None)
             .await
             .unwrap();
         // The number of returned volumes should be equal to the number of specified max entries.
@@ -163,7 +169,7 @@ async fn get_pools() {
         .await
         .rest_v00()
         .pools_api()
-        .get_pools()
+        .get_pools(None, None)
         .await
         .unwrap();
     let pool_state = pools[0].state.as_ref().unwrap().clone();
@@ -72,7 +72,7 @@ async fn get_paginated_volumes() -> Option<Vec<openapi::models::Volume>> {
     while starting_token.is_some() {
         match RestClient::client()
             .volumes_api()
-            .get_volumes(max_entries, starting_token)
+            .get_volumes(max_entries, starting_token, None, None)
             .await
         {
             Ok(vols) => {
@@ -116,7 +116,7 @@ impl Scale for Volume {
     async fn scale(id: &Self::ID, replica_count: u8, output: &utils::OutputFormat) {
         match RestClient::client()
             .volumes_api()
-            .put_volume_replica_count(id, replica_count)
+            .put_volume_replica_count(id, replica_count, None)
             .await
         {
             Ok(volume) => match output {
@@ -1,5 +1,5 @@
 use actix_web::HttpRequest;
-use jsonwebtoken::{crypto, Algorithm, DecodingKey};
+use jsonwebtoken::{crypto, Algorithm, DecodingKey, Validation};
 
 use http::HeaderValue;
 use std::fs::File;
@@ -85,6 +85,16 @@ impl JsonWebKey {
         }
     }
 
+    /// Decode the claims of a bearer token, verifying its signature
+    fn claims(&self, token: &str) -> Result<Claims, AuthError> {
+        let mut validation = Validation::new(self.algorithm());
+        validation.validate_exp = false;
+        validation.required_spec_claims.clear();
+        jsonwebtoken::decode::<Claims>(token, &self.decoding_key()?, &validation)
+            .map(|data| data.claims)
+            .map_err(|source| AuthError::Verification { source })
+    }
+
     // Returns true if REST calls should be authenticated.
     fn auth_enabled(&self) -> bool {
         self.enabled
@@ -133,6 +143,24 @@ pub fn authenticate(req: &HttpRequest) -> Result<(), AuthError> {
     }
 }
 
+/// Claims of the bearer token which are made use of by the REST server.
+#[derive(serde::Deserialize, Debug)]
+struct Claims {
+    /// the tenant on behalf of which the requests are made, which the core agent rate limits
+    tenant: Option<String>,
+}
+
+/// Get the tenant claim of the bearer token of the HTTP request, if authentication is enabled
+/// and the token is valid.
+pub fn tenant(req: &HttpRequest) -> Option<String> {
+    let jwk: &JsonWebKey = req.app_data()?;
+    if !jwk.auth_enabled() {
+        return None;
+    }
+    let token = format_token(req.headers().get(http::header::AUTHORIZATION)?).ok()?;
+    jwk.claims(&token).ok()?.tenant
+}
+
 // Ensure the token is formatted correctly by removing the "Bearer " prefix if
 // present.
 fn format_token(token: &HeaderValue) -> Result<String, AuthError> {
@@ -0,0 +1,388 @@
+//! Optional in-process cache of the responses of the hot list endpoints, eg: `GET /v0/volumes`,
+//! which spares the core agent from the dashboards polling them.
+
+use actix_service::{forward_ready, Service, Transform};
+use actix_web::{
+    body::{to_bytes, BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::{
+        header::{self, HeaderValue},
+        Method, StatusCode,
+    },
+    web::Bytes,
+    HttpResponse,
+};
+use futures::future::{ready, LocalBoxFuture, Ready};
+use std::{
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+/// The resources whose lists are cached.
+const CACHED_KINDS: [&str; 5] = ["nodes", "pools", "volumes", "replicas", "nexuses"];
+/// The maximum number of cached responses, as each tenant and query is cached separately.
+const MAX_ENTRIES: usize = 1024;
+
+/// A cached response is only returned to the same tenant, on the same path and query.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+struct CacheKey {
+    path: String,
+    query: String,
+    /// The authorization header, as each tenant may see a different list.
+    tenant: String,
+}
+
+#[derive(Debug)]
+struct CachedResponse {
+    kind: &'static str,
+    cached_at: Instant,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    /// The response to a cache hit, with its `Age` so clients can tell how stale it is.
+    fn response(&self) -> HttpResponse {
+        let mut response = HttpResponse::Ok();
+        if let Some(content_type) = &self.content_type {
+            response.insert_header((header::CONTENT_TYPE, content_type.clone()));
+        }
+        response.insert_header((header::AGE, self.cached_at.elapsed().as_secs().to_string()));
+        response.body(self.body.clone())
+    }
+}
+
+/// Middleware which caches the successful responses of the list endpoints for `ttl`.
+/// The cached lists of a resource are dropped when it is modified through this instance.
+#[derive(Debug, Clone)]
+pub(crate) struct ResponseCache {
+    ttl: Option<Duration>,
+    entries: Arc<Mutex<HashMap<CacheKey, CachedResponse>>>,
+}
+
+impl ResponseCache {
+    /// Return a new `Self` which caches responses for `ttl`, or not at all if `None`.
+    pub(crate) fn new(ttl: Option<Duration>) -> Self {
+        Self {
+            ttl,
+            entries: Default::default(),
+        }
+    }
+
+    /// The key of the request, if it's for a cached list, along with the kind of the list.
+    fn list_key(req: &ServiceRequest) -> Option<(&'static str, CacheKey)> {
+        if req.method() != Method::GET {
+            return None;
+        }
+        let kind = req.path().trim_end_matches('/').strip_prefix("/v0/")?;
+        let kind = CACHED_KINDS.iter().find(|k| **k == kind)?;
+        let tenant = req
+            .headers()
+            .get(header::AUTHORIZATION)
+            .map(|t| String::from_utf8_lossy(t.as_bytes()).to_string())
+            .unwrap_or_default();
+        Some((
+            kind,
+            CacheKey {
+                path: req.path().to_string(),
+                query: req.query_string().to_string(),
+                tenant,
+            },
+        ))
+    }
+
+    /// The kinds of the lists which a request to `path` may modify.
+    fn modified_kinds(path: &str) -> Vec<&'static str> {
+        let mut kinds = CACHED_KINDS
+            .iter()
+            .filter(|k| path.split('/').any(|segment| segment == **k))
+            .copied()
+            .collect::<Vec<_>>();
+        // volumes are made of replicas and nexuses, which take up the pools' capacity
+        if kinds.contains(&"volumes") {
+            kinds.extend(["replicas", "nexuses", "pools"]);
+        }
+        kinds
+    }
+
+    fn get(&self, key: &CacheKey) -> Option<HttpResponse> {
+        let ttl = self.ttl?;
+        let mut entries = self.entries.lock().expect("not poisoned");
+        match entries.get(key) {
+            Some(entry) if entry.cached_at.elapsed() < ttl => Some(entry.response()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn insert(&self, kind: &'static str, key: CacheKey, response: &HttpResponse<Bytes>) {
+        let ttl = match self.ttl {
+            Some(ttl) => ttl,
+            None => return,
+        };
+        let entry = CachedResponse {
+            kind,
+            cached_at: Instant::now(),
+            content_type: response.headers().get(header::CONTENT_TYPE).cloned(),
+            body: response.body().clone(),
+        };
+        let mut entries = self.entries.lock().expect("not poisoned");
+        entries.retain(|_, entry| entry.cached_at.elapsed() < ttl);
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.cached_at)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, entry);
+    }
+
+    fn invalidate(&self, kinds: &[&'static str]) {
+        if !kinds.is_empty() {
+            let mut entries = self.entries.lock().expect("not poisoned");
+            entries.retain(|_, entry| !kinds.contains(&entry.kind));
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for ResponseCache
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = actix_web::Error;
+    type Transform = ResponseCacheMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(ResponseCacheMiddleware {
+            service: Rc::new(service),
+            cache: self.clone(),
+        }))
+    }
+}
+
+/// The `ResponseCache` middleware service.
+pub(crate) struct ResponseCacheMiddleware<S> {
+    service: Rc<S>,
+    cache: ResponseCache,
+}
+
+impl<S, B> Service<ServiceRequest> for ResponseCacheMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: MessageBody + 'static,
+{
+    type Response = ServiceResponse<BoxBody>;
+    type Error = actix_web::Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let cache = self.cache.clone();
+        Box::pin(async move {
+            if cache.ttl.is_none() {
+                return service.call(req).await.map(|r| r.map_into_boxed_body());
+            }
+            let (kind, key) = match ResponseCache::list_key(&req) {
+                Some(list) => list,
+                None if req.method() == Method::GET => {
+                    return service.call(req).await.map(|r| r.map_into_boxed_body());
+                }
+                None => {
+                    let kinds = ResponseCache::modified_kinds(req.path());
+                    let response = service.call(req).await;
+                    cache.invalidate(&kinds);
+                    return response.map(|r| r.map_into_boxed_body());
+                }
+            };
+            // the handlers authenticate the request, so a hit is only served to a request which
+            // would be let through, eg: not to a token which has since expired
+            if crate::authentication::authenticate(req.request()).is_ok() {
+                if let Some(response) = cache.get(&key) {
+                    return Ok(req.into_response(response));
+                }
+            }
+
+            let response = service.call(req).await?;
+            if response.status() != StatusCode::OK {
+                return Ok(response.map_into_boxed_body());
+            }
+            let (req, response) = response.into_parts();
+            let (response, body) = response.into_parts();
+            let body = to_bytes(body).await.map_err(|error| {
+                let error: Box<dyn std::error::Error> = error.into();
+                actix_web::error::ErrorInternalServerError(error.to_string())
+            })?;
+            let response = response.set_body(body);
+            cache.insert(kind, key, &response);
+            Ok(ServiceResponse::new(req, response.map_into_boxed_body()))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::{test, web, App};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Requests which made it past the cache, ie: which would call the core agent.
+    #[derive(Clone, Default)]
+    struct AgentCalls(Arc<AtomicUsize>);
+    impl AgentCalls {
+        fn count(&self) -> usize {
+            self.0.load(Ordering::SeqCst)
+        }
+    }
+
+    macro_rules! app {
+        ($ttl:expr, $calls:expr) => {
+            app!(ResponseCache::new($ttl), None, $calls)
+        };
+        ($cache:expr, $jwk:expr, $calls:expr) => {{
+            let calls = $calls.clone();
+            test::init_service(
+                App::new()
+                    .wrap_fn(move |req, srv| {
+                        calls.0.fetch_add(1, Ordering::SeqCst);
+                        srv.call(req)
+                    })
+                    .wrap($cache)
+                    .app_data(crate::authentication::init($jwk))
+                    .route(
+                        "/v0/volumes",
+                        web::get().to(|| async { HttpResponse::Ok().json(vec!["volume"]) }),
+                    )
+                    .route(
+                        "/v0/volumes/{id}",
+                        web::put().to(|| async { HttpResponse::Ok().finish() }),
+                    )
+                    .route(
+                        "/v0/nodes",
+                        web::get().to(|| async { HttpResponse::Ok().json(vec!["node"]) }),
+                    ),
+            )
+            .await
+        }};
+    }
+
+    fn get(path: &str) -> test::TestRequest {
+        test::TestRequest::get().uri(path)
+    }
+
+    #[actix_web::test]
+    async fn cache_off() {
+        let calls = AgentCalls::default();
+        let app = app!(None, calls);
+
+        for _ in 0 .. 10 {
+            let response = test::call_service(&app, get("/v0/volumes").to_request()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            assert!(response.headers().get(header::AGE).is_none());
+        }
+        assert_eq!(calls.count(), 10);
+    }
+
+    #[actix_web::test]
+    async fn cache_on() {
+        let calls = AgentCalls::default();
+        let app = app!(Some(Duration::from_millis(500)), calls);
+
+        for _ in 0 .. 10 {
+            let response = test::call_service(&app, get("/v0/volumes").to_request()).await;
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = test::read_body(response).await;
+            assert_eq!(body, Bytes::from_static(b"[\"volume\"]"));
+        }
+        assert_eq!(calls.count(), 1);
+
+        // the hits tell their age
+        let response = test::call_service(&app, get("/v0/volumes").to_request()).await;
+        assert_eq!(response.headers().get(header::AGE).unwrap(), "0");
+
+        // other tenants and queries are cached separately
+        let request = get("/v0/volumes").insert_header((header::AUTHORIZATION, "Bearer other"));
+        test::call_service(&app, request.to_request()).await;
+        test::call_service(&app, get("/v0/volumes?max_entries=1").to_request()).await;
+        assert_eq!(calls.count(), 3);
+
+        // until the responses expire
+        actix_web::rt::time::sleep(Duration::from_millis(500)).await;
+        test::call_service(&app, get("/v0/volumes").to_request()).await;
+        assert_eq!(calls.count(), 4);
+    }
+
+    #[actix_web::test]
+    async fn cache_invalidation() {
+        let calls = AgentCalls::default();
+        let app = app!(Some(Duration::from_secs(60)), calls);
+
+        test::call_service(&app, get("/v0/volumes").to_request()).await;
+        test::call_service(&app, get("/v0/nodes").to_request()).await;
+        assert_eq!(calls.count(), 2);
+
+        let request = test::TestRequest::put().uri("/v0/volumes/1").to_request();
+        test::call_service(&app, request).await;
+        assert_eq!(calls.count(), 3);
+
+        // only the lists of the modified resource are dropped
+        test::call_service(&app, get("/v0/volumes").to_request()).await;
+        test::call_service(&app, get("/v0/nodes").to_request()).await;
+        assert_eq!(calls.count(), 4);
+    }
+
+    #[actix_web::test]
+    async fn cache_expiry() {
+        let calls = AgentCalls::default();
+        let cache = ResponseCache::new(Some(Duration::from_millis(200)));
+        let app = app!(cache.clone(), None, calls);
+
+        for query in 0 .. MAX_ENTRIES + 10 {
+            let uri = format!("/v0/volumes?max_entries={}", query);
+            test::call_service(&app, get(&uri).to_request()).await;
+        }
+        assert_eq!(cache.entries.lock().unwrap().len(), MAX_ENTRIES);
+        // the oldest responses make room for the new ones
+        test::call_service(&app, get("/v0/volumes?max_entries=0").to_request()).await;
+        assert_eq!(calls.count(), MAX_ENTRIES + 11);
+
+        // expired responses are swept on the next insert, whatever their key
+        actix_web::rt::time::sleep(Duration::from_millis(200)).await;
+        test::call_service(&app, get("/v0/nodes").to_request()).await;
+        assert_eq!(cache.entries.lock().unwrap().len(), 1);
+    }
+
+    #[actix_web::test]
+    async fn cache_authentication() {
+        let calls = AgentCalls::default();
+        let cache = ResponseCache::new(Some(Duration::from_secs(60)));
+        let request = || get("/v0/volumes").insert_header((header::AUTHORIZATION, "Bearer bad"));
+
+        let app = app!(cache.clone(), None, calls);
+        test::call_service(&app, request().to_request()).await;
+        test::call_service(&app, request().to_request()).await;
+        assert_eq!(calls.count(), 1);
+
+        // a token which no longer authenticates is not served from the cache
+        let jwk = std::env::current_dir()
+            .expect("Failed to get current directory")
+            .join("authentication")
+            .join("jwk");
+        let app = app!(cache, Some(jwk.to_string_lossy().to_string()), calls);
+        test::call_service(&app, request().to_request()).await;
+        assert_eq!(calls.count(), 2);
+    }
+}
@@ -0,0 +1,238 @@
+//! Aggregated health of the control plane, served on `GET /health/cluster`, so that operators
+//! can tell whether it's healthy with a single request rather than probing each component.
+
+use crate::v0::{cluster_agent, core_grpc};
+use actix_web::{http::StatusCode, web, HttpResponse};
+use common_lib::types::v0::transport::{Filter, GetStoreHealth, NodeStatus};
+use grpc::operations::{
+    ha_node::traits::ClusterAgentOperations, node::traits::NodeOperations,
+    registry::traits::RegistryOperations, volume::traits::VolumeOperations,
+};
+use serde::Serialize;
+use std::{future::Future, time::Duration};
+
+/// Health of the control plane or of one of its components.
+#[derive(Serialize, Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum HealthStatus {
+    Healthy,
+    Degraded,
+    Unhealthy,
+    /// The health of the component could not be determined, eg: it's not deployed.
+    /// The control plane as a whole is never reported as such.
+    Unknown,
+}
+
+/// Health of a control plane component.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ComponentHealth {
+    name: &'static str,
+    status: HealthStatus,
+    /// What the check found, eg: how many nodes are online.
+    details: String,
+    /// When the check completed, in RFC 3339 format.
+    checked_at: String,
+}
+
+impl ComponentHealth {
+    fn new(name: &'static str, status: HealthStatus, details: impl Into<String>) -> Self {
+        Self {
+            name,
+            status,
+            details: details.into(),
+            checked_at: now(),
+        }
+    }
+}
+
+/// Health of the control plane as a whole, along with the health of each component.
+#[derive(Serialize, Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ClusterHealth {
+    status: HealthStatus,
+    components: Vec<ComponentHealth>,
+    timestamp: String,
+}
+
+/// The components without which the control plane cannot serve any request.
+/// The control plane is merely degraded when any of the other components is not healthy.
+const CRITICAL_COMPONENTS: [&str; 2] = ["rest", "core"];
+
+impl ClusterHealth {
+    fn new(components: Vec<ComponentHealth>) -> Self {
+        let status = components
+            .iter()
+            .fold(HealthStatus::Healthy, |status, component| {
+                match component.status {
+                    HealthStatus::Healthy | HealthStatus::Unknown => status,
+                    HealthStatus::Unhealthy if CRITICAL_COMPONENTS.contains(&component.name) => {
+                        HealthStatus::Unhealthy
+                    }
+                    _ if status == HealthStatus::Unhealthy => status,
+                    _ => HealthStatus::Degraded,
+                }
+            });
+        Self {
+            status,
+            components,
+            timestamp: now(),
+        }
+    }
+}
+
+fn now() -> String {
+    humantime::format_rfc3339_seconds(std::time::SystemTime::now()).to_string()
+}
+
+pub(crate) fn configure(cfg: &mut web::ServiceConfig, timeout: Duration) {
+    cfg.service(
+        web::resource("/health/cluster")
+            .name("get_cluster_health")
+            .guard(actix_web::guard::Get())
+            .route(web::get().to(move || get_cluster_health(timeout))),
+    );
+}
+
+/// Check all the components concurrently, each within `timeout`, so that a component which
+/// doesn't respond doesn't hold up the others.
+async fn get_cluster_health(timeout: Duration) -> HttpResponse {
+    let (core, store, cluster_agent, nodes) = futures::join!(
+        check("core", timeout, core_health()),
+        check("store", timeout, store_health()),
+        check("cluster-agent", timeout, cluster_agent_health()),
+        check("nodes", timeout, nodes_health()),
+    );
+    let rest = ComponentHealth::new("rest", HealthStatus::Healthy, "serving requests");
+    let health = ClusterHealth::new(vec![rest, core, store, cluster_agent, nodes]);
+
+    let status = match health.status {
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::OK,
+    };
+    HttpResponse::build(status).json(health)
+}
+
+async fn check(
+    name: &'static str,
+    timeout: Duration,
+    check: impl Future<Output = (HealthStatus, String)>,
+) -> ComponentHealth {
+    match actix_web::rt::time::timeout(timeout, check).await {
+        Ok((status, details)) => ComponentHealth::new(name, status, details),
+        Err(_) => ComponentHealth::new(
+            name,
+            HealthStatus::Unhealthy,
+            format!("no response within {:?}", timeout),
+        ),
+    }
+}
+
+async fn core_health() -> (HealthStatus, String) {
+    match core_grpc().volume().probe(None).await {
+        Ok(true) => (HealthStatus::Healthy, "ready".to_string()),
+        Ok(false) => (HealthStatus::Unhealthy, "not ready".to_string()),
+        Err(error) => (HealthStatus::Unhealthy, error.to_string()),
+    }
+}
+
+/// The store is checked through the core agent, as only it connects to the store.
+async fn store_health() -> (HealthStatus, String) {
+    let health = match core_grpc()
+        .registry()
+        .get_store_health(&GetStoreHealth {}, None)
+        .await
+    {
+        Ok(health) => health,
+        Err(error) => return (HealthStatus::Unknown, error.to_string()),
+    };
+    let reachable = health.endpoints.iter().filter(|e| e.reachable).count();
+    let status = if reachable == health.endpoints.len() {
+        HealthStatus::Healthy
+    } else if reachable == 0 {
+        HealthStatus::Unhealthy
+    } else {
+        HealthStatus::Degraded
+    };
+    let endpoints = health
+        .endpoints
+        .iter()
+        .map(|e| match &e.error {
+            Some(error) => format!("'{}' is unreachable: {}", e.endpoint, error),
+            None => format!("'{}' is reachable", e.endpoint),
+        })
+        .collect::<Vec<_>>();
+    (status, endpoints.join(", "))
+}
+
+async fn cluster_agent_health() -> (HealthStatus, String) {
+    let client = match cluster_agent() {
+        Ok(client) => client,
+        Err(_) => return (HealthStatus::Unknown, "not configured".to_string()),
+    };
+    match client.get_registrations().await {
+        Ok(registered) => (
+            HealthStatus::Healthy,
+            format!("{} node-agents registered", registered),
+        ),
+        Err(error) => (HealthStatus::Unhealthy, error.to_string()),
+    }
+}
+
+async fn nodes_health() -> (HealthStatus, String) {
+    let nodes = match core_grpc().node().get(Filter::None, None).await {
+        Ok(nodes) => nodes.into_inner(),
+        Err(error) => return (HealthStatus::Unknown, error.to_string()),
+    };
+    let online = nodes
+        .iter()
+        .filter(|node| matches!(node.state(), Some(state) if state.status == NodeStatus::Online))
+        .count();
+    let status = if online == nodes.len() {
+        HealthStatus::Healthy
+    } else if online == 0 {
+        HealthStatus::Unhealthy
+    } else {
+        HealthStatus::Degraded
+    };
+    (status, format!("{}/{} nodes online", online, nodes.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn component(name: &'static str, status: HealthStatus) -> ComponentHealth {
+        ComponentHealth::new(name, status, "")
+    }
+
+    #[test]
+    fn cluster_health_status() {
+        let health = |statuses: &[(&'static str, HealthStatus)]| {
+            let components = statuses
+                .iter()
+                .map(|(name, status)| component(name, *status))
+                .collect();
+            ClusterHealth::new(components).status
+        };
+        use HealthStatus::*;
+        assert_eq!(health(&[("rest", Healthy), ("core", Healthy)]), Healthy);
+        assert_eq!(
+            health(&[("core", Healthy), ("cluster-agent", Unknown)]),
+            Healthy
+        );
+        assert_eq!(health(&[("core", Healthy), ("store", Unhealthy)]), Degraded);
+        assert_eq!(health(&[("nodes", Degraded), ("store", Unknown)]), Degraded);
+        assert_eq!(
+            health(&[("core", Unhealthy), ("store", Unknown)]),
+            Unhealthy
+        );
+        assert_eq!(
+            health(&[("store", Degraded), ("core", Unhealthy)]),
+            Unhealthy
+        );
+        assert_eq!(
+            health(&[("core", Unhealthy), ("nodes", Degraded)]),
+            Unhealthy
+        );
+    }
+}
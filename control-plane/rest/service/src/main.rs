@@ -1,8 +1,11 @@
 mod authentication;
+mod cache;
+mod health;
 mod v0;
+mod v1;
 
-use crate::v0::{CORE_CLIENT, JSON_GRPC_CLIENT};
-use actix_service::ServiceFactory;
+use crate::v0::{CLUSTER_AGENT_CLIENT, CORE_CLIENT, JSON_GRPC_CLIENT};
+use actix_service::{Service, ServiceFactory};
 use actix_web::{
     body::MessageBody,
     dev::{ServiceRequest, ServiceResponse},
@@ -33,6 +36,10 @@ pub(crate) struct CliArgs {
     #[structopt(long, short = "J")]
     json_grpc: Option<Uri>,
 
+    /// The HA Cluster Agent URL or address to connect to the service.
+    #[structopt(long)]
+    cluster_agent: Option<Uri>,
+
     /// Path to the certificate file
     #[structopt(long, short, required_unless = "dummy-certificates")]
     cert_file: Option<String>,
@@ -77,6 +84,16 @@ pub(crate) struct CliArgs {
     /// The value 0 means the number of available physical CPUs is used.
     #[structopt(long, short, default_value = utils::DEFAULT_REST_MAX_WORKER_THREADS)]
     max_workers: usize,
+
+    /// Cache the responses of the resource lists, eg: GET /v0/volumes, for this long.
+    /// Lists are refetched when their resources are modified through this server.
+    /// Disabled by default.
+    #[structopt(long)]
+    cache_ttl: Option<humantime::Duration>,
+
+    /// How long each component is given to respond to the checks of GET /health/cluster.
+    #[structopt(long, default_value = "2s")]
+    health_check_timeout: humantime::Duration,
 }
 impl CliArgs {
     fn args() -> Self {
@@ -103,7 +120,10 @@ fn timeout_opts() -> TimeoutOptions {
 
 use actix_web_opentelemetry::RequestTracing;
 use common_lib::transport_api::{RequestMinTimeout, TimeoutOptions};
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient},
+};
 use http::Uri;
 use opentelemetry::{global, KeyValue};
 
@@ -211,12 +231,22 @@ async fn main() -> anyhow::Result<()> {
         cli_args.jaeger.clone(),
     );
 
+    let cache = cache::ResponseCache::new(cli_args.cache_ttl.map(Into::into));
+    let health_check_timeout = cli_args.health_check_timeout.into();
     let app = move || {
         App::new()
+            .wrap(cache.clone())
             .wrap(RequestTracing::new())
             .wrap(middleware::Logger::default())
+            // the tenant of the caller is sent along with the gRPC requests, to be rate limited
+            .wrap_fn(|req, srv| {
+                let tenant = authentication::tenant(req.request());
+                grpc::context::with_tenant(tenant, srv.call(req))
+            })
             .app_data(authentication::init(get_jwk_path()))
+            .configure(|cfg| health::configure(cfg, health_check_timeout))
             .configure_api(&v0::configure_api)
+            .configure_api(&v1::configure_api)
     };
 
     // Initialise the core client to be used in rest
@@ -233,6 +263,14 @@ async fn main() -> anyhow::Result<()> {
             .expect("Expect to be initialised only once");
     }
 
+    // Initialise the cluster agent client to be used in rest
+    if let Some(cluster_agent) = CliArgs::args().cluster_agent {
+        CLUSTER_AGENT_CLIENT
+            .set(ClusterAgentClient::new(cluster_agent, timeout_opts()).await)
+            .ok()
+            .expect("Expect to be initialised only once");
+    }
+
     let server = HttpServer::new(app).bind_rustls(CliArgs::args().https, get_certificates()?)?;
     let result = if let Some(http) = CliArgs::args().http {
         server.bind(http).map_err(anyhow::Error::from)?
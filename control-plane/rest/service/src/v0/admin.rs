@@ -0,0 +1,182 @@
+use super::*;
+use common_lib::types::v0::{
+    openapi::apis::Uuid,
+    store::reconciler::ReconcilerPass,
+    transport::{
+        ClearPendingOperation, GetClusterDefaults, GetLatencyMatrix, GetOrphans,
+        GetPendingOperation, GetPendingOperations, GetRateLimits, GetReconcilerPasses,
+        GetSchedulingTraces, GetStoreHealth, GetVolumeQuotas, PatchSpec, ReleaseOperationGuard,
+        RetryOperationReplay, SetClusterDefaults, SetReconcilerPass, SetVolumeQuotas, VolumeId,
+    },
+};
+use grpc::operations::registry::traits::RegistryOperations;
+use serde_json::Value;
+use std::str::FromStr;
+
+fn client() -> impl RegistryOperations {
+    core_grpc().registry()
+}
+
+/// Parse the kind of a resource whose spec may be repaired by hand.
+fn spec_kind(kind: &str) -> Result<ResourceKind, ReplyError> {
+    match kind.to_lowercase().as_str() {
+        "volume" => Ok(ResourceKind::Volume),
+        "nexus" => Ok(ResourceKind::Nexus),
+        "pool" => Ok(ResourceKind::Pool),
+        "replica" => Ok(ResourceKind::Replica),
+        _ => Err(ReplyError::invalid_argument(
+            ResourceKind::Spec,
+            "kind",
+            format!("'{}' is not one of volume, nexus, pool or replica", kind),
+        )),
+    }
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Admin for RestApi {
+    async fn get_admin_defaults() -> Result<models::ClusterDefaults, RestError<RestJsonError>> {
+        let defaults = client()
+            .get_cluster_defaults(&GetClusterDefaults {}, None)
+            .await?;
+        Ok(defaults.into())
+    }
+
+    async fn put_admin_defaults(
+        Body(cluster_defaults): Body<models::ClusterDefaults>,
+    ) -> Result<models::ClusterDefaults, RestError<RestJsonError>> {
+        let request = SetClusterDefaults {
+            defaults: cluster_defaults.into(),
+        };
+        let defaults = client().set_cluster_defaults(&request, None).await?;
+        Ok(defaults.into())
+    }
+
+    async fn get_admin_quotas() -> Result<models::VolumeQuotasUsage, RestError<RestJsonError>> {
+        let usage = client()
+            .get_volume_quotas(&GetVolumeQuotas {}, None)
+            .await?;
+        Ok(usage.into())
+    }
+
+    async fn put_admin_quotas(
+        Body(volume_quotas): Body<models::VolumeQuotas>,
+    ) -> Result<models::VolumeQuotasUsage, RestError<RestJsonError>> {
+        let request = SetVolumeQuotas {
+            quotas: volume_quotas.into(),
+        };
+        let usage = client().set_volume_quotas(&request, None).await?;
+        Ok(usage.into())
+    }
+
+    async fn get_admin_reconciler() -> Result<models::ReconcilerPassStates, RestError<RestJsonError>>
+    {
+        let passes = client()
+            .get_reconciler_passes(&GetReconcilerPasses {}, None)
+            .await?;
+        Ok(passes.into())
+    }
+
+    async fn put_admin_reconciler_pass(
+        Path(pass): Path<String>,
+        Body(switch): Body<models::ReconcilerPassSwitch>,
+    ) -> Result<models::ReconcilerPassState, RestError<RestJsonError>> {
+        let pass = ReconcilerPass::from_str(&pass)
+            .map_err(|error| ReplyError::invalid_argument(ResourceKind::Spec, "pass", error))?;
+        let request = SetReconcilerPass {
+            pass,
+            enabled: switch.enabled,
+        };
+        let passes = client().set_reconciler_pass(&request, None).await?;
+        match passes.passes.into_iter().find(|state| state.pass == pass) {
+            Some(state) => Ok(state.into()),
+            None => Err(ReplyError::invalid_response(ResourceKind::Spec).into()),
+        }
+    }
+
+    async fn get_admin_rate_limits() -> Result<models::RateLimits, RestError<RestJsonError>> {
+        let limits = client().get_rate_limits(&GetRateLimits {}, None).await?;
+        Ok(limits.into())
+    }
+
+    async fn get_admin_scheduling_traces(
+        Query(volume_id): Query<Option<Uuid>>,
+    ) -> Result<models::SchedulingTraces, RestError<RestJsonError>> {
+        let volume = volume_id.map(VolumeId::from);
+        let request = GetSchedulingTraces::new(volume.as_ref());
+        let traces = client().get_scheduling_traces(&request, None).await?;
+        Ok(traces.into())
+    }
+
+    async fn get_admin_latency() -> Result<models::LatencyMatrix, RestError<RestJsonError>> {
+        let matrix = client()
+            .get_latency_matrix(&GetLatencyMatrix {}, None)
+            .await?;
+        Ok(matrix.into())
+    }
+
+    async fn get_admin_orphans() -> Result<models::OrphanReport, RestError<RestJsonError>> {
+        let report = client().get_orphans(&GetOrphans {}, None).await?;
+        Ok(report.into())
+    }
+
+    async fn get_admin_store_health() -> Result<models::StoreHealth, RestError<RestJsonError>> {
+        let health = client().get_store_health(&GetStoreHealth {}, None).await?;
+        Ok(health.into())
+    }
+
+    async fn patch_admin_spec(
+        Path((kind, id)): Path<(String, String)>,
+        Query((dry_run, confirm)): Query<(Option<bool>, Option<String>)>,
+        Body(patch): Body<Value>,
+    ) -> Result<models::SpecPatch, RestError<RestJsonError>> {
+        let request = PatchSpec {
+            kind: spec_kind(&kind)?,
+            id,
+            patch,
+            dry_run: dry_run.unwrap_or(false),
+            confirm,
+        };
+        let patch = client().patch_spec(&request, None).await?;
+        Ok(patch.into())
+    }
+
+    async fn get_admin_spec_pending_operation(
+        Path((kind, id)): Path<(String, String)>,
+    ) -> Result<models::PendingOperation, RestError<RestJsonError>> {
+        let request = GetPendingOperation::new(spec_kind(&kind)?, &id);
+        let operation = client().get_pending_operation(&request, None).await?;
+        Ok(operation.into())
+    }
+
+    async fn del_admin_spec_pending_operation(
+        Path((kind, id)): Path<(String, String)>,
+        Query(force): Query<Option<bool>>,
+    ) -> Result<models::PendingOperation, RestError<RestJsonError>> {
+        let request = ClearPendingOperation::new(spec_kind(&kind)?, &id, force.unwrap_or(false));
+        let operation = client().clear_pending_operation(&request, None).await?;
+        Ok(operation.into())
+    }
+
+    async fn post_admin_operation_release(
+        Path((kind, id)): Path<(String, String)>,
+    ) -> Result<models::PendingOperation, RestError<RestJsonError>> {
+        let request = ReleaseOperationGuard::new(spec_kind(&kind)?, &id);
+        let operation = client().release_operation_guard(&request, None).await?;
+        Ok(operation.into())
+    }
+
+    async fn get_admin_operations() -> Result<models::PendingOperations, RestError<RestJsonError>> {
+        let operations = client()
+            .get_pending_operations(&GetPendingOperations {}, None)
+            .await?;
+        Ok(operations.into())
+    }
+
+    async fn post_admin_operation_retry(
+        Path((kind, id)): Path<(String, String)>,
+    ) -> Result<models::PendingOperation, RestError<RestJsonError>> {
+        let request = RetryOperationReplay::new(spec_kind(&kind)?, &id);
+        let operation = client().retry_operation_replay(&request, None).await?;
+        Ok(operation.into())
+    }
+}
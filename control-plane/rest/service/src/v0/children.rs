@@ -2,7 +2,10 @@ use super::*;
 use crate::v0::nexuses::nexus;
 use common_lib::types::v0::{
     openapi::apis::Uuid,
-    transport::{AddNexusChild, Child, ChildUri, Filter, Nexus, RemoveNexusChild},
+    transport::{
+        AddNexusChild, Child, ChildUri, Filter, Nexus, OfflineNexusChild, OnlineNexusChild,
+        RemoveNexusChild,
+    },
 };
 use grpc::operations::nexus::traits::NexusOperations;
 use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
@@ -108,12 +111,51 @@ async fn delete_child_filtered(
         node: nexus.node,
         nexus: nexus.uuid,
         uri: child_uri,
+        force: false,
     };
 
     client().remove_nexus_child(&destroy, None).await?;
     Ok(())
 }
 
+async fn online_child_filtered(
+    child_id: ChildUri,
+    query: &str,
+    filter: Filter,
+) -> Result<models::Child, RestError<RestJsonError>> {
+    let child_uri = build_child_uri(child_id, query);
+    let nexus = nexus(
+        match &filter {
+            Filter::Nexus(id) => Some(id.to_string()),
+            _ => None,
+        },
+        client().get(filter, None).await?.into_inner().get(0),
+    )?;
+
+    let online = OnlineNexusChild::new(&nexus.node, &nexus.uuid, &child_uri);
+    let child = client().online_nexus_child(&online, None).await?;
+    Ok(child.into())
+}
+
+async fn offline_child_filtered(
+    child_id: ChildUri,
+    query: &str,
+    filter: Filter,
+) -> Result<models::Child, RestError<RestJsonError>> {
+    let child_uri = build_child_uri(child_id, query);
+    let nexus = nexus(
+        match &filter {
+            Filter::Nexus(id) => Some(id.to_string()),
+            _ => None,
+        },
+        client().get(filter, None).await?.into_inner().get(0),
+    )?;
+
+    let offline = OfflineNexusChild::new(&nexus.node, &nexus.uuid, &child_uri);
+    let child = client().offline_nexus_child(&offline, None).await?;
+    Ok(child.into())
+}
+
 /// The child uri should be in the "percent-encode" format, but if it's not try to use
 /// the query string to build up the url
 fn build_child_uri(child_id: ChildUri, query: &str) -> ChildUri {
@@ -185,6 +227,20 @@ impl apis::actix_server::Children for RestApi {
         get_children_response(Filter::NodeNexus(node_id.into(), nexus_id.into())).await
     }
 
+    async fn post_nexus_child_offline(
+        query: &str,
+        Path((nexus_id, child_id)): Path<(Uuid, String)>,
+    ) -> Result<models::Child, RestError<RestJsonError>> {
+        offline_child_filtered(child_id.into(), query, Filter::Nexus(nexus_id.into())).await
+    }
+
+    async fn post_nexus_child_online(
+        query: &str,
+        Path((nexus_id, child_id)): Path<(Uuid, String)>,
+    ) -> Result<models::Child, RestError<RestJsonError>> {
+        online_child_filtered(child_id.into(), query, Filter::Nexus(nexus_id.into())).await
+    }
+
     async fn put_nexus_child(
         query: &str,
         Path((nexus_id, child_id)): Path<(Uuid, String)>,
@@ -0,0 +1,17 @@
+//! Provides a REST interface to the HA cluster agent.
+
+use super::*;
+use common_lib::types::v0::{openapi::apis::Uuid, transport::GetPathStats};
+use grpc::operations::ha_node::traits::ClusterAgentOperations;
+
+#[async_trait::async_trait]
+impl apis::actix_server::Ha for RestApi {
+    async fn get_path_stats(
+        Query(volume): Query<Uuid>,
+    ) -> Result<models::VolumePathStats, RestError<RestJsonError>> {
+        let stats = cluster_agent()?
+            .get_path_stats(&GetPathStats::new(volume.into()))
+            .await?;
+        Ok(stats.into())
+    }
+}
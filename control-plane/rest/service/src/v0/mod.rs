@@ -2,8 +2,10 @@
 //! Version 0 of the URI's
 //! Ex: /v0/nodes
 
+pub mod admin;
 pub mod block_devices;
 pub mod children;
+pub mod ha;
 pub mod jsongrpc;
 pub mod nexuses;
 pub mod nodes;
@@ -30,7 +32,12 @@ pub use common_lib::{
     IntoVec,
 };
 use futures::future::Ready;
-use grpc::{client::CoreClient, operations::jsongrpc::client::JsonGrpcClient};
+use grpc::{
+    client::CoreClient,
+    operations::{
+        ha_node::client::ClusterAgentClient, jsongrpc::client::JsonGrpcClient, SortOrder, Sorting,
+    },
+};
 use once_cell::sync::OnceCell;
 use rest_client::versions::v0::*;
 use serde::Deserialize;
@@ -40,6 +47,8 @@ use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 pub static CORE_CLIENT: OnceCell<CoreClient> = OnceCell::new();
 /// Once cell static variable to store the json grpc client and initialise once at startup
 pub static JSON_GRPC_CLIENT: OnceCell<JsonGrpcClient> = OnceCell::new();
+/// Once cell static variable to store the cluster agent client and initialise once at startup
+pub static CLUSTER_AGENT_CLIENT: OnceCell<ClusterAgentClient> = OnceCell::new();
 
 /// Get Core gRPC Client
 pub(crate) fn core_grpc<'a>() -> &'a CoreClient {
@@ -61,6 +70,29 @@ pub(crate) fn json_grpc<'a>() -> Result<&'a JsonGrpcClient, ReplyError> {
     }
 }
 
+/// Get HA Cluster Agent gRPC Client
+pub(crate) fn cluster_agent<'a>() -> Result<&'a ClusterAgentClient, ReplyError> {
+    match CLUSTER_AGENT_CLIENT.get() {
+        None => Err(ReplyError {
+            kind: ReplyErrorKind::Unavailable,
+            resource: ResourceKind::Unknown,
+            source: "HA Cluster Agent is not configured/running.".to_string(),
+            extra: "".to_string(),
+        }),
+        Some(client) => Ok(client),
+    }
+}
+
+/// Get the sorting of the listed resources from the `sort_by` and `order` query parameters.
+/// The results are not sorted unless the `sort_by` field is specified.
+fn sorting(sort_by: Option<String>, order: Option<models::SortOrder>) -> Option<Sorting> {
+    let order = match order {
+        Some(models::SortOrder::Desc) => SortOrder::Descending,
+        Some(models::SortOrder::Asc) | None => SortOrder::Ascending,
+    };
+    sort_by.map(|sort_by| Sorting::new(&sort_by, order))
+}
+
 fn version() -> String {
     "v0".into()
 }
@@ -76,7 +108,10 @@ fn configure(cfg: &mut actix_web::web::ServiceConfig) {
     states::configure(cfg);
 }
 
-fn json_error(err: impl std::fmt::Display, _req: &actix_web::HttpRequest) -> actix_web::Error {
+pub(crate) fn json_error(
+    err: impl std::fmt::Display,
+    _req: &actix_web::HttpRequest,
+) -> actix_web::Error {
     RestError::from(ReplyError {
         kind: ReplyErrorKind::DeserializeReq,
         resource: ResourceKind::Unknown,
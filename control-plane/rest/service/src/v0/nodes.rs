@@ -19,6 +19,14 @@ impl apis::actix_server::Nodes for RestApi {
         Ok(node.into())
     }
 
+    async fn del_node(
+        Path(id): Path<String>,
+        Query(force): Query<Option<bool>>,
+    ) -> Result<(), RestError<RestJsonError>> {
+        client().delete(id.into(), force.unwrap_or(false)).await?;
+        Ok(())
+    }
+
     async fn get_nodes() -> Result<Vec<models::Node>, RestError<RestJsonError>> {
         let nodes = client().get(Filter::None, None).await?;
         Ok(nodes.into_inner().into_vec())
@@ -26,8 +34,10 @@ impl apis::actix_server::Nodes for RestApi {
 
     async fn put_node_cordon(
         Path((id, label)): Path<(String, String)>,
+        Query(expiry): Query<Option<u64>>,
     ) -> Result<models::Node, RestError<RestJsonError>> {
-        let node = client().cordon(id.into(), label).await?;
+        let expiry = expiry.map(std::time::Duration::from_secs);
+        let node = client().cordon(id.into(), label, expiry).await?;
         Ok(node.into())
     }
 
@@ -37,10 +47,26 @@ impl apis::actix_server::Nodes for RestApi {
         let node = client().uncordon(id.into(), label).await?;
         Ok(node.into())
     }
+
+    async fn put_node_maintenance(
+        Path(id): Path<String>,
+        Query(duration): Query<u64>,
+    ) -> Result<models::Node, RestError<RestJsonError>> {
+        let duration = std::time::Duration::from_secs(duration);
+        let node = client().put_maintenance(id.into(), duration).await?;
+        Ok(node.into())
+    }
+
+    async fn delete_node_maintenance(
+        Path(id): Path<String>,
+    ) -> Result<models::Node, RestError<RestJsonError>> {
+        let node = client().delete_maintenance(id.into()).await?;
+        Ok(node.into())
+    }
 }
 
 /// returns node from node option and returns an error on non existence
-fn node(node_id: String, node: Option<&Node>) -> Result<Node, ReplyError> {
+pub fn node(node_id: String, node: Option<&Node>) -> Result<Node, ReplyError> {
     match node {
         Some(node) => Ok(node.clone()),
         None => Err(ReplyError {
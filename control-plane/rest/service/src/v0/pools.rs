@@ -1,6 +1,6 @@
 use super::*;
 use common_lib::types::v0::transport::{DestroyPool, Filter};
-use grpc::operations::pool::traits::PoolOperations;
+use grpc::operations::{node::traits::NodeOperations, pool::traits::PoolOperations};
 use transport_api::{ReplyError, ReplyErrorKind, ResourceKind};
 
 fn client() -> impl PoolOperations {
@@ -62,6 +62,13 @@ impl apis::actix_server::Pools for RestApi {
         Ok(pool.into())
     }
 
+    async fn get_node_discovered_pools(
+        Path(id): Path<String>,
+    ) -> Result<Vec<models::DiscoveredPool>, RestError<RestJsonError>> {
+        let pools = core_grpc().node().discover_pools(id.into()).await?;
+        Ok(pools.into_inner().into_iter().map(From::from).collect())
+    }
+
     async fn get_node_pools(
         Path(id): Path<String>,
     ) -> Result<Vec<models::Pool>, RestError<RestJsonError>> {
@@ -83,8 +90,12 @@ impl apis::actix_server::Pools for RestApi {
         Ok(pool.into())
     }
 
-    async fn get_pools() -> Result<Vec<models::Pool>, RestError<RestJsonError>> {
-        let pools = client().get(Filter::None, None).await?;
+    async fn get_pools(
+        Query((sort_by, order)): Query<(Option<String>, Option<models::SortOrder>)>,
+    ) -> Result<Vec<models::Pool>, RestError<RestJsonError>> {
+        let pools = client()
+            .get_sorted(Filter::None, sorting(sort_by, order), None)
+            .await?;
         Ok(pools.into_inner().into_iter().map(From::from).collect())
     }
 
@@ -232,8 +232,12 @@ impl apis::actix_server::Replicas for RestApi {
         Ok(replica.into())
     }
 
-    async fn get_replicas() -> Result<Vec<models::Replica>, RestError<RestJsonError>> {
-        let replicas = replica_client().get(Filter::None, None).await?;
+    async fn get_replicas(
+        Query((sort_by, order)): Query<(Option<String>, Option<models::SortOrder>)>,
+    ) -> Result<Vec<models::Replica>, RestError<RestJsonError>> {
+        let replicas = replica_client()
+            .get_sorted(Filter::None, sorting(sort_by, order), None)
+            .await?;
         Ok(replicas.into_inner().into_iter().map(From::from).collect())
     }
 
@@ -1,5 +1,5 @@
 use super::*;
-use common_lib::types::v0::transport::GetSpecs;
+use common_lib::types::v0::transport::{GetSpecs, RefreshRegistry};
 use grpc::operations::registry::traits::RegistryOperations;
 
 fn client() -> impl RegistryOperations {
@@ -12,4 +12,9 @@ impl apis::actix_server::Specs for RestApi {
         let specs = client().get_specs(&GetSpecs {}, None).await?;
         Ok(specs.into())
     }
+
+    async fn put_specs_refresh() -> Result<models::RegistryRefresh, RestError<RestJsonError>> {
+        let refresh = client().refresh_registry(&RefreshRegistry {}, None).await?;
+        Ok(refresh.into())
+    }
 }
@@ -1,9 +1,14 @@
 use super::*;
 use common_lib::types::v0::{
-    openapi::{apis::Uuid, models::VolumeShareProtocol},
+    openapi::{
+        apis::Uuid,
+        models::{NvmeReservationType, VolumeShareProtocol},
+    },
     transport::{
-        DestroyVolume, Filter, PublishVolume, SetVolumeReplica, ShareVolume, UnpublishVolume,
-        UnshareVolume, Volume,
+        DestroyVolume, Filter, FreezeVolume, GetVolumeHistory, GetVolumeRevisionDiff,
+        GetVolumesStatus, NvmeReservation, PatchVolume, PauseVolumeRebuild, PublishVolume,
+        ResumeVolumeRebuild, SetVolumeReplica, SetVolumeSourceReplica, ShareVolume, ThawVolume,
+        UnpublishVolume, UnshareVolume, Volume, VolumeId,
     },
 };
 use grpc::operations::{volume::traits::VolumeOperations, MaxEntries, Pagination, StartingToken};
@@ -40,11 +45,12 @@ impl apis::actix_server::Volumes for RestApi {
 
     async fn del_volume_target(
         Path(volume_id): Path<Uuid>,
-        Query(force): Query<Option<bool>>,
+        Query((force, release_reservation)): Query<(Option<bool>, Option<bool>)>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .unpublish(
-                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false)),
+                &UnpublishVolume::new(&volume_id.into(), force.unwrap_or(false))
+                    .with_release_reservation(release_reservation.unwrap_or(false)),
                 None,
             )
             .await?;
@@ -65,8 +71,34 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn get_volume_history(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::VolumeHistory, RestError<RestJsonError>> {
+        let history = client()
+            .get_history(&GetVolumeHistory::new(&volume_id.into()), None)
+            .await?;
+        Ok(history.into())
+    }
+
+    async fn get_volume_revision_diff(
+        Path((volume_id, revision)): Path<(Uuid, u64)>,
+    ) -> Result<models::VolumeRevisionDiff, RestError<RestJsonError>> {
+        let diff = client()
+            .get_revision_diff(
+                &GetVolumeRevisionDiff::new(&volume_id.into(), revision),
+                None,
+            )
+            .await?;
+        Ok(diff.into())
+    }
+
     async fn get_volumes(
-        Query((max_entries, starting_token)): Query<(isize, Option<isize>)>,
+        Query((max_entries, starting_token, sort_by, order)): Query<(
+            isize,
+            Option<isize>,
+            Option<String>,
+            Option<models::SortOrder>,
+        )>,
     ) -> Result<models::Volumes, RestError<RestJsonError>> {
         let starting_token = starting_token.unwrap_or_default();
 
@@ -80,7 +112,9 @@ impl apis::actix_server::Volumes for RestApi {
         } else {
             None
         };
-        let volumes = client().get(Filter::None, pagination, None).await?;
+        let volumes = client()
+            .get_sorted(Filter::None, sorting(sort_by, order), pagination, None)
+            .await?;
         Ok(models::Volumes {
             entries: volumes.entries.into_iter().map(|e| e.into()).collect(),
             next_token: volumes.next_token.map(|t| t as isize),
@@ -96,14 +130,47 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn patch_volume(
+        Path(volume_id): Path<Uuid>,
+        Body(patch_volume_body): Body<models::PatchVolumeBody>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let patch = PatchVolume::new(
+            volume_id.into(),
+            patch_volume_body.rebuild_priority.map(From::from),
+        );
+        let volume = client().patch(&patch, None).await?;
+        Ok(volume.into())
+    }
+
+    async fn post_volumes_validate(
+        Body(create_volume_body): Body<models::CreateVolumeBody>,
+    ) -> Result<models::VolumeViolations, RestError<RestJsonError>> {
+        // validated as the request of a volume which does not exist yet
+        let create = CreateVolumeBody::from(create_volume_body).to_create_volume(VolumeId::new());
+        let violations = client().validate_create(&create, None).await?;
+        Ok(violations.into())
+    }
+
+    async fn put_volumes_status(
+        Body(body): Body<models::GetVolumesStatusBody>,
+    ) -> Result<models::VolumesStatus, RestError<RestJsonError>> {
+        let volumes = body.volume_ids.into_iter().map(Into::into).collect();
+        let status = client()
+            .get_status(&GetVolumesStatus::new(volumes), None)
+            .await?;
+        Ok(status.into())
+    }
+
     async fn put_volume_replica_count(
         Path((volume_id, replica_count)): Path<(Uuid, u8)>,
+        Query(override_redundancy_check): Query<Option<bool>>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
         let volume = client()
             .set_replica(
                 &SetVolumeReplica {
                     uuid: volume_id.into(),
                     replicas: replica_count,
+                    override_redundancy_check: override_redundancy_check.unwrap_or_default(),
                 },
                 None,
             )
@@ -111,6 +178,59 @@ impl apis::actix_server::Volumes for RestApi {
         Ok(volume.into())
     }
 
+    async fn put_volume_source_replica(
+        Path((volume_id, replica_id)): Path<(Uuid, Uuid)>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .set_source_replica(
+                &SetVolumeSourceReplica {
+                    uuid: volume_id.into(),
+                    replica: replica_id.into(),
+                },
+                None,
+            )
+            .await?;
+        Ok(volume.into())
+    }
+
+    async fn post_volume_freeze(
+        Path(volume_id): Path<Uuid>,
+        Query(timeout): Query<u64>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let timeout = std::time::Duration::from_secs(timeout);
+        let volume = client()
+            .freeze(&FreezeVolume::new(volume_id.into(), timeout), None)
+            .await?;
+        Ok(volume.into())
+    }
+
+    async fn post_volume_thaw(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .thaw(&ThawVolume::new(volume_id.into()), None)
+            .await?;
+        Ok(volume.into())
+    }
+
+    async fn post_volume_rebuild_pause(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .pause_rebuild(&PauseVolumeRebuild::new(volume_id.into()), None)
+            .await?;
+        Ok(volume.into())
+    }
+
+    async fn post_volume_rebuild_resume(
+        Path(volume_id): Path<Uuid>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let volume = client()
+            .resume_rebuild(&ResumeVolumeRebuild::new(volume_id.into()), None)
+            .await?;
+        Ok(volume.into())
+    }
+
     async fn put_volume_share(
         Path((volume_id, protocol)): Path<(Uuid, models::VolumeShareProtocol)>,
     ) -> Result<String, RestError<RestJsonError>> {
@@ -128,14 +248,34 @@ impl apis::actix_server::Volumes for RestApi {
 
     async fn put_volume_target(
         Path(volume_id): Path<Uuid>,
-        Query((node, protocol)): Query<(String, VolumeShareProtocol)>,
+        Query((node, protocol, reservation_key, reservation_type)): Query<(
+            String,
+            VolumeShareProtocol,
+            Option<u64>,
+            Option<NvmeReservationType>,
+        )>,
     ) -> Result<models::Volume, RestError<RestJsonError>> {
+        let reservation = match (reservation_key, reservation_type) {
+            (None, None) => None,
+            (Some(key), Some(reservation_type)) => {
+                Some(NvmeReservation::new(key, reservation_type.into()))
+            }
+            _ => {
+                return Err(ReplyError::invalid_argument(
+                    ResourceKind::Volume,
+                    "reservation",
+                    "the reservation key and type must be specified together".to_string(),
+                )
+                .into())
+            }
+        };
         let volume = client()
             .publish(
                 &PublishVolume {
                     uuid: volume_id.into(),
                     target_node: Some(node.into()),
                     share: Some(protocol.into()),
+                    reservation,
                 },
                 None,
             )
@@ -145,7 +285,7 @@ impl apis::actix_server::Volumes for RestApi {
 }
 
 /// returns volume from volume option and returns an error on non existence
-fn volume(volume_id: String, volume: Option<&Volume>) -> Result<Volume, ReplyError> {
+pub fn volume(volume_id: String, volume: Option<&Volume>) -> Result<Volume, ReplyError> {
     match volume {
         Some(volume) => Ok(volume.clone()),
         None => Err(ReplyError {
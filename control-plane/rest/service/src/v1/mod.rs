@@ -0,0 +1,134 @@
+//! Version 1 of the URI's, which carries the breaking changes to the models
+//! Ex: /v1/nodes
+//! The handlers are backed by the same agent operations as version 0, the agent types being
+//! adapted to the version 1 models by each resource's module.
+
+pub mod nodes;
+pub mod pools;
+pub mod volumes;
+
+use crate::v0::{core_grpc, json_error, BearerToken};
+use actix_service::ServiceFactory;
+use actix_web::{
+    body::MessageBody,
+    dev::{ServiceRequest, ServiceResponse},
+    web, HttpResponse,
+};
+use common_lib::{
+    transport_api::{ReplyError, ReplyErrorKind, ResourceKind},
+    types::rest_json_error,
+};
+use rest_client::versions::v1::*;
+pub use rest_client::versions::v1::{
+    apis::actix_server::{Path, Query},
+    models::RestJsonError,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+pub(crate) struct RestApi {}
+
+fn configure(cfg: &mut web::ServiceConfig) {
+    apis::actix_server::configure::<RestApi, BearerToken>(cfg);
+}
+
+async fn get_v1_spec() -> HttpResponse {
+    let spec_str = include_str!("../../../openapi-specs/v1_api_spec.yaml");
+    match serde_yaml::from_str::<serde_json::Value>(spec_str) {
+        Ok(value) => HttpResponse::Ok().json(value),
+        Err(error) => HttpResponse::InternalServerError()
+            .json(serde_json::json!({ "error": error.to_string() })),
+    }
+}
+
+pub(super) fn configure_api<T, B>(api: actix_web::App<T>) -> actix_web::App<T>
+where
+    B: MessageBody,
+    T: ServiceFactory<
+        ServiceRequest,
+        Config = (),
+        Response = ServiceResponse<B>,
+        Error = actix_web::Error,
+        InitError = (),
+    >,
+{
+    api.service(web::resource("/v1/api/spec").route(web::get().to(get_v1_spec)))
+        .service(
+            // any /v1 services must either live within this scope or be
+            // declared beforehand
+            web::scope("/v1")
+                .app_data(web::PathConfig::default().error_handler(|e, r| json_error(e, r)))
+                .app_data(web::JsonConfig::default().error_handler(|e, r| json_error(e, r)))
+                .app_data(web::QueryConfig::default().error_handler(|e, r| json_error(e, r)))
+                .configure(configure),
+        )
+}
+
+/// Convert the `ReplyError` into the version 1 `RestError`, which is served with the same status
+/// code and json body as in version 0.
+pub(crate) fn rest_error(error: ReplyError) -> RestError<RestJsonError> {
+    let (status, error) = rest_json_error(error);
+    let kind = serde_json::to_value(error.kind)
+        .and_then(serde_json::from_value)
+        .unwrap_or(models::rest_json_error::Kind::Internal);
+    RestError::new(
+        status,
+        RestJsonError::new(error.details, error.message, kind),
+    )
+}
+
+/// Convert a version 0 model into the version 1 model which shares its json representation,
+/// dropping any of the fields which version 1 no longer has.
+fn model<V0: Serialize, V1: DeserializeOwned>(
+    model: V0,
+    resource: ResourceKind,
+) -> Result<V1, ReplyError> {
+    serde_json::to_value(model)
+        .and_then(serde_json::from_value)
+        .map_err(|error| ReplyError {
+            kind: ReplyErrorKind::Internal,
+            resource,
+            source: "Failed to convert into the v1 model".to_string(),
+            extra: error.to_string(),
+        })
+}
+
+/// Get the page of the `entries` which starts at the `starting_token` offset and has at most
+/// `max_entries` entries, along with the token of the next page if there is one.
+/// If max entries is 0, pagination is disabled and all the entries are returned.
+fn paginate<T>(
+    entries: Vec<T>,
+    max_entries: isize,
+    starting_token: Option<isize>,
+) -> (Vec<T>, Option<isize>) {
+    if max_entries <= 0 {
+        return (entries, None);
+    }
+    let len = entries.len();
+    let offset = (starting_token.unwrap_or_default().max(0) as usize).min(len);
+    let next = offset + max_entries as usize;
+    let page = entries
+        .into_iter()
+        .skip(offset)
+        .take(max_entries as usize)
+        .collect();
+    (page, (next < len).then(|| next as isize))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::paginate;
+
+    #[test]
+    fn pagination() {
+        let entries = (0 .. 5).collect::<Vec<_>>();
+        assert_eq!(
+            paginate(entries.clone(), 0, Some(3)),
+            (entries.clone(), None)
+        );
+        assert_eq!(paginate(entries.clone(), 2, None), (vec![0, 1], Some(2)));
+        assert_eq!(paginate(entries.clone(), 2, Some(2)), (vec![2, 3], Some(4)));
+        assert_eq!(paginate(entries.clone(), 2, Some(4)), (vec![4], None));
+        assert_eq!(paginate(entries.clone(), 5, None), (entries, None));
+        assert_eq!(paginate(vec![0], 2, Some(7)), (vec![], None));
+    }
+}
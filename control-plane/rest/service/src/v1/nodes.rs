@@ -0,0 +1,61 @@
+use super::*;
+use common_lib::types::v0::{
+    openapi::models as models_v0,
+    transport::{Filter, Node, NodeStatus},
+};
+use grpc::operations::node::traits::NodeOperations;
+
+fn client() -> impl NodeOperations {
+    core_grpc().node()
+}
+
+async fn nodes(filter: Filter) -> Result<Vec<Node>, ReplyError> {
+    Ok(client().get(filter, None).await?.into_inner())
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Nodes for RestApi {
+    async fn get_node(Path(id): Path<String>) -> Result<models::Node, RestError<RestJsonError>> {
+        nodes(Filter::Node(id.clone().into()))
+            .await
+            .and_then(|nodes| crate::v0::nodes::node(id, nodes.get(0)))
+            .and_then(node)
+            .map_err(rest_error)
+    }
+
+    async fn get_nodes(
+        Query((max_entries, starting_token)): Query<(isize, Option<isize>)>,
+    ) -> Result<models::Nodes, RestError<RestJsonError>> {
+        let nodes = nodes(Filter::None).await.map_err(rest_error)?;
+        let (entries, next_token) = paginate(nodes, max_entries, starting_token);
+        Ok(models::Nodes {
+            entries: entries
+                .into_iter()
+                .map(node)
+                .collect::<Result<_, _>>()
+                .map_err(rest_error)?,
+            next_token,
+        })
+    }
+}
+
+/// Adapt the node into the version 1 model, which has the status at the top-level, Unknown when
+/// the node has no state.
+fn node(node: Node) -> Result<models::Node, ReplyError> {
+    let status = match node.state().map(|state| &state.status) {
+        Some(NodeStatus::Online) => models::NodeStatus::Online,
+        Some(NodeStatus::Offline) => models::NodeStatus::Offline,
+        Some(NodeStatus::Unknown) | None => models::NodeStatus::Unknown,
+    };
+    let node = models_v0::Node::from(node);
+    Ok(models::Node::new_all(
+        node.id,
+        status,
+        node.spec
+            .map(|spec| model(spec, ResourceKind::Node))
+            .transpose()?,
+        node.state
+            .map(|state| model(state, ResourceKind::Node))
+            .transpose()?,
+    ))
+}
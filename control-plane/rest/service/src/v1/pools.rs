@@ -0,0 +1,81 @@
+use super::*;
+use common_lib::types::v0::{
+    openapi::models as models_v0,
+    transport::{Filter, Pool, PoolStatus},
+};
+use grpc::operations::pool::traits::PoolOperations;
+
+fn client() -> impl PoolOperations {
+    core_grpc().pool()
+}
+
+async fn pools(filter: Filter) -> Result<Vec<Pool>, ReplyError> {
+    Ok(client().get(filter, None).await?.into_inner())
+}
+
+async fn paginated_pools(
+    filter: Filter,
+    max_entries: isize,
+    starting_token: Option<isize>,
+) -> Result<models::Pools, ReplyError> {
+    let (entries, next_token) = paginate(pools(filter).await?, max_entries, starting_token);
+    Ok(models::Pools {
+        entries: entries.into_iter().map(pool).collect::<Result<_, _>>()?,
+        next_token,
+    })
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Pools for RestApi {
+    async fn get_node_pools(
+        Path(id): Path<String>,
+        Query((max_entries, starting_token)): Query<(isize, Option<isize>)>,
+    ) -> Result<models::Pools, RestError<RestJsonError>> {
+        paginated_pools(Filter::Node(id.into()), max_entries, starting_token)
+            .await
+            .map_err(rest_error)
+    }
+
+    async fn get_pool(
+        Path(pool_id): Path<String>,
+    ) -> Result<models::Pool, RestError<RestJsonError>> {
+        pools(Filter::Pool(pool_id.clone().into()))
+            .await
+            .and_then(|pools| crate::v0::pools::pool(pool_id, pools.get(0)))
+            .and_then(pool)
+            .map_err(rest_error)
+    }
+
+    async fn get_pools(
+        Query((max_entries, starting_token)): Query<(isize, Option<isize>)>,
+    ) -> Result<models::Pools, RestError<RestJsonError>> {
+        paginated_pools(Filter::None, max_entries, starting_token)
+            .await
+            .map_err(rest_error)
+    }
+}
+
+/// Adapt the pool into the version 1 model, which has its node and status at the top-level, the
+/// status being Unknown when the pool has no state, and which always has its conditions.
+fn pool(pool: Pool) -> Result<models::Pool, ReplyError> {
+    let node = pool.node();
+    let status = match pool.state().map(|state| state.status) {
+        Some(PoolStatus::Online) => models::PoolStatus::Online,
+        Some(PoolStatus::Degraded) => models::PoolStatus::Degraded,
+        Some(PoolStatus::Faulted) => models::PoolStatus::Faulted,
+        Some(PoolStatus::Unknown) | None => models::PoolStatus::Unknown,
+    };
+    let pool = models_v0::Pool::from(pool);
+    Ok(models::Pool::new_all(
+        pool.id,
+        node,
+        status,
+        pool.spec
+            .map(|spec| model(spec, ResourceKind::Pool))
+            .transpose()?,
+        pool.state
+            .map(|state| model(state, ResourceKind::Pool))
+            .transpose()?,
+        model(pool.conditions.unwrap_or_default(), ResourceKind::Pool)?,
+    ))
+}
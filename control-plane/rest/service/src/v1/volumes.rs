@@ -0,0 +1,61 @@
+use super::*;
+use common_lib::types::v0::{
+    openapi::models as models_v0,
+    transport::{Filter, Volume, VolumeStatus},
+};
+use grpc::operations::volume::traits::VolumeOperations;
+
+fn client() -> impl VolumeOperations {
+    core_grpc().volume()
+}
+
+async fn volumes(filter: Filter) -> Result<Vec<Volume>, ReplyError> {
+    Ok(client().get(filter, None, None).await?.entries)
+}
+
+#[async_trait::async_trait]
+impl apis::actix_server::Volumes for RestApi {
+    async fn get_volume(
+        Path(volume_id): Path<apis::Uuid>,
+    ) -> Result<models::Volume, RestError<RestJsonError>> {
+        volumes(Filter::Volume(volume_id.into()))
+            .await
+            .and_then(|volumes| crate::v0::volumes::volume(volume_id.to_string(), volumes.get(0)))
+            .and_then(volume)
+            .map_err(rest_error)
+    }
+
+    async fn get_volumes(
+        Query((max_entries, starting_token)): Query<(isize, Option<isize>)>,
+    ) -> Result<models::Volumes, RestError<RestJsonError>> {
+        let volumes = volumes(Filter::None).await.map_err(rest_error)?;
+        let (entries, next_token) = paginate(volumes, max_entries, starting_token);
+        Ok(models::Volumes {
+            entries: entries
+                .into_iter()
+                .map(volume)
+                .collect::<Result<_, _>>()
+                .map_err(rest_error)?,
+            next_token,
+        })
+    }
+}
+
+/// Adapt the volume into the version 1 model, which has its status at the top-level and which
+/// always has its conditions, the divergences of its state from its spec included.
+fn volume(volume: Volume) -> Result<models::Volume, ReplyError> {
+    let status = match volume.status() {
+        Some(VolumeStatus::Online) => models::VolumeStatus::Online,
+        Some(VolumeStatus::Degraded) => models::VolumeStatus::Degraded,
+        Some(VolumeStatus::Faulted) => models::VolumeStatus::Faulted,
+        Some(VolumeStatus::Unknown) | None => models::VolumeStatus::Unknown,
+    };
+    let volume = models_v0::Volume::from(volume);
+    Ok(models::Volume::new_all(
+        volume.spec.uuid,
+        status,
+        model(volume.spec, ResourceKind::Volume)?,
+        Some(model(volume.state, ResourceKind::Volume)?),
+        model(volume.conditions.unwrap_or_default(), ResourceKind::Volume)?,
+    ))
+}
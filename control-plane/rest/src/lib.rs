@@ -21,6 +21,7 @@ use common_lib::types::v0::openapi::client;
 #[derive(Clone)]
 pub struct RestClient {
     openapi_client_v0: client::direct::ApiClient,
+    openapi_client_v1: versions::v1::client::direct::ApiClient,
 }
 
 impl RestClient {
@@ -57,13 +58,29 @@ impl RestClient {
     ) -> anyhow::Result<Self> {
         let cert_file = &std::include_bytes!("../certs/rsa/ca.cert")[..];
 
-        let openapi_client_config =
-            client::Configuration::new(url, timeout, bearer_token, Some(cert_file), trace)
-                .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
+        let openapi_client_config = client::Configuration::new(
+            url.clone(),
+            timeout,
+            bearer_token.clone(),
+            Some(cert_file),
+            trace,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
         let openapi_client = client::direct::ApiClient::new(openapi_client_config);
+        let openapi_client_v1_config = versions::v1::client::Configuration::new(
+            url,
+            timeout,
+            bearer_token,
+            Some(cert_file),
+            trace,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
 
         Ok(Self {
             openapi_client_v0: openapi_client,
+            openapi_client_v1: versions::v1::client::direct::ApiClient::new(
+                openapi_client_v1_config,
+            ),
         })
     }
     /// creates a new client
@@ -74,11 +91,17 @@ impl RestClient {
         trace: bool,
     ) -> anyhow::Result<Self> {
         let openapi_client_config =
-            client::Configuration::new(url, timeout, bearer_token, None, trace)
+            client::Configuration::new(url.clone(), timeout, bearer_token.clone(), None, trace)
                 .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
         let openapi_client = client::direct::ApiClient::new(openapi_client_config);
+        let openapi_client_v1_config =
+            versions::v1::client::Configuration::new(url, timeout, bearer_token, None, trace)
+                .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
         Ok(Self {
             openapi_client_v0: openapi_client,
+            openapi_client_v1: versions::v1::client::direct::ApiClient::new(
+                openapi_client_v1_config,
+            ),
         })
     }
 }
@@ -2,3 +2,5 @@
 
 /// version 0
 pub mod v0;
+/// version 1
+pub mod v1;
@@ -10,9 +10,10 @@ pub use common_lib::{
             AddNexusChild, BlockDevice, Child, ChildUri, CreateNexus, CreatePool, CreateReplica,
             CreateVolume, DestroyNexus, DestroyPool, DestroyReplica, DestroyVolume, Filter,
             GetBlockDevices, JsonGrpcRequest, Nexus, NexusId, Node, NodeId, Pool, PoolDeviceUri,
-            PoolId, Protocol, RemoveNexusChild, Replica, ReplicaId, ReplicaShareProtocol,
-            ShareNexus, ShareReplica, Specs, Topology, UnshareNexus, UnshareReplica, VolumeId,
-            VolumeLabels, VolumePolicy, Watch, WatchCallback, WatchResourceId,
+            PoolId, Protocol, RebuildPriority, RemoveNexusChild, Replica, ReplicaId,
+            ReplicaShareProtocol, ShareNexus, ShareReplica, Specs, Topology, UnshareNexus,
+            UnshareReplica, VolumeDefault, VolumeId, VolumeLabels, VolumePlacementPolicy,
+            VolumePolicy, Watch, WatchCallback, WatchResourceId,
         },
     },
 };
@@ -117,12 +118,16 @@ pub struct CreateNexusBody {
     ///
     /// uris to the targets we connect to
     pub children: Vec<ChildUri>,
+    /// don't probe whether the node can connect to the remote children before the creation
+    #[serde(default)]
+    pub skip_child_probe: bool,
 }
 impl From<CreateNexus> for CreateNexusBody {
     fn from(create: CreateNexus) -> Self {
         Self {
             size: create.size,
             children: create.children.into_vec(),
+            skip_child_probe: create.skip_child_probe,
         }
     }
 }
@@ -131,6 +136,7 @@ impl From<models::CreateNexusBody> for CreateNexusBody {
         Self {
             size: src.size as u64,
             children: src.children.into_iter().map(From::from).collect(),
+            skip_child_probe: src.skip_child_probe.unwrap_or_default(),
         }
     }
 }
@@ -145,6 +151,7 @@ impl CreateNexusBody {
             managed: false,
             owner: None,
             config: None,
+            skip_child_probe: self.skip_child_probe,
         }
     }
 }
@@ -154,52 +161,90 @@ impl CreateNexusBody {
 pub struct CreateVolumeBody {
     /// size of the volume in bytes
     pub size: u64,
-    /// number of storage replicas
-    pub replicas: u64,
+    /// number of storage replicas, the cluster default if unset
+    pub replicas: Option<u64>,
     /// Volume policy used to determine if and how to replace a replica
     pub policy: VolumePolicy,
     /// Volume topology used to determine how to place/distribute the data
     pub topology: Option<Topology>,
     /// Volume labels, used ot store custom volume information
     pub labels: Option<VolumeLabels>,
-    /// Flag indicating whether the volume should be thin provisioned
-    pub thin: bool,
+    /// Flag indicating whether the volume should be thin provisioned, the cluster default if unset
+    pub thin: Option<bool>,
+    /// What to do when not all of the replicas can be placed, the cluster default if unset
+    pub placement: Option<VolumePlacementPolicy>,
+    /// Pools explicitly selected to host the replicas, bypassing the scheduler
+    pub pools: Vec<PoolId>,
+    /// Allow more than one of the selected pools to be on the same node
+    pub allow_same_node: bool,
+    /// Priority of the rebuilds of the volume replicas, Normal if unset
+    pub rebuild_priority: Option<RebuildPriority>,
 }
 impl From<models::CreateVolumeBody> for CreateVolumeBody {
     fn from(src: models::CreateVolumeBody) -> Self {
         Self {
             size: src.size as u64,
-            replicas: src.replicas as u64,
+            replicas: src.replicas.map(|replicas| replicas as u64),
             policy: src.policy.into(),
             topology: src.topology.into_opt(),
             labels: src.labels,
             thin: src.thin,
+            placement: src.placement.map(From::from),
+            pools: src
+                .pools
+                .unwrap_or_default()
+                .into_iter()
+                .map(PoolId::from)
+                .collect(),
+            allow_same_node: src.allow_same_node.unwrap_or_default(),
+            rebuild_priority: src.rebuild_priority.map(From::from),
         }
     }
 }
 impl From<CreateVolume> for CreateVolumeBody {
     fn from(create: CreateVolume) -> Self {
+        let defaults = create.defaults;
+        let set = |default| !defaults.contains(&default);
         CreateVolumeBody {
             size: create.size,
-            replicas: create.replicas,
+            replicas: set(VolumeDefault::Replicas).then(|| create.replicas),
             policy: create.policy,
             topology: create.topology,
             labels: create.labels,
-            thin: create.thin,
+            thin: set(VolumeDefault::Thin).then(|| create.thin),
+            placement: set(VolumeDefault::Placement).then(|| create.placement),
+            pools: create.pools,
+            allow_same_node: create.allow_same_node,
+            rebuild_priority: Some(create.rebuild_priority),
         }
     }
 }
 impl CreateVolumeBody {
     /// convert into message bus type
     pub fn to_create_volume(&self, volume_id: VolumeId) -> CreateVolume {
+        let defaults = [
+            (VolumeDefault::Replicas, self.replicas.is_none()),
+            (VolumeDefault::Thin, self.thin.is_none()),
+            (VolumeDefault::Placement, self.placement.is_none()),
+        ];
         CreateVolume {
             uuid: volume_id,
             size: self.size,
-            replicas: self.replicas,
+            replicas: self.replicas.unwrap_or_default(),
             policy: self.policy.clone(),
             topology: self.topology.clone(),
             labels: self.labels.clone(),
-            thin: self.thin,
+            thin: self.thin.unwrap_or_default(),
+            placement: self.placement.unwrap_or_default(),
+            pools: self.pools.clone(),
+            allow_same_node: self.allow_same_node,
+            defaults: defaults
+                .iter()
+                .filter(|(_, unset)| *unset)
+                .map(|(default, _)| *default)
+                .collect(),
+            requested_size: None,
+            rebuild_priority: self.rebuild_priority.unwrap_or_default(),
         }
     }
 }
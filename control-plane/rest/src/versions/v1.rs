@@ -0,0 +1,10 @@
+use super::super::RestClient;
+
+pub use openapi_v1::{apis, apis::actix_server::RestError, models, tower::client};
+
+impl RestClient {
+    /// Get Autogenerated Openapi client v1
+    pub fn v01(&self) -> client::direct::ApiClient {
+        self.openapi_client_v1.clone()
+    }
+}
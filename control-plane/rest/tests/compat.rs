@@ -0,0 +1,168 @@
+use common_lib::types::v0::{openapi::models as models_v0, transport::VolumeId};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use openapi_v1::models;
+use serde_json::Value;
+
+/// The fields whose values depend on the deployment or on the time, and which are left out of the
+/// golden fixtures.
+const VOLATILE_FIELDS: [&str; 9] = [
+    "grpcEndpoint",
+    "disks",
+    "capacity",
+    "used",
+    "createdAt",
+    "updatedAt",
+    "created_at",
+    "updated_at",
+    "lastTransitionTime",
+];
+
+async fn setup() -> Cluster {
+    ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(2)
+        .build()
+        .await
+        .unwrap()
+}
+
+/// Get the given uri of the api, over http so that the raw json is compared.
+async fn get(uri: &str) -> (reqwest::StatusCode, Value) {
+    let response = reqwest::get(format!("http://localhost:8081{}", uri))
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+/// Remove the volatile fields from the json and sort the listed resources by their id.
+fn normalize(value: &mut Value) {
+    match value {
+        Value::Object(object) => {
+            VOLATILE_FIELDS.iter().for_each(|field| {
+                object.remove(*field);
+            });
+            object.values_mut().for_each(normalize);
+        }
+        Value::Array(array) => {
+            array.iter_mut().for_each(normalize);
+            array.sort_by_key(|entry| entry["id"].as_str().map(ToString::to_string));
+        }
+        _ => {}
+    }
+}
+
+fn fixture(name: &str) -> Value {
+    let path = format!("{}/tests/fixtures/v0/{}", env!("CARGO_MANIFEST_DIR"), name);
+    let fixture = std::fs::read_to_string(&path).unwrap();
+    serde_json::from_str(&fixture).unwrap_or_else(|e| panic!("Invalid fixture {}: {}", path, e))
+}
+
+async fn assert_golden(uri: &str, name: &str) {
+    let (status, mut body) = get(uri).await;
+    assert_eq!(status, reqwest::StatusCode::OK, "{}", body);
+    normalize(&mut body);
+    assert_eq!(
+        body,
+        fixture(name),
+        "{} no longer matches the v0 fixture {}",
+        uri,
+        name
+    );
+}
+
+/// The v0 responses must stay unchanged whilst the breaking model changes are served under /v1.
+#[tokio::test]
+async fn v0_compatibility() {
+    let _cluster = setup().await;
+
+    assert_golden("/v0/nodes", "nodes.json").await;
+    assert_golden("/v0/pools", "pools.json").await;
+    assert_golden("/v0/nodes/io-engine-1/pools", "pools.json").await;
+
+    // the errors are served in the same format by both versions
+    let v0 = get("/v0/pools/missing").await;
+    let v1 = get("/v1/pools/missing").await;
+    assert_eq!(v0.0, reqwest::StatusCode::NOT_FOUND);
+    assert_eq!(v0, v1);
+}
+
+#[tokio::test]
+async fn v1_models() {
+    let cluster = setup().await;
+    let client = cluster.rest_v01();
+
+    let nodes = client.nodes_api().get_nodes(0, None).await.unwrap();
+    assert_eq!(nodes.entries.len(), 1);
+    assert_eq!(nodes.next_token, None);
+    assert_eq!(nodes.entries[0].status, models::NodeStatus::Online);
+
+    let node = client
+        .nodes_api()
+        .get_node(cluster.node(0).as_str())
+        .await
+        .unwrap();
+    assert_eq!(node, nodes.entries[0]);
+
+    // the pools are paginated
+    let page = client.pools_api().get_pools(1, None).await.unwrap();
+    assert_eq!(page.entries.len(), 1);
+    assert_eq!(page.next_token, Some(1));
+    let last = client
+        .pools_api()
+        .get_pools(1, page.next_token)
+        .await
+        .unwrap();
+    assert_eq!(last.entries.len(), 1);
+    assert_eq!(last.next_token, None);
+    assert_ne!(page.entries[0].id, last.entries[0].id);
+
+    let pools = client
+        .pools_api()
+        .get_node_pools(cluster.node(0).as_str(), 0, None)
+        .await
+        .unwrap();
+    assert_eq!(pools.entries.len(), 2);
+    for pool in pools.entries {
+        assert_eq!(pool.node, cluster.node(0).as_str());
+        assert_eq!(pool.status, models::PoolStatus::Online);
+        assert!(pool.conditions.iter().any(|c| c.r#type == "Ready"));
+    }
+
+    let pool = client
+        .pools_api()
+        .get_pool(cluster.pool(0, 0).as_str())
+        .await
+        .unwrap();
+    assert_eq!(pool.id, cluster.pool(0, 0).as_str());
+
+    let volume_id = VolumeId::new();
+    cluster
+        .rest_v00()
+        .volumes_api()
+        .put_volume(
+            &volume_id,
+            models_v0::CreateVolumeBody {
+                replicas: Some(1),
+                ..models_v0::CreateVolumeBody::new(models_v0::VolumePolicy::default(), 5242880u64)
+            },
+        )
+        .await
+        .unwrap();
+    let volumes = client.volumes_api().get_volumes(0, None).await.unwrap();
+    assert_eq!(volumes.entries.len(), 1);
+    assert_eq!(volumes.next_token, None);
+    let volume = client.volumes_api().get_volume(&volume_id).await.unwrap();
+    assert_eq!(volume, volumes.entries[0]);
+    assert_eq!(volume.id.to_string(), volume_id.to_string());
+    assert_eq!(volume.spec.num_replicas, 1);
+    assert_eq!(volume.status, models::VolumeStatus::Online);
+    assert_eq!(volume.state.unwrap().replica_topology.unwrap().len(), 1);
+    // the conditions of the volume include those of its divergences from its spec
+    assert!(volume.conditions.iter().any(|c| c.r#type == "Ready"));
+    assert!(volume.conditions.len() > 1);
+
+    let (status, _) = get(&format!("/v1/volumes/{}", VolumeId::new())).await;
+    assert_eq!(status, reqwest::StatusCode::NOT_FOUND);
+}
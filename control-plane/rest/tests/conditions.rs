@@ -0,0 +1,109 @@
+use common_lib::types::v0::{
+    openapi::models,
+    transport::{PoolId, VolumeId},
+};
+use deployer_cluster::{Cluster, ClusterBuilder};
+use std::time::Duration;
+
+/// Get the condition of the given type, which must be present.
+fn condition(
+    conditions: &Option<Vec<models::ResourceCondition>>,
+    kind: &str,
+) -> models::ResourceCondition {
+    conditions
+        .iter()
+        .flatten()
+        .find(|c| c.r#type == kind)
+        .cloned()
+        .unwrap_or_else(|| panic!("Condition {} not found in {:?}", kind, conditions))
+}
+
+async fn pool_ready(cluster: &Cluster, pool: &PoolId) -> models::ResourceCondition {
+    let pool = cluster
+        .rest_v00()
+        .pools_api()
+        .get_pool(pool.as_str())
+        .await
+        .unwrap();
+    condition(&pool.conditions, "Ready")
+}
+
+#[tokio::test]
+async fn pool_faulted_conditions() {
+    let cluster = ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_cache_period("1s")
+        .with_node_deadline("2s")
+        .build()
+        .await
+        .unwrap();
+    let client = cluster.rest_v00();
+
+    let volume_id = VolumeId::new();
+    let volume = client
+        .volumes_api()
+        .put_volume(
+            &volume_id,
+            models::CreateVolumeBody {
+                replicas: Some(1),
+                ..models::CreateVolumeBody::new(models::VolumePolicy::default(), 5242880u64)
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        condition(&volume.conditions, "Ready").status,
+        models::ConditionStatus::True
+    );
+    let divergences = volume
+        .conditions
+        .iter()
+        .flatten()
+        .filter(|c| c.r#type != "Ready")
+        .collect::<Vec<_>>();
+    assert_eq!(divergences.len(), 6);
+    assert!(divergences
+        .iter()
+        .all(|c| c.status == models::ConditionStatus::False));
+
+    let pool = cluster.pool(1, 0);
+    let online = pool_ready(&cluster, &pool).await;
+    assert_eq!(online.status, models::ConditionStatus::True);
+    assert_eq!(online.reason, "Online");
+
+    // refreshing the pool does not bump the time of the last transition
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert_eq!(pool_ready(&cluster, &pool).await, online);
+
+    // the pool is no longer reported once its node is down
+    cluster
+        .composer()
+        .stop(cluster.node(1).as_str())
+        .await
+        .unwrap();
+    let start = std::time::Instant::now();
+    let faulted = loop {
+        let ready = pool_ready(&cluster, &pool).await;
+        if ready.status != models::ConditionStatus::True {
+            break ready;
+        }
+        if start.elapsed() > Duration::from_secs(15) {
+            panic!("Timeout waiting for the pool to fault: {:?}", ready);
+        }
+        tokio::time::sleep(Duration::from_millis(250)).await;
+    };
+    assert_eq!(faulted.status, models::ConditionStatus::Unknown);
+    assert_eq!(faulted.reason, "StateMissing");
+    assert_eq!(faulted.message, "the pool is not reported by its node");
+    assert!(faulted.last_transition_time > online.last_transition_time);
+
+    tokio::time::sleep(Duration::from_secs(2)).await;
+    assert_eq!(pool_ready(&cluster, &pool).await, faulted);
+
+    // the pool on the other node is unaffected
+    let other = pool_ready(&cluster, &cluster.pool(0, 0)).await;
+    assert_eq!(other.status, models::ConditionStatus::True);
+}
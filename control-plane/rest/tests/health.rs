@@ -0,0 +1,66 @@
+use deployer_cluster::{Cluster, ClusterBuilder};
+use serde_json::Value;
+
+async fn setup() -> Cluster {
+    ClusterBuilder::builder()
+        .with_rest(true)
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .build()
+        .await
+        .unwrap()
+}
+
+/// Get the cluster health, over http as it's not part of the openapi spec.
+async fn cluster_health() -> (reqwest::StatusCode, Value) {
+    let response = reqwest::get("http://localhost:8081/health/cluster")
+        .await
+        .unwrap();
+    (response.status(), response.json().await.unwrap())
+}
+
+fn component<'a>(health: &'a Value, name: &str) -> &'a Value {
+    health["components"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|c| c["name"] == name)
+        .unwrap()
+}
+
+#[tokio::test]
+async fn cluster_health_store_down() {
+    let cluster = setup().await;
+
+    let (status, health) = cluster_health().await;
+    assert_eq!(status, reqwest::StatusCode::OK, "{}", health);
+    assert_eq!(health["status"], "Healthy", "{}", health);
+    assert_eq!(component(&health, "nodes")["details"], "1/1 nodes online");
+    // the cluster agent is not deployed
+    assert_eq!(component(&health, "cluster-agent")["status"], "Unknown");
+
+    cluster.composer().stop("etcd").await.unwrap();
+    let (status, health) = cluster_health().await;
+    assert_eq!(status, reqwest::StatusCode::OK, "{}", health);
+    assert_eq!(health["status"], "Degraded", "{}", health);
+    assert_eq!(component(&health, "store")["status"], "Unhealthy");
+    assert_eq!(component(&health, "core")["status"], "Healthy");
+}
+
+#[tokio::test]
+async fn cluster_health_core_down() {
+    let cluster = setup().await;
+
+    cluster.composer().stop("core").await.unwrap();
+    let (status, health) = cluster_health().await;
+    assert_eq!(
+        status,
+        reqwest::StatusCode::SERVICE_UNAVAILABLE,
+        "{}",
+        health
+    );
+    assert_eq!(health["status"], "Unhealthy", "{}", health);
+    assert_eq!(component(&health, "core")["status"], "Unhealthy");
+    assert_eq!(component(&health, "rest")["status"], "Healthy");
+    assert!(health["timestamp"].is_string());
+}
@@ -85,7 +85,13 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
     let io_engine1 = cluster.node(0);
     let io_engine2 = cluster.node(1);
 
-    let listed_node = client.nodes_api().get_node(io_engine1.as_str()).await;
+    let listed_node = client
+        .nodes_api()
+        .get_node(io_engine1.as_str())
+        .await
+        .unwrap();
+    let registered_at = listed_node.spec.as_ref().and_then(|spec| spec.created_at);
+    assert!(registered_at.is_some());
     let mut node = models::Node {
         id: io_engine1.to_string(),
         spec: Some(models::NodeSpec {
@@ -95,6 +101,10 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
                 cluster.composer().container_ip(cluster.node(0).as_str())
             ),
             cordon_labels: vec![],
+            cordon_expiry: None,
+            maintenance_until: None,
+            created_at: registered_at,
+            updated_at: registered_at,
         }),
         state: Some(models::NodeState {
             id: io_engine1.to_string(),
@@ -105,9 +115,9 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
             status: models::NodeStatus::Online,
         }),
     };
-    assert_eq!(listed_node.unwrap(), node);
+    assert_eq!(listed_node, node);
 
-    let _ = client.pools_api().get_pools().await.unwrap();
+    let _ = client.pools_api().get_pools(None, None).await.unwrap();
     let pool = client
         .pools_api()
         .put_node_pool(
@@ -121,18 +131,34 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
         .unwrap();
 
     info!("Pools: {:#?}", pool);
+    let created_at = pool.spec.as_ref().and_then(|spec| spec.created_at);
+    assert!(created_at.is_some());
     assert_eq!(
         pool,
         models::Pool::new_all(
             "pooloop",
-            models::PoolSpec::new(vec!["malloc:///malloc0?blk_size=512&size_mb=100&uuid=b940f4f2-d45d-4404-8167-3b0366f9e2b0"], "pooloop", &io_engine1, models::SpecStatus::Created),
-            models::PoolState::new(100663296u64, vec!["malloc:///malloc0?blk_size=512&size_mb=100&uuid=b940f4f2-d45d-4404-8167-3b0366f9e2b0"], "pooloop", &io_engine1, models::PoolStatus::Online, 0u64)
+            models::PoolSpec {
+                created_at,
+                updated_at: created_at,
+                ..models::PoolSpec::new(vec!["malloc:///malloc0?blk_size=512&size_mb=100&uuid=b940f4f2-d45d-4404-8167-3b0366f9e2b0"], "pooloop", &io_engine1, models::SpecStatus::Created)
+            },
+            models::PoolState::new(100663296u64, vec!["malloc:///malloc0?blk_size=512&size_mb=100&uuid=b940f4f2-d45d-4404-8167-3b0366f9e2b0"], "pooloop", &io_engine1, models::PoolStatus::Online, 0u64),
+            pool.conditions.clone()
         )
     );
+    let conditions = pool.conditions.clone().unwrap_or_default();
+    assert_eq!(conditions.len(), 1);
+    assert_eq!(conditions[0].r#type, "Ready");
+    assert_eq!(conditions[0].status, models::ConditionStatus::True);
 
     assert_eq!(
         Some(&pool),
-        client.pools_api().get_pools().await.unwrap().first()
+        client
+            .pools_api()
+            .get_pools(None, None)
+            .await
+            .unwrap()
+            .first()
     );
 
     let pool = client
@@ -149,7 +175,11 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     info!("Pools: {:#?}", pool);
 
-    let _ = client.replicas_api().get_replicas().await.unwrap();
+    let _ = client
+        .replicas_api()
+        .get_replicas(None, None)
+        .await
+        .unwrap();
     let replica = client
         .replicas_api()
         .put_node_pool_replica(
@@ -184,7 +214,12 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
     );
     assert_eq!(
         Some(&replica),
-        client.replicas_api().get_replicas().await.unwrap().first()
+        client
+            .replicas_api()
+            .get_replicas(None, None)
+            .await
+            .unwrap()
+            .first()
     );
     client
         .replicas_api()
@@ -192,7 +227,11 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
         .await
         .unwrap();
 
-    let replicas = client.replicas_api().get_replicas().await.unwrap();
+    let replicas = client
+        .replicas_api()
+        .get_replicas(None, None)
+        .await
+        .unwrap();
     assert!(replicas.is_empty());
 
     let nexuses = client.nexuses_api().get_nexuses().await.unwrap();
@@ -221,7 +260,8 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
             children: vec![models::Child {
                 uri: "malloc:///malloc1?blk_size=512&size_mb=100&uuid=b940f4f2-d45d-4404-8167-3b0366f9e2b0".into(),
                 state: models::ChildState::Online,
-                rebuild_progress: None
+                rebuild_progress: None,
+                last_fault: None
             }],
             device_uri: "".to_string(),
             rebuilds: 0,
@@ -268,7 +308,11 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
         .volumes_api()
         .put_volume(
             &volume_uuid,
-            models::CreateVolumeBody::new(models::VolumePolicy::default(), 1, 12582912u64, false),
+            models::CreateVolumeBody {
+                replicas: Some(1),
+                thin: Some(false),
+                ..models::CreateVolumeBody::new(models::VolumePolicy::default(), 12582912u64)
+            },
         )
         .await
         .unwrap();
@@ -285,6 +329,8 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
             &volume.state.uuid,
             io_engine1.as_str(),
             models::VolumeShareProtocol::Nvmf,
+            None,
+            None,
         )
         .await
         .unwrap();
@@ -294,7 +340,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     let volume = client
         .volumes_api()
-        .put_volume_replica_count(&volume_state.uuid, 2)
+        .put_volume_replica_count(&volume_state.uuid, 2, None)
         .await
         .expect("We have 2 nodes with a pool each");
     tracing::info!("Volume: {:#?}", volume);
@@ -304,7 +350,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     let volume = client
         .volumes_api()
-        .put_volume_replica_count(&volume_state.uuid, 1)
+        .put_volume_replica_count(&volume_state.uuid, 1, None)
         .await
         .expect("Should be able to reduce back to 1");
     tracing::info!("Volume: {:#?}", volume);
@@ -314,7 +360,7 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     let volume = client
         .volumes_api()
-        .del_volume_target(&volume_state.uuid, None)
+        .del_volume_target(&volume_state.uuid, None, None)
         .await
         .unwrap();
     tracing::info!("Volume: {:#?}", volume);
@@ -354,7 +400,11 @@ async fn client_test(cluster: &Cluster, auth: &bool) {
 
     client.volumes_api().del_volume(&volume_uuid).await.unwrap();
 
-    let volumes = client.volumes_api().get_volumes(0, None).await.unwrap();
+    let volumes = client
+        .volumes_api()
+        .get_volumes(0, None, None, None)
+        .await
+        .unwrap();
     assert!(volumes.entries.is_empty());
 
     client
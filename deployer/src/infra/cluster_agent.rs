@@ -5,12 +5,13 @@ use super::*;
 
 #[async_trait]
 impl ComponentAction for ClusterAgent {
-    fn configure(&self, _options: &StartOptions, cfg: Builder) -> Result<Builder, Error> {
-        let spec = ContainerSpec::from_binary(
-            "agent-cluster",
-            Binary::from_dbg("agent-cluster").with_args(vec!["-g=0.0.0.0:11500"]),
-        )
-        .with_portmap("11500", "11500");
+    fn configure(&self, options: &StartOptions, cfg: Builder) -> Result<Builder, Error> {
+        let mut binary = Binary::from_dbg("agent-cluster").with_args(vec!["-g=0.0.0.0:11500"]);
+        if let Some(ttl) = &options.node_agent_ttl {
+            binary = binary.with_args(vec!["--node-agent-ttl", &ttl.to_string()]);
+        }
+        let spec =
+            ContainerSpec::from_binary("agent-cluster", binary).with_portmap("11500", "11500");
 
         Ok(cfg.add_container_spec(spec))
     }
@@ -18,8 +18,7 @@ impl ComponentAction for Core {
             }
         }
 
-        let etcd = format!("etcd.{}:2379", options.cluster_label.name());
-        binary = binary.with_args(vec!["--store", &etcd]);
+        binary = binary.with_args(vec!["--store", &Etcd::store_endpoints(options)]);
         if let Some(cache_period) = &options.cache_period {
             binary = binary.with_args(vec!["-c", &cache_period.to_string()]);
         }
@@ -32,6 +31,15 @@ impl ComponentAction for Core {
         if let Some(timeout) = &options.request_timeout {
             binary = binary.with_args(vec!["--request-timeout", &timeout.to_string()]);
         }
+        if let Some(timeout) = &options.fast_request_timeout {
+            binary = binary.with_args(vec!["--fast-request-timeout", &timeout.to_string()]);
+        }
+        if let Some(timeout) = &options.medium_request_timeout {
+            binary = binary.with_args(vec!["--medium-request-timeout", &timeout.to_string()]);
+        }
+        if let Some(timeout) = &options.slow_request_timeout {
+            binary = binary.with_args(vec!["--slow-request-timeout", &timeout.to_string()]);
+        }
         if options.no_min_timeouts {
             binary = binary.with_arg("--no-min-timeouts");
         }
@@ -41,6 +49,9 @@ impl ComponentAction for Core {
         if let Some(ttl) = &options.store_lease_ttl {
             binary = binary.with_args(vec!["--store-lease-ttl", &ttl.to_string()]);
         }
+        if let Some(policy) = &options.store_unavailable_policy {
+            binary = binary.with_args(vec!["--store-unavailable-policy", policy]);
+        }
         if let Some(period) = &options.reconcile_period {
             binary = binary.with_args(vec!["--reconcile-period", &period.to_string()]);
         }
@@ -57,6 +68,84 @@ impl ComponentAction for Core {
         if let Some(max_rebuilds) = &options.max_rebuilds {
             binary = binary.with_args(vec!["--max-rebuilds", &max_rebuilds.to_string()]);
         }
+        if let Some(max) = &options.max_rebuild_destinations {
+            binary = binary.with_args(vec!["--max-rebuild-destinations", &max.to_string()]);
+        }
+        if let Some(aging) = &options.rebuild_aging {
+            binary = binary.with_args(vec!["--rebuild-aging", &aging.to_string()]);
+        }
+        if options.legacy_unshare {
+            binary = binary.with_arg("--legacy-unshare");
+        }
+        if let Some(ttl) = &options.tombstone_ttl {
+            binary = binary.with_args(vec!["--tombstone-ttl", &ttl.to_string()]);
+        }
+        if let Some(topology) = &options.default_topology {
+            binary = binary.with_args(vec!["--default-topology", topology]);
+        }
+        if let Some(policy) = &options.conflict_policy {
+            binary = binary.with_args(vec!["--conflict-policy", policy]);
+        }
+        if let Some(policy) = &options.topology_policy {
+            binary = binary.with_args(vec!["--topology-policy", policy]);
+        }
+        if let Some(revisions) = &options.volume_spec_revisions {
+            binary = binary.with_args(vec!["--volume-spec-revisions", &revisions.to_string()]);
+        }
+        if let Some(size) = &options.max_volume_size {
+            binary = binary.with_args(vec!["--max-volume-size", size]);
+        }
+        if let Some(size) = &options.min_volume_size {
+            binary = binary.with_args(vec!["--min-volume-size", size]);
+        }
+        if let Some(period) = &options.timed_out_child_grace_period {
+            binary = binary.with_args(vec!["--timed-out-child-grace-period", &period.to_string()]);
+        }
+        if let Some(policy) = &options.nexus_children_policy {
+            binary = binary.with_args(vec!["--nexus-children-policy", policy]);
+        }
+        if options.retire_undersized_children {
+            binary = binary.with_arg("--retire-undersized-children");
+        }
+        if options.allow_guard_release {
+            binary = binary.with_arg("--allow-guard-release");
+        }
+        if options.allow_spec_patch {
+            binary = binary.with_arg("--allow-spec-patch");
+        }
+        if let Some(threshold) = &options.node_failure_threshold {
+            binary = binary.with_args(vec!["--node-failure-threshold", &threshold.to_string()]);
+        }
+        if let Some(cooldown) = &options.node_failure_cooldown {
+            binary = binary.with_args(vec!["--node-failure-cooldown", &cooldown.to_string()]);
+        }
+        if let Some(period) = &options.disowned_nexus_grace_period {
+            binary = binary.with_args(vec!["--disowned-nexus-grace-period", &period.to_string()]);
+        }
+        if let Some(period) = &options.replica_creation_grace_period {
+            binary = binary.with_args(vec!["--replica-creation-grace-period", &period.to_string()]);
+        }
+        if let Some(timeout) = &options.node_poll_timeout {
+            binary = binary.with_args(vec!["--node-poll-timeout", &timeout.to_string()]);
+        }
+        if let Some(rate) = &options.grpc_mutation_rate_limit {
+            binary = binary.with_args(vec!["--grpc-mutation-rate-limit", &rate.to_string()]);
+        }
+        if let Some(burst) = &options.grpc_mutation_burst {
+            binary = binary.with_args(vec!["--grpc-mutation-burst", &burst.to_string()]);
+        }
+        if let Some(rate) = &options.grpc_read_rate_limit {
+            binary = binary.with_args(vec!["--grpc-read-rate-limit", &rate.to_string()]);
+        }
+        if let Some(burst) = &options.grpc_read_burst {
+            binary = binary.with_args(vec!["--grpc-read-burst", &burst.to_string()]);
+        }
+        if let Some(networks) = &options.grpc_trusted_networks {
+            binary = binary.with_args(vec!["--grpc-trusted-networks", networks]);
+        }
+        if let Some(factor) = &options.pool_overcommit_factor {
+            binary = binary.with_args(vec!["--pool-overcommit-factor", &factor.to_string()]);
+        }
         Ok(cfg.add_container_bin(name, binary))
     }
     async fn start(&self, _options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
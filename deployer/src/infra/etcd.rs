@@ -4,31 +4,63 @@ use common_lib::store::etcd::Etcd as EtcdStore;
 #[async_trait]
 impl ComponentAction for Etcd {
     fn configure(&self, options: &StartOptions, cfg: Builder) -> Result<Builder, Error> {
-        Ok(if !options.no_etcd {
+        let mut cfg = cfg;
+        if options.no_etcd {
+            return Ok(cfg);
+        }
+        for i in 0 .. options.etcd_members.max(1) {
+            let name = Self::name(i);
+            let mut args = vec![
+                "--data-dir".to_string(),
+                "/tmp/etcd-data".to_string(),
+                "--advertise-client-urls".to_string(),
+                "http://0.0.0.0:2379".to_string(),
+                "--listen-client-urls".to_string(),
+                "http://0.0.0.0:2379".to_string(),
+            ];
+            if options.etcd_members > 1 {
+                let peer_url = |i: u32| format!("http://{}:2380", Self::host(i, options));
+                let cluster = (0 .. options.etcd_members)
+                    .map(|i| format!("{}={}", Self::name(i), peer_url(i)))
+                    .collect::<Vec<_>>()
+                    .join(",");
+                args.extend(vec![
+                    "--name".to_string(),
+                    name.clone(),
+                    "--initial-advertise-peer-urls".to_string(),
+                    peer_url(i),
+                    "--listen-peer-urls".to_string(),
+                    "http://0.0.0.0:2380".to_string(),
+                    "--initial-cluster".to_string(),
+                    cluster,
+                    "--initial-cluster-state".to_string(),
+                    "new".to_string(),
+                ]);
+            }
             let container_spec = ContainerSpec::from_binary(
-                "etcd",
-                Binary::from_path("etcd").with_args(vec![
-                    "--data-dir",
-                    "/tmp/etcd-data",
-                    "--advertise-client-urls",
-                    "http://0.0.0.0:2379",
-                    "--listen-client-urls",
-                    "http://0.0.0.0:2379",
-                ]),
-            )
-            .with_portmap("2379", "2379")
-            .with_portmap("2380", "2380");
+                &name,
+                Binary::from_path("etcd").with_args(args.iter().map(String::as_str).collect()),
+            );
+            // only the first member is reachable from the host
+            let container_spec = if i == 0 {
+                container_spec
+                    .with_portmap("2379", "2379")
+                    .with_portmap("2380", "2380")
+            } else {
+                container_spec
+            };
 
             #[cfg(target_arch = "aarch64")]
             let container_spec = container_spec.with_env("ETCD_UNSUPPORTED_ARCH", "arm64");
-            cfg.add_container_spec(container_spec)
-        } else {
-            cfg
-        })
+            cfg = cfg.add_container_spec(container_spec);
+        }
+        Ok(cfg)
     }
     async fn start(&self, options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
         if !options.no_etcd {
-            cfg.start("etcd").await?;
+            for i in 0 .. options.etcd_members.max(1) {
+                cfg.start(&Self::name(i)).await?;
+            }
         }
         Ok(())
     }
@@ -41,3 +73,24 @@ impl ComponentAction for Etcd {
         Ok(())
     }
 }
+
+impl Etcd {
+    /// Name of the `i`th member of the etcd cluster
+    pub fn name(i: u32) -> String {
+        match i {
+            0 => "etcd".to_string(),
+            _ => format!("etcd-{}", i + 1),
+        }
+    }
+    /// Host name of the `i`th member of the etcd cluster
+    fn host(i: u32, options: &StartOptions) -> String {
+        format!("{}.{}", Self::name(i), options.cluster_label.name())
+    }
+    /// Comma-separated client endpoints of all the members of the etcd cluster
+    pub(crate) fn store_endpoints(options: &StartOptions) -> String {
+        (0 .. options.etcd_members.max(1))
+            .map(|i| format!("{}:2379", Self::host(i, options)))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
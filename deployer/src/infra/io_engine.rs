@@ -7,28 +7,48 @@ use utils::DEFAULT_GRPC_CLIENT_ADDR;
 impl ComponentAction for IoEngine {
     fn configure(&self, options: &StartOptions, cfg: Builder) -> Result<Builder, Error> {
         let mut cfg = cfg;
-        for i in 0 .. options.io_engines {
-            let io_engine_socket =
-                format!("{}:10124", cfg.next_ip_for_name(&Self::name(i, options))?);
-            let name = Self::name(i, options);
+        for host in 0 .. Self::hosts(options) {
+            let first = host * Self::per_host(options);
+            let last = options.io_engines.min(first + Self::per_host(options));
+            let host_name = Self::host_name(first, options);
+            let host_ip = cfg.next_ip_for_name(&host_name)?.to_string();
             let bin = utils::DATA_PLANE_BINARY;
             let binary = options.io_engine_bin.clone().or_else(|| Self::binary(bin));
 
-            let mut spec = if let Some(binary) = binary {
-                ContainerSpec::from_binary(&name, Binary::from_path(&binary))
+            let mut spec = if let Some(binary) = &binary {
+                ContainerSpec::from_binary(&host_name, Binary::from_path(binary))
                     .with_bind_binary_dir(true)
             } else {
-                ContainerSpec::from_image(&name, &options.io_engine_image)
+                ContainerSpec::from_image(&host_name, &options.io_engine_image)
                     .with_pull_policy(options.image_pull_policy.clone())
             }
-            .with_args(vec!["-N", &name])
-            .with_args(vec!["-g", &io_engine_socket])
-            .with_args(vec!["-R", DEFAULT_GRPC_CLIENT_ADDR])
-            .with_args(vec!["--api-versions", &options.io_engine_api_versions])
             .with_bind("/tmp", "/host/tmp");
 
-            if options.io_engine_isolate {
-                spec = spec.with_args(vec!["-l", format!("{}", i).as_str()]);
+            if Self::per_host(options) == 1 {
+                let args = Self::args(first, &host_ip, options);
+                spec = spec.with_args(args.iter().map(String::as_str).collect());
+            } else {
+                // run each instance in the background, with its own nvmf ports
+                let bin = binary.unwrap_or_else(|| "io-engine".to_string());
+                let mut script = String::new();
+                for i in first .. last {
+                    let slot = Self::slot(i, options);
+                    let config = format!("/tmp/{}.yaml", Self::name(i, options));
+                    let nexus_opts = format!(
+                        "nexus_opts:\\n  nvmf_nexus_port: {}\\n  nvmf_replica_port: {}\\n",
+                        4421 + slot,
+                        8420 + slot
+                    );
+                    script.push_str(&format!("printf '{}' > {}\n", nexus_opts, config));
+                    let name = Self::name(i, options);
+                    let args = Self::args(i, &host_ip, options).join(" ");
+                    script.push_str(&format!(
+                        "{} {} -y {} --env-context=--file-prefix={} &\n",
+                        bin, args, config, name
+                    ));
+                }
+                script.push_str("wait\n");
+                spec = spec.with_entrypoints(vec!["sh", "-c", &script]);
             }
 
             if let Some(env) = &options.io_engine_env {
@@ -48,26 +68,25 @@ impl ComponentAction for IoEngine {
                 spec = spec.with_env("DEVELOPER_DELAYED", "1");
             }
 
-            if !options.no_etcd {
-                let etcd = format!("etcd.{}:2379", options.cluster_label.name());
-                spec = spec.with_args(vec!["-p", &etcd]);
-            }
             cfg = cfg.add_container_spec(spec)
         }
         Ok(cfg)
     }
     async fn start(&self, options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
-        let io_engines = (0 .. options.io_engines)
+        let io_engines = (0 .. Self::hosts(options))
             .into_iter()
-            .map(|i| async move { cfg.start(&Self::name(i, options)).await });
+            .map(|host| async move {
+                let host_name = Self::host_name(host * Self::per_host(options), options);
+                cfg.start(&host_name).await
+            });
         futures::future::try_join_all(io_engines).await?;
         Ok(())
     }
     async fn wait_on(&self, options: &StartOptions, cfg: &ComposeTest) -> Result<(), Error> {
         for i in 0 .. options.io_engines {
             let name = Self::name(i, options);
-            let container_ip = cfg.container_ip_as_ref(&name);
-            let socket = SocketAddr::new(IpAddr::from(*container_ip), 10124);
+            let container_ip = cfg.container_ip_as_ref(&Self::host_name(i, options));
+            let socket = SocketAddr::new(IpAddr::from(*container_ip), Self::grpc_port(i, options));
             let mut hdl = RpcHandle::connect(&name, socket).await?;
             hdl.io_engine
                 .list_nexus(rpc::io_engine::Null {})
@@ -79,9 +98,52 @@ impl ComponentAction for IoEngine {
 }
 
 impl IoEngine {
+    /// Name of the io_engine node `i`.
     pub fn name(i: u32, _options: &StartOptions) -> String {
         format!("io-engine-{}", i + 1)
     }
+    /// Name of the container which hosts the io_engine node `i`.
+    /// Unless the io_engines are packed, each node has its own container, named after it.
+    pub fn host_name(i: u32, options: &StartOptions) -> String {
+        match Self::per_host(options) {
+            1 => Self::name(i, options),
+            per_host => format!("io-engine-host-{}", i / per_host + 1),
+        }
+    }
+    /// The grpc port of the io_engine node `i`.
+    pub fn grpc_port(i: u32, options: &StartOptions) -> u16 {
+        10124 + Self::slot(i, options)
+    }
+    fn per_host(options: &StartOptions) -> u32 {
+        options.io_engines_per_host.max(1)
+    }
+    fn hosts(options: &StartOptions) -> u32 {
+        (options.io_engines + Self::per_host(options) - 1) / Self::per_host(options)
+    }
+    /// Index of the io_engine node `i` within its container.
+    fn slot(i: u32, options: &StartOptions) -> u16 {
+        (i % Self::per_host(options)) as u16
+    }
+    fn args(i: u32, host_ip: &str, options: &StartOptions) -> Vec<String> {
+        let mut args = vec![
+            "-N".to_string(),
+            Self::name(i, options),
+            "-g".to_string(),
+            format!("{}:{}", host_ip, Self::grpc_port(i, options)),
+            "-R".to_string(),
+            DEFAULT_GRPC_CLIENT_ADDR.to_string(),
+            "--api-versions".to_string(),
+            options.io_engine_api_versions.clone(),
+        ];
+        if options.io_engine_isolate {
+            args.extend(vec!["-l".to_string(), format!("{}", i)]);
+        }
+        if !options.no_etcd {
+            let etcd = format!("etcd.{}:2379", options.cluster_label.name());
+            args.extend(vec!["-p".to_string(), etcd]);
+        }
+        args
+    }
     fn binary(path: &str) -> Option<String> {
         match std::env::var_os(&path) {
             None => None,
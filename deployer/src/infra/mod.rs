@@ -17,7 +17,10 @@ use super::StartOptions;
 use async_trait::async_trait;
 
 use composer::{Binary, Builder, BuilderConfigure, ComposeTest, ContainerSpec};
-use futures::future::{join_all, try_join_all};
+use futures::{
+    future::{join_all, try_join_all, LocalBoxFuture},
+    Future, FutureExt,
+};
 use paste::paste;
 use std::{cmp::Ordering, convert::TryFrom, str::FromStr};
 use structopt::StructOpt;
@@ -154,7 +157,17 @@ impl Components {
         cfg: &ComposeTest,
         timeout: std::time::Duration,
     ) -> Result<(), Error> {
-        match tokio::time::timeout(timeout, self.start_wait_inner(cfg)).await {
+        self.start_wait_hooked(cfg, timeout, vec![]).await
+    }
+    /// Start all components and wait up to the provided timeout, running each of the `hooks`
+    /// once its component is ready and before any component which boots after it is started
+    pub async fn start_wait_hooked(
+        &self,
+        cfg: &ComposeTest,
+        timeout: std::time::Duration,
+        hooks: Vec<StartHook<'_>>,
+    ) -> Result<(), Error> {
+        match tokio::time::timeout(timeout, self.start_wait_inner(cfg, hooks)).await {
             Ok(result) => result,
             Err(_) => {
                 let error = format!("Time out of {:?} expired", timeout);
@@ -187,7 +200,11 @@ impl Components {
     }
     /// Start all components, in order. Then wait for all components with a wait between each
     /// component to make sure they start orderly
-    async fn start_wait_inner(&self, cfg: &ComposeTest) -> Result<(), Error> {
+    async fn start_wait_inner(
+        &self,
+        cfg: &ComposeTest,
+        mut hooks: Vec<StartHook<'_>>,
+    ) -> Result<(), Error> {
         let mut last_done = None;
 
         for component in &self.0 {
@@ -208,8 +225,15 @@ impl Components {
             try_join_all(wait_components).await?;
             self.wait_on_components(&components, cfg).await?;
             last_done = Some(component.boot_order());
+
+            let (ready, pending): (Vec<_>, Vec<_>) = hooks
+                .into_iter()
+                .partition(|hook| hook.after.boot_order() <= component.boot_order());
+            hooks = pending;
+            StartHook::run_all(ready).await?;
         }
-        Ok(())
+        // hooks for components which are not deployed run once everything else is up
+        StartHook::run_all(hooks).await
     }
     pub fn shutdown_order(&self) -> Vec<String> {
         let ordered = self
@@ -228,6 +252,33 @@ impl Components {
     }
 }
 
+/// Work to be done during the startup of the components, once the `after` component is ready
+/// but before any component which boots after it is started, eg: seeding the persistent store
+/// before the agents start.
+pub struct StartHook<'a> {
+    after: Component,
+    run: LocalBoxFuture<'a, Result<(), Error>>,
+}
+impl<'a> StartHook<'a> {
+    /// Create a new `Self` which runs `run` after the `after` component is ready.
+    pub fn new(
+        after: impl Into<Component>,
+        run: impl Future<Output = Result<(), Error>> + 'a,
+    ) -> Self {
+        Self {
+            after: after.into(),
+            run: run.boxed_local(),
+        }
+    }
+    async fn run_all(hooks: Vec<StartHook<'a>>) -> Result<(), Error> {
+        for hook in hooks {
+            tracing::trace!(component=%hook.after.to_string(), "Running start hook");
+            hook.run.await?;
+        }
+        Ok(())
+    }
+}
+
 #[macro_export]
 macro_rules! impl_component {
     ($($name:ident,$order:literal,)+) => {
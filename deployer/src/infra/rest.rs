@@ -49,6 +49,14 @@ impl ComponentAction for Rest {
                 binary = binary.with_args(vec!["--json-grpc", DEFAULT_JSON_GRPC_CLIENT_ADDR]);
             }
 
+            if options
+                .agents
+                .iter()
+                .any(|agent| matches!(agent, ControlPlaneAgent::ClusterAgent(_)))
+            {
+                binary = binary.with_args(vec!["--cluster-agent", "https://agent-cluster:11500"]);
+            }
+
             if let Some(size) = &options.otel_max_batch_size {
                 binary = binary.with_env("OTEL_BSP_MAX_EXPORT_BATCH_SIZE", size);
             }
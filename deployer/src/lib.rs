@@ -165,6 +165,11 @@ pub struct StartOptions {
     #[structopt(long)]
     pub io_engine_isolate: bool,
 
+    /// Pack `N` io_engine instances in each io_engine container, to test a high node count on
+    /// limited hardware. Each instance is still a distinct node, with its own grpc and nvmf ports.
+    #[structopt(long, default_value = "1", conflicts_with = "local-nodes")]
+    pub io_engines_per_host: u32,
+
     /// Add the following environment variables to the io_engine containers.
     #[structopt(long, env = "IO_ENGINE_ENV", value_delimiter=",", parse(try_from_str = utils::tracing_telemetry::parse_key_value))]
     pub io_engine_env: Option<Vec<KeyValue>>,
@@ -209,6 +214,10 @@ pub struct StartOptions {
     #[structopt(long)]
     pub no_etcd: bool,
 
+    /// Number of members of the etcd cluster, all of which are given to the core agent.
+    #[structopt(long, default_value = "1")]
+    pub etcd_members: u32,
+
     /// The period at which the registry updates its cache of all
     /// resources from all nodes.
     #[structopt(long)]
@@ -222,6 +231,18 @@ pub struct StartOptions {
     #[structopt(long)]
     pub request_timeout: Option<humantime::Duration>,
 
+    /// Override the request timeout of the GRPC requests which list or get resources.
+    #[structopt(long)]
+    fast_request_timeout: Option<humantime::Duration>,
+
+    /// Override the request timeout of the GRPC requests which operate on existing resources.
+    #[structopt(long)]
+    medium_request_timeout: Option<humantime::Duration>,
+
+    /// Override the request timeout of the GRPC requests which create or destroy resources.
+    #[structopt(long)]
+    slow_request_timeout: Option<humantime::Duration>,
+
     /// Override the node's connection timeout.
     #[structopt(long)]
     pub node_conn_timeout: Option<humantime::Duration>,
@@ -239,6 +260,11 @@ pub struct StartOptions {
     #[structopt(long)]
     pub store_lease_ttl: Option<humantime::Duration>,
 
+    /// Set the core agent's policy for operating whilst the persistent store is unavailable:
+    /// wait or read-only.
+    #[structopt(long)]
+    store_unavailable_policy: Option<String>,
+
     /// Override the core agent's reconcile period.
     #[structopt(long)]
     pub reconcile_period: Option<humantime::Duration>,
@@ -274,6 +300,124 @@ pub struct StartOptions {
     #[structopt(long)]
     max_rebuilds: Option<u32>,
 
+    /// Maximum number of rebuilds a node may be the destination of to host a replacement replica.
+    #[structopt(long)]
+    max_rebuild_destinations: Option<u32>,
+
+    /// Override the core agent's aging threshold of the volumes waiting for a rebuild.
+    #[structopt(long)]
+    rebuild_aging: Option<humantime::Duration>,
+
+    /// Unsharing a resource which is not shared fails with NotShared rather than succeeding.
+    #[structopt(long)]
+    legacy_unshare: bool,
+
+    /// Override the core agent's tombstone ttl of the destroyed pools and replicas.
+    #[structopt(long)]
+    tombstone_ttl: Option<humantime::Duration>,
+
+    /// Set the core agent's default topology of volumes created without one, as json.
+    #[structopt(long)]
+    default_topology: Option<String>,
+
+    /// Set the core agent's policy for resolving the pools and replicas which a registering node
+    /// reports differently from their specs: prefer-store, prefer-node or manual.
+    #[structopt(long)]
+    conflict_policy: Option<String>,
+
+    /// Set the core agent's policy for the volumes missing replicas which can't be placed as per
+    /// their topology: strict or relaxed.
+    #[structopt(long)]
+    topology_policy: Option<String>,
+
+    /// Override the core agent's number of revisions retained in the history of each volume spec.
+    #[structopt(long)]
+    volume_spec_revisions: Option<u32>,
+
+    /// The core agent's maximum volume size, eg: 10TiB.
+    #[structopt(long)]
+    max_volume_size: Option<String>,
+
+    /// The core agent's minimum volume size, eg: 1MiB.
+    #[structopt(long)]
+    min_volume_size: Option<String>,
+
+    /// Override the core agent's grace period of the nexus children whose device timed out.
+    #[structopt(long)]
+    timed_out_child_grace_period: Option<humantime::Duration>,
+
+    /// Set the core agent's policy for creating a nexus with children on offline nodes, or which
+    /// fail to attach: all or best-effort.
+    #[structopt(long)]
+    nexus_children_policy: Option<String>,
+
+    /// Retire the nexus children which are backed by a replica smaller than their nexus.
+    #[structopt(long)]
+    retire_undersized_children: bool,
+
+    /// Allow the core agent's operation guards to be released forcibly through the admin API.
+    #[structopt(long)]
+    allow_guard_release: bool,
+
+    /// Allow the core agent's persisted specs to be patched through the admin API.
+    #[structopt(long)]
+    allow_spec_patch: bool,
+
+    /// The number of consecutive failed calls to a node after which the core agent fails fast
+    /// the calls to it.
+    #[structopt(long)]
+    node_failure_threshold: Option<u32>,
+
+    /// Override the core agent's cooldown of the nodes whose calls fail fast.
+    #[structopt(long)]
+    node_failure_cooldown: Option<humantime::Duration>,
+
+    /// Override the core agent's grace period of the managed nexuses which are not owned,
+    /// after which they are destroyed.
+    #[structopt(long)]
+    disowned_nexus_grace_period: Option<humantime::Duration>,
+
+    /// Override the core agent's grace period of the replicas absent from their node's state
+    /// right after their creation.
+    #[structopt(long)]
+    replica_creation_grace_period: Option<humantime::Duration>,
+
+    /// Override the core agent's timeout of the poll of each node.
+    #[structopt(long)]
+    node_poll_timeout: Option<humantime::Duration>,
+
+    /// The core agent's number of mutating gRPC requests which each caller may make per second.
+    #[structopt(long)]
+    grpc_mutation_rate_limit: Option<u32>,
+
+    /// The core agent's number of mutating gRPC requests which each caller may make in a burst.
+    #[structopt(long)]
+    grpc_mutation_burst: Option<u32>,
+
+    /// The core agent's number of gRPC requests which get resources which each caller may make
+    /// per second.
+    #[structopt(long)]
+    grpc_read_rate_limit: Option<u32>,
+
+    /// The core agent's number of gRPC requests which get resources which each caller may make
+    /// in a burst.
+    #[structopt(long)]
+    grpc_read_burst: Option<u32>,
+
+    /// The core agent's networks of the gRPC callers which are trusted to tell their tenant and
+    /// whether they're the control-plane itself.
+    #[structopt(long)]
+    grpc_trusted_networks: Option<String>,
+
+    /// The core agent's factor of the capacity of a pool which the replicas placed on it may
+    /// commit.
+    #[structopt(long)]
+    pool_overcommit_factor: Option<f64>,
+
+    /// Override the cluster-agent's ttl of the node-agent registrations.
+    #[structopt(long)]
+    node_agent_ttl: Option<humantime::Duration>,
+
     /// api versions to be passed to the io-engine
     #[structopt(long, env = "IO_ENGINE_API_VERSIONS", default_value = "V0")]
     io_engine_api_versions: String,
@@ -325,6 +469,12 @@ impl StartOptions {
         self
     }
     #[must_use]
+    pub fn with_agents_env(mut self, key: &str, value: &str) -> Self {
+        let env = KeyValue::new(key.to_string(), value.to_string());
+        self.agents_env.get_or_insert_with(Vec::new).push(env);
+        self
+    }
+    #[must_use]
     pub fn with_cache_period(mut self, period: &str) -> Self {
         self.cache_period = Some(humantime::Duration::from_str(period).unwrap());
         self
@@ -340,11 +490,21 @@ impl StartOptions {
         self
     }
     #[must_use]
+    pub fn with_etcd_members(mut self, members: u32) -> Self {
+        self.etcd_members = members;
+        self
+    }
+    #[must_use]
     pub fn with_store_lease_ttl(mut self, ttl: Duration) -> Self {
         self.store_lease_ttl = Some(ttl.into());
         self
     }
     #[must_use]
+    pub fn with_store_unavailable_policy(mut self, policy: &str) -> Self {
+        self.store_unavailable_policy = Some(policy.to_string());
+        self
+    }
+    #[must_use]
     pub fn with_reconcile_period(mut self, busy: Duration, idle: Duration) -> Self {
         self.reconcile_period = Some(busy.into());
         self.reconcile_idle_period = Some(idle.into());
@@ -358,6 +518,21 @@ impl StartOptions {
         self
     }
     #[must_use]
+    pub fn with_fast_req_timeout(mut self, timeout: Duration) -> Self {
+        self.fast_request_timeout = Some(timeout.into());
+        self
+    }
+    #[must_use]
+    pub fn with_medium_req_timeout(mut self, timeout: Duration) -> Self {
+        self.medium_request_timeout = Some(timeout.into());
+        self
+    }
+    #[must_use]
+    pub fn with_slow_req_timeout(mut self, timeout: Duration) -> Self {
+        self.slow_request_timeout = Some(timeout.into());
+        self
+    }
+    #[must_use]
     pub fn with_rest(mut self, enabled: bool, jwk: Option<String>) -> Self {
         self.no_rest = !enabled;
         self.rest_jwk = jwk;
@@ -404,6 +579,11 @@ impl StartOptions {
         self.io_engine_isolate = isolate;
         self
     }
+    #[must_use]
+    pub fn with_io_engines_per_host(mut self, count: u32) -> Self {
+        self.io_engines_per_host = count;
+        self
+    }
     pub fn with_show_info(mut self, show_info: bool) -> Self {
         self.show_info = show_info;
         self
@@ -437,6 +617,133 @@ impl StartOptions {
         self.max_rebuilds = max;
         self
     }
+    #[must_use]
+    pub fn with_max_rebuild_destinations(mut self, max: Option<u32>) -> Self {
+        self.max_rebuild_destinations = max;
+        self
+    }
+    #[must_use]
+    pub fn with_rebuild_aging(mut self, aging: Duration) -> Self {
+        self.rebuild_aging = Some(aging.into());
+        self
+    }
+    #[must_use]
+    pub fn with_legacy_unshare(mut self, enabled: bool) -> Self {
+        self.legacy_unshare = enabled;
+        self
+    }
+    #[must_use]
+    pub fn with_tombstone_ttl(mut self, ttl: Duration) -> Self {
+        self.tombstone_ttl = Some(ttl.into());
+        self
+    }
+    #[must_use]
+    pub fn with_default_topology(mut self, topology: &str) -> Self {
+        self.default_topology = Some(topology.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_conflict_policy(mut self, policy: &str) -> Self {
+        self.conflict_policy = Some(policy.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_topology_policy(mut self, policy: &str) -> Self {
+        self.topology_policy = Some(policy.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_volume_spec_revisions(mut self, revisions: u32) -> Self {
+        self.volume_spec_revisions = Some(revisions);
+        self
+    }
+    #[must_use]
+    pub fn with_max_volume_size(mut self, size: &str) -> Self {
+        self.max_volume_size = Some(size.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_min_volume_size(mut self, size: &str) -> Self {
+        self.min_volume_size = Some(size.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_timed_out_child_grace_period(mut self, period: Duration) -> Self {
+        self.timed_out_child_grace_period = Some(period.into());
+        self
+    }
+    #[must_use]
+    pub fn with_nexus_children_policy(mut self, policy: &str) -> Self {
+        self.nexus_children_policy = Some(policy.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_retire_undersized_children(mut self, enabled: bool) -> Self {
+        self.retire_undersized_children = enabled;
+        self
+    }
+    #[must_use]
+    pub fn with_allow_guard_release(mut self, allowed: bool) -> Self {
+        self.allow_guard_release = allowed;
+        self
+    }
+    #[must_use]
+    pub fn with_allow_spec_patch(mut self, allowed: bool) -> Self {
+        self.allow_spec_patch = allowed;
+        self
+    }
+    #[must_use]
+    pub fn with_node_failure_threshold(mut self, threshold: u32) -> Self {
+        self.node_failure_threshold = Some(threshold);
+        self
+    }
+    #[must_use]
+    pub fn with_node_failure_cooldown(mut self, cooldown: Duration) -> Self {
+        self.node_failure_cooldown = Some(cooldown.into());
+        self
+    }
+    #[must_use]
+    pub fn with_disowned_nexus_grace_period(mut self, period: Duration) -> Self {
+        self.disowned_nexus_grace_period = Some(period.into());
+        self
+    }
+    #[must_use]
+    pub fn with_replica_creation_grace_period(mut self, period: Duration) -> Self {
+        self.replica_creation_grace_period = Some(period.into());
+        self
+    }
+    #[must_use]
+    pub fn with_node_poll_timeout(mut self, timeout: Duration) -> Self {
+        self.node_poll_timeout = Some(timeout.into());
+        self
+    }
+    #[must_use]
+    pub fn with_grpc_mutation_rate_limit(mut self, rate: u32, burst: Option<u32>) -> Self {
+        self.grpc_mutation_rate_limit = Some(rate);
+        self.grpc_mutation_burst = burst;
+        self
+    }
+    #[must_use]
+    pub fn with_grpc_read_rate_limit(mut self, rate: u32, burst: Option<u32>) -> Self {
+        self.grpc_read_rate_limit = Some(rate);
+        self.grpc_read_burst = burst;
+        self
+    }
+    #[must_use]
+    pub fn with_grpc_trusted_networks(mut self, networks: &str) -> Self {
+        self.grpc_trusted_networks = Some(networks.to_string());
+        self
+    }
+    #[must_use]
+    pub fn with_pool_overcommit_factor(mut self, factor: f64) -> Self {
+        self.pool_overcommit_factor = Some(factor);
+        self
+    }
+    #[must_use]
+    pub fn with_node_agent_ttl(mut self, ttl: Duration) -> Self {
+        self.node_agent_ttl = Some(ttl.into());
+        self
+    }
 
     pub(crate) fn app_nodes(&self) -> u32 {
         if self.csi_node {
@@ -84,7 +84,7 @@ impl ReplicaClientWrapper {
         let replicas = self
             .rest_client
             .replicas_api()
-            .get_replicas()
+            .get_replicas(None, None)
             .await?
             .into_body();
         Ok(replicas)
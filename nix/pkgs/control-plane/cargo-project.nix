@@ -59,6 +59,7 @@ let
     "k8s"
     "kubectl-plugin"
     "openapi"
+    "openapi-v1"
     "rpc"
     "scripts"
     "tests"
@@ -87,6 +88,8 @@ let
         if [ ! -f build.rs ]; then
           patchShebangs ./scripts/rust/generate-openapi-bindings.sh
           ./scripts/rust/generate-openapi-bindings.sh
+          ./scripts/rust/generate-openapi-bindings.sh \
+            --spec-file=control-plane/rest/openapi-specs/v1_api_spec.yaml --target-dir=openapi-v1
         fi
         # remove the tests lib dependency since we don't run tests during this build
         find . -name \*.toml | xargs -I% sed -i '/^io-engine-tests.*=/d' %
@@ -12,9 +12,6 @@ set -e
 SCRIPTDIR=$(dirname "$0")
 ROOTDIR="$SCRIPTDIR"/../../
 TARGET="$ROOTDIR/openapi"
-VERSION_FILE="$TARGET/version.txt"
-RUST_FMT="$ROOTDIR/.rustfmt.toml"
-CARGO_TOML="$TARGET/Cargo.toml"
 SPEC="$ROOTDIR/control-plane/rest/openapi-specs/v0_api_spec.yaml"
 
 # Regenerate the bindings only if the rest src changed
@@ -23,6 +20,8 @@ check_spec="no"
 default_toml="no"
 # skip git diff at the end
 skip_git_diff="no"
+# regenerate the bindings only if the generator changed
+if_rev_changed="no"
 # overwrite files only if the md5 changes
 skip_if_md5_same="no"
 
@@ -46,11 +45,7 @@ while [ "$#" -gt 0 ]; do
           shift
           ;;
       --if-rev-changed)
-          if [[ -f "$VERSION_FILE" ]]; then
-            version=$(cat "$VERSION_FILE")
-            bin_version=$(which openapi-generator-cli)
-            [[ "$version" = "$bin_version" ]] && exit 0
-          fi
+          if_rev_changed="yes"
           skip_git_diff="yes"
           shift
           ;;
@@ -84,6 +79,17 @@ while [ "$#" -gt 0 ]; do
   esac
 done
 
+# Derived from the target and root directories, which may have been overridden
+VERSION_FILE="$TARGET/version.txt"
+RUST_FMT="$ROOTDIR/.rustfmt.toml"
+CARGO_TOML="$TARGET/Cargo.toml"
+
+if [[ $if_rev_changed = "yes" ]] && [[ -f "$VERSION_FILE" ]]; then
+    version=$(cat "$VERSION_FILE")
+    bin_version=$(which openapi-generator-cli)
+    [[ "$version" = "$bin_version" ]] && exit 0
+fi
+
 if [[ $check_spec = "yes" ]]; then
     git diff --cached --exit-code "$SPEC" 1>/dev/null && exit 0
 fi
@@ -14,9 +14,14 @@ cleanup_handler() {
 trap cleanup_handler ERR INT QUIT TERM HUP
 
 set -euxo pipefail
-# test dependencies
-cargo build --bins
+# test dependencies, with the fault injection points which some tests arm
+cargo build --bins --features agents/fault-injection
 for test in deployer-cluster grpc agents rest io-engine-tests kubectl-plugin shutdown csi-driver; do
-    cargo test -p ${test} -- --test-threads=1
+    if [ "$test" = "agents" ]; then
+        # testing the agents rebuilds their binaries, which must keep the fault injection points
+        cargo test -p ${test} --features fault-injection -- --test-threads=1
+    else
+        cargo test -p ${test} -- --test-threads=1
+    fi
 done
 cleanup_handler
@@ -0,0 +1,26 @@
+Feature: Freezing the IO of a Volume
+
+  Background:
+    Given a published volume connected to the host
+
+  Scenario: freezing and thawing the volume IO
+    When the volume IO is frozen
+    Then the volume shall be reported as frozen with a thaw deadline
+    And the host IO to the volume shall block
+    When the volume IO is thawed
+    Then the blocked host IO shall complete
+    And the volume shall not be reported as frozen
+
+  Scenario: the volume IO is not thawed by the client
+    When the volume IO is frozen with a short timeout
+    Then the host IO to the volume shall block
+    And the volume IO shall be thawed by the control plane once the timeout elapses
+    And the blocked host IO shall complete
+
+  Scenario: freezing an already frozen volume
+    When the volume IO is frozen
+    Then freezing the volume IO again should fail with a precondition error
+
+  Scenario: freezing an unpublished volume
+    When the volume is unpublished
+    Then freezing the volume IO should fail with a not published error
@@ -0,0 +1,166 @@
+"""Freezing the IO of a Volume feature tests."""
+import http
+import subprocess
+import time
+
+from pytest_bdd import given, scenario, then, when
+import pytest
+from retrying import retry
+
+from common.deployer import Deployer
+from common.apiclient import ApiClient
+from common.nvme import nvme_connect, nvme_disconnect
+
+from openapi.model.create_pool_body import CreatePoolBody
+from openapi.model.create_volume_body import CreateVolumeBody
+from openapi.model.protocol import Protocol
+from openapi.model.volume_policy import VolumePolicy
+from openapi.exceptions import ApiException
+
+POOL_UUID = "4cc6ee64-7232-497d-a26f-38284a444980"
+VOLUME_UUID = "5cd5378e-3f05-47f1-a830-a0f5873a1449"
+NODE_NAME = "io-engine-1"
+VOLUME_SIZE = 10485761
+FREEZE_TIMEOUT_SECS = 300
+SHORT_FREEZE_TIMEOUT_SECS = 5
+
+
+@scenario("feature.feature", "freezing and thawing the volume IO")
+def test_freezing_and_thawing_the_volume_io():
+    """freezing and thawing the volume IO."""
+
+
+@scenario("feature.feature", "the volume IO is not thawed by the client")
+def test_the_volume_io_is_not_thawed_by_the_client():
+    """the volume IO is not thawed by the client."""
+
+
+@scenario("feature.feature", "freezing an already frozen volume")
+def test_freezing_an_already_frozen_volume():
+    """freezing an already frozen volume."""
+
+
+@scenario("feature.feature", "freezing an unpublished volume")
+def test_freezing_an_unpublished_volume():
+    """freezing an unpublished volume."""
+
+
+@given("a published volume connected to the host")
+def a_published_volume_connected_to_the_host(connected_volume):
+    """a published volume connected to the host."""
+
+
+@when("the volume IO is frozen")
+def the_volume_io_is_frozen():
+    """the volume IO is frozen."""
+    ApiClient.volumes_api().post_volume_freeze(VOLUME_UUID, FREEZE_TIMEOUT_SECS)
+
+
+@when("the volume IO is frozen with a short timeout")
+def the_volume_io_is_frozen_with_a_short_timeout():
+    """the volume IO is frozen with a short timeout."""
+    ApiClient.volumes_api().post_volume_freeze(VOLUME_UUID, SHORT_FREEZE_TIMEOUT_SECS)
+
+
+@when("the volume IO is thawed")
+def the_volume_io_is_thawed():
+    """the volume IO is thawed."""
+    volume = ApiClient.volumes_api().post_volume_thaw(VOLUME_UUID)
+    assert not volume.state.frozen
+
+
+@when("the volume is unpublished")
+def the_volume_is_unpublished():
+    """the volume is unpublished."""
+    ApiClient.volumes_api().del_volume_target(VOLUME_UUID)
+
+
+@then("the volume shall be reported as frozen with a thaw deadline")
+def the_volume_shall_be_reported_as_frozen_with_a_thaw_deadline():
+    """the volume shall be reported as frozen with a thaw deadline."""
+    volume = ApiClient.volumes_api().get_volume(VOLUME_UUID)
+    assert volume.state.frozen
+    assert volume.state.thaw_deadline > time.time()
+    assert volume.state.thaw_deadline <= time.time() + FREEZE_TIMEOUT_SECS
+
+
+@then("the host IO to the volume shall block", target_fixture="host_io")
+def the_host_io_to_the_volume_shall_block(connected_volume):
+    """the host IO to the volume shall block."""
+    command = f"sudo dd if=/dev/zero of={connected_volume} bs=4k count=1 oflag=direct"
+    host_io = subprocess.Popen(command, shell=True)
+    time.sleep(2)
+    assert host_io.poll() is None, "The host IO did not block"
+    return host_io
+
+
+@then("the blocked host IO shall complete")
+def the_blocked_host_io_shall_complete(host_io):
+    """the blocked host IO shall complete."""
+    assert host_io.wait(timeout=10) == 0
+
+
+@then("the volume shall not be reported as frozen")
+def the_volume_shall_not_be_reported_as_frozen():
+    """the volume shall not be reported as frozen."""
+    volume = ApiClient.volumes_api().get_volume(VOLUME_UUID)
+    assert not volume.state.frozen
+    assert not hasattr(volume.state, "thaw_deadline")
+
+
+@then("the volume IO shall be thawed by the control plane once the timeout elapses")
+def the_volume_io_shall_be_thawed_by_the_control_plane_once_the_timeout_elapses():
+    """the volume IO shall be thawed by the control plane once the timeout elapses."""
+    check_volume_thawed()
+
+
+@then("freezing the volume IO again should fail with a precondition error")
+def freezing_the_volume_io_again_should_fail_with_a_precondition_error():
+    """freezing the volume IO again should fail with a precondition error."""
+    try:
+        ApiClient.volumes_api().post_volume_freeze(VOLUME_UUID, FREEZE_TIMEOUT_SECS)
+        assert False, "The volume IO should already be frozen"
+    except ApiException as e:
+        assert e.status == http.HTTPStatus.PRECONDITION_FAILED
+        assert "FailedPrecondition" in e.body
+
+
+@then("freezing the volume IO should fail with a not published error")
+def freezing_the_volume_io_should_fail_with_a_not_published_error():
+    """freezing the volume IO should fail with a not published error."""
+    try:
+        ApiClient.volumes_api().post_volume_freeze(VOLUME_UUID, FREEZE_TIMEOUT_SECS)
+        assert False, "The volume is not published"
+    except ApiException as e:
+        assert e.status == http.HTTPStatus.PRECONDITION_FAILED
+        assert "NotPublished" in e.body
+
+
+@retry(wait_fixed=500, stop_max_delay=(SHORT_FREEZE_TIMEOUT_SECS + 10) * 1000)
+def check_volume_thawed():
+    volume = ApiClient.volumes_api().get_volume(VOLUME_UUID)
+    assert not volume.state.frozen
+
+
+@pytest.fixture(scope="function")
+def init():
+    # Shorten the reconcile periods and cache period to speed up the tests.
+    Deployer.start(1, reconcile_period="500ms", cache_period="1s")
+    ApiClient.pools_api().put_node_pool(
+        NODE_NAME, POOL_UUID, CreatePoolBody(["malloc:///disk?size_mb=50"])
+    )
+    yield
+    Deployer.stop()
+
+
+@pytest.fixture(scope="function")
+def connected_volume(init):
+    ApiClient.volumes_api().put_volume(
+        VOLUME_UUID, CreateVolumeBody(VolumePolicy(False), 1, VOLUME_SIZE, False)
+    )
+    volume = ApiClient.volumes_api().put_volume_target(
+        VOLUME_UUID, NODE_NAME, Protocol("nvmf")
+    )
+    device_uri = volume.state.target["deviceUri"]
+    yield nvme_connect(device_uri)
+    nvme_disconnect(device_uri)
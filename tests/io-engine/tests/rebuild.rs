@@ -1,3 +1,4 @@
+use control_plane_client::CreateVolumeRequest;
 use deployer_cluster::{Cluster, ClusterBuilder};
 use openapi::{
     apis::{volumes_api, Url, Uuid},
@@ -27,27 +28,19 @@ async fn concurrent_rebuilds() {
 
     let cli = cluster.rest_v00();
     let vol_cli = cli.volumes_api();
+    let client = cluster.client();
 
     let mut volumes = vec![];
     for i in 0 .. nr_volumes {
-        let i = i % io_engines;
-        #[allow(clippy::identity_op)]
-        let volume = vol_cli
-            .put_volume(
-                &Uuid::new_v4(),
-                models::CreateVolumeBody::new(
-                    models::VolumePolicy::new(true),
-                    replica_count,
-                    1 * gig,
-                    false,
-                ),
-            )
-            .await
-            .unwrap();
-        let volume = vol_cli
-            .put_volume_target(
+        let node = cluster.node(i % io_engines);
+        let request = CreateVolumeRequest::new(gig)
+            .with_replicas(replica_count)
+            .with_thin(false);
+        let volume = client.create_volume(request).await.unwrap();
+        let volume = client
+            .publish(
                 &volume.spec.uuid,
-                cluster.node(i).as_str(),
+                node.as_str(),
                 models::VolumeShareProtocol::Nvmf,
             )
             .await
@@ -61,7 +54,7 @@ async fn concurrent_rebuilds() {
             1 => {
                 for volume in &volumes {
                     vol_cli
-                        .put_volume_replica_count(&volume.spec.uuid, 2)
+                        .put_volume_replica_count(&volume.spec.uuid, 2, None)
                         .await
                         .unwrap();
                 }
@@ -70,13 +63,13 @@ async fn concurrent_rebuilds() {
             2 => {
                 for volume in &volumes {
                     vol_cli
-                        .put_volume_replica_count(&volume.spec.uuid, 1)
+                        .put_volume_replica_count(&volume.spec.uuid, 1, None)
                         .await
                         .unwrap();
                 }
                 for volume in &volumes {
                     vol_cli
-                        .put_volume_replica_count(&volume.spec.uuid, 2)
+                        .put_volume_replica_count(&volume.spec.uuid, 2, None)
                         .await
                         .unwrap();
                 }
@@ -101,7 +94,11 @@ async fn concurrent_rebuilds() {
         let mut added_slack = false;
         let check_interval = std::time::Duration::from_secs(5);
         loop {
-            let curr_volumes = vol_cli.get_volumes(0, None).await.unwrap().entries;
+            let curr_volumes = vol_cli
+                .get_volumes(0, None, None, None)
+                .await
+                .unwrap()
+                .entries;
             assert_eq!(volumes.len(), curr_volumes.len());
             // volumes should either be online or degraded (while rebuilding)
             let not_expected = curr_volumes
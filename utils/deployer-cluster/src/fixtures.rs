@@ -0,0 +1,75 @@
+//! Key/values with which the persistent store may be seeded before the control plane agents
+//! start, see `ClusterBuilder::with_store_fixtures`.
+
+use common_lib::types::v0::store::definitions::{ObjectKey, StorableObject};
+
+/// Store key/value of the `object`, in its current format.
+pub fn spec<O: StorableObject>(object: &O) -> (String, String) {
+    let value = serde_json::to_string(object).expect("specs are serializable");
+    (object.key().key(), value)
+}
+
+/// Store key/value of the `object` without the given top level `fields`, as it would have been
+/// stored by a release which predates them.
+pub fn spec_without<O: StorableObject>(object: &O, fields: &[&str]) -> (String, String) {
+    let mut value = serde_json::to_value(object).expect("specs are serializable");
+    if let Some(value) = value.as_object_mut() {
+        for field in fields {
+            value.remove(*field);
+        }
+    }
+    (object.key().key(), value.to_string())
+}
+
+/// Store key/value with the given `value` as is, eg: to seed a malformed spec.
+pub fn raw(key: impl Into<String>, value: impl Into<String>) -> (String, String) {
+    (key.into(), value.into())
+}
+
+/// Deliberately old formats of the specs, which the agents must still be able to load.
+/// Each leaves out the optional fields which were added to the specs over time.
+pub mod legacy {
+    use super::spec_without;
+    use common_lib::types::v0::store::{
+        nexus::NexusSpec, pool::PoolSpec, replica::ReplicaSpec, volume::VolumeSpec,
+    };
+
+    /// The oldest format of the `VolumeSpec`.
+    pub fn volume_spec(spec: &VolumeSpec) -> (String, String) {
+        spec_without(
+            spec,
+            &[
+                "thin",
+                "reservation",
+                "created_at",
+                "defaults",
+                "updated_at",
+                "source_replica",
+            ],
+        )
+    }
+    /// The oldest format of the `ReplicaSpec`.
+    pub fn replica_spec(spec: &ReplicaSpec) -> (String, String) {
+        spec_without(
+            spec,
+            &["generation", "created_at", "updated_at", "out_of_sync"],
+        )
+    }
+    /// The oldest format of the `PoolSpec`.
+    pub fn pool_spec(spec: &PoolSpec) -> (String, String) {
+        spec_without(spec, &["created_at", "updated_at"])
+    }
+    /// The oldest format of the `NexusSpec`.
+    pub fn nexus_spec(spec: &NexusSpec) -> (String, String) {
+        spec_without(
+            spec,
+            &[
+                "config",
+                "deferred_children",
+                "created_at",
+                "updated_at",
+                "frozen_until",
+            ],
+        )
+    }
+}
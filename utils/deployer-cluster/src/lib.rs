@@ -1,9 +1,10 @@
+pub mod fixtures;
 pub mod rest_client;
 
 use composer::{Builder, ComposeTest};
 use deployer_lib::{
     default_agents,
-    infra::{Components, Error, IoEngine},
+    infra::{Components, Error, Etcd, IoEngine, StartHook},
     StartOptions,
 };
 use opentelemetry::{global, sdk::propagation::TraceContextPropagator};
@@ -18,7 +19,7 @@ use common_lib::{
             definitions::ObjectKey,
             registry::{ControlPlaneService, StoreLeaseLockKey},
         },
-        transport::CreatePool,
+        transport::{CreatePool, CreateVolume, PublishVolume, VolumeId},
     },
 };
 pub use etcd_client;
@@ -49,6 +50,15 @@ use utils::tracing_telemetry::default_tracing_tags;
 const RUST_LOG_SILENCE_DEFAULTS: &str =
     "h2=info,hyper=info,tower_buffer=info,tower=info,rustls=info,reqwest=info,tokio_util=info,async_io=info,polling=info,tonic=info,want=info,mio=info,bollard=info,composer=info";
 
+/// Url of the REST API of an external cluster, eg: a long-lived deployment provided by the CI.
+/// When set, the clusters are attached to it rather than composed, see `Cluster::attach`.
+pub const EXTERNAL_REST_URL_ENV: &str = "EXTERNAL_CLUSTER_REST_URL";
+/// Url of the gRPC API of the core agent of the external cluster, required along with the url of
+/// its REST API.
+pub const EXTERNAL_GRPC_URL_ENV: &str = "EXTERNAL_CLUSTER_GRPC_URL";
+/// Size of the malloc pools added by `ClusterBuilder::with_pools`.
+pub const DEFAULT_POOL_SIZE: u64 = 100 * 1024 * 1024;
+
 #[tokio::test]
 async fn smoke_test() {
     // make sure the cluster can bootstrap properly
@@ -59,6 +69,45 @@ async fn smoke_test() {
         .expect("Should bootstrap the cluster!");
 }
 
+#[tokio::test]
+async fn volumes_bootstrap_failure() {
+    // the volume is larger than the pool so it cannot be placed, the error should point out
+    // which one failed
+    let error = ClusterBuilder::builder()
+        .with_agents(vec!["core"])
+        .with_io_engines(1)
+        .with_pools(1)
+        .with_volumes(1, 2 * DEFAULT_POOL_SIZE, 1)
+        .build()
+        .await
+        .err()
+        .expect("The volume creation should fail on a pool which is too small");
+    assert!(
+        error.to_string().contains("volume 0"),
+        "Unexpected error: {}",
+        error
+    );
+}
+
+#[tokio::test]
+async fn volumes_bootstrap_too_few_pools() {
+    // refused before any container is started
+    let error = ClusterBuilder::builder()
+        .with_io_engines(2)
+        .with_pools(1)
+        .with_volumes(1, 5 * 1024 * 1024, 2)
+        .with_volumes(1, 5 * 1024 * 1024, 3)
+        .build()
+        .await
+        .err()
+        .expect("The volume has more replicas than the cluster has pools");
+    assert!(
+        error.to_string().contains("Volume 1"),
+        "Unexpected error: {}",
+        error
+    );
+}
+
 /// Default options to create a cluster
 pub fn default_options() -> StartOptions {
     // using from_iter as Default::default would not set the default_value from structopt
@@ -73,19 +122,38 @@ pub fn default_options() -> StartOptions {
 }
 
 /// Cluster with the composer, the rest client and the jaeger pipeline
+/// An external cluster which is attached to has no composer.
 #[allow(unused)]
 pub struct Cluster {
-    composer: ComposeTest,
+    composer: Option<ComposeTest>,
     rest_client: rest_client::RestClient,
     grpc_client: Option<CoreClient>,
     trace_guard: Arc<tracing::subscriber::DefaultGuard>,
     builder: ClusterBuilder,
+    volumes: Vec<VolumeId>,
+    /// nodes of an attached cluster, sorted by id
+    nodes: Vec<transport::NodeId>,
 }
 
 impl Cluster {
     /// compose utility
+    /// Panics if the cluster is attached to an external deployment, see `try_composer`.
     pub fn composer(&self) -> &ComposeTest {
-        &self.composer
+        self.try_composer().unwrap()
+    }
+
+    /// compose utility, or an error if the cluster is attached to an external deployment
+    pub fn try_composer(&self) -> Result<&ComposeTest, Error> {
+        self.composer.as_ref().ok_or_else(|| {
+            "The cluster is attached to an external deployment, which is not managed by the \
+             composer"
+                .into()
+        })
+    }
+
+    /// whether the cluster is attached to an external deployment rather than composed
+    pub fn is_attached(&self) -> bool {
+        self.composer.is_none()
     }
 
     /// grpc client for connection
@@ -155,9 +223,62 @@ impl Cluster {
         ))
     }
 
+    /// Wait for the pools created by the builder to be usable, ie: neither `Unknown` nor `Faulted`,
+    /// returning an error with those which still aren't once the `timeout` expires.
+    pub async fn wait_pools_online(&self, timeout: Duration) -> Result<(), Error> {
+        let expected = self
+            .builder
+            .pools(|index| self.node(index).to_string())
+            .iter()
+            .map(Pool::id)
+            .collect::<Vec<_>>();
+        self.wait_pools_usable(&expected, timeout).await
+    }
+    /// Wait for the `expected` pools to be usable, listing them through the gRPC api of the core
+    /// agent so that the REST api doesn't need to be enabled.
+    async fn wait_pools_usable(
+        &self,
+        expected: &[transport::PoolId],
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        let start = std::time::Instant::now();
+        loop {
+            let pools = self
+                .grpc_client()
+                .pool()
+                .get(transport::Filter::None, None)
+                .await
+                .map_err(|error| format!("Failed to list the pools: {:?}", error))?
+                .into_inner();
+            let pending = expected
+                .iter()
+                .filter(|id| {
+                    !pools.iter().any(|pool| {
+                        pool.id() == *id
+                            && matches!(
+                                pool.state().map(|state| state.status),
+                                Some(transport::PoolStatus::Online)
+                                    | Some(transport::PoolStatus::Degraded)
+                            )
+                    })
+                })
+                .collect::<Vec<_>>();
+            if pending.is_empty() {
+                return Ok(());
+            }
+            if start.elapsed() >= timeout {
+                return Err(
+                    format!("The pools {:?} are not online after {:?}", pending, timeout).into(),
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        }
+    }
+
     /// return grpc handle to the container
     pub async fn grpc_handle(&self, name: &str) -> Result<RpcHandle, String> {
-        match self.composer.containers().iter().find(|&c| c.0 == name) {
+        let composer = self.try_composer().map_err(|error| error.to_string())?;
+        match composer.containers().iter().find(|&c| c.0 == name) {
             Some(container) => Ok(RpcHandle::connect(
                 container.0,
                 format!("{}:10124", container.1 .1)
@@ -170,34 +291,57 @@ impl Cluster {
     }
 
     /// restart the core agent
-    pub async fn restart_core(&self) {
-        self.remove_store_lock(ControlPlaneService::CoreAgent).await;
-        self.composer.restart("core").await.unwrap();
+    pub async fn restart_core(&self) -> Result<(), Error> {
+        let composer = self.try_composer()?;
+        self.remove_store_lock(ControlPlaneService::CoreAgent)
+            .await?;
+        composer.restart("core").await?;
+        Ok(())
+    }
+
+    /// Write the raw `fixtures` into the etcd store, as is, so that they may also be malformed
+    async fn seed_store(fixtures: Vec<(String, String)>) -> Result<(), Error> {
+        let mut store = etcd_client::Client::connect(["0.0.0.0:2379"], None).await?;
+        for (key, value) in fixtures {
+            tracing::debug!(key=%key, "Seeding the store");
+            store.put(key, value, None).await?;
+        }
+        Ok(())
     }
 
     /// remove etcd store lock for `name` instance
-    pub async fn remove_store_lock(&self, name: ControlPlaneService) {
-        let mut store = etcd_client::Client::connect(["0.0.0.0:2379"], None)
-            .await
-            .expect("Failed to connect to etcd.");
+    pub async fn remove_store_lock(&self, name: ControlPlaneService) -> Result<(), Error> {
+        // the store of an external deployment is not exposed locally
+        self.try_composer()?;
+        let mut store = etcd_client::Client::connect(["0.0.0.0:2379"], None).await?;
         store
             .delete(
                 StoreLeaseLockKey::new(&name).key(),
                 Some(DeleteOptions::new().with_prefix()),
             )
-            .await
-            .unwrap();
+            .await?;
+        Ok(())
     }
 
     /// node id for `index`
+    /// The nodes of an attached cluster are indexed in the order of their ids.
     pub fn node(&self, index: u32) -> transport::NodeId {
+        if self.is_attached() {
+            return self.nodes.get(index as usize).cloned().unwrap_or_else(|| {
+                panic!(
+                    "The attached cluster has {} nodes, node {} is out of range",
+                    self.nodes.len(),
+                    index
+                )
+            });
+        }
         IoEngine::name(index, &self.builder.opts).into()
     }
 
     /// node ip for `index`
-    pub fn node_ip(&self, index: u32) -> String {
-        let name = self.node(index);
-        self.composer.container_ip(name.as_str())
+    pub fn node_ip(&self, index: u32) -> Result<String, Error> {
+        let name = IoEngine::host_name(index, &self.builder.opts);
+        Ok(self.try_composer()?.container_ip(&name))
     }
 
     /// pool id for `pool` index on `node` index
@@ -205,6 +349,12 @@ impl Cluster {
         format!("{}-pool-{}", self.node(node), pool + 1).into()
     }
 
+    /// size of the disk of the `pool` index on `node` index, if it's a malloc pool
+    /// The capacity of the pool is somewhat smaller, less the metadata of the pool.
+    pub fn pool_size(&self, node: u32, pool: u32) -> Option<u64> {
+        self.builder.malloc_pool_size(node, pool)
+    }
+
     /// replica id with index for `pool` index and `replica` index
     pub fn replica(node: u32, pool: usize, replica: u32) -> transport::ReplicaId {
         if replica > 254 || pool > 254 || node > 254 {
@@ -222,11 +372,40 @@ impl Cluster {
         .unwrap()
     }
 
+    /// uuids of the volumes created during the cluster bootstrap, in the order they were specified
+    pub fn volumes(&self) -> &[VolumeId] {
+        &self.volumes
+    }
+    /// uuid of the volume `index` created during the cluster bootstrap, in the order they were
+    /// specified
+    pub fn volume(&self, index: u32) -> VolumeId {
+        self.volumes
+            .get(index as usize)
+            .cloned()
+            .unwrap_or_else(|| {
+                panic!(
+                    "The cluster has {} volumes, volume {} is out of range",
+                    self.volumes.len(),
+                    index
+                )
+            })
+    }
+
     /// openapi rest client v0
     pub fn rest_v00(&self) -> common_lib::types::v0::openapi::tower::client::direct::ApiClient {
         self.rest_client.v0()
     }
 
+    /// openapi rest client v1
+    pub fn rest_v01(&self) -> openapi_v1::tower::client::direct::ApiClient {
+        self.rest_client.v1()
+    }
+
+    /// typed high-level rest client, built on the openapi rest client v0
+    pub fn client(&self) -> control_plane_client::Client {
+        self.rest_client.v0().into()
+    }
+
     /// New cluster
     async fn new(
         trace: bool,
@@ -234,6 +413,7 @@ impl Cluster {
         timeout_rest: std::time::Duration,
         grpc_timeout: TimeoutOptions,
         bearer_token: Option<String>,
+        store_fixtures: Vec<(String, String)>,
         components: Components,
         composer: ComposeTest,
     ) -> Result<Cluster, Error> {
@@ -245,8 +425,15 @@ impl Cluster {
         )
         .unwrap();
 
+        let hooks = match store_fixtures.is_empty() {
+            true => vec![],
+            false => vec![StartHook::new(
+                Etcd::default(),
+                Self::seed_store(store_fixtures),
+            )],
+        };
         components
-            .start_wait(&composer, std::time::Duration::from_secs(30))
+            .start_wait_hooked(&composer, std::time::Duration::from_secs(30), hooks)
             .await?;
 
         let unknown_module = "unknown".to_string();
@@ -287,15 +474,72 @@ impl Cluster {
         };
 
         let cluster = Cluster {
-            composer,
+            composer: Some(composer),
             rest_client,
             grpc_client,
             trace_guard,
             builder: ClusterBuilder::builder(),
+            volumes: vec![],
+            nodes: vec![],
         };
 
         Ok(cluster)
     }
+
+    /// Attach to an external cluster, eg: a long-lived deployment provided by the CI, whose REST
+    /// API is served on `rest_url` and the gRPC API of its core agent on `grpc_url`, rather than
+    /// composing a cluster of its own.
+    /// The options of the `builder` which configure the composed components are ignored, and its
+    /// pools, replicas and volumes are only created if it's set to provision external clusters.
+    /// The helpers which depend on the composer return an error.
+    pub async fn attach(
+        rest_url: &str,
+        grpc_url: &str,
+        mut builder: ClusterBuilder,
+    ) -> Result<Cluster, Error> {
+        let trace_guard = builder.trace_guard();
+        let rest_client = rest_client::RestClient::new_timeout(
+            rest_url,
+            builder.trace,
+            builder.bearer_token.clone(),
+            builder.rest_timeout,
+        )
+        .map_err(|error| format!("Invalid REST url '{}': {}", rest_url, error))?;
+        let grpc_uri = Uri::try_from(grpc_url)
+            .map_err(|error| format!("Invalid gRPC url '{}': {}", grpc_url, error))?;
+        let grpc_client = CoreClient::new(grpc_uri, builder.grpc_timeout.clone()).await;
+
+        let mut nodes = rest_client
+            .v0()
+            .nodes_api()
+            .get_nodes()
+            .await
+            .map_err(|error| {
+                format!(
+                    "Failed to list the nodes of the cluster at '{}': {:?}",
+                    rest_url, error
+                )
+            })?
+            .into_iter()
+            .map(|node| transport::NodeId::from(node.id))
+            .collect::<Vec<_>>();
+        nodes.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+        let mut cluster = Cluster {
+            composer: None,
+            rest_client,
+            grpc_client: Some(grpc_client),
+            trace_guard,
+            builder: ClusterBuilder::builder(),
+            volumes: vec![],
+            nodes,
+        };
+        if builder.external_provisioning {
+            builder.provision(&mut cluster).await?;
+        }
+        cluster.builder = builder;
+        Ok(cluster)
+    }
 }
 
 fn option_str<F: ToString>(input: Option<F>) -> String {
@@ -361,34 +605,52 @@ pub struct TmpDiskFile {
 struct TmpDiskFileInner {
     path: String,
     uri: String,
+    blk_size: u32,
 }
 
 impl TmpDiskFile {
-    /// Creates a new file on `path` with `size`.
+    /// Creates a new file on `path` with `size`, exposed with a block size of 512 bytes.
     /// The file is deleted on drop.
     pub fn new(name: &str, size: u64) -> Self {
+        Self::new_with_blk_size(name, size, 512)
+    }
+    /// Creates a new file on `path` with `size`, exposed with a block size of `blk_size` bytes,
+    /// which must be either 512 or 4096.
+    /// The file is deleted on drop.
+    pub fn new_with_blk_size(name: &str, size: u64, blk_size: u32) -> Self {
+        assert!(
+            matches!(blk_size, 512 | 4096),
+            "Unsupported tmp disk block size: {}",
+            blk_size
+        );
         Self {
-            inner: std::sync::Arc::new(TmpDiskFileInner::new(name, size)),
+            inner: std::sync::Arc::new(TmpDiskFileInner::new(name, size, blk_size)),
         }
     }
     /// Disk URI to be used by the dataplane
     pub fn uri(&self) -> &str {
         self.inner.uri()
     }
+    /// Block size of the disk, in bytes
+    pub fn blk_size(&self) -> u32 {
+        self.inner.blk_size
+    }
 }
 impl TmpDiskFileInner {
-    fn new(name: &str, size: u64) -> Self {
+    fn new(name: &str, size: u64, blk_size: u32) -> Self {
         let path = format!("/tmp/io-engine-disk-{}", name);
         let file = std::fs::File::create(&path).expect("to create the tmp file");
         file.set_len(size).expect("to truncate the tmp file");
         Self {
             // the io-engine is setup with a bind mount from /tmp to /host/tmp
             uri: format!(
-                "aio:///host{}?blk_size=512&uuid={}",
+                "aio:///host{}?blk_size={}&uuid={}",
                 path,
+                blk_size,
                 transport::PoolId::new()
             ),
             path,
+            blk_size,
         }
     }
     fn uri(&self) -> &str {
@@ -402,16 +664,69 @@ impl Drop for TmpDiskFileInner {
     }
 }
 
+/// Volume to be created, and optionally published, when the cluster is built
+#[derive(Clone)]
+pub struct ClusterVolume {
+    create: CreateVolume,
+    publish: Option<PublishVolume>,
+}
+
+impl ClusterVolume {
+    fn new(size: u64, replicas: u64) -> Self {
+        Self {
+            create: CreateVolume {
+                uuid: VolumeId::new(),
+                size,
+                replicas,
+                ..Default::default()
+            },
+            publish: None,
+        }
+    }
+    /// Update the volume create request
+    #[must_use]
+    pub fn with_create<F>(mut self, set: F) -> Self
+    where
+        F: FnOnce(CreateVolume) -> CreateVolume,
+    {
+        self.create = set(self.create);
+        self
+    }
+    /// Publish the volume on the `target_node` (or any node if None) once it's created
+    #[must_use]
+    pub fn with_publish(
+        mut self,
+        target_node: Option<transport::NodeId>,
+        share: Option<transport::VolumeShareProtocol>,
+    ) -> Self {
+        self.publish = Some(PublishVolume::new(
+            self.create.uuid.clone(),
+            target_node,
+            share,
+        ));
+        self
+    }
+}
+
 /// Builder for the Cluster
 pub struct ClusterBuilder {
     opts: StartOptions,
     pools: HashMap<u32, Vec<PoolDisk>>,
+    /// sizes of the malloc pools, by node and pool index, overriding their default size
+    pool_sizes: HashMap<(u32, u32), u64>,
+    /// sizes of all the malloc pools of a node, by node index, overriding their default size
+    node_pool_sizes: HashMap<u32, u64>,
     replicas: Replica,
+    /// replicas with a given uuid, by node and pool index, created alongside the others
+    replicas_with_uuid: Vec<(u32, u32, transport::ReplicaId, Replica)>,
+    volumes: Vec<ClusterVolume>,
     trace: bool,
     env_filter: Option<EnvFilter>,
     bearer_token: Option<String>,
     rest_timeout: std::time::Duration,
     grpc_timeout: TimeoutOptions,
+    store_fixtures: Vec<(String, String)>,
+    external_provisioning: bool,
 }
 
 #[derive(Default)]
@@ -436,12 +751,18 @@ impl ClusterBuilder {
         ClusterBuilder {
             opts: default_options(),
             pools: Default::default(),
+            pool_sizes: Default::default(),
+            node_pool_sizes: Default::default(),
             replicas: Default::default(),
+            replicas_with_uuid: vec![],
+            volumes: vec![],
             trace: true,
             env_filter: None,
             bearer_token: None,
             rest_timeout: std::time::Duration::from_secs(5),
             grpc_timeout: grpc_timeout_opts(),
+            store_fixtures: vec![],
+            external_provisioning: false,
         }
         .with_default_tracing()
     }
@@ -505,19 +826,37 @@ impl ClusterBuilder {
     }
     /// Add `count` malloc pools (100MiB size) to each node
     #[must_use]
-    pub fn with_pools(mut self, count: u32) -> Self {
+    pub fn with_pools(self, count: u32) -> Self {
+        self.with_pools_sized(count, DEFAULT_POOL_SIZE)
+    }
+    /// Add `count` malloc pools of `size` bytes to each node, which must be a multiple of 512
+    #[must_use]
+    pub fn with_pools_sized(mut self, count: u32, size: u64) -> Self {
         for _ in 0 .. count {
             for node in 0 .. self.opts.io_engines {
                 if let Some(pools) = self.pools.get_mut(&node) {
-                    pools.push(PoolDisk::Malloc(100 * 1024 * 1024));
+                    pools.push(PoolDisk::Malloc(size));
                 } else {
-                    self.pools
-                        .insert(node, vec![PoolDisk::Malloc(100 * 1024 * 1024)]);
+                    self.pools.insert(node, vec![PoolDisk::Malloc(size)]);
                 }
             }
         }
         self
     }
+    /// Override the size of all the malloc pools of the node `node` index to `size` bytes, which
+    /// must be a multiple of 512, unless a pool has its own size set by `with_pool_size`
+    #[must_use]
+    pub fn with_node_pool_size(mut self, node: u32, size: u64) -> Self {
+        self.node_pool_sizes.insert(node, size);
+        self
+    }
+    /// Override the size of the malloc pool `pool` index of the node `node` index, which is added
+    /// by `with_pools` or `with_pools_sized`, to `size` bytes, which must be a multiple of 512
+    #[must_use]
+    pub fn with_pool_size(mut self, node: u32, pool: u32, size: u64) -> Self {
+        self.pool_sizes.insert((node, pool), size);
+        self
+    }
     /// Add pool URI with `disk` to the node `index`
     #[must_use]
     pub fn with_pool(mut self, index: u32, disk: &str) -> Self {
@@ -548,12 +887,58 @@ impl ClusterBuilder {
         self.replicas = Replica { count, size, share };
         self
     }
+    /// Add a replica with the given `uuid`, `size` and `share` to the `pool` index of the `node`
+    /// index, alongside those added by `with_replicas`
+    #[must_use]
+    pub fn with_replica_uuid(
+        mut self,
+        node: u32,
+        pool: u32,
+        uuid: transport::ReplicaId,
+        size: u64,
+        share: transport::Protocol,
+    ) -> Self {
+        let replica = Replica {
+            count: 1,
+            size,
+            share,
+        };
+        self.replicas_with_uuid.push((node, pool, uuid, replica));
+        self
+    }
+    /// Create `count` volumes with `size` and `replicas` once the pools are online, failing the
+    /// build if the cluster has fewer pools than `replicas`
+    #[must_use]
+    pub fn with_volumes(mut self, count: u32, size: u64, replicas: u64) -> Self {
+        for _ in 0 .. count {
+            self.volumes.push(ClusterVolume::new(size, replicas));
+        }
+        self
+    }
+    /// Create a volume once the pools have been created, using a closure to specify it, eg:
+    /// .with_volume(|v| v.with_create(|c| CreateVolume { thin: true, ..c })
+    ///     .with_publish(None, None))
+    #[must_use]
+    pub fn with_volume<F>(mut self, set: F) -> Self
+    where
+        F: FnOnce(ClusterVolume) -> ClusterVolume,
+    {
+        self.volumes
+            .push(set(ClusterVolume::new(5 * 1024 * 1024, 1)));
+        self
+    }
     /// Specify `count` io_engines for the cluster
     #[must_use]
     pub fn with_io_engines(mut self, count: u32) -> Self {
         self.opts = self.opts.with_io_engines(count);
         self
     }
+    /// Pack `count` io_engines in each io_engine container
+    #[must_use]
+    pub fn with_io_engines_per_host(mut self, count: u32) -> Self {
+        self.opts = self.opts.with_io_engines_per_host(count);
+        self
+    }
     /// Specify the image pull policy.
     #[must_use]
     pub fn with_pull_policy(mut self, policy: composer::ImagePullPolicy) -> Self {
@@ -624,6 +1009,21 @@ impl ClusterBuilder {
         self.grpc_timeout = timeout;
         self
     }
+    /// Seed the persistent store with the `fixtures` key/values once it's up but before the
+    /// control plane agents are started, eg: to test how the agents handle existing specs.
+    /// See the `fixtures` module for the serializers of the key/values.
+    #[must_use]
+    pub fn with_store_fixtures(mut self, fixtures: Vec<(String, String)>) -> Self {
+        self.store_fixtures.extend(fixtures);
+        self
+    }
+    /// Specify whether the pools, replicas and volumes are also created when attaching to an
+    /// external cluster, which is otherwise expected to be provisioned already
+    #[must_use]
+    pub fn with_external_provisioning(mut self, enabled: bool) -> Self {
+        self.external_provisioning = enabled;
+        self
+    }
     /// Specify whether rest is enabled or not
     #[must_use]
     pub fn with_rest(mut self, enabled: bool) -> Self {
@@ -659,10 +1059,16 @@ impl ClusterBuilder {
     }
     /// Build into the resulting Cluster using a composer closure, eg:
     /// .compose_build(|c| c.with_logs(false))
+    /// The closure is not used when attaching to an external cluster, see `Cluster::attach`.
     pub async fn compose_build<F>(mut self, set: F) -> Result<Cluster, Error>
     where
         F: Fn(Builder) -> Builder,
     {
+        self.validate_replicas()?;
+        self.validate_volumes()?;
+        if let Some((rest_url, grpc_url)) = external_cluster()? {
+            return Cluster::attach(&rest_url, &grpc_url, self).await;
+        }
         let (components, composer) = self.build_prepare()?;
         let composer = set(composer);
         let mut cluster = self.new_cluster(components, composer).await?;
@@ -670,12 +1076,119 @@ impl ClusterBuilder {
         Ok(cluster)
     }
     /// Build into the resulting Cluster
+    /// The cluster is attached to an external one rather than composed if its urls are set in the
+    /// environment, see `Cluster::attach`.
     pub async fn build(mut self) -> Result<Cluster, Error> {
+        self.validate_pool_sizes()?;
+        self.validate_replicas()?;
+        self.validate_volumes()?;
+        if let Some((rest_url, grpc_url)) = external_cluster()? {
+            return Cluster::attach(&rest_url, &grpc_url, self).await;
+        }
         let (components, composer) = self.build_prepare()?;
         let mut cluster = self.new_cluster(components, composer).await?;
         cluster.builder = self;
         Ok(cluster)
     }
+    /// Check that the overridden pool sizes are those of malloc pools, and can be built
+    fn validate_pool_sizes(&self) -> Result<(), Error> {
+        for (node, size) in &self.node_pool_sizes {
+            if *size == 0 || size % 512 != 0 {
+                return Err(format!(
+                    "The size {} of the pools of node {} is not a multiple of 512 bytes",
+                    size, node
+                )
+                .into());
+            }
+            let malloc = |pool: &PoolDisk| matches!(pool, PoolDisk::Malloc(_));
+            if !self.pools.get(node).map_or(false, |p| p.iter().any(malloc)) {
+                return Err(format!(
+                    "Node {} has no malloc pool, so the size of its pools can't be set",
+                    node
+                )
+                .into());
+            }
+        }
+        for pool in self.pools.values().flatten() {
+            if let PoolDisk::Malloc(size) = pool {
+                if *size == 0 || size % 512 != 0 {
+                    return Err(
+                        format!("The pool size {} is not a multiple of 512 bytes", size).into(),
+                    );
+                }
+            }
+        }
+        for ((node, pool), size) in &self.pool_sizes {
+            if *size == 0 || size % 512 != 0 {
+                return Err(format!(
+                    "The size {} of pool {} of node {} is not a multiple of 512 bytes",
+                    size, pool, node
+                )
+                .into());
+            }
+            match self.pools.get(node).and_then(|p| p.get(*pool as usize)) {
+                Some(PoolDisk::Malloc(_)) => {}
+                _ => {
+                    return Err(format!(
+                        "Pool {} of node {} is not a malloc pool, so its size can't be set",
+                        pool, node
+                    )
+                    .into())
+                }
+            }
+        }
+        Ok(())
+    }
+    /// Check that the cluster has enough pools for the replicas of each of the volumes, which
+    /// could otherwise never be created
+    fn validate_volumes(&self) -> Result<(), Error> {
+        let pools = self.pools.values().map(Vec::len).sum::<usize>() as u64;
+        for (index, volume) in self.volumes.iter().enumerate() {
+            if volume.create.replicas > pools {
+                return Err(format!(
+                    "Volume {} has {} replicas, but the cluster only has {} pools",
+                    index, volume.create.replicas, pools
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+    /// Check that the replicas with a given uuid are on existing pools, and that their uuids
+    /// are not used by any other replica of the cluster, which the io-engine would refuse
+    fn validate_replicas(&self) -> Result<(), Error> {
+        let mut uuids = HashMap::new();
+        for (node, pools) in &self.pools {
+            for pool in 0 .. pools.len() {
+                for replica in 0 .. self.replicas.count {
+                    uuids.insert(Cluster::replica(*node, pool, replica), (*node, pool as u32));
+                }
+            }
+        }
+        for (node, pool, uuid, _) in &self.replicas_with_uuid {
+            if self
+                .pools
+                .get(node)
+                .and_then(|p| p.get(*pool as usize))
+                .is_none()
+            {
+                return Err(format!(
+                    "Replica {} is added to pool {} of node {}, which does not exist",
+                    uuid, pool, node
+                )
+                .into());
+            }
+            if let Some((other_node, other_pool)) = uuids.insert(uuid.clone(), (*node, *pool)) {
+                return Err(format!(
+                    "Replica {} of pool {} of node {} has the same uuid as a replica of pool {} \
+                     of node {}",
+                    uuid, pool, node, other_pool, other_node
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
     fn build_prepare(&self) -> Result<(Components, Builder), Error> {
         // Ensure that the composer is initialised with the correct root path.
         composer::initialize(
@@ -696,18 +1209,15 @@ impl ClusterBuilder {
         Ok((components, composer))
     }
 
-    async fn new_cluster(
-        &mut self,
-        components: Components,
-        compose_builder: Builder,
-    ) -> Result<Cluster, Error> {
+    /// Set up the tracing of the test, returning the guard of its subscriber
+    fn trace_guard(&mut self) -> Arc<DefaultGuard> {
         let subscriber = Registry::default()
             // todo: add env filter as an optional layer
             .with(self.env_filter.take().unwrap())
             .with(tracing_subscriber::fmt::layer());
 
         let mut tracing_tags = vec![];
-        let trace_guard = Arc::new(match self.trace {
+        Arc::new(match self.trace {
             true => {
                 tracing_tags.append(&mut default_tracing_tags(
                     utils::raw_version_str(),
@@ -728,24 +1238,33 @@ impl ClusterBuilder {
                 tracing::subscriber::set_default(subscriber.with(telemetry))
             }
             false => tracing::subscriber::set_default(subscriber),
-        });
+        })
+    }
+
+    async fn new_cluster(
+        &mut self,
+        components: Components,
+        compose_builder: Builder,
+    ) -> Result<Cluster, Error> {
+        let trace_guard = self.trace_guard();
 
         let compose_builder = compose_builder.with_shutdown_order(components.shutdown_order());
         let composer = compose_builder.build().await?;
 
-        let cluster = Cluster::new(
+        let mut cluster = Cluster::new(
             self.trace,
             trace_guard,
             self.rest_timeout,
             self.grpc_timeout.clone(),
             self.bearer_token.clone(),
+            self.store_fixtures.clone(),
             components,
             composer,
         )
         .await?;
 
         if self.opts.show_info {
-            for container in cluster.composer.list_cluster_containers().await? {
+            for container in cluster.composer().list_cluster_containers().await? {
                 let networks = container.network_settings.unwrap().networks.unwrap();
                 let ip = networks
                     .get(&self.opts.cluster_label.name())
@@ -761,7 +1280,17 @@ impl ClusterBuilder {
             }
         }
 
-        for pool in &self.pools() {
+        self.provision(&mut cluster).await?;
+        Ok(cluster)
+    }
+    /// Create the pools, replicas and volumes on the `cluster`
+    async fn provision(&self, cluster: &mut Cluster) -> Result<(), Error> {
+        let node_name = |index| match cluster.is_attached() {
+            true => cluster.node(index).to_string(),
+            false => IoEngine::name(index, &self.opts),
+        };
+        let pools = self.pools(node_name);
+        for pool in &pools {
             let pool_client = cluster.grpc_client().pool();
             let replica_client = cluster.grpc_client().replica();
             pool_client
@@ -782,16 +1311,59 @@ impl ClusterBuilder {
             }
         }
 
-        Ok(cluster)
+        if !self.volumes.is_empty() {
+            // the volumes can only be scheduled on the pools once their state has been fetched
+            let pools = pools.iter().map(Pool::id).collect::<Vec<_>>();
+            cluster
+                .wait_pools_usable(&pools, Duration::from_secs(30))
+                .await?;
+        }
+        for (index, volume) in self.volumes.iter().enumerate() {
+            let volume_client = cluster.grpc_client().volume();
+            volume_client
+                .create(&volume.create, None)
+                .await
+                .map_err(|error| format!("Failed to create volume {}: {:?}", index, error))?;
+            if let Some(publish) = &volume.publish {
+                let publish = PublishVolume {
+                    uuid: volume.create.uuid.clone(),
+                    ..publish.clone()
+                };
+                volume_client
+                    .publish(&publish, None)
+                    .await
+                    .map_err(|error| format!("Failed to publish volume {}: {:?}", index, error))?;
+            }
+            cluster.volumes.push(volume.create.uuid.clone());
+        }
+        Ok(())
+    }
+    /// The size of the `pool` index of the `node` index, if it's a malloc pool
+    fn malloc_pool_size(&self, node: u32, pool: u32) -> Option<u64> {
+        match self.pools.get(&node)?.get(pool as usize)? {
+            PoolDisk::Malloc(size) => Some(
+                self.pool_sizes
+                    .get(&(node, pool))
+                    .or_else(|| self.node_pool_sizes.get(&node))
+                    .copied()
+                    .unwrap_or(*size),
+            ),
+            _ => None,
+        }
     }
-    fn pools(&self) -> Vec<Pool> {
+    /// The pools to create, on the nodes named by `node_name` from their index
+    fn pools(&self, node_name: impl Fn(u32) -> String) -> Vec<Pool> {
         let mut pools = vec![];
 
         for (node, i_pools) in &self.pools {
             for (pool_index, pool) in i_pools.iter().enumerate() {
+                let disk = match self.malloc_pool_size(*node, pool_index as u32) {
+                    Some(size) => PoolDisk::Malloc(size),
+                    None => pool.clone(),
+                };
                 let mut pool = Pool {
-                    node: IoEngine::name(*node, &self.opts),
-                    disk: pool.clone(),
+                    node: node_name(*node),
+                    disk,
                     index: (pool_index + 1) as u32,
                     replicas: vec![],
                 };
@@ -809,6 +1381,23 @@ impl ClusterBuilder {
                         owners: Default::default(),
                     });
                 }
+                let with_uuid = self
+                    .replicas_with_uuid
+                    .iter()
+                    .filter(|(n, p, _, _)| n == node && *p as usize == pool_index);
+                for (_, _, uuid, replica) in with_uuid {
+                    pool.replicas.push(transport::CreateReplica {
+                        node: pool.node.clone().into(),
+                        name: None,
+                        uuid: uuid.clone(),
+                        pool: pool.id(),
+                        size: replica.size,
+                        thin: false,
+                        share: replica.share,
+                        managed: false,
+                        owners: Default::default(),
+                    });
+                }
                 pools.push(pool);
             }
         }
@@ -830,9 +1419,13 @@ impl Pool {
     fn disk(&self) -> transport::PoolDeviceUri {
         match &self.disk {
             PoolDisk::Malloc(size) => {
-                let size = size / (1024 * 1024);
+                // sizes which aren't whole MiBs are given in blocks of the default 512 bytes
+                let size = match size % (1024 * 1024) {
+                    0 => format!("size_mb={}", size / (1024 * 1024)),
+                    _ => format!("num_blocks={}", size / 512),
+                };
                 format!(
-                    "malloc:///disk{}?size_mb={}&uuid={}",
+                    "malloc:///disk{}?{}&uuid={}",
                     self.index,
                     size,
                     transport::PoolId::new()
@@ -845,6 +1438,22 @@ impl Pool {
     }
 }
 
+/// The urls of the REST and gRPC APIs of the external cluster which the tests run against, if any
+fn external_cluster() -> Result<Option<(String, String)>, Error> {
+    let rest_url = match std::env::var(EXTERNAL_REST_URL_ENV) {
+        Ok(url) => url,
+        Err(_) => return Ok(None),
+    };
+    match std::env::var(EXTERNAL_GRPC_URL_ENV) {
+        Ok(grpc_url) => Ok(Some((rest_url, grpc_url))),
+        Err(_) => Err(format!(
+            "{} is set but {} is not, both are required to attach to an external cluster",
+            EXTERNAL_REST_URL_ENV, EXTERNAL_GRPC_URL_ENV
+        )
+        .into()),
+    }
+}
+
 fn grpc_addr(ip: String) -> String {
     format!("https://{}:50051", ip)
 }
@@ -4,6 +4,7 @@ use openapi::tower::{client, client::Url};
 #[derive(Clone)]
 pub(crate) struct RestClient {
     openapi_client_v0: client::direct::ApiClient,
+    openapi_client_v1: openapi_v1::tower::client::direct::ApiClient,
 }
 
 impl RestClient {
@@ -11,6 +12,10 @@ impl RestClient {
     pub fn v0(&self) -> client::direct::ApiClient {
         self.openapi_client_v0.clone()
     }
+    /// Get Autogenerated Openapi client v1
+    pub fn v1(&self) -> openapi_v1::tower::client::direct::ApiClient {
+        self.openapi_client_v1.clone()
+    }
     /// creates a new client which uses the specified `url` and specified timeout
     /// uses the rustls connector if the url has the https scheme
     pub(crate) fn new_timeout(
@@ -39,13 +44,29 @@ impl RestClient {
     ) -> anyhow::Result<Self> {
         let cert_file = &std::include_bytes!("../../../control-plane/rest/certs/rsa/ca.cert")[..];
 
-        let openapi_client_config =
-            client::Configuration::new(url, timeout, bearer_token, Some(cert_file), trace)
-                .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
+        let openapi_client_config = client::Configuration::new(
+            url.clone(),
+            timeout,
+            bearer_token.clone(),
+            Some(cert_file),
+            trace,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
         let openapi_client = client::direct::ApiClient::new(openapi_client_config);
+        let openapi_client_v1_config = openapi_v1::tower::client::Configuration::new(
+            url,
+            timeout,
+            bearer_token,
+            Some(cert_file),
+            trace,
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
 
         Ok(Self {
             openapi_client_v0: openapi_client,
+            openapi_client_v1: openapi_v1::tower::client::direct::ApiClient::new(
+                openapi_client_v1_config,
+            ),
         })
     }
     /// creates a new client
@@ -56,11 +77,17 @@ impl RestClient {
         trace: bool,
     ) -> anyhow::Result<Self> {
         let openapi_client_config =
-            client::Configuration::new(url, timeout, bearer_token, None, trace)
+            client::Configuration::new(url.clone(), timeout, bearer_token.clone(), None, trace)
                 .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
         let openapi_client = client::direct::ApiClient::new(openapi_client_config);
+        let openapi_client_v1_config =
+            openapi_v1::tower::client::Configuration::new(url, timeout, bearer_token, None, trace)
+                .map_err(|e| anyhow::anyhow!("Failed to create rest client config: '{:?}'", e))?;
         Ok(Self {
             openapi_client_v0: openapi_client,
+            openapi_client_v1: openapi_v1::tower::client::direct::ApiClient::new(
+                openapi_client_v1_config,
+            ),
         })
     }
 }
@@ -31,12 +31,14 @@ impl ResourceMgr for VolMgr {
                 .volumes_api()
                 .put_volume(
                     &Uuid::new_v4(),
-                    models::CreateVolumeBody::new(
-                        models::VolumePolicy::new(false),
-                        self.n_replicas,
-                        self.size_bytes,
-                        false,
-                    ),
+                    models::CreateVolumeBody {
+                        replicas: Some(self.n_replicas),
+                        thin: Some(false),
+                        ..models::CreateVolumeBody::new(
+                            models::VolumePolicy::new(false),
+                            self.size_bytes,
+                        )
+                    },
                 )
                 .await?;
             created_volumes.push(volume);
@@ -85,13 +87,15 @@ impl ResourceUpdates for Vec<models::Volume> {
                     &volume.spec.uuid,
                     node_id,
                     models::VolumeShareProtocol::Nvmf,
+                    None,
+                    None,
                 )
                 .await?;
             node_index = (node_index + 1) % node_ids.len();
 
             client
                 .volumes_api()
-                .del_volume_target(&volume.spec.uuid, Some(true))
+                .del_volume_target(&volume.spec.uuid, Some(true), None)
                 .await?;
         }
         Ok(())
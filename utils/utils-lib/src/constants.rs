@@ -94,3 +94,12 @@ pub const NVME_PATH_RETRANSMISSION_PERIOD: &str = "10s";
 
 /// Period for aggregating multiple failed paths before reporting them.
 pub const NVME_PATH_AGGREGATION_PERIOD: &str = "1s";
+
+/// Period for reporting the NVMe path statistics.
+pub const NVME_PATH_STATS_REPORT_PERIOD: &str = "30s";
+
+/// Number of aggregated NVMe path statistics samples kept per volume.
+pub const NVME_PATH_STATS_HISTORY: &str = "16";
+
+/// How long a node-agent registration lasts unless the node-agent renews it.
+pub const NODE_AGENT_REGISTRATION_TTL: &str = "30s";